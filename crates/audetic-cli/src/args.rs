@@ -8,6 +8,11 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
+    /// Print JSON instead of text (history, logs, provider status/show,
+    /// update, jobs, version); failures print `{"error": "..."}`
+    #[arg(long, global = true)]
+    pub json: bool,
+
     #[command(subcommand)]
     pub command: Option<CliCommand>,
 }
@@ -16,24 +21,63 @@ pub struct Cli {
 pub enum CliCommand {
     /// Manage updates (manual install/check/enable/disable)
     Update(UpdateCliArgs),
-    /// Print version information
+    /// Print version information (with --json: version, target, channel,
+    /// git SHA, and build date)
     Version,
     /// Inspect or configure transcription providers
     Provider(ProviderCliArgs),
     /// Search and view transcription history
     History(HistoryCliArgs),
+    /// Show recent dictation job outcomes
+    Jobs(JobsCliArgs),
     /// View application and transcription logs
     Logs(LogsCliArgs),
     /// Manage Hyprland keybindings for Audetic
     Keybind(KeybindCliArgs),
-    /// Transcribe a local audio or video file
+    /// Transcribe an audio or video file (local path or http(s) URL)
     Transcribe(TranscribeCliArgs),
     /// Manage on-device transcription models (list, download)
     Models(ModelsCliArgs),
+    /// Inspect audio input devices
+    Audio(AudioCliArgs),
     /// Record and transcribe meetings
     Meeting(MeetingCliArgs),
     /// Manage post-processing jobs (run commands on daemon events)
     PostProcessing(PostProcessingCliArgs),
+    /// Export, import, reload, or check the whole configuration
+    Config(ConfigCliArgs),
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct ConfigCliArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Print the config as TOML, without API keys unless --with-secrets
+    Export {
+        /// Include API keys and other credentials
+        #[arg(long)]
+        with_secrets: bool,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Replace the config with a TOML file (the current one is backed up)
+    Import {
+        /// Config file to import
+        file: PathBuf,
+        /// Only change the settings present in the file; keep existing API keys
+        #[arg(long)]
+        merge: bool,
+    },
+    /// Apply the current config file to the running service without restarting it
+    Reload,
+    /// Check the config file for unknown keys and provider problems; fails if
+    /// dictation couldn't work with it
+    Check,
 }
 
 #[derive(ClapArgs, Debug)]
@@ -127,6 +171,18 @@ pub enum ModelsCommand {
     },
 }
 
+#[derive(ClapArgs, Debug)]
+pub struct AudioCliArgs {
+    #[command(subcommand)]
+    pub command: AudioCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AudioCommand {
+    /// List microphones; set one with `[audio] input_device` in config.toml
+    Devices,
+}
+
 #[derive(ClapArgs, Debug)]
 pub struct MeetingCliArgs {
     #[command(subcommand)]
@@ -174,6 +230,12 @@ pub enum MeetingCommand {
         /// Meeting ID
         id: i64,
     },
+    /// Re-run compression and transcription for a meeting that failed or
+    /// was interrupted (e.g. by a crash), using its stored audio
+    Resume {
+        /// Meeting ID
+        id: i64,
+    },
     /// Import an existing audio or video file as a new meeting
     Import {
         /// Path to the media file (audio or video) to import
@@ -201,6 +263,9 @@ pub struct UpdateCliArgs {
     /// Disable automatic background updates
     #[arg(long)]
     pub disable: bool,
+    /// Restore the version that the last update replaced
+    #[arg(long, conflicts_with_all = ["check", "force", "channel", "enable", "disable"])]
+    pub rollback: bool,
 }
 
 #[derive(ClapArgs, Debug)]
@@ -224,6 +289,9 @@ pub enum ProviderCommand {
         /// Path to audio file to test with (records brief sample if not provided)
         #[arg(short, long)]
         file: Option<String>,
+        /// Only check that the endpoint is reachable and accepts the API key; nothing is transcribed
+        #[arg(long, conflicts_with = "file")]
+        dry_network: bool,
     },
     /// Show provider status and readiness
     Status,
@@ -232,12 +300,17 @@ pub enum ProviderCommand {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// Show which fields would be cleared without resetting or backing up
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
 #[derive(ClapArgs, Debug)]
 pub struct HistoryCliArgs {
-    /// Search query to filter transcriptions by text content
+    #[command(subcommand)]
+    pub command: Option<HistoryCommand>,
+    /// Search transcriptions by text: every word must appear (partial words match their start); "quote" a phrase to match it exactly
     #[arg(short, long)]
     pub query: Option<String>,
     /// Filter by start date (YYYY-MM-DD format)
@@ -246,12 +319,108 @@ pub struct HistoryCliArgs {
     /// Filter by end date (YYYY-MM-DD format)
     #[arg(long)]
     pub to: Option<String>,
+    /// Only show transcriptions with this tag
+    #[arg(short, long)]
+    pub tag: Option<String>,
     /// Maximum number of results to show
     #[arg(short, long, default_value = "20")]
     pub limit: usize,
     /// ID of specific workflow to copy to clipboard
     #[arg(short, long)]
     pub copy: Option<i64>,
+    /// Show only the first CHARS characters of each transcription (listings
+    /// default to 100, the picker to 80, and --json to the full text)
+    #[arg(long, value_name = "CHARS", conflicts_with = "full")]
+    pub preview: Option<usize>,
+    /// Show each transcription's complete text instead of a preview
+    #[arg(long)]
+    pub full: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HistoryCommand {
+    /// Re-apply the current normalizer to stored raw transcriptions
+    Renormalize {
+        /// Only entries created on or after this date (YYYY-MM-DD format)
+        #[arg(long)]
+        from: Option<String>,
+        /// Report how many entries would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Dump the full history, oldest first, as JSON, NDJSON, CSV, or Markdown
+    Export {
+        /// json (one array), ndjson (one object per line), csv, or markdown
+        #[arg(short, long, value_enum, default_value = "json")]
+        format: HistoryExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Set the tags on a transcription (replaces existing tags)
+    Tag {
+        /// Transcription ID
+        id: i64,
+        /// Tags to set, e.g. `work ideas`; omit to clear all tags
+        tags: Vec<String>,
+    },
+    /// Delete a transcription (and its recording if still in the temp directory)
+    Delete {
+        /// Transcription ID
+        id: i64,
+    },
+    /// Transcribe a stored recording again and replace its text
+    Retranscribe {
+        /// Transcription ID
+        id: i64,
+        /// Use this provider instead of the configured one (e.g. local). It
+        /// can't be one that needs an API key
+        #[arg(long)]
+        provider: Option<String>,
+    },
+    /// Show usage statistics: totals, this week, average length, busiest day
+    Stats,
+    /// Delete every transcription created before a date
+    Clear {
+        /// Delete transcriptions created before this date (YYYY-MM-DD format)
+        #[arg(long)]
+        before: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum HistoryExportFormat {
+    Json,
+    Ndjson,
+    Csv,
+    Markdown,
+}
+
+impl HistoryExportFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HistoryExportFormat::Json => "json",
+            HistoryExportFormat::Ndjson => "ndjson",
+            HistoryExportFormat::Csv => "csv",
+            HistoryExportFormat::Markdown => "markdown",
+        }
+    }
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct JobsCliArgs {
+    #[command(subcommand)]
+    pub command: JobsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum JobsCommand {
+    /// List the latest dictation jobs, completed and failed
+    Recent {
+        /// Number of jobs to show (at most 100)
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
 }
 
 #[derive(ClapArgs, Debug)]
@@ -259,6 +428,16 @@ pub struct LogsCliArgs {
     /// Number of log entries to show
     #[arg(short = 'n', long, default_value = "30")]
     pub lines: usize,
+    /// Only app logs at this priority or more severe (emerg, alert, crit,
+    /// err, warning, notice, info, debug)
+    #[arg(short = 'p', long)]
+    pub level: Option<String>,
+    /// Only app logs since this time, e.g. "2024-05-01 09:00", "yesterday", "-1h"
+    #[arg(long)]
+    pub since: Option<String>,
+    /// Only app logs until this time (same formats as --since)
+    #[arg(long)]
+    pub until: Option<String>,
 }
 
 #[derive(ClapArgs, Debug)]
@@ -278,14 +457,16 @@ pub enum KeybindCommand {
         #[arg(long)]
         dry_run: bool,
     },
-    /// Remove Audetic keybinding from config
+    /// Remove Audetic keybindings from every Hyprland config file
     Uninstall {
-        /// Preview changes without applying
+        /// List the bindings that would be removed without changing any file
         #[arg(long)]
         dry_run: bool,
     },
     /// Show current keybinding status
     Status,
+    /// Remove duplicate Audetic sections and bindings left by earlier installs
+    Cleanup,
 }
 
 /// Transcribe audio or video files to text.
@@ -294,25 +475,60 @@ pub enum KeybindCommand {
 /// Use --no-compress to send the file in its original format.
 #[derive(ClapArgs, Debug)]
 pub struct TranscribeCliArgs {
-    /// Path to audio or video file to transcribe
-    pub file: PathBuf,
+    /// Paths or http(s) URLs of the audio or video files to transcribe. With
+    /// more than one, each transcript is written next to its input (or into
+    /// --output-dir)
+    #[arg(required_unless_present_any = ["from_clipboard", "watch", "glob"])]
+    pub files: Vec<PathBuf>,
+
+    /// Transcribe every supported file matching this pattern (e.g.
+    /// 'recordings/*.m4a'), like passing several files
+    #[arg(long, value_name = "PATTERN", conflicts_with_all = ["output", "copy"])]
+    pub glob: Option<String>,
+
+    /// Read the file path (or file:// URI) to transcribe from the clipboard
+    #[arg(long, conflicts_with_all = ["files", "glob"])]
+    pub from_clipboard: bool,
+
+    /// Keep running and transcribe each supported file that lands in this
+    /// directory, writing the transcript next to it (or into --output-dir,
+    /// else `[transcribe].default_output_dir`). Files that already have a
+    /// transcript are skipped
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["files", "glob", "from_clipboard", "output", "copy"])]
+    pub watch: Option<PathBuf>,
 
     /// Language code (e.g., 'en', 'es', 'auto')
     #[arg(short, long)]
     pub language: Option<String>,
 
-    /// Write transcription to file (default: stdout)
+    /// Write transcription to file (default: stdout); `-` prints it
     #[arg(short, long)]
     pub output: Option<PathBuf>,
 
-    /// Output format: text, json, srt
-    #[arg(short, long, default_value = "text")]
-    pub format: OutputFormat,
+    /// Print the transcription, even when `[transcribe].default_output_dir`
+    /// is set. Same as `-o -`
+    #[arg(long, conflicts_with_all = ["output", "output_dir", "watch", "glob"])]
+    pub stdout: bool,
+
+    /// Write transcription into this directory, named after the input file
+    /// (default: `[transcribe].default_output_dir`, else stdout)
+    #[arg(long, conflicts_with = "output")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Output format: text, json, srt (default: `[transcribe].default_format`,
+    /// else text)
+    #[arg(short, long)]
+    pub format: Option<OutputFormat>,
 
     /// Include timestamps in output
     #[arg(long)]
     pub timestamps: bool,
 
+    /// Split SRT output into one cue per word, highlighting the spoken word,
+    /// when the provider returned word timings
+    #[arg(long)]
+    pub karaoke: bool,
+
     /// Disable progress indicator
     #[arg(long)]
     pub no_progress: bool,
@@ -328,9 +544,14 @@ pub struct TranscribeCliArgs {
     /// Skip compression (send file in original format)
     #[arg(long)]
     pub no_compress: bool,
+
+    /// Report each file's duration, format, upload size, and target endpoint
+    /// without uploading anything
+    #[arg(long, conflicts_with_all = ["watch", "glob", "from_clipboard", "output", "output_dir", "copy"])]
+    pub estimate: bool,
 }
 
-#[derive(Clone, Debug, ValueEnum)]
+#[derive(Clone, Debug, PartialEq, ValueEnum)]
 pub enum OutputFormat {
     Text,
     Json,
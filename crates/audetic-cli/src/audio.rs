@@ -0,0 +1,54 @@
+//! CLI handler for audio input devices.
+//!
+//! The daemon does the recording, so it lists the devices it can see
+//! (`GET /audio/devices`).
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::args::{AudioCliArgs, AudioCommand};
+use crate::client::{daemon_client, json_or_error, CONNECT_HINT};
+use audetic_core::url::{api_url, paths};
+
+pub async fn handle_audio_command(args: AudioCliArgs) -> Result<()> {
+    match args.command {
+        AudioCommand::Devices => handle_devices().await,
+    }
+}
+
+async fn handle_devices() -> Result<()> {
+    let response = daemon_client()
+        .get(api_url(paths::AUDIO_DEVICES))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "list audio devices").await?;
+
+    let devices = body
+        .get("devices")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if devices.is_empty() {
+        println!("No audio input devices found.");
+        return Ok(());
+    }
+
+    println!();
+    println!("Audio input devices");
+    println!("===================");
+    println!();
+    for device in &devices {
+        let name = device.get("name").and_then(Value::as_str).unwrap_or("?");
+        let is_default = device
+            .get("is_default")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let marker = if is_default { " (default)" } else { "" };
+        println!("  {name}{marker}");
+    }
+    println!();
+    println!("Record from one by setting `input_device` under [audio] in config.toml");
+    println!("(any part of the name works), then restart the daemon.");
+    Ok(())
+}
@@ -1,7 +1,8 @@
 //! Shared helpers for talking to the `audeticd` daemon over its REST API.
 
 use anyhow::{bail, Context, Result};
-use audetic_core::url::api_url;
+use audetic_core::url::{api_url, auth_token};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use serde_json::Value;
 
 /// Friendly hint shown when the daemon can't be reached.
@@ -17,6 +18,25 @@ pub fn base_url() -> String {
     url
 }
 
+/// HTTP client for daemon requests. Sends `[api] auth_token` as a bearer
+/// token when one is set, so commands keep working against a
+/// token-protected daemon. Only use it for daemon URLs.
+pub fn daemon_client() -> reqwest::Client {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = auth_token() {
+        match HeaderValue::from_str(&format!("Bearer {token}")) {
+            Ok(value) => {
+                headers.insert(AUTHORIZATION, value);
+            }
+            Err(_) => tracing::warn!("Ignoring [api] auth_token: not a valid header value"),
+        }
+    }
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap_or_default()
+}
+
 /// Decode the API response body, turning non-2xx status codes into a friendly
 /// `anyhow::Error`. Extracts `.message` from a JSON error envelope when present,
 /// otherwise falls back to a generic HTTP status message. An empty body decodes
@@ -0,0 +1,225 @@
+//! CLI handler for `audetic config export|import|reload|check`.
+//!
+//! Export and check only read, so they work off the local `config.toml` and
+//! don't need the daemon. Import writes the file, so it goes through the daemon
+//! (`POST /api/config/import`), which validates the result and backs up the
+//! current config first. Reload asks the daemon to apply the file as it is
+//! now (`POST /api/config/reload`).
+
+use anyhow::{bail, Context, Result};
+use audetic_core::config::{validate_provider_config, Config};
+use audetic_core::global;
+use audetic_core::url::paths;
+use serde_json::json;
+use std::path::Path;
+
+use crate::args::{ConfigCliArgs, ConfigCommand};
+use crate::client::{base_url, daemon_client, json_or_error, CONNECT_HINT};
+
+pub async fn handle_config_command(args: ConfigCliArgs) -> Result<()> {
+    match args.command {
+        ConfigCommand::Export {
+            with_secrets,
+            output,
+        } => export_config(with_secrets, output.as_deref()),
+        ConfigCommand::Import { file, merge } => import_config(&file, merge).await,
+        ConfigCommand::Reload => reload_config().await,
+        ConfigCommand::Check => check_config(),
+    }
+}
+
+/// Report unknown keys, odd values, and provider problems in the config
+/// file. Fails when the config can't produce a working provider.
+fn check_config() -> Result<()> {
+    let path = global::config_file()?;
+    if !path.exists() {
+        println!("No config file at {}; the defaults apply.", path.display());
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let report = check_content(&content)
+        .with_context(|| format!("{} is not a valid Audetic config", path.display()))?;
+
+    println!("Checked {}", path.display());
+    for warning in &report.warnings {
+        println!("Warning: {warning}");
+    }
+    for error in &report.errors {
+        println!("Error: {error}");
+    }
+    if !report.errors.is_empty() {
+        bail!("The config can't produce a working transcription provider");
+    }
+    if report.warnings.is_empty() {
+        println!("No problems found.");
+    }
+    Ok(())
+}
+
+/// What `config check` found in a config file.
+#[derive(Debug, Default)]
+struct CheckReport {
+    /// Settings that are ignored or look wrong; the daemon still starts.
+    warnings: Vec<String>,
+    /// Problems that leave dictation without a working provider.
+    errors: Vec<String>,
+}
+
+fn check_content(content: &str) -> Result<CheckReport> {
+    let (config, mut warnings) = Config::parse_checked(content)?;
+    warnings.extend(config.validate());
+    let errors = match config.whisper.provider.as_deref() {
+        None => vec!["[whisper].provider: not set, so dictation is off. \
+             Run `audetic provider configure` to pick one."
+            .to_string()],
+        Some(provider) => validate_provider_config(provider, &config.whisper)
+            .map(|error| format!("[whisper].provider: {error}"))
+            .into_iter()
+            .collect(),
+    };
+    Ok(CheckReport { warnings, errors })
+}
+
+fn export_config(with_secrets: bool, output: Option<&Path>) -> Result<()> {
+    let content = Config::load()?.to_export_toml(with_secrets)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Config exported to {}", path.display());
+            if with_secrets {
+                eprintln!("Warning: the file contains API keys; don't share it publicly.");
+            }
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+async fn import_config(file: &Path, merge: bool) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+
+    // Catch syntax errors before bothering the daemon.
+    Config::default()
+        .import_toml(&content, false)
+        .with_context(|| format!("{} is not a valid Audetic config", file.display()))?;
+
+    let client = daemon_client();
+    let response = client
+        .post(format!("{}{}", base_url(), paths::CONFIG_IMPORT))
+        .json(&json!({ "toml": content, "merge": merge }))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "Config import").await?;
+
+    println!(
+        "Config {} from {}",
+        if merge { "merged" } else { "imported" },
+        file.display()
+    );
+    if let Some(backup) = body.get("backup_path").and_then(|v| v.as_str()) {
+        println!("Previous config backed up to {backup}");
+    }
+    if let Some(warnings) = body.get("warnings").and_then(|v| v.as_array()) {
+        for warning in warnings.iter().filter_map(|w| w.as_str()) {
+            println!("Warning: {warning}");
+        }
+    }
+    println!("Run `audetic config reload` to apply the new config.");
+    Ok(())
+}
+
+async fn reload_config() -> Result<()> {
+    let response = daemon_client()
+        .post(format!("{}{}", base_url(), paths::CONFIG_RELOAD))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "Config reload").await?;
+
+    let strings = |key: &str| -> Vec<String> {
+        body.get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let changed = strings("changed");
+    if changed.is_empty() {
+        println!("Config reloaded; nothing changed.");
+    } else {
+        println!("Config reloaded. Changed:");
+        for field in &changed {
+            println!("  {field}");
+        }
+    }
+    let restart_required = strings("restart_required");
+    if !restart_required.is_empty() {
+        println!(
+            "Restart the Audetic service to apply: {}",
+            restart_required.join(", ")
+        );
+    }
+    for warning in strings("warnings") {
+        println!("Warning: {warning}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_reports_typos_as_warnings() {
+        let report = check_content(
+            r#"
+            [whisper]
+            provider = "audetic-api"
+            languge = "en"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            report.warnings,
+            ["[whisper].languge: unknown key, ignored (did you mean `language`?)"]
+        );
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn check_fails_providers_missing_required_settings() {
+        let report = check_content(
+            r#"
+            [whisper]
+            provider = "whisper-cpp"
+            command_path = "/usr/bin/whisper-cli"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            report.errors,
+            ["[whisper].provider: Model path required for whisper.cpp"]
+        );
+
+        let report = check_content("[whisper]\nprovider = \"deepgram\"\n").unwrap();
+        assert_eq!(
+            report.errors,
+            ["[whisper].provider: API key required for Deepgram"]
+        );
+    }
+
+    #[test]
+    fn check_rejects_unparseable_config() {
+        assert!(check_content("[whisper]\nword_timestamps = \"yes\"\n").is_err());
+    }
+}
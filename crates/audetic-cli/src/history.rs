@@ -1,42 +1,117 @@
 //! CLI handler for transcription history.
 //!
-//! Talks to the daemon's REST API (`GET /api/history`, `GET /api/history/{id}`).
+//! Talks to the daemon's REST API (`GET /api/history`, `GET /api/history/{id}`,
+//! `DELETE /api/history/{id}`, `DELETE /api/history`, `GET /api/history/export`,
+//! `PUT /api/history/{id}/tags`, `POST /api/history/renormalize`,
+//! `POST /api/history/{id}/retranscribe`, `GET /api/history/stats`).
 //! Clipboard copy happens client-side.
 
 use anyhow::{Context, Result};
 use audetic_core::clipboard::copy_to_clipboard_sync;
 use dialoguer::FuzzySelect;
 use serde::Deserialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-use crate::args::HistoryCliArgs;
-use crate::client::{base_url, json_or_error, CONNECT_HINT};
+use crate::args::{HistoryCliArgs, HistoryCommand, HistoryExportFormat};
+use crate::client::{base_url, daemon_client, json_or_error, CONNECT_HINT};
+use crate::prompt::PromptResultExt;
 
 #[derive(Debug, Deserialize)]
 struct HistoryEntry {
     id: i64,
     created_at: String,
     text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    word_count: usize,
+    /// Set when the daemon cut `text` to a preview.
+    #[serde(default)]
+    truncated: bool,
 }
 
-pub async fn handle_history_command(args: HistoryCliArgs) -> Result<()> {
+impl HistoryEntry {
+    /// `text`, marked with an ellipsis when it's only a preview.
+    fn display_text(&self) -> String {
+        if self.truncated {
+            format!("{}...", self.text)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// Characters a search listing shows per entry unless `--preview`/`--full`.
+const LISTING_PREVIEW_CHARS: usize = 100;
+/// Characters each picker line shows unless `--preview`/`--full`.
+const PICKER_PREVIEW_CHARS: usize = 80;
+
+/// With `json`, each command prints the daemon's response instead of text,
+/// and a bare `audetic history` lists entries rather than opening the picker.
+pub async fn handle_history_command(args: HistoryCliArgs, json: bool) -> Result<()> {
+    match args.command {
+        Some(HistoryCommand::Renormalize { from, dry_run }) => {
+            return handle_renormalize(from, dry_run, json).await;
+        }
+        Some(HistoryCommand::Tag { id, tags }) => return handle_tag(id, tags, json).await,
+        Some(HistoryCommand::Delete { id }) => return handle_delete(id, json).await,
+        Some(HistoryCommand::Retranscribe { id, provider }) => {
+            return handle_retranscribe(id, provider, json).await;
+        }
+        Some(HistoryCommand::Clear { before }) => return handle_clear(before, json).await,
+        Some(HistoryCommand::Stats) => return handle_stats(json).await,
+        Some(HistoryCommand::Export { format, output }) => {
+            return handle_export(format, output).await;
+        }
+        None => {}
+    }
+
     if let Some(id) = args.copy {
-        return handle_copy_by_id(id).await;
+        return handle_copy_by_id(id, json).await;
+    }
+
+    if json {
+        let body = fetch_history_body(&args, args.preview).await?;
+        return print_json(&body);
     }
 
-    let no_filters = args.query.is_none() && args.from.is_none() && args.to.is_none();
+    let no_filters =
+        args.query.is_none() && args.from.is_none() && args.to.is_none() && args.tag.is_none();
     if no_filters {
-        handle_interactive_mode(args.limit).await
+        handle_interactive_mode(&args).await
     } else {
         handle_search_mode(&args).await
     }
 }
 
+/// How many characters of each entry to fetch: `--preview`, else `default`,
+/// or everything with `--full`.
+fn preview_chars(args: &HistoryCliArgs, default: usize) -> Option<usize> {
+    if args.full {
+        None
+    } else {
+        Some(args.preview.unwrap_or(default))
+    }
+}
+
 /// Fetch history entries from the daemon, applying the given filters.
-async fn fetch_history(args: &HistoryCliArgs) -> Result<Vec<HistoryEntry>> {
-    let client = reqwest::Client::new();
+async fn fetch_history(args: &HistoryCliArgs, preview: Option<usize>) -> Result<Vec<HistoryEntry>> {
+    let body = fetch_history_body(args, preview).await?;
+    serde_json::from_value(body).context("Failed to parse history entries")
+}
+
+/// The daemon's `GET /history` response for the given filters, with each
+/// entry's text cut to `preview` characters when set.
+async fn fetch_history_body(args: &HistoryCliArgs, preview: Option<usize>) -> Result<Value> {
+    let client = daemon_client();
     let mut req = client
         .get(format!("{}/history", base_url()))
         .query(&[("limit", args.limit.to_string())]);
+    if let Some(preview) = preview {
+        req = req.query(&[("preview", preview.to_string())]);
+    }
     if let Some(q) = &args.query {
         req = req.query(&[("q", q)]);
     }
@@ -46,25 +121,41 @@ async fn fetch_history(args: &HistoryCliArgs) -> Result<Vec<HistoryEntry>> {
     if let Some(to) = &args.to {
         req = req.query(&[("to", to)]);
     }
+    if let Some(tag) = &args.tag {
+        req = req.query(&[("tag", tag)]);
+    }
 
     let response = req.send().await.context(CONNECT_HINT)?;
-    let body = json_or_error(response, "list history").await?;
-    serde_json::from_value(body).context("Failed to parse history entries")
+    json_or_error(response, "list history").await
 }
 
-/// Copy a specific transcription to clipboard by ID.
-async fn handle_copy_by_id(id: i64) -> Result<()> {
-    let client = reqwest::Client::new();
+/// Print a daemon response as-is, for `--json`.
+fn print_json(body: &Value) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(body)?);
+    Ok(())
+}
+
+/// The daemon's `GET /history/{id}` response: one entry, full text.
+async fn fetch_entry_body(id: i64) -> Result<Value> {
+    let client = daemon_client();
     let response = client
         .get(format!("{}/history/{}", base_url(), id))
         .send()
         .await
         .context(CONNECT_HINT)?;
-    let body = json_or_error(response, "get transcription").await?;
+    json_or_error(response, "get transcription").await
+}
+
+/// Copy a specific transcription to clipboard by ID.
+async fn handle_copy_by_id(id: i64, json: bool) -> Result<()> {
+    let body = fetch_entry_body(id).await?;
     let entry: HistoryEntry =
-        serde_json::from_value(body).context("Failed to parse transcription")?;
+        serde_json::from_value(body.clone()).context("Failed to parse transcription")?;
 
     copy_to_clipboard_sync(&entry.text)?;
+    if json {
+        return print_json(&body);
+    }
     println!(
         "Copied transcription #{} to clipboard ({} chars)",
         entry.id,
@@ -73,16 +164,241 @@ async fn handle_copy_by_id(id: i64) -> Result<()> {
     Ok(())
 }
 
-/// Interactive mode with fuzzy selection.
-async fn handle_interactive_mode(limit: usize) -> Result<()> {
-    let args = HistoryCliArgs {
-        query: None,
-        from: None,
-        to: None,
-        limit,
-        copy: None,
+/// Stream the daemon's history export to stdout or a file chunk by chunk, so
+/// even huge histories never sit in memory on either side. The daemon breaks
+/// off the transfer when an export fails partway, which is reported as an
+/// error here rather than passing off a truncated export as complete.
+async fn handle_export(format: HistoryExportFormat, output: Option<PathBuf>) -> Result<()> {
+    let client = daemon_client();
+    let mut response = client
+        .get(format!("{}/history/export", base_url()))
+        .query(&[("format", format.as_str())])
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    if !response.status().is_success() {
+        json_or_error(response, "export history").await?;
+        return Ok(());
+    }
+
+    let mut out: Box<dyn AsyncWrite + Unpin> = match &output {
+        Some(path) => Box::new(
+            tokio::fs::File::create(path)
+                .await
+                .with_context(|| format!("Failed to create {}", path.display()))?,
+        ),
+        None => Box::new(tokio::io::stdout()),
     };
-    let entries = fetch_history(&args).await?;
+    while let Some(chunk) = response.chunk().await.with_context(|| match &output {
+        Some(path) => format!(
+            "History export was interrupted; {} is incomplete",
+            path.display()
+        ),
+        None => "History export was interrupted; the output above is incomplete".to_string(),
+    })? {
+        out.write_all(&chunk)
+            .await
+            .context("Failed to write history export")?;
+    }
+    out.flush().await?;
+
+    if let Some(path) = output {
+        eprintln!("History exported to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Replace the tags on a transcription (an empty list clears them).
+async fn handle_tag(id: i64, tags: Vec<String>, json: bool) -> Result<()> {
+    let client = daemon_client();
+    let response = client
+        .put(format!("{}/history/{}/tags", base_url(), id))
+        .json(&serde_json::json!({ "tags": tags }))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "tag transcription").await?;
+    if json {
+        return print_json(&body);
+    }
+    let entry: HistoryEntry =
+        serde_json::from_value(body).context("Failed to parse transcription")?;
+
+    if entry.tags.is_empty() {
+        println!("Cleared tags on transcription #{}", entry.id);
+    } else {
+        println!(
+            "Tagged transcription #{}: {}",
+            entry.id,
+            entry.tags.join(", ")
+        );
+    }
+    Ok(())
+}
+
+async fn handle_delete(id: i64, json: bool) -> Result<()> {
+    let client = daemon_client();
+    let response = client
+        .delete(format!("{}/history/{}", base_url(), id))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "delete transcription").await?;
+    if json {
+        return print_json(&body);
+    }
+    println!("Deleted transcription #{id}");
+    Ok(())
+}
+
+/// Re-run a stored recording through the configured (or given) provider and
+/// show the text that replaced the old transcript.
+async fn handle_retranscribe(id: i64, provider: Option<String>, json: bool) -> Result<()> {
+    match &provider {
+        Some(provider) => eprintln!("Re-transcribing #{id} with {provider}..."),
+        None => eprintln!("Re-transcribing #{id}..."),
+    }
+    let client = daemon_client();
+    let response = client
+        .post(format!("{}/history/{}/retranscribe", base_url(), id))
+        .json(&serde_json::json!({ "provider": provider }))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "re-transcribe").await?;
+    if json {
+        return print_json(&body);
+    }
+    let entry: HistoryEntry =
+        serde_json::from_value(body).context("Failed to parse transcription")?;
+
+    println!("Updated transcription #{}:", entry.id);
+    println!("{}", entry.text);
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct ClearReport {
+    deleted: usize,
+}
+
+/// Delete every transcription created before `before`.
+async fn handle_clear(before: String, json: bool) -> Result<()> {
+    let client = daemon_client();
+    let response = client
+        .delete(format!("{}/history", base_url()))
+        .query(&[("before", &before)])
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "clear history").await?;
+    if json {
+        return print_json(&body);
+    }
+    let report: ClearReport =
+        serde_json::from_value(body).context("Failed to parse clear report")?;
+
+    println!(
+        "Deleted {} transcription(s) created before {}",
+        report.deleted, before
+    );
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryStats {
+    total: i64,
+    total_words: i64,
+    average_words: f64,
+    this_week: i64,
+    most_active_day: Option<DayCount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DayCount {
+    date: String,
+    count: i64,
+}
+
+/// Print a summary of the daemon's history statistics.
+async fn handle_stats(json: bool) -> Result<()> {
+    let client = daemon_client();
+    let response = client
+        .get(format!("{}/history/stats", base_url()))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "get history stats").await?;
+    if json {
+        return print_json(&body);
+    }
+    let stats: HistoryStats =
+        serde_json::from_value(body).context("Failed to parse history stats")?;
+
+    if stats.total == 0 {
+        println!("No transcriptions in history yet.");
+        return Ok(());
+    }
+    println!("Transcriptions:   {}", stats.total);
+    println!("This week:        {} (last 7 days)", stats.this_week);
+    println!(
+        "Words:            {} total, {:.1} on average",
+        stats.total_words, stats.average_words
+    );
+    if let Some(day) = stats.most_active_day {
+        println!(
+            "Most active day:  {} ({} transcription(s))",
+            day.date, day.count
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct RenormalizeReport {
+    scanned: usize,
+    changed: usize,
+}
+
+/// Re-apply the daemon's current normalizer to stored raw text.
+async fn handle_renormalize(from: Option<String>, dry_run: bool, json: bool) -> Result<()> {
+    let client = daemon_client();
+    let response = client
+        .post(format!("{}/history/renormalize", base_url()))
+        .json(&serde_json::json!({ "from": from, "dry_run": dry_run }))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "renormalize history").await?;
+    if json {
+        return print_json(&body);
+    }
+    let report: RenormalizeReport =
+        serde_json::from_value(body).context("Failed to parse renormalize report")?;
+
+    if dry_run {
+        println!(
+            "Dry run: {} of {} transcription(s) would change.",
+            report.changed, report.scanned
+        );
+    } else {
+        println!(
+            "Updated {} of {} transcription(s).",
+            report.changed, report.scanned
+        );
+    }
+    if report.scanned == 0 {
+        println!(
+            "Only transcriptions recorded after raw text storage was added can be re-normalized."
+        );
+    }
+    Ok(())
+}
+
+/// Interactive mode with fuzzy selection. Lines show a preview; the chosen
+/// entry is fetched whole before it's copied.
+async fn handle_interactive_mode(args: &HistoryCliArgs) -> Result<()> {
+    let entries = fetch_history(args, preview_chars(args, PICKER_PREVIEW_CHARS)).await?;
 
     if entries.is_empty() {
         println!("No transcriptions found in history.");
@@ -92,12 +408,18 @@ async fn handle_interactive_mode(limit: usize) -> Result<()> {
     let items: Vec<String> = entries
         .iter()
         .map(|entry| {
-            let display_text = if entry.text.len() > 80 {
-                format!("{}...", &entry.text[..80])
+            let display_text = entry.display_text();
+            if entry.tags.is_empty() {
+                format!("[{}] {} - {}", entry.id, entry.created_at, display_text)
             } else {
-                entry.text.clone()
-            };
-            format!("[{}] {} - {}", entry.id, entry.created_at, display_text)
+                format!(
+                    "[{}] {} #{} - {}",
+                    entry.id,
+                    entry.created_at,
+                    entry.tags.join(" #"),
+                    display_text
+                )
+            }
         })
         .collect();
 
@@ -105,14 +427,23 @@ async fn handle_interactive_mode(limit: usize) -> Result<()> {
         .with_prompt("Search and select a transcription to copy")
         .items(&items)
         .default(0)
-        .interact_opt()?;
+        .interact_opt()
+        .or_cancelled()?;
 
     if let Some(index) = selection {
         let entry = &entries[index];
-        copy_to_clipboard_sync(&entry.text)?;
-        println!("\n✓ Copied to clipboard ({} chars)", entry.text.len());
+        let text = if entry.truncated {
+            let body = fetch_entry_body(entry.id).await?;
+            serde_json::from_value::<HistoryEntry>(body)
+                .context("Failed to parse transcription")?
+                .text
+        } else {
+            entry.text.clone()
+        };
+        copy_to_clipboard_sync(&text)?;
+        println!("\n✓ Copied to clipboard ({} chars)", text.len());
         println!("\nFull text:");
-        println!("{}", entry.text);
+        println!("{}", text);
     } else {
         println!("Selection cancelled.");
     }
@@ -122,7 +453,7 @@ async fn handle_interactive_mode(limit: usize) -> Result<()> {
 
 /// Search mode with filters - displays results without interaction.
 async fn handle_search_mode(args: &HistoryCliArgs) -> Result<()> {
-    let entries = fetch_history(args).await?;
+    let entries = fetch_history(args, preview_chars(args, LISTING_PREVIEW_CHARS)).await?;
 
     if entries.is_empty() {
         println!("No transcriptions found matching your criteria.");
@@ -132,15 +463,13 @@ async fn handle_search_mode(args: &HistoryCliArgs) -> Result<()> {
     println!("Found {} transcription(s):\n", entries.len());
 
     for entry in entries {
-        let display_text = if entry.text.len() > 100 {
-            format!("{}...", &entry.text[..100])
-        } else {
-            entry.text.clone()
-        };
-
         println!("ID: {}", entry.id);
         println!("Date: {}", entry.created_at);
-        println!("Text: {}", display_text);
+        if !entry.tags.is_empty() {
+            println!("Tags: {}", entry.tags.join(", "));
+        }
+        println!("Words: {}", entry.word_count);
+        println!("Text: {}", entry.display_text());
         println!("---");
     }
 
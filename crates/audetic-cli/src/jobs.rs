@@ -0,0 +1,126 @@
+//! CLI handler for dictation job outcomes.
+//!
+//! Talks to the daemon's REST API (`GET /api/jobs/recent`).
+
+use anyhow::{Context, Result};
+use audetic_core::url::{api_url, paths};
+use serde::Deserialize;
+
+use crate::args::{JobsCliArgs, JobsCommand};
+use crate::client::{daemon_client, json_or_error, CONNECT_HINT};
+
+#[derive(Debug, Deserialize)]
+struct RecentJob {
+    status: String,
+    history_id: Option<i64>,
+    job_id: Option<String>,
+    text: Option<String>,
+    error: Option<String>,
+    created_at: String,
+}
+
+pub async fn handle_jobs_command(args: JobsCliArgs, json: bool) -> Result<()> {
+    match args.command {
+        JobsCommand::Recent { limit } => handle_recent(limit, json).await,
+    }
+}
+
+async fn handle_recent(limit: usize, json: bool) -> Result<()> {
+    let response = daemon_client()
+        .get(api_url(paths::JOBS_RECENT))
+        .query(&[("limit", limit.to_string())])
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "list recent jobs").await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+
+    let jobs: Vec<RecentJob> =
+        serde_json::from_value(body).context("Failed to parse recent jobs")?;
+    if jobs.is_empty() {
+        println!("No dictation jobs yet.");
+        return Ok(());
+    }
+    for job in &jobs {
+        println!("{}", job_line(job));
+    }
+    Ok(())
+}
+
+/// One line per job: date, status, then the history id and text, or the
+/// error for a failure.
+fn job_line(job: &RecentJob) -> String {
+    let detail = match (&job.error, &job.text) {
+        (Some(error), _) => error.clone(),
+        (None, Some(text)) => preview(text, 80),
+        (None, None) => String::new(),
+    };
+    let reference = match (job.history_id, &job.job_id) {
+        (Some(id), _) => format!("#{id}"),
+        (None, Some(job_id)) => job_id.chars().take(8).collect(),
+        (None, None) => "-".to_string(),
+    };
+    format!(
+        "{}  {:<9}  {:<8}  {}",
+        job.created_at, job.status, reference, detail
+    )
+}
+
+fn preview(text: &str, max_chars: usize) -> String {
+    let mut chars = text.chars();
+    let head: String = chars.by_ref().take(max_chars).collect();
+    if chars.next().is_some() {
+        format!("{head}...")
+    } else {
+        head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(status: &str) -> RecentJob {
+        RecentJob {
+            status: status.to_string(),
+            history_id: None,
+            job_id: None,
+            text: None,
+            error: None,
+            created_at: "2026-01-02 10:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn completed_jobs_show_history_id_and_text() {
+        let line = job_line(&RecentJob {
+            history_id: Some(42),
+            text: Some("buy milk".to_string()),
+            ..job("completed")
+        });
+        assert_eq!(line, "2026-01-02 10:00:00  completed  #42       buy milk");
+    }
+
+    #[test]
+    fn failed_jobs_show_short_job_id_and_error() {
+        let line = job_line(&RecentJob {
+            job_id: Some("0c4b2a9e-1111-2222".to_string()),
+            error: Some("provider timed out".to_string()),
+            ..job("failed")
+        });
+        assert_eq!(
+            line,
+            "2026-01-02 10:00:00  failed     0c4b2a9e  provider timed out"
+        );
+    }
+
+    #[test]
+    fn long_text_is_truncated_on_char_boundaries() {
+        assert_eq!(preview("héllo wörld", 5), "héllo...");
+        assert_eq!(preview("short", 80), "short");
+    }
+}
@@ -1,8 +1,9 @@
 //! CLI handler for keybinding management.
 //!
 //! Talks to the daemon's REST API (`GET /api/keybind/status`,
-//! `POST /api/keybind/install`, `DELETE /api/keybind`). The daemon owns the
-//! Hyprland config (conflict detection, backups), so it is the single writer.
+//! `POST /api/keybind/install`, `POST /api/keybind/cleanup`,
+//! `DELETE /api/keybind`). The daemon owns the Hyprland config (conflict
+//! detection, backups), so it is the single writer.
 
 use anyhow::{Context, Result};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input};
@@ -10,19 +11,21 @@ use serde_json::{json, Value};
 use std::io::{self, IsTerminal};
 
 use crate::args::{KeybindCliArgs, KeybindCommand};
-use crate::client::{base_url, json_or_error, CONNECT_HINT};
+use crate::client::{base_url, daemon_client, json_or_error, CONNECT_HINT};
+use crate::prompt::PromptResultExt;
 
 pub async fn handle_keybind_command(args: KeybindCliArgs) -> Result<()> {
     match args.command {
         Some(KeybindCommand::Install { key, dry_run }) => install(key, dry_run).await,
         Some(KeybindCommand::Uninstall { dry_run }) => uninstall(dry_run).await,
         Some(KeybindCommand::Status) => status().await,
+        Some(KeybindCommand::Cleanup) => cleanup().await,
         None => interactive().await,
     }
 }
 
 async fn status() -> Result<()> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .get(format!("{}/keybind/status", base_url()))
         .send()
         .await
@@ -78,7 +81,7 @@ async fn install(key: Option<String>, dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .post(format!("{}/keybind/install", base_url()))
         .json(&json!({ "key": key }))
         .send()
@@ -90,34 +93,80 @@ async fn install(key: Option<String>, dry_run: bool) -> Result<()> {
 }
 
 async fn uninstall(dry_run: bool) -> Result<()> {
-    if dry_run {
-        println!(
-            "Dry-run preview isn't available from the CLI — the daemon applies keybind \
-             changes directly (with a backup). Run without --dry-run to uninstall."
-        );
-        return Ok(());
-    }
-
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .delete(format!("{}/keybind", base_url()))
+        .query(&[("dry_run", dry_run)])
         .send()
         .await
         .context(CONNECT_HINT)?;
     let body = json_or_error(response, "uninstall keybinding").await?;
 
-    if let Some(backup) = body.get("backup_path").and_then(|v| v.as_str()) {
-        println!("Backup: {backup}");
+    if let Some(backups) = body.get("backup_paths").and_then(|v| v.as_array()) {
+        for backup in backups.iter().filter_map(|v| v.as_str()) {
+            println!("Backup: {backup}");
+        }
+    }
+
+    let removed = body
+        .get("removed")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    for line in &removed {
+        let file = line.get("file").and_then(|v| v.as_str()).unwrap_or("?");
+        let number = line.get("line").and_then(|v| v.as_u64()).unwrap_or(0);
+        let content = line.get("content").and_then(|v| v.as_str()).unwrap_or("");
+        println!("{verb} {file}:{number}: {content}");
     }
+
     let message = body
         .get("message")
         .and_then(|v| v.as_str())
         .unwrap_or("Done");
     println!("{message}");
-    if body
+    if !removed.is_empty() {
+        if dry_run {
+            println!("Run without --dry-run to remove them.");
+        } else {
+            println!("Run 'hyprctl reload' to apply changes.");
+        }
+    }
+    Ok(())
+}
+
+async fn cleanup() -> Result<()> {
+    let response = daemon_client()
+        .post(format!("{}/keybind/cleanup", base_url()))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "clean up keybindings").await?;
+
+    if let Some(backups) = body.get("backup_paths").and_then(|v| v.as_array()) {
+        for backup in backups.iter().filter_map(|v| v.as_str()) {
+            println!("Backup: {backup}");
+        }
+    }
+
+    let removed = body
         .get("removed")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(false)
-    {
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for line in &removed {
+        let file = line.get("file").and_then(|v| v.as_str()).unwrap_or("?");
+        let number = line.get("line").and_then(|v| v.as_u64()).unwrap_or(0);
+        let content = line.get("content").and_then(|v| v.as_str()).unwrap_or("");
+        println!("Removed {file}:{number}: {content}");
+    }
+
+    let message = body
+        .get("message")
+        .and_then(|v| v.as_str())
+        .unwrap_or("Done");
+    println!("{message}");
+    if !removed.is_empty() {
         println!("Run 'hyprctl reload' to apply changes.");
     }
     Ok(())
@@ -139,7 +188,8 @@ async fn interactive() -> Result<()> {
     let proceed = Confirm::with_theme(&theme)
         .with_prompt("Install or update the Audetic keybinding now?")
         .default(true)
-        .interact()?;
+        .interact()
+        .or_cancelled()?;
     if !proceed {
         println!("No changes made.");
         return Ok(());
@@ -148,7 +198,8 @@ async fn interactive() -> Result<()> {
     let key: String = Input::with_theme(&theme)
         .with_prompt("Keybinding (e.g. \"SUPER, R\" or \"SUPER SHIFT, T\")")
         .default("SUPER, R".to_string())
-        .interact_text()?;
+        .interact_text()
+        .or_cancelled()?;
 
     install(Some(key), false).await
 }
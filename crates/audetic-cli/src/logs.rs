@@ -6,7 +6,7 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 
 use crate::args::LogsCliArgs;
-use crate::client::{base_url, json_or_error, CONNECT_HINT};
+use crate::client::{base_url, daemon_client, json_or_error, CONNECT_HINT};
 
 #[derive(Debug, Deserialize)]
 struct LogsResult {
@@ -23,15 +23,32 @@ struct TranscriptionEntry {
     text: String,
 }
 
-pub async fn handle_logs_command(args: LogsCliArgs) -> Result<()> {
-    let client = reqwest::Client::new();
+pub async fn handle_logs_command(args: LogsCliArgs, json: bool) -> Result<()> {
+    let mut query = vec![("lines", args.lines.to_string())];
+    for (name, value) in [
+        ("level", &args.level),
+        ("since", &args.since),
+        ("until", &args.until),
+    ] {
+        if let Some(value) = value {
+            query.push((name, value.clone()));
+        }
+    }
+
+    let client = daemon_client();
     let response = client
         .get(format!("{}/logs", base_url()))
-        .query(&[("lines", args.lines.to_string())])
+        .query(&query)
         .send()
         .await
         .context(CONNECT_HINT)?;
     let body = json_or_error(response, "get logs").await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+
     let result: LogsResult = serde_json::from_value(body).context("Failed to parse logs")?;
 
     println!("=== Application Logs (last {} entries) ===\n", args.lines);
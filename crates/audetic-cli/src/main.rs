@@ -9,13 +9,17 @@
 //! must run from inside the app bundle for TCC attribution.
 
 mod args;
+mod audio;
 mod client;
+mod config;
 mod history;
+mod jobs;
 mod keybind;
 mod logs;
 mod meeting;
 mod models;
 mod post_processing;
+mod prompt;
 mod provider;
 mod transcribe;
 mod update;
@@ -36,22 +40,57 @@ async fn main() -> Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
+    let json = cli.json;
+    match run(cli).await {
+        // Scripts reading `--json` output get failures in the same shape.
+        Err(err) if json => {
+            println!("{}", serde_json::json!({ "error": format!("{err:#}") }));
+            std::process::exit(exit_code(&err));
+        }
+        Err(err) if transcribe::is_interrupted(&err) => {
+            eprintln!("Error: {err:#}");
+            std::process::exit(exit_code(&err));
+        }
+        result => result,
+    }
+}
+
+/// 130 (128 + SIGINT) when Ctrl-C stopped a transcription, as a shell
+/// reports an interrupted command; 1 for any other failure.
+fn exit_code(err: &anyhow::Error) -> i32 {
+    if transcribe::is_interrupted(err) {
+        130
+    } else {
+        1
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let json = cli.json;
     match cli.command {
         Some(CliCommand::Version) => {
-            println!("Audetic {}", env!("CARGO_PKG_VERSION"));
+            if json {
+                let info = audetic_core::build_info::BuildInfo::current();
+                println!("{}", serde_json::to_string_pretty(&info)?);
+            } else {
+                println!("Audetic {}", env!("CARGO_PKG_VERSION"));
+            }
             Ok(())
         }
-        Some(CliCommand::Update(args)) => update::handle_update_command(args).await,
-        Some(CliCommand::Provider(args)) => provider::handle_provider_command(args).await,
-        Some(CliCommand::History(args)) => history::handle_history_command(args).await,
-        Some(CliCommand::Logs(args)) => logs::handle_logs_command(args).await,
+        Some(CliCommand::Update(args)) => update::handle_update_command(args, json).await,
+        Some(CliCommand::Provider(args)) => provider::handle_provider_command(args, json).await,
+        Some(CliCommand::History(args)) => history::handle_history_command(args, json).await,
+        Some(CliCommand::Jobs(args)) => jobs::handle_jobs_command(args, json).await,
+        Some(CliCommand::Logs(args)) => logs::handle_logs_command(args, json).await,
         Some(CliCommand::Keybind(args)) => keybind::handle_keybind_command(args).await,
         Some(CliCommand::Transcribe(args)) => transcribe::handle_transcribe_command(args).await,
         Some(CliCommand::Models(args)) => models::handle_models_command(args).await,
+        Some(CliCommand::Audio(args)) => audio::handle_audio_command(args).await,
         Some(CliCommand::Meeting(args)) => meeting::handle_meeting_command(args).await,
         Some(CliCommand::PostProcessing(args)) => {
             post_processing::handle_post_processing_command(args).await
         }
+        Some(CliCommand::Config(args)) => config::handle_config_command(args).await,
         None => {
             use clap::CommandFactory;
             Cli::command().print_help()?;
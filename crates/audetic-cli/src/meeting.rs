@@ -7,6 +7,7 @@ use serde_json::Value;
 use std::path::PathBuf;
 
 use crate::args::{MeetingCliArgs, MeetingCommand};
+use crate::client::daemon_client;
 use audetic_core::url::{api_url, paths};
 
 /// Daemon API base — single derived value so we never inline
@@ -29,6 +30,7 @@ pub async fn handle_meeting_command(args: MeetingCliArgs) -> Result<()> {
         MeetingCommand::List { limit } => list_meetings(limit).await,
         MeetingCommand::Show { id } => show_meeting(id).await,
         MeetingCommand::Delete { id } => delete_meeting(id).await,
+        MeetingCommand::Resume { id } => resume_meeting(id).await,
         MeetingCommand::Import { path, title } => import_meeting(path, title).await,
     }
 }
@@ -59,7 +61,7 @@ async fn json_or_error(response: reqwest::Response, op: &str) -> Result<Value> {
 }
 
 async fn start_meeting(title: Option<String>) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
     let mut body = serde_json::Map::new();
     if let Some(t) = &title {
         body.insert("title".to_string(), Value::String(t.clone()));
@@ -93,7 +95,7 @@ async fn start_meeting(title: Option<String>) -> Result<()> {
 }
 
 async fn stop_meeting() -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
 
     let response = client
         .post(format!("{}/meetings/stop", base_url()))
@@ -152,7 +154,7 @@ async fn confirm_meeting(start: Option<String>, end: Option<String>) -> Result<(
         }
     }
 
-    let client = reqwest::Client::new();
+    let client = daemon_client();
     let mut body = serde_json::Map::new();
     if let Some(s) = start_seconds {
         body.insert("start_seconds".to_string(), Value::from(s));
@@ -185,7 +187,7 @@ async fn confirm_meeting(start: Option<String>, end: Option<String>) -> Result<(
 }
 
 async fn cancel_meeting() -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
 
     let response = client
         .post(format!("{}/meetings/cancel", base_url()))
@@ -207,7 +209,7 @@ async fn cancel_meeting() -> Result<()> {
 }
 
 async fn show_status() -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
 
     let response = client
         .get(format!("{}/meetings/status", base_url()))
@@ -290,7 +292,7 @@ async fn show_status() -> Result<()> {
 }
 
 async fn list_meetings(limit: usize) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
 
     let response = client
         .get(format!("{}/meetings?limit={}", base_url(), limit))
@@ -339,7 +341,7 @@ async fn list_meetings(limit: usize) -> Result<()> {
 }
 
 async fn show_meeting(id: i64) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
 
     let response = client
         .get(format!("{}/meetings/{}", base_url(), id))
@@ -393,7 +395,7 @@ async fn show_meeting(id: i64) -> Result<()> {
 /// kept on disk); a missing/already-deleted meeting comes back as a friendly
 /// 404 via `json_or_error`.
 async fn delete_meeting(id: i64) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
 
     let response = client
         .delete(format!("{}/meetings/{}", base_url(), id))
@@ -407,6 +409,28 @@ async fn delete_meeting(id: i64) -> Result<()> {
     Ok(())
 }
 
+/// Resume a failed or interrupted meeting. The daemon refuses completed
+/// meetings (and the one it's still processing) with a 409, which
+/// `json_or_error` turns into the daemon's message.
+async fn resume_meeting(id: i64) -> Result<()> {
+    let client = daemon_client();
+
+    let response = client
+        .post(format!("{}/meetings/{}/resume", base_url(), id))
+        .send()
+        .await
+        .context("Failed to connect to Audetic service. Is it running?")?;
+
+    json_or_error(response, "resume meeting").await?;
+
+    println!("Resuming meeting #{}.", id);
+    println!(
+        "Transcription running in background. Run 'audetic meeting show {}' to check progress.",
+        id
+    );
+    Ok(())
+}
+
 async fn import_meeting(path: PathBuf, title: Option<String>) -> Result<()> {
     if !path.exists() {
         bail!("File does not exist: {}", path.display());
@@ -445,7 +469,7 @@ async fn import_meeting(path: PathBuf, title: Option<String>) -> Result<()> {
         form = form.text("title", t.to_string());
     }
 
-    let client = reqwest::Client::new();
+    let client = daemon_client();
     let response = client
         .post(api_url(paths::MEETINGS_IMPORT))
         .multipart(form)
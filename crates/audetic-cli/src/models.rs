@@ -11,7 +11,7 @@ use std::time::Duration;
 use tokio::time::sleep;
 
 use crate::args::{ModelsCliArgs, ModelsCommand};
-use crate::client::{json_or_error, CONNECT_HINT};
+use crate::client::{daemon_client, json_or_error, CONNECT_HINT};
 use audetic_core::url::{api_url, model_download_path, model_path, paths};
 
 pub async fn handle_models_command(args: ModelsCliArgs) -> Result<()> {
@@ -22,7 +22,7 @@ pub async fn handle_models_command(args: ModelsCliArgs) -> Result<()> {
 }
 
 async fn handle_list() -> Result<()> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .get(api_url(paths::MODELS))
         .send()
         .await
@@ -82,7 +82,7 @@ fn describe_download(download: Option<&Value>) -> String {
 /// Trigger a download and poll until the model is installed (or fails),
 /// rendering a progress bar.
 pub async fn ensure_downloaded(id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
 
     let response = client
         .post(api_url(&model_download_path(id)))
@@ -8,6 +8,7 @@ use anyhow::{bail, Context, Result};
 use serde_json::{json, Value};
 
 use crate::args::{PostProcessingCliArgs, PostProcessingCommand};
+use crate::client::daemon_client;
 use audetic_core::url::{api_url, paths, post_processing_job_path, post_processing_job_test_path};
 
 pub async fn handle_post_processing_command(args: PostProcessingCliArgs) -> Result<()> {
@@ -69,7 +70,7 @@ async fn json_or_error(response: reqwest::Response, op: &str) -> Result<Value> {
 }
 
 async fn list_jobs(event: Option<String>) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
     let mut url = api_url(paths::POST_PROCESSING_JOBS);
     if let Some(e) = &event {
         url.push_str("?event=");
@@ -100,7 +101,7 @@ async fn list_jobs(event: Option<String>) -> Result<()> {
 }
 
 async fn show_job(id: i64) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = daemon_client();
     let response = client
         .get(api_url(&post_processing_job_path(id)))
         .send()
@@ -128,7 +129,7 @@ async fn add_job(
         },
         "enabled": enabled,
     });
-    let client = reqwest::Client::new();
+    let client = daemon_client();
     let response = client
         .post(api_url(paths::POST_PROCESSING_JOBS))
         .json(&body)
@@ -162,7 +163,7 @@ async fn update_job(
     if command.is_some() || timeout.is_some() {
         // Action must be supplied as a whole — fetch existing so we can
         // overlay only the fields the user changed.
-        let existing = reqwest::Client::new()
+        let existing = daemon_client()
             .get(api_url(&post_processing_job_path(id)))
             .send()
             .await
@@ -196,7 +197,7 @@ async fn update_job(
         return Ok(());
     }
 
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .patch(api_url(&post_processing_job_path(id)))
         .json(&Value::Object(patch))
         .send()
@@ -209,7 +210,7 @@ async fn update_job(
 }
 
 async fn remove_job(id: i64) -> Result<()> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .delete(api_url(&post_processing_job_path(id)))
         .send()
         .await
@@ -220,7 +221,7 @@ async fn remove_job(id: i64) -> Result<()> {
 }
 
 async fn test_job(id: i64) -> Result<()> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .post(api_url(&post_processing_job_test_path(id)))
         .send()
         .await
@@ -260,7 +261,7 @@ async fn test_job(id: i64) -> Result<()> {
 }
 
 async fn list_events() -> Result<()> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .get(api_url(paths::POST_PROCESSING_EVENTS))
         .send()
         .await
@@ -0,0 +1,93 @@
+//! Error handling shared by the interactive `dialoguer` prompts.
+//!
+//! The wizards only start when stdin is a terminal, but that terminal can
+//! still go away mid-prompt (ssh disconnect) or the user can hit Ctrl-C.
+//! `dialoguer` reports both as raw I/O errors; [`PromptResultExt::or_cancelled`]
+//! turns them into a single [`InputCancelled`] error so the wizard aborts
+//! before anything is saved and the user sees why.
+
+use std::fmt;
+use std::io;
+
+/// The user's input went away before a prompt was answered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputCancelled;
+
+impl fmt::Display for InputCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Input cancelled; no changes were saved")
+    }
+}
+
+impl std::error::Error for InputCancelled {}
+
+pub trait PromptResultExt<T> {
+    /// Convert a prompt result, mapping a closed or interrupted input stream
+    /// to [`InputCancelled`].
+    fn or_cancelled(self) -> anyhow::Result<T>;
+}
+
+impl<T> PromptResultExt<T> for dialoguer::Result<T> {
+    fn or_cancelled(self) -> anyhow::Result<T> {
+        self.map_err(|dialoguer::Error::IO(e)| {
+            if is_cancellation(&e) {
+                anyhow::Error::new(InputCancelled)
+            } else {
+                anyhow::Error::new(e).context("Failed to read input")
+            }
+        })
+    }
+}
+
+/// EOF or a dropped terminal (the stream closed under us), or Ctrl-C, which
+/// the terminal backend reports as `Interrupted` while in raw mode.
+fn is_cancellation(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::UnexpectedEof
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::NotConnected
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt_failing_with(kind: io::ErrorKind) -> dialoguer::Result<String> {
+        Err(dialoguer::Error::IO(io::Error::new(kind, "stdin closed")))
+    }
+
+    #[test]
+    fn eof_during_prompt_is_cancellation() {
+        let err = prompt_failing_with(io::ErrorKind::UnexpectedEof)
+            .or_cancelled()
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<InputCancelled>(), Some(&InputCancelled));
+        assert!(err.to_string().contains("Input cancelled"));
+    }
+
+    #[test]
+    fn ctrl_c_during_prompt_is_cancellation() {
+        let err = prompt_failing_with(io::ErrorKind::Interrupted)
+            .or_cancelled()
+            .unwrap_err();
+        assert!(err.is::<InputCancelled>());
+    }
+
+    #[test]
+    fn other_io_errors_pass_through() {
+        let err = prompt_failing_with(io::ErrorKind::PermissionDenied)
+            .or_cancelled()
+            .unwrap_err();
+        assert!(!err.is::<InputCancelled>());
+        assert!(err.to_string().contains("Failed to read input"));
+    }
+
+    #[test]
+    fn answers_pass_through() {
+        let answer: dialoguer::Result<String> = Ok("en".to_string());
+        assert_eq!(answer.or_cancelled().unwrap(), "en");
+    }
+}
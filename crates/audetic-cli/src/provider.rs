@@ -7,23 +7,26 @@
 //! so there is a single writer.
 
 use crate::args::{ProviderCliArgs, ProviderCommand};
-use crate::client::{base_url, json_or_error, CONNECT_HINT};
+use crate::client::{base_url, daemon_client, json_or_error, CONNECT_HINT};
+use crate::prompt::PromptResultExt;
 use anyhow::{Context, Result};
 use audetic_core::config::WhisperConfig;
+use audetic_core::language;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Password, Select};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::io::{self, IsTerminal};
 use std::path::Path;
 use which::which;
 
-pub async fn handle_provider_command(args: ProviderCliArgs) -> Result<()> {
+pub async fn handle_provider_command(args: ProviderCliArgs, json: bool) -> Result<()> {
     match args.command {
-        Some(ProviderCommand::Show) => handle_show().await,
+        Some(ProviderCommand::Show) => handle_show(json).await,
         Some(ProviderCommand::Configure { dry_run }) => handle_configure(dry_run).await,
-        Some(ProviderCommand::Test { file }) => handle_test(file).await,
-        Some(ProviderCommand::Status) => handle_status().await,
-        Some(ProviderCommand::Reset { force }) => handle_reset(force).await,
+        Some(ProviderCommand::Test { file, dry_network }) => handle_test(file, dry_network).await,
+        Some(ProviderCommand::Status) => handle_status(json).await,
+        Some(ProviderCommand::Reset { force, dry_run }) => handle_reset(force, dry_run).await,
         None => handle_interactive().await,
     }
 }
@@ -34,7 +37,7 @@ pub async fn handle_provider_command(args: ProviderCliArgs) -> Result<()> {
 
 /// Fetch the raw provider config from the daemon.
 async fn fetch_config() -> Result<WhisperConfig> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .get(format!("{}/provider/config", base_url()))
         .send()
         .await
@@ -45,7 +48,7 @@ async fn fetch_config() -> Result<WhisperConfig> {
 
 /// Persist a provider config via the daemon (it backs up `config.toml` first).
 async fn save_config(whisper: &WhisperConfig) -> Result<()> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .put(format!("{}/provider/config", base_url()))
         .json(whisper)
         .send()
@@ -91,13 +94,14 @@ async fn handle_interactive() -> Result<()> {
         .with_prompt("What would you like to do?")
         .items(&options)
         .default(0)
-        .interact()?;
+        .interact()
+        .or_cancelled()?;
 
     match selection {
         0 => handle_configure(false).await,
-        1 => handle_test(None).await,
-        2 => handle_show().await,
-        3 => handle_reset(false).await,
+        1 => handle_test(None, false).await,
+        2 => handle_show(false).await,
+        3 => handle_reset(false, false).await,
         _ => {
             println!("Exiting provider setup.");
             Ok(())
@@ -105,8 +109,15 @@ async fn handle_interactive() -> Result<()> {
     }
 }
 
-async fn handle_show() -> Result<()> {
+async fn handle_show(json: bool) -> Result<()> {
     let whisper = fetch_config().await?;
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&masked_config(&whisper)?)?
+        );
+        return Ok(());
+    }
 
     println!();
     println!("Provider Configuration");
@@ -162,7 +173,13 @@ async fn handle_configure(dry_run: bool) -> Result<()> {
     match selection {
         ProviderSelection::AudeticApi => configure_audetic_api(&theme, &mut whisper)?,
         ProviderSelection::AssemblyAi => configure_assembly_ai(&theme, &mut whisper)?,
-        ProviderSelection::OpenAiApi => configure_openai_api(&theme, &mut whisper)?,
+        ProviderSelection::Deepgram => configure_deepgram(&theme, &mut whisper)?,
+        ProviderSelection::OpenAiApi | ProviderSelection::Groq => configure_openai_compatible(
+            &theme,
+            &mut whisper,
+            selection,
+            old_config.provider.as_deref(),
+        )?,
         ProviderSelection::OpenAiCli => configure_openai_cli(&theme, &mut whisper)?,
         ProviderSelection::WhisperCpp => configure_whisper_cpp(&theme, &mut whisper)?,
         ProviderSelection::Local => configure_local(&theme, &mut whisper)?,
@@ -184,7 +201,8 @@ async fn handle_configure(dry_run: bool) -> Result<()> {
     let proceed = Confirm::with_theme(&theme)
         .with_prompt("Save these changes?")
         .default(true)
-        .interact()?;
+        .interact()
+        .or_cancelled()?;
     if !proceed {
         println!("Configuration cancelled.");
         return Ok(());
@@ -209,7 +227,8 @@ async fn handle_configure(dry_run: bool) -> Result<()> {
                 let download = Confirm::with_theme(&theme)
                     .with_prompt(format!("Download model '{model_id}' now?"))
                     .default(true)
-                    .interact()?;
+                    .interact()
+                    .or_cancelled()?;
                 if download {
                     crate::models::ensure_downloaded(&model_id).await?;
                 } else {
@@ -227,7 +246,7 @@ async fn handle_configure(dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-async fn handle_test(file: Option<String>) -> Result<()> {
+async fn handle_test(file: Option<String>, dry_network: bool) -> Result<()> {
     println!();
     println!("Provider Test");
     println!("=============");
@@ -238,9 +257,9 @@ async fn handle_test(file: Option<String>) -> Result<()> {
     }
     print!("Testing... ");
 
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .post(format!("{}/provider/test", base_url()))
-        .json(&json!({ "file": file }))
+        .json(&json!({ "file": file, "check_network": dry_network }))
         .send()
         .await
         .context(CONNECT_HINT)?;
@@ -253,6 +272,20 @@ async fn handle_test(file: Option<String>) -> Result<()> {
     println!("{}", if success { "OK" } else { "failed" });
     println!();
 
+    // Only present when the network check ran against a remote provider.
+    if let Some(reachable) = body.get("reachable").and_then(|v| v.as_bool()) {
+        println!(
+            "Endpoint:  {}",
+            if reachable {
+                "reachable, API key accepted"
+            } else {
+                "not ready"
+            }
+        );
+    } else if dry_network {
+        println!("Endpoint:  not checked (on-device provider)");
+    }
+
     if let Some(err) = body.get("error").and_then(|v| v.as_str()) {
         if !err.is_empty() {
             println!("Error: {err}");
@@ -273,19 +306,28 @@ async fn handle_test(file: Option<String>) -> Result<()> {
     }
 
     if success {
-        println!("Provider is working correctly.");
+        if dry_network {
+            println!("Provider is ready.");
+        } else {
+            println!("Provider is working correctly.");
+        }
     }
     Ok(())
 }
 
-async fn handle_status() -> Result<()> {
-    let response = reqwest::Client::new()
+async fn handle_status(json: bool) -> Result<()> {
+    let response = daemon_client()
         .get(format!("{}/provider/status", base_url()))
         .send()
         .await
         .context(CONNECT_HINT)?;
     let body = json_or_error(response, "get provider status").await?;
 
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+
     println!();
     println!("Audetic Provider Status");
     println!("=======================");
@@ -337,9 +379,45 @@ async fn handle_status() -> Result<()> {
     Ok(())
 }
 
-async fn handle_reset(force: bool) -> Result<()> {
+async fn handle_reset(force: bool, dry_run: bool) -> Result<()> {
     let whisper = fetch_config().await?;
-    let current_provider = whisper.provider.as_deref().unwrap_or("<not set>");
+    let applied = run_reset(&whisper, force, dry_run, || async {
+        let response = daemon_client()
+            .post(format!("{}/provider/reset", base_url()))
+            .send()
+            .await
+            .context(CONNECT_HINT)?;
+        json_or_error(response, "reset provider config").await?;
+        Ok(())
+    })
+    .await?;
+
+    if applied {
+        println!();
+        println!("Provider configuration reset to defaults.");
+        println!();
+        println!("Next steps:");
+        println!("  audetic provider           - Configure a new provider");
+        println!("  Restart the Audetic daemon to apply changes");
+    }
+
+    Ok(())
+}
+
+/// Preview a reset of `current` and, unless this is a dry run or the user
+/// declines, call `apply` to perform it. Returns whether `apply` ran. Split
+/// from `handle_reset` so the dry-run path can be checked without a daemon.
+async fn run_reset<F, Fut>(
+    current: &WhisperConfig,
+    force: bool,
+    dry_run: bool,
+    apply: F,
+) -> Result<bool>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let current_provider = current.provider.as_deref().unwrap_or("<not set>");
 
     println!();
     println!("Reset Provider Configuration");
@@ -347,6 +425,23 @@ async fn handle_reset(force: bool) -> Result<()> {
     println!();
     println!("Current provider: {current_provider}");
     println!();
+
+    if dry_run {
+        let changes = config_diff(current, &WhisperConfig::default());
+        if changes.is_empty() {
+            println!("Already at defaults - nothing would change.");
+        } else {
+            println!("Reset would change:");
+            for line in changes {
+                println!("{line}");
+            }
+        }
+        println!();
+        println!("Dry run mode - nothing cleared, no backup made.");
+        println!("Remove --dry-run to reset.");
+        return Ok(false);
+    }
+
     println!("This will reset to:");
     println!("  Provider: audetic-api (default)");
     println!("  Model:    base");
@@ -357,34 +452,22 @@ async fn handle_reset(force: bool) -> Result<()> {
     if !force {
         if !io::stdin().is_terminal() {
             println!("Non-interactive session. Use --force to reset without confirmation.");
-            return Ok(());
+            return Ok(false);
         }
         let theme = ColorfulTheme::default();
         let proceed = Confirm::with_theme(&theme)
             .with_prompt("Proceed with reset?")
             .default(false)
-            .interact()?;
+            .interact()
+            .or_cancelled()?;
         if !proceed {
             println!("Reset cancelled.");
-            return Ok(());
+            return Ok(false);
         }
     }
 
-    let response = reqwest::Client::new()
-        .post(format!("{}/provider/reset", base_url()))
-        .send()
-        .await
-        .context(CONNECT_HINT)?;
-    json_or_error(response, "reset provider config").await?;
-
-    println!();
-    println!("Provider configuration reset to defaults.");
-    println!();
-    println!("Next steps:");
-    println!("  audetic provider           - Configure a new provider");
-    println!("  Restart the Audetic daemon to apply changes");
-
-    Ok(())
+    apply().await?;
+    Ok(true)
 }
 
 // ============================================================================
@@ -392,26 +475,133 @@ async fn handle_reset(force: bool) -> Result<()> {
 // ============================================================================
 
 fn print_config_diff(old: &WhisperConfig, new: &WhisperConfig) {
-    print_field_diff("Provider", &old.provider, &new.provider);
-    print_field_diff("Model", &old.model, &new.model);
-    print_field_diff("Language", &old.language, &new.language);
-    print_field_diff("API Endpoint", &old.api_endpoint, &new.api_endpoint);
-    print_secret_diff("API Key", &old.api_key, &new.api_key);
-    print_field_diff("Command Path", &old.command_path, &new.command_path);
-    print_field_diff("Model Path", &old.model_path, &new.model_path);
+    for line in config_diff(old, new) {
+        println!("{line}");
+    }
 }
 
-fn print_field_diff(name: &str, old: &Option<String>, new: &Option<String>) {
-    if old != new {
-        let old_display = old.as_deref().unwrap_or("<not set>");
-        let new_display = new.as_deref().unwrap_or("<not set>");
-        println!("  {name}: {old_display} -> {new_display}");
+/// One `  Field: old -> new` line per changed field; secrets are masked.
+fn config_diff(old: &WhisperConfig, new: &WhisperConfig) -> Vec<String> {
+    let mut lines = Vec::new();
+    field_diff(&mut lines, "Provider", &old.provider, &new.provider);
+    field_diff(&mut lines, "Model", &old.model, &new.model);
+    field_diff(&mut lines, "Language", &old.language, &new.language);
+    field_diff(
+        &mut lines,
+        "API Endpoint",
+        &old.api_endpoint,
+        &new.api_endpoint,
+    );
+    if old.api_key != new.api_key {
+        lines.push(format!(
+            "  API Key: {} -> {}",
+            mask_secret(&old.api_key),
+            mask_secret(&new.api_key)
+        ));
+    }
+    field_diff(
+        &mut lines,
+        "Command Path",
+        &old.command_path,
+        &new.command_path,
+    );
+    field_diff(&mut lines, "Model Path", &old.model_path, &new.model_path);
+    field_diff(
+        &mut lines,
+        "Connect Timeout",
+        &old.connect_timeout_seconds.map(|s| format!("{s}s")),
+        &new.connect_timeout_seconds.map(|s| format!("{s}s")),
+    );
+    field_diff(
+        &mut lines,
+        "Request Timeout",
+        &old.request_timeout_seconds.map(|s| format!("{s}s")),
+        &new.request_timeout_seconds.map(|s| format!("{s}s")),
+    );
+    field_diff(
+        &mut lines,
+        "Max Retries",
+        &old.max_retries.map(|n| n.to_string()),
+        &new.max_retries.map(|n| n.to_string()),
+    );
+    field_diff(
+        &mut lines,
+        "Max Audio",
+        &old.max_audio_seconds.map(|s| format!("{s}s")),
+        &new.max_audio_seconds.map(|s| format!("{s}s")),
+    );
+    if old.word_timestamps != new.word_timestamps {
+        lines.push(format!(
+            "  Word Timestamps: {} -> {}",
+            old.word_timestamps, new.word_timestamps
+        ));
+    }
+    field_diff(
+        &mut lines,
+        "Candidate Languages",
+        &old.candidate_languages
+            .as_ref()
+            .map(|langs| langs.join(", ")),
+        &new.candidate_languages
+            .as_ref()
+            .map(|langs| langs.join(", ")),
+    );
+    field_diff(
+        &mut lines,
+        "AssemblyAI Features",
+        &old.assemblyai_features.as_ref().map(join_pairs),
+        &new.assemblyai_features.as_ref().map(join_pairs),
+    );
+    field_diff(
+        &mut lines,
+        "CLI Timeout",
+        &old.cli_timeout_seconds.map(|s| format!("{s}s")),
+        &new.cli_timeout_seconds.map(|s| format!("{s}s")),
+    );
+    field_diff(
+        &mut lines,
+        "CLI Max Output",
+        &old.cli_max_output_bytes.map(|n| format!("{n} bytes")),
+        &new.cli_max_output_bytes.map(|n| format!("{n} bytes")),
+    );
+    if old.stream_partials != new.stream_partials {
+        lines.push(format!(
+            "  Stream Partials: {} -> {}",
+            old.stream_partials, new.stream_partials
+        ));
     }
+    // Header values often carry gateway credentials, so they're masked
+    // like the API key, one line per header.
+    let no_headers = BTreeMap::new();
+    let old_headers = old.extra_headers.as_ref().unwrap_or(&no_headers);
+    let new_headers = new.extra_headers.as_ref().unwrap_or(&no_headers);
+    let names: BTreeSet<&String> = old_headers.keys().chain(new_headers.keys()).collect();
+    for name in names {
+        let (old_value, new_value) = (old_headers.get(name), new_headers.get(name));
+        if old_value != new_value {
+            lines.push(format!(
+                "  Extra Header {name}: {} -> {}",
+                mask_secret(&old_value.cloned()),
+                mask_secret(&new_value.cloned())
+            ));
+        }
+    }
+    lines
+}
+
+/// `key=value` pairs, comma-separated, for a settings map in a diff line.
+fn join_pairs<V: std::fmt::Display>(map: &BTreeMap<String, V>) -> String {
+    map.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-fn print_secret_diff(name: &str, old: &Option<String>, new: &Option<String>) {
+fn field_diff(lines: &mut Vec<String>, name: &str, old: &Option<String>, new: &Option<String>) {
     if old != new {
-        println!("  {name}: {} -> {}", mask_secret(old), mask_secret(new));
+        let old_display = old.as_deref().unwrap_or("<not set>");
+        let new_display = new.as_deref().unwrap_or("<not set>");
+        lines.push(format!("  {name}: {old_display} -> {new_display}"));
     }
 }
 
@@ -467,17 +657,65 @@ fn configure_assembly_ai(theme: &ColorfulTheme, whisper: &mut WhisperConfig) ->
     Ok(())
 }
 
-fn configure_openai_api(theme: &ColorfulTheme, whisper: &mut WhisperConfig) -> Result<()> {
+fn configure_deepgram(theme: &ColorfulTheme, whisper: &mut WhisperConfig) -> Result<()> {
+    whisper.command_path = None;
+    whisper.model_path = None;
+
+    let api_key = prompt_secret(theme, "Deepgram API key", whisper.api_key.as_ref())?;
+    whisper.api_key = Some(api_key);
+
+    let endpoint_default = whisper
+        .api_endpoint
+        .clone()
+        .unwrap_or_else(|| "https://api.deepgram.com".to_string());
+    whisper.api_endpoint = Some(prompt_string_with_default(
+        theme,
+        "API base URL",
+        &endpoint_default,
+    )?);
+
+    whisper.model = None;
+    prompt_language_choice(theme, whisper, "en")?;
+    Ok(())
+}
+
+/// Configure a provider that speaks OpenAI's transcription API (`openai-api`
+/// or `groq`). The saved endpoint and model are only offered as defaults when
+/// the provider is unchanged, so switching services starts from the new
+/// service's URL rather than the old one's.
+fn configure_openai_compatible(
+    theme: &ColorfulTheme,
+    whisper: &mut WhisperConfig,
+    selection: ProviderSelection,
+    previous_provider: Option<&str>,
+) -> Result<()> {
+    let (key_prompt, default_endpoint, default_model) = match selection {
+        ProviderSelection::Groq => (
+            "Groq API key (gsk_...)",
+            "https://api.groq.com/openai/v1/audio/transcriptions",
+            "whisper-large-v3",
+        ),
+        _ => (
+            "OpenAI API key (sk-...)",
+            "https://api.openai.com/v1/audio/transcriptions",
+            "whisper-1",
+        ),
+    };
+
     whisper.command_path = None;
     whisper.model_path = None;
+    if previous_provider != Some(selection.as_str()) {
+        whisper.api_endpoint = None;
+        whisper.model = None;
+    }
 
-    let api_key = prompt_secret(theme, "OpenAI API key (sk-...)", whisper.api_key.as_ref())?;
+    let api_key = prompt_secret(theme, key_prompt, whisper.api_key.as_ref())?;
     whisper.api_key = Some(api_key);
 
     let endpoint_default = whisper
         .api_endpoint
         .clone()
-        .unwrap_or_else(|| "https://api.openai.com/v1/audio/transcriptions".to_string());
+        .unwrap_or_else(|| default_endpoint.to_string());
     whisper.api_endpoint = Some(prompt_string_with_default(
         theme,
         "API endpoint",
@@ -487,10 +725,10 @@ fn configure_openai_api(theme: &ColorfulTheme, whisper: &mut WhisperConfig) -> R
     let model_default = whisper
         .model
         .clone()
-        .unwrap_or_else(|| "whisper-1".to_string());
+        .unwrap_or_else(|| default_model.to_string());
     whisper.model = Some(prompt_string_with_default(
         theme,
-        "Model (whisper-1)",
+        &format!("Model ({default_model})"),
         &model_default,
     )?);
 
@@ -552,7 +790,8 @@ fn configure_local(theme: &ColorfulTheme, whisper: &mut WhisperConfig) -> Result
         .with_prompt("Select a local model")
         .items(&items)
         .default(default_index)
-        .interact()?;
+        .interact()
+        .or_cancelled()?;
 
     let model = &catalog[selection];
     whisper.model = Some(model.id.to_string());
@@ -613,7 +852,9 @@ fn prompt_provider_selection(
             "Audetic Cloud API (default, no setup required)",
         ),
         ("assembly-ai", "AssemblyAI API (requires API key)"),
+        ("deepgram", "Deepgram API (requires API key)"),
         ("openai-api", "OpenAI Whisper API (requires API key)"),
+        ("groq", "Groq Whisper API (requires API key)"),
         (
             "openai-cli",
             "Local OpenAI Whisper CLI (requires local install)",
@@ -641,7 +882,8 @@ fn prompt_provider_selection(
         .with_prompt("Select a transcription provider")
         .items(&items)
         .default(default_index)
-        .interact()?;
+        .interact()
+        .or_cancelled()?;
 
     Ok(ProviderSelection::from_index(selection))
 }
@@ -651,14 +893,18 @@ fn prompt_secret(theme: &ColorfulTheme, prompt: &str, current: Option<&String>)
         let keep = Confirm::with_theme(theme)
             .with_prompt(format!("Keep existing {prompt}?"))
             .default(true)
-            .interact()?;
+            .interact()
+            .or_cancelled()?;
         if keep {
             return Ok(existing.clone());
         }
     }
 
     loop {
-        let value = Password::new().with_prompt(prompt).interact()?;
+        let value = Password::new()
+            .with_prompt(prompt)
+            .interact()
+            .or_cancelled()?;
         let trimmed = value.trim();
         if trimmed.is_empty() {
             println!("{prompt} cannot be empty.");
@@ -672,7 +918,8 @@ fn prompt_string_with_default(theme: &ColorfulTheme, label: &str, current: &str)
     let value: String = Input::with_theme(theme)
         .with_prompt(format!("{label} [{current}]"))
         .allow_empty(true)
-        .interact_text()?;
+        .interact_text()
+        .or_cancelled()?;
 
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -692,20 +939,30 @@ fn prompt_language_choice(
         .clone()
         .unwrap_or_else(|| fallback.to_string());
 
-    let value: String = Input::with_theme(theme)
-        .with_prompt(format!(
-            "Language code (ISO 639-1, e.g. en, es, auto) [{current}]"
-        ))
-        .allow_empty(true)
-        .interact_text()?;
+    loop {
+        let value: String = Input::with_theme(theme)
+            .with_prompt(format!(
+                "Language code (ISO 639-1, e.g. en, es, auto) [{current}]"
+            ))
+            .allow_empty(true)
+            .interact_text()
+            .or_cancelled()?;
 
-    let trimmed = value.trim();
-    whisper.language = Some(if trimmed.is_empty() {
-        current
-    } else {
-        trimmed.to_string()
-    });
-    Ok(())
+        let trimmed = value.trim();
+        let candidate = if trimmed.is_empty() {
+            current.clone()
+        } else {
+            trimmed.to_ascii_lowercase()
+        };
+
+        match language::check(&candidate) {
+            None => {
+                whisper.language = Some(candidate);
+                return Ok(());
+            }
+            Some(warning) => println!("{warning}"),
+        }
+    }
 }
 
 fn prompt_required_path(
@@ -723,7 +980,8 @@ fn prompt_required_path(
         let value: String = Input::with_theme(theme)
             .with_prompt(prompt)
             .allow_empty(default.is_some())
-            .interact_text()?;
+            .interact_text()
+            .or_cancelled()?;
 
         let candidate = if value.trim().is_empty() {
             match &default {
@@ -786,6 +1044,23 @@ fn mask_secret(value: &Option<String>) -> String {
     }
 }
 
+/// `whisper` as JSON for `provider show --json`, with the API key and
+/// extra header values masked like the text view masks them.
+fn masked_config(whisper: &WhisperConfig) -> Result<Value> {
+    let mut value = serde_json::to_value(whisper)?;
+    if whisper.api_key.is_some() {
+        value["api_key"] = json!(mask_secret(&whisper.api_key));
+    }
+    if let Some(headers) = &whisper.extra_headers {
+        let masked: serde_json::Map<String, Value> = headers
+            .iter()
+            .map(|(name, secret)| (name.clone(), json!(mask_secret(&Some(secret.clone())))))
+            .collect();
+        value["extra_headers"] = Value::Object(masked);
+    }
+    Ok(value)
+}
+
 // ============================================================================
 // Provider selection enum
 // ============================================================================
@@ -794,7 +1069,9 @@ fn mask_secret(value: &Option<String>) -> String {
 enum ProviderSelection {
     AudeticApi,
     AssemblyAi,
+    Deepgram,
     OpenAiApi,
+    Groq,
     OpenAiCli,
     WhisperCpp,
     Local,
@@ -805,7 +1082,9 @@ impl ProviderSelection {
         match self {
             ProviderSelection::AudeticApi => "audetic-api",
             ProviderSelection::AssemblyAi => "assembly-ai",
+            ProviderSelection::Deepgram => "deepgram",
             ProviderSelection::OpenAiApi => "openai-api",
+            ProviderSelection::Groq => "groq",
             ProviderSelection::OpenAiCli => "openai-cli",
             ProviderSelection::WhisperCpp => "whisper-cpp",
             ProviderSelection::Local => "local",
@@ -816,9 +1095,11 @@ impl ProviderSelection {
         match index {
             0 => ProviderSelection::AudeticApi,
             1 => ProviderSelection::AssemblyAi,
-            2 => ProviderSelection::OpenAiApi,
-            3 => ProviderSelection::OpenAiCli,
-            4 => ProviderSelection::WhisperCpp,
+            2 => ProviderSelection::Deepgram,
+            3 => ProviderSelection::OpenAiApi,
+            4 => ProviderSelection::Groq,
+            5 => ProviderSelection::OpenAiCli,
+            6 => ProviderSelection::WhisperCpp,
             _ => ProviderSelection::Local,
         }
     }
@@ -838,4 +1119,112 @@ mod tests {
             "sk-1****ef"
         );
     }
+
+    fn customized_config() -> WhisperConfig {
+        WhisperConfig {
+            provider: Some("openai-api".to_string()),
+            model: Some("whisper-1".to_string()),
+            api_key: Some("sk-1234567890abcdef".to_string()),
+            api_endpoint: Some("https://api.openai.com/v1/audio/transcriptions".to_string()),
+            ..WhisperConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_masked_config_hides_secrets() {
+        let whisper = WhisperConfig {
+            provider: Some("openai-api".to_string()),
+            api_key: Some("sk-1234567890abcdef".to_string()),
+            extra_headers: Some(
+                [(
+                    "x-api-gateway-key".to_string(),
+                    "gateway-secret".to_string(),
+                )]
+                .into(),
+            ),
+            ..WhisperConfig::default()
+        };
+        let value = masked_config(&whisper).unwrap();
+        assert_eq!(value["provider"], "openai-api");
+        assert_eq!(value["api_key"], "sk-1****ef");
+        assert_eq!(value["extra_headers"]["x-api-gateway-key"], "gate****et");
+        assert!(masked_config(&WhisperConfig::default()).unwrap()["api_key"].is_null());
+    }
+
+    #[test]
+    fn test_config_diff_masks_secrets() {
+        let diff = config_diff(&customized_config(), &WhisperConfig::default());
+        assert!(diff.contains(&"  Provider: openai-api -> audetic-api".to_string()));
+        assert!(diff.contains(&"  API Key: sk-1****ef -> <not set>".to_string()));
+        assert!(diff
+            .iter()
+            .all(|line| !line.contains("sk-1234567890abcdef")));
+    }
+
+    #[test]
+    fn test_config_diff_covers_every_reset_field() {
+        let current = WhisperConfig {
+            candidate_languages: Some(vec!["en".to_string(), "de".to_string()]),
+            assemblyai_features: Some([("punctuate".to_string(), false)].into()),
+            cli_timeout_seconds: Some(600),
+            cli_max_output_bytes: Some(1024),
+            stream_partials: true,
+            extra_headers: Some(
+                [(
+                    "x-api-gateway-key".to_string(),
+                    "gateway-secret".to_string(),
+                )]
+                .into(),
+            ),
+            ..WhisperConfig::default()
+        };
+        let diff = config_diff(&current, &WhisperConfig::default());
+        assert_eq!(
+            diff,
+            [
+                "  Candidate Languages: en, de -> <not set>",
+                "  AssemblyAI Features: punctuate=false -> <not set>",
+                "  CLI Timeout: 600s -> <not set>",
+                "  CLI Max Output: 1024 bytes -> <not set>",
+                "  Stream Partials: true -> false",
+                "  Extra Header x-api-gateway-key: gate****et -> <not set>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_diff_empty_for_defaults() {
+        assert!(config_diff(&WhisperConfig::default(), &WhisperConfig::default()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reset_dry_run_writes_nothing() {
+        let current = customized_config();
+        assert!(!config_diff(&current, &WhisperConfig::default()).is_empty());
+
+        let mut called = false;
+        let applied = run_reset(&current, true, true, || {
+            called = true;
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert!(!applied);
+        assert!(!called, "dry run must not call the reset endpoint");
+    }
+
+    #[tokio::test]
+    async fn test_reset_force_applies() {
+        let mut called = false;
+        let applied = run_reset(&customized_config(), true, false, || {
+            called = true;
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert!(applied);
+        assert!(called);
+    }
 }
@@ -1,56 +1,182 @@
 //! CLI handler for transcribing audio/video files.
 //!
 //! Submits files to the jobs API, polls for progress, and outputs results.
+//! An http(s) URL is downloaded to a temp file first and removed afterwards.
+//! Several files (or `--glob`) are transcribed one after another; see
+//! [`batch`]. `--watch <dir>` keeps running and transcribes files dropped
+//! into a directory; see [`watch`]. `--estimate` reports what would be sent
+//! and stops there; see [`estimate`].
+
+mod batch;
+mod estimate;
+mod watch;
 
 use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::fmt;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::watch;
+use tokio::time::{sleep, Instant};
 
 use crate::args::{OutputFormat, TranscribeCliArgs};
-use audetic_core::clipboard::copy_to_clipboard_sync;
-use audetic_core::compression::{cleanup_temp_file, get_file_size, prepare_for_upload};
-use audetic_core::config::Config;
+use audetic_core::clipboard::{copy_to_clipboard_sync, read_clipboard_sync};
+use audetic_core::compression::{
+    cleanup_temp_file, get_file_size, needs_compression, prepare_for_upload,
+};
+use audetic_core::config::{Config, TranscribeConfig};
+use audetic_core::http::HttpOptions;
 use audetic_core::jobs_client::{
     mime_type_for_extension, status, Job, JobsClient, TranscriptionResult,
 };
-const POLL_INTERVAL_MS: u64 = 1000;
-const MAX_POLL_ATTEMPTS: u32 = 1800; // 30 minutes at 1s intervals
+use audetic_core::subtitles;
+
+/// First wait between job status polls, and the wait again after progress.
+const POLL_INITIAL_INTERVAL: Duration = Duration::from_millis(500);
+/// Longest wait between polls once a job stops reporting progress.
+const POLL_MAX_INTERVAL: Duration = Duration::from_secs(5);
+/// How long to poll before giving up and salvaging a partial result.
+const POLL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
 const DEFAULT_API_URL: &str = "https://audio.audetic.link/api/v1/jobs";
+/// Largest remote file `transcribe <url>` will download (2GB).
+const MAX_DOWNLOAD_BYTES: u64 = 2_000_000_000;
+/// Extensions `validate_file` accepts; a downloaded file gets one of these.
+const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "wav", "mp3", "m4a", "flac", "ogg", "opus", "mp4", "mkv", "webm", "avi", "mov",
+];
+
+/// Ctrl-C stopped a transcription. Batch and watch runs stop on it rather
+/// than moving on to the next file, and the process exits with 130.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interrupted;
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transcription interrupted")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Resolves once Ctrl-C has been pressed, immediately if it already was.
+///
+/// The first tokio Ctrl-C listener replaces the default SIGINT handler for
+/// the rest of the process, so a single listener serves the whole command
+/// and a press between two awaits (say, while compressing) isn't lost.
+async fn interrupted() {
+    static PRESSED: OnceLock<watch::Receiver<bool>> = OnceLock::new();
+    let mut pressed = PRESSED
+        .get_or_init(|| {
+            let (tx, rx) = watch::channel(false);
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    let _ = tx.send(true);
+                }
+            });
+            rx
+        })
+        .clone();
+    if pressed.wait_for(|value| *value).await.is_err() {
+        // The listener couldn't be installed; nothing will interrupt us.
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Run `work` unless Ctrl-C comes first, in which case it is dropped and
+/// [`Interrupted`] returned.
+async fn interruptible<T>(work: impl Future<Output = Result<T>>) -> Result<T> {
+    tokio::select! {
+        result = work => result,
+        _ = interrupted() => Err(Interrupted.into()),
+    }
+}
+
+/// Whether `err` is, or wraps, [`Interrupted`].
+pub fn is_interrupted(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<Interrupted>())
+}
 
 /// Handle the transcribe CLI command.
 pub async fn handle_transcribe_command(args: TranscribeCliArgs) -> Result<()> {
-    // 1. Validate file exists and is supported format
-    validate_file(&args.file)?;
+    // Loaded once here and passed down, so nothing below (and no test)
+    // reads or creates the user's config.toml on its own.
+    let config = Config::load()?;
+    if let Some(dir) = &args.watch {
+        return watch::run(&args, dir, &config).await;
+    }
+    if args.estimate {
+        return estimate::run(&args, &config);
+    }
+    if args.files.len() > 1 || args.glob.is_some() {
+        return batch::run(&args, &config).await;
+    }
+
+    // 1. Resolve the input: a local path, an http(s) URL, or the clipboard
+    let input = match args.files.first() {
+        Some(file) => file.clone(),
+        None => file_from_clipboard(read_clipboard_sync)?,
+    };
+
+    let output = resolve_output(&args, &input, &config.transcribe)?;
+    transcribe_input(&args, &input, &output, &config).await
+}
+
+/// Transcribe a local path or http(s) URL, downloading the latter to a temp
+/// file first.
+async fn transcribe_input(
+    args: &TranscribeCliArgs,
+    input: &Path,
+    output: &Output,
+    config: &Config,
+) -> Result<()> {
+    let Some(url) = remote_url(input)? else {
+        return transcribe_file(args, input, output, config).await;
+    };
+
+    let client = HttpOptions::from_config(config).build_client()?;
+    let pb = (!args.no_progress).then(create_progress_bar);
+    let downloaded = download_to_temp(&client, &url, MAX_DOWNLOAD_BYTES, pb.as_ref()).await;
+    if let Some(pb) = pb {
+        pb.finish_and_clear();
+    }
+    let downloaded = downloaded?;
+
+    let result = transcribe_file(args, &downloaded, output, config).await;
+    cleanup_temp_file(&downloaded);
+    result
+}
+
+/// Transcribe a file on disk, via the daemon for on-device models or the
+/// jobs API otherwise.
+async fn transcribe_file(
+    args: &TranscribeCliArgs,
+    file: &Path,
+    output: &Output,
+    config: &Config,
+) -> Result<()> {
+    validate_file(file)?;
 
     // On-device transcription routes through the daemon — the slim CLI can't
     // link the engine (crate boundary). Cloud providers go direct to the jobs
     // API below, no daemon required.
-    if Config::load()
-        .map(|c| c.whisper.provider.as_deref() == Some("local"))
-        .unwrap_or(false)
-    {
-        return transcribe_via_daemon(&args).await;
+    if config.whisper.provider.as_deref() == Some("local") {
+        return interruptible(transcribe_via_daemon(args, file, output)).await;
     }
 
     // 2. Check file size and compress if needed
-    let (file_to_upload, temp_file) = prepare_file_for_upload(&args.file, args.no_compress)?;
+    let (file_to_upload, temp_file) = prepare_file_for_upload(
+        file,
+        args.no_compress,
+        config.behavior.compress_min_size_bytes,
+    )?;
 
     // 3. Determine API URL
-    let config = Config::load()?;
-    let base_url = args
-        .api_url
-        .or_else(|| {
-            config
-                .whisper
-                .api_endpoint
-                .as_ref()
-                .map(|e| derive_jobs_url(e))
-        })
-        .unwrap_or_else(|| DEFAULT_API_URL.to_string());
-
-    let client = JobsClient::new(&base_url);
+    let base_url = jobs_api_url(args, config);
+    let client = JobsClient::with_http(&base_url, &HttpOptions::from_config(config))?;
 
     // 4. Submit job with progress indicator
     let show_progress = !args.no_progress;
@@ -67,57 +193,97 @@ pub async fn handle_transcribe_command(args: TranscribeCliArgs) -> Result<()> {
         .as_deref()
         .or(config.whisper.language.as_deref());
 
-    let job_id = client
-        .submit_job(&file_to_upload, language, args.timestamps)
-        .await
-        .context("Failed to submit transcription job")?;
+    let submitted = interruptible(async {
+        client
+            .submit_job(&file_to_upload, language, args.timestamps)
+            .await
+            .context("Failed to submit transcription job")
+    })
+    .await;
+    let job_id = match submitted {
+        Ok(job_id) => job_id,
+        Err(e) => {
+            if let Some(temp) = &temp_file {
+                cleanup_temp_file(temp);
+            }
+            return Err(e);
+        }
+    };
 
-    // 5. Poll for completion
-    let job = poll_until_complete(&client, &job_id, pb.as_ref()).await?;
+    // 5. Poll for completion; Ctrl-C cancels the job on the server too
+    let outcome = tokio::select! {
+        outcome = poll_until_complete(
+            &client,
+            &job_id,
+            pb.as_ref(),
+            PollBackoff::new(POLL_INITIAL_INTERVAL, POLL_MAX_INTERVAL),
+            POLL_TIMEOUT,
+        ) => outcome,
+        _ = interrupted() => {
+            if let Some(pb) = &pb {
+                pb.abandon_with_message("Interrupted");
+            }
+            cancel_remote_job(&client, &job_id).await;
+            Err(Interrupted.into())
+        }
+    };
 
     // 6. Clean up temp file if one was created
     if let Some(temp) = temp_file {
         cleanup_temp_file(&temp);
     }
 
-    if let Some(pb) = pb {
-        pb.finish_with_message("Complete");
-    }
-
     // 7. Handle result
-    if job.status == status::FAILED {
-        bail!(
-            "Transcription failed: {}",
-            job.error.unwrap_or_else(|| "Unknown error".to_string())
-        );
-    }
-
-    let result = job
-        .result
-        .ok_or_else(|| anyhow::anyhow!("Job completed but no result available"))?;
+    let result = match outcome? {
+        PollOutcome::Finished(job) => {
+            if let Some(pb) = pb {
+                pb.finish_with_message("Complete");
+            }
+            if job.status == status::FAILED {
+                bail!(
+                    "Transcription failed: {}",
+                    job.error.unwrap_or_else(|| "Unknown error".to_string())
+                );
+            }
+            job.result
+                .ok_or_else(|| anyhow::anyhow!("Job completed but no result available"))?
+        }
+        PollOutcome::Partial(result) => {
+            if let Some(pb) = pb {
+                pb.abandon_with_message("Timed out");
+            }
+            // Keep what the server had, but not where the finished transcript
+            // goes: the run still fails, and `--watch` retries the file.
+            let partial = Output {
+                format: output.format.clone(),
+                path: output.path.as_deref().map(partial_path),
+            };
+            let text = format_output(&result, &partial.format, args.timestamps, args.karaoke);
+            emit_output(args, &partial, &text)?;
+            bail!(
+                "Transcription timed out after {} seconds; the transcript {} is partial",
+                POLL_TIMEOUT.as_secs(),
+                match &partial.path {
+                    Some(path) => format!("saved to {}", path.display()),
+                    None => "above".to_string(),
+                }
+            );
+        }
+    };
 
     // 8. Format and output
-    let output_text = format_output(&result, &args.format, args.timestamps);
-
-    if let Some(output_path) = &args.output {
-        std::fs::write(output_path, &output_text).context("Failed to write output file")?;
-        eprintln!("Transcription saved to: {}", output_path.display());
-    } else {
-        println!("{}", output_text);
-    }
-
-    if args.copy {
-        copy_to_clipboard_sync(&output_text)?;
-        eprintln!("Copied to clipboard");
-    }
-
-    Ok(())
+    let output_text = format_output(&result, &output.format, args.timestamps, args.karaoke);
+    emit_output(args, output, &output_text)
 }
 
 /// Transcribe a file on-device by uploading it to the daemon's `/transcribe`
 /// endpoint, which runs the configured local engine. Returns plain text (no
 /// segment timestamps), so `--format json/srt` degrade to text here.
-async fn transcribe_via_daemon(args: &TranscribeCliArgs) -> Result<()> {
+async fn transcribe_via_daemon(
+    args: &TranscribeCliArgs,
+    file: &Path,
+    output: &Output,
+) -> Result<()> {
     use audetic_core::url::{api_url, paths};
 
     let pb = if args.no_progress {
@@ -128,11 +294,10 @@ async fn transcribe_via_daemon(args: &TranscribeCliArgs) -> Result<()> {
         Some(pb)
     };
 
-    let bytes = tokio::fs::read(&args.file)
+    let bytes = tokio::fs::read(file)
         .await
         .context("Failed to read input file")?;
-    let filename = args
-        .file
+    let filename = file
         .file_name()
         .and_then(|n| n.to_str())
         .unwrap_or("audio.wav")
@@ -140,7 +305,7 @@ async fn transcribe_via_daemon(args: &TranscribeCliArgs) -> Result<()> {
     let part = reqwest::multipart::Part::bytes(bytes).file_name(filename);
     let form = reqwest::multipart::Form::new().part("file", part);
 
-    let response = reqwest::Client::new()
+    let response = crate::client::daemon_client()
         .post(api_url(paths::TRANSCRIBE))
         .multipart(form)
         .send()
@@ -164,17 +329,121 @@ async fn transcribe_via_daemon(args: &TranscribeCliArgs) -> Result<()> {
         text,
         segments: None,
     };
-    let output_text = format_output(&result, &args.format, args.timestamps);
+    let output_text = format_output(&result, &output.format, args.timestamps, args.karaoke);
+    emit_output(args, output, &output_text)
+}
+
+/// Where and how a transcript is written, after applying `[transcribe]`
+/// config defaults to the flags the user omitted.
+#[derive(Debug, PartialEq)]
+struct Output {
+    format: OutputFormat,
+    /// `None` prints to stdout.
+    path: Option<PathBuf>,
+}
+
+/// Resolve the output format and destination. Flags win over
+/// `[transcribe].default_format` / `default_output_dir`; with neither the
+/// transcript is plain text on stdout. A directory gets a file named after
+/// the input with the format's extension. `--stdout` and `-o -` print
+/// regardless of the config.
+fn resolve_output(
+    args: &TranscribeCliArgs,
+    input: &Path,
+    defaults: &TranscribeConfig,
+) -> Result<Output> {
+    let format = match (&args.format, &defaults.default_format) {
+        (Some(format), _) => format.clone(),
+        (None, Some(name)) => OutputFormat::from_str(name, true).map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid [transcribe].default_format '{name}' (expected text, json, or srt)"
+            )
+        })?,
+        (None, None) => OutputFormat::Text,
+    };
+
+    let path = match (&args.output, &args.output_dir, &defaults.default_output_dir) {
+        _ if args.stdout => None,
+        (Some(path), _, _) if path.as_os_str() == "-" => None,
+        (Some(path), _, _) => Some(path.clone()),
+        (None, Some(dir), _) | (None, None, Some(dir)) => Some(dir.join(format!(
+            "{}.{}",
+            output_stem(input),
+            extension_for_format(&format)
+        ))),
+        (None, None, None) => None,
+    };
+
+    Ok(Output { format, path })
+}
+
+/// Where batch and watch mode collect transcripts: `--output-dir`, else
+/// `[transcribe].default_output_dir`. `None` puts each one beside its input.
+fn output_dir<'a>(args: &'a TranscribeCliArgs, defaults: &'a TranscribeConfig) -> Option<&'a Path> {
+    args.output_dir
+        .as_deref()
+        .or(defaults.default_output_dir.as_deref())
+}
+
+/// Base name for a transcript written into an output directory: the input's
+/// file stem, or the last segment of a URL's path.
+fn output_stem(input: &Path) -> String {
+    let text = input.to_string_lossy();
+    let name = match text.split_once("://") {
+        Some((_, rest)) => {
+            let path = rest.split(['?', '#']).next().unwrap_or_default();
+            path.rsplit('/').next().unwrap_or_default().to_string()
+        }
+        None => input
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+    let stem = Path::new(&name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    if stem.is_empty() {
+        "transcript".to_string()
+    } else {
+        stem
+    }
+}
+
+/// Where the transcript for `file` goes when it isn't printed: `output_dir`
+/// when given, otherwise beside the input.
+fn output_path(file: &Path, output_dir: Option<&Path>, extension: &str) -> PathBuf {
+    let dir = output_dir
+        .map(Path::to_path_buf)
+        .or_else(|| file.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    dir.join(format!("{}.{}", output_stem(file), extension))
+}
 
-    if let Some(output_path) = &args.output {
-        std::fs::write(output_path, &output_text).context("Failed to write output file")?;
+fn extension_for_format(format: &OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Text => "txt",
+        OutputFormat::Json => "json",
+        OutputFormat::Srt => "srt",
+    }
+}
+
+/// Write the formatted transcript to its destination and, with `--copy`, the
+/// clipboard.
+fn emit_output(args: &TranscribeCliArgs, output: &Output, text: &str) -> Result<()> {
+    if let Some(output_path) = &output.path {
+        if let Some(dir) = output_path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        std::fs::write(output_path, text).context("Failed to write output file")?;
         eprintln!("Transcription saved to: {}", output_path.display());
     } else {
-        println!("{output_text}");
+        println!("{text}");
     }
 
     if args.copy {
-        copy_to_clipboard_sync(&output_text)?;
+        copy_to_clipboard_sync(text)?;
         eprintln!("Copied to clipboard");
     }
     Ok(())
@@ -202,28 +471,241 @@ fn validate_file(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// The http(s) URL to download when `input` is one, `None` for a local path.
+/// Any other `scheme://` is rejected rather than treated as a filename.
+fn remote_url(input: &Path) -> Result<Option<String>> {
+    let Some(text) = input.to_str() else {
+        return Ok(None);
+    };
+    let Some((scheme, _)) = text.split_once("://") else {
+        return Ok(None);
+    };
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" | "https" => Ok(Some(text.to_string())),
+        other => bail!(
+            "Unsupported URL scheme '{other}://'; only http and https URLs can be transcribed"
+        ),
+    }
+}
+
+/// Supported extension named by the last path segment of `url`, if any.
+fn extension_from_url(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next()?;
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    let (_, segment) = path.split_once('/')?;
+    let (_, ext) = segment.rsplit('/').next()?.rsplit_once('.')?;
+    let ext = ext.to_ascii_lowercase();
+    SUPPORTED_EXTENSIONS.iter().copied().find(|e| *e == ext)
+}
+
+/// Supported extension for a `Content-Type` header value, ignoring
+/// parameters such as `; charset=...`.
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    // Non-canonical spellings servers commonly send.
+    match essence.as_str() {
+        "audio/mp3" => return Some("mp3"),
+        "audio/x-wav" | "audio/wave" => return Some("wav"),
+        "audio/x-m4a" => return Some("m4a"),
+        _ => {}
+    }
+    SUPPORTED_EXTENSIONS
+        .iter()
+        .copied()
+        .find(|ext| mime_type_for_extension(ext) == Some(essence.as_str()))
+}
+
+/// Stream `url` into a temp file named with the inferred extension, failing
+/// once more than `max_bytes` arrive. The caller removes the returned file.
+async fn download_to_temp(
+    client: &reqwest::Client,
+    url: &str,
+    max_bytes: u64,
+    pb: Option<&ProgressBar>,
+) -> Result<PathBuf> {
+    if let Some(pb) = pb {
+        pb.set_message("Downloading...");
+    }
+
+    let mut response = interruptible(async {
+        client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {url}"))
+    })
+    .await?;
+    if !response.status().is_success() {
+        bail!("Download failed: HTTP {} from {}", response.status(), url);
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let ext = extension_from_url(response.url().as_str())
+        .or_else(|| extension_from_url(url))
+        .or_else(|| content_type.and_then(extension_for_content_type))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Can't tell the format of {} (Content-Type: {}).\nSupported formats: {}",
+                url,
+                content_type.unwrap_or("<none>"),
+                SUPPORTED_EXTENSIONS.join(", ")
+            )
+        })?;
+
+    let total = response.content_length();
+    if let Some(total) = total.filter(|total| *total > max_bytes) {
+        bail!(
+            "Remote file is {:.1}MB, over the {:.0}MB download limit",
+            total as f64 / 1_000_000.0,
+            max_bytes as f64 / 1_000_000.0
+        );
+    }
+
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let path = std::env::temp_dir().join(format!(
+        "audetic-download-{}-{}.{}",
+        std::process::id(),
+        nanos,
+        ext
+    ));
+
+    let mut file = tokio::fs::File::create(&path)
+        .await
+        .context("Failed to create download temp file")?;
+    let mut received: u64 = 0;
+    let streamed: Result<()> = interruptible(async {
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .with_context(|| format!("Download of {url} was interrupted"))?
+        {
+            received += chunk.len() as u64;
+            if received > max_bytes {
+                bail!(
+                    "Remote file exceeds the {:.0}MB download limit",
+                    max_bytes as f64 / 1_000_000.0
+                );
+            }
+            file.write_all(&chunk)
+                .await
+                .context("Failed to write download temp file")?;
+
+            if let Some(pb) = pb {
+                match total {
+                    Some(total) if total > 0 => pb.set_position(received * 100 / total),
+                    _ => pb.set_message(format!(
+                        "Downloading... {:.1}MB",
+                        received as f64 / 1_000_000.0
+                    )),
+                }
+            }
+        }
+        file.flush()
+            .await
+            .context("Failed to write download temp file")
+    })
+    .await;
+
+    if let Err(e) = streamed {
+        drop(file);
+        cleanup_temp_file(&path);
+        return Err(e);
+    }
+    Ok(path)
+}
+
+/// Resolve the file to transcribe from the clipboard. `read` supplies the
+/// clipboard text so tests can stand in for the system clipboard.
+fn file_from_clipboard(read: impl FnOnce() -> Result<String>) -> Result<PathBuf> {
+    let text = read().context("Failed to read the clipboard")?;
+    let path = path_from_clipboard_text(&text)?;
+    validate_file(&path).with_context(|| {
+        format!(
+            "Clipboard does not point to a usable audio/video file: {}",
+            path.display()
+        )
+    })?;
+    Ok(path)
+}
+
+/// Extract a file path from clipboard text: a plain absolute path or a
+/// `file://` URI (percent-encoded, as file managers copy them). Only the
+/// first entry is used; GNOME's leading `copy`/`cut` line is skipped.
+fn path_from_clipboard_text(text: &str) -> Result<PathBuf> {
+    let entry = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && *line != "copy" && *line != "cut")
+        .ok_or_else(|| anyhow::anyhow!("Clipboard is empty; copy an audio or video file first"))?;
+
+    let Some(rest) = entry.strip_prefix("file://") else {
+        let path = PathBuf::from(entry);
+        if !path.is_absolute() {
+            bail!(
+                "Clipboard does not contain a file path or file:// URI: {}",
+                entry
+            );
+        }
+        return Ok(path);
+    };
+
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    if !rest.starts_with('/') {
+        bail!("Unsupported file URI (remote host?): {}", entry);
+    }
+
+    Ok(PathBuf::from(percent_decode(rest)?))
+}
+
+/// Decode `%XX` escapes in a URI path.
+fn percent_decode(input: &str) -> Result<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = input
+                .get(i + 1..i + 3)
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+                .ok_or_else(|| anyhow::anyhow!("Invalid percent-encoding in URI: {}", input))?;
+            decoded.push(hex);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).context("File URI is not valid UTF-8")
+}
+
 /// Prepare file for upload, compressing if needed.
 ///
 /// Wraps `compression::prepare_for_upload` with CLI-friendly progress output.
 /// Returns (file_to_upload, Option<temp_file_path>); the temp file should be
-/// cleaned up after upload when present.
+/// cleaned up after upload when present. Files under `min_size_bytes` are
+/// uploaded as-is.
 fn prepare_file_for_upload(
     path: &Path,
     skip_compression: bool,
+    min_size_bytes: u64,
 ) -> Result<(PathBuf, Option<PathBuf>)> {
-    let needs_compression = !skip_compression
-        && path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| !matches!(e.to_ascii_lowercase().as_str(), "mp3" | "opus"))
-            .unwrap_or(true);
-
-    if needs_compression {
+    if needs_compression(path, skip_compression, min_size_bytes)? {
         let size_mb = get_file_size(path)? as f64 / 1_000_000.0;
         eprintln!("Compressing to mp3 for upload ({:.1}MB)...", size_mb);
     }
 
-    let (upload_path, temp) = prepare_for_upload(path, skip_compression)?;
+    let (upload_path, temp) = prepare_for_upload(path, skip_compression, min_size_bytes)?;
 
     if let Some(temp_path) = &temp {
         let compressed_size_mb = get_file_size(temp_path)? as f64 / 1_000_000.0;
@@ -233,6 +715,21 @@ fn prepare_file_for_upload(
     Ok((upload_path, temp))
 }
 
+/// The jobs API to submit to: `--api-url`, else one derived from
+/// `[whisper].api_endpoint`, else the hosted default.
+fn jobs_api_url(args: &TranscribeCliArgs, config: &Config) -> String {
+    args.api_url
+        .clone()
+        .or_else(|| {
+            config
+                .whisper
+                .api_endpoint
+                .as_ref()
+                .map(|e| derive_jobs_url(e))
+        })
+        .unwrap_or_else(|| DEFAULT_API_URL.to_string())
+}
+
 /// Derive the jobs URL from a transcriptions endpoint.
 fn derive_jobs_url(endpoint: &str) -> String {
     // If endpoint ends with /transcriptions, replace with /jobs
@@ -259,13 +756,64 @@ fn create_progress_bar() -> ProgressBar {
     pb
 }
 
-/// Poll the job status until completion or failure.
+/// How polling a job ended.
+#[derive(Debug)]
+enum PollOutcome {
+    /// The job reached `completed` or `failed`.
+    Finished(Job),
+    /// Polling timed out, but the server had part of the transcript.
+    Partial(TranscriptionResult),
+}
+
+/// Wait between job status polls: starts at `initial` and doubles up to
+/// `max` while nothing changes, so short jobs finish promptly and long ones
+/// aren't polled thousands of times. Progress resets it to `initial`.
+#[derive(Debug, Clone)]
+struct PollBackoff {
+    initial: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl PollBackoff {
+    fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            current: initial,
+        }
+    }
+
+    /// The wait before the next poll, cut short to `remaining` so the last
+    /// poll lands on the deadline rather than past it.
+    fn next_delay(&mut self, remaining: Duration) -> Duration {
+        let delay = self.current.min(remaining);
+        self.current = (self.current * 2).min(self.max);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.current = self.initial;
+    }
+}
+
+/// Poll the job status until completion or failure. If the job is still
+/// running after `timeout`, salvage whatever transcript the server has
+/// instead of discarding hours of work.
+///
+/// Polls slow down per `backoff` while the job reports the same progress;
+/// the progress bar's steady tick keeps it animated between polls.
 async fn poll_until_complete(
     client: &JobsClient,
     job_id: &str,
     pb: Option<&ProgressBar>,
-) -> Result<Job> {
-    for _ in 0..MAX_POLL_ATTEMPTS {
+    mut backoff: PollBackoff,
+    timeout: Duration,
+) -> Result<PollOutcome> {
+    let deadline = Instant::now() + timeout;
+    let mut last_progress = None;
+
+    loop {
         let status = client.get_status(job_id).await?;
 
         if let Some(pb) = pb {
@@ -283,27 +831,88 @@ async fn poll_until_complete(
             }
         }
 
+        let progress = (
+            status.status.clone(),
+            status.progress,
+            status.progress_message.clone(),
+        );
+        if last_progress.as_ref().is_some_and(|last| *last != progress) {
+            backoff.reset();
+        }
+        last_progress = Some(progress);
+
         match status.status.as_str() {
             status::COMPLETED | status::FAILED => {
-                return client.get_job(job_id).await;
+                return client.get_job(job_id).await.map(PollOutcome::Finished);
             }
             status::CANCELLED => {
                 bail!("Job was cancelled");
             }
-            _ => {
-                sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
-            }
+            _ => {}
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
         }
+        sleep(backoff.next_delay(remaining)).await;
     }
 
-    bail!(
-        "Transcription timed out after {} seconds",
-        MAX_POLL_ATTEMPTS
-    );
+    match salvage_partial(client, job_id).await {
+        Some(partial) => Ok(PollOutcome::Partial(partial)),
+        None => bail!(
+            "Transcription timed out after {} seconds",
+            timeout.as_secs()
+        ),
+    }
+}
+
+/// Cancel an interrupted job so the server stops working on it, saying
+/// whether that worked.
+async fn cancel_remote_job(client: &JobsClient, job_id: &str) {
+    match client.cancel_job(job_id).await {
+        Ok(()) => eprintln!("Cancelled job {job_id} on the server."),
+        Err(e) => {
+            eprintln!("Could not cancel job {job_id}: {e:#}. It may keep running on the server.")
+        }
+    }
+}
+
+/// Last-chance fetch after a poll timeout: the job's partial `result`, if
+/// the server has transcribed any of it. Progress messages aren't
+/// transcript text and are never passed off as one.
+async fn salvage_partial(client: &JobsClient, job_id: &str) -> Option<TranscriptionResult> {
+    match client.get_job(job_id).await {
+        Ok(Job {
+            result: Some(result),
+            ..
+        }) if !result.text.trim().is_empty() => Some(result),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::debug!("Final job fetch after timeout failed: {e:#}");
+            None
+        }
+    }
+}
+
+/// Where a partial transcript goes instead of `path`: `talk.txt` becomes
+/// `talk.partial.txt`.
+fn partial_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}.partial.{}", ext.to_string_lossy()),
+        None => format!("{stem}.partial"),
+    };
+    path.with_file_name(name)
 }
 
 /// Format the transcription result according to the requested format.
-fn format_output(result: &TranscriptionResult, format: &OutputFormat, timestamps: bool) -> String {
+fn format_output(
+    result: &TranscriptionResult,
+    format: &OutputFormat,
+    timestamps: bool,
+    karaoke: bool,
+) -> String {
     match format {
         OutputFormat::Text => {
             if timestamps {
@@ -315,7 +924,13 @@ fn format_output(result: &TranscriptionResult, format: &OutputFormat, timestamps
         OutputFormat::Json => {
             serde_json::to_string_pretty(result).unwrap_or_else(|_| result.text.clone())
         }
-        OutputFormat::Srt => format_as_srt(result),
+        OutputFormat::Srt => {
+            if karaoke {
+                format_as_karaoke_srt(result)
+            } else {
+                format_as_srt(result)
+            }
+        }
     }
 }
 
@@ -333,38 +948,107 @@ fn format_text_with_timestamps(result: &TranscriptionResult) -> String {
 
 /// Format result as SRT subtitles.
 fn format_as_srt(result: &TranscriptionResult) -> String {
-    match &result.segments {
-        Some(segments) if !segments.is_empty() => segments
-            .iter()
-            .enumerate()
-            .map(|(i, s)| {
-                format!(
-                    "{}\n{} --> {}\n{}\n",
-                    i + 1,
-                    format_srt_time(s.start),
-                    format_srt_time(s.end),
-                    s.text.trim()
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n"),
-        _ => format!("1\n00:00:00,000 --> 00:00:00,000\n{}\n", result.text),
-    }
+    subtitles::format_srt(result.segments.as_deref(), &result.text)
 }
 
-/// Format seconds as SRT timestamp (HH:MM:SS,mmm).
-fn format_srt_time(seconds: f64) -> String {
-    let hours = (seconds / 3600.0) as u32;
-    let minutes = ((seconds % 3600.0) / 60.0) as u32;
-    let secs = (seconds % 60.0) as u32;
-    let millis = ((seconds % 1.0) * 1000.0) as u32;
-    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, secs, millis)
+/// Format result as SRT with one cue per word: each cue shows the whole
+/// segment with the active word underlined. Segments without word timings
+/// keep a single cue.
+fn format_as_karaoke_srt(result: &TranscriptionResult) -> String {
+    let Some(segments) = result.segments.as_ref().filter(|s| !s.is_empty()) else {
+        return format_as_srt(result);
+    };
+
+    let mut cues = Vec::new();
+    for segment in segments {
+        match segment.words.as_ref().filter(|w| !w.is_empty()) {
+            Some(words) => {
+                for (i, word) in words.iter().enumerate() {
+                    let line = words
+                        .iter()
+                        .enumerate()
+                        .map(|(j, w)| {
+                            if i == j {
+                                format!("<u>{}</u>", w.text.trim())
+                            } else {
+                                w.text.trim().to_string()
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    cues.push((word.start, word.end, line));
+                }
+            }
+            None => cues.push((segment.start, segment.end, segment.text.trim().to_string())),
+        }
+    }
+
+    subtitles::format_srt_cues(cues)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use audetic_core::jobs_client::{Segment, Word};
     use std::path::PathBuf;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// Accept one connection and answer it with `body` as `content_type`.
+    async fn serve_one(listener: TcpListener, content_type: &'static str, body: &'static [u8]) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 4096];
+        let _ = stream.read(&mut buf).await.unwrap();
+        let head = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            content_type,
+            body.len()
+        );
+        // The client may hang up early (e.g. over the size cap); ignore that.
+        let _ = stream.write_all(head.as_bytes()).await;
+        let _ = stream.write_all(body).await;
+    }
+
+    /// Answer `requests` connections like the jobs API: `/status` polls get
+    /// `status_body`, anything else gets `job_body`.
+    async fn serve_jobs_api(
+        listener: TcpListener,
+        requests: usize,
+        status_body: &'static str,
+        job_body: &'static str,
+    ) {
+        for _ in 0..requests {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let body = if path.ends_with("/status") {
+                status_body
+            } else {
+                job_body
+            };
+            let head = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(head.as_bytes()).await.unwrap();
+            stream.write_all(body.as_bytes()).await.unwrap();
+        }
+    }
+
+    fn parse_args(extra: &[&str]) -> TranscribeCliArgs {
+        use crate::args::{Cli, CliCommand};
+        use clap::Parser;
+
+        let argv = ["audetic", "transcribe", "talk.mp3"].iter().chain(extra);
+        match Cli::try_parse_from(argv).unwrap().command {
+            Some(CliCommand::Transcribe(args)) => args,
+            other => panic!("expected transcribe args, got {other:?}"),
+        }
+    }
+
+    const RUNNING_STATUS: &str = r#"{"success":true,"jobId":"j1","status":"transcribing","progress":90,"progressMessage":"Transcribing chunk 9 of 10"}"#;
 
     #[test]
     fn test_validate_file_supported_audio() {
@@ -420,21 +1104,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_format_srt_time_zero() {
-        assert_eq!(format_srt_time(0.0), "00:00:00,000");
-    }
-
-    #[test]
-    fn test_format_srt_time_minutes() {
-        assert_eq!(format_srt_time(61.5), "00:01:01,500");
-    }
-
-    #[test]
-    fn test_format_srt_time_hours() {
-        assert_eq!(format_srt_time(3661.123), "01:01:01,123");
-    }
-
     #[test]
     fn test_format_output_text() {
         let result = TranscriptionResult {
@@ -442,7 +1111,7 @@ mod tests {
             segments: None,
         };
         assert_eq!(
-            format_output(&result, &OutputFormat::Text, false),
+            format_output(&result, &OutputFormat::Text, false, false),
             "Hello world"
         );
     }
@@ -452,7 +1121,7 @@ mod tests {
         let path = PathBuf::from("/tmp/test_skip_compress.opus");
         std::fs::write(&path, b"fake opus data").unwrap();
 
-        let (upload_path, temp_file) = prepare_file_for_upload(&path, false).unwrap();
+        let (upload_path, temp_file) = prepare_file_for_upload(&path, false, 0).unwrap();
 
         assert_eq!(upload_path, path);
         assert!(temp_file.is_none());
@@ -465,7 +1134,7 @@ mod tests {
         let path = PathBuf::from("/tmp/test_no_compress_flag.wav");
         std::fs::write(&path, b"fake wav data").unwrap();
 
-        let (upload_path, temp_file) = prepare_file_for_upload(&path, true).unwrap();
+        let (upload_path, temp_file) = prepare_file_for_upload(&path, true, 0).unwrap();
 
         assert_eq!(upload_path, path);
         assert!(temp_file.is_none());
@@ -473,14 +1142,382 @@ mod tests {
         std::fs::remove_file(&path).unwrap();
     }
 
+    #[test]
+    fn test_prepare_small_wav_skips_compression() {
+        let path = std::env::temp_dir().join("test_small_wav_threshold.wav");
+        std::fs::write(&path, b"fake wav data").unwrap();
+
+        let (upload_path, temp_file) = prepare_file_for_upload(&path, false, 1024).unwrap();
+
+        assert_eq!(upload_path, path);
+        assert!(temp_file.is_none());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_prepare_large_wav_compresses() {
+        let path = std::env::temp_dir().join("test_large_wav_threshold.wav");
+        std::fs::write(&path, vec![0u8; 4096]).unwrap();
+
+        // Not real audio, so ffmpeg (when present) rejects it — either way
+        // the file went to compression rather than being uploaded as-is.
+        match prepare_file_for_upload(&path, false, 1024) {
+            Ok((upload_path, temp_file)) => {
+                assert_ne!(upload_path, path);
+                cleanup_temp_file(&temp_file.unwrap());
+            }
+            Err(e) => assert!(e.to_string().contains("FFmpeg")),
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_format_output_json() {
         let result = TranscriptionResult {
             text: "Hello".to_string(),
             segments: None,
         };
-        let output = format_output(&result, &OutputFormat::Json, false);
+        let output = format_output(&result, &OutputFormat::Json, false, false);
         assert!(output.contains("\"text\""));
         assert!(output.contains("Hello"));
     }
+
+    #[test]
+    fn test_format_karaoke_srt_word_cues() {
+        let result = TranscriptionResult {
+            text: "Hello world".to_string(),
+            segments: Some(vec![Segment {
+                start: 0.0,
+                end: 1.0,
+                text: "Hello world".to_string(),
+                words: Some(vec![
+                    Word {
+                        start: 0.0,
+                        end: 0.4,
+                        text: "Hello".to_string(),
+                    },
+                    Word {
+                        start: 0.5,
+                        end: 1.0,
+                        text: "world".to_string(),
+                    },
+                ]),
+            }]),
+        };
+
+        let output = format_output(&result, &OutputFormat::Srt, false, true);
+        assert_eq!(
+            output,
+            "1\n00:00:00,000 --> 00:00:00,400\n<u>Hello</u> world\n\n\
+             2\n00:00:00,500 --> 00:00:01,000\nHello <u>world</u>\n"
+        );
+    }
+
+    #[test]
+    fn test_path_from_clipboard_file_uri() {
+        assert_eq!(
+            path_from_clipboard_text("file:///home/me/My%20Recording.wav\n").unwrap(),
+            PathBuf::from("/home/me/My Recording.wav")
+        );
+        assert_eq!(
+            path_from_clipboard_text("file://localhost/tmp/a.mp3").unwrap(),
+            PathBuf::from("/tmp/a.mp3")
+        );
+    }
+
+    #[test]
+    fn test_path_from_clipboard_gnome_copy_format() {
+        let text = "copy\nfile:///tmp/first.wav\nfile:///tmp/second.wav";
+        assert_eq!(
+            path_from_clipboard_text(text).unwrap(),
+            PathBuf::from("/tmp/first.wav")
+        );
+    }
+
+    #[test]
+    fn test_path_from_clipboard_rejects_non_paths() {
+        assert!(path_from_clipboard_text("").is_err());
+        assert!(path_from_clipboard_text("hello world").is_err());
+        assert!(path_from_clipboard_text("file://server/share/a.wav").is_err());
+        assert!(path_from_clipboard_text("file:///tmp/bad%zz.wav").is_err());
+    }
+
+    #[test]
+    fn test_file_from_clipboard_validates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test from clipboard.wav");
+        std::fs::write(&path, b"fake wav data").unwrap();
+
+        let uri = format!(
+            "file://{}/test%20from%20clipboard.wav",
+            dir.path().display()
+        );
+        let resolved = file_from_clipboard(|| Ok(uri)).unwrap();
+        assert_eq!(resolved, path);
+
+        let err = file_from_clipboard(|| Ok("/tmp/clipboard_notes.txt".to_string())).unwrap_err();
+        assert!(err.to_string().contains("usable audio/video file"));
+    }
+
+    #[test]
+    fn test_remote_url_detection() {
+        assert_eq!(
+            remote_url(Path::new("https://example.com/episode.mp3")).unwrap(),
+            Some("https://example.com/episode.mp3".to_string())
+        );
+        assert!(remote_url(Path::new("HTTP://example.com/a.wav"))
+            .unwrap()
+            .is_some());
+        assert_eq!(remote_url(Path::new("/tmp/recording.wav")).unwrap(), None);
+        assert_eq!(remote_url(Path::new("notes/http-talk.mp3")).unwrap(), None);
+        assert!(remote_url(Path::new("ftp://example.com/a.mp3")).is_err());
+        assert!(remote_url(Path::new("file:///tmp/a.mp3")).is_err());
+    }
+
+    #[test]
+    fn test_extension_from_url() {
+        assert_eq!(
+            extension_from_url("https://cdn.example.com/pod/Episode-12.MP3?token=abc#t=10"),
+            Some("mp3")
+        );
+        assert_eq!(
+            extension_from_url("https://example.com/watch?v=x.mp4"),
+            None
+        );
+        assert_eq!(extension_from_url("https://example.com/download"), None);
+        assert_eq!(extension_from_url("https://example.com"), None);
+    }
+
+    #[test]
+    fn test_extension_for_content_type() {
+        assert_eq!(extension_for_content_type("audio/mpeg"), Some("mp3"));
+        assert_eq!(
+            extension_for_content_type("Audio/OGG; codecs=opus"),
+            Some("ogg")
+        );
+        assert_eq!(extension_for_content_type("audio/x-wav"), Some("wav"));
+        assert_eq!(extension_for_content_type("text/html"), None);
+    }
+
+    #[tokio::test]
+    async fn test_download_infers_format_from_content_type() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_one(listener, "audio/mpeg", b"fake mp3 data"));
+
+        let url = format!("http://{addr}/download?id=7");
+        let path = download_to_temp(&reqwest::Client::new(), &url, 1024, None)
+            .await
+            .unwrap();
+        server.await.unwrap();
+
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("mp3"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"fake mp3 data");
+        cleanup_temp_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_download_rejects_oversized_file() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_one(listener, "audio/wav", b"0123456789"));
+
+        let url = format!("http://{addr}/big.wav");
+        let err = download_to_temp(&reqwest::Client::new(), &url, 4, None)
+            .await
+            .unwrap_err();
+        server.await.unwrap();
+
+        assert!(err.to_string().contains("download limit"));
+    }
+
+    fn no_wait() -> PollBackoff {
+        PollBackoff::new(Duration::ZERO, Duration::ZERO)
+    }
+
+    #[test]
+    fn test_poll_backoff_grows_and_caps() {
+        let mut backoff = PollBackoff::new(POLL_INITIAL_INTERVAL, POLL_MAX_INTERVAL);
+        let delays: Vec<u64> = (0..7)
+            .map(|_| backoff.next_delay(POLL_TIMEOUT).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, [500, 1000, 2000, 4000, 5000, 5000, 5000]);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(POLL_TIMEOUT), POLL_INITIAL_INTERVAL);
+    }
+
+    #[test]
+    fn test_poll_backoff_respects_the_timeout() {
+        let mut backoff = PollBackoff::new(POLL_INITIAL_INTERVAL, POLL_MAX_INTERVAL);
+        let mut elapsed = Duration::ZERO;
+        let mut polls = 1;
+        while elapsed < POLL_TIMEOUT {
+            elapsed += backoff.next_delay(POLL_TIMEOUT - elapsed);
+            polls += 1;
+        }
+        assert_eq!(elapsed, POLL_TIMEOUT);
+        // A fixed 1s interval polled 1800 times over the same half hour.
+        assert!(polls < 400, "{polls} polls");
+
+        // The last wait is cut short to land on the deadline.
+        let mut backoff = PollBackoff::new(POLL_MAX_INTERVAL, POLL_MAX_INTERVAL);
+        assert_eq!(
+            backoff.next_delay(Duration::from_millis(1200)),
+            Duration::from_millis(1200)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_poll_returns_partial_result() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let job = r#"{"success":true,"job":{"id":"j1","status":"transcribing","progress":90,"result":{"text":"most of the talk","segments":null},"error":null,"createdAt":"2024-01-01T00:00:00Z","completedAt":null}}"#;
+        let server = tokio::spawn(serve_jobs_api(listener, 2, RUNNING_STATUS, job));
+
+        let client = JobsClient::new(&format!("http://{addr}/jobs"));
+        let outcome = poll_until_complete(&client, "j1", None, no_wait(), Duration::ZERO)
+            .await
+            .unwrap();
+        server.await.unwrap();
+
+        match outcome {
+            PollOutcome::Partial(result) => assert_eq!(result.text, "most of the talk"),
+            other => panic!("expected a partial result, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_poll_never_uses_progress_message_as_transcript() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let job = r#"{"success":true,"job":{"id":"j1","status":"transcribing","progress":90,"result":null,"error":null,"createdAt":"2024-01-01T00:00:00Z","completedAt":null}}"#;
+        let server = tokio::spawn(serve_jobs_api(listener, 2, RUNNING_STATUS, job));
+
+        let client = JobsClient::new(&format!("http://{addr}/jobs"));
+        let err = poll_until_complete(&client, "j1", None, no_wait(), Duration::ZERO)
+            .await
+            .unwrap_err();
+        server.await.unwrap();
+
+        assert!(err.to_string().contains("timed out"), "{err}");
+    }
+
+    #[test]
+    fn test_interrupted_is_found_under_context() {
+        let err = anyhow::Error::new(Interrupted).context("Failed to transcribe talk.mp3");
+        assert!(is_interrupted(&err));
+        assert!(!is_interrupted(&anyhow::anyhow!(
+            "Transcription interrupted"
+        )));
+    }
+
+    #[test]
+    fn test_partial_transcripts_get_their_own_file() {
+        assert_eq!(
+            partial_path(Path::new("/subs/talk.srt")),
+            PathBuf::from("/subs/talk.partial.srt")
+        );
+        assert_eq!(
+            partial_path(Path::new("notes")),
+            PathBuf::from("notes.partial")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_poll_without_partial_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let status = r#"{"success":true,"jobId":"j1","status":"pending","progress":0,"progressMessage":null}"#;
+        let job = r#"{"success":true,"job":{"id":"j1","status":"pending","progress":0,"result":null,"error":null,"createdAt":"2024-01-01T00:00:00Z","completedAt":null}}"#;
+        let server = tokio::spawn(serve_jobs_api(listener, 2, status, job));
+
+        let client = JobsClient::new(&format!("http://{addr}/jobs"));
+        let err = poll_until_complete(&client, "j1", None, no_wait(), Duration::ZERO)
+            .await
+            .unwrap_err();
+        server.await.unwrap();
+
+        assert!(err.to_string().contains("timed out"));
+    }
+
+    #[test]
+    fn test_output_uses_config_defaults_when_flags_omitted() {
+        let defaults = TranscribeConfig {
+            default_format: Some("srt".to_string()),
+            default_output_dir: Some(PathBuf::from("subs")),
+        };
+        let output =
+            resolve_output(&parse_args(&[]), Path::new("/videos/talk.mp4"), &defaults).unwrap();
+        assert_eq!(output.format, OutputFormat::Srt);
+        assert_eq!(output.path, Some(PathBuf::from("subs/talk.srt")));
+    }
+
+    #[test]
+    fn test_output_flags_override_config_defaults() {
+        let defaults = TranscribeConfig {
+            default_format: Some("srt".to_string()),
+            default_output_dir: Some(PathBuf::from("subs")),
+        };
+        let args = parse_args(&["--format", "json", "--output-dir", "out"]);
+        let output = resolve_output(&args, Path::new("talk.mp3"), &defaults).unwrap();
+        assert_eq!(output.format, OutputFormat::Json);
+        assert_eq!(output.path, Some(PathBuf::from("out/talk.json")));
+
+        let args = parse_args(&["-o", "talk.txt"]);
+        let output = resolve_output(&args, Path::new("talk.mp3"), &defaults).unwrap();
+        assert_eq!(output.format, OutputFormat::Srt);
+        assert_eq!(output.path, Some(PathBuf::from("talk.txt")));
+    }
+
+    #[test]
+    fn test_output_stdout_overrides_default_output_dir() {
+        let defaults = TranscribeConfig {
+            default_format: None,
+            default_output_dir: Some(PathBuf::from("subs")),
+        };
+        for flags in [&["--stdout"][..], &["-o", "-"][..]] {
+            let output =
+                resolve_output(&parse_args(flags), Path::new("talk.mp3"), &defaults).unwrap();
+            assert_eq!(output.path, None, "{flags:?}");
+        }
+    }
+
+    #[test]
+    fn test_output_without_config_is_text_on_stdout() {
+        let output = resolve_output(
+            &parse_args(&[]),
+            Path::new("talk.mp3"),
+            &TranscribeConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            Output {
+                format: OutputFormat::Text,
+                path: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_output_rejects_invalid_config_format() {
+        let defaults = TranscribeConfig {
+            default_format: Some("docx".to_string()),
+            default_output_dir: None,
+        };
+        let err = resolve_output(&parse_args(&[]), Path::new("talk.mp3"), &defaults).unwrap_err();
+        assert!(err.to_string().contains("default_format"));
+    }
+
+    #[test]
+    fn test_output_stem_from_url() {
+        assert_eq!(
+            output_stem(Path::new("https://example.com/ep/42.mp3?dl=1")),
+            "42"
+        );
+        assert_eq!(output_stem(Path::new("https://example.com/")), "transcript");
+    }
 }
@@ -0,0 +1,261 @@
+//! `audetic transcribe a.m4a b.m4a ...` and `--glob <pattern>`: transcribe
+//! several files in one run.
+//!
+//! Inputs go through the regular single-file pipeline one at a time, each
+//! transcript written next to its input (or into `--output-dir` /
+//! `[transcribe].default_output_dir`). Inputs whose transcripts would land on
+//! the same path get numbered names instead of overwriting each other. A
+//! failed file is reported and the run moves on; the closing summary lists
+//! what succeeded and what didn't, and the command fails if anything did.
+//! Ctrl-C stops the whole run.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::{
+    extension_for_format, is_interrupted, output_dir, output_path, output_stem, resolve_output,
+    transcribe_input, Output,
+};
+use crate::args::TranscribeCliArgs;
+use audetic_core::config::Config;
+use audetic_core::jobs_client::mime_type_for_extension;
+
+/// Transcribe every file named on the command line or matched by `--glob`.
+pub async fn run(args: &TranscribeCliArgs, config: &Config) -> Result<()> {
+    if args.output.is_some() || args.stdout || args.copy {
+        bail!("--output, --stdout and --copy take a single file; use --output-dir to collect the transcripts of several");
+    }
+
+    let mut inputs = args.files.clone();
+    if let Some(pattern) = &args.glob {
+        inputs.extend(expand_glob(pattern)?);
+    }
+
+    // Resolved once for the format; each file gets its own destination.
+    let format = resolve_output(args, Path::new(""), &config.transcribe)?.format;
+    let output_dir = output_dir(args, &config.transcribe);
+    let destinations = destinations(&inputs, output_dir, extension_for_format(&format));
+
+    let total = inputs.len();
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+    for (index, (input, path)) in inputs.iter().zip(destinations).enumerate() {
+        eprintln!("File {} of {}: {}", index + 1, total, input.display());
+        let output = Output {
+            format: format.clone(),
+            path: Some(path),
+        };
+        match transcribe_input(args, input, &output, config).await {
+            Ok(()) => succeeded.push(input),
+            Err(e) if is_interrupted(&e) => {
+                eprintln!(
+                    "Stopped with {} of {} files transcribed",
+                    succeeded.len(),
+                    total
+                );
+                return Err(e);
+            }
+            Err(e) => {
+                eprintln!("Failed to transcribe {}: {e:#}", input.display());
+                failed.push((input, e));
+            }
+        }
+    }
+
+    eprintln!();
+    eprintln!("Transcribed {} of {} files", succeeded.len(), total);
+    for input in &succeeded {
+        eprintln!("  ok      {}", input.display());
+    }
+    for (input, e) in &failed {
+        eprintln!("  failed  {}: {e}", input.display());
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        bail!("{} of {} files failed to transcribe", failed.len(), total)
+    }
+}
+
+/// Supported audio/video files matching `pattern`, in path order. Anything
+/// else it catches (earlier transcripts, notes) is left out.
+fn expand_glob(pattern: &str) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = glob::glob(pattern)
+        .with_context(|| format!("Invalid --glob pattern '{pattern}'"))?
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file() && is_supported(path))
+        .collect();
+    files.sort();
+    if files.is_empty() {
+        bail!("No supported audio or video files match '{pattern}'");
+    }
+    Ok(files)
+}
+
+fn is_supported(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| mime_type_for_extension(&ext.to_lowercase()).is_some())
+}
+
+/// Where `input`'s transcript goes. A URL has no directory to sit beside, so
+/// without `--output-dir` its transcript lands in the current one.
+fn destination(input: &Path, output_dir: Option<&Path>, extension: &str) -> PathBuf {
+    let is_url = input.to_string_lossy().contains("://");
+    let output_dir = output_dir.or(is_url.then_some(Path::new(".")));
+    output_path(input, output_dir, extension)
+}
+
+/// [`destination`] for every input, numbering later ones that would land on
+/// a path already taken (`talk.txt`, `talk-2.txt`, ...), as happens for
+/// same-named files from different directories collected in one output dir.
+fn destinations(inputs: &[PathBuf], output_dir: Option<&Path>, extension: &str) -> Vec<PathBuf> {
+    let mut taken = HashSet::new();
+    inputs
+        .iter()
+        .map(|input| {
+            let first = destination(input, output_dir, extension);
+            let mut path = first.clone();
+            let mut n = 2;
+            while !taken.insert(path.clone()) {
+                path = first.with_file_name(format!("{}-{n}.{extension}", output_stem(input)));
+                n += 1;
+            }
+            if path != first {
+                eprintln!(
+                    "{} would overwrite another transcript; writing {} instead",
+                    first.display(),
+                    path.display()
+                );
+            }
+            path
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{Cli, CliCommand};
+    use clap::Parser;
+
+    fn parse(argv: &[&str]) -> Result<TranscribeCliArgs, clap::Error> {
+        let argv = ["audetic", "transcribe"].iter().chain(argv);
+        match Cli::try_parse_from(argv)?.command {
+            Some(CliCommand::Transcribe(args)) => Ok(args),
+            other => panic!("expected transcribe args, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn several_files_or_a_glob_parse() {
+        let args = parse(&["a.mp3", "b.wav", "--output-dir", "out"]).unwrap();
+        assert_eq!(
+            args.files,
+            vec![PathBuf::from("a.mp3"), PathBuf::from("b.wav")]
+        );
+
+        let args = parse(&["--glob", "talks/*.m4a"]).unwrap();
+        assert!(args.files.is_empty());
+        assert_eq!(args.glob.as_deref(), Some("talks/*.m4a"));
+
+        assert!(parse(&["--glob", "*.m4a", "--output", "all.txt"]).is_err());
+        assert!(parse(&["--glob", "*.m4a", "--from-clipboard"]).is_err());
+    }
+
+    #[test]
+    fn glob_keeps_supported_files_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        for name in ["b.m4a", "a.wav", "a.txt", "notes.md"] {
+            std::fs::write(dir.join(name), b"x").unwrap();
+        }
+        std::fs::create_dir_all(dir.join("nested.mp3")).unwrap();
+
+        let pattern = format!("{}/*", dir.display());
+        assert_eq!(
+            expand_glob(&pattern).unwrap(),
+            vec![dir.join("a.wav"), dir.join("b.m4a")]
+        );
+
+        let err = expand_glob(&format!("{}/*.flac", dir.display())).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("No supported audio or video files"));
+    }
+
+    #[test]
+    fn transcripts_go_beside_inputs_and_urls_into_the_current_dir() {
+        assert_eq!(
+            destination(Path::new("/rec/standup.m4a"), None, "txt"),
+            PathBuf::from("/rec/standup.txt")
+        );
+        assert_eq!(
+            destination(
+                Path::new("/rec/standup.m4a"),
+                Some(Path::new("/out")),
+                "srt"
+            ),
+            PathBuf::from("/out/standup.srt")
+        );
+        assert_eq!(
+            destination(Path::new("https://example.com/ep/42.mp3?dl=1"), None, "txt"),
+            PathBuf::from("./42.txt")
+        );
+    }
+
+    #[test]
+    fn same_named_inputs_get_distinct_transcripts() {
+        let inputs = [
+            PathBuf::from("/mon/standup.m4a"),
+            PathBuf::from("/tue/standup.m4a"),
+            PathBuf::from("/tue/standup.wav"),
+            PathBuf::from("/tue/retro.m4a"),
+        ];
+        assert_eq!(
+            destinations(&inputs, Some(Path::new("/out")), "txt"),
+            vec![
+                PathBuf::from("/out/standup.txt"),
+                PathBuf::from("/out/standup-2.txt"),
+                PathBuf::from("/out/standup-3.txt"),
+                PathBuf::from("/out/retro.txt"),
+            ]
+        );
+        // Beside their inputs, only the two in /tue collide.
+        assert_eq!(
+            destinations(&inputs, None, "txt")[..3],
+            [
+                PathBuf::from("/mon/standup.txt"),
+                PathBuf::from("/tue/standup.txt"),
+                PathBuf::from("/tue/standup-2.txt"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failures_are_reported_and_the_run_continues() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let missing = dir.join("missing.wav");
+        let unsupported = dir.join("notes.txt");
+        std::fs::write(&unsupported, b"not audio").unwrap();
+
+        let args = parse(&[
+            missing.to_str().unwrap(),
+            unsupported.to_str().unwrap(),
+            "--no-progress",
+        ])
+        .unwrap();
+        let err = run(&args, &Config::default()).await.unwrap_err();
+        assert_eq!(err.to_string(), "2 of 2 files failed to transcribe");
+    }
+
+    #[tokio::test]
+    async fn output_and_copy_need_a_single_file() {
+        let args = parse(&["a.mp3", "b.mp3", "--output", "both.txt"]).unwrap();
+        let err = run(&args, &Config::default()).await.unwrap_err();
+        assert!(err.to_string().contains("--output-dir"));
+    }
+}
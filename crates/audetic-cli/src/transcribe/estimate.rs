@@ -0,0 +1,258 @@
+//! `audetic transcribe <file> --estimate`: a dry run.
+//!
+//! Reports what a transcription of each file would involve — its duration,
+//! format, whether it gets compressed and to roughly what size, and where it
+//! would be sent — and exits without uploading anything.
+
+use anyhow::{bail, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::{jobs_api_url, remote_url, validate_file};
+use crate::args::TranscribeCliArgs;
+use audetic_core::compression::{
+    estimated_compressed_size, get_file_size, is_already_compressed, needs_compression,
+};
+use audetic_core::config::Config;
+use audetic_core::jobs_client::mime_type_for_extension;
+use audetic_core::media;
+use audetic_core::url::{api_url, paths};
+
+/// Print an estimate for every file on the command line.
+pub fn run(args: &TranscribeCliArgs, config: &Config) -> Result<()> {
+    for (index, file) in args.files.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        let estimate = estimate(args, file, config)?;
+        print!("{estimate}");
+    }
+    println!();
+    println!("Nothing was uploaded.");
+    Ok(())
+}
+
+/// What sending `file` would look like under `config`.
+fn estimate(args: &TranscribeCliArgs, file: &Path, config: &Config) -> Result<Estimate> {
+    if remote_url(file)?.is_some() {
+        bail!(
+            "--estimate works on local files; download {} first",
+            file.display()
+        );
+    }
+    validate_file(file)?;
+
+    let extension = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let size = get_file_size(file)?;
+    let duration = media::duration_seconds(file);
+
+    // On-device transcription uploads the file untouched to the daemon.
+    let local = config.whisper.provider.as_deref() == Some("local");
+    let upload = if local {
+        Upload::AsIs("on-device transcription sends the original")
+    } else if needs_compression(
+        file,
+        args.no_compress,
+        config.behavior.compress_min_size_bytes,
+    )? {
+        Upload::Compressed(duration.map(estimated_compressed_size))
+    } else if args.no_compress {
+        Upload::AsIs("--no-compress")
+    } else if is_already_compressed(file) {
+        Upload::AsIs("already compressed")
+    } else {
+        Upload::AsIs("under [behavior].compress_min_size_bytes")
+    };
+    let endpoint = if local {
+        format!("{} (Audetic daemon, on-device)", api_url(paths::TRANSCRIBE))
+    } else {
+        jobs_api_url(args, config)
+    };
+
+    Ok(Estimate {
+        file: file.to_path_buf(),
+        mime: mime_type_for_extension(&extension).unwrap_or("unknown"),
+        extension,
+        duration,
+        size,
+        upload,
+        endpoint,
+    })
+}
+
+/// The dry-run report for one file.
+#[derive(Debug)]
+struct Estimate {
+    file: PathBuf,
+    extension: String,
+    mime: &'static str,
+    /// `None` when neither the WAV header nor `ffprobe` could tell.
+    duration: Option<f64>,
+    size: u64,
+    upload: Upload,
+    endpoint: String,
+}
+
+/// How the file would be uploaded.
+#[derive(Debug, PartialEq)]
+enum Upload {
+    /// Re-encoded to mp3 first; the estimated size needs a known duration.
+    Compressed(Option<u64>),
+    /// Sent unchanged, for the given reason.
+    AsIs(&'static str),
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "File:      {}", self.file.display())?;
+        writeln!(f, "Format:    {} ({})", self.extension, self.mime)?;
+        match self.duration {
+            Some(seconds) => writeln!(f, "Duration:  {}", format_duration(seconds))?,
+            None => writeln!(
+                f,
+                "Duration:  unknown (ffprobe is missing or couldn't read the file)"
+            )?,
+        }
+        writeln!(f, "Size:      {}", format_mb(self.size))?;
+        match &self.upload {
+            Upload::Compressed(Some(bytes)) => writeln!(
+                f,
+                "Upload:    compressed to mp3, about {}",
+                format_mb(*bytes)
+            )?,
+            Upload::Compressed(None) => writeln!(
+                f,
+                "Upload:    compressed to mp3 (size unknown without a duration)"
+            )?,
+            Upload::AsIs(reason) => {
+                writeln!(f, "Upload:    as-is, {} ({reason})", format_mb(self.size))?
+            }
+        }
+        writeln!(f, "Endpoint:  {}", self.endpoint)
+    }
+}
+
+fn format_mb(bytes: u64) -> String {
+    format!("{:.1}MB", bytes as f64 / 1_000_000.0)
+}
+
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    match (total / 3600, (total % 3600) / 60, total % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, s) => format!("{h}h {m:02}m {s:02}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{Cli, CliCommand};
+    use clap::Parser;
+
+    fn parse(argv: &[&str]) -> Result<TranscribeCliArgs, clap::Error> {
+        let argv = ["audetic", "transcribe"].iter().chain(argv);
+        match Cli::try_parse_from(argv)?.command {
+            Some(CliCommand::Transcribe(args)) => Ok(args),
+            other => panic!("expected transcribe args, got {other:?}"),
+        }
+    }
+
+    /// Write `bytes` to `dir/name`.
+    fn temp_file(dir: &Path, name: &str, bytes: &[u8]) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    /// A 16 kHz mono 16-bit WAV of `seconds` of silence.
+    fn silent_wav(dir: &Path, name: &str, seconds: u32) -> PathBuf {
+        let data_len = 16_000 * 2 * seconds;
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&16_000u32.to_le_bytes());
+        bytes.extend_from_slice(&32_000u32.to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        bytes.resize(bytes.len() + data_len as usize, 0);
+        temp_file(dir, name, &bytes)
+    }
+
+    #[test]
+    fn estimate_reports_wav_duration_and_compressed_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav = silent_wav(dir.path(), "minute.wav", 60);
+        let path = wav.to_str().unwrap();
+        let args = parse(&[path, "--estimate", "--api-url", "https://jobs.test/jobs"]).unwrap();
+
+        let estimate = estimate(&args, &wav, &Config::default()).unwrap();
+        assert_eq!(estimate.duration, Some(60.0));
+        assert_eq!(estimate.mime, "audio/wav");
+        // 1.92MB of WAV is over the default threshold, so it gets compressed.
+        assert_eq!(estimate.upload, Upload::Compressed(Some(480_000)));
+        assert_eq!(estimate.endpoint, "https://jobs.test/jobs");
+
+        let report = estimate.to_string();
+        assert!(report.contains("Duration:  1m 00s"), "{report}");
+        assert!(
+            report.contains("compressed to mp3, about 0.5MB"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn estimate_explains_uncompressed_uploads() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav = silent_wav(dir.path(), "second.wav", 1);
+        let path = wav.to_str().unwrap();
+
+        let args = parse(&[path, "--estimate"]).unwrap();
+        let estimate = estimate(&args, &wav, &Config::default()).unwrap();
+        assert_eq!(
+            estimate.upload,
+            Upload::AsIs("under [behavior].compress_min_size_bytes")
+        );
+
+        let args = parse(&[path, "--estimate", "--no-compress"]).unwrap();
+        let estimate = estimate(&args, &wav, &Config::default()).unwrap();
+        assert_eq!(estimate.upload, Upload::AsIs("--no-compress"));
+    }
+
+    #[test]
+    fn unreadable_media_has_an_unknown_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = temp_file(dir.path(), "garbled.m4a", b"not really audio");
+        let path = file.to_str().unwrap();
+        let args = parse(&[path, "--estimate"]).unwrap();
+
+        let estimate = estimate(&args, &file, &Config::default()).unwrap();
+        assert_eq!(estimate.duration, None);
+        assert!(
+            estimate.to_string().contains("Duration:  unknown"),
+            "{estimate}"
+        );
+    }
+
+    #[test]
+    fn estimate_rejects_urls_and_conflicting_flags() {
+        let args = parse(&["https://example.com/a.mp3", "--estimate"]).unwrap();
+        assert!(estimate(
+            &args,
+            Path::new("https://example.com/a.mp3"),
+            &Config::default()
+        )
+        .is_err());
+        assert!(parse(&["a.mp3", "--estimate", "--copy"]).is_err());
+    }
+}
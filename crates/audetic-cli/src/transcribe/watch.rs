@@ -0,0 +1,374 @@
+//! `audetic transcribe --watch <dir>`: transcribe files as they are dropped
+//! into a directory.
+//!
+//! New supported audio/video files are picked up via filesystem events (plus
+//! one scan at startup), held until their size stops changing so a file that
+//! is still being copied isn't uploaded half-written, then run through the
+//! regular single-file pipeline. Transcripts land next to each input (or in
+//! `--output-dir`, else `[transcribe].default_output_dir`); an input whose
+//! transcript already exists there is skipped. A
+//! file that fails is retried with a growing delay, up to [`MAX_ATTEMPTS`]
+//! times, and again whenever it changes. Ctrl-C stops watching, and
+//! interrupts a transcription in progress.
+
+use anyhow::{Context, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use super::{
+    extension_for_format, interrupted, is_interrupted, output_dir, output_path, resolve_output,
+    transcribe_file, Output,
+};
+use crate::args::TranscribeCliArgs;
+use audetic_core::config::Config;
+use audetic_core::jobs_client::mime_type_for_extension;
+
+/// How often pending files are re-checked for growth.
+const SETTLE_INTERVAL: Duration = Duration::from_secs(1);
+/// Consecutive checks with an unchanged, non-zero size before a file counts
+/// as fully written.
+const STABLE_CHECKS: u32 = 2;
+/// Attempts at a file before giving up on it until it changes.
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait before retrying a failed file; doubles after each further failure.
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Watch `dir` until Ctrl-C, transcribing each new file once it settles.
+pub async fn run(args: &TranscribeCliArgs, dir: &Path, config: &Config) -> Result<()> {
+    if !dir.is_dir() {
+        anyhow::bail!("Not a directory: {}", dir.display());
+    }
+
+    // Resolved once for the format; each file gets its own destination.
+    let format = resolve_output(args, dir, &config.transcribe)?.format;
+    // The same directory batch mode writes to, so `needs_transcript` looks
+    // for existing transcripts where they actually land.
+    let output_dir = output_dir(args, &config.transcribe);
+    let output_for = |file: &Path| Output {
+        format: format.clone(),
+        path: Some(output_path(file, output_dir, extension_for_format(&format))),
+    };
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+        }
+    })
+    .context("Failed to start file watcher")?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+    let mut pending: HashMap<PathBuf, SizeTracker> = HashMap::new();
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        pending.insert(entry.path(), SizeTracker::default());
+    }
+    let mut retries = RetryQueue::default();
+
+    eprintln!("Watching {} for new files (Ctrl-C to stop)", dir.display());
+
+    let mut ticker = tokio::time::interval(SETTLE_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interrupted() => {
+                eprintln!("Stopped watching {}", dir.display());
+                return Ok(());
+            }
+            Some(path) = rx.recv() => {
+                // A changed file starts over with a fresh set of attempts.
+                retries.clear(&path);
+                pending.entry(path).or_default();
+            }
+            _ = ticker.tick() => {
+                for file in retries.due(Instant::now()) {
+                    pending.entry(file).or_default();
+                }
+                let ready = settled_files(&mut pending, |path| {
+                    std::fs::metadata(path).ok().filter(|m| m.is_file()).map(|m| m.len())
+                });
+                for file in ready {
+                    let output = output_for(&file);
+                    if !needs_transcript(&file, &output) {
+                        continue;
+                    }
+                    eprintln!("Transcribing {}", file.display());
+                    let Err(e) = transcribe_file(args, &file, &output, config).await else {
+                        retries.clear(&file);
+                        continue;
+                    };
+                    if is_interrupted(&e) {
+                        eprintln!("Stopped watching {}", dir.display());
+                        return Err(e);
+                    }
+                    eprintln!("Failed to transcribe {}: {e:#}", file.display());
+                    match retries.record_failure(&file, Instant::now()) {
+                        Some(delay) => eprintln!("Retrying in {}s", delay.as_secs()),
+                        None => eprintln!(
+                            "Giving up on {} after {MAX_ATTEMPTS} attempts; it's retried if it changes",
+                            file.display()
+                        ),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether `file` is a supported input that doesn't have a transcript yet.
+fn needs_transcript(file: &Path, output: &Output) -> bool {
+    let supported = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| mime_type_for_extension(&ext.to_lowercase()).is_some());
+    supported && !output.path.as_ref().is_some_and(|p| p.exists())
+}
+
+/// Tracks a pending file's size between checks.
+#[derive(Debug, Default)]
+struct SizeTracker {
+    last_size: Option<u64>,
+    stable_checks: u32,
+}
+
+impl SizeTracker {
+    /// Record the current size; true once it has held steady (and non-zero)
+    /// for [`STABLE_CHECKS`] consecutive checks.
+    fn observe(&mut self, size: u64) -> bool {
+        if size > 0 && self.last_size == Some(size) {
+            self.stable_checks += 1;
+        } else {
+            self.stable_checks = 0;
+        }
+        self.last_size = Some(size);
+        self.stable_checks >= STABLE_CHECKS
+    }
+}
+
+/// Files whose last transcription failed: how many attempts they've had and
+/// when the next one is due.
+#[derive(Debug, Default)]
+struct RetryQueue {
+    files: HashMap<PathBuf, Retry>,
+}
+
+#[derive(Debug)]
+struct Retry {
+    attempts: u32,
+    /// `None` while an attempt is in flight or after the last one failed.
+    due: Option<Instant>,
+}
+
+impl RetryQueue {
+    /// Record a failed attempt at `file`. Returns the delay before the next
+    /// one, or `None` once [`MAX_ATTEMPTS`] are used up.
+    fn record_failure(&mut self, file: &Path, now: Instant) -> Option<Duration> {
+        let retry = self.files.entry(file.to_path_buf()).or_insert(Retry {
+            attempts: 0,
+            due: None,
+        });
+        retry.attempts += 1;
+        if retry.attempts >= MAX_ATTEMPTS {
+            retry.due = None;
+            return None;
+        }
+        let delay = RETRY_DELAY * 2u32.pow(retry.attempts - 1);
+        retry.due = Some(now + delay);
+        Some(delay)
+    }
+
+    /// Files whose retry is due at `now`, marked as in flight.
+    fn due(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut due: Vec<PathBuf> = self
+            .files
+            .iter_mut()
+            .filter(|(_, retry)| retry.due.is_some_and(|at| at <= now))
+            .map(|(path, retry)| {
+                retry.due = None;
+                path.clone()
+            })
+            .collect();
+        due.sort();
+        due
+    }
+
+    /// Forget `file`, after it succeeded or changed.
+    fn clear(&mut self, file: &Path) {
+        self.files.remove(file);
+    }
+}
+
+/// Check every pending file's size and remove and return the ones that have
+/// settled. Files that vanished (or aren't regular files) are dropped.
+fn settled_files(
+    pending: &mut HashMap<PathBuf, SizeTracker>,
+    size_of: impl Fn(&Path) -> Option<u64>,
+) -> Vec<PathBuf> {
+    let mut ready = Vec::new();
+    pending.retain(|path, tracker| match size_of(path) {
+        Some(size) if tracker.observe(size) => {
+            ready.push(path.clone());
+            false
+        }
+        Some(_) => true,
+        None => false,
+    });
+    ready.sort();
+    ready
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::{Cli, CliCommand, OutputFormat};
+    use audetic_core::config::TranscribeConfig;
+    use clap::Parser;
+
+    fn parse(argv: &[&str]) -> TranscribeCliArgs {
+        let argv = ["audetic", "transcribe"].iter().chain(argv);
+        match Cli::parse_from(argv).command {
+            Some(CliCommand::Transcribe(args)) => args,
+            other => panic!("expected transcribe args, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn size_must_hold_steady_before_a_file_is_ready() {
+        let mut tracker = SizeTracker::default();
+        assert!(!tracker.observe(100));
+        assert!(!tracker.observe(250)); // still growing
+        assert!(!tracker.observe(250));
+        assert!(tracker.observe(250));
+    }
+
+    #[test]
+    fn empty_files_never_settle() {
+        let mut tracker = SizeTracker::default();
+        for _ in 0..5 {
+            assert!(!tracker.observe(0));
+        }
+    }
+
+    #[test]
+    fn settled_files_waits_for_growth_to_stop_and_drops_missing_files() {
+        let growing = PathBuf::from("/in/growing.wav");
+        let done = PathBuf::from("/in/done.wav");
+        let gone = PathBuf::from("/in/gone.wav");
+        let mut pending: HashMap<PathBuf, SizeTracker> = [&growing, &done, &gone]
+            .into_iter()
+            .map(|p| (p.clone(), SizeTracker::default()))
+            .collect();
+
+        let mut tick = 0u64;
+        let mut check = |pending: &mut HashMap<PathBuf, SizeTracker>| {
+            tick += 1;
+            settled_files(pending, |path| match path.to_str().unwrap() {
+                "/in/growing.wav" => Some(tick * 1000),
+                "/in/done.wav" => Some(500),
+                _ => None,
+            })
+        };
+
+        assert!(check(&mut pending).is_empty());
+        assert!(check(&mut pending).is_empty());
+        assert_eq!(check(&mut pending), vec![done]);
+        assert_eq!(pending.keys().collect::<Vec<_>>(), vec![&growing]);
+    }
+
+    #[test]
+    fn output_goes_beside_the_input_or_into_output_dir() {
+        let file = Path::new("/drop/interview.mp3");
+        assert_eq!(
+            output_path(file, None, "srt"),
+            PathBuf::from("/drop/interview.srt")
+        );
+        assert_eq!(
+            output_path(file, Some(Path::new("/out")), "txt"),
+            PathBuf::from("/out/interview.txt")
+        );
+    }
+
+    #[test]
+    fn failed_files_are_retried_with_backoff_then_given_up() {
+        let file = PathBuf::from("/in/talk.wav");
+        let start = Instant::now();
+        let mut retries = RetryQueue::default();
+
+        assert_eq!(retries.record_failure(&file, start), Some(RETRY_DELAY));
+        assert!(retries.due(start).is_empty());
+        let first = start + RETRY_DELAY;
+        assert_eq!(retries.due(first), vec![file.clone()]);
+        // Handed out once, not again on every tick.
+        assert!(retries.due(first).is_empty());
+
+        assert_eq!(retries.record_failure(&file, first), Some(RETRY_DELAY * 2));
+        let second = first + RETRY_DELAY * 2;
+        assert_eq!(retries.due(second), vec![file.clone()]);
+
+        assert_eq!(retries.record_failure(&file, second), None);
+        assert!(retries.due(second + RETRY_DELAY * 100).is_empty());
+
+        // A change to the file earns it a fresh set of attempts.
+        retries.clear(&file);
+        assert_eq!(retries.record_failure(&file, start), Some(RETRY_DELAY));
+    }
+
+    #[test]
+    fn transcripts_go_to_the_configured_default_output_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let out = tmp.path().join("transcripts");
+        let defaults = TranscribeConfig {
+            default_output_dir: Some(out.clone()),
+            ..TranscribeConfig::default()
+        };
+        let args = parse(&["--watch", "/drop"]);
+        assert_eq!(output_dir(&args, &defaults), Some(out.as_path()));
+
+        // An existing transcript there marks the input as done.
+        let audio = tmp.path().join("talk.wav");
+        std::fs::write(&audio, b"audio").unwrap();
+        let output = Output {
+            format: OutputFormat::Text,
+            path: Some(output_path(&audio, output_dir(&args, &defaults), "txt")),
+        };
+        assert!(needs_transcript(&audio, &output));
+        std::fs::create_dir_all(&out).unwrap();
+        std::fs::write(out.join("talk.txt"), "transcript").unwrap();
+        assert!(!needs_transcript(&audio, &output));
+
+        // --output-dir still wins.
+        let args = parse(&["--watch", "/drop", "--output-dir", "/elsewhere"]);
+        assert_eq!(output_dir(&args, &defaults), Some(Path::new("/elsewhere")));
+    }
+
+    #[test]
+    fn already_transcribed_and_unsupported_files_are_skipped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let audio = dir.join("talk.wav");
+        std::fs::write(&audio, b"audio").unwrap();
+        let output = Output {
+            format: OutputFormat::Text,
+            path: Some(output_path(&audio, None, "txt")),
+        };
+
+        assert!(needs_transcript(&audio, &output));
+
+        std::fs::write(output.path.as_ref().unwrap(), "transcript").unwrap();
+        assert!(!needs_transcript(&audio, &output));
+
+        let notes = dir.join("notes.txt");
+        let notes_output = Output {
+            format: OutputFormat::Text,
+            path: Some(output_path(&notes, Some(&dir.join("out")), "txt")),
+        };
+        assert!(!needs_transcript(&notes, &notes_output));
+    }
+}
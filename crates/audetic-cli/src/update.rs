@@ -2,21 +2,62 @@
 //!
 //! Talks to the daemon's REST API (`GET /api/update/check`,
 //! `POST /api/update/install`, `PUT /api/update/auto`). The daemon owns the
-//! update engine; the CLI just drives it and reports results.
+//! update engine; the CLI just drives it and reports results. Rollback is the
+//! exception: it restores the backed-up daemon binary itself, so it works
+//! when the release being rolled back won't even start, and only asks the
+//! daemon (`POST /api/update/rollback`) when it can't find the binary.
+//!
+//! Exit codes follow the report's `outcome` so scripts can branch without
+//! parsing messages: 0 up to date / installed / rolled back / auto-update
+//! changed, 2 update available, 3 auto-update disabled, 4 unsupported
+//! platform, 1 on errors.
 
 use anyhow::{anyhow, Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::io;
+use std::path::PathBuf;
 use std::process::Command;
 
 use crate::args::UpdateCliArgs;
-use crate::client::{base_url, json_or_error, CONNECT_HINT};
+use crate::client::{base_url, daemon_client, json_or_error, CONNECT_HINT};
+use audetic_core::{global, rollback};
 
 const SERVICE_NAME: &str = "audeticd.service";
+const DAEMON_BIN: &str = "audeticd";
+
+/// Mirrors the daemon's `UpdateOutcome`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum UpdateOutcome {
+    UpToDate,
+    UpdateAvailable,
+    Installed,
+    Disabled,
+    Unsupported,
+    AutoUpdateChanged,
+    RolledBack,
+}
 
-#[derive(Debug, Deserialize)]
+impl UpdateOutcome {
+    fn exit_code(self) -> i32 {
+        match self {
+            UpdateOutcome::UpToDate
+            | UpdateOutcome::Installed
+            | UpdateOutcome::AutoUpdateChanged
+            | UpdateOutcome::RolledBack => 0,
+            UpdateOutcome::UpdateAvailable => 2,
+            UpdateOutcome::Disabled => 3,
+            UpdateOutcome::Unsupported => 4,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 struct UpdateReport {
+    /// Absent from daemons that predate structured outcomes.
+    #[serde(default)]
+    outcome: Option<UpdateOutcome>,
     message: String,
     current_version: String,
     #[serde(default)]
@@ -25,7 +66,7 @@ struct UpdateReport {
     restart_required: bool,
 }
 
-pub async fn handle_update_command(args: UpdateCliArgs) -> Result<()> {
+pub async fn handle_update_command(args: UpdateCliArgs, json: bool) -> Result<()> {
     if args.enable && args.disable {
         return Err(anyhow!(
             "Cannot enable and disable auto-update at the same time"
@@ -34,20 +75,26 @@ pub async fn handle_update_command(args: UpdateCliArgs) -> Result<()> {
 
     // Toggling auto-update is its own action.
     if args.enable || args.disable {
-        return set_auto_update(args.enable).await;
+        return set_auto_update(args.enable, json).await;
     }
 
-    let report = if args.check {
+    let report = if args.rollback {
+        rollback_update().await?
+    } else if args.check {
         check_update().await?
     } else {
         install_update(args.channel, args.force).await?
     };
 
-    println!("{}", report.message);
-    if let Some(remote) = report.remote_version.as_deref() {
-        println!("Current: {} | Remote: {}", report.current_version, remote);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
     } else {
-        println!("Current: {}", report.current_version);
+        println!("{}", report.message);
+        if let Some(remote) = report.remote_version.as_deref() {
+            println!("Current: {} | Remote: {}", report.current_version, remote);
+        } else {
+            println!("Current: {}", report.current_version);
+        }
     }
 
     if report.restart_required {
@@ -55,22 +102,33 @@ pub async fn handle_update_command(args: UpdateCliArgs) -> Result<()> {
             .remote_version
             .as_deref()
             .unwrap_or("the newly installed version");
+        // With --json, stdout carries only the report.
+        let say = |line: String| {
+            if json {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        };
         match restart_user_service() {
-            Ok(()) => println!("Audetic service restarted via systemd user service."),
+            Ok(()) => say("Audetic service restarted via systemd user service.".to_string()),
             Err(err) => {
                 eprintln!("Failed to restart Audetic automatically: {err}");
-                println!(
+                say(format!(
                     "Please restart the Audetic service manually (e.g. `systemctl --user restart {SERVICE_NAME}`) to begin running {remote}."
-                );
+                ));
             }
         }
     }
 
-    Ok(())
+    match report.outcome.map(UpdateOutcome::exit_code) {
+        Some(code) if code != 0 => std::process::exit(code),
+        _ => Ok(()),
+    }
 }
 
 async fn check_update() -> Result<UpdateReport> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .get(format!("{}/update/check", base_url()))
         .send()
         .await
@@ -80,7 +138,7 @@ async fn check_update() -> Result<UpdateReport> {
 }
 
 async fn install_update(channel: Option<String>, force: bool) -> Result<UpdateReport> {
-    let response = reqwest::Client::new()
+    let response = daemon_client()
         .post(format!("{}/update/install", base_url()))
         .json(&json!({ "channel": channel, "force": force }))
         .send()
@@ -90,14 +148,58 @@ async fn install_update(channel: Option<String>, force: bool) -> Result<UpdateRe
     serde_json::from_value(body).context("Failed to parse update report")
 }
 
-async fn set_auto_update(enabled: bool) -> Result<()> {
-    let response = reqwest::Client::new()
+/// Restore the daemon binary the last update replaced. Done here rather than
+/// by the daemon, which may be the thing that's broken.
+async fn rollback_update() -> Result<UpdateReport> {
+    let Some(binary) = daemon_binary() else {
+        return rollback_via_daemon().await;
+    };
+    let state_file = global::update_state_file()?;
+    let lock_file = global::update_lock_file()?;
+    let restored =
+        tokio::task::spawn_blocking(move || rollback::rollback(&binary, &state_file, &lock_file))
+            .await??;
+    Ok(UpdateReport {
+        outcome: Some(UpdateOutcome::RolledBack),
+        message: format!("Rolled back to {restored}. Restart required to run it."),
+        current_version: env!("CARGO_PKG_VERSION").to_string(),
+        remote_version: Some(restored),
+        restart_required: true,
+    })
+}
+
+/// The installed daemon: next to this binary, as the installer lays them
+/// out, or else on PATH.
+fn daemon_binary() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(DAEMON_BIN)))
+        .filter(|path| path.is_file())
+        .or_else(|| which::which(DAEMON_BIN).ok())
+}
+
+async fn rollback_via_daemon() -> Result<UpdateReport> {
+    let response = daemon_client()
+        .post(format!("{}/update/rollback", base_url()))
+        .send()
+        .await
+        .context(CONNECT_HINT)?;
+    let body = json_or_error(response, "roll back update").await?;
+    serde_json::from_value(body).context("Failed to parse update report")
+}
+
+async fn set_auto_update(enabled: bool, json: bool) -> Result<()> {
+    let response = daemon_client()
         .put(format!("{}/update/auto", base_url()))
         .json(&json!({ "enabled": enabled }))
         .send()
         .await
         .context(CONNECT_HINT)?;
     let body = json_or_error(response, "set auto-update").await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
     let message = body
         .get("message")
         .and_then(|m| m.as_str())
@@ -129,3 +231,42 @@ fn restart_user_service() -> Result<()> {
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_outcome_and_maps_exit_codes() {
+        let report: UpdateReport = serde_json::from_str(
+            r#"{"outcome":"update_available","message":"Update available","current_version":"1.0.0","remote_version":"1.1.0"}"#,
+        )
+        .unwrap();
+        assert_eq!(report.outcome, Some(UpdateOutcome::UpdateAvailable));
+        assert_eq!(UpdateOutcome::UpdateAvailable.exit_code(), 2);
+        assert_eq!(UpdateOutcome::UpToDate.exit_code(), 0);
+        assert_eq!(UpdateOutcome::Unsupported.exit_code(), 4);
+    }
+
+    #[test]
+    fn tolerates_reports_without_outcome() {
+        let report: UpdateReport =
+            serde_json::from_str(r#"{"message":"ok","current_version":"1.0.0"}"#).unwrap();
+        assert_eq!(report.outcome, None);
+    }
+
+    #[test]
+    fn json_is_a_global_flag() {
+        use crate::args::{Cli, CliCommand};
+        use clap::Parser;
+
+        for argv in [
+            ["audetic", "update", "--check", "--json"],
+            ["audetic", "--json", "update", "--check"],
+        ] {
+            let cli = Cli::try_parse_from(argv).unwrap();
+            assert!(cli.json);
+            assert!(matches!(cli.command, Some(CliCommand::Update(ref args)) if args.check));
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Stamp build metadata into `audetic_core::build_info`.
+//!
+//! Sets `AUDETIC_GIT_SHA` (short commit, `unknown` outside a git checkout)
+//! and `AUDETIC_BUILD_DATE` (UTC, `YYYY-MM-DD`). Release pipelines can pin
+//! either by exporting it before `cargo build`; `SOURCE_DATE_EPOCH` is
+//! honored for reproducible builds.
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let git_dir = manifest_dir.join("../../.git");
+    // HEAD changes on checkout; the ref it points at changes on commit.
+    println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+    println!("cargo:rerun-if-changed={}", git_dir.join("refs").display());
+    for var in [
+        "AUDETIC_GIT_SHA",
+        "AUDETIC_BUILD_DATE",
+        "AUDETIC_RELEASE_CHANNEL",
+        "SOURCE_DATE_EPOCH",
+    ] {
+        println!("cargo:rerun-if-env-changed={var}");
+    }
+
+    let git_sha = std::env::var("AUDETIC_GIT_SHA")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .or_else(git_short_sha)
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=AUDETIC_GIT_SHA={git_sha}");
+
+    let build_date = std::env::var("AUDETIC_BUILD_DATE")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| utc_date(build_epoch()));
+    println!("cargo:rustc-env=AUDETIC_BUILD_DATE={build_date}");
+}
+
+fn git_short_sha() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!sha.is_empty()).then_some(sha)
+}
+
+fn build_epoch() -> u64 {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default()
+        })
+}
+
+/// `YYYY-MM-DD` for a Unix timestamp (Howard Hinnant's civil-from-days).
+fn utc_date(epoch_secs: u64) -> String {
+    let days = (epoch_secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
@@ -0,0 +1,97 @@
+//! What this binary is: version, release target, and build provenance.
+//!
+//! Shared by `audetic version --json` and the daemon's `GET /version`, so a
+//! bug report shows the same fields whichever one the user ran. The git SHA
+//! and build date come from `build.rs`.
+
+use serde::Serialize;
+
+/// Release channel baked in at build time (`AUDETIC_RELEASE_CHANNEL`);
+/// local builds report `stable`, matching the updater's default channel.
+const RELEASE_CHANNEL: &str = match option_env!("AUDETIC_RELEASE_CHANNEL") {
+    Some(channel) => channel,
+    None => "stable",
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct BuildInfo {
+    pub version: String,
+    /// Release artifact target (e.g. `linux-x86_64-gnu`); `None` on
+    /// platforms without published builds.
+    pub target: Option<String>,
+    pub channel: String,
+    /// Short commit hash, or `unknown` when built outside a git checkout.
+    pub git_sha: String,
+    /// UTC build date, `YYYY-MM-DD`.
+    pub build_date: String,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            target: target_id().map(str::to_string),
+            channel: RELEASE_CHANNEL.to_string(),
+            git_sha: env!("AUDETIC_GIT_SHA").to_string(),
+            build_date: env!("AUDETIC_BUILD_DATE").to_string(),
+        }
+    }
+}
+
+/// Release artifact id for the platform this was compiled for, as used in
+/// update manifests. `None` where no release is published.
+pub fn target_id() -> Option<&'static str> {
+    if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("linux-x86_64-gnu")
+    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+        Some("linux-aarch64-gnu")
+    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("macos-aarch64")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Some("macos-x86_64")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_includes_version_and_target() {
+        let json = serde_json::to_value(BuildInfo::current()).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        for field in ["channel", "git_sha", "build_date"] {
+            assert!(
+                json[field].as_str().is_some_and(|s| !s.is_empty()),
+                "{field} should be a non-empty string"
+            );
+        }
+
+        let supported = cfg!(any(
+            all(
+                target_os = "linux",
+                any(target_arch = "x86_64", target_arch = "aarch64")
+            ),
+            all(
+                target_os = "macos",
+                any(target_arch = "x86_64", target_arch = "aarch64")
+            ),
+        ));
+        if supported {
+            assert!(json["target"].as_str().is_some_and(|s| !s.is_empty()));
+        } else {
+            assert!(json["target"].is_null());
+        }
+    }
+
+    #[test]
+    fn build_date_is_iso_formatted() {
+        let date = BuildInfo::current().build_date;
+        let parts: Vec<&str> = date.split('-').collect();
+        assert_eq!(parts.len(), 3, "{date}");
+        assert_eq!(parts[0].len(), 4);
+    }
+}
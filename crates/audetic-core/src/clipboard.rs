@@ -2,7 +2,8 @@
 //!
 //! The daemon's text-injection path (`text_io`) reuses [`CLIPBOARD_BACKENDS`]
 //! for its async clipboard fallback; the CLI uses [`copy_to_clipboard_sync`]
-//! for `transcribe --copy` / `history --copy`. Keeping the backend table in
+//! for `transcribe --copy` / `history --copy` and [`read_clipboard_sync`] for
+//! `transcribe --from-clipboard`. Keeping the backend table in
 //! one place avoids the two diverging.
 
 use anyhow::{anyhow, Result};
@@ -10,12 +11,14 @@ use std::io::Write;
 use std::process::{Command, Stdio};
 use which::which;
 
-/// A system clipboard tool and how to feed it text.
+/// A system clipboard tool, how to feed it text, and how to read it back.
 pub struct ClipboardBackend {
     pub name: &'static str,
     pub copy_cmd: &'static str,
     pub copy_args: &'static [&'static str],
     pub use_stdin: bool,
+    pub paste_cmd: &'static str,
+    pub paste_args: &'static [&'static str],
 }
 
 /// Clipboard tools tried in order: wl-copy (Wayland) first, then xclip/xsel (X11).
@@ -25,18 +28,24 @@ pub const CLIPBOARD_BACKENDS: &[ClipboardBackend] = &[
         copy_cmd: "wl-copy",
         copy_args: &[],
         use_stdin: true,
+        paste_cmd: "wl-paste",
+        paste_args: &["--no-newline"],
     },
     ClipboardBackend {
         name: "xclip",
         copy_cmd: "xclip",
         copy_args: &["-selection", "clipboard"],
         use_stdin: true,
+        paste_cmd: "xclip",
+        paste_args: &["-selection", "clipboard", "-o"],
     },
     ClipboardBackend {
         name: "xsel",
         copy_cmd: "xsel",
         copy_args: &["--clipboard", "--input"],
         use_stdin: true,
+        paste_cmd: "xsel",
+        paste_args: &["--clipboard", "--output"],
     },
 ];
 
@@ -82,3 +91,31 @@ pub fn copy_to_clipboard_sync(text: &str) -> Result<()> {
         "No clipboard tool available. Please install wl-copy (Wayland), xclip, or xsel (X11)."
     ))
 }
+
+/// Read the clipboard's text contents using the same system tools as
+/// [`copy_to_clipboard_sync`], trying each backend in order.
+pub fn read_clipboard_sync() -> Result<String> {
+    for backend in CLIPBOARD_BACKENDS {
+        if which(backend.paste_cmd).is_err() {
+            continue;
+        }
+
+        let output = match Command::new(backend.paste_cmd)
+            .args(backend.paste_args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        {
+            Ok(output) => output,
+            Err(_) => continue,
+        };
+
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+        }
+    }
+
+    Err(anyhow!(
+        "No clipboard tool available. Please install wl-paste (Wayland), xclip, or xsel (X11)."
+    ))
+}
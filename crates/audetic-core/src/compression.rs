@@ -8,6 +8,19 @@ use anyhow::{bail, Context, Result};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Default `[behavior].compress_min_size_bytes`: about 30 seconds of 16 kHz
+/// mono WAV. Below this, uploading as-is beats waiting on ffmpeg.
+pub const DEFAULT_COMPRESS_MIN_SIZE_BYTES: u64 = 1_000_000;
+
+/// Bitrate [`compress_for_transcription`] encodes at, in kbit/s.
+pub const COMPRESSED_BITRATE_KBPS: u64 = 64;
+
+/// Roughly how large compressing `duration_seconds` of media will come out:
+/// constant-bitrate mp3 grows linearly with length.
+pub fn estimated_compressed_size(duration_seconds: f64) -> u64 {
+    (duration_seconds.max(0.0) * (COMPRESSED_BITRATE_KBPS * 1000 / 8) as f64).round() as u64
+}
+
 /// Check if a file is already in a compressed audio format suitable for upload.
 ///
 /// Files already in a compressed audio format (mp3, opus) are sent as-is.
@@ -24,6 +37,16 @@ pub fn get_file_size(path: &Path) -> Result<u64> {
     Ok(metadata.len())
 }
 
+/// Whether [`prepare_for_upload`] would compress `path`: not when it's
+/// already compressed, when `skip_compression` is set, or when it's smaller
+/// than `min_size_bytes` (0 compresses regardless of size).
+pub fn needs_compression(path: &Path, skip_compression: bool, min_size_bytes: u64) -> Result<bool> {
+    if is_already_compressed(path) || skip_compression {
+        return Ok(false);
+    }
+    Ok(min_size_bytes == 0 || get_file_size(path)? >= min_size_bytes)
+}
+
 /// Check if FFmpeg is available — either as the app-local sidecar binary in
 /// the daemon's exe dir or on the system PATH. See [`crate::ffmpeg`] for the
 /// resolution order.
@@ -74,11 +97,12 @@ pub fn compress_for_transcription(input: &Path) -> Result<PathBuf> {
     // -codec:a libmp3lame: use MP3 codec (universally supported)
     // -b:a 64k: 64kbps bitrate (good for speech)
     // -y: overwrite output without asking
+    let bitrate = format!("{COMPRESSED_BITRATE_KBPS}k");
     let status = Command::new(&ffmpeg)
         .args(["-i", input.to_str().unwrap()])
         .args(["-vn"])
         .args(["-codec:a", "libmp3lame"])
-        .args(["-b:a", "64k"])
+        .args(["-b:a", &bitrate])
         .args(["-y"])
         .arg(&output)
         .output()
@@ -105,9 +129,9 @@ pub fn cleanup_temp_file(path: &Path) {
 /// Prepare a media file for upload to the transcription API.
 ///
 /// Returns `(upload_path, temp_to_cleanup)`:
-/// - If the input is already in a compressed audio format (mp3/opus) or
-///   `skip_compression` is true, returns `(path, None)` and no temp file is
-///   created.
+/// - If the input is already in a compressed audio format (mp3/opus),
+///   `skip_compression` is true, or it's smaller than `min_size_bytes`,
+///   returns `(path, None)` and no temp file is created.
 /// - Otherwise compresses to mp3 in the system temp directory and returns
 ///   `(temp_path, Some(temp_path))` so the caller can delete the temp file
 ///   after upload.
@@ -118,8 +142,9 @@ pub fn cleanup_temp_file(path: &Path) {
 pub fn prepare_for_upload(
     path: &Path,
     skip_compression: bool,
+    min_size_bytes: u64,
 ) -> Result<(PathBuf, Option<PathBuf>)> {
-    if is_already_compressed(path) || skip_compression {
+    if !needs_compression(path, skip_compression, min_size_bytes)? {
         return Ok((path.to_path_buf(), None));
     }
 
@@ -164,7 +189,7 @@ mod tests {
         let path = PathBuf::from("/tmp/test_prepare_already_compressed.mp3");
         std::fs::write(&path, b"fake mp3").unwrap();
 
-        let (upload_path, temp) = prepare_for_upload(&path, false).unwrap();
+        let (upload_path, temp) = prepare_for_upload(&path, false, 0).unwrap();
         assert_eq!(upload_path, path);
         assert!(temp.is_none());
 
@@ -176,10 +201,39 @@ mod tests {
         let path = PathBuf::from("/tmp/test_prepare_skip_flag.wav");
         std::fs::write(&path, b"fake wav").unwrap();
 
-        let (upload_path, temp) = prepare_for_upload(&path, true).unwrap();
+        let (upload_path, temp) = prepare_for_upload(&path, true, 0).unwrap();
         assert_eq!(upload_path, path);
         assert!(temp.is_none());
 
         std::fs::remove_file(&path).unwrap();
     }
+
+    #[test]
+    fn test_needs_compression_respects_size_threshold() {
+        let mut file = tempfile::Builder::new().suffix(".wav").tempfile().unwrap();
+        file.write_all(&[0u8; 2048]).unwrap();
+
+        assert!(!needs_compression(file.path(), false, 4096).unwrap());
+        assert!(needs_compression(file.path(), false, 1024).unwrap());
+        assert!(needs_compression(file.path(), false, 0).unwrap());
+        assert!(!needs_compression(file.path(), true, 0).unwrap());
+    }
+
+    #[test]
+    fn test_estimated_compressed_size() {
+        // 64 kbit/s is 8000 bytes a second.
+        assert_eq!(estimated_compressed_size(60.0), 480_000);
+        assert_eq!(estimated_compressed_size(0.5), 4_000);
+        assert_eq!(estimated_compressed_size(-1.0), 0);
+    }
+
+    #[test]
+    fn test_prepare_for_upload_below_threshold() {
+        let mut file = tempfile::Builder::new().suffix(".wav").tempfile().unwrap();
+        file.write_all(b"short dictation").unwrap();
+
+        let (upload_path, temp) = prepare_for_upload(file.path(), false, 1024).unwrap();
+        assert_eq!(upload_path, file.path());
+        assert!(temp.is_none());
+    }
 }
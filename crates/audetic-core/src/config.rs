@@ -1,8 +1,9 @@
 use crate::global;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use tracing::info;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -11,6 +12,14 @@ pub struct Config {
     pub ui: UiConfig,
     pub wayland: WaylandConfig,
     pub behavior: BehaviorConfig,
+    pub network: NetworkConfig,
+    pub history: HistoryConfig,
+    pub normalizer: NormalizerConfig,
+    pub audio: AudioConfig,
+    pub transcribe: TranscribeConfig,
+    pub update: UpdaterConfig,
+    pub meeting: MeetingConfig,
+    pub api: ApiConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,13 +33,88 @@ pub struct WhisperConfig {
     pub api_endpoint: Option<String>,
     pub provider: Option<String>,
     pub api_key: Option<String>,
+    /// Seconds allowed to establish a connection to an API provider before
+    /// failing (default 10). Independent of how long transcription may take.
+    pub connect_timeout_seconds: Option<u64>,
+    /// Seconds an API provider request may take before it's abandoned
+    /// (default 120; 0 disables). Audio uploads get extra time in proportion
+    /// to their size on top, so a slow link doesn't cut off a long recording.
+    pub request_timeout_seconds: Option<u64>,
+    /// Times to retry an API provider call that was rate limited (429),
+    /// failed server-side (5xx), or timed out (default 3; 0 disables).
+    pub max_retries: Option<u32>,
+    /// Request word-level timestamps from providers that support them
+    /// (whisper.cpp, OpenAI API, Groq). Ignored by the rest.
+    pub word_timestamps: bool,
+    /// Longest audio to send to the provider, checked before upload. Unset
+    /// uses the provider's built-in limit (OpenAI 1500 s, AssemblyAI 10 h);
+    /// 0 disables the check.
+    pub max_audio_seconds: Option<u64>,
+    /// Languages to try in turn, keeping the transcript the provider is most
+    /// confident in. Providers that don't report confidence use the first
+    /// entry. Overrides `language` when set.
+    pub candidate_languages: Option<Vec<String>>,
+    /// AssemblyAI request toggles such as `punctuate`, `format_text` and
+    /// `disfluencies`, sent as-is with each transcript request. Unset keys
+    /// keep AssemblyAI's defaults (punctuation and formatting on).
+    pub assemblyai_features: Option<BTreeMap<String, bool>>,
+    /// Seconds a CLI engine (whisper.cpp, OpenAI Whisper) may run before it
+    /// is killed. Unset means 1800.
+    pub cli_timeout_seconds: Option<u64>,
+    /// Most stdout a CLI engine run may produce before it is killed. Unset
+    /// means 64 MiB.
+    pub cli_max_output_bytes: Option<u64>,
+    /// Read whisper.cpp's output as it prints, showing partial transcripts
+    /// in the indicator and `/status` while a long recording is processed.
+    /// Off by default: not every whisper.cpp build flushes its output
+    /// segment by segment.
+    pub stream_partials: bool,
+    /// Extra HTTP headers sent with every request by the `audetic-api`,
+    /// `openai-api` and `groq` providers, after the defaults so they can
+    /// replace them (`Authorization` included). Checked when the config is
+    /// loaded.
+    pub extra_headers: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct UiConfig {
+    /// How recording state is shown: `hyprland` (`hyprctl notify`, the
+    /// default) or `notification` (freedesktop desktop notifications).
+    pub indicator: IndicatorStyle,
     pub notification_color: String,
     pub waybar: WaybarConfig,
+    pub sounds: SoundsConfig,
+}
+
+/// `[ui].indicator`: where recording, processing, and completion are shown.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IndicatorStyle {
+    /// `hyprctl notify` overlays, colored with `notification_color`.
+    #[default]
+    Hyprland,
+    /// Desktop notifications over D-Bus, updated in place as the state
+    /// changes. Works under any freedesktop notification daemon (GNOME,
+    /// KDE, mako, dunst, swaync).
+    Notification,
+}
+
+/// `[ui.sounds]`: what plays for each indicator event while
+/// `[behavior].audio_feedback` is on. Each entry is a sound file path, a
+/// preset name (`beep`, `bell`, `complete`, `message`, `error`), or `""` to
+/// stay silent for that event. Unset entries play the built-in beep.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundsConfig {
+    /// Recording started.
+    pub start: Option<String>,
+    /// Recording stopped or paused.
+    pub stop: Option<String>,
+    /// Dictation failed.
+    pub error: Option<String>,
+    /// Transcript delivered.
+    pub complete: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +124,8 @@ pub struct WaybarConfig {
     pub recording_text: String,
     pub idle_tooltip: String,
     pub recording_tooltip: String,
+    pub paused_text: String,
+    pub paused_tooltip: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -48,14 +134,314 @@ pub struct WaylandConfig {
     pub input_method: String,
 }
 
+/// Outbound network settings shared by every HTTP client (see
+/// [`crate::http::HttpOptions`]).
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Proxy for all outbound requests, e.g. `http://proxy:3128` or
+    /// `socks5://127.0.0.1:1080`. Unset means `HTTPS_PROXY`/`ALL_PROXY`.
+    pub proxy: Option<String>,
+    /// Comma-separated hosts that bypass `proxy` (defaults to `NO_PROXY`).
+    pub no_proxy: Option<String>,
+    /// PEM root certificate to trust, e.g. for an internal transcription
+    /// server signed by a private CA.
+    pub ca_cert_path: Option<String>,
+    /// DANGER: skip TLS certificate verification. Off by default; for lab
+    /// servers only. A warning is logged whenever it's in effect.
+    pub danger_accept_invalid_certs: bool,
+    /// Most transcription jobs this process keeps open on the jobs server at
+    /// once, shared by meetings, imports and resumes. Unset means no cap.
+    pub max_concurrent_jobs: Option<usize>,
+}
+
+/// Dictation and meeting audio capture.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioConfig {
+    /// Name, or part of one, of the microphone to dictate with (see
+    /// `audetic audio devices`). Unset, or matching no device, uses the
+    /// system default.
+    pub input_device: Option<String>,
+    /// Seconds of audio to keep rolling while idle and prepend to each
+    /// dictation, so speech just before the hotkey isn't lost. 0 (the
+    /// default) disables it; when enabled the microphone stays open.
+    pub prebuffer_seconds: u32,
+    /// Sample size of recorded WAVs: 32 (float, the default) or 16 (PCM,
+    /// half the size, plenty for speech).
+    pub bits_per_sample: Option<u16>,
+    /// RMS level (0.0–1.0) below which dictation audio counts as silence.
+    /// When set, leading and trailing silence is trimmed before upload and
+    /// clips with too little speech aren't transcribed at all. Unset (the
+    /// default) sends recordings as captured.
+    pub vad_threshold: Option<f32>,
+    /// Least speech, in milliseconds, a clip needs above `vad_threshold` to
+    /// be transcribed. Defaults to 300.
+    pub vad_min_speech_ms: Option<u32>,
+    /// Directory dictation WAVs are written to before transcription. Unset
+    /// means the system temp directory (`$TMPDIR`, usually `/tmp`); created
+    /// on first use if missing.
+    pub temp_dir: Option<String>,
+}
+
+impl AudioConfig {
+    /// The effective `vad_min_speech_ms`.
+    pub fn vad_min_speech_ms(&self) -> u32 {
+        self.vad_min_speech_ms.unwrap_or(300)
+    }
+
+    /// The effective `temp_dir`.
+    pub fn temp_dir(&self) -> PathBuf {
+        self.temp_dir
+            .as_deref()
+            .filter(|dir| !dir.trim().is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir)
+    }
+
+    /// The effective WAV sample size; anything but 16 means 32.
+    pub fn bits_per_sample(&self) -> u16 {
+        match self.bits_per_sample {
+            Some(16) => 16,
+            _ => 32,
+        }
+    }
+}
+
+/// Defaults for `audetic transcribe` when its flags are omitted.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscribeConfig {
+    /// Output format used without `--format`: `text`, `json`, or `srt`.
+    pub default_format: Option<String>,
+    /// Directory transcripts are written to without `--output` or
+    /// `--output-dir`, named after the input file. Unset prints to stdout.
+    pub default_output_dir: Option<PathBuf>,
+}
+
+/// Dictation history storage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Also store each dictation's timestamped segments as searchable rows,
+    /// so a phrase can be traced to the recording and time it was said. Off
+    /// by default to keep the database small; only providers that return
+    /// segments populate it.
+    pub index_segments: bool,
+    /// Most transcriptions kept; the oldest are pruned after each new one.
+    /// `0` keeps everything.
+    pub max_entries: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            index_segments: false,
+            max_entries: DEFAULT_MAX_HISTORY_ENTRIES,
+        }
+    }
+}
+
+/// Default for `[history].max_entries`.
+pub const DEFAULT_MAX_HISTORY_ENTRIES: usize = 10_000;
+
+/// `[normalizer]`: user fixes applied to every transcript after the
+/// provider's own cleanup, in this order: `rules`, then `words`, then
+/// `sentence_case`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizerConfig {
+    /// Capitalize the first letter of the transcript and of each sentence.
+    pub sentence_case: bool,
+    /// Whole-word fixes, matched case-insensitively: `github = "GitHub"`.
+    pub words: BTreeMap<String, String>,
+    /// Regex replacements, applied in the order listed.
+    pub rules: Vec<ReplaceRule>,
+}
+
+/// One `[[normalizer.rules]]` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplaceRule {
+    /// Regular expression (Rust `regex` syntax).
+    pub pattern: String,
+    /// Replacement; `$1` or `${name}` insert capture groups. Empty deletes
+    /// the match.
+    #[serde(default)]
+    pub replace: String,
+}
+
+/// Background and manual self-updates.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdaterConfig {
+    /// Refuse to install a release that has no signature. Off by default:
+    /// unsigned releases install with a warning, while a signature that
+    /// fails to verify always aborts the install.
+    pub require_signature: bool,
+}
+
+/// Meeting transcription.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeetingConfig {
+    /// Ask the provider to label who said what (AssemblyAI and Deepgram).
+    /// Off by default since providers bill it extra; other providers,
+    /// including on-device, keep the unlabeled transcript.
+    pub diarize: bool,
+    /// Transcribe recordings longer than this many minutes in overlapping
+    /// pieces of this length, stitched back into one transcript, to stay
+    /// under provider size and duration limits. 0 sends the whole file.
+    pub chunk_minutes: u32,
+    /// Level of the microphone in the meeting mix. 1.0 (the default) keeps
+    /// it as captured.
+    pub mic_gain: f32,
+    /// Level of system audio (the other participants) in the meeting mix.
+    /// 1.0 (the default) keeps it as captured.
+    pub system_gain: f32,
+    /// How far system audio is lowered while the microphone picks up
+    /// speech: 0.0 (the default) leaves it alone, 1.0 mutes it.
+    pub ducking: f32,
+}
+
+impl Default for MeetingConfig {
+    fn default() -> Self {
+        Self {
+            diarize: false,
+            chunk_minutes: 0,
+            mic_gain: 1.0,
+            system_gain: 1.0,
+            ducking: 0.0,
+        }
+    }
+}
+
+/// The daemon's local HTTP API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// Port on 127.0.0.1 the daemon listens on and the CLI connects to.
+    /// `AUDETIC_API_PORT` overrides it (see [`crate::url::port`]).
+    pub port: u16,
+    /// When set, every API request except the service info and version
+    /// endpoints must carry `Authorization: Bearer <token>`. Unset leaves
+    /// the API open to anything on this machine.
+    pub auth_token: Option<String>,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            port: crate::url::DEFAULT_PORT,
+            auth_token: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct BehaviorConfig {
     pub auto_paste: bool,
     pub preserve_clipboard: bool,
+    /// Legacy switch, superseded by `keep_audio_on`: `true` behaves like
+    /// `on_failure`, `false` like `always`.
     pub delete_audio_files: bool,
+    /// Which dictation recordings to keep once transcription finishes. Unset
+    /// falls back to `delete_audio_files`.
+    pub keep_audio_on: Option<KeepAudio>,
     #[serde(default = "default_audio_feedback")]
     pub audio_feedback: bool,
+    /// Stamp injected dictation with the time it was transcribed. Unset
+    /// (the default) injects the transcript unchanged.
+    pub inject_timestamp: Option<InjectTimestampConfig>,
+    /// Files smaller than this are uploaded without compressing to mp3 first,
+    /// since ffmpeg can take longer than the upload it saves. 0 always
+    /// compresses.
+    pub compress_min_size_bytes: u64,
+    /// Milliseconds to wait before typing or pasting dictation, so the
+    /// target window can regain focus first. 0 (the default) doesn't wait.
+    pub paste_delay_ms: u64,
+    /// With `preserve_clipboard`, milliseconds to wait after pasting
+    /// dictation before putting the previous clipboard contents back, so the
+    /// target app has read the pasted text first.
+    pub clipboard_restore_delay_ms: u64,
+    /// Refuse to start when `[whisper].provider` is unset. Off by default:
+    /// the service starts without dictation so the provider can be set up
+    /// through the API.
+    pub require_provider: bool,
+    /// Global shortcut that toggles dictation, e.g. `"SUPER+R"`, caught by
+    /// the daemon itself instead of a window-manager keybind. Only honored
+    /// by builds with the `hotkey` feature. Unset (the default) listens for
+    /// nothing.
+    pub hotkey: Option<String>,
+}
+
+/// `[behavior].keep_audio_on`: which recordings survive transcription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepAudio {
+    Always,
+    Never,
+    /// Keep recordings whose transcription failed, for retrying or
+    /// inspecting; delete the rest.
+    OnFailure,
+    OnSuccess,
+}
+
+impl KeepAudio {
+    /// Whether to keep a recording whose transcription `succeeded` (an
+    /// empty "no speech" result counts as success).
+    pub fn keeps(self, succeeded: bool) -> bool {
+        match self {
+            KeepAudio::Always => true,
+            KeepAudio::Never => false,
+            KeepAudio::OnFailure => !succeeded,
+            KeepAudio::OnSuccess => succeeded,
+        }
+    }
+}
+
+impl BehaviorConfig {
+    /// The effective audio retention policy: `keep_audio_on` if set,
+    /// otherwise what `delete_audio_files` has always done — delete after a
+    /// successful transcription and leave failed recordings behind.
+    pub fn keep_audio(&self) -> KeepAudio {
+        match self.keep_audio_on {
+            Some(policy) => policy,
+            None if self.delete_audio_files => KeepAudio::OnFailure,
+            None => KeepAudio::Always,
+        }
+    }
+}
+
+/// `[behavior.inject_timestamp]`: how to stamp injected dictation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InjectTimestampConfig {
+    /// strftime-style format expanded in local time, e.g. `[%H:%M]`.
+    pub format: String,
+    /// Whether the stamp goes before or after the text.
+    pub position: TimestampPosition,
+    /// Also store the stamped text in history. Off by default so history
+    /// search and exports see the plain transcript.
+    pub include_in_history: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampPosition {
+    #[default]
+    Prefix,
+    Suffix,
+}
+
+impl Default for InjectTimestampConfig {
+    fn default() -> Self {
+        Self {
+            format: "[%H:%M]".to_string(),
+            position: TimestampPosition::Prefix,
+            include_in_history: false,
+        }
+    }
 }
 
 fn default_audio_feedback() -> bool {
@@ -72,6 +458,17 @@ impl Default for WhisperConfig {
             api_endpoint: None,
             provider: Some("audetic-api".to_string()),
             api_key: None,
+            connect_timeout_seconds: None,
+            request_timeout_seconds: None,
+            max_retries: None,
+            word_timestamps: false,
+            max_audio_seconds: None,
+            candidate_languages: None,
+            assemblyai_features: None,
+            cli_timeout_seconds: None,
+            cli_max_output_bytes: None,
+            stream_partials: false,
+            extra_headers: None,
         }
     }
 }
@@ -79,8 +476,10 @@ impl Default for WhisperConfig {
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
+            indicator: IndicatorStyle::default(),
             notification_color: "rgb(ff1744)".to_string(),
             waybar: WaybarConfig::default(),
+            sounds: SoundsConfig::default(),
         }
     }
 }
@@ -92,6 +491,8 @@ impl Default for WaybarConfig {
             recording_text: "󰻃".to_string(), // Nerd Font record button (recording)
             idle_tooltip: "Press Super+R to record".to_string(),
             recording_tooltip: "Recording... Press Super+R to stop".to_string(),
+            paused_text: "󰏤".to_string(), // Nerd Font pause (paused dictation)
+            paused_tooltip: "Recording paused".to_string(),
         }
     }
 }
@@ -110,43 +511,133 @@ impl Default for BehaviorConfig {
             auto_paste: true,
             preserve_clipboard: false,
             delete_audio_files: true,
+            keep_audio_on: None,
             audio_feedback: true,
+            inject_timestamp: None,
+            compress_min_size_bytes: crate::compression::DEFAULT_COMPRESS_MIN_SIZE_BYTES,
+            paste_delay_ms: 0,
+            clipboard_restore_delay_ms: 300,
+            require_provider: false,
+            hotkey: None,
         }
     }
 }
 
 impl Config {
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
+        Self::load_from(&Self::config_path()?)
+    }
+
+    /// Load from an explicit path, creating a default config there if it
+    /// doesn't exist yet. [`Config::load`] uses the user's config file.
+    pub fn load_from(config_path: &Path) -> Result<Self> {
         if !config_path.exists() {
             info!(
                 "Config file not found, creating default at {:?}",
                 config_path
             );
             let config = Self::default();
-            config.save()?;
+            config.save_to(config_path)?;
             return Ok(config);
         }
 
-        let content =
-            std::fs::read_to_string(&config_path).context("Failed to read config file")?;
+        let content = std::fs::read_to_string(config_path).context("Failed to read config file")?;
 
-        let config: Self = toml::from_str(&content).context("Failed to parse config file")?;
+        let (config, unknown_keys) = Self::parse_checked(&content)?;
+        for warning in unknown_keys {
+            warn!("Config: {}", warning);
+        }
 
         info!("Loaded config from {:?}", config_path);
         Ok(config)
     }
 
+    /// Parse config file `content`, along with a warning for each key in it
+    /// that Audetic doesn't read. Unknown keys are otherwise dropped without
+    /// complaint, so a typo would silently leave a setting at its default.
+    /// `[whisper].extra_headers` that couldn't be sent are an error.
+    pub fn parse_checked(content: &str) -> Result<(Self, Vec<String>)> {
+        let config: Self = toml::from_str(content).context("Failed to parse config file")?;
+        let table: toml::Table = toml::from_str(content).context("Failed to parse config file")?;
+        if let Some(headers) = &config.whisper.extra_headers {
+            crate::http::header_map(headers)?;
+        }
+        Ok((config, crate::config_keys::unknown_keys(&table)))
+    }
+
+    /// Non-fatal problems with the loaded settings, one message per issue.
+    /// Callers log these; an odd value is still passed through to providers.
+    pub fn validate(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(warning) = self
+            .whisper
+            .language
+            .as_deref()
+            .and_then(crate::language::check)
+        {
+            warnings.push(format!("[whisper].language: {warning}"));
+        }
+        if let Some(bits) = self.audio.bits_per_sample.filter(|b| !matches!(b, 16 | 32)) {
+            warnings.push(format!(
+                "[audio].bits_per_sample: {bits} is not supported; use 16 or 32. Recording at 32."
+            ));
+        }
+        for (key, gain) in [
+            ("mic_gain", self.meeting.mic_gain),
+            ("system_gain", self.meeting.system_gain),
+        ] {
+            if gain < 0.0 || !gain.is_finite() {
+                warnings.push(format!(
+                    "[meeting].{key}: {gain} is not a valid gain; use 0.0 or more. Mixing at 1.0."
+                ));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.meeting.ducking) {
+            warnings.push(format!(
+                "[meeting].ducking: {} is outside 0.0-1.0; clamping it.",
+                self.meeting.ducking
+            ));
+        }
+        if let Some(token) = self.api.auth_token.as_deref() {
+            if token
+                .chars()
+                .any(|c| c.is_whitespace() || c == '\'' || c == '"')
+            {
+                warnings.push(
+                    "[api].auth_token: contains spaces or quotes, which break the curl \
+                     commands used by keybinds; use letters, digits, and punctuation like -_."
+                        .to_string(),
+                );
+            }
+        }
+        for (i, rule) in self.normalizer.rules.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(&rule.pattern) {
+                warnings.push(format!(
+                    "[normalizer].rules[{i}]: invalid pattern `{}`: {e}",
+                    rule.pattern
+                ));
+            }
+        }
+        for candidate in self.whisper.candidate_languages.iter().flatten() {
+            if let Some(warning) = crate::language::check(candidate) {
+                warnings.push(format!("[whisper].candidate_languages: {warning}"));
+            }
+        }
+        warnings
+    }
+
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path()?;
+        self.save_to(&Self::config_path()?)
+    }
 
+    pub fn save_to(&self, config_path: &Path) -> Result<()> {
         if let Some(parent) = config_path.parent() {
             std::fs::create_dir_all(parent).context("Failed to create config directory")?;
         }
 
         let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
 
-        std::fs::write(&config_path, content).context("Failed to write config file")?;
+        std::fs::write(config_path, content).context("Failed to write config file")?;
 
         Ok(())
     }
@@ -154,4 +645,407 @@ impl Config {
     fn config_path() -> Result<PathBuf> {
         global::config_file()
     }
+
+    fn to_table(&self) -> Result<toml::Table> {
+        match toml::Value::try_from(self).context("Failed to serialize config")? {
+            toml::Value::Table(table) => Ok(table),
+            _ => anyhow::bail!("Config did not serialize to a TOML table"),
+        }
+    }
+
+    /// Serialize for sharing (`audetic config export`). Credentials listed in
+    /// [`SECRET_FIELDS`] are dropped unless `with_secrets` is set.
+    pub fn to_export_toml(&self, with_secrets: bool) -> Result<String> {
+        let mut table = self.to_table()?;
+        if !with_secrets {
+            for (section, key) in SECRET_FIELDS {
+                if let Some(section) = table.get_mut(*section).and_then(|v| v.as_table_mut()) {
+                    section.remove(*key);
+                }
+            }
+        }
+        toml::to_string_pretty(&table).context("Failed to serialize config")
+    }
+
+    /// The config that importing `content` over `self` produces. With `merge`,
+    /// only the settings present in `content` change and existing secrets are
+    /// never overwritten; without it, `content` replaces the config outright
+    /// (settings it omits fall back to defaults).
+    pub fn import_toml(&self, content: &str, merge: bool) -> Result<Config> {
+        let imported: toml::Table =
+            toml::from_str(content).context("Imported file is not valid TOML")?;
+
+        let merged = if merge {
+            let mut merged = self.to_table()?;
+            let current = merged.clone();
+            merge_tables(&mut merged, imported);
+            for (section, key) in SECRET_FIELDS {
+                let existing = current.get(*section).and_then(|t| t.get(*key));
+                let table = merged.get_mut(*section).and_then(|v| v.as_table_mut());
+                if let (Some(existing), Some(table)) = (existing, table) {
+                    table.insert(key.to_string(), existing.clone());
+                }
+            }
+            merged
+        } else {
+            imported
+        };
+
+        toml::Value::Table(merged)
+            .try_into::<Config>()
+            .context("Imported file is not a valid Audetic config")
+    }
+}
+
+/// Validate provider configuration and return an error message if invalid.
+/// Shared by the daemon (imports, reloads, the provider wizard) and
+/// `audetic config check`.
+pub fn validate_provider_config(provider: &str, whisper: &WhisperConfig) -> Option<String> {
+    match provider {
+        "audetic-api" => None, // No additional config required
+        "assembly-ai" => {
+            if whisper.api_key.is_none() {
+                Some("API key required for AssemblyAI".to_string())
+            } else {
+                None
+            }
+        }
+        "deepgram" => {
+            if whisper.api_key.is_none() {
+                Some("API key required for Deepgram".to_string())
+            } else {
+                None
+            }
+        }
+        "openai-api" => {
+            if whisper.api_key.is_none() {
+                Some("API key required for OpenAI API".to_string())
+            } else {
+                None
+            }
+        }
+        "groq" => {
+            if whisper.api_key.is_none() {
+                Some("API key required for Groq".to_string())
+            } else {
+                None
+            }
+        }
+        "openai-cli" => {
+            if whisper.command_path.is_none() {
+                Some("Command path required for OpenAI CLI".to_string())
+            } else {
+                None
+            }
+        }
+        "whisper-cpp" => {
+            if whisper.command_path.is_none() {
+                Some("Command path required for whisper.cpp".to_string())
+            } else if whisper.model_path.is_none() {
+                Some("Model path required for whisper.cpp".to_string())
+            } else {
+                None
+            }
+        }
+        "local" => {
+            // A model is selected by id and downloaded into the data dir; the
+            // engine is linked in-process, so no command/model path is needed.
+            let model_id = whisper
+                .model
+                .as_deref()
+                .unwrap_or(crate::local_models::DEFAULT_MODEL_ID);
+            match crate::local_models::find(model_id) {
+                Some(model) => match global::data_dir() {
+                    Ok(data_dir) if crate::local_models::is_installed(&data_dir, model) => None,
+                    Ok(_) => Some(format!(
+                        "Local model '{model_id}' is not downloaded yet. Run `audetic models download {model_id}`."
+                    )),
+                    Err(e) => Some(format!("Could not resolve data directory: {e}")),
+                },
+                None => Some(format!("Unknown local model '{model_id}'.")),
+            }
+        }
+        _ => Some(format!("Unknown provider: {}", provider)),
+    }
+}
+
+/// Credentials stripped from exports and protected on merge-imports, as
+/// `(section, key)` pairs in `config.toml`.
+pub const SECRET_FIELDS: &[(&str, &str)] = &[
+    ("whisper", "api_key"),
+    ("whisper", "extra_headers"),
+    ("api", "auth_token"),
+];
+
+/// Overlay `overlay` onto `base`, recursing into tables present in both.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(incoming)) => {
+                merge_tables(existing, incoming);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_language(language: &str) -> Config {
+        Config {
+            whisper: WhisperConfig {
+                language: Some(language.to_string()),
+                ..WhisperConfig::default()
+            },
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn inject_timestamp_parses_from_toml() {
+        let config: Config = toml::from_str(
+            "[behavior.inject_timestamp]\nformat = \"%Y-%m-%d\"\nposition = \"suffix\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.behavior.inject_timestamp,
+            Some(InjectTimestampConfig {
+                format: "%Y-%m-%d".to_string(),
+                position: TimestampPosition::Suffix,
+                include_in_history: false,
+            })
+        );
+        assert_eq!(Config::default().behavior.inject_timestamp, None);
+    }
+
+    #[test]
+    fn keep_audio_modes_by_outcome() {
+        use KeepAudio::*;
+        // (policy, keeps on success, keeps on failure)
+        for (policy, success, failure) in [
+            (Always, true, true),
+            (Never, false, false),
+            (OnFailure, false, true),
+            (OnSuccess, true, false),
+        ] {
+            assert_eq!(policy.keeps(true), success, "{policy:?} on success");
+            assert_eq!(policy.keeps(false), failure, "{policy:?} on failure");
+        }
+    }
+
+    #[test]
+    fn keep_audio_on_supersedes_legacy_boolean() {
+        let config: Config =
+            toml::from_str("[behavior]\ndelete_audio_files = true\nkeep_audio_on = \"never\"\n")
+                .unwrap();
+        assert_eq!(config.behavior.keep_audio(), KeepAudio::Never);
+
+        let config: Config =
+            toml::from_str("[behavior]\nkeep_audio_on = \"on_success\"\n").unwrap();
+        assert_eq!(config.behavior.keep_audio(), KeepAudio::OnSuccess);
+    }
+
+    #[test]
+    fn keep_audio_maps_legacy_boolean() {
+        assert_eq!(
+            Config::default().behavior.keep_audio(),
+            KeepAudio::OnFailure
+        );
+
+        let config: Config = toml::from_str("[behavior]\ndelete_audio_files = false\n").unwrap();
+        assert_eq!(config.behavior.keep_audio(), KeepAudio::Always);
+    }
+
+    #[test]
+    fn invalid_extra_headers_fail_to_load() {
+        let (config, _) =
+            Config::parse_checked("[whisper.extra_headers]\nx-api-gateway-key = \"abc\"\n")
+                .unwrap();
+        assert_eq!(
+            config.whisper.extra_headers.unwrap()["x-api-gateway-key"],
+            "abc"
+        );
+
+        let err = Config::parse_checked("[whisper.extra_headers]\n\"bad header\" = \"abc\"\n")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[whisper].extra_headers: invalid header name `bad header`"
+        );
+
+        let err = Config::parse_checked("[whisper.extra_headers]\nx-key = \"line\\nbreak\"\n")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[whisper].extra_headers: invalid value for header `x-key`"
+        );
+    }
+
+    fn with_api_key(key: &str) -> Config {
+        Config {
+            whisper: WhisperConfig {
+                provider: Some("openai-api".to_string()),
+                api_key: Some(key.to_string()),
+                ..WhisperConfig::default()
+            },
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn export_omits_secrets_by_default() {
+        let mut config = with_api_key("sk-team-secret");
+        config.api.auth_token = Some("local-token".to_string());
+        let exported = config.to_export_toml(false).unwrap();
+        assert!(!exported.contains("sk-team-secret"));
+        assert!(!exported.contains("api_key"));
+        assert!(!exported.contains("local-token"));
+        assert!(exported.contains("openai-api"));
+    }
+
+    #[test]
+    fn export_with_secrets_includes_key() {
+        let exported = with_api_key("sk-team-secret").to_export_toml(true).unwrap();
+        assert!(exported.contains("api_key = \"sk-team-secret\""));
+    }
+
+    #[test]
+    fn merge_import_keeps_existing_key() {
+        let current = with_api_key("sk-mine");
+        let shared = "[whisper]\nmodel = \"whisper-1\"\napi_key = \"sk-theirs\"\n\n\
+                      [behavior]\nauto_paste = false\n";
+
+        let imported = current.import_toml(shared, true).unwrap();
+        assert_eq!(imported.whisper.api_key.as_deref(), Some("sk-mine"));
+        assert_eq!(imported.whisper.model.as_deref(), Some("whisper-1"));
+        assert!(!imported.behavior.auto_paste);
+        // Settings the file doesn't mention are left alone.
+        assert_eq!(imported.whisper.provider.as_deref(), Some("openai-api"));
+    }
+
+    #[test]
+    fn replace_import_uses_file_as_is() {
+        let current = with_api_key("sk-mine");
+        let imported = current
+            .import_toml("[whisper]\nprovider = \"audetic-api\"\n", false)
+            .unwrap();
+        assert_eq!(imported.whisper.api_key, None);
+        assert_eq!(imported.whisper.provider.as_deref(), Some("audetic-api"));
+        assert!(imported.behavior.auto_paste);
+    }
+
+    #[test]
+    fn import_rejects_invalid_files() {
+        let current = Config::default();
+        assert!(current.import_toml("not = [valid", true).is_err());
+        assert!(current
+            .import_toml("[behavior]\nauto_paste = \"yes\"\n", true)
+            .is_err());
+    }
+
+    #[test]
+    fn history_cap_defaults_and_zero_means_unlimited() {
+        assert_eq!(
+            Config::default().history.max_entries,
+            DEFAULT_MAX_HISTORY_ENTRIES
+        );
+        let config: Config = toml::from_str("[history]\nindex_segments = true\n").unwrap();
+        assert_eq!(config.history.max_entries, DEFAULT_MAX_HISTORY_ENTRIES);
+        let config: Config = toml::from_str("[history]\nmax_entries = 0\n").unwrap();
+        assert_eq!(config.history.max_entries, 0);
+    }
+
+    #[test]
+    fn normalizer_rules_parse_in_order_and_bad_patterns_are_flagged() {
+        let config: Config = toml::from_str(
+            r#"
+[normalizer]
+sentence_case = true
+
+[normalizer.words]
+github = "GitHub"
+
+[[normalizer.rules]]
+pattern = '\s*\bcomma\b'
+replace = ","
+
+[[normalizer.rules]]
+pattern = "(unclosed"
+"#,
+        )
+        .unwrap();
+        assert!(config.normalizer.sentence_case);
+        assert_eq!(config.normalizer.words["github"], "GitHub");
+        assert_eq!(config.normalizer.rules[0].pattern, r"\s*\bcomma\b");
+        assert_eq!(config.normalizer.rules[0].replace, ",");
+        // `replace` defaults to deleting the match.
+        assert_eq!(config.normalizer.rules[1].replace, "");
+
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(
+            warnings[0].starts_with("[normalizer].rules[1]"),
+            "{warnings:?}"
+        );
+    }
+
+    #[test]
+    fn validate_accepts_known_languages() {
+        for language in ["en", "es", "auto"] {
+            assert!(with_language(language).validate().is_empty());
+        }
+    }
+
+    #[test]
+    fn validate_flags_unknown_languages() {
+        for language in ["english", "eng"] {
+            let warnings = with_language(language).validate();
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0].contains("did you mean 'en'"));
+        }
+    }
+
+    #[test]
+    fn validate_flags_unknown_candidate_languages() {
+        let mut config = Config::default();
+        config.whisper.candidate_languages = Some(vec!["en".to_string(), "spa".to_string()]);
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("[whisper].candidate_languages"));
+        assert!(warnings[0].contains("did you mean 'es'"));
+    }
+
+    #[test]
+    fn bits_per_sample_defaults_to_32_and_flags_odd_values() {
+        let mut config = Config::default();
+        assert_eq!(config.audio.bits_per_sample(), 32);
+
+        config.audio.bits_per_sample = Some(16);
+        assert_eq!(config.audio.bits_per_sample(), 16);
+        assert!(config.validate().is_empty());
+
+        config.audio.bits_per_sample = Some(24);
+        assert_eq!(config.audio.bits_per_sample(), 32);
+        assert!(config.validate()[0].starts_with("[audio].bits_per_sample"));
+    }
+
+    #[test]
+    fn meeting_mix_levels_default_to_unity_and_flag_odd_values() {
+        let mut config: Config = toml::from_str("[meeting]\nmic_gain = 1.5\n").unwrap();
+        assert_eq!(config.meeting.mic_gain, 1.5);
+        assert_eq!(config.meeting.system_gain, 1.0);
+        assert_eq!(config.meeting.ducking, 0.0);
+        assert!(config.validate().is_empty());
+
+        config.meeting.system_gain = -0.5;
+        config.meeting.ducking = 1.5;
+        let warnings = config.validate();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("[meeting].system_gain"));
+        assert!(warnings[1].starts_with("[meeting].ducking"));
+    }
 }
@@ -0,0 +1,246 @@
+//! Typo detection for `config.toml`.
+//!
+//! Every config section is `#[serde(default)]`, so a key serde doesn't know
+//! is skipped without a word: `provder = "deepgram"` loads as if the line
+//! weren't there. This module knows the keys of each section — read off the
+//! serde derives, so the list can't fall behind the structs — and reports
+//! the ones that aren't among them, with the closest known key.
+
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+
+use crate::config::{
+    ApiConfig, AudioConfig, BehaviorConfig, Config, HistoryConfig, InjectTimestampConfig,
+    MeetingConfig, NetworkConfig, NormalizerConfig, ReplaceRule, SoundsConfig, TranscribeConfig,
+    UiConfig, UpdaterConfig, WaybarConfig, WaylandConfig, WhisperConfig,
+};
+
+/// The tables of `config.toml` with a fixed set of keys, by dotted path
+/// (`""` is the top level). Tables not listed here, such as
+/// `[normalizer.words]` and `[whisper.assemblyai_features]`, take any key.
+fn known_tables() -> Vec<(&'static str, &'static [&'static str])> {
+    vec![
+        ("", fields::<Config>()),
+        ("whisper", fields::<WhisperConfig>()),
+        ("ui", fields::<UiConfig>()),
+        ("ui.waybar", fields::<WaybarConfig>()),
+        ("ui.sounds", fields::<SoundsConfig>()),
+        ("wayland", fields::<WaylandConfig>()),
+        ("behavior", fields::<BehaviorConfig>()),
+        (
+            "behavior.inject_timestamp",
+            fields::<InjectTimestampConfig>(),
+        ),
+        ("network", fields::<NetworkConfig>()),
+        ("history", fields::<HistoryConfig>()),
+        ("normalizer", fields::<NormalizerConfig>()),
+        ("normalizer.rules", fields::<ReplaceRule>()),
+        ("audio", fields::<AudioConfig>()),
+        ("transcribe", fields::<TranscribeConfig>()),
+        ("update", fields::<UpdaterConfig>()),
+        ("meeting", fields::<MeetingConfig>()),
+        ("api", fields::<ApiConfig>()),
+    ]
+}
+
+/// One warning per key in `config` (a whole parsed `config.toml`) that
+/// Audetic doesn't read, suggesting the closest known key when one is near.
+pub fn unknown_keys(config: &toml::Table) -> Vec<String> {
+    let tables = known_tables();
+    let mut warnings = Vec::new();
+    check_table(&tables, "", config, &mut warnings);
+    warnings
+}
+
+fn check_table(
+    tables: &[(&str, &[&str])],
+    path: &str,
+    table: &toml::Table,
+    warnings: &mut Vec<String>,
+) {
+    let Some((_, known)) = tables.iter().find(|(p, _)| *p == path) else {
+        return;
+    };
+    for (key, value) in table {
+        if !known.contains(&key.as_str()) {
+            let hint = closest(key, known)
+                .map(|k| format!(" (did you mean `{k}`?)"))
+                .unwrap_or_default();
+            warnings.push(if path.is_empty() {
+                format!("[{key}]: unknown section, ignored{hint}")
+            } else {
+                format!("[{path}].{key}: unknown key, ignored{hint}")
+            });
+            continue;
+        }
+
+        let child = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        match value {
+            toml::Value::Table(inner) => check_table(tables, &child, inner, warnings),
+            toml::Value::Array(items) => {
+                for item in items {
+                    if let toml::Value::Table(inner) = item {
+                        check_table(tables, &child, inner, warnings);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The entry of `candidates` nearest to `input` by edit distance, if it's
+/// close enough to be a plausible typo.
+pub(crate) fn closest<'a>(input: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let input = input.to_lowercase();
+    let allowed = (input.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(&input, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= allowed)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The field names `T`'s serde derive accepts. The derive hands them to
+/// `deserialize_struct`, which [`FieldNames`] records before bailing out.
+fn fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("field names recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sorted, since table key order depends on toml's features.
+    fn warnings_for(content: &str) -> Vec<String> {
+        let mut warnings = unknown_keys(&toml::from_str(content).unwrap());
+        warnings.sort();
+        warnings
+    }
+
+    #[test]
+    fn typos_are_flagged_with_the_closest_key() {
+        let warnings = warnings_for(
+            r#"
+            [whisper]
+            provder = "deepgram"
+            language = "en"
+
+            [behaviour]
+            auto_paste = true
+
+            [ui.waybar]
+            idle_txt = "x"
+            "#,
+        );
+        assert_eq!(
+            warnings,
+            [
+                "[behaviour]: unknown section, ignored (did you mean `behavior`?)",
+                "[ui.waybar].idle_txt: unknown key, ignored (did you mean `idle_text`?)",
+                "[whisper].provder: unknown key, ignored (did you mean `provider`?)",
+            ]
+        );
+    }
+
+    #[test]
+    fn far_off_keys_get_no_suggestion() {
+        assert_eq!(
+            warnings_for("[audio]\nsomething_else = 1"),
+            ["[audio].something_else: unknown key, ignored"]
+        );
+    }
+
+    #[test]
+    fn a_default_config_has_no_unknown_keys() {
+        let content = toml::to_string(&Config::default()).unwrap();
+        assert!(warnings_for(&content).is_empty());
+    }
+
+    #[test]
+    fn free_form_tables_take_any_key_and_rule_entries_are_checked() {
+        let warnings = warnings_for(
+            r#"
+            [whisper.assemblyai_features]
+            anything_goes = true
+
+            [normalizer.words]
+            github = "GitHub"
+
+            [[normalizer.rules]]
+            pattern = "a"
+            replace = "b"
+
+            [[normalizer.rules]]
+            pattern = "c"
+            replce = "d"
+            "#,
+        );
+        assert_eq!(
+            warnings,
+            ["[normalizer.rules].replce: unknown key, ignored (did you mean `replace`?)"]
+        );
+    }
+
+    #[test]
+    fn field_names_come_from_the_serde_derive() {
+        assert!(fields::<Config>().contains(&"whisper"));
+        assert!(fields::<WhisperConfig>().contains(&"model_path"));
+        assert!(fields::<BehaviorConfig>().contains(&"inject_timestamp"));
+    }
+
+    #[test]
+    fn edit_distance_counts_characters() {
+        assert_eq!(edit_distance("provder", "provider"), 1);
+        assert_eq!(edit_distance("modle", "model"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("héllo", "hello"), 1);
+    }
+}
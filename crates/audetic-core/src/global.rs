@@ -1,12 +1,113 @@
-use anyhow::{anyhow, Context, Result};
-use std::path::PathBuf;
+//! Where Audetic keeps its config and data.
+//!
+//! Every path the daemon and CLI persist to derives from [`config_dir`] or
+//! [`data_dir`], resolved in the same order everywhere:
+//!
+//! 1. `AUDETIC_CONFIG_DIR` / `AUDETIC_DATA_DIR`, used as-is.
+//! 2. `$XDG_CONFIG_HOME/audetic` / `$XDG_DATA_HOME/audetic` (absolute paths
+//!    only, per the XDG spec). On Linux and the BSDs always; on macOS and
+//!    Windows, where XDG isn't the native layout, only when that directory
+//!    already exists, so a stray `XDG_*` variable doesn't silently move an
+//!    existing install away from its platform directory.
+//! 3. The platform default from `dirs` (`~/.config`, `~/.local/share`,
+//!    `~/Library/Application Support`, `%APPDATA%`), plus `audetic`.
+//! 4. `~/.config/audetic` / `~/.local/share/audetic`.
+//!
+//! If none resolve (no home directory, as in some containers) the lookup
+//! fails with an error naming the override variable, rather than silently
+//! writing somewhere temporary.
+
+use anyhow::{anyhow, Result};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 
 const APP_DIR: &str = "audetic";
 
+/// Overrides the config directory (holds `config.toml`).
+pub const CONFIG_DIR_ENV: &str = "AUDETIC_CONFIG_DIR";
+/// Overrides the data directory (database, models, meetings, backups).
+pub const DATA_DIR_ENV: &str = "AUDETIC_DATA_DIR";
+
+/// XDG isn't the native layout on these platforms; see the module docs.
+const XDG_REQUIRES_EXISTING: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+/// One directory's lookup rules; see the module docs for the order.
+struct DirSpec {
+    override_var: &'static str,
+    xdg_var: &'static str,
+    /// Whether the XDG directory is only used when it already exists.
+    xdg_requires_existing: bool,
+    platform_default: fn() -> Option<PathBuf>,
+    home_relative: &'static [&'static str],
+    kind: &'static str,
+}
+
+const CONFIG_DIR: DirSpec = DirSpec {
+    override_var: CONFIG_DIR_ENV,
+    xdg_var: "XDG_CONFIG_HOME",
+    xdg_requires_existing: XDG_REQUIRES_EXISTING,
+    platform_default: dirs::config_dir,
+    home_relative: &[".config"],
+    kind: "config",
+};
+
+const DATA_DIR: DirSpec = DirSpec {
+    override_var: DATA_DIR_ENV,
+    xdg_var: "XDG_DATA_HOME",
+    xdg_requires_existing: XDG_REQUIRES_EXISTING,
+    platform_default: dirs::data_dir,
+    home_relative: &[".local", "share"],
+    kind: "data",
+};
+
+impl DirSpec {
+    fn resolve(
+        &self,
+        env: impl Fn(&str) -> Option<OsString>,
+        home: Option<PathBuf>,
+        exists: impl Fn(&Path) -> bool,
+    ) -> Result<PathBuf> {
+        let var = |name: &str| {
+            env(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+
+        if let Some(dir) = var(self.override_var) {
+            return Ok(dir);
+        }
+        if let Some(dir) = var(self.xdg_var)
+            .filter(|p| p.is_absolute())
+            .map(|base| base.join(APP_DIR))
+            .filter(|dir| !self.xdg_requires_existing || exists(dir))
+        {
+            return Ok(dir);
+        }
+        if let Some(base) = (self.platform_default)() {
+            return Ok(base.join(APP_DIR));
+        }
+        if let Some(home) = home {
+            let base = self.home_relative.iter().fold(home, |p, c| p.join(c));
+            return Ok(base.join(APP_DIR));
+        }
+        Err(anyhow!(
+            "Unable to determine {} directory: no home directory found. Set {} to choose one.",
+            self.kind,
+            self.override_var
+        ))
+    }
+}
+
+fn resolve(spec: &DirSpec) -> Result<PathBuf> {
+    spec.resolve(
+        |name| std::env::var_os(name),
+        dirs::home_dir(),
+        Path::is_dir,
+    )
+}
+
 pub fn config_dir() -> Result<PathBuf> {
-    dirs::config_dir()
-        .map(|dir| dir.join(APP_DIR))
-        .context("Unable to determine config directory")
+    resolve(&CONFIG_DIR)
 }
 
 pub fn config_file() -> Result<PathBuf> {
@@ -14,13 +115,7 @@ pub fn config_file() -> Result<PathBuf> {
 }
 
 pub fn data_dir() -> Result<PathBuf> {
-    if let Some(dir) = dirs::data_dir() {
-        return Ok(dir.join(APP_DIR));
-    }
-    if let Some(home) = dirs::home_dir() {
-        return Ok(home.join(".local").join("share").join(APP_DIR));
-    }
-    Err(anyhow!("Unable to determine data directory"))
+    resolve(&DATA_DIR)
 }
 
 pub fn updates_dir() -> Result<PathBuf> {
@@ -38,3 +133,105 @@ pub fn update_lock_file() -> Result<PathBuf> {
 pub fn db_file() -> Result<PathBuf> {
     Ok(data_dir()?.join("audetic.db"))
 }
+
+/// Durable home for meeting recordings and imported meeting audio.
+pub fn meetings_dir() -> Result<PathBuf> {
+    Ok(data_dir()?.join("meetings"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: HashMap<String, OsString> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn no_dirs(_: &Path) -> bool {
+        false
+    }
+
+    /// `DATA_DIR` with the platform default stubbed out, to exercise the
+    /// home-directory fallback.
+    const DATA_DIR_NO_PLATFORM: DirSpec = DirSpec {
+        platform_default: || None,
+        ..DATA_DIR
+    };
+
+    #[test]
+    fn audetic_override_takes_precedence() {
+        let vars = env(&[
+            ("AUDETIC_DATA_DIR", "/srv/audetic-data"),
+            ("XDG_DATA_HOME", "/xdg/data"),
+        ]);
+        let dir = DATA_DIR
+            .resolve(vars, Some("/home/me".into()), no_dirs)
+            .unwrap();
+        assert_eq!(dir, PathBuf::from("/srv/audetic-data"));
+
+        let vars = env(&[("AUDETIC_CONFIG_DIR", "/etc/audetic")]);
+        let dir = CONFIG_DIR.resolve(vars, None, no_dirs).unwrap();
+        assert_eq!(dir, PathBuf::from("/etc/audetic"));
+    }
+
+    #[test]
+    fn xdg_base_is_next() {
+        let vars = env(&[("XDG_CONFIG_HOME", "/xdg/config")]);
+        let linux = DirSpec {
+            xdg_requires_existing: false,
+            ..CONFIG_DIR
+        };
+        let dir = linux
+            .resolve(vars, Some("/home/me".into()), no_dirs)
+            .unwrap();
+        assert_eq!(dir, PathBuf::from("/xdg/config/audetic"));
+    }
+
+    #[test]
+    fn xdg_must_already_exist_where_it_is_not_native() {
+        const CONFIG_DIR_MACOS: DirSpec = DirSpec {
+            xdg_requires_existing: true,
+            platform_default: || Some("/Users/me/Library/Application Support".into()),
+            ..CONFIG_DIR
+        };
+        let vars = || env(&[("XDG_CONFIG_HOME", "/xdg/config")]);
+
+        let dir = CONFIG_DIR_MACOS.resolve(vars(), None, no_dirs).unwrap();
+        assert_eq!(
+            dir,
+            PathBuf::from("/Users/me/Library/Application Support/audetic")
+        );
+
+        let dir = CONFIG_DIR_MACOS
+            .resolve(vars(), None, |dir| dir == Path::new("/xdg/config/audetic"))
+            .unwrap();
+        assert_eq!(dir, PathBuf::from("/xdg/config/audetic"));
+    }
+
+    #[test]
+    fn empty_and_relative_values_are_ignored() {
+        let vars = env(&[("AUDETIC_DATA_DIR", ""), ("XDG_DATA_HOME", "relative/data")]);
+        let dir = DATA_DIR_NO_PLATFORM
+            .resolve(vars, Some("/home/me".into()), no_dirs)
+            .unwrap();
+        assert_eq!(dir, PathBuf::from("/home/me/.local/share/audetic"));
+    }
+
+    #[test]
+    fn falls_back_to_home_then_errors() {
+        let dir = DATA_DIR_NO_PLATFORM
+            .resolve(env(&[]), Some("/home/me".into()), no_dirs)
+            .unwrap();
+        assert_eq!(dir, PathBuf::from("/home/me/.local/share/audetic"));
+
+        let err = DATA_DIR_NO_PLATFORM
+            .resolve(env(&[]), None, no_dirs)
+            .unwrap_err();
+        assert!(err.to_string().contains("AUDETIC_DATA_DIR"));
+    }
+}
@@ -0,0 +1,432 @@
+//! Shared HTTP client construction.
+//!
+//! Outbound clients (providers, the jobs client, the update engine, model
+//! downloads) are built from [`HttpOptions`] rather than
+//! `reqwest::Client::new()` so network settings apply the same way everywhere.
+//!
+//! Proxy resolution: `[network].proxy` wins when set. Otherwise reqwest's
+//! default behaviour applies, honoring `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY`
+//! and `NO_PROXY` from the environment.
+//!
+//! TLS: `[network].ca_cert_path` adds a private root on top of the bundled
+//! roots; `[network].danger_accept_invalid_certs` disables verification
+//! entirely and logs a warning every time a client is built with it.
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::config::Config;
+
+/// Connect timeout used when `[whisper].connect_timeout_seconds` is unset.
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+/// Request timeout for provider calls when
+/// `[whisper].request_timeout_seconds` is unset.
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 120;
+
+/// Provider retries used when `[whisper].max_retries` is unset.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Network settings applied to every outbound `reqwest::Client`.
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    /// Upper bound on establishing the TCP/TLS connection. Kept separate from
+    /// any overall request timeout so an unreachable endpoint fails fast while
+    /// slow transcriptions can still take their time.
+    pub connect_timeout: Duration,
+    /// Upper bound on a whole request, from connecting to the last byte of
+    /// the response. `None` (the default) leaves requests unbounded for
+    /// callers that stream large bodies, like model downloads; providers set
+    /// it from `[whisper].request_timeout_seconds`.
+    pub request_timeout: Option<Duration>,
+    /// Proxy URL for all requests (`http://`, `https://`, `socks5://`,
+    /// `socks5h://`).
+    pub proxy: Option<String>,
+    /// Comma-separated hosts/domains/CIDRs that bypass `proxy`. Falls back to
+    /// the `NO_PROXY` environment variable.
+    pub no_proxy: Option<String>,
+    /// PEM-encoded root certificate trusted in addition to the defaults.
+    pub ca_cert_path: Option<PathBuf>,
+    /// Accept any server certificate. Lab use only.
+    pub danger_accept_invalid_certs: bool,
+    /// Cap on concurrently open jobs-API jobs. Only `JobsClient` reads this.
+    pub max_concurrent_jobs: Option<usize>,
+    /// Retries for rate-limited, failed (5xx), or timed-out provider calls;
+    /// audio uploads are only retried when they never reached the server.
+    /// Only the HTTP transcription providers read this.
+    pub max_retries: u32,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            request_timeout: None,
+            proxy: None,
+            no_proxy: None,
+            ca_cert_path: None,
+            danger_accept_invalid_certs: false,
+            max_concurrent_jobs: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+}
+
+impl HttpOptions {
+    /// Options with the given connect timeout, falling back to the default.
+    pub fn with_connect_timeout_secs(secs: Option<u64>) -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(secs.unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS)),
+            ..Self::default()
+        }
+    }
+
+    /// Options from the `[whisper]` timeout and `[network]` settings.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            proxy: config.network.proxy.clone().filter(|p| !p.is_empty()),
+            no_proxy: config.network.no_proxy.clone(),
+            ca_cert_path: config
+                .network
+                .ca_cert_path
+                .as_deref()
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from),
+            danger_accept_invalid_certs: config.network.danger_accept_invalid_certs,
+            max_concurrent_jobs: config.network.max_concurrent_jobs.filter(|&n| n > 0),
+            ..Self::with_connect_timeout_secs(config.whisper.connect_timeout_seconds)
+        }
+    }
+
+    /// A client builder with these options applied, for callers that need to
+    /// layer on extra settings.
+    pub fn client_builder(&self) -> Result<reqwest::ClientBuilder> {
+        let mut builder = reqwest::Client::builder().connect_timeout(self.connect_timeout);
+
+        if let Some(timeout) = self.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        if let Some(url) = &self.proxy {
+            let no_proxy = match &self.no_proxy {
+                Some(list) => reqwest::NoProxy::from_string(list),
+                None => reqwest::NoProxy::from_env(),
+            };
+            let proxy = reqwest::Proxy::all(url)
+                .with_context(|| format!("Invalid [network].proxy URL: {url}"))?
+                .no_proxy(no_proxy);
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(path) = &self.ca_cert_path {
+            let pem = std::fs::read(path).with_context(|| {
+                format!("Failed to read [network].ca_cert_path {}", path.display())
+            })?;
+            let cert = reqwest::Certificate::from_pem(&pem)
+                .with_context(|| format!("Invalid PEM certificate in {}", path.display()))?;
+            builder = builder.add_root_certificate(cert);
+        }
+
+        if self.danger_accept_invalid_certs {
+            warn!(
+                "TLS CERTIFICATE VERIFICATION IS DISABLED ([network].danger_accept_invalid_certs = true). \
+                 Connections can be intercepted; use only against trusted lab servers."
+            );
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        Ok(builder)
+    }
+
+    /// `err` under `context`, saying plainly when a timeout cut the request
+    /// off; reqwest only mentions that deep in the source chain.
+    pub fn request_error(&self, err: reqwest::Error, context: &str) -> anyhow::Error {
+        let message = if !err.is_timeout() {
+            context.to_string()
+        } else if err.is_connect() {
+            format!(
+                "{context}: timed out connecting after {:?}",
+                self.connect_timeout
+            )
+        } else if let Some(limit) = self.request_timeout {
+            format!("{context}: request timed out after {limit:?}")
+        } else {
+            format!("{context}: request timed out")
+        };
+        anyhow::Error::new(err).context(message)
+    }
+
+    pub fn build_client(&self) -> Result<reqwest::Client> {
+        self.client_builder()?
+            .build()
+            .context("Failed to create HTTP client")
+    }
+}
+
+/// `[whisper].extra_headers` as a header map for
+/// `RequestBuilder::headers`, which replaces same-named headers already set.
+/// Values are left out of errors since they often hold credentials.
+pub fn header_map(headers: &BTreeMap<String, String>) -> Result<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let header = HeaderName::from_bytes(name.as_bytes())
+            .with_context(|| format!("[whisper].extra_headers: invalid header name `{name}`"))?;
+        let value = HeaderValue::from_str(value).with_context(|| {
+            format!("[whisper].extra_headers: invalid value for header `{name}`")
+        })?;
+        map.insert(header, value);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Self-signed "Audetic Test CA" root, used only to check loading.
+    const TEST_CA_PEM: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIBizCCATGgAwIBAgIUZpDnCp7Lahxy0Q9CJVAxjhtWNscwCgYIKoZIzj0EAwIw\n\
+GjEYMBYGA1UEAwwPQXVkZXRpYyBUZXN0IENBMCAXDTI2MTAxNjEwMDM0MloYDzIx\n\
+MjYwOTIyMTAwMzQyWjAaMRgwFgYDVQQDDA9BdWRldGljIFRlc3QgQ0EwWTATBgcq\n\
+hkjOPQIBBggqhkjOPQMBBwNCAARndDdTpyhIuvyvLpJddm1uPttxIXRLAFBJfXuD\n\
+hniLI/s+uPk8vTN6eDmhgaWPQv5YqBWTKnQHfRXq0qdHW1y5o1MwUTAdBgNVHQ4E\n\
+FgQU+aWvgoWlCDetykKVQqA/o8SpnN0wHwYDVR0jBBgwFoAU+aWvgoWlCDetykKV\n\
+QqA/o8SpnN0wDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiAmuzGh\n\
+9lr1Qk7OHoE82kcC1u+UvfY7tZ+C26v55KAdigIhAMZAns2g/uTdsKZMwF03c+Uk\n\
+DAO2XqCElnw/XE+gUafh\n\
+-----END CERTIFICATE-----\n\
+";
+
+    /// Writer that captures formatted log output for assertions.
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Accept one connection, return its request line and answer `200 OK`.
+    async fn serve_one(listener: TcpListener) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; 4096];
+        let n = stream.read(&mut buf).await.unwrap();
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        request.lines().next().unwrap_or_default().to_string()
+    }
+
+    #[test]
+    fn test_connect_timeout_defaults() {
+        assert_eq!(
+            HttpOptions::with_connect_timeout_secs(None).connect_timeout,
+            Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS)
+        );
+        assert_eq!(
+            HttpOptions::with_connect_timeout_secs(Some(5)).connect_timeout,
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_from_config_reads_network_section() {
+        let mut config = Config::default();
+        config.network.proxy = Some("socks5://127.0.0.1:1080".to_string());
+        config.network.no_proxy = Some("localhost,.internal".to_string());
+
+        let options = HttpOptions::from_config(&config);
+        assert_eq!(options.proxy.as_deref(), Some("socks5://127.0.0.1:1080"));
+        assert_eq!(options.no_proxy.as_deref(), Some("localhost,.internal"));
+        assert!(options.build_client().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_proxy_url_is_an_error() {
+        let options = HttpOptions {
+            proxy: Some("not a url".to_string()),
+            ..HttpOptions::default()
+        };
+        let err = options.build_client().unwrap_err();
+        assert!(err.to_string().contains("[network].proxy"));
+    }
+
+    #[tokio::test]
+    async fn test_configured_proxy_receives_requests() {
+        let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_url = format!("http://{}", proxy.local_addr().unwrap());
+        let server = tokio::spawn(serve_one(proxy));
+
+        let client = HttpOptions {
+            proxy: Some(proxy_url),
+            no_proxy: Some(String::new()),
+            ..HttpOptions::default()
+        }
+        .build_client()
+        .unwrap();
+
+        let response = client.get("http://audetic.invalid/ping").send().await;
+        assert!(response.is_ok());
+
+        // A forward proxy sees the absolute-form target.
+        let request_line = server.await.unwrap();
+        assert_eq!(request_line, "GET http://audetic.invalid/ping HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_no_proxy_hosts_bypass_the_proxy() {
+        let target = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let target_addr = target.local_addr().unwrap();
+        let server = tokio::spawn(serve_one(target));
+
+        // Nothing listens on port 9 — going through the proxy would fail.
+        let client = HttpOptions {
+            proxy: Some("http://127.0.0.1:9".to_string()),
+            no_proxy: Some("127.0.0.1".to_string()),
+            ..HttpOptions::default()
+        }
+        .build_client()
+        .unwrap();
+
+        let response = client
+            .get(format!("http://{target_addr}/ping"))
+            .send()
+            .await;
+        assert!(response.is_ok());
+        assert_eq!(server.await.unwrap(), "GET /ping HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_connect_timeout_fails_before_request_timeout() {
+        let options = HttpOptions {
+            connect_timeout: Duration::from_millis(300),
+            ..HttpOptions::default()
+        };
+        let client = options
+            .client_builder()
+            .unwrap()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        // 10.255.255.1 is unroutable: SYNs go unanswered, so only the connect
+        // timeout (or an immediate "network unreachable") can end the request.
+        let started = Instant::now();
+        let result = client.get("http://10.255.255.1:81/").send().await;
+
+        assert!(result.is_err());
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "connect took {:?}",
+            started.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_cuts_off_slow_responses() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Accept the connection but never answer.
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            drop(stream);
+        });
+
+        let options = HttpOptions {
+            request_timeout: Some(Duration::from_millis(1)),
+            ..HttpOptions::default()
+        };
+        let client = options.build_client().unwrap();
+
+        let err = client
+            .get(format!("http://{addr}/slow"))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout(), "{err:?}");
+        assert_eq!(
+            options.request_error(err, "Failed to fetch").to_string(),
+            "Failed to fetch: request timed out after 1ms"
+        );
+        server.abort();
+    }
+
+    #[test]
+    fn test_ca_cert_path_is_loaded() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(TEST_CA_PEM.as_bytes()).unwrap();
+
+        let mut config = Config::default();
+        config.network.ca_cert_path = Some(file.path().to_string_lossy().to_string());
+
+        let options = HttpOptions::from_config(&config);
+        assert_eq!(options.ca_cert_path.as_deref(), Some(file.path()));
+        assert!(options.build_client().is_ok());
+    }
+
+    #[test]
+    fn test_missing_ca_cert_path_is_an_error() {
+        let options = HttpOptions {
+            ca_cert_path: Some(PathBuf::from("/nonexistent/audetic-ca.pem")),
+            ..HttpOptions::default()
+        };
+        let err = options.build_client().unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/audetic-ca.pem"));
+    }
+
+    #[test]
+    fn test_danger_accept_invalid_certs_logs_warning() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+
+        let options = HttpOptions {
+            danger_accept_invalid_certs: true,
+            ..HttpOptions::default()
+        };
+        tracing::subscriber::with_default(subscriber, || {
+            options.build_client().unwrap();
+        });
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("WARN"));
+        assert!(output.contains("TLS CERTIFICATE VERIFICATION IS DISABLED"));
+    }
+
+    #[test]
+    fn test_default_options_log_no_tls_warning() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            HttpOptions::default().build_client().unwrap();
+        });
+
+        assert!(logs.0.lock().unwrap().is_empty());
+    }
+}
@@ -1,19 +1,115 @@
 //! HTTP client for the transcription-manager jobs API.
 //!
 //! Provides methods for submitting files for transcription, polling status,
-//! and retrieving results. Supports both in-memory and streaming file uploads.
+//! and retrieving results. Files are streamed to the server rather than read
+//! into memory, so multi-hour recordings upload in bounded memory.
+//!
+//! Callers that open jobs should hold a slot from
+//! [`JobsClient::acquire_job_slot`] from submission until the job settles, so
+//! `[network].max_concurrent_jobs` caps open jobs across every client sharing
+//! a [`JobLimiter`].
 
 use anyhow::{Context, Result};
 use reqwest::multipart::{Form, Part};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use tokio::fs;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
 use tokio_util::io::ReaderStream;
 
+use crate::http::HttpOptions;
+
 /// Client for interacting with the jobs API.
 pub struct JobsClient {
     client: reqwest::Client,
     base_url: String,
+    /// Shared cap on open jobs; `None` means unlimited.
+    job_limiter: Option<Arc<JobLimiter>>,
+}
+
+/// Stream the file at `path` as a request body, returning it with the file's
+/// size for `Content-Length`. Only a read buffer's worth of the file is in
+/// memory at a time. The file is opened synchronously so retry loops, which
+/// build each attempt's request in a plain closure, can reopen it per attempt.
+pub fn file_body(path: &Path) -> Result<(reqwest::Body, u64)> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open {} for upload", path.display()))?;
+    let size = file
+        .metadata()
+        .context("Failed to read file metadata")?
+        .len();
+    let stream = ReaderStream::new(tokio::fs::File::from_std(file));
+    Ok((reqwest::Body::wrap_stream(stream), size))
+}
+
+/// Cap on open jobs behind `[network].max_concurrent_jobs`, shared by every
+/// client it's attached to. The daemon keeps one for the meeting pipeline,
+/// imports and resumes, and sets a new limit on config reload. Jobs already
+/// open keep their slots; a lower limit only holds back new ones.
+#[derive(Debug, Default)]
+pub struct JobLimiter {
+    slots: Mutex<Slots>,
+    /// Woken when a slot frees up or the limit changes.
+    changed: Notify,
+}
+
+#[derive(Debug, Default)]
+struct Slots {
+    /// `None` means unlimited.
+    limit: Option<usize>,
+    open: usize,
+}
+
+impl JobLimiter {
+    /// A limiter allowing `limit` open jobs; `None` (or 0) means unlimited.
+    pub fn new(limit: Option<usize>) -> Self {
+        let limiter = Self::default();
+        limiter.set_limit(limit);
+        limiter
+    }
+
+    /// Change the limit, waking anything waiting for a slot it now allows.
+    pub fn set_limit(&self, limit: Option<usize>) {
+        self.lock().limit = limit.filter(|&n| n > 0);
+        self.changed.notify_waiters();
+    }
+
+    /// Jobs currently holding a slot.
+    pub fn open_jobs(&self) -> usize {
+        self.lock().open
+    }
+
+    /// Wait until a slot is free and take it.
+    pub async fn acquire(self: &Arc<Self>) -> JobSlot {
+        loop {
+            // Registered before checking, so a slot freed in between still
+            // wakes this waiter.
+            let changed = self.changed.notified();
+            {
+                let mut slots = self.lock();
+                if slots.limit.is_none_or(|limit| slots.open < limit) {
+                    slots.open += 1;
+                    return JobSlot(Arc::clone(self));
+                }
+            }
+            changed.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Slots> {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// An open job's slot in a [`JobLimiter`]; dropping it frees the slot.
+#[derive(Debug)]
+pub struct JobSlot(Arc<JobLimiter>);
+
+impl Drop for JobSlot {
+    fn drop(&mut self) {
+        self.0.lock().open -= 1;
+        self.0.changed.notify_waiters();
+    }
 }
 
 /// Response from submitting a new transcription job.
@@ -77,6 +173,21 @@ pub struct Segment {
     pub start: f64,
     pub end: f64,
     pub text: String,
+    /// Per-word timings, present only when word-level timestamps were
+    /// requested and the provider supports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<Word>>,
+}
+
+/// A single word with timestamps. OpenAI-style responses name the text field
+/// `word`, so that is accepted as an alias.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct Word {
+    pub start: f64,
+    pub end: f64,
+    #[serde(alias = "word")]
+    pub text: String,
 }
 
 /// Map a lowercase file extension to its MIME type.
@@ -114,96 +225,68 @@ impl JobsClient {
         Self {
             client: reqwest::Client::new(),
             base_url: base_url.trim_end_matches('/').to_string(),
+            job_limiter: None,
         }
     }
 
+    /// Create a client whose connections follow the given network settings
+    /// (connect timeout, proxy) and whose jobs are capped at
+    /// `max_concurrent_jobs`. Use [`Self::with_job_limiter`] to share the cap
+    /// with other clients.
+    pub fn with_http(base_url: &str, http: &HttpOptions) -> Result<Self> {
+        Ok(Self {
+            client: http.build_client()?,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            job_limiter: http
+                .max_concurrent_jobs
+                .map(|limit| Arc::new(JobLimiter::new(Some(limit)))),
+        })
+    }
+
+    /// Count this client's jobs against `limiter` instead of its own cap.
+    pub fn with_job_limiter(mut self, limiter: Arc<JobLimiter>) -> Self {
+        self.job_limiter = Some(limiter);
+        self
+    }
+
+    /// Wait for a free job slot. Hold the returned slot until the job has
+    /// completed or failed; dropping it frees the slot. `None` when no
+    /// limiter is attached.
+    pub async fn acquire_job_slot(&self) -> Option<JobSlot> {
+        Some(self.job_limiter.as_ref()?.acquire().await)
+    }
+
     /// Create with a custom reqwest client (for testing, proxy config, timeouts).
     #[cfg(test)]
     pub fn with_client(client: reqwest::Client, base_url: &str) -> Self {
         Self {
             client,
             base_url: base_url.trim_end_matches('/').to_string(),
+            job_limiter: None,
         }
     }
 
-    /// Submit a file for transcription by reading it entirely into memory.
-    /// Suitable for small files (< 50MB). For large files, use `submit_job_streaming`.
+    /// Submit a file for transcription. The file is streamed, so memory use
+    /// doesn't grow with its size.
     pub async fn submit_job(
         &self,
         file_path: &Path,
         language: Option<&str>,
         timestamps: bool,
     ) -> Result<String> {
-        let file_data = fs::read(file_path).await.context("Failed to read file")?;
-
-        let filename = file_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("audio")
-            .to_string();
-
-        // Determine MIME type from extension
-        let mime_type = file_path
-            .extension()
-            .and_then(|e| e.to_str())
-            .and_then(mime_type_for_extension)
-            .unwrap_or("application/octet-stream");
-
-        let mut form = Form::new().part(
-            "file",
-            Part::bytes(file_data)
-                .file_name(filename)
-                .mime_str(mime_type)?,
-        );
-
-        if let Some(lang) = language {
-            form = form.text("language", lang.to_string());
-        }
-        form = form.text("timestamps", timestamps.to_string());
-
-        let response = self
-            .client
-            .post(&self.base_url)
-            .multipart(form)
-            .send()
+        self.submit_job_streaming(file_path, language, timestamps)
             .await
-            .context("Failed to submit job")?;
-
-        let status = response.status();
-        let body = response.text().await?;
-
-        if !status.is_success() {
-            return Err(anyhow::anyhow!(
-                "Job submission failed ({}): {}",
-                status,
-                body
-            ));
-        }
-
-        let result: SubmitJobResponse =
-            serde_json::from_str(&body).context("Failed to parse job submission response")?;
-
-        Ok(result.job_id)
     }
 
-    /// Submit a file for transcription using streaming upload.
-    /// Suitable for large files (meetings, long recordings) that shouldn't be
-    /// loaded entirely into memory.
+    /// Submit a file for transcription. Streamed like
+    /// [`JobsClient::submit_job`].
     pub async fn submit_job_streaming(
         &self,
         file_path: &Path,
         language: Option<&str>,
         timestamps: bool,
     ) -> Result<String> {
-        let file = tokio::fs::File::open(file_path)
-            .await
-            .context("Failed to open file for streaming upload")?;
-
-        let file_size = file
-            .metadata()
-            .await
-            .context("Failed to read file metadata")?
-            .len();
+        let (body, file_size) = file_body(file_path)?;
 
         let filename = file_path
             .file_name()
@@ -217,16 +300,14 @@ impl JobsClient {
             .and_then(mime_type_for_extension)
             .unwrap_or("application/octet-stream");
 
-        let stream = ReaderStream::new(file);
-        let body = reqwest::Body::wrap_stream(stream);
-
         let part = Part::stream_with_length(body, file_size)
             .file_name(filename)
             .mime_str(mime_type)?;
 
         let mut form = Form::new().part("file", part);
 
-        if let Some(lang) = language {
+        // `auto` is left out so the service detects the language.
+        if let Some(lang) = language.and_then(crate::language::explicit) {
             form = form.text("language", lang.to_string());
         }
         form = form.text("timestamps", timestamps.to_string());
@@ -304,6 +385,31 @@ impl JobsClient {
 
         Ok(result.job)
     }
+
+    /// Ask the server to stop a job (`DELETE {base}/{id}`); its status then
+    /// reads [`status::CANCELLED`].
+    pub async fn cancel_job(&self, job_id: &str) -> Result<()> {
+        let url = format!("{}/{}", self.base_url, job_id);
+
+        let response = self
+            .client
+            .delete(&url)
+            .send()
+            .await
+            .context("Failed to cancel job")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!(
+                "Failed to cancel job ({}): {}",
+                status,
+                body
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -421,10 +527,271 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deserialize_segment_with_words() {
+        let json = r#"{
+            "text": "Hello world",
+            "segments": [{
+                "start": 0.0,
+                "end": 1.0,
+                "text": "Hello world",
+                "words": [
+                    {"start": 0.0, "end": 0.4, "word": "Hello"},
+                    {"start": 0.5, "end": 1.0, "text": "world"}
+                ]
+            }]
+        }"#;
+        let result: TranscriptionResult = serde_json::from_str(json).unwrap();
+        let words = result.segments.unwrap()[0].words.clone().unwrap();
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!(words[1].text, "world");
+    }
+
+    #[test]
+    fn test_segment_without_words_omits_field() {
+        let segment = Segment {
+            start: 0.0,
+            end: 1.0,
+            text: "Hi".to_string(),
+            words: None,
+        };
+        let json = serde_json::to_string(&segment).unwrap();
+        assert!(!json.contains("words"));
+    }
+
+    #[tokio::test]
+    async fn test_job_slots_wait_for_a_free_permit() {
+        let client = JobsClient::new("https://example.com/api")
+            .with_job_limiter(Arc::new(JobLimiter::new(Some(2))));
+
+        let first = client.acquire_job_slot().await.unwrap();
+        let _second = client.acquire_job_slot().await.unwrap();
+
+        // Both slots are taken, so a third submission has to wait...
+        let blocked = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            client.acquire_job_slot(),
+        )
+        .await;
+        assert!(blocked.is_err(), "third slot should wait while at the cap");
+
+        // ...until one finishes.
+        drop(first);
+        let third = tokio::time::timeout(
+            std::time::Duration::from_millis(500),
+            client.acquire_job_slot(),
+        )
+        .await
+        .expect("slot should free up once a job completes");
+        assert!(third.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_jobs_never_exceed_cap() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let limiter = Arc::new(JobLimiter::new(Some(2)));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let completed = Arc::new(AtomicUsize::new(0));
+
+        let mut tasks = Vec::new();
+        for i in 0..6 {
+            // Separate clients sharing one limiter, like the meeting pipeline
+            // and an import running side by side.
+            let client =
+                JobsClient::new("https://example.com/api").with_job_limiter(Arc::clone(&limiter));
+            let (in_flight, peak, completed) = (
+                Arc::clone(&in_flight),
+                Arc::clone(&peak),
+                Arc::clone(&completed),
+            );
+            tasks.push(tokio::spawn(async move {
+                let _slot = client.acquire_job_slot().await;
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                // Odd jobs "fail"; their slot must be released all the same.
+                if i % 2 == 1 {
+                    return Err(anyhow::anyhow!("mock job failure"));
+                }
+                completed.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }));
+        }
+        for task in tasks {
+            let _ = task.await.unwrap();
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(completed.load(Ordering::SeqCst), 3);
+        assert_eq!(limiter.open_jobs(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_new_limit_applies_to_waiting_jobs() {
+        let limiter = Arc::new(JobLimiter::new(Some(1)));
+        let client = JobsClient::new("https://example.com/api").with_job_limiter(limiter.clone());
+        let _first = client.acquire_job_slot().await.unwrap();
+
+        let waiting = tokio::spawn(async move { client.acquire_job_slot().await });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(
+            !waiting.is_finished(),
+            "second job should wait at a cap of 1"
+        );
+
+        // A reload raising the cap lets it through without a slot freeing.
+        limiter.set_limit(Some(2));
+        let _second = tokio::time::timeout(std::time::Duration::from_millis(500), waiting)
+            .await
+            .expect("a raised cap should admit the waiting job")
+            .unwrap()
+            .unwrap();
+        assert_eq!(limiter.open_jobs(), 2);
+
+        // Lowering it leaves open jobs alone and holds back new ones.
+        limiter.set_limit(Some(1));
+        assert_eq!(limiter.open_jobs(), 2);
+        let client = JobsClient::new("https://example.com/api").with_job_limiter(limiter);
+        let blocked = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            client.acquire_job_slot(),
+        )
+        .await;
+        assert!(blocked.is_err(), "new jobs wait while over the lowered cap");
+    }
+
+    #[tokio::test]
+    async fn test_no_limit_by_default() {
+        let client = JobsClient::new("https://example.com/api");
+        assert!(client.acquire_job_slot().await.is_none());
+    }
+
     #[test]
     fn test_with_client_constructor() {
         let client = reqwest::Client::new();
         let jobs_client = JobsClient::with_client(client, "https://example.com/api/");
         assert_eq!(jobs_client.base_url, "https://example.com/api");
     }
+
+    /// A file big enough that buffering it would show, without writing its
+    /// bytes to disk (`set_len` leaves it sparse).
+    fn large_file(dir: &tempfile::TempDir, size: u64) -> std::path::PathBuf {
+        let path = dir.path().join("meeting.wav");
+        std::fs::File::create(&path).unwrap().set_len(size).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_file_body_streams_instead_of_buffering() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = large_file(&dir, 64 * 1024 * 1024);
+
+        let (body, size) = file_body(&path).unwrap();
+        assert_eq!(size, 64 * 1024 * 1024);
+        // Buffered bodies expose their bytes; streamed ones don't.
+        assert!(body.as_bytes().is_none());
+
+        assert!(file_body(&dir.path().join("missing.wav")).is_err());
+    }
+
+    /// Answer one request with `response` and hand back its request line.
+    async fn serve_once(response: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/v1/jobs", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            stream.write_all(response.as_bytes()).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).into_owned();
+            request.lines().next().unwrap_or_default().to_string()
+        });
+        (url, server)
+    }
+
+    #[tokio::test]
+    async fn test_cancel_job_sends_delete_for_the_job() {
+        let (url, server) =
+            serve_once("HTTP/1.1 204 No Content\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await;
+        let client = JobsClient::with_client(reqwest::Client::new(), &format!("{url}/"));
+
+        client.cancel_job("job-1").await.unwrap();
+
+        assert_eq!(server.await.unwrap(), "DELETE /api/v1/jobs/job-1 HTTP/1.1");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_job_reports_server_refusal() {
+        let (url, server) = serve_once(
+            "HTTP/1.1 409 Conflict\r\ncontent-type: application/json\r\ncontent-length: 32\r\nconnection: close\r\n\r\n{\"error\":\"Job already finished\"}",
+        )
+        .await;
+        let client = JobsClient::with_client(reqwest::Client::new(), &url);
+
+        let err = client.cancel_job("job-2").await.unwrap_err().to_string();
+
+        assert!(err.contains("409"), "{err}");
+        assert!(err.contains("Job already finished"), "{err}");
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_submit_job_streams_with_content_length() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        const FILE_SIZE: u64 = 8 * 1024 * 1024;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/v1/jobs", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 64 * 1024];
+            let (head_end, content_length) = loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before the headers ended");
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(head_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..head_end].lines().find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<u64>().ok())?
+                    });
+                    break (head_end + 4, content_length);
+                }
+            };
+            let expected = content_length.expect("upload sent without Content-Length");
+            let mut received = (request.len() - head_end) as u64;
+            while received < expected {
+                let n = stream.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed mid-upload");
+                received += n as u64;
+            }
+            let body = r#"{"success":true,"jobId":"job-1","status":"pending"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            (expected, received)
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = large_file(&dir, FILE_SIZE);
+        let client = JobsClient::with_client(reqwest::Client::new(), &url);
+        let job_id = client.submit_job(&path, Some("en"), true).await.unwrap();
+        assert_eq!(job_id, "job-1");
+
+        let (content_length, received) = server.await.unwrap();
+        assert_eq!(received, content_length);
+        // The multipart framing and form fields come on top of the file.
+        assert!(content_length > FILE_SIZE);
+    }
 }
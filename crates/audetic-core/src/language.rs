@@ -0,0 +1,180 @@
+//! Recognized values for `[whisper].language`.
+//!
+//! The setting is a free-form string handed straight to the provider, so a
+//! typo like `eng` or `english` only surfaces as ignored or failed
+//! transcriptions. This module knows the ISO 639-1 codes (plus `auto`) and a
+//! handful of common mistakes, so the config check and the provider wizard can
+//! flag an unrecognized value and suggest the code the user most likely meant.
+
+/// Value meaning "let the provider detect the language".
+pub const AUTO: &str = "auto";
+
+/// Every ISO 639-1 two-letter code.
+const ISO_639_1_CODES: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bh",
+    "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da",
+    "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr",
+    "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz",
+    "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj",
+    "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb",
+    "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi",
+    "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk",
+    "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti",
+    "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo",
+    "wa", "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
+
+/// Common non-ISO-639-1 spellings — English names and ISO 639-2 codes — for
+/// the languages people actually dictate in, mapped to the code they meant.
+const ALIASES: &[(&str, &str)] = &[
+    ("english", "en"),
+    ("eng", "en"),
+    ("spanish", "es"),
+    ("spa", "es"),
+    ("french", "fr"),
+    ("fra", "fr"),
+    ("fre", "fr"),
+    ("german", "de"),
+    ("deu", "de"),
+    ("ger", "de"),
+    ("italian", "it"),
+    ("ita", "it"),
+    ("portuguese", "pt"),
+    ("por", "pt"),
+    ("dutch", "nl"),
+    ("nld", "nl"),
+    ("dut", "nl"),
+    ("russian", "ru"),
+    ("rus", "ru"),
+    ("ukrainian", "uk"),
+    ("ukr", "uk"),
+    ("polish", "pl"),
+    ("pol", "pl"),
+    ("swedish", "sv"),
+    ("swe", "sv"),
+    ("japanese", "ja"),
+    ("jpn", "ja"),
+    ("jp", "ja"),
+    ("chinese", "zh"),
+    ("zho", "zh"),
+    ("chi", "zh"),
+    ("cn", "zh"),
+    ("korean", "ko"),
+    ("kor", "ko"),
+    ("kr", "ko"),
+    ("arabic", "ar"),
+    ("ara", "ar"),
+    ("hindi", "hi"),
+    ("hin", "hi"),
+    ("turkish", "tr"),
+    ("tur", "tr"),
+    ("greek", "el"),
+    ("gr", "el"),
+    ("hebrew", "he"),
+    ("heb", "he"),
+    ("czech", "cs"),
+    ("ces", "cs"),
+    ("cz", "cs"),
+    ("danish", "da"),
+    ("dan", "da"),
+    ("dk", "da"),
+    ("finnish", "fi"),
+    ("fin", "fi"),
+    ("norwegian", "no"),
+    ("nor", "no"),
+    ("vietnamese", "vi"),
+    ("vie", "vi"),
+    ("indonesian", "id"),
+    ("ind", "id"),
+    ("automatic", AUTO),
+    ("detect", AUTO),
+];
+
+/// Whether `code` is `auto` or an ISO 639-1 code (case-insensitive).
+pub fn is_recognized(code: &str) -> bool {
+    let code = code.trim().to_ascii_lowercase();
+    code == AUTO || ISO_639_1_CODES.contains(&code.as_str())
+}
+
+/// The language to request from a provider, or `None` when `code` is empty
+/// or `auto` and the provider should detect it instead.
+pub fn explicit(code: &str) -> Option<&str> {
+    let code = code.trim();
+    (!code.is_empty() && !code.eq_ignore_ascii_case(AUTO)).then_some(code)
+}
+
+/// The code the user most likely meant by an unrecognized `code`, if it's a
+/// known name, ISO 639-2 code, or a locale tag like `en-US` / `pt_BR`.
+pub fn suggest(code: &str) -> Option<&'static str> {
+    let code = code.trim().to_ascii_lowercase();
+    if let Some((_, suggestion)) = ALIASES.iter().find(|(alias, _)| *alias == code) {
+        return Some(*suggestion);
+    }
+    let primary = code.split(['-', '_']).next()?;
+    if primary != code {
+        return ISO_639_1_CODES.iter().copied().find(|c| *c == primary);
+    }
+    None
+}
+
+/// Human-readable warning for an unrecognized `code`, or `None` if it's fine.
+pub fn check(code: &str) -> Option<String> {
+    if is_recognized(code) {
+        return None;
+    }
+    Some(match suggest(code) {
+        Some(suggestion) => format!(
+            "Unrecognized language code '{code}' (did you mean '{suggestion}'?). \
+             Use an ISO 639-1 code like 'en' or 'es', or 'auto'."
+        ),
+        None => format!(
+            "Unrecognized language code '{code}'. \
+             Use an ISO 639-1 code like 'en' or 'es', or 'auto'."
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_iso_codes_and_auto() {
+        for code in ["en", "es", "auto", "EN", " de "] {
+            assert!(is_recognized(code), "{code} should be recognized");
+            assert_eq!(check(code), None);
+        }
+    }
+
+    #[test]
+    fn empty_or_auto_is_not_an_explicit_language() {
+        for code in ["", "  ", "auto", "AUTO", " auto "] {
+            assert_eq!(explicit(code), None, "{code:?}");
+        }
+        assert_eq!(explicit(" de "), Some("de"));
+    }
+
+    #[test]
+    fn flags_names_and_three_letter_codes() {
+        assert!(!is_recognized("english"));
+        assert!(!is_recognized("eng"));
+        assert_eq!(suggest("english"), Some("en"));
+        assert_eq!(suggest("eng"), Some("en"));
+        assert!(check("eng").unwrap().contains("did you mean 'en'"));
+    }
+
+    #[test]
+    fn suggests_primary_subtag_of_locale() {
+        assert_eq!(suggest("en-US"), Some("en"));
+        assert_eq!(suggest("pt_BR"), Some("pt"));
+        assert_eq!(suggest("xx-YY"), None);
+    }
+
+    #[test]
+    fn unknown_code_has_no_suggestion() {
+        assert_eq!(suggest("klingon"), None);
+        let warning = check("klingon").unwrap();
+        assert!(!warning.contains("did you mean"));
+    }
+}
@@ -9,11 +9,18 @@
 //! `crate::config`, `crate::api::url`, `crate::transcription::jobs_client`) so
 //! its internal call sites keep compiling unchanged.
 
+pub mod build_info;
 pub mod clipboard;
 pub mod compression;
 pub mod config;
+pub mod config_keys;
 pub mod ffmpeg;
 pub mod global;
+pub mod http;
 pub mod jobs_client;
+pub mod language;
 pub mod local_models;
+pub mod media;
+pub mod rollback;
+pub mod subtitles;
 pub mod url;
@@ -0,0 +1,175 @@
+//! Media file inspection without decoding.
+//!
+//! WAV durations come straight from the RIFF header; anything else is asked
+//! of `ffprobe` when it's on PATH. Either way an unreadable file is `None`,
+//! never an error: callers report the duration as unknown and carry on.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::process::Command;
+
+use crate::ffmpeg::resolve_ffprobe_binary;
+
+/// Duration of the media at `path` in seconds, or `None` if it can't be read.
+pub fn duration_seconds(path: &Path) -> Option<f64> {
+    let is_wav = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    if is_wav {
+        if let Some(seconds) = wav_duration_seconds(path) {
+            return Some(seconds);
+        }
+    }
+    ffprobe_duration_seconds(path)
+}
+
+/// Duration of a WAV file from its `fmt ` byte rate and `data` chunk size.
+/// A `data` size left at the streaming placeholder (`0xFFFFFFFF`) is taken
+/// to run to the end of the file.
+pub fn wav_duration_seconds(path: &Path) -> Option<f64> {
+    let file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+    let mut reader = BufReader::new(file);
+
+    let mut header = [0u8; 12];
+    reader.read_exact(&mut header).ok()?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut byte_rate = None;
+    loop {
+        let mut chunk = [0u8; 8];
+        reader.read_exact(&mut chunk).ok()?;
+        let size = u32::from_le_bytes(chunk[4..8].try_into().ok()?);
+        match &chunk[0..4] {
+            b"fmt " => {
+                let mut fmt = [0u8; 12];
+                reader.read_exact(&mut fmt).ok()?;
+                byte_rate = Some(u32::from_le_bytes(fmt[8..12].try_into().ok()?));
+                // Skip the rest of the chunk, including its pad byte.
+                let rest = i64::from(size) - 12 + i64::from(size % 2);
+                reader.seek(SeekFrom::Current(rest)).ok()?;
+            }
+            b"data" => {
+                let byte_rate = byte_rate.filter(|rate| *rate > 0)?;
+                let remaining = file_len.saturating_sub(reader.stream_position().ok()?);
+                let data_len = match size {
+                    u32::MAX => remaining,
+                    size => u64::from(size).min(remaining),
+                };
+                return Some(data_len as f64 / f64::from(byte_rate));
+            }
+            _ => {
+                reader
+                    .seek(SeekFrom::Current(i64::from(size) + i64::from(size % 2)))
+                    .ok()?;
+            }
+        }
+    }
+}
+
+/// Container duration reported by `ffprobe`, or `None` when it's missing or
+/// can't read the file.
+pub fn ffprobe_duration_seconds(path: &Path) -> Option<f64> {
+    let ffprobe = resolve_ffprobe_binary()?;
+    let output = Command::new(ffprobe)
+        .args([
+            "-v",
+            "quiet",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let seconds = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<f64>()
+        .ok()?;
+    (seconds.is_finite() && seconds >= 0.0).then_some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// A 16-bit mono WAV with `extra` chunks before `data` and `samples`
+    /// zeroed samples at `rate` Hz.
+    fn wav(
+        rate: u32,
+        samples: u32,
+        extra: &[(&[u8; 4], &[u8])],
+        data_size: Option<u32>,
+    ) -> Vec<u8> {
+        let data_len = samples * 2;
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        fmt.extend_from_slice(&1u16.to_le_bytes()); // mono
+        fmt.extend_from_slice(&rate.to_le_bytes());
+        fmt.extend_from_slice(&(rate * 2).to_le_bytes()); // byte rate
+        fmt.extend_from_slice(&2u16.to_le_bytes()); // block align
+        fmt.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+
+        let mut body = b"WAVE".to_vec();
+        body.extend_from_slice(b"fmt ");
+        body.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+        body.extend_from_slice(&fmt);
+        for (id, payload) in extra {
+            body.extend_from_slice(*id);
+            body.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            body.extend_from_slice(payload);
+            if payload.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        body.extend_from_slice(b"data");
+        body.extend_from_slice(&data_size.unwrap_or(data_len).to_le_bytes());
+        body.extend(std::iter::repeat(0u8).take(data_len as usize));
+
+        let mut out = b"RIFF".to_vec();
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend(body);
+        out
+    }
+
+    fn write_temp(bytes: &[u8]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".wav").tempfile().unwrap();
+        file.write_all(bytes).unwrap();
+        file
+    }
+
+    #[test]
+    fn wav_duration_reads_the_header() {
+        let file = write_temp(&wav(16_000, 24_000, &[], None));
+        assert_eq!(wav_duration_seconds(file.path()), Some(1.5));
+        assert_eq!(duration_seconds(file.path()), Some(1.5));
+    }
+
+    #[test]
+    fn wav_duration_skips_other_chunks() {
+        let file = write_temp(&wav(8_000, 8_000, &[(b"LIST", b"odd")], None));
+        assert_eq!(wav_duration_seconds(file.path()), Some(1.0));
+    }
+
+    #[test]
+    fn wav_duration_handles_a_streaming_data_size() {
+        let file = write_temp(&wav(16_000, 8_000, &[], Some(u32::MAX)));
+        assert_eq!(wav_duration_seconds(file.path()), Some(0.5));
+    }
+
+    #[test]
+    fn non_wav_bytes_are_unknown() {
+        let file = write_temp(b"not a wav file at all");
+        assert_eq!(wav_duration_seconds(file.path()), None);
+        assert_eq!(wav_duration_seconds(Path::new("/nonexistent.wav")), None);
+    }
+}
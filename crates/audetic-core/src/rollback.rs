@@ -0,0 +1,230 @@
+//! Restoring the binary an update replaced.
+//!
+//! Every install copies the running binary to `audetic-<version>.bak` next to
+//! it before swapping in the new one. Rolling back copies the newest backup
+//! over the binary again. This lives here rather than in the daemon's update
+//! engine so the CLI can roll back on its own: a release bad enough to need
+//! a rollback may well be one whose daemon won't start.
+
+use anyhow::{anyhow, Context, Result};
+use fs2::FileExt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the installed binary, as used in backup file names.
+pub const BIN_NAME: &str = "audetic";
+
+/// Where an install backs up the binary in `dir` that runs `version`.
+pub fn backup_path(dir: &Path, version: &str) -> PathBuf {
+    dir.join(format!("{BIN_NAME}-{version}.bak"))
+}
+
+/// Restore the newest backup over `binary_path` and record the rollback in
+/// the update state file, holding the update lock throughout. Returns the
+/// restored version; it runs from the next restart.
+pub fn rollback(binary_path: &Path, state_file: &Path, lock_file: &Path) -> Result<String> {
+    let _lock = lock_updates(lock_file)?;
+    let (backup, version) = previous_binary(binary_path)?;
+    restore_binary(&backup, binary_path)?;
+    record_rollback(state_file, &version)?;
+    Ok(version)
+}
+
+/// Take the update lock at `lock_file`, blocking until any install or
+/// rollback in another process finishes. Released when the file drops.
+pub fn lock_updates(lock_file: &Path) -> Result<File> {
+    if let Some(parent) = lock_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .truncate(false)
+        .open(lock_file)?;
+    file.lock_exclusive()
+        .with_context(|| "Failed to acquire update lock")?;
+    Ok(file)
+}
+
+/// The backup a rollback of `binary_path` would restore, with its version.
+pub fn previous_binary(binary_path: &Path) -> Result<(PathBuf, String)> {
+    let parent = binary_path
+        .parent()
+        .context("Binary path missing parent directory")?;
+    latest_backup(parent)?.ok_or_else(|| {
+        anyhow!(
+            "No previous version to roll back to: no {BIN_NAME}-<version>.bak next to {}",
+            binary_path.display()
+        )
+    })
+}
+
+/// The newest `audetic-<version>.bak` in `dir` by modification time, with
+/// the version parsed from its name.
+pub fn latest_backup(dir: &Path) -> Result<Option<(PathBuf, String)>> {
+    let prefix = format!("{BIN_NAME}-");
+    let mut newest: Option<(SystemTime, PathBuf, String)> = None;
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(version) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(&prefix))
+            .and_then(|rest| rest.strip_suffix(".bak"))
+        else {
+            continue;
+        };
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+        if newest.as_ref().is_none_or(|(time, _, _)| modified > *time) {
+            newest = Some((modified, entry.path(), version.to_string()));
+        }
+    }
+    Ok(newest.map(|(_, path, version)| (path, version)))
+}
+
+/// Copy `backup` over `target` via a temp file, so a crash mid-copy never
+/// leaves a truncated binary in place.
+pub fn restore_binary(backup: &Path, target: &Path) -> Result<()> {
+    let parent = target
+        .parent()
+        .context("Binary path missing parent directory")?;
+    let tmp_path = parent.join(format!("{BIN_NAME}-rollback.tmp"));
+    std::fs::copy(backup, &tmp_path)
+        .with_context(|| format!("Failed to copy backup to {}", tmp_path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&tmp_path)?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&tmp_path, perms)?;
+    }
+    std::fs::rename(&tmp_path, target)
+        .with_context(|| format!("Failed to replace {}", target.display()))?;
+    Ok(())
+}
+
+/// Note in the daemon's update state that `version` is installed and no
+/// update is waiting for a restart. Other fields are left as they are; a
+/// missing state file is left for the daemon to create.
+fn record_rollback(state_file: &Path, version: &str) -> Result<()> {
+    let content = match std::fs::read_to_string(state_file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", state_file.display()))
+        }
+    };
+    let mut state: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", state_file.display()))?;
+    let Some(fields) = state.as_object_mut() else {
+        return Ok(());
+    };
+    fields.insert("current_version".into(), version.into());
+    fields.insert("last_downloaded_version".into(), serde_json::Value::Null);
+    fields.insert("pending_restart".into(), false.into());
+
+    let tmp_path = state_file.with_extension("json.tmp");
+    std::fs::write(&tmp_path, serde_json::to_string_pretty(&state)?)?;
+    std::fs::rename(&tmp_path, state_file)
+        .with_context(|| format!("Failed to replace {}", state_file.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn latest_backup_picks_newest_and_ignores_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(latest_backup(dir.path()).unwrap().is_none());
+
+        let older = backup_path(dir.path(), "0.1.24");
+        let newer = backup_path(dir.path(), "0.1.25");
+        std::fs::write(&older, "old").unwrap();
+        std::fs::write(&newer, "new").unwrap();
+        std::fs::write(dir.path().join("audetic"), "current").unwrap();
+        std::fs::write(dir.path().join("audetic-0.1.26.tmp"), "partial").unwrap();
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        File::options()
+            .write(true)
+            .open(&older)
+            .unwrap()
+            .set_modified(an_hour_ago)
+            .unwrap();
+
+        let (path, version) = latest_backup(dir.path()).unwrap().unwrap();
+        assert_eq!(path, newer);
+        assert_eq!(version, "0.1.25");
+    }
+
+    #[test]
+    fn restore_replaces_target_with_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup = backup_path(dir.path(), "0.1.25");
+        let target = dir.path().join("audetic");
+        std::fs::write(&backup, "previous build").unwrap();
+        std::fs::write(&target, "broken build").unwrap();
+
+        restore_binary(&backup, &target).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "previous build");
+        assert!(backup.exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&target).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    #[test]
+    fn rollback_restores_and_records_the_previous_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("audeticd");
+        let state_file = dir.path().join("update_state.json");
+        std::fs::write(&target, "broken build").unwrap();
+        std::fs::write(backup_path(dir.path(), "0.1.25"), "previous build").unwrap();
+        std::fs::write(
+            &state_file,
+            r#"{"current_version":"0.1.26","channel":"beta","auto_update":false,"last_downloaded_version":"0.1.26","pending_restart":true}"#,
+        )
+        .unwrap();
+
+        let version = rollback(&target, &state_file, &dir.path().join("update.lock")).unwrap();
+
+        assert_eq!(version, "0.1.25");
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "previous build");
+        let state: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&state_file).unwrap()).unwrap();
+        assert_eq!(state["current_version"], "0.1.25");
+        assert_eq!(state["pending_restart"], false);
+        assert!(state["last_downloaded_version"].is_null());
+        assert_eq!(state["channel"], "beta");
+        assert_eq!(state["auto_update"], false);
+    }
+
+    #[test]
+    fn rollback_without_a_backup_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("audeticd");
+        std::fs::write(&target, "only build").unwrap();
+        let err = rollback(
+            &target,
+            &dir.path().join("update_state.json"),
+            &dir.path().join("update.lock"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("No previous version"), "{err}");
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "only build");
+    }
+}
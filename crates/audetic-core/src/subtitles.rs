@@ -0,0 +1,91 @@
+//! SRT subtitle formatting for timestamped transcripts, shared by
+//! `audetic transcribe --format srt` and the daemon's meeting transcript
+//! download.
+
+use crate::jobs_client::Segment;
+
+/// Format `segments` as SRT, one cue per segment. Without segments the
+/// whole `text` becomes a single cue, since there are no timings to split
+/// it by.
+pub fn format_srt(segments: Option<&[Segment]>, text: &str) -> String {
+    match segments {
+        Some(segments) if !segments.is_empty() => format_srt_cues(
+            segments
+                .iter()
+                .map(|s| (s.start, s.end, s.text.trim().to_string())),
+        ),
+        _ => format!("1\n00:00:00,000 --> 00:00:00,000\n{}\n", text),
+    }
+}
+
+/// Number and format `(start, end, text)` cues as SRT.
+pub fn format_srt_cues(cues: impl IntoIterator<Item = (f64, f64, String)>) -> String {
+    cues.into_iter()
+        .enumerate()
+        .map(|(i, (start, end, text))| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                i + 1,
+                format_srt_time(start),
+                format_srt_time(end),
+                text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format seconds as SRT timestamp (HH:MM:SS,mmm).
+pub fn format_srt_time(seconds: f64) -> String {
+    let hours = (seconds / 3600.0) as u32;
+    let minutes = ((seconds % 3600.0) / 60.0) as u32;
+    let secs = (seconds % 60.0) as u32;
+    let millis = ((seconds % 1.0) * 1000.0) as u32;
+    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, secs, millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start: f64, end: f64, text: &str) -> Segment {
+        Segment {
+            start,
+            end,
+            text: text.to_string(),
+            words: None,
+        }
+    }
+
+    #[test]
+    fn test_format_srt_time_zero() {
+        assert_eq!(format_srt_time(0.0), "00:00:00,000");
+    }
+
+    #[test]
+    fn test_format_srt_time_minutes() {
+        assert_eq!(format_srt_time(61.5), "00:01:01,500");
+    }
+
+    #[test]
+    fn test_format_srt_time_hours() {
+        assert_eq!(format_srt_time(3661.123), "01:01:01,123");
+    }
+
+    #[test]
+    fn test_format_srt_numbers_one_cue_per_segment() {
+        let segments = [segment(0.0, 1.5, " Hello "), segment(1.5, 3.0, "world")];
+        assert_eq!(
+            format_srt(Some(&segments), "Hello world"),
+            "1\n00:00:00,000 --> 00:00:01,500\nHello\n\n\
+             2\n00:00:01,500 --> 00:00:03,000\nworld\n"
+        );
+    }
+
+    #[test]
+    fn test_format_srt_without_segments_is_one_cue() {
+        let expected = "1\n00:00:00,000 --> 00:00:00,000\nHello world\n";
+        assert_eq!(format_srt(None, "Hello world"), expected);
+        assert_eq!(format_srt(Some(&[]), "Hello world"), expected);
+    }
+}
@@ -4,15 +4,21 @@
 //! router nest, the OpenAPI `servers` URL, the hyprland keybind
 //! command, install-time readiness probes, daemon startup log
 //! examples — derives from these constants instead of hardcoding
-//! `http://127.0.0.1:3737/api`.
+//! `http://127.0.0.1:3737/api`. The port itself is configurable (see
+//! [`port`]); 3737 is only the default.
 //!
 //! Adding a new "well-known" endpoint? Add a path constant under
 //! `paths` and call sites build the full URL via [`api_url`] rather
 //! than inlining the string. The OpenAPI spec's `servers` URL is
-//! still a literal in `api::docs` (utoipa requires it at macro time);
+//! still a literal in `api::docs` (utoipa requires it at macro time,
+//! so it always names the default port);
 //! `tests::openapi_servers_url_matches` keeps it in sync with this
 //! module.
 
+use crate::config::{ApiConfig, Config};
+use std::sync::OnceLock;
+use tracing::warn;
+
 /// Loopback host the daemon binds to. The daemon never listens on
 /// anything else — this is local IPC over TCP, not a network service.
 pub const HOST: &str = "127.0.0.1";
@@ -20,6 +26,56 @@ pub const HOST: &str = "127.0.0.1";
 /// Default TCP port. WHSP in numbers (W=23, H=8, S=19, P=16 → 3737).
 pub const DEFAULT_PORT: u16 = 3737;
 
+/// Environment variable overriding `[api].port`, e.g. to run a second
+/// instance alongside the first.
+pub const PORT_ENV: &str = "AUDETIC_API_PORT";
+
+/// Port the daemon listens on, and the one the CLI and keybinds talk to:
+/// `AUDETIC_API_PORT`, else `[api].port` in config.toml, else
+/// [`DEFAULT_PORT`]. Resolved once per process.
+pub fn port() -> u16 {
+    static PORT: OnceLock<u16> = OnceLock::new();
+    *PORT.get_or_init(|| resolve_port(std::env::var(PORT_ENV).ok().as_deref(), api_config().port))
+}
+
+/// `[api].auth_token`, for clients of a token-protected daemon. Blank
+/// counts as unset.
+pub fn auth_token() -> Option<&'static str> {
+    api_config()
+        .auth_token
+        .as_deref()
+        .filter(|token| !token.trim().is_empty())
+}
+
+/// `[api]` from config.toml, read once per process. A missing config file
+/// means defaults and is not created here.
+fn api_config() -> &'static ApiConfig {
+    static API: OnceLock<ApiConfig> = OnceLock::new();
+    API.get_or_init(|| {
+        crate::global::config_file()
+            .ok()
+            .filter(|path| path.exists())
+            .and_then(|path| Config::load_from(&path).ok())
+            .map(|config| config.api)
+            .unwrap_or_default()
+    })
+}
+
+/// [`port`] for a config that's already loaded.
+pub fn port_from(config: &Config) -> u16 {
+    resolve_port(std::env::var(PORT_ENV).ok().as_deref(), config.api.port)
+}
+
+fn resolve_port(env: Option<&str>, configured: u16) -> u16 {
+    let Some(value) = env.map(str::trim).filter(|value| !value.is_empty()) else {
+        return configured;
+    };
+    value.parse().unwrap_or_else(|_| {
+        warn!("Ignoring {PORT_ENV}={value:?}: not a port number; using {configured}");
+        configured
+    })
+}
+
 /// Path prefix every API route is mounted under. Kept in sync with
 /// the OpenAPI `servers` URL declared in `api::docs` so generated
 /// clients hit the right path without translation.
@@ -34,6 +90,7 @@ pub mod paths {
     pub const TOGGLE: &str = "/toggle";
     pub const MEETINGS_TOGGLE: &str = "/meetings/toggle";
     pub const MEETINGS_IMPORT: &str = "/meetings/import";
+    pub const AUDIO_DEVICES: &str = "/audio/devices";
     pub const AGENT_PROFILES: &str = "/agent-profiles";
     pub const SUMMARY_TEMPLATES: &str = "/summary/templates";
     pub const POST_PROCESSING_JOBS: &str = "/post-processing/jobs";
@@ -43,16 +100,22 @@ pub mod paths {
     pub const PROVIDER_CONFIG: &str = "/provider/config";
     pub const PROVIDER_RESET: &str = "/provider/reset";
     pub const PROVIDER_TEST: &str = "/provider/test";
+    pub const CONFIG_IMPORT: &str = "/config/import";
+    pub const CONFIG_RELOAD: &str = "/config/reload";
     pub const HISTORY: &str = "/history";
+    pub const HISTORY_RENORMALIZE: &str = "/history/renormalize";
+    pub const JOBS_RECENT: &str = "/jobs/recent";
     pub const LOGS: &str = "/logs";
     pub const MODELS: &str = "/models";
     pub const TRANSCRIBE: &str = "/transcribe";
     pub const KEYBIND_STATUS: &str = "/keybind/status";
     pub const KEYBIND_INSTALL: &str = "/keybind/install";
+    pub const KEYBIND_CLEANUP: &str = "/keybind/cleanup";
     pub const KEYBIND: &str = "/keybind";
     pub const UPDATE_CHECK: &str = "/update/check";
     pub const UPDATE_INSTALL: &str = "/update/install";
     pub const UPDATE_AUTO: &str = "/update/auto";
+    pub const UPDATE_ROLLBACK: &str = "/update/rollback";
 }
 
 /// Path to one agent profile test endpoint: `AGENT_PROFILES/{id}/test`.
@@ -90,15 +153,25 @@ pub fn post_processing_job_test_path(id: i64) -> String {
     format!("{}/{id}/test", paths::POST_PROCESSING_JOBS)
 }
 
-/// Build a fully-qualified daemon API URL — e.g.
+/// Build a fully-qualified daemon API URL on the configured [`port`] — e.g.
 /// `api_url(paths::TOGGLE)` → `http://127.0.0.1:3737/api/toggle`.
 pub fn api_url(path: &str) -> String {
-    format!("http://{HOST}:{DEFAULT_PORT}{API_PREFIX}{path}")
+    api_url_on(port(), path)
+}
+
+/// [`api_url`] for an explicit port.
+pub fn api_url_on(port: u16, path: &str) -> String {
+    format!("http://{HOST}:{port}{API_PREFIX}{path}")
 }
 
 /// Root URL serving the bundled SPA — `http://127.0.0.1:3737/`.
 pub fn app_url() -> String {
-    format!("http://{HOST}:{DEFAULT_PORT}/")
+    app_url_on(port())
+}
+
+/// [`app_url`] for an explicit port.
+pub fn app_url_on(port: u16) -> String {
+    format!("http://{HOST}:{port}/")
 }
 
 #[cfg(test)]
@@ -107,16 +180,31 @@ mod tests {
 
     #[test]
     fn api_url_formats_correctly() {
-        assert_eq!(api_url(paths::TOGGLE), "http://127.0.0.1:3737/api/toggle");
         assert_eq!(
-            api_url(paths::MEETINGS_TOGGLE),
+            api_url_on(DEFAULT_PORT, paths::TOGGLE),
+            "http://127.0.0.1:3737/api/toggle"
+        );
+        assert_eq!(
+            api_url_on(DEFAULT_PORT, paths::MEETINGS_TOGGLE),
             "http://127.0.0.1:3737/api/meetings/toggle"
         );
-        assert_eq!(api_url(paths::VERSION), "http://127.0.0.1:3737/api/version");
+        assert_eq!(
+            api_url_on(4040, paths::VERSION),
+            "http://127.0.0.1:4040/api/version"
+        );
     }
 
     #[test]
     fn app_url_formats_correctly() {
-        assert_eq!(app_url(), "http://127.0.0.1:3737/");
+        assert_eq!(app_url_on(DEFAULT_PORT), "http://127.0.0.1:3737/");
+    }
+
+    #[test]
+    fn env_port_overrides_config() {
+        assert_eq!(resolve_port(None, 4040), 4040);
+        assert_eq!(resolve_port(Some("5050"), 4040), 5050);
+        assert_eq!(resolve_port(Some(" "), 4040), 4040);
+        assert_eq!(resolve_port(Some("not-a-port"), 4040), 4040);
+        assert_eq!(resolve_port(Some("70000"), 4040), 4040);
     }
 }
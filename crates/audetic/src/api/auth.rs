@@ -0,0 +1,113 @@
+//! Optional bearer-token auth for the API (`[api] auth_token`).
+//!
+//! The server only listens on 127.0.0.1, but that still lets any local
+//! process — including web pages making requests to localhost — toggle
+//! recording or read history. With a token configured, requests must carry
+//! `Authorization: Bearer <token>`; without one the API stays open.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use std::sync::Arc;
+
+use super::error::ApiError;
+
+/// Put every route in `api` behind `token`, when there is one. Routes added
+/// to the returned router afterwards stay public.
+pub fn protect(api: Router, token: Option<Arc<str>>) -> Router {
+    match token {
+        Some(token) => api.route_layer(middleware::from_fn_with_state(token, require_token)),
+        None => api,
+    }
+}
+
+async fn require_token(State(token): State<Arc<str>>, request: Request, next: Next) -> Response {
+    let presented = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match presented {
+        Some(presented) if tokens_match(presented.trim(), &token) => next.run(request).await,
+        _ => {
+            let mut response = ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "Missing or invalid API token. Send `Authorization: Bearer <token>` with the \
+                 token from `[api] auth_token` in config.toml",
+            )
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            response
+        }
+    }
+}
+
+/// Compare without bailing at the first differing byte, so response timing
+/// doesn't reveal how much of a guess was right.
+fn tokens_match(presented: &str, expected: &str) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get};
+    use tower::ServiceExt;
+
+    async fn status_for(token: Option<&str>, authorization: Option<&str>) -> StatusCode {
+        let app = protect(
+            Router::new().route("/history", get(|| async { "entries" })),
+            token.map(Arc::from),
+        );
+        let mut request = Request::get("/history");
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn without_a_token_the_api_is_open() {
+        assert_eq!(status_for(None, None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn matching_bearer_token_is_allowed() {
+        assert_eq!(
+            status_for(Some("s3cret"), Some("Bearer s3cret")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_or_wrong_token_is_unauthorized() {
+        for authorization in [
+            None,
+            Some("Bearer wrong"),
+            Some("Bearer s3cre"),
+            Some("Basic s3cret"),
+            Some("s3cret"),
+        ] {
+            assert_eq!(
+                status_for(Some("s3cret"), authorization).await,
+                StatusCode::UNAUTHORIZED,
+                "{authorization:?}"
+            );
+        }
+    }
+}
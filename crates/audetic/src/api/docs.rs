@@ -7,8 +7,8 @@
 use utoipa::OpenApi;
 
 use super::routes::{
-    agents, history, keybind, logs, meeting_artifacts, meetings, models, post_processing, provider,
-    recording, summary_templates, system, transcribe, update,
+    agents, audio, config, history, jobs, keybind, logs, meeting_artifacts, meetings, models,
+    post_processing, provider, recording, summary_templates, system, transcribe, update,
 };
 
 #[derive(OpenApi)]
@@ -28,23 +28,43 @@ use super::routes::{
         super::version,
         // Recording (dictation)
         recording::toggle_recording,
+        recording::pause_recording,
+        recording::resume_recording,
         recording::recording_status,
+        recording::recording_status_stream,
+        jobs::list_recent_jobs,
+        // Audio input
+        audio::list_audio_devices,
         // History
         history::list_history,
+        history::clear_history,
         history::get_history_by_id,
+        history::delete_history,
+        history::renormalize_history,
+        history::set_history_tags,
+        history::export_history,
+        history::history_stats,
+        history::get_history_audio,
+        history::retranscribe_history,
         // Keybind
         keybind::get_status,
         keybind::install_keybind,
         keybind::uninstall_keybind,
+        keybind::cleanup_keybind,
         // Logs
         logs::get_logs,
         // Provider
         provider::get_config,
+        provider::update_config,
         provider::get_status,
         provider::get_raw_config,
         provider::set_raw_config,
         provider::reset_config,
         provider::run_test,
+        provider::run_init_test,
+        // Config
+        config::import_config,
+        config::reload_config,
         // Local models + on-device transcription
         models::list_models,
         models::get_model,
@@ -57,6 +77,7 @@ use super::routes::{
         // Update
         update::check_update,
         update::install_update,
+        update::rollback_update,
         update::get_auto_update,
         update::set_auto_update,
         // Meetings
@@ -70,7 +91,9 @@ use super::routes::{
         meetings::get_meeting,
         meetings::delete_meeting,
         meetings::meeting_audio,
+        meetings::meeting_transcript,
         meetings::retry_meeting,
+        meetings::resume_meeting,
         meetings::import_meeting,
         // Meeting intelligence
         agents::list_agent_profiles,
@@ -93,18 +116,34 @@ use super::routes::{
         // Service
         super::ServiceInfo,
         super::VersionInfo,
+        audetic_core::build_info::BuildInfo,
         // Recording
         recording::ToggleRequest,
         recording::ToggleResponse,
         recording::CompletedJobSummary,
         recording::RecordingStatusResponse,
+        crate::db::RecentJob,
+        crate::db::RecentJobStatus,
         // History
         crate::history::HistoryEntry,
+        crate::history::RenormalizeReport,
+        crate::history::ExportFormat,
+        crate::db::HistoryStats,
+        crate::db::DayCount,
+        history::RenormalizeRequest,
+        history::SetTagsRequest,
+        history::RetranscribeRequest,
+        history::DeleteHistoryResponse,
+        history::ClearHistoryResponse,
         // Keybind
         crate::keybind::KeybindStatus,
         keybind::InstallRequest,
         keybind::InstallResponse,
         keybind::UninstallResponse,
+        keybind::CleanupResponse,
+        crate::keybind::CleanupResult,
+        crate::keybind::UninstallResult,
+        crate::keybind::RemovedLine,
         // Logs
         crate::logs::LogsResult,
         // Provider
@@ -113,6 +152,14 @@ use super::routes::{
         crate::transcription::ProviderTestResult,
         crate::config::WhisperConfig,
         provider::ProviderTestRequest,
+        provider::ProviderUpdateResponse,
+        // Config
+        config::ConfigImportRequest,
+        config::ConfigImportResponse,
+        crate::app::ConfigReloadOutcome,
+        // Audio input
+        crate::audio::input_device::InputDeviceInfo,
+        audio::AudioDevicesResponse,
         // Local models + on-device transcription
         crate::transcription::models::ModelDescriptor,
         crate::transcription::models::DownloadProgress,
@@ -124,6 +171,7 @@ use super::routes::{
         system::InstallStatusResponse,
         // Update
         crate::update::UpdateReport,
+        crate::update::UpdateOutcome,
         update::UpdateInstallRequest,
         update::AutoUpdateRequest,
         update::AutoUpdateResponse,
@@ -138,8 +186,11 @@ use super::routes::{
         meetings::MeetingSummary,
         meetings::MeetingsListResponse,
         meetings::MeetingDetailResponse,
+        meetings::TranscriptFormat,
         audetic_core::jobs_client::Segment,
+        crate::transcription::Utterance,
         meetings::MeetingRetryResponse,
+        meetings::MeetingResumeResponse,
         meetings::MeetingDeleteResponse,
         meetings::MeetingImportResponse,
         // Meeting intelligence
@@ -171,6 +222,7 @@ use super::routes::{
     tags(
         (name = "service", description = "Service identity and liveness"),
         (name = "recording", description = "Dictation (voice-to-text) control"),
+        (name = "audio", description = "Microphone selection"),
         (name = "meetings", description = "Long-form meeting recording"),
         (name = "meeting_artifacts", description = "Generated meeting summaries and notes"),
         (name = "agents", description = "Local coding-agent CLI profiles"),
@@ -178,6 +230,7 @@ use super::routes::{
         (name = "history", description = "Past transcriptions"),
         (name = "keybind", description = "Hyprland keybinding management"),
         (name = "provider", description = "Transcription provider configuration"),
+        (name = "config", description = "Whole-config import"),
         (name = "models", description = "On-device transcription model management"),
         (name = "transcribe", description = "One-shot file transcription"),
         (name = "system", description = "External tool / dependency availability"),
@@ -191,7 +244,7 @@ pub struct ApiDoc;
 #[cfg(test)]
 mod tests {
     use super::ApiDoc;
-    use crate::api::url::{api_url, paths};
+    use crate::api::url::{api_url_on, paths, DEFAULT_PORT};
     use utoipa::OpenApi;
 
     /// utoipa requires a literal in the `servers(url = ...)` macro, so we can't
@@ -208,10 +261,11 @@ mod tests {
             .map(|s| s.url.clone())
             .expect("OpenAPI doc must declare at least one server");
 
-        // Server URL is the base (no path suffix), so we compare against `api_url("")`.
+        // Server URL is the base (no path suffix) on the default port, so we
+        // compare against `api_url_on(DEFAULT_PORT, "")`.
         assert_eq!(
             server_url,
-            api_url(""),
+            api_url_on(DEFAULT_PORT, ""),
             "OpenAPI servers URL drifted from api::url::api_url(\"\"). \
              Update either api/docs.rs servers() or audetic_core::url to match."
         );
@@ -7,11 +7,15 @@ use axum::{
 };
 use serde_json::json;
 
+use crate::transcription::{OverLimit, ProviderChoiceError, TranscriptionError};
+
 /// API error type that converts to JSON responses.
 #[derive(Debug)]
 pub struct ApiError {
     status: StatusCode,
     message: String,
+    /// Machine-readable reason, sent as `code` when set.
+    code: Option<&'static str>,
 }
 
 impl ApiError {
@@ -19,6 +23,7 @@ impl ApiError {
         Self {
             status,
             message: message.into(),
+            code: None,
         }
     }
 
@@ -33,23 +38,160 @@ impl ApiError {
     pub fn not_found(message: impl Into<String>) -> Self {
         Self::new(StatusCode::NOT_FOUND, message)
     }
+
+    /// Keep the status and code but replace the message, e.g. to add
+    /// context.
+    pub fn with_message(self, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..self
+        }
+    }
+
+    pub fn with_code(self, code: &'static str) -> Self {
+        Self {
+            code: Some(code),
+            ..self
+        }
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let body = Json(json!({
+        let mut body = json!({
             "error": true,
             "message": self.message,
-        }));
-        (self.status, body).into_response()
+        });
+        if let Some(code) = self.code {
+            body["code"] = json!(code);
+        }
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// Classified transcription failures keep their meaning over HTTP, with the
+/// variant in `code`. A rejected provider key is a 502 like any other
+/// upstream failure, so it can't be mistaken for a 401 from the daemon's own
+/// token check.
+impl From<TranscriptionError> for ApiError {
+    fn from(err: TranscriptionError) -> Self {
+        let status = match &err {
+            TranscriptionError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            TranscriptionError::UnsupportedFormat(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            TranscriptionError::Auth(_)
+            | TranscriptionError::Network(_)
+            | TranscriptionError::Provider(_) => StatusCode::BAD_GATEWAY,
+        };
+        Self::new(status, err.to_string()).with_code(err.code())
     }
 }
 
+/// Transcription errors anywhere in the chain map as above. A provider that
+/// can't be used is the caller's to fix (400, or 409 when none is
+/// configured), and audio over a provider's limits is a 413.
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
+        if err
+            .chain()
+            .any(|cause| cause.downcast_ref::<TranscriptionError>().is_some())
+        {
+            return TranscriptionError::from(err).into();
+        }
+        if let Some(choice) = err.downcast_ref::<ProviderChoiceError>() {
+            let status = match choice {
+                ProviderChoiceError::NotConfigured => StatusCode::CONFLICT,
+                ProviderChoiceError::NeedsApiKey(_) | ProviderChoiceError::Unusable { .. } => {
+                    StatusCode::BAD_REQUEST
+                }
+            };
+            return Self::new(status, format!("{err:#}"));
+        }
+        if err.downcast_ref::<OverLimit>().is_some() {
+            return Self::new(StatusCode::PAYLOAD_TOO_LARGE, format!("{err:#}"));
+        }
         Self::internal(err.to_string())
     }
 }
 
 /// Result type for API handlers.
 pub type ApiResult<T> = Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn status_for(err: anyhow::Error) -> StatusCode {
+        ApiError::from(err).status
+    }
+
+    #[test]
+    fn transcription_errors_map_to_status_codes() {
+        let cases = [
+            (
+                TranscriptionError::Auth("bad key".into()),
+                StatusCode::BAD_GATEWAY,
+            ),
+            (
+                TranscriptionError::RateLimited("slow down".into()),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                TranscriptionError::UnsupportedFormat("not audio".into()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                TranscriptionError::Network("refused".into()),
+                StatusCode::BAD_GATEWAY,
+            ),
+            (
+                TranscriptionError::Provider("boom".into()),
+                StatusCode::BAD_GATEWAY,
+            ),
+        ];
+        for (err, expected) in cases {
+            assert_eq!(status_for(err.into()), expected);
+        }
+    }
+
+    #[test]
+    fn wrapped_transcription_errors_keep_status_and_context() {
+        let err = Err::<(), _>(TranscriptionError::Auth(
+            "Deepgram API error: Invalid credentials".into(),
+        ))
+        .context("Re-transcription failed")
+        .unwrap_err();
+
+        let api = ApiError::from(err);
+        assert_eq!(api.status, StatusCode::BAD_GATEWAY);
+        assert_eq!(api.code, Some("provider_auth"));
+        assert_eq!(
+            api.message,
+            "Re-transcription failed: Deepgram API error: Invalid credentials"
+        );
+    }
+
+    #[test]
+    fn provider_choice_and_limit_errors_are_client_errors() {
+        assert_eq!(
+            status_for(ProviderChoiceError::NotConfigured.into()),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            status_for(ProviderChoiceError::NeedsApiKey("deepgram".into()).into()),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status_for(OverLimit("Audio is 2h long".into()).into()),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[test]
+    fn other_errors_stay_internal() {
+        assert_eq!(
+            status_for(anyhow::anyhow!("database is locked")),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -9,6 +9,7 @@
 //! - Application logs
 //! - OpenAPI spec (/openapi.json)
 
+pub mod auth;
 pub mod docs;
 pub mod error;
 pub mod routes;
@@ -21,15 +22,21 @@ pub use audetic_core::url;
 use crate::config::Config;
 use crate::post_processing::PostProcessingService;
 use anyhow::Result;
+use audetic_core::build_info::BuildInfo;
 use axum::{response::Json, routing::get, Router};
 use serde::Serialize;
 use serde_json::Value;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use tokio::net::TcpListener;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tracing::info;
 use utoipa::{OpenApi, ToSchema};
 
-pub use routes::recording::{ApiCommand, RecordingState, ToggleRequest};
+pub use routes::recording::{ApiCommand, DictationUnavailable, RecordingState, ToggleRequest};
 
 /// Response for GET / — service identity and basic status.
 #[derive(Debug, Serialize, ToSchema)]
@@ -39,18 +46,59 @@ pub struct ServiceInfo {
     pub status: String,
 }
 
-/// Response for GET /version.
+/// Response for GET /version: the daemon's name plus the same build
+/// metadata `audetic version --json` prints.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct VersionInfo {
     pub name: String,
-    pub version: String,
+    #[serde(flatten)]
+    pub build: BuildInfo,
+}
+
+/// The API port is already taken. In practice this means another daemon
+/// (usually the installed service) is running, so callers surface it as a
+/// friendly message instead of a raw bind error.
+#[derive(Debug)]
+pub struct AlreadyRunning {
+    pub port: u16,
+}
+
+impl fmt::Display for AlreadyRunning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Port {port} is already in use. Audetic already appears to be running — open \
+             http://{host}:{port}/ or stop the existing service first. If another program \
+             holds the port, set `[api] port` in config.toml (or {env}) to use a different one",
+            host = url::HOST,
+            port = self.port,
+            env = url::PORT_ENV
+        )
+    }
+}
+
+impl std::error::Error for AlreadyRunning {}
+
+/// Bind the API listener on [`url::HOST`], mapping `AddrInUse` to
+/// [`AlreadyRunning`].
+pub async fn bind_listener(port: u16) -> Result<TcpListener> {
+    match TcpListener::bind((url::HOST, port)).await {
+        Ok(listener) => Ok(listener),
+        Err(e) if e.kind() == io::ErrorKind::AddrInUse => Err(AlreadyRunning { port }.into()),
+        Err(e) => Err(anyhow::Error::new(e).context(format!(
+            "Failed to bind API server to {}:{}",
+            url::HOST,
+            port
+        ))),
+    }
 }
 
 pub struct ApiServer {
-    port: u16,
     recording_state: RecordingState,
     meeting_state: Option<routes::meetings::MeetingState>,
     post_processing_state: routes::post_processing::PostProcessingApiState,
+    /// `[api] auth_token`; `None` leaves the API open.
+    auth_token: Option<Arc<str>>,
 }
 
 impl ApiServer {
@@ -61,19 +109,43 @@ impl ApiServer {
         post_processing: std::sync::Arc<PostProcessingService>,
     ) -> Self {
         Self {
-            port: url::DEFAULT_PORT,
             recording_state: RecordingState {
                 tx,
                 status,
                 waybar_config: config.ui.waybar.clone(),
+                unavailable: Default::default(),
             },
             meeting_state: None,
             post_processing_state: routes::post_processing::PostProcessingApiState {
                 service: post_processing,
             },
+            auth_token: config
+                .api
+                .auth_token
+                .as_deref()
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(Arc::from),
         }
     }
 
+    /// Refuse dictation toggles with `reason`, for a service started
+    /// without a transcription provider.
+    pub fn with_dictation_unavailable(self, reason: impl Into<String>) -> Self {
+        *self
+            .recording_state
+            .unavailable
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = Some(reason.into());
+        self
+    }
+
+    /// The handle behind [`with_dictation_unavailable`](Self::with_dictation_unavailable),
+    /// for the service to update when a config reload adds or removes the provider.
+    pub fn dictation_unavailable(&self) -> DictationUnavailable {
+        std::sync::Arc::clone(&self.recording_state.unavailable)
+    }
+
     pub fn with_meeting_state(
         mut self,
         meeting_status: crate::meeting::MeetingStatusHandle,
@@ -83,10 +155,13 @@ impl ApiServer {
         post_processing: std::sync::Arc<PostProcessingService>,
         inspector: std::sync::Arc<dyn crate::meeting::MediaInspector>,
         meetings_dir: std::path::PathBuf,
+        meeting: &crate::config::MeetingConfig,
     ) -> Self {
         let services = crate::meeting::ProcessingServices {
             transcription: transcription.clone(),
             post_processing,
+            diarize: meeting.diarize,
+            chunk_minutes: meeting.chunk_minutes,
         };
         self.meeting_state = Some(routes::meetings::MeetingState {
             tx: self.recording_state.tx.clone(),
@@ -99,16 +174,26 @@ impl ApiServer {
         self
     }
 
-    pub async fn start(self) -> Result<()> {
+    /// Serve the API on a listener from [`bind_listener`] until `shutdown`
+    /// resolves, then stop accepting connections and let in-flight requests
+    /// finish. Binding is split out so startup can fail fast on a taken port
+    /// before spawning the server task.
+    pub async fn start(
+        self,
+        listener: TcpListener,
+        shutdown: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<()> {
         // Build the API surface. All routes nest under `/api` so the daemon
         // can serve the bundled web-ui at `/` without colliding with API
         // paths (e.g. /meetings is also a SPA route).
+        let commands = self.recording_state.tx.clone();
         let mut api = Router::new()
-            .route("/", get(status))
-            .route("/version", get(version))
             .route("/openapi.json", get(openapi_spec))
             .nest("", routes::recording::router(self.recording_state))
+            .nest("/audio", routes::audio::router())
+            .nest("/config", routes::config::router(commands))
             .nest("/history", routes::history::router())
+            .nest("/jobs", routes::jobs::router())
             .nest("/keybind", routes::keybind::router())
             .nest("/logs", routes::logs::router())
             .nest("/models", routes::models::router())
@@ -125,6 +210,10 @@ impl ApiServer {
         if let Some(meeting_state) = self.meeting_state {
             api = api.merge(routes::meetings::router(meeting_state));
         }
+        if self.auth_token.is_some() {
+            info!("API token auth enabled");
+        }
+        let api = with_public_routes(api, self.auth_token);
 
         // Permissive CORS is safe here: the server binds to 127.0.0.1 only, so
         // the only callers that can reach it are already on this machine.
@@ -135,22 +224,31 @@ impl ApiServer {
             .fallback(static_assets::serve_static)
             .layer(ServiceBuilder::new().layer(CorsLayer::permissive()));
 
-        let listener =
-            tokio::net::TcpListener::bind(&format!("{}:{}", url::HOST, self.port)).await?;
-
-        info!("API server listening on http://{}:{}", url::HOST, self.port);
-        info!("API spec: {}", url::api_url("/openapi.json"));
+        let addr = listener.local_addr()?;
+        info!("API server listening on http://{}", addr);
+        info!("API spec: http://{}{}/openapi.json", addr, url::API_PREFIX);
         info!(
             "Meeting endpoints {}",
             if has_meeting { "enabled" } else { "disabled" }
         );
 
-        axum::serve(listener, app).await?;
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown)
+            .await?;
 
         Ok(())
     }
 }
 
+/// `api` behind the optional token, plus the service info and version
+/// endpoints, which stay public so clients can find the daemon before
+/// authenticating.
+fn with_public_routes(api: Router, auth_token: Option<Arc<str>>) -> Router {
+    auth::protect(api, auth_token)
+        .route("/", get(status))
+        .route("/version", get(version))
+}
+
 #[utoipa::path(
     get,
     path = "/",
@@ -174,13 +272,13 @@ pub async fn status() -> Json<ServiceInfo> {
     tag = "service",
     operation_id = "service_version",
     responses(
-        (status = 200, description = "Daemon name and version", body = VersionInfo),
+        (status = 200, description = "Daemon name, version, and build metadata", body = VersionInfo),
     ),
 )]
 pub async fn version() -> Json<VersionInfo> {
     Json(VersionInfo {
         name: "audetic".to_string(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
+        build: BuildInfo::current(),
     })
 }
 
@@ -189,3 +287,50 @@ async fn openapi_spec() -> Json<Value> {
     let spec = docs::ApiDoc::openapi();
     Json(serde_json::to_value(spec).unwrap_or(Value::Null))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bind_occupied_port_reports_already_running() {
+        let occupied = TcpListener::bind((url::HOST, 0)).await.unwrap();
+        let port = occupied.local_addr().unwrap().port();
+
+        let err = bind_listener(port).await.unwrap_err();
+        let already = err
+            .downcast_ref::<AlreadyRunning>()
+            .expect("AddrInUse should map to AlreadyRunning");
+        assert_eq!(already.port, port);
+        assert!(err.to_string().contains("already appears to be running"));
+        assert!(err.to_string().contains("[api] port"));
+        assert_eq!(
+            crate::app::exit_code(&err),
+            crate::app::EXIT_ALREADY_RUNNING
+        );
+    }
+
+    #[tokio::test]
+    async fn test_info_and_version_stay_public_with_a_token() {
+        use axum::{body::Body, http::Request, http::StatusCode};
+        use tower::ServiceExt;
+
+        let api = Router::new().route("/history", get(|| async { "entries" }));
+        let app = with_public_routes(api, Some(Arc::from("s3cret")));
+        let status_of = |path: &str| {
+            let request = Request::get(path).body(Body::empty()).unwrap();
+            let app = app.clone();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+
+        assert_eq!(status_of("/").await, StatusCode::OK);
+        assert_eq!(status_of("/version").await, StatusCode::OK);
+        assert_eq!(status_of("/history").await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_bind_free_port_succeeds() {
+        let listener = bind_listener(0).await.unwrap();
+        assert_ne!(listener.local_addr().unwrap().port(), 0);
+    }
+}
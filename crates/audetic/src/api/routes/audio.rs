@@ -0,0 +1,39 @@
+//! Audio input routes.
+//!
+//! `GET /audio/devices` lists the microphones the daemon can record from, so
+//! `[audio].input_device` can be set to one of their names.
+
+use crate::api::error::{ApiError, ApiResult};
+use crate::audio::input_device::{self, InputDeviceInfo};
+use axum::{response::Json, routing::get, Router};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Response for `GET /audio/devices`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AudioDevicesResponse {
+    pub devices: Vec<InputDeviceInfo>,
+}
+
+pub fn router() -> Router {
+    Router::new().route("/devices", get(list_audio_devices))
+}
+
+/// List input devices, flagging the system default.
+#[utoipa::path(
+    get,
+    path = "/audio/devices",
+    tag = "audio",
+    operation_id = "list_audio_devices",
+    responses(
+        (status = 200, description = "Available input devices", body = AudioDevicesResponse),
+        (status = 500, description = "The audio host couldn't list devices"),
+    ),
+)]
+pub async fn list_audio_devices() -> ApiResult<Json<AudioDevicesResponse>> {
+    // Enumerating may block in CoreAudio (and on the mic permission prompt).
+    let devices = tokio::task::spawn_blocking(input_device::list_input_devices)
+        .await
+        .map_err(|e| ApiError::internal(format!("device listing panicked: {e}")))??;
+    Ok(Json(AudioDevicesResponse { devices }))
+}
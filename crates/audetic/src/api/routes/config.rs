@@ -0,0 +1,122 @@
+//! Whole-config import (`POST /config/import`) and reload
+//! (`POST /config/reload`).
+//!
+//! Backs `audetic config import` and `audetic config reload`. Export is
+//! read-only and runs in the CLI against the local file; import writes
+//! `config.toml`, so — like the provider config endpoints — it goes through
+//! the daemon, which validates the result and backs up the current file
+//! first.
+
+use crate::api::error::{ApiError, ApiResult};
+use crate::app::ConfigReloadOutcome;
+use crate::config::Config;
+use crate::transcription::validate_provider_config;
+use audetic_core::config_keys::unknown_keys;
+use axum::{extract::State, http::StatusCode, response::Json, routing::post, Router};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use utoipa::ToSchema;
+
+use super::provider::backup_config_file;
+use super::recording::ApiCommand;
+
+/// Request body for `POST /config/import`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ConfigImportRequest {
+    /// Contents of the shared `config.toml`.
+    pub toml: String,
+    /// Overlay the file onto the current config, keeping settings it omits
+    /// and existing secrets. When false the file replaces the config.
+    #[serde(default)]
+    pub merge: bool,
+}
+
+/// Response for `POST /config/import`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConfigImportResponse {
+    /// Where the previous `config.toml` was backed up, if one existed.
+    pub backup_path: Option<String>,
+    /// Non-fatal problems with the imported settings, including keys that
+    /// were ignored.
+    pub warnings: Vec<String>,
+}
+
+/// Create the config router. `commands` reaches the service loop, which
+/// applies reloads.
+pub fn router(commands: mpsc::Sender<ApiCommand>) -> Router {
+    Router::new()
+        .route("/import", post(import_config))
+        .route("/reload", post(reload_config))
+        .with_state(commands)
+}
+
+/// Import a shared config file, replacing or merging over the current one.
+/// Takes effect on the next reload or restart.
+#[utoipa::path(
+    post,
+    path = "/config/import",
+    tag = "config",
+    request_body = ConfigImportRequest,
+    responses(
+        (status = 200, description = "Config imported", body = ConfigImportResponse),
+        (status = 400, description = "Invalid TOML, unknown value types, or invalid provider settings"),
+    ),
+)]
+pub async fn import_config(
+    Json(request): Json<ConfigImportRequest>,
+) -> ApiResult<Json<ConfigImportResponse>> {
+    let current = Config::load().map_err(ApiError::from)?;
+    let imported = current
+        .import_toml(&request.toml, request.merge)
+        .map_err(|e| ApiError::bad_request(format!("{e:#}")))?;
+
+    if let Some(provider) = imported.whisper.provider.as_deref() {
+        if let Some(error) = validate_provider_config(provider, &imported.whisper) {
+            return Err(ApiError::bad_request(error));
+        }
+    }
+
+    let backup_path = backup_config_file().map_err(ApiError::from)?;
+    imported.save().map_err(ApiError::from)?;
+
+    // Only known keys are saved, so name the ones the file had that were
+    // dropped.
+    let mut warnings = toml::from_str::<toml::Table>(&request.toml)
+        .map(|table| unknown_keys(&table))
+        .unwrap_or_default();
+    warnings.extend(imported.validate());
+
+    Ok(Json(ConfigImportResponse {
+        backup_path: backup_path.map(|p| p.display().to_string()),
+        warnings,
+    }))
+}
+
+/// Re-read `config.toml` and apply it without restarting: the dictation
+/// provider, text output, and indicator are rebuilt and used from the next
+/// toggle. A config that fails to load or validate is rejected and the
+/// running one kept.
+#[utoipa::path(
+    post,
+    path = "/config/reload",
+    tag = "config",
+    responses(
+        (status = 200, description = "Config reloaded", body = ConfigReloadOutcome),
+        (status = 400, description = "The new config is invalid; the running config was kept"),
+    ),
+)]
+pub async fn reload_config(
+    State(commands): State<mpsc::Sender<ApiCommand>>,
+) -> ApiResult<Json<ConfigReloadOutcome>> {
+    let (reply, outcome) = oneshot::channel();
+    let unavailable = || ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "event loop unavailable");
+    commands
+        .send(ApiCommand::ReloadConfig { reply })
+        .await
+        .map_err(|_| unavailable())?;
+    outcome
+        .await
+        .map_err(|_| unavailable())?
+        .map(Json)
+        .map_err(|e| ApiError::bad_request(format!("{e:#}; keeping the current config")))
+}
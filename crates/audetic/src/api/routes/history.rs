@@ -1,37 +1,123 @@
 //! History API routes.
 
 use crate::api::error::{ApiError, ApiResult};
-use crate::history::{self, HistoryEntry, SearchParams};
+use crate::db;
+use crate::history::{self, ExportFormat, HistoryEntry, RenormalizeReport, SearchParams};
+use crate::transcription::jobs_client::mime_type_for_extension;
 use axum::{
+    body::{Body, Bytes},
     extract::{Path, Query},
-    response::Json,
-    routing::get,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post, put},
     Router,
 };
-use serde::Deserialize;
-use utoipa::IntoParams;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::io::ReaderStream;
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
 
 /// Query parameters for history search.
 #[derive(Debug, Deserialize, Default, IntoParams)]
 pub struct HistoryQueryParams {
-    /// Search query
+    /// Search terms, ranked by relevance; `"quoted"` text matches as a phrase
     pub q: Option<String>,
     /// Start date (YYYY-MM-DD)
     pub from: Option<String>,
     /// End date (YYYY-MM-DD)
     pub to: Option<String>,
+    /// Only entries carrying this tag
+    pub tag: Option<String>,
     /// Maximum results (default 20)
     pub limit: Option<usize>,
+    /// Cut each entry's text to this many characters and drop its segments;
+    /// `word_count` still covers the whole transcript
+    pub preview: Option<usize>,
+    /// Return complete text even when `preview` is set
+    #[serde(default)]
+    pub full: bool,
+}
+
+/// Query parameters for a history export.
+#[derive(Debug, Deserialize, Default, IntoParams)]
+pub struct ExportQueryParams {
+    /// `json` (default), `ndjson`, `csv`, or `markdown`
+    pub format: Option<ExportFormat>,
+    /// Only entries matching these search terms
+    pub q: Option<String>,
+    /// Start date (YYYY-MM-DD)
+    pub from: Option<String>,
+    /// End date (YYYY-MM-DD)
+    pub to: Option<String>,
+    /// Only entries carrying this tag
+    pub tag: Option<String>,
+}
+
+/// Query parameters for clearing old history.
+#[derive(Debug, Deserialize, Default, IntoParams)]
+pub struct ClearHistoryParams {
+    /// Delete entries created before this date (YYYY-MM-DD); required
+    pub before: Option<String>,
+}
+
+/// Request body for re-normalizing stored history.
+#[derive(Debug, Deserialize, Default, ToSchema)]
+pub struct RenormalizeRequest {
+    /// Only entries created on or after this date (YYYY-MM-DD)
+    pub from: Option<String>,
+    /// Count changes without writing them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Request body for re-transcribing a stored recording.
+#[derive(Debug, Deserialize, Default, ToSchema)]
+pub struct RetranscribeRequest {
+    /// Provider to use instead of `[whisper].provider`, e.g. `local`. It
+    /// runs without the configured provider's API key and headers, so
+    /// providers that need a key can't be named here.
+    pub provider: Option<String>,
+}
+
+/// Request body for replacing a transcription's tags.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetTagsRequest {
+    /// New tags; an empty list clears them
+    pub tags: Vec<String>,
+}
+
+/// Response body for a deleted transcription.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeleteHistoryResponse {
+    pub deleted: bool,
+}
+
+/// Response body for clearing old history.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClearHistoryResponse {
+    /// Number of transcriptions deleted
+    pub deleted: usize,
 }
 
 /// Create the history router.
 pub fn router() -> Router {
     Router::new()
-        .route("/", get(list_history))
-        .route("/:id", get(get_history_by_id))
+        .route("/", get(list_history).delete(clear_history))
+        .route("/renormalize", post(renormalize_history))
+        .route("/export", get(export_history))
+        .route("/stats", get(history_stats))
+        .route("/:id", get(get_history_by_id).delete(delete_history))
+        .route("/:id/tags", put(set_history_tags))
+        .route("/:id/audio", get(get_history_audio))
+        .route("/:id/retranscribe", post(retranscribe_history))
 }
 
-/// List transcription history.
+/// List transcription history. Entries carry their full text unless
+/// `preview` is given.
 #[utoipa::path(
     get,
     path = "/history",
@@ -48,13 +134,52 @@ pub async fn list_history(
         query: params.q,
         from: params.from,
         to: params.to,
+        tag: params.tag,
         limit: params.limit.unwrap_or(20),
     };
 
     let entries = history::search(&search_params).map_err(ApiError::from)?;
+    let entries = match params.preview.filter(|_| !params.full) {
+        Some(max_chars) => entries
+            .into_iter()
+            .map(|entry| entry.into_preview(max_chars))
+            .collect(),
+        None => entries,
+    };
     Ok(Json(entries))
 }
 
+/// Delete every transcription created before a date.
+#[utoipa::path(
+    delete,
+    path = "/history",
+    tag = "history",
+    params(ClearHistoryParams),
+    responses(
+        (status = 200, description = "Old transcriptions deleted", body = ClearHistoryResponse),
+        (status = 400, description = "Missing or malformed `before` date"),
+    ),
+)]
+pub async fn clear_history(
+    Query(params): Query<ClearHistoryParams>,
+) -> ApiResult<Json<ClearHistoryResponse>> {
+    let before = parse_before(params.before.as_deref())?;
+    let deleted = history::clear_before(&before).map_err(ApiError::from)?;
+    Ok(Json(ClearHistoryResponse { deleted }))
+}
+
+/// Require `before` and check it's a real `YYYY-MM-DD` date, so a typo can't
+/// turn into a string comparison that deletes more than intended.
+fn parse_before(before: Option<&str>) -> ApiResult<String> {
+    let before = before
+        .map(str::trim)
+        .filter(|before| !before.is_empty())
+        .ok_or_else(|| ApiError::bad_request("`before` is required (YYYY-MM-DD)"))?;
+    chrono::NaiveDate::parse_from_str(before, "%Y-%m-%d")
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .map_err(|_| ApiError::bad_request(format!("Invalid date '{before}': use YYYY-MM-DD")))
+}
+
 /// Get a single transcription.
 #[utoipa::path(
     get,
@@ -64,7 +189,7 @@ pub async fn list_history(
         ("id" = i64, Path, description = "Transcription history id"),
     ),
     responses(
-        (status = 200, description = "Transcription entry", body = HistoryEntry),
+        (status = 200, description = "Transcription entry, with timestamped segments when the provider returned them", body = HistoryEntry),
         (status = 404, description = "Not found"),
     ),
 )]
@@ -75,3 +200,414 @@ pub async fn get_history_by_id(Path(id): Path<i64>) -> ApiResult<Json<HistoryEnt
 
     Ok(Json(entry))
 }
+
+/// Delete a transcription, and its recording if it's still in the temp
+/// directory.
+#[utoipa::path(
+    delete,
+    path = "/history/{id}",
+    tag = "history",
+    params(
+        ("id" = i64, Path, description = "Transcription history id"),
+    ),
+    responses(
+        (status = 200, description = "Transcription deleted", body = DeleteHistoryResponse),
+        (status = 404, description = "Not found"),
+    ),
+)]
+pub async fn delete_history(Path(id): Path<i64>) -> ApiResult<Json<DeleteHistoryResponse>> {
+    if !history::delete(id).map_err(ApiError::from)? {
+        return Err(ApiError::not_found(format!(
+            "Transcription {} not found",
+            id
+        )));
+    }
+    Ok(Json(DeleteHistoryResponse { deleted: true }))
+}
+
+/// Stream the recording behind a transcription, for playback.
+#[utoipa::path(
+    get,
+    path = "/history/{id}/audio",
+    tag = "history",
+    params(
+        ("id" = i64, Path, description = "Transcription history id"),
+    ),
+    responses(
+        (status = 200, description = "The recorded audio", content_type = "audio/wav"),
+        (status = 404, description = "No such transcription, or it has no audio"),
+        (status = 410, description = "The audio file was deleted after transcription"),
+    ),
+)]
+pub async fn get_history_audio(Path(id): Path<i64>) -> ApiResult<Response> {
+    let entry = history::get_by_id(id).map_err(ApiError::from)?;
+    serve_audio(id, entry).await
+}
+
+/// Only ever opens the path stored on the entry, never one derived from the
+/// request, so there is nothing to traverse.
+async fn serve_audio(id: i64, entry: Option<HistoryEntry>) -> ApiResult<Response> {
+    let entry =
+        entry.ok_or_else(|| ApiError::not_found(format!("Transcription {} not found", id)))?;
+    if entry.audio_path.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "Transcription {} has no audio",
+            id
+        )));
+    }
+
+    let path = std::path::Path::new(&entry.audio_path);
+    let file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(ApiError::new(
+                StatusCode::GONE,
+                format!("Audio for transcription {} is no longer on disk", id),
+            ));
+        }
+        Err(e) => {
+            return Err(ApiError::internal(format!(
+                "Failed to open audio for transcription {}: {}",
+                id, e
+            )));
+        }
+    };
+
+    let content_type = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .and_then(|ext| mime_type_for_extension(&ext))
+        .unwrap_or("application/octet-stream");
+
+    Ok((
+        [(header::CONTENT_TYPE, content_type)],
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response())
+}
+
+/// Run a transcription's recording through a provider again and store the
+/// new text in place of the old.
+#[utoipa::path(
+    post,
+    path = "/history/{id}/retranscribe",
+    tag = "history",
+    params(
+        ("id" = i64, Path, description = "Transcription history id"),
+    ),
+    request_body = RetranscribeRequest,
+    responses(
+        (status = 200, description = "Updated transcription entry", body = HistoryEntry),
+        (status = 400, description = "The override names an unknown provider, or one that needs an API key or setup the configured provider doesn't share"),
+        (status = 404, description = "No such transcription, or it has no audio"),
+        (status = 409, description = "No provider is configured and none was given"),
+        (status = 410, description = "The audio file was deleted after transcription"),
+        (status = 413, description = "The audio is over the provider's length or size limit"),
+        (status = 422, description = "The provider couldn't decode the audio"),
+        (status = 429, description = "The provider is rate limiting requests"),
+        (status = 502, description = "The provider rejected its API key (`code: provider_auth`), failed, or couldn't be reached"),
+    ),
+)]
+pub async fn retranscribe_history(
+    Path(id): Path<i64>,
+    Json(req): Json<RetranscribeRequest>,
+) -> ApiResult<Json<HistoryEntry>> {
+    let entry = history::get_by_id(id).map_err(ApiError::from)?;
+    let audio = retranscribable_audio(id, entry)?;
+
+    let provider = req
+        .provider
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let transcript = crate::transcription::transcribe_with_provider(&audio, provider)
+        .await
+        .map_err(|e| {
+            let message = format!("Re-transcription failed: {e:#}");
+            ApiError::from(e).with_message(message)
+        })?;
+
+    let entry = history::replace_transcript(id, &transcript)
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Transcription {} not found", id)))?;
+    Ok(Json(entry))
+}
+
+/// The recording to re-transcribe, if it's still on disk.
+fn retranscribable_audio(id: i64, entry: Option<HistoryEntry>) -> ApiResult<PathBuf> {
+    let entry =
+        entry.ok_or_else(|| ApiError::not_found(format!("Transcription {} not found", id)))?;
+    if entry.audio_path.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "Transcription {} has no audio",
+            id
+        )));
+    }
+
+    let path = PathBuf::from(&entry.audio_path);
+    if !path.is_file() {
+        return Err(ApiError::new(
+            StatusCode::GONE,
+            format!(
+                "Audio for transcription {} is no longer on disk ({}); it was likely deleted after transcription. Set `[behavior] keep_audio_on = \"always\"` to keep future recordings",
+                id,
+                path.display()
+            ),
+        ));
+    }
+    Ok(path)
+}
+
+/// Export the history, oldest first. Without filters the whole history is
+/// streamed as rows are read, so large histories never sit in memory. An
+/// export that fails partway aborts the response instead of ending it
+/// cleanly, so clients can tell it was cut short.
+#[utoipa::path(
+    get,
+    path = "/history/export",
+    tag = "history",
+    params(ExportQueryParams),
+    responses(
+        (status = 200, description = "Matching history entries as a JSON array, NDJSON lines, CSV, or Markdown", body = Vec<HistoryEntry>),
+        (status = 500, description = "The history database couldn't be read"),
+    ),
+)]
+pub async fn export_history(Query(params): Query<ExportQueryParams>) -> ApiResult<Response> {
+    let format = params.format.unwrap_or_default();
+    let search_params = SearchParams {
+        query: params.q,
+        from: params.from,
+        to: params.to,
+        tag: params.tag,
+        // Filters narrow the export; they don't cap it.
+        limit: i64::MAX as usize,
+    };
+    if search_params.has_filters() {
+        let body = history::export(&search_params, format).map_err(ApiError::from)?;
+        return Ok(([(header::CONTENT_TYPE, format.content_type())], body).into_response());
+    }
+
+    // Opened before the response starts, so a database that can't be read
+    // is an error status rather than an empty 200.
+    let conn = db::init_db().map_err(ApiError::from)?;
+    Ok((
+        [(header::CONTENT_TYPE, format.content_type())],
+        streamed_export(move |out| history::export_to(&conn, format, out)),
+    )
+        .into_response())
+}
+
+/// A response body fed by `export` on a blocking thread. If `export` fails,
+/// the body ends with an error, which drops the connection before the final
+/// chunk; a clean end always means a complete export.
+fn streamed_export<F>(export: F) -> Body
+where
+    F: FnOnce(&mut dyn io::Write) -> anyhow::Result<usize> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(16);
+    let failed = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut out = io::BufWriter::with_capacity(64 * 1024, ChannelWriter(tx));
+        let result = export(&mut out);
+        drop(out);
+        // A client that hangs up mid-export surfaces here as a write error.
+        if let Err(e) = result {
+            warn!("History export ended early: {e:#}");
+            let _ = failed.blocking_send(Err(io::Error::other(format!("{e:#}"))));
+        }
+    });
+    Body::from_stream(ReceiverStream::new(rx))
+}
+
+/// Usage statistics: totals, averages, and transcriptions per day.
+#[utoipa::path(
+    get,
+    path = "/history/stats",
+    tag = "history",
+    responses(
+        (status = 200, description = "Totals and per-day counts over the whole history", body = db::HistoryStats),
+    ),
+)]
+pub async fn history_stats() -> ApiResult<Json<db::HistoryStats>> {
+    let stats = history::stats().map_err(ApiError::from)?;
+    Ok(Json(stats))
+}
+
+/// Lets the blocking SQLite scan write into the async response body.
+struct ChannelWriter(mpsc::Sender<io::Result<Bytes>>);
+
+impl io::Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Replace the tags on a transcription.
+#[utoipa::path(
+    put,
+    path = "/history/{id}/tags",
+    tag = "history",
+    params(
+        ("id" = i64, Path, description = "Transcription history id"),
+    ),
+    request_body = SetTagsRequest,
+    responses(
+        (status = 200, description = "Updated transcription entry", body = HistoryEntry),
+        (status = 404, description = "Not found"),
+    ),
+)]
+pub async fn set_history_tags(
+    Path(id): Path<i64>,
+    Json(req): Json<SetTagsRequest>,
+) -> ApiResult<Json<HistoryEntry>> {
+    let entry = history::set_tags(id, &req.tags)
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Transcription {} not found", id)))?;
+
+    Ok(Json(entry))
+}
+
+/// Re-apply the current normalizer to stored raw transcription text.
+#[utoipa::path(
+    post,
+    path = "/history/renormalize",
+    tag = "history",
+    request_body = RenormalizeRequest,
+    responses(
+        (status = 200, description = "Counts of scanned and changed entries", body = RenormalizeReport),
+    ),
+)]
+pub async fn renormalize_history(
+    Json(req): Json<RenormalizeRequest>,
+) -> ApiResult<Json<RenormalizeReport>> {
+    let report = history::renormalize(req.from.as_deref(), req.dry_run).map_err(ApiError::from)?;
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn entry(audio_path: &std::path::Path) -> HistoryEntry {
+        HistoryEntry {
+            id: 7,
+            text: "hello".to_string(),
+            audio_path: audio_path.to_string_lossy().into_owned(),
+            created_at: "2024-01-01 00:00:00".to_string(),
+            tags: Vec::new(),
+            segments: None,
+            word_count: 1,
+            truncated: false,
+        }
+    }
+
+    /// `GET /history/7/audio` with `entry` standing in for the database row.
+    async fn get_audio(entry: Option<HistoryEntry>) -> Response {
+        let app = Router::new().route(
+            "/history/:id/audio",
+            get(move |Path(id): Path<i64>| async move { serve_audio(id, entry).await }),
+        );
+        app.oneshot(
+            Request::get("/history/7/audio")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn streams_existing_audio_with_content_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.wav");
+        std::fs::write(&path, b"RIFF-fake-wav").unwrap();
+
+        let response = get_audio(Some(entry(&path))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "audio/wav");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"RIFF-fake-wav");
+    }
+
+    #[test]
+    fn clear_requires_a_valid_date() {
+        assert_eq!(parse_before(Some(" 2024-06-01 ")).unwrap(), "2024-06-01");
+        for before in [
+            None,
+            Some(""),
+            Some("2024-13-01"),
+            Some("June 1"),
+            Some("2024-6-1x"),
+        ] {
+            assert!(parse_before(before).is_err(), "{before:?}");
+        }
+    }
+
+    #[test]
+    fn retranscribe_needs_audio_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recording.wav");
+        std::fs::write(&path, b"RIFF-fake-wav").unwrap();
+        assert_eq!(retranscribable_audio(7, Some(entry(&path))).unwrap(), path);
+
+        let status = |entry| {
+            retranscribable_audio(7, entry)
+                .unwrap_err()
+                .into_response()
+                .status()
+        };
+        assert_eq!(status(None), StatusCode::NOT_FOUND);
+        assert_eq!(
+            status(Some(entry(std::path::Path::new("")))),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(Some(entry(&dir.path().join("deleted.wav")))),
+            StatusCode::GONE
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_row_is_not_found() {
+        let response = get_audio(None).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn deleted_file_is_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let response = get_audio(Some(entry(&dir.path().join("deleted.wav")))).await;
+        assert_eq!(response.status(), StatusCode::GONE);
+    }
+
+    #[tokio::test]
+    async fn a_complete_export_ends_cleanly() {
+        let body = streamed_export(|out| {
+            out.write_all(b"{\"id\":1}\n{\"id\":2}\n")?;
+            out.flush()?;
+            Ok(2)
+        });
+        let bytes = to_bytes(body, usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], b"{\"id\":1}\n{\"id\":2}\n");
+    }
+
+    #[tokio::test]
+    async fn an_export_that_fails_partway_breaks_the_body() {
+        let body = streamed_export(|out| {
+            out.write_all(b"{\"id\":1}\n")?;
+            anyhow::bail!("disk I/O error")
+        });
+        assert!(to_bytes(body, usize::MAX).await.is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Dictation job outcome routes.
+
+use crate::api::error::{ApiError, ApiResult};
+use crate::db::{self, RecentJob};
+use axum::{extract::Query, response::Json, routing::get, Router};
+use rusqlite::Connection;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+
+/// Query parameters for recent jobs.
+#[derive(Debug, Deserialize, Default, IntoParams)]
+pub struct RecentJobsParams {
+    /// Maximum results (default 10, at most 100)
+    pub limit: Option<usize>,
+}
+
+/// Create the jobs router.
+pub fn router() -> Router {
+    Router::new().route("/recent", get(list_recent_jobs))
+}
+
+/// List the most recent dictation outcomes, successes and failures together.
+#[utoipa::path(
+    get,
+    path = "/jobs/recent",
+    tag = "recording",
+    params(RecentJobsParams),
+    responses(
+        (status = 200, description = "Recent job outcomes, newest first", body = Vec<RecentJob>),
+        (status = 400, description = "Invalid limit"),
+    ),
+)]
+pub async fn list_recent_jobs(
+    Query(params): Query<RecentJobsParams>,
+) -> ApiResult<Json<Vec<RecentJob>>> {
+    let jobs = tokio::task::spawn_blocking(move || {
+        let conn = db::init_db().map_err(ApiError::from)?;
+        recent_jobs(&conn, &params)
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("db task panicked: {e}")))??;
+    Ok(Json(jobs))
+}
+
+fn recent_jobs(conn: &Connection, params: &RecentJobsParams) -> ApiResult<Vec<RecentJob>> {
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+    if !(1..=MAX_LIMIT).contains(&limit) {
+        return Err(ApiError::bad_request(format!(
+            "limit must be between 1 and {MAX_LIMIT}"
+        )));
+    }
+    db::get_recent_jobs(conn, limit).map_err(ApiError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{RecentJobStatus, VoiceToTextData, Workflow, WorkflowData, WorkflowType};
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use std::sync::{Arc, Mutex};
+    use tower::ServiceExt;
+
+    fn seeded_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        let workflow = Workflow::new(
+            WorkflowType::VoiceToText,
+            WorkflowData::VoiceToText(VoiceToTextData {
+                text: "buy milk".to_string(),
+                audio_path: "/tmp/a.wav".to_string(),
+                raw_text: None,
+                segments: None,
+            }),
+        );
+        let id = db::insert_workflow(&conn, &workflow).unwrap();
+        conn.execute(
+            "UPDATE workflows SET created_at = datetime('now', '-1 hour') WHERE id = ?1",
+            [id],
+        )
+        .unwrap();
+        db::insert_failed_job(&conn, Some("job-9"), "provider unreachable").unwrap();
+        conn
+    }
+
+    /// `GET uri` against a router backed by `conn` instead of the real DB.
+    async fn get(conn: Connection, uri: &str) -> (StatusCode, serde_json::Value) {
+        let conn = Arc::new(Mutex::new(conn));
+        let app = Router::new().route(
+            "/jobs/recent",
+            axum::routing::get(move |Query(params): Query<RecentJobsParams>| async move {
+                recent_jobs(&conn.lock().unwrap(), &params).map(Json)
+            }),
+        );
+        let response = app
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn lists_failures_alongside_completed_jobs() {
+        let (status, body) = get(seeded_db(), "/jobs/recent").await;
+        assert_eq!(status, StatusCode::OK);
+        let jobs: Vec<RecentJob> = serde_json::from_value(body).unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].status, RecentJobStatus::Failed);
+        assert_eq!(jobs[0].job_id.as_deref(), Some("job-9"));
+        assert_eq!(jobs[0].error.as_deref(), Some("provider unreachable"));
+        assert_eq!(jobs[1].status, RecentJobStatus::Completed);
+        assert_eq!(jobs[1].text.as_deref(), Some("buy milk"));
+    }
+
+    #[tokio::test]
+    async fn limit_is_applied_and_bounded() {
+        let (_, body) = get(seeded_db(), "/jobs/recent?limit=1").await;
+        assert_eq!(body.as_array().unwrap().len(), 1);
+
+        let (status, _) = get(seeded_db(), "/jobs/recent?limit=0").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
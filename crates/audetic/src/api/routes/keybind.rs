@@ -1,14 +1,15 @@
 //! Keybind API routes.
 
 use crate::api::error::{ApiError, ApiResult};
-use crate::keybind::{self, InstallResult, KeybindStatus, UninstallResult};
+use crate::keybind::{self, CleanupResult, InstallResult, KeybindStatus, UninstallResult};
 use axum::{
+    extract::Query,
     response::Json,
     routing::{delete, get, post},
     Router,
 };
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 /// Request body for keybind install.
 #[derive(Debug, Deserialize, Default, ToSchema)]
@@ -28,14 +29,30 @@ pub struct InstallResponse {
     pub config_path: Option<String>,
 }
 
-/// Result of removing an Audetic-managed hyprland binding.
+/// Query parameters for keybind uninstall.
+#[derive(Debug, Deserialize, Default, IntoParams)]
+pub struct UninstallQuery {
+    /// List the bindings that would be removed without changing any file
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Result of removing Audetic-managed hyprland bindings.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UninstallResponse {
     pub success: bool,
-    pub removed: Option<bool>,
     pub message: String,
-    pub backup_path: Option<String>,
-    pub config_path: Option<String>,
+    #[serde(flatten)]
+    pub result: UninstallResult,
+}
+
+/// Result of collapsing duplicate Audetic sections.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CleanupResponse {
+    pub success: bool,
+    pub message: String,
+    #[serde(flatten)]
+    pub result: CleanupResult,
 }
 
 /// Create the keybind router.
@@ -43,6 +60,7 @@ pub fn router() -> Router {
     Router::new()
         .route("/status", get(get_status))
         .route("/install", post(install_keybind))
+        .route("/cleanup", post(cleanup_keybind))
         .route("/", delete(uninstall_keybind))
 }
 
@@ -98,40 +116,63 @@ pub async fn install_keybind(
     }))
 }
 
-/// Uninstall the keybinding.
+/// Uninstall every Audetic keybinding, or list them with `?dry_run=true`.
 #[utoipa::path(
     delete,
     path = "/keybind",
     tag = "keybind",
+    params(UninstallQuery),
     responses(
         (status = 200, description = "Uninstall result", body = UninstallResponse),
     ),
 )]
-pub async fn uninstall_keybind() -> ApiResult<Json<UninstallResponse>> {
-    let result = keybind::uninstall(false).map_err(ApiError::from)?;
+pub async fn uninstall_keybind(
+    Query(query): Query<UninstallQuery>,
+) -> ApiResult<Json<UninstallResponse>> {
+    let result = keybind::uninstall(query.dry_run).map_err(ApiError::from)?;
 
-    Ok(Json(match result {
-        Some(UninstallResult {
-            removed,
-            backup_path,
-            config_path,
-        }) => UninstallResponse {
-            success: true,
-            removed: Some(removed),
-            message: if removed {
-                "Keybinding removed".to_string()
-            } else {
-                "No keybinding found to remove".to_string()
-            },
-            backup_path: backup_path.map(|p| p.to_string_lossy().into_owned()),
-            config_path: Some(config_path.to_string_lossy().into_owned()),
-        },
-        None => UninstallResponse {
-            success: false,
-            removed: None,
-            message: "No changes made (dry run)".to_string(),
-            backup_path: None,
-            config_path: None,
-        },
+    let message = match (result.removed_count, result.dry_run) {
+        (0, _) => "No keybinding found to remove".to_string(),
+        (count, true) => format!(
+            "Would remove {} keybinding(s) from {} file(s)",
+            count,
+            result.files.len()
+        ),
+        (count, false) => format!(
+            "Removed {} keybinding(s) from {} file(s)",
+            count,
+            result.files.len()
+        ),
+    };
+
+    Ok(Json(UninstallResponse {
+        success: true,
+        message,
+        result,
+    }))
+}
+
+/// Remove duplicate Audetic sections and bindings across all config files.
+#[utoipa::path(
+    post,
+    path = "/keybind/cleanup",
+    tag = "keybind",
+    responses(
+        (status = 200, description = "Cleanup result", body = CleanupResponse),
+    ),
+)]
+pub async fn cleanup_keybind() -> ApiResult<Json<CleanupResponse>> {
+    let result = keybind::cleanup().map_err(ApiError::from)?;
+
+    let message = if result.removed.is_empty() {
+        "No duplicate Audetic bindings found".to_string()
+    } else {
+        format!("Removed {} duplicate line(s)", result.removed.len())
+    };
+
+    Ok(Json(CleanupResponse {
+        success: true,
+        message,
+        result,
     }))
 }
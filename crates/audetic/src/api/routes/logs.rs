@@ -11,6 +11,12 @@ use utoipa::IntoParams;
 pub struct LogsQueryParams {
     /// Number of log entries (default 30)
     pub lines: Option<usize>,
+    /// Minimum priority: emerg, alert, crit, err, warning, notice, info, debug
+    pub level: Option<String>,
+    /// Only entries since this time (journalctl `--since` syntax)
+    pub since: Option<String>,
+    /// Only entries until this time (journalctl `--until` syntax)
+    pub until: Option<String>,
 }
 
 /// Create the logs router.
@@ -26,10 +32,19 @@ pub fn router() -> Router {
     params(LogsQueryParams),
     responses(
         (status = 200, description = "Combined app + transcription logs", body = LogsResult),
+        (status = 400, description = "Unknown level or empty time bound"),
     ),
 )]
 pub async fn get_logs(Query(params): Query<LogsQueryParams>) -> ApiResult<Json<LogsResult>> {
-    let options = LogsOptions::new(params.lines.unwrap_or(30));
+    let options = LogsOptions {
+        lines: params.lines.unwrap_or(30),
+        level: params.level,
+        since: params.since,
+        until: params.until,
+    };
+    options
+        .validate()
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
     let result = logs::get_logs(&options).map_err(ApiError::from)?;
     Ok(Json(result))
 }
@@ -7,7 +7,7 @@ use crate::meeting::{
 };
 use axum::{
     extract::{DefaultBodyLimit, Multipart, Path, Query, State},
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
@@ -138,6 +138,9 @@ pub struct MeetingDetailResponse {
     /// Per-segment timestamps for clickable transcript lines. `None` for
     /// meetings transcribed before timestamps were captured.
     pub transcript_segments: Option<Vec<audetic_core::jobs_client::Segment>>,
+    /// Speaker-labelled turns, present when the meeting was transcribed with
+    /// `[meeting].diarize` and the provider returned speaker labels.
+    pub transcript_utterances: Option<Vec<crate::transcription::Utterance>>,
     pub duration_seconds: Option<i64>,
     pub started_at: String,
     pub completed_at: Option<String>,
@@ -152,6 +155,44 @@ pub struct MeetingsListQuery {
     pub limit: Option<usize>,
 }
 
+/// Query parameters for a meeting transcript download.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct MeetingTranscriptQuery {
+    /// `txt` (default), `srt`, or `json`
+    pub format: Option<TranscriptFormat>,
+}
+
+/// File format of a meeting transcript download.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// Plain transcript text
+    #[default]
+    Txt,
+    /// SubRip subtitles, one cue per stored segment
+    Srt,
+    /// Text plus segments and speaker turns
+    Json,
+}
+
+impl TranscriptFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            TranscriptFormat::Txt => "txt",
+            TranscriptFormat::Srt => "srt",
+            TranscriptFormat::Json => "json",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            TranscriptFormat::Txt => "text/plain; charset=utf-8",
+            TranscriptFormat::Srt => "application/x-subrip; charset=utf-8",
+            TranscriptFormat::Json => "application/json",
+        }
+    }
+}
+
 /// Confirmation that an imported media file has been accepted as a new
 /// meeting. The processing pipeline runs in the background; clients poll
 /// `GET /meetings/{id}` for phase progression and the final transcript.
@@ -181,7 +222,9 @@ pub fn router(state: MeetingState) -> Router {
         )
         .route("/meetings/:id", get(get_meeting).delete(delete_meeting))
         .route("/meetings/:id/audio", get(meeting_audio))
+        .route("/meetings/:id/transcript", get(meeting_transcript))
         .route("/meetings/:id/retry", post(retry_meeting))
+        .route("/meetings/:id/resume", post(resume_meeting))
         .with_state(state)
 }
 
@@ -194,6 +237,15 @@ pub struct MeetingRetryResponse {
     pub message: String,
 }
 
+/// Confirmation that a meeting's post-recording pipeline has been
+/// re-entered; compression and transcription run in the background.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MeetingResumeResponse {
+    pub success: bool,
+    pub meeting_id: i64,
+    pub message: String,
+}
+
 /// Confirmation that a meeting has been deleted. The delete is *soft*: the
 /// meeting is hidden from every API surface but its row and on-disk audio
 /// survive.
@@ -594,6 +646,7 @@ pub async fn get_meeting(
             transcript_path: m.transcript_path,
             transcript_text: m.transcript_text,
             transcript_segments: m.transcript_segments,
+            transcript_utterances: m.transcript_utterances,
             duration_seconds: m.duration_seconds,
             started_at: m.started_at,
             completed_at: m.completed_at,
@@ -699,6 +752,112 @@ fn audio_not_found(id: i64) -> Response {
         .into_response()
 }
 
+/// Download a meeting's transcript as a file: plain text, SRT subtitles
+/// built from the stored segments, or JSON with segments and speaker turns.
+#[utoipa::path(
+    get,
+    path = "/meetings/{id}/transcript",
+    tag = "meetings",
+    params(
+        ("id" = i64, Path, description = "Meeting id"),
+        MeetingTranscriptQuery,
+    ),
+    responses(
+        (status = 200, description = "Transcript file (text/plain, application/x-subrip, or application/json)"),
+        (status = 404, description = "Meeting not found, or not transcribed yet"),
+    ),
+)]
+pub async fn meeting_transcript(
+    Path(id): Path<i64>,
+    Query(query): Query<MeetingTranscriptQuery>,
+    State(_state): State<MeetingState>,
+) -> Response {
+    let lookup = tokio::task::spawn_blocking(move || {
+        let conn = crate::db::init_db()?;
+        crate::db::meetings::MeetingRepository::get(&conn, id)
+    })
+    .await;
+
+    let meeting = match lookup {
+        Ok(Ok(meeting)) => meeting,
+        Ok(Err(e)) => {
+            error!("Failed to load meeting {} for transcript: {}", id, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "success": false, "message": e.to_string() })),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            error!("DB task panicked loading meeting {} transcript: {}", id, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "success": false, "message": "db task panicked" })),
+            )
+                .into_response();
+        }
+    };
+
+    transcript_download(id, meeting, query.format.unwrap_or_default())
+}
+
+/// Render `meeting`'s transcript as `format`, as an attachment named
+/// `meeting-<id>.<ext>`.
+fn transcript_download(
+    id: i64,
+    meeting: Option<crate::db::meetings::MeetingRecord>,
+    format: TranscriptFormat,
+) -> Response {
+    let Some(meeting) = meeting else {
+        return transcript_not_found(format!("Meeting {} not found", id));
+    };
+    let Some(text) = meeting.transcript_text.filter(|t| !t.trim().is_empty()) else {
+        return transcript_not_found(format!("Meeting {} has no transcript yet", id));
+    };
+
+    let body = match format {
+        TranscriptFormat::Txt => format!("{}\n", text.trim_end()),
+        TranscriptFormat::Srt => {
+            audetic_core::subtitles::format_srt(meeting.transcript_segments.as_deref(), text.trim())
+        }
+        TranscriptFormat::Json => json!({
+            "id": meeting.id,
+            "title": meeting.title,
+            "text": text,
+            "segments": meeting.transcript_segments,
+            "utterances": meeting.transcript_utterances,
+        })
+        .to_string(),
+    };
+
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"meeting-{}.{}\"",
+                    id,
+                    format.extension()
+                ),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn transcript_not_found(message: String) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({
+            "success": false,
+            "message": message,
+        })),
+    )
+        .into_response()
+}
+
 /// Re-run transcription on the durable mp3 from a previously failed
 /// meeting. Useful when the backend was the cause (e.g. the 5-min
 /// Bun-fetch idle bug in InferenceServerManager) and the audio is fine.
@@ -865,9 +1024,18 @@ pub async fn retry_meeting(Path(id): Path<i64>, State(state): State<MeetingState
 
     let duration = meeting.duration_seconds.unwrap_or(0);
     let transcription = state.transcription.clone();
+    let diarize = state.services.diarize;
+    let chunk_minutes = state.services.chunk_minutes;
     tokio::spawn(async move {
-        crate::meeting::retry_meeting_transcription(id, resolved_path, duration, transcription)
-            .await;
+        crate::meeting::retry_meeting_transcription(
+            id,
+            resolved_path,
+            duration,
+            transcription,
+            diarize,
+            chunk_minutes,
+        )
+        .await;
     });
 
     (
@@ -881,6 +1049,91 @@ pub async fn retry_meeting(Path(id): Path<i64>, State(state): State<MeetingState
         .into_response()
 }
 
+/// Resume a meeting whose pipeline failed or was left incomplete.
+///
+/// Unlike `/retry`, which only re-runs transcription for `error` meetings,
+/// this re-enters the full pipeline from the compress step and also accepts
+/// meetings a daemon crash stranded in `compressing` / `transcribing`.
+/// Completed meetings are refused. The meeting the live machine is still
+/// processing is refused too — its row is in-flight for a real reason.
+#[utoipa::path(
+    post,
+    path = "/meetings/{id}/resume",
+    tag = "meetings",
+    params(
+        ("id" = i64, Path, description = "Meeting id"),
+    ),
+    responses(
+        (status = 202, description = "Resume kicked off; poll /meetings/:id", body = MeetingResumeResponse),
+        (status = 404, description = "Meeting not found"),
+        (status = 409, description = "Meeting is not resumable, still processing, or audio file missing"),
+    ),
+)]
+pub async fn resume_meeting(Path(id): Path<i64>, State(state): State<MeetingState>) -> Response {
+    info!("Meeting {} resume requested", id);
+
+    let live = state.status.get().await;
+    if live.meeting_id == Some(id) && !MeetingPhase::is_terminal(live.phase.as_str()) {
+        return (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "success": false,
+                "message": format!("Meeting {id} is still being processed"),
+            })),
+        )
+            .into_response();
+    }
+
+    use crate::meeting::ResumeOutcome;
+    match crate::meeting::resume_meeting(id, state.services.clone()).await {
+        Ok(ResumeOutcome::Started { .. }) => (
+            StatusCode::ACCEPTED,
+            Json(MeetingResumeResponse {
+                success: true,
+                meeting_id: id,
+                message: "Resume started; poll /meetings/:id for status".to_string(),
+            }),
+        )
+            .into_response(),
+        Ok(ResumeOutcome::NotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "success": false,
+                "message": format!("Meeting {id} not found"),
+            })),
+        )
+            .into_response(),
+        Ok(ResumeOutcome::NotResumable { status }) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "success": false,
+                "message": format!(
+                    "Meeting {id} is in state '{status}'; only failed or interrupted meetings can be resumed"
+                ),
+            })),
+        )
+            .into_response(),
+        Ok(ResumeOutcome::AudioMissing { audio_path }) => (
+            StatusCode::CONFLICT,
+            Json(json!({
+                "success": false,
+                "message": format!(
+                    "Audio file no longer on disk: {audio_path} (and no .mp3 sibling)"
+                ),
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            error!("Failed to resume meeting {}: {}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "success": false, "message": e.to_string() })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Soft-delete a meeting.
 ///
 /// The user-facing label is "Delete", but the row is only hidden — we stamp
@@ -1162,3 +1415,110 @@ fn import_error(status: StatusCode, message: String) -> Response {
     )
         .into_response()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::meetings::MeetingRecord;
+    use audetic_core::jobs_client::Segment;
+    use axum::body::to_bytes;
+
+    fn meeting(text: Option<&str>, segments: Option<Vec<Segment>>) -> MeetingRecord {
+        MeetingRecord {
+            id: 3,
+            title: Some("Standup".to_string()),
+            status: "completed".to_string(),
+            audio_path: "/tmp/meeting-3.mp3".to_string(),
+            transcript_path: None,
+            transcript_text: text.map(str::to_string),
+            transcript_segments: segments,
+            transcript_utterances: None,
+            duration_seconds: Some(4),
+            started_at: "2024-01-01 09:00:00".to_string(),
+            completed_at: Some("2024-01-01 09:00:04".to_string()),
+            error: None,
+            created_at: "2024-01-01 09:00:00".to_string(),
+            deleted_at: None,
+        }
+    }
+
+    fn segment(start: f64, end: f64, text: &str) -> Segment {
+        Segment {
+            start,
+            end,
+            text: text.to_string(),
+            words: None,
+        }
+    }
+
+    async fn download(
+        meeting: Option<MeetingRecord>,
+        format: TranscriptFormat,
+    ) -> (StatusCode, axum::http::HeaderMap, String) {
+        let response = transcript_download(3, meeting, format);
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, headers, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn text_download_is_an_attachment() {
+        let (status, headers, body) = download(
+            Some(meeting(Some("Morning all. Ship it."), None)),
+            TranscriptFormat::Txt,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[header::CONTENT_TYPE], "text/plain; charset=utf-8");
+        assert_eq!(
+            headers[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"meeting-3.txt\""
+        );
+        assert_eq!(body, "Morning all. Ship it.\n");
+    }
+
+    #[tokio::test]
+    async fn srt_download_uses_stored_segments() {
+        let segments = vec![
+            segment(0.0, 1.5, "Morning all."),
+            segment(1.5, 4.0, " Ship it. "),
+        ];
+        let (status, headers, body) = download(
+            Some(meeting(Some("Morning all. Ship it."), Some(segments))),
+            TranscriptFormat::Srt,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            headers[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"meeting-3.srt\""
+        );
+        assert_eq!(
+            body,
+            "1\n00:00:00,000 --> 00:00:01,500\nMorning all.\n\n\
+             2\n00:00:01,500 --> 00:00:04,000\nShip it.\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn srt_without_segments_is_a_single_cue() {
+        let (_, _, body) = download(
+            Some(meeting(Some("Just text"), None)),
+            TranscriptFormat::Srt,
+        )
+        .await;
+        assert_eq!(body, "1\n00:00:00,000 --> 00:00:00,000\nJust text\n");
+    }
+
+    #[tokio::test]
+    async fn missing_meeting_or_transcript_is_not_found() {
+        let (status, _, _) = download(None, TranscriptFormat::Txt).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) = download(Some(meeting(None, None)), TranscriptFormat::Srt).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _, _) =
+            download(Some(meeting(Some("  "), None)), TranscriptFormat::Json).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
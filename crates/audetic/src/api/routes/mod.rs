@@ -1,7 +1,10 @@
 //! API route modules.
 
 pub mod agents;
+pub mod audio;
+pub mod config;
 pub mod history;
+pub mod jobs;
 pub mod keybind;
 pub mod logs;
 pub mod meeting_artifacts;
@@ -1,47 +1,87 @@
 //! Provider API routes.
 //!
 //! Read endpoints (`GET /provider`, `GET /provider/status`) expose a sanitized
-//! view. The config endpoints (`GET`/`PUT /provider/config`, `POST
-//! /provider/reset`) let the CLL's setup wizard read and write the raw
-//! `WhisperConfig` — the daemon owns the on-disk `config.toml` (and its backups)
-//! so there is a single writer. `POST /provider/test` runs a transcription with
-//! the configured provider so the slim CLI never has to link the provider stack.
+//! view, and `PUT /provider` merges a partial update for clients (e.g. a
+//! companion GUI) that should never see the stored API key. The config
+//! endpoints (`GET`/`PUT /provider/config`, `POST /provider/reset`) let the
+//! CLI's setup wizard read and write the raw `WhisperConfig` — the daemon owns
+//! the on-disk `config.toml` (and its backups) so there is a single writer.
+//! `POST /provider/test` runs a transcription with the configured provider so
+//! the slim CLI never has to link the provider stack — given a file path as
+//! JSON, or an uploaded sample as multipart for a setup UI that has no access
+//! to the daemon's filesystem. `GET /provider/test` is the no-audio variant a
+//! dashboard can poll.
 
 use crate::api::error::{ApiError, ApiResult};
+use crate::audio::wav::temp_file_path;
 use crate::config::{Config, WhisperConfig};
 use crate::global;
 use crate::transcription::{
-    get_provider_info, get_provider_status, test_provider, ProviderInfo, ProviderStatus,
-    ProviderTestResult,
+    get_provider_info, get_provider_status, test_provider_with_config, ProviderInfo,
+    ProviderStatus, ProviderTestResult,
 };
 use anyhow::{Context, Result};
 use axum::{
+    extract::{
+        multipart::{Field, MultipartError},
+        DefaultBodyLimit, FromRequest, Multipart, Request,
+    },
+    http::header::CONTENT_TYPE,
     response::Json,
     routing::{get, post},
     Router,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use utoipa::ToSchema;
 
 const MAX_CONFIG_BACKUPS: usize = 3;
 
-/// Request body for `POST /provider/test`.
+/// Largest sample `POST /provider/test` accepts as an upload. A test clip
+/// only needs a few seconds of speech.
+const MAX_TEST_UPLOAD_BYTES: usize = 25 * 1024 * 1024;
+
+/// How long a provider test may run before it's reported as failed, so a
+/// provider that never answers can't hang the request.
+const PROVIDER_TEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// JSON request body for `POST /provider/test`.
 #[derive(Debug, Default, Deserialize, ToSchema)]
 pub struct ProviderTestRequest {
     /// Optional path to an audio file to transcribe. When omitted, the daemon
     /// only validates that the configured provider initializes.
     pub file: Option<String>,
+    /// Also make a lightweight authenticated request to the provider's
+    /// endpoint, reported in `reachable`.
+    #[serde(default)]
+    pub check_network: bool,
+}
+
+/// Response for `PUT /provider`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProviderUpdateResponse {
+    /// The saved configuration, with the API key reduced to `has_api_key`.
+    pub config: ProviderInfo,
+    /// The running daemon keeps its current transcriber until restarted.
+    pub restart_required: bool,
 }
 
 /// Create the provider router.
 pub fn router() -> Router {
     Router::new()
-        .route("/", get(get_config))
+        .route("/", get(get_config).put(update_config))
         .route("/status", get(get_status))
         .route("/config", get(get_raw_config).put(set_raw_config))
         .route("/reset", post(reset_config))
-        .route("/test", post(run_test))
+        .route(
+            "/test",
+            get(run_init_test)
+                .post(run_test)
+                .layer(DefaultBodyLimit::max(MAX_TEST_UPLOAD_BYTES)),
+        )
 }
 
 /// Get provider configuration.
@@ -59,6 +99,83 @@ pub async fn get_config() -> ApiResult<Json<ProviderInfo>> {
     Ok(Json(info))
 }
 
+/// Update the provider configuration. The body is a partial `WhisperConfig`:
+/// fields present replace the current value (`null` clears one), omitted
+/// fields are kept. The merged config must pass provider validation; the
+/// existing `config.toml` is backed up before saving.
+#[utoipa::path(
+    put,
+    path = "/provider",
+    tag = "provider",
+    operation_id = "update_provider_config",
+    request_body = WhisperConfig,
+    responses(
+        (status = 200, description = "The saved provider configuration", body = ProviderUpdateResponse),
+        (status = 400, description = "Unknown field, wrong type, or invalid provider configuration"),
+    ),
+)]
+pub async fn update_config(Json(patch): Json<Value>) -> ApiResult<Json<ProviderUpdateResponse>> {
+    let config_path = global::config_file().map_err(ApiError::from)?;
+    let backup_dir = global::data_dir()
+        .map_err(ApiError::from)?
+        .join("config-backups");
+    update_config_at(&config_path, &backup_dir, patch).map(Json)
+}
+
+fn update_config_at(
+    config_path: &Path,
+    backup_dir: &Path,
+    patch: Value,
+) -> ApiResult<ProviderUpdateResponse> {
+    let mut config = Config::load_from(config_path).map_err(ApiError::from)?;
+    let whisper = merge_whisper_config(&config.whisper, patch)?;
+
+    let provider = whisper
+        .provider
+        .as_deref()
+        .ok_or_else(|| ApiError::bad_request("provider is required"))?;
+    if let Some(error) = validate_provider_config(provider, &whisper) {
+        return Err(ApiError::bad_request(error));
+    }
+
+    let changed = serde_json::to_value(&whisper).ok() != serde_json::to_value(&config.whisper).ok();
+    if changed {
+        backup_config_file_at(config_path, backup_dir).map_err(ApiError::from)?;
+        config.whisper = whisper;
+        config.save_to(config_path).map_err(ApiError::from)?;
+    }
+
+    Ok(ProviderUpdateResponse {
+        config: get_provider_info_from_config(&config.whisper),
+        restart_required: changed,
+    })
+}
+
+/// Apply a JSON merge patch to `current`, rejecting unknown fields and
+/// values of the wrong type.
+fn merge_whisper_config(current: &WhisperConfig, patch: Value) -> ApiResult<WhisperConfig> {
+    let Value::Object(patch) = patch else {
+        return Err(ApiError::bad_request("Expected a JSON object"));
+    };
+    let mut merged =
+        serde_json::to_value(current).map_err(|e| ApiError::internal(e.to_string()))?;
+    let fields = merged
+        .as_object_mut()
+        .ok_or_else(|| ApiError::internal("Provider config did not serialize to an object"))?;
+
+    for (key, value) in patch {
+        if !fields.contains_key(&key) {
+            return Err(ApiError::bad_request(format!(
+                "Unknown provider field: {key}"
+            )));
+        }
+        fields.insert(key, value);
+    }
+
+    serde_json::from_value(merged)
+        .map_err(|e| ApiError::bad_request(format!("Invalid provider config: {e}")))
+}
+
 /// Get provider status and health.
 #[utoipa::path(
     get,
@@ -128,41 +245,198 @@ pub async fn reset_config() -> ApiResult<Json<WhisperConfig>> {
 }
 
 /// Test the currently-configured provider, optionally against an audio file.
+///
+/// Takes either a JSON [`ProviderTestRequest`] naming a file on the daemon's
+/// machine, or a `multipart/form-data` body with an optional `file` part
+/// (the sample, at most 25 MiB) and an optional `check_network` part
+/// (`true`/`false`). Without a file, only initialization is checked. A test
+/// that doesn't finish within two minutes answers 200 with `success: false`.
 #[utoipa::path(
     post,
     path = "/provider/test",
     tag = "provider",
-    request_body = ProviderTestRequest,
+    request_body(
+        content(
+            (ProviderTestRequest = "application/json"),
+            (String = "multipart/form-data"),
+        ),
+    ),
     responses(
         (status = 200, description = "Provider test result", body = ProviderTestResult),
+        (status = 400, description = "Malformed request body"),
+        (status = 413, description = "Uploaded sample is too large"),
     ),
 )]
-pub async fn run_test(
-    Json(request): Json<ProviderTestRequest>,
+pub async fn run_test(request: Request) -> ApiResult<Json<ProviderTestResult>> {
+    let config = Config::load().map_err(ApiError::from)?;
+    provider_test(&config, request).await
+}
+
+async fn provider_test(config: &Config, request: Request) -> ApiResult<Json<ProviderTestResult>> {
+    let is_multipart = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("multipart/form-data"));
+    if !is_multipart {
+        let Json(request) = Json::<ProviderTestRequest>::from_request(request, &())
+            .await
+            .map_err(|e| ApiError::new(e.status(), e.body_text()))?;
+        let path = request.file.as_deref().map(Path::new);
+        return run_with_timeout(config, path, request.check_network).await;
+    }
+
+    let multipart = Multipart::from_request(request, &())
+        .await
+        .map_err(|e| ApiError::new(e.status(), e.body_text()))?;
+    let (sample, check_network) = read_test_upload(multipart, &config.audio.temp_dir()).await?;
+    let result = run_with_timeout(config, sample.as_deref(), check_network).await;
+    if let Some(path) = &sample {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+    result
+}
+
+/// Stage the `file` part of a provider-test upload in `temp_dir` and read
+/// the `check_network` flag. Other parts are ignored.
+async fn read_test_upload(
+    mut multipart: Multipart,
+    temp_dir: &Path,
+) -> ApiResult<(Option<PathBuf>, bool)> {
+    let mut sample: Option<PathBuf> = None;
+    let mut check_network = false;
+    loop {
+        let field = match multipart.next_field().await {
+            Ok(Some(field)) => field,
+            Ok(None) => break,
+            Err(e) => {
+                if let Some(path) = &sample {
+                    let _ = tokio::fs::remove_file(path).await;
+                }
+                return Err(ApiError::new(
+                    e.status(),
+                    format!("Malformed multipart upload: {}", e.body_text()),
+                ));
+            }
+        };
+        match field.name() {
+            Some("check_network") => {
+                let value = field.text().await.map_err(upload_error)?;
+                check_network = value.trim() == "true";
+            }
+            Some("file") if sample.is_none() => {
+                // Preserve the original extension so format detection works.
+                let ext = field
+                    .file_name()
+                    .and_then(|name| {
+                        Path::new(name)
+                            .extension()
+                            .map(|e| e.to_string_lossy().to_string())
+                    })
+                    .unwrap_or_else(|| "wav".to_string());
+                let path = temp_file_path(
+                    temp_dir,
+                    &format!("audetic-provider-test-{}", uuid::Uuid::new_v4()),
+                    &ext,
+                )
+                .map_err(ApiError::from)?;
+                let staged = stage_field(field, &path).await;
+                if staged.is_err() {
+                    let _ = tokio::fs::remove_file(&path).await;
+                }
+                staged?;
+                sample = Some(path);
+            }
+            _ => {}
+        }
+    }
+    Ok((sample, check_network))
+}
+
+async fn stage_field(mut field: Field<'_>, destination: &Path) -> ApiResult<()> {
+    let mut file = tokio::fs::File::create(destination)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to stage upload: {e}")))?;
+    while let Some(chunk) = field.chunk().await.map_err(upload_error)? {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed writing upload: {e}")))?;
+    }
+    file.flush().await.ok();
+    Ok(())
+}
+
+/// Keeps the extractor's status, so an oversized sample answers 413.
+fn upload_error(e: MultipartError) -> ApiError {
+    ApiError::new(
+        e.status(),
+        format!("Failed reading upload: {}", e.body_text()),
+    )
+}
+
+async fn run_with_timeout(
+    config: &Config,
+    audio_file: Option<&Path>,
+    check_network: bool,
 ) -> ApiResult<Json<ProviderTestResult>> {
-    let path = request.file.as_deref().map(Path::new);
-    let result = test_provider(path).await.map_err(ApiError::from)?;
-    Ok(Json(result))
+    let test = test_provider_with_config(config, audio_file, check_network);
+    match tokio::time::timeout(PROVIDER_TEST_TIMEOUT, test).await {
+        Ok(result) => Ok(Json(result.map_err(ApiError::from)?)),
+        Err(_) => Ok(Json(ProviderTestResult {
+            success: false,
+            transcription: None,
+            error: Some(format!(
+                "Provider test timed out after {}s",
+                PROVIDER_TEST_TIMEOUT.as_secs()
+            )),
+            duration_secs: PROVIDER_TEST_TIMEOUT.as_secs_f64(),
+            reachable: None,
+        })),
+    }
+}
+
+/// Check that the configured provider initializes, without transcribing any
+/// audio. A provider that fails to initialize still answers 200, with
+/// `success: false` and the reason in `error`.
+#[utoipa::path(
+    get,
+    path = "/provider/test",
+    tag = "provider",
+    operation_id = "test_provider_init",
+    responses(
+        (status = 200, description = "Init-only provider test result", body = ProviderTestResult),
+    ),
+)]
+pub async fn run_init_test() -> ApiResult<Json<ProviderTestResult>> {
+    let config = Config::load().map_err(ApiError::from)?;
+    init_test(&config).await
+}
+
+async fn init_test(config: &Config) -> ApiResult<Json<ProviderTestResult>> {
+    run_with_timeout(config, None, false).await
 }
 
 /// Back up the current `config.toml` to `<data_dir>/config-backups/`, keeping
 /// the most recent [`MAX_CONFIG_BACKUPS`]. No-op when no config exists yet.
-fn backup_config_file() -> Result<Option<PathBuf>> {
-    let config_path = global::config_file()?;
+pub(super) fn backup_config_file() -> Result<Option<PathBuf>> {
+    let backup_dir = global::data_dir()?.join("config-backups");
+    backup_config_file_at(&global::config_file()?, &backup_dir)
+}
+
+fn backup_config_file_at(config_path: &Path, backup_dir: &Path) -> Result<Option<PathBuf>> {
     if !config_path.exists() {
         return Ok(None);
     }
 
-    let backup_dir = global::data_dir()?.join("config-backups");
-    std::fs::create_dir_all(&backup_dir)
+    std::fs::create_dir_all(backup_dir)
         .with_context(|| format!("Failed to create backup directory: {:?}", backup_dir))?;
 
     let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
     let backup_path = backup_dir.join(format!("config.toml.backup-{timestamp}"));
-    std::fs::copy(&config_path, &backup_path)
+    std::fs::copy(config_path, &backup_path)
         .with_context(|| format!("Failed to back up {:?}", config_path))?;
 
-    rotate_backups(&backup_dir)?;
+    rotate_backups(backup_dir)?;
     Ok(Some(backup_path))
 }
 
@@ -189,3 +463,181 @@ fn rotate_backups(backup_dir: &Path) -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use axum::response::IntoResponse;
+    use tower::ServiceExt;
+
+    /// `GET /provider/test` against `whisper` instead of the user's config.
+    async fn get_init_test(whisper: WhisperConfig) -> (StatusCode, serde_json::Value) {
+        let app = Router::new().route(
+            "/provider/test",
+            get(move || async move {
+                let config = Config {
+                    whisper,
+                    ..Config::default()
+                };
+                init_test(&config).await
+            }),
+        );
+        let response = app
+            .oneshot(Request::get("/provider/test").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    /// `POST /provider/test` with a multipart `body` against `whisper`.
+    async fn post_upload_test(whisper: WhisperConfig, body: &str) -> (StatusCode, Value) {
+        let app = Router::new().route(
+            "/provider/test",
+            post(move |request: axum::extract::Request| async move {
+                let config = Config {
+                    whisper,
+                    ..Config::default()
+                };
+                provider_test(&config, request).await
+            }),
+        );
+        let request = Request::post("/provider/test")
+            .header(CONTENT_TYPE, "multipart/form-data; boundary=X")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    /// A config file whose provider is the keyless `audetic-api` default.
+    fn write_default_config(dir: &Path) -> PathBuf {
+        let path = dir.join("config.toml");
+        Config::default().save_to(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn update_merges_persists_and_masks_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_default_config(dir.path());
+        let backup_dir = dir.path().join("backups");
+
+        let response = update_config_at(
+            &config_path,
+            &backup_dir,
+            serde_json::json!({
+                "provider": "openai-api",
+                "api_key": "sk-secret",
+                "model": "whisper-1",
+            }),
+        )
+        .unwrap();
+
+        assert!(response.restart_required);
+        assert!(response.config.has_api_key);
+        let body = serde_json::to_string(&response).unwrap();
+        assert!(!body.contains("sk-secret"));
+
+        let saved = Config::load_from(&config_path).unwrap().whisper;
+        assert_eq!(saved.provider.as_deref(), Some("openai-api"));
+        assert_eq!(saved.api_key.as_deref(), Some("sk-secret"));
+        assert_eq!(saved.model.as_deref(), Some("whisper-1"));
+        // Omitted fields keep their current value.
+        assert_eq!(saved.language.as_deref(), Some("en"));
+        assert_eq!(std::fs::read_dir(&backup_dir).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn invalid_update_is_rejected_without_saving() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = write_default_config(dir.path());
+        let before = std::fs::read_to_string(&config_path).unwrap();
+
+        let err = update_config_at(
+            &config_path,
+            &dir.path().join("backups"),
+            serde_json::json!({ "provider": "openai-api" }),
+        )
+        .unwrap_err();
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["message"], "API key required for OpenAI API");
+
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), before);
+        assert!(!dir.path().join("backups").exists());
+    }
+
+    #[test]
+    fn update_rejects_unknown_fields_and_bad_types() {
+        let current = WhisperConfig::default();
+        assert!(merge_whisper_config(&current, serde_json::json!({ "apikey": "x" })).is_err());
+        assert!(merge_whisper_config(&current, serde_json::json!({ "model": 3 })).is_err());
+        assert!(merge_whisper_config(&current, serde_json::json!(["provider"])).is_err());
+
+        let cleared =
+            merge_whisper_config(&current, serde_json::json!({ "language": null })).unwrap();
+        assert_eq!(cleared.language, None);
+    }
+
+    #[tokio::test]
+    async fn init_test_reports_success_for_valid_provider() {
+        let (status, body) = get_init_test(WhisperConfig::default()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+        assert!(body["error"].is_null());
+        assert!(body["transcription"].is_null());
+    }
+
+    #[tokio::test]
+    async fn init_test_reports_failure_for_missing_api_key() {
+        let (status, body) = get_init_test(WhisperConfig {
+            provider: Some("openai-api".to_string()),
+            api_key: None,
+            ..WhisperConfig::default()
+        })
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], false);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .contains("api_key is required"));
+    }
+
+    #[tokio::test]
+    async fn upload_without_a_file_only_checks_initialization() {
+        let body = "--X\r\n\
+                    Content-Disposition: form-data; name=\"check_network\"\r\n\r\n\
+                    false\r\n\
+                    --X--\r\n";
+        let (status, result) = post_upload_test(WhisperConfig::default(), body).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(result["success"], true);
+        assert!(result["transcription"].is_null());
+        assert!(result["reachable"].is_null());
+
+        let (status, result) = post_upload_test(
+            WhisperConfig {
+                provider: Some("openai-api".to_string()),
+                api_key: None,
+                ..WhisperConfig::default()
+            },
+            body,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(result["success"], false);
+        assert!(result["error"]
+            .as_str()
+            .unwrap()
+            .contains("api_key is required"));
+    }
+}
@@ -2,19 +2,28 @@
 //! `/api/openapi.json` for the canonical method/path list — don't
 //! enumerate them here.
 
-use crate::audio::{JobOptions, RecordingPhase, RecordingStatus, RecordingStatusHandle};
+use crate::api::error::{ApiError, ApiResult};
+use crate::audio::{
+    JobOptions, RecordingPhase, RecordingStatus, RecordingStatusHandle, ToggleResult,
+};
 use crate::config::WaybarConfig;
 use axum::{
     extract::{Query, State},
     http::StatusCode,
-    response::Json,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json,
+    },
     routing::{get, post},
     Router,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use tokio::sync::mpsc;
+use std::convert::Infallible;
+use std::sync::{Arc, RwLock};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::{wrappers::WatchStream, Stream, StreamExt};
 use tracing::{error, info};
 use utoipa::ToSchema;
 
@@ -58,6 +67,9 @@ pub struct RecordingStatusResponse {
     pub job_id: Option<String>,
     pub last_completed_job: Option<CompletedJobSummary>,
     pub last_error: Option<String>,
+    /// The transcript so far while processing, when the provider streams it
+    /// (`[whisper].stream_partials`).
+    pub partial_text: Option<String>,
 }
 
 /// Commands dispatched from the HTTP layer to the main event loop.
@@ -66,12 +78,20 @@ pub struct RecordingStatusResponse {
 /// a keybinding where immediate return is a feature, and errors self-correct
 /// on the next keypress.
 ///
-/// The meeting variants carry a `tokio::sync::oneshot` reply channel so the
-/// HTTP handler can `.await` the machine's actual `Result` and surface proper
-/// status codes / error messages to the CLI.
+/// Pause/resume and the meeting variants carry a `tokio::sync::oneshot` reply
+/// channel so the HTTP handler can `.await` the machine's actual `Result` and
+/// surface proper status codes / error messages to the CLI.
 pub enum ApiCommand {
     /// Toggle recording with optional per-job options
     ToggleRecording(Option<JobOptions>),
+    /// Pause the dictation in progress, keeping its audio so far
+    PauseRecording {
+        reply: oneshot::Sender<anyhow::Result<ToggleResult>>,
+    },
+    /// Resume a paused dictation
+    ResumeRecording {
+        reply: oneshot::Sender<anyhow::Result<ToggleResult>>,
+    },
     /// Start meeting recording
     MeetingStart {
         options: Option<crate::meeting::MeetingStartOptions>,
@@ -97,20 +117,34 @@ pub enum ApiCommand {
         options: Option<crate::meeting::MeetingStartOptions>,
         reply: tokio::sync::oneshot::Sender<anyhow::Result<crate::meeting::ToggleOutcome>>,
     },
+    /// Re-read `config.toml` and rebuild the dictation services from it
+    ReloadConfig {
+        reply: tokio::sync::oneshot::Sender<anyhow::Result<crate::app::ConfigReloadOutcome>>,
+    },
 }
 
+/// Why dictation can't run (no provider configured), or `None` when it can.
+/// Shared with the service so a config reload can change it.
+pub type DictationUnavailable = Arc<RwLock<Option<String>>>;
+
 #[derive(Clone)]
 pub struct RecordingState {
     pub tx: mpsc::Sender<ApiCommand>,
     pub status: RecordingStatusHandle,
     pub waybar_config: WaybarConfig,
+    /// Why dictation can't run (no provider configured); toggles are
+    /// refused with this message instead of being dispatched.
+    pub unavailable: DictationUnavailable,
 }
 
 /// Creates the recording router with all recording-related endpoints.
 pub fn router(state: RecordingState) -> Router {
     Router::new()
         .route("/toggle", post(toggle_recording))
+        .route("/pause", post(pause_recording))
+        .route("/resume", post(resume_recording))
         .route("/status", get(recording_status))
+        .route("/status/stream", get(recording_status_stream))
         .with_state(state)
 }
 
@@ -122,12 +156,22 @@ pub fn router(state: RecordingState) -> Router {
     request_body(content = ToggleRequest, description = "Optional per-job overrides"),
     responses(
         (status = 200, description = "Toggle dispatched; reflects immediate phase", body = ToggleResponse),
+        (status = 503, description = "No transcription provider is configured"),
     ),
 )]
 pub async fn toggle_recording(
     State(state): State<RecordingState>,
     body: Option<Json<ToggleRequest>>,
-) -> Result<Json<ToggleResponse>, StatusCode> {
+) -> ApiResult<Json<ToggleResponse>> {
+    let unavailable = state
+        .unavailable
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(reason) = unavailable {
+        return Err(ApiError::new(StatusCode::SERVICE_UNAVAILABLE, reason));
+    }
+
     let job_options = body.and_then(|Json(req)| {
         if req.copy_to_clipboard.is_some() || req.auto_paste.is_some() {
             Some(JobOptions {
@@ -163,11 +207,69 @@ pub async fn toggle_recording(
         }
         Err(e) => {
             error!("Failed to send toggle command: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(ApiError::internal("Failed to dispatch toggle command"))
         }
     }
 }
 
+/// Pauses the dictation in progress. The audio captured so far and the job
+/// id are kept; toggling or `POST /resume` carries on from there.
+#[utoipa::path(
+    post,
+    path = "/pause",
+    tag = "recording",
+    responses(
+        (status = 200, description = "Recording paused", body = ToggleResponse),
+        (status = 409, description = "Nothing is recording"),
+    ),
+)]
+pub async fn pause_recording(
+    State(state): State<RecordingState>,
+) -> ApiResult<Json<ToggleResponse>> {
+    let (reply, result) = oneshot::channel();
+    dispatch(&state, ApiCommand::PauseRecording { reply }, result).await
+}
+
+/// Resumes a paused dictation; new audio is appended to what was recorded
+/// before the pause.
+#[utoipa::path(
+    post,
+    path = "/resume",
+    tag = "recording",
+    responses(
+        (status = 200, description = "Recording resumed", body = ToggleResponse),
+        (status = 409, description = "No recording is paused"),
+    ),
+)]
+pub async fn resume_recording(
+    State(state): State<RecordingState>,
+) -> ApiResult<Json<ToggleResponse>> {
+    let (reply, result) = oneshot::channel();
+    dispatch(&state, ApiCommand::ResumeRecording { reply }, result).await
+}
+
+/// Send `command` to the event loop and turn the machine's reply into a
+/// response. Refusals (wrong phase, device errors) are 409s.
+async fn dispatch(
+    state: &RecordingState,
+    command: ApiCommand,
+    result: oneshot::Receiver<anyhow::Result<ToggleResult>>,
+) -> ApiResult<Json<ToggleResponse>> {
+    let unavailable = || ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "event loop unavailable");
+    state.tx.send(command).await.map_err(|_| unavailable())?;
+    let result = result
+        .await
+        .map_err(|_| unavailable())?
+        .map_err(|e| ApiError::new(StatusCode::CONFLICT, format!("{e:#}")))?;
+
+    Ok(Json(ToggleResponse {
+        success: true,
+        phase: result.phase.as_str().to_string(),
+        job_id: result.job_id,
+        message: format!("Recording {}", result.phase.as_str()),
+    }))
+}
+
 /// Gets the current recording status.
 ///
 /// Pass `?style=waybar` for a Waybar-formatted `{text, class, tooltip}` payload.
@@ -187,9 +289,46 @@ pub async fn recording_status(
     State(state): State<RecordingState>,
 ) -> Json<Value> {
     let status = state.status.get().await;
+    let waybar = params.get("style").is_some_and(|style| style == "waybar");
+    Json(status_payload(
+        &status,
+        waybar.then_some(&state.waybar_config),
+    ))
+}
 
-    if params.get("style") == Some(&"waybar".to_string()) {
-        return Json(generate_waybar_response(&status, &state.waybar_config));
+/// Streams the recording status as Server-Sent Events: the current status
+/// on connect, then one event per change, each carrying the same JSON as
+/// `GET /status` (including `?style=waybar`).
+#[utoipa::path(
+    get,
+    path = "/status/stream",
+    tag = "recording",
+    params(
+        ("style" = Option<String>, Query, description = "Set to `waybar` for Waybar-formatted events"),
+    ),
+    responses(
+        (status = 200, description = "Event stream of recording status payloads", content_type = "text/event-stream"),
+    ),
+)]
+pub async fn recording_status_stream(
+    Query(params): Query<HashMap<String, String>>,
+    State(state): State<RecordingState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let waybar = params.get("style").is_some_and(|style| style == "waybar");
+    let waybar_config = state.waybar_config;
+    // The stream (and its receiver) is dropped when the client disconnects.
+    let events = WatchStream::new(state.status.subscribe()).map(move |status| {
+        let payload = status_payload(&status, waybar.then_some(&waybar_config));
+        Ok(Event::default().data(payload.to_string()))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// The `/status` body: the Waybar shape when `waybar` is given, otherwise a
+/// [`RecordingStatusResponse`].
+fn status_payload(status: &RecordingStatus, waybar: Option<&WaybarConfig>) -> Value {
+    if let Some(config) = waybar {
+        return generate_waybar_response(status, config);
     }
 
     let last_completed_job = status.last_completed_job.as_ref().map(|job| {
@@ -201,13 +340,14 @@ pub async fn recording_status(
         })
     });
 
-    Json(json!({
+    json!({
         "recording": status.phase == RecordingPhase::Recording,
         "phase": status.phase.as_str(),
         "job_id": status.current_job_id,
         "last_completed_job": last_completed_job,
         "last_error": status.last_error,
-    }))
+        "partial_text": status.partial_text,
+    })
 }
 
 fn generate_waybar_response(status: &RecordingStatus, config: &WaybarConfig) -> Value {
@@ -222,6 +362,11 @@ fn generate_waybar_response(status: &RecordingStatus, config: &WaybarConfig) ->
             "audetic-recording".to_string(),
             config.recording_tooltip.clone(),
         ),
+        RecordingPhase::Paused => (
+            config.paused_text.clone(),
+            "audetic-paused".to_string(),
+            config.paused_tooltip.clone(),
+        ),
         RecordingPhase::Processing => (
             "󰦖".to_string(),
             "audetic-processing".to_string(),
@@ -243,3 +388,197 @@ fn generate_waybar_response(status: &RecordingStatus, config: &WaybarConfig) ->
         "tooltip": tooltip
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn open_stream(state: RecordingState, uri: &str) -> axum::body::BodyDataStream {
+        let response = router(state)
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+        response.into_body().into_data_stream()
+    }
+
+    async fn next_event(body: &mut axum::body::BodyDataStream) -> String {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+            .await
+            .expect("event in time")
+            .expect("stream open")
+            .unwrap();
+        String::from_utf8(chunk.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn stream_sends_current_status_then_each_change_to_every_subscriber() {
+        let (tx, _rx) = mpsc::channel(1);
+        let state = RecordingState {
+            tx,
+            status: RecordingStatusHandle::default(),
+            waybar_config: WaybarConfig::default(),
+            unavailable: Default::default(),
+        };
+        let mut plain = open_stream(state.clone(), "/status/stream").await;
+        let mut waybar = open_stream(state.clone(), "/status/stream?style=waybar").await;
+
+        assert!(next_event(&mut plain).await.contains(r#""phase":"idle""#));
+        assert!(next_event(&mut waybar).await.contains("audetic-idle"));
+
+        state
+            .status
+            .start_job("job-1".to_string(), JobOptions::default())
+            .await;
+
+        let event = next_event(&mut plain).await;
+        assert!(event.starts_with("data: "), "{event}");
+        assert!(event.contains(r#""phase":"recording""#), "{event}");
+        assert!(event.contains(r#""job_id":"job-1""#), "{event}");
+        assert!(next_event(&mut waybar).await.contains("audetic-recording"));
+
+        // Hanging up one client leaves the other subscribed.
+        drop(waybar);
+        state.status.set_processing().await;
+        assert!(next_event(&mut plain)
+            .await
+            .contains(r#""phase":"processing""#));
+    }
+
+    #[tokio::test]
+    async fn paused_recordings_keep_their_job_and_show_paused_in_waybar() {
+        let status = RecordingStatusHandle::default();
+        status
+            .start_job("job-1".to_string(), JobOptions::default())
+            .await;
+        status.pause_job().await;
+
+        let status = status.get().await;
+        let plain = status_payload(&status, None);
+        assert_eq!(plain["phase"], "paused");
+        assert_eq!(plain["recording"], false);
+        assert_eq!(plain["job_id"], "job-1");
+
+        let waybar = status_payload(&status, Some(&WaybarConfig::default()));
+        assert_eq!(waybar["class"], "audetic-paused");
+        assert_eq!(waybar["tooltip"], "Recording paused");
+    }
+
+    async fn get_status(state: &RecordingState, uri: &str) -> Value {
+        let response = router(state.clone())
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn waybar_status_covers_every_phase() {
+        let (tx, _rx) = mpsc::channel(1);
+        let state = RecordingState {
+            tx,
+            status: RecordingStatusHandle::default(),
+            waybar_config: WaybarConfig {
+                idle_text: "idle".to_string(),
+                recording_text: "rec".to_string(),
+                idle_tooltip: "Press to dictate".to_string(),
+                recording_tooltip: "Dictating".to_string(),
+                paused_text: "hold".to_string(),
+                paused_tooltip: "On hold".to_string(),
+            },
+            unavailable: Default::default(),
+        };
+        let waybar = |state: RecordingState| async move {
+            let body = get_status(&state, "/status?style=waybar").await;
+            (
+                body["text"].clone(),
+                body["class"].clone(),
+                body["tooltip"].clone(),
+            )
+        };
+
+        assert_eq!(
+            waybar(state.clone()).await,
+            (
+                json!("idle"),
+                json!("audetic-idle"),
+                json!("Press to dictate")
+            )
+        );
+
+        state
+            .status
+            .start_job("job-1".to_string(), JobOptions::default())
+            .await;
+        assert_eq!(
+            waybar(state.clone()).await,
+            (json!("rec"), json!("audetic-recording"), json!("Dictating"))
+        );
+
+        state.status.pause_job().await;
+        assert_eq!(
+            waybar(state.clone()).await,
+            (json!("hold"), json!("audetic-paused"), json!("On hold"))
+        );
+
+        state.status.resume_job().await;
+        state.status.set_processing().await;
+        assert_eq!(
+            waybar(state.clone()).await,
+            (
+                json!("󰦖"),
+                json!("audetic-processing"),
+                json!("Processing transcription")
+            )
+        );
+
+        state
+            .status
+            .fail_job("microphone unplugged".to_string())
+            .await;
+        assert_eq!(
+            waybar(state.clone()).await,
+            (
+                json!(""),
+                json!("audetic-error"),
+                json!("microphone unplugged")
+            )
+        );
+
+        // Without `style`, the default shape is untouched.
+        let plain = get_status(&state, "/status").await;
+        assert_eq!(plain["phase"], "error");
+        assert_eq!(plain["last_error"], "microphone unplugged");
+        assert!(plain.get("class").is_none());
+    }
+
+    #[tokio::test]
+    async fn status_shows_partial_text_only_while_processing() {
+        let status = RecordingStatusHandle::default();
+        let partial_text =
+            |status: RecordingStatus| status_payload(&status, None)["partial_text"].clone();
+        status
+            .start_job("job-1".to_string(), JobOptions::default())
+            .await;
+        status.set_partial_text("too early".to_string()).await;
+        assert!(partial_text(status.get().await).is_null());
+
+        status.set_processing().await;
+        status.set_partial_text("so far".to_string()).await;
+        assert_eq!(partial_text(status.get().await), "so far");
+
+        status.fail_job("engine crashed".to_string()).await;
+        assert!(partial_text(status.get().await).is_null());
+    }
+}
@@ -8,6 +8,8 @@
 //! selected; otherwise it talks to the cloud jobs API directly.
 
 use crate::api::error::{ApiError, ApiResult};
+use crate::audio::wav::temp_file_path;
+use crate::config::Config;
 use axum::{extract::Multipart, response::Json, routing::post, Router};
 use serde::Serialize;
 use std::path::Path;
@@ -38,9 +40,15 @@ pub fn router() -> Router {
     responses(
         (status = 200, description = "Transcribed text", body = TranscribeResponse),
         (status = 400, description = "Missing or unreadable file"),
+        (status = 409, description = "No provider is configured"),
+        (status = 413, description = "The audio is over the provider's length or size limit"),
+        (status = 422, description = "The provider couldn't decode the audio"),
+        (status = 429, description = "The provider is rate limiting requests"),
+        (status = 502, description = "The provider rejected its API key (`code: provider_auth`), failed, or couldn't be reached"),
     ),
 )]
 pub async fn transcribe(mut multipart: Multipart) -> ApiResult<Json<TranscribeResponse>> {
+    let temp_dir = Config::load().map_err(ApiError::from)?.audio.temp_dir();
     let mut temp_path: Option<std::path::PathBuf> = None;
 
     while let Some(mut field) = multipart
@@ -61,8 +69,12 @@ pub async fn transcribe(mut multipart: Multipart) -> ApiResult<Json<TranscribeRe
                     .map(|e| e.to_string_lossy().to_string())
             })
             .unwrap_or_else(|| "wav".to_string());
-        let path =
-            std::env::temp_dir().join(format!("audetic-transcribe-{}.{ext}", uuid::Uuid::new_v4()));
+        let path = temp_file_path(
+            &temp_dir,
+            &format!("audetic-transcribe-{}", uuid::Uuid::new_v4()),
+            &ext,
+        )
+        .map_err(ApiError::from)?;
 
         let mut file = tokio::fs::File::create(&path)
             .await
@@ -45,6 +45,7 @@ pub fn router() -> Router {
     Router::new()
         .route("/check", get(check_update))
         .route("/install", post(install_update))
+        .route("/rollback", post(rollback_update))
         .route("/auto", get(get_auto_update).put(set_auto_update))
 }
 
@@ -105,6 +106,23 @@ pub async fn install_update(
     Ok(Json(report))
 }
 
+/// Restore the binary that the last update replaced.
+#[utoipa::path(
+    post,
+    path = "/update/rollback",
+    tag = "update",
+    responses(
+        (status = 200, description = "Previous version restored; restart required", body = UpdateReport),
+        (status = 500, description = "No backup to restore, or the restore failed"),
+    ),
+)]
+pub async fn rollback_update() -> ApiResult<Json<UpdateReport>> {
+    let config = UpdateConfig::detect(None).map_err(ApiError::from)?;
+    let engine = UpdateEngine::new(config).map_err(ApiError::from)?;
+    let report = engine.rollback().await.map_err(ApiError::from)?;
+    Ok(Json(report))
+}
+
 /// Read the current auto-update flag.
 #[utoipa::path(
     get,
@@ -1,29 +1,105 @@
 #![allow(clippy::arc_with_non_send_sync)]
 
-use crate::api::{ApiCommand, ApiServer};
+use crate::api::{AlreadyRunning, ApiCommand, ApiServer, DictationUnavailable};
 use crate::audio::{
-    mic_source::MicAudioSource, system_source::SystemAudioSource, AudioStreamManager,
-    BehaviorOptions, RecordingMachine, RecordingPhase, RecordingStatusHandle, ToggleResult,
+    audio_mixer::MixOptions, mic_source::MicAudioSource, system_source::SystemAudioSource,
+    vad::VadSettings, AudioStreamManager, BehaviorOptions, Recorder, RecordingMachine,
+    RecordingPhase, RecordingStatusHandle, ToggleResult,
 };
 use crate::config::Config;
-use crate::meeting::{FfprobeMediaInspector, MediaInspector, MeetingMachine, MeetingStatusHandle};
+use crate::meeting::{
+    FfprobeMediaInspector, MediaInspector, MeetingMachine, MeetingStatusHandle, ProcessingServices,
+};
 use crate::post_processing::PostProcessingService;
-use crate::text_io::TextIoService;
+use crate::text_io::{TextIoService, TextOutput};
 use crate::transcription::job_service::{
     LocalTranscriptionJobService, RemoteTranscriptionJobService,
 };
-use crate::transcription::{ProviderConfig, Transcriber, TranscriptionService};
+use crate::transcription::{
+    ProviderConfig, Transcriber, TranscriptionService, NO_PROVIDER_CONFIGURED,
+};
 use crate::ui::Indicator;
 use crate::update::{UpdateConfig, UpdateEngine};
 use anyhow::{anyhow, Result};
+use audetic_core::http::HttpOptions;
+use audetic_core::jobs_client::JobLimiter;
+use std::future::Future;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, watch, Mutex};
 use tracing::{error, info, warn};
 
+mod reload;
+
+pub use reload::ConfigReloadOutcome;
+
 const DEFAULT_JOBS_API_URL: &str = "https://audio.audetic.link/api/v1/jobs";
 const MEETING_TRANSCRIPTION_TIMEOUT_SECS: u64 = 7200; // 2 hours
 
+/// How long shutdown waits for an in-flight dictation to finish
+/// transcribing. The systemd unit's `TimeoutStopSec` leaves room for it.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(30);
+
+/// Process exit code when another instance already holds the API port.
+pub const EXIT_ALREADY_RUNNING: i32 = 3;
+
+/// Map a `run_service` error to a process exit code.
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    if err.downcast_ref::<AlreadyRunning>().is_some() {
+        EXIT_ALREADY_RUNNING
+    } else {
+        1
+    }
+}
+
+/// Everything the service takes from the outside world. [`run_service`]
+/// builds it from the user's config and real hardware; integration tests
+/// inject fakes and bind an ephemeral port.
+pub struct ServiceDeps {
+    pub config: Config,
+    pub recorder: Arc<Mutex<dyn Recorder>>,
+    /// `None` starts the service without dictation: the API serves, but
+    /// toggles are refused until a provider is configured.
+    pub transcription: Option<Arc<TranscriptionService>>,
+    pub text_io: Arc<dyn TextOutput>,
+    pub indicator: Indicator,
+    /// API port; 0 picks a free one (see [`Service::local_addr`]).
+    pub port: u16,
+    /// Run the background auto-update checks.
+    pub auto_update: bool,
+}
+
+impl ServiceDeps {
+    /// Production dependencies: the configured input device, the configured
+    /// provider, the system clipboard and keyboard, and the standard port.
+    pub fn from_config(config: Config) -> Result<Self> {
+        let audio_recorder = AudioStreamManager::new(config.audio.input_device.clone())?
+            .with_prebuffer_seconds(config.audio.prebuffer_seconds)
+            .with_bits_per_sample(config.audio.bits_per_sample());
+        // On macOS the first stream open gates on the mic permission prompt, so
+        // the prebuffer waits for the first user-initiated recording there.
+        #[cfg(not(target_os = "macos"))]
+        if let Err(e) = audio_recorder.start_prebuffer() {
+            warn!("Failed to start dictation prebuffer: {}", e);
+        }
+
+        let transcription = build_dictation_transcription(&config)?.map(Arc::new);
+        let text_io = build_text_io(&config)?;
+
+        Ok(Self {
+            recorder: Arc::new(Mutex::new(audio_recorder)),
+            transcription,
+            text_io: Arc::new(text_io),
+            indicator: build_indicator(&config),
+            port: crate::api::url::port_from(&config),
+            auto_update: true,
+            config,
+        })
+    }
+}
+
 pub async fn run_service() -> Result<()> {
     info!("Starting Audetic service");
 
@@ -40,205 +116,497 @@ pub async fn run_service() -> Result<()> {
     );
 
     let config = Config::load()?;
+    for warning in config.validate() {
+        warn!("Config: {}", warning);
+    }
 
-    let (tx, mut rx) = mpsc::channel::<ApiCommand>(10);
-    let audio_recorder = Arc::new(Mutex::new(AudioStreamManager::new()?));
+    // Claim the API port before initialising audio and providers, so a second
+    // instance exits straight away with a clear message.
+    let port = crate::api::url::port_from(&config);
+    let listener = crate::api::bind_listener(port).await?;
+    let deps = ServiceDeps::from_config(config)?;
 
-    let whisper = build_transcriber(&config)?;
-    let transcription_service = Arc::new(TranscriptionService::new(whisper)?);
+    let toggle_url = crate::api::url::api_url_on(port, crate::api::url::paths::TOGGLE);
+    let meetings_toggle_url =
+        crate::api::url::api_url_on(port, crate::api::url::paths::MEETINGS_TOGGLE);
+    let service = Service::start_on(listener, deps).await?;
+    #[cfg(unix)]
+    service.reload_on_sighup();
+    service.toggle_on_hotkey();
+    info!("Audetic is ready!");
+    info!("Add this to your Hyprland config:");
+    let toggle = crate::keybind::curl_post(&toggle_url);
+    let meetings_toggle = crate::keybind::curl_post(&meetings_toggle_url);
+    info!("bindd = SUPER, R, Audetic, exec, {toggle}");
+    info!("bindd = SUPER SHIFT, R, Audetic Meeting, exec, {meetings_toggle}");
+    info!("Or test manually: {toggle}");
 
-    let text_io = TextIoService::new(
-        Some(&config.wayland.input_method),
-        config.behavior.preserve_clipboard,
-    )?;
-    let indicator =
-        Indicator::from_config(&config.ui).with_audio_feedback(config.behavior.audio_feedback);
-
-    // Post-processing service is shared across both pipelines + the API
-    // server. Cheap to clone (zero-sized), so the Arc is only for the
-    // explicit `&Arc<...>` shape MeetingMachine/RecordingMachine accept.
-    let post_processing = Arc::new(PostProcessingService::new());
-
-    let status_handle = RecordingStatusHandle::default();
-    let recording_machine = RecordingMachine::new(
-        audio_recorder.clone(),
-        transcription_service,
-        indicator.clone(),
-        text_io,
-        BehaviorOptions {
-            auto_paste: config.behavior.auto_paste,
-            delete_audio_files: config.behavior.delete_audio_files,
-        },
-        status_handle.clone(),
-        Arc::clone(&post_processing),
-    );
+    service.run_until(shutdown_signal()).await
+}
 
-    // Meeting pipeline (independent from recording pipeline). `meetings_dir`,
-    // the media inspector, and the post-processing service all live at the
-    // app level so the live recording machine and the import endpoint share
-    // a single instance — no path drift between recording and imports, and
-    // no duplicate dispatch of `meeting.completed` jobs.
-    let meeting_status = MeetingStatusHandle::default();
-    let meeting_transcription = build_meeting_transcription_service(&config);
-    let meetings_dir = resolve_meetings_dir();
-    let meeting_inspector: Arc<dyn MediaInspector> = Arc::new(FfprobeMediaInspector);
-
-    let mut meeting_machine = build_meeting_machine(
-        indicator,
-        meeting_status.clone(),
-        meeting_transcription.clone(),
-        Arc::clone(&post_processing),
-        meetings_dir.clone(),
-    );
+/// Resolves on SIGTERM (`systemctl stop`) or Ctrl-C.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
 
-    let api_server = ApiServer::new(
-        tx,
-        status_handle.clone(),
-        &config,
-        Arc::clone(&post_processing),
-    )
-    .with_meeting_state(
-        meeting_status.clone(),
-        meeting_transcription.clone(),
-        Arc::clone(&post_processing),
-        meeting_inspector,
-        meetings_dir.clone(),
-    );
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminations) => {
+                tokio::select! {
+                    _ = terminations.recv() => info!("SIGTERM received, shutting down"),
+                    _ = tokio::signal::ctrl_c() => info!("Interrupted, shutting down"),
+                }
+                return;
+            }
+            Err(e) => warn!("Failed to listen for SIGTERM: {}", e),
+        }
+    }
 
-    tokio::spawn(async move {
-        if let Err(e) = api_server.start().await {
-            error!("API server failed: {}", e);
+    match tokio::signal::ctrl_c().await {
+        Ok(()) => info!("Interrupted, shutting down"),
+        // Without a signal to wait for, never shut down on our own.
+        Err(e) => {
+            warn!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await
         }
-    });
+    }
+}
 
-    spawn_update_manager();
+/// A started service: the API is serving, and [`Service::run`] drives the
+/// recording and meeting pipelines from its commands.
+pub struct Service {
+    local_addr: SocketAddr,
+    rx: mpsc::Receiver<ApiCommand>,
+    /// Weak so a SIGHUP listener doesn't keep the command loop alive after
+    /// the API server goes away.
+    commands: mpsc::WeakSender<ApiCommand>,
+    /// The config the running services were built from; reloads diff against it.
+    config: Config,
+    /// Shared with the API so a reload that adds or removes the provider
+    /// changes whether toggles are accepted.
+    dictation_unavailable: DictationUnavailable,
+    /// `[network].max_concurrent_jobs` for meeting jobs; resized on reload.
+    job_limiter: Arc<JobLimiter>,
+    recording_machine: RecordingMachine,
+    meeting_machine: MeetingMachine,
+    /// Flipped to `true` to stop the API server accepting connections.
+    stop_serving: watch::Sender<bool>,
+}
 
-    let toggle_url = crate::api::url::api_url(crate::api::url::paths::TOGGLE);
-    let meetings_toggle_url = crate::api::url::api_url(crate::api::url::paths::MEETINGS_TOGGLE);
-    info!("Audetic is ready!");
-    info!("Add this to your Hyprland config:");
-    info!("bindd = SUPER, R, Audetic, exec, curl -X POST {toggle_url}");
-    info!("bindd = SUPER SHIFT, R, Audetic Meeting, exec, curl -X POST {meetings_toggle_url}");
-    info!("Or test manually: curl -X POST {toggle_url}");
-
-    while let Some(command) = rx.recv().await {
-        match command {
-            ApiCommand::ToggleRecording(job_options) => {
-                match recording_machine.toggle(job_options).await {
-                    Ok(ToggleResult {
-                        phase: RecordingPhase::Recording,
-                        job_id,
-                    }) => {
-                        info!("Recording started with job_id={:?}", job_id);
-                    }
-                    Ok(ToggleResult {
-                        phase: RecordingPhase::Processing,
-                        job_id,
-                    }) => {
-                        info!(
-                            "Recording stopped, processing audio for job_id={:?}",
-                            job_id
-                        );
-                    }
-                    Ok(ToggleResult { phase, job_id }) => {
-                        info!(
-                            "RecordingMachine is currently {:?} (job_id={:?})",
-                            phase, job_id
-                        );
-                    }
-                    Err(e) => error!("Failed to toggle recording: {}", e),
-                }
+impl Service {
+    /// Bind `deps.port` and start serving the API.
+    pub async fn start(deps: ServiceDeps) -> Result<Self> {
+        let listener = crate::api::bind_listener(deps.port).await?;
+        Self::start_on(listener, deps).await
+    }
+
+    async fn start_on(listener: TcpListener, deps: ServiceDeps) -> Result<Self> {
+        let ServiceDeps {
+            config,
+            recorder,
+            transcription,
+            text_io,
+            indicator,
+            port: _,
+            auto_update,
+        } = deps;
+        let local_addr = listener.local_addr()?;
+        let (tx, rx) = mpsc::channel::<ApiCommand>(10);
+
+        // Post-processing service is shared across both pipelines + the API
+        // server. Cheap to clone (zero-sized), so the Arc is only for the
+        // explicit `&Arc<...>` shape MeetingMachine/RecordingMachine accept.
+        let post_processing = Arc::new(PostProcessingService::new());
+
+        let status_handle = RecordingStatusHandle::default();
+        let dictation_available = transcription.is_some();
+        let recording_machine = RecordingMachine::new(
+            recorder,
+            transcription,
+            indicator.clone(),
+            text_io,
+            behavior_options(&config),
+            status_handle.clone(),
+            Arc::clone(&post_processing),
+        );
+
+        // Meeting pipeline (independent from recording pipeline). `meetings_dir`,
+        // the media inspector, and the post-processing service all live at the
+        // app level so the live recording machine and the import endpoint share
+        // a single instance — no path drift between recording and imports, and
+        // no duplicate dispatch of `meeting.completed` jobs.
+        let meeting_status = MeetingStatusHandle::default();
+        let job_limiter = Arc::new(JobLimiter::new(config.network.max_concurrent_jobs));
+        let meeting_transcription =
+            build_meeting_transcription_service(&config, Arc::clone(&job_limiter))?;
+        let meetings_dir = crate::global::meetings_dir()?;
+        let meeting_inspector: Arc<dyn MediaInspector> = Arc::new(FfprobeMediaInspector);
+
+        // Settle meetings a previous run left mid-flight before anything can start
+        // a new one. Best-effort: a failure here must not keep the daemon down.
+        if let Err(e) = crate::meeting::recover_interrupted_meetings(ProcessingServices {
+            transcription: meeting_transcription.clone(),
+            post_processing: Arc::clone(&post_processing),
+            diarize: config.meeting.diarize,
+            chunk_minutes: config.meeting.chunk_minutes,
+        })
+        .await
+        {
+            warn!("Failed to reconcile interrupted meetings: {}", e);
+        }
+
+        let meeting_machine = build_meeting_machine(
+            indicator,
+            meeting_status.clone(),
+            meeting_transcription.clone(),
+            Arc::clone(&post_processing),
+            meetings_dir.clone(),
+            config.audio.bits_per_sample(),
+            config.meeting.diarize,
+        )
+        .with_chunk_minutes(config.meeting.chunk_minutes)
+        .with_mix(MixOptions::from(&config.meeting));
+
+        let commands = tx.downgrade();
+        let mut api_server = ApiServer::new(
+            tx,
+            status_handle.clone(),
+            &config,
+            Arc::clone(&post_processing),
+        );
+        if !dictation_available {
+            api_server = api_server.with_dictation_unavailable(NO_PROVIDER_CONFIGURED);
+        }
+        let dictation_unavailable = api_server.dictation_unavailable();
+        let api_server = api_server.with_meeting_state(
+            meeting_status.clone(),
+            meeting_transcription.clone(),
+            Arc::clone(&post_processing),
+            meeting_inspector,
+            meetings_dir.clone(),
+            &config.meeting,
+        );
+
+        let (stop_serving, mut serving) = watch::channel(false);
+        let stopped = async move {
+            // Also stops when the service is dropped.
+            let _ = serving.wait_for(|stop| *stop).await;
+        };
+        tokio::spawn(async move {
+            if let Err(e) = api_server.start(listener, stopped).await {
+                error!("API server failed: {}", e);
             }
-            ApiCommand::MeetingStart { options, reply } => {
-                let result = meeting_machine.start(options).await;
-                match &result {
-                    Ok(r) => info!(
-                        "Meeting {} started: {:?} ({})",
-                        r.meeting_id,
-                        r.audio_path,
-                        r.capture_state.as_str()
-                    ),
-                    Err(e) => error!("Failed to start meeting: {}", e),
-                }
-                let _ = reply.send(result);
+        });
+
+        if auto_update {
+            spawn_update_manager();
+        }
+
+        Ok(Self {
+            local_addr,
+            rx,
+            commands,
+            config,
+            dictation_unavailable,
+            job_limiter,
+            recording_machine,
+            meeting_machine,
+            stop_serving,
+        })
+    }
+
+    /// Where the API is listening.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Reload the config whenever the process gets SIGHUP (`systemctl --user
+    /// reload audetic`). The outcome is logged by the command loop.
+    #[cfg(unix)]
+    pub fn reload_on_sighup(&self) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let commands = self.commands.clone();
+        let mut hangups = match signal(SignalKind::hangup()) {
+            Ok(hangups) => hangups,
+            Err(e) => {
+                warn!(
+                    "Failed to listen for SIGHUP; config reload is API-only: {}",
+                    e
+                );
+                return;
             }
-            ApiCommand::MeetingStop { reply } => {
-                let result = meeting_machine.stop().await;
-                match &result {
-                    Ok(r) => info!("Meeting {} stopped ({}s)", r.meeting_id, r.duration_seconds),
-                    Err(e) => error!("Failed to stop meeting: {}", e),
+        };
+        tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                info!("SIGHUP received, reloading config");
+                let Some(tx) = commands.upgrade() else { break };
+                let (reply, done) = tokio::sync::oneshot::channel();
+                if tx.send(ApiCommand::ReloadConfig { reply }).await.is_err() {
+                    break;
                 }
-                let _ = reply.send(result);
+                drop(tx);
+                let _ = done.await;
             }
-            ApiCommand::MeetingCancel { reply } => {
-                let result = meeting_machine.cancel().await;
-                match &result {
-                    Ok(r) => info!(
-                        "Meeting {} cancelled ({}s)",
-                        r.meeting_id, r.duration_seconds
-                    ),
-                    Err(e) => error!("Failed to cancel meeting: {}", e),
+        });
+    }
+
+    /// Toggle recording on each press of `[behavior] hotkey`, when one is
+    /// set and a listener for it could be started. Presses go through the
+    /// command loop like `POST /api/toggle`, and are ignored the same way
+    /// while no provider is configured.
+    pub fn toggle_on_hotkey(&self) {
+        let Some(spec) = self.config.behavior.hotkey.as_deref() else {
+            return;
+        };
+        let Some(mut presses) = crate::hotkey::spawn_listener(spec) else {
+            return;
+        };
+        let commands = self.commands.clone();
+        let dictation_unavailable = Arc::clone(&self.dictation_unavailable);
+        tokio::spawn(async move {
+            while presses.recv().await.is_some() {
+                let unavailable = dictation_unavailable
+                    .read()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone();
+                if let Some(reason) = unavailable {
+                    warn!("Hotkey ignored: {}", reason);
+                    continue;
                 }
-                let _ = reply.send(result);
-            }
-            ApiCommand::MeetingConfirm {
-                start_seconds,
-                end_seconds,
-                reply,
-            } => {
-                let result = meeting_machine.confirm(start_seconds, end_seconds).await;
-                match &result {
-                    Ok(r) => info!(
-                        "Meeting {} confirmed for transcription ({}s)",
-                        r.meeting_id, r.duration_seconds
-                    ),
-                    Err(e) => error!("Failed to confirm meeting: {}", e),
+                let Some(tx) = commands.upgrade() else { break };
+                if tx.send(ApiCommand::ToggleRecording(None)).await.is_err() {
+                    break;
                 }
-                let _ = reply.send(result);
             }
-            ApiCommand::MeetingToggle { options, reply } => {
-                let result = meeting_machine.toggle(options).await;
-                match &result {
-                    Ok(outcome) => match outcome {
-                        crate::meeting::ToggleOutcome::Started(r) => {
-                            info!("Meeting {} started via toggle", r.meeting_id);
+        });
+    }
+
+    /// Handle API commands until the API server goes away.
+    pub async fn run(self) -> Result<()> {
+        self.run_until(std::future::pending()).await
+    }
+
+    /// Handle API commands until the API server goes away or `shutdown`
+    /// resolves. On shutdown, toggles are refused, the API server stops
+    /// taking connections, and dictation is settled (see
+    /// [`RecordingMachine::shutdown`]) before this returns. Meetings left
+    /// mid-flight are reconciled on the next start.
+    pub async fn run_until(self, shutdown: impl Future<Output = ()>) -> Result<()> {
+        let Self {
+            mut rx,
+            mut config,
+            dictation_unavailable,
+            job_limiter,
+            mut recording_machine,
+            mut meeting_machine,
+            stop_serving,
+            ..
+        } = self;
+        tokio::pin!(shutdown);
+        loop {
+            let command = tokio::select! {
+                command = rx.recv() => match command {
+                    Some(command) => command,
+                    None => break,
+                },
+                () = &mut shutdown => {
+                    *dictation_unavailable
+                        .write()
+                        .unwrap_or_else(|e| e.into_inner()) =
+                        Some("Audetic is shutting down".to_string());
+                    let _ = stop_serving.send(true);
+                    recording_machine.shutdown(SHUTDOWN_GRACE).await;
+                    info!("Audetic stopped");
+                    break;
+                }
+            };
+            match command {
+                ApiCommand::ToggleRecording(job_options) => {
+                    match recording_machine.toggle(job_options).await {
+                        Ok(ToggleResult {
+                            phase: RecordingPhase::Recording,
+                            job_id,
+                        }) => {
+                            info!("Recording started with job_id={:?}", job_id);
+                        }
+                        Ok(ToggleResult {
+                            phase: RecordingPhase::Processing,
+                            job_id,
+                        }) => {
+                            info!(
+                                "Recording stopped, processing audio for job_id={:?}",
+                                job_id
+                            );
                         }
-                        crate::meeting::ToggleOutcome::Stopped(r) => {
+                        Ok(ToggleResult { phase, job_id }) => {
                             info!(
-                                "Meeting {} stopped via toggle ({}s)",
-                                r.meeting_id, r.duration_seconds
+                                "RecordingMachine is currently {:?} (job_id={:?})",
+                                phase, job_id
                             );
                         }
-                    },
-                    Err(e) => error!("Failed to toggle meeting: {}", e),
+                        Err(e) => error!("Failed to toggle recording: {}", e),
+                    }
+                }
+                ApiCommand::PauseRecording { reply } => {
+                    let result = recording_machine.pause().await;
+                    match &result {
+                        Ok(r) => info!("Recording paused (job_id={:?})", r.job_id),
+                        Err(e) => error!("Failed to pause recording: {}", e),
+                    }
+                    let _ = reply.send(result);
+                }
+                ApiCommand::ResumeRecording { reply } => {
+                    let result = recording_machine.resume().await;
+                    match &result {
+                        Ok(r) => info!("Recording resumed (job_id={:?})", r.job_id),
+                        Err(e) => error!("Failed to resume recording: {}", e),
+                    }
+                    let _ = reply.send(result);
+                }
+                ApiCommand::MeetingStart { options, reply } => {
+                    let result = meeting_machine.start(options).await;
+                    match &result {
+                        Ok(r) => info!(
+                            "Meeting {} started: {:?} ({})",
+                            r.meeting_id,
+                            r.audio_path,
+                            r.capture_state.as_str()
+                        ),
+                        Err(e) => error!("Failed to start meeting: {}", e),
+                    }
+                    let _ = reply.send(result);
+                }
+                ApiCommand::MeetingStop { reply } => {
+                    let result = meeting_machine.stop().await;
+                    match &result {
+                        Ok(r) => {
+                            info!("Meeting {} stopped ({}s)", r.meeting_id, r.duration_seconds)
+                        }
+                        Err(e) => error!("Failed to stop meeting: {}", e),
+                    }
+                    let _ = reply.send(result);
+                }
+                ApiCommand::MeetingCancel { reply } => {
+                    let result = meeting_machine.cancel().await;
+                    match &result {
+                        Ok(r) => info!(
+                            "Meeting {} cancelled ({}s)",
+                            r.meeting_id, r.duration_seconds
+                        ),
+                        Err(e) => error!("Failed to cancel meeting: {}", e),
+                    }
+                    let _ = reply.send(result);
+                }
+                ApiCommand::MeetingConfirm {
+                    start_seconds,
+                    end_seconds,
+                    reply,
+                } => {
+                    let result = meeting_machine.confirm(start_seconds, end_seconds).await;
+                    match &result {
+                        Ok(r) => info!(
+                            "Meeting {} confirmed for transcription ({}s)",
+                            r.meeting_id, r.duration_seconds
+                        ),
+                        Err(e) => error!("Failed to confirm meeting: {}", e),
+                    }
+                    let _ = reply.send(result);
+                }
+                ApiCommand::ReloadConfig { reply } => {
+                    let result = reload::prepare(&config).map(|prepared| {
+                        let unavailable = prepared
+                            .transcription
+                            .is_none()
+                            .then(|| NO_PROVIDER_CONFIGURED.to_string());
+                        *dictation_unavailable
+                            .write()
+                            .unwrap_or_else(|e| e.into_inner()) = unavailable;
+                        recording_machine.reconfigure(
+                            prepared.transcription,
+                            prepared.indicator,
+                            prepared.text_io,
+                            prepared.behavior,
+                        );
+                        job_limiter.set_limit(prepared.config.network.max_concurrent_jobs);
+                        config = prepared.config;
+                        prepared.outcome
+                    });
+                    match &result {
+                        Ok(outcome) if outcome.changed.is_empty() => {
+                            info!("Config reloaded; nothing changed")
+                        }
+                        Ok(outcome) => {
+                            info!("Config reloaded; changed: {}", outcome.changed.join(", "));
+                            if !outcome.restart_required.is_empty() {
+                                warn!(
+                                    "Restart the service to apply: {}",
+                                    outcome.restart_required.join(", ")
+                                );
+                            }
+                            for warning in &outcome.warnings {
+                                warn!("Config: {}", warning);
+                            }
+                        }
+                        Err(e) => error!("Config reload failed, keeping the current config: {e:#}"),
+                    }
+                    let _ = reply.send(result);
+                }
+                ApiCommand::MeetingToggle { options, reply } => {
+                    let result = meeting_machine.toggle(options).await;
+                    match &result {
+                        Ok(outcome) => match outcome {
+                            crate::meeting::ToggleOutcome::Started(r) => {
+                                info!("Meeting {} started via toggle", r.meeting_id);
+                            }
+                            crate::meeting::ToggleOutcome::Stopped(r) => {
+                                info!(
+                                    "Meeting {} stopped via toggle ({}s)",
+                                    r.meeting_id, r.duration_seconds
+                                );
+                            }
+                        },
+                        Err(e) => error!("Failed to toggle meeting: {}", e),
+                    }
+                    let _ = reply.send(result);
                 }
-                let _ = reply.send(result);
             }
         }
-    }
 
-    Ok(())
+        Ok(())
+    }
 }
 
 /// Build the transcription service used by the meeting pipeline. Lives at the
 /// app level (not inside `build_meeting_machine`) so the API server can hand
 /// the same instance to retry endpoints — re-running an old failed meeting
-/// shouldn't double up the HTTP client or the timeout config.
+/// shouldn't double up the HTTP client or the timeout config. Remote jobs
+/// count against `job_limiter`.
 fn build_meeting_transcription_service(
     config: &Config,
-) -> Arc<dyn crate::transcription::job_service::TranscriptionJobService> {
+    job_limiter: Arc<JobLimiter>,
+) -> Result<Arc<dyn crate::transcription::job_service::TranscriptionJobService>> {
     // On-device transcription: run the configured local engine directly instead
-    // of submitting to the cloud jobs API. Falls back to remote if the local
-    // engine can't be constructed (so a misconfigured local provider doesn't
-    // wedge the meeting pipeline at startup).
-    if config.whisper.provider.as_deref() == Some("local") {
+    // of submitting to the cloud jobs API. Speaker labels come from the
+    // provider's own API, so diarized meetings go straight to a provider that
+    // has one. Falls back to remote if the provider can't be constructed (so
+    // a misconfigured provider doesn't wedge the meeting pipeline at startup).
+    let provider = config.whisper.provider.as_deref();
+    let diarizing = config.meeting.diarize && matches!(provider, Some("assembly-ai" | "deepgram"));
+    if provider == Some("local") || diarizing {
         match build_transcriber(config).and_then(TranscriptionService::new) {
+            Ok(service) if diarizing => {
+                info!(
+                    "Meetings will be transcribed with speaker labels by the configured provider"
+                );
+                return Ok(Arc::new(LocalTranscriptionJobService::new(service)));
+            }
             Ok(service) => {
                 info!("Meetings will transcribe on-device (local engine)");
-                return Arc::new(LocalTranscriptionJobService::new(service));
+                return Ok(Arc::new(LocalTranscriptionJobService::new(service)));
             }
             Err(e) => {
-                warn!("Failed to build local meeting transcription, falling back to remote: {e:#}")
+                warn!("Failed to build meeting transcription, falling back to remote: {e:#}")
             }
         }
     }
@@ -256,9 +624,13 @@ fn build_meeting_transcription_service(
         })
         .unwrap_or_else(|| DEFAULT_JOBS_API_URL.to_string());
 
-    Arc::new(RemoteTranscriptionJobService::new(
-        &jobs_url,
-        Duration::from_secs(MEETING_TRANSCRIPTION_TIMEOUT_SECS),
+    Ok(Arc::new(
+        RemoteTranscriptionJobService::new(
+            &jobs_url,
+            Duration::from_secs(MEETING_TRANSCRIPTION_TIMEOUT_SECS),
+            &HttpOptions::from_config(config),
+        )?
+        .with_job_limiter(job_limiter),
     ))
 }
 
@@ -268,6 +640,8 @@ fn build_meeting_machine(
     transcription: Arc<dyn crate::transcription::job_service::TranscriptionJobService>,
     post_processing: Arc<PostProcessingService>,
     meetings_dir: std::path::PathBuf,
+    bits_per_sample: u16,
+    diarize: bool,
 ) -> MeetingMachine {
     let mic_source = MicAudioSource::new(16000)
         .map(|s| Box::new(s) as Box<dyn crate::audio::audio_source::AudioSource>)
@@ -290,16 +664,8 @@ fn build_meeting_machine(
         status,
         meetings_dir,
     )
-}
-
-/// Resolve the durable meetings directory used for both live recordings
-/// and imported files. Falls back to `/tmp/audetic/meetings` if `dirs`
-/// can't find a data dir (e.g. degraded container env), matching what
-/// `MeetingMachine` did inline before this was hoisted.
-fn resolve_meetings_dir() -> std::path::PathBuf {
-    crate::global::data_dir()
-        .map(|d| d.join("meetings"))
-        .unwrap_or_else(|_| std::path::PathBuf::from("/tmp/audetic/meetings"))
+    .with_bits_per_sample(bits_per_sample)
+    .with_diarize(diarize)
 }
 
 /// Fallback audio source that produces no samples (for when mic init fails).
@@ -320,6 +686,45 @@ impl crate::audio::audio_source::AudioSource for NullAudioSource {
     }
 }
 
+/// Dictation behavior from `[behavior]`, `[history]`, and `[audio]`.
+fn behavior_options(config: &Config) -> BehaviorOptions {
+    BehaviorOptions {
+        auto_paste: config.behavior.auto_paste,
+        keep_audio: config.behavior.keep_audio(),
+        index_segments: config.history.index_segments,
+        max_history_entries: config.history.max_entries,
+        inject_timestamp: config.behavior.inject_timestamp.clone(),
+        vad: config.audio.vad_threshold.map(|threshold| VadSettings {
+            threshold,
+            min_speech_ms: config.audio.vad_min_speech_ms(),
+        }),
+        temp_dir: config.audio.temp_dir(),
+    }
+}
+
+fn build_text_io(config: &Config) -> Result<TextIoService> {
+    TextIoService::new(
+        Some(&config.wayland.input_method),
+        config.behavior.preserve_clipboard,
+        std::time::Duration::from_millis(config.behavior.paste_delay_ms),
+        std::time::Duration::from_millis(config.behavior.clipboard_restore_delay_ms),
+    )
+}
+
+fn build_indicator(config: &Config) -> Indicator {
+    Indicator::from_config(&config.ui).with_audio_feedback(config.behavior.audio_feedback)
+}
+
+/// The dictation transcription service, or `None` when no provider is
+/// configured and `[behavior].require_provider` allows starting without one.
+fn build_dictation_transcription(config: &Config) -> Result<Option<TranscriptionService>> {
+    if config.whisper.provider.is_none() && !config.behavior.require_provider {
+        warn!("{}", NO_PROVIDER_CONFIGURED);
+        return Ok(None);
+    }
+    TranscriptionService::new(build_transcriber(config)?).map(Some)
+}
+
 fn build_transcriber(config: &Config) -> Result<Transcriber> {
     let provider = config
         .whisper
@@ -327,16 +732,7 @@ fn build_transcriber(config: &Config) -> Result<Transcriber> {
         .as_deref()
         .ok_or_else(|| anyhow!("No transcription provider configured. Set [whisper].provider in ~/.config/audetic/config.toml"))?;
 
-    let provider_config = ProviderConfig {
-        model: config.whisper.model.clone(),
-        model_path: config.whisper.model_path.clone(),
-        language: config.whisper.language.clone(),
-        command_path: config.whisper.command_path.clone(),
-        api_endpoint: config.whisper.api_endpoint.clone(),
-        api_key: config.whisper.api_key.clone(),
-    };
-
-    Transcriber::with_provider(provider, provider_config)
+    Transcriber::with_provider(provider, ProviderConfig::from_config(config))
 }
 
 fn spawn_update_manager() {
@@ -349,3 +745,23 @@ fn spawn_update_manager() {
         Err(err) => warn!("Failed to initialize auto-update manager: {err:?}"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_provider_starts_without_dictation_unless_required() {
+        let mut config = Config::default();
+        config.whisper.provider = None;
+        assert!(build_dictation_transcription(&config).unwrap().is_none());
+
+        config.behavior.require_provider = true;
+        let err = build_dictation_transcription(&config)
+            .err()
+            .expect("startup refused");
+        assert!(err
+            .to_string()
+            .contains("No transcription provider configured"));
+    }
+}
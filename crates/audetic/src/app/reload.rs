@@ -0,0 +1,195 @@
+//! Hot config reload, triggered by SIGHUP or `POST /config/reload`.
+//!
+//! The new `config.toml` is loaded and the dictation pieces built from it
+//! (transcriber, text output, indicator, behavior options) are swapped into
+//! the recording machine by the service loop, between commands. Jobs already
+//! processing keep the services they started with. A config that doesn't
+//! parse, or whose provider can't be built, is rejected and the running one
+//! stays in place.
+//!
+//! Audio capture and the meeting pipeline are built once at startup, so
+//! changes to their settings are reported as needing a restart. The one
+//! exception is `[network].max_concurrent_jobs`, whose limiter the service
+//! loop resizes.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use super::{behavior_options, build_dictation_transcription, build_indicator, build_text_io};
+use crate::audio::BehaviorOptions;
+use crate::config::Config;
+use crate::text_io::TextOutput;
+use crate::transcription::{validate_provider_config, TranscriptionService};
+use crate::ui::Indicator;
+
+/// Settings (or whole sections) only read at startup. The rest of `[audio]`
+/// (VAD, `temp_dir`) is read per dictation. Meeting transcription also keeps
+/// the `[whisper]` endpoint and `[network]` settings it started with, but
+/// those do apply to dictation, so they aren't listed.
+const RESTART_SETTINGS: &[&str] = &[
+    "api",
+    "audio.input_device",
+    "audio.prebuffer_seconds",
+    "audio.bits_per_sample",
+    "behavior.hotkey",
+    "meeting",
+    "ui.waybar",
+];
+
+/// What a successful reload changed.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ConfigReloadOutcome {
+    /// Settings that differ from the running config, as `section.key`.
+    pub changed: Vec<String>,
+    /// The part of `changed` that only takes effect after a restart.
+    pub restart_required: Vec<String>,
+    /// Non-fatal problems with the new config.
+    pub warnings: Vec<String>,
+}
+
+/// A validated config and the dictation services built from it, ready to be
+/// swapped in.
+pub(super) struct Reload {
+    pub config: Config,
+    pub transcription: Option<Arc<TranscriptionService>>,
+    pub text_io: Arc<dyn TextOutput>,
+    pub indicator: Indicator,
+    pub behavior: BehaviorOptions,
+    pub outcome: ConfigReloadOutcome,
+}
+
+/// Load `config.toml` and build everything a reload swaps in. Nothing is
+/// applied here, so an error leaves the running service untouched.
+pub(super) fn prepare(current: &Config) -> Result<Reload> {
+    let config = Config::load().context("Failed to load config.toml")?;
+    prepare_from(current, config)
+}
+
+fn prepare_from(current: &Config, config: Config) -> Result<Reload> {
+    if let Some(provider) = config.whisper.provider.as_deref() {
+        if let Some(error) = validate_provider_config(provider, &config.whisper) {
+            anyhow::bail!("Invalid [whisper] settings: {error}");
+        }
+    }
+    let transcription = build_dictation_transcription(&config)
+        .context("Failed to initialize the transcription provider")?
+        .map(Arc::new);
+    let text_io = build_text_io(&config)?;
+
+    let changed = changed_fields(current, &config);
+    let restart_required = changed
+        .iter()
+        .filter(|field| needs_restart(field))
+        .cloned()
+        .collect();
+
+    Ok(Reload {
+        transcription,
+        text_io: Arc::new(text_io),
+        indicator: build_indicator(&config),
+        behavior: behavior_options(&config),
+        outcome: ConfigReloadOutcome {
+            changed,
+            restart_required,
+            warnings: config.validate(),
+        },
+        config,
+    })
+}
+
+fn needs_restart(field: &str) -> bool {
+    RESTART_SETTINGS.iter().any(|setting| {
+        field
+            .strip_prefix(setting)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+    })
+}
+
+/// Dotted paths of every setting that differs between `old` and `new`. Only
+/// names are reported, so secrets never reach the log.
+fn changed_fields(old: &Config, new: &Config) -> Vec<String> {
+    let old = serde_json::to_value(old).unwrap_or_default();
+    let new = serde_json::to_value(new).unwrap_or_default();
+    let mut changed = Vec::new();
+    diff_values("", &old, &new, &mut changed);
+    changed
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, changed: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(
+                    &path,
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    changed,
+                );
+            }
+        }
+        _ if old != new => changed.push(path.to_string()),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_fields_names_each_differing_setting() {
+        let old = Config::default();
+        let mut new = Config::default();
+        new.whisper.api_key = Some("sk-secret".to_string());
+        new.behavior.auto_paste = !old.behavior.auto_paste;
+        new.audio.prebuffer_seconds = 5;
+
+        assert_eq!(
+            changed_fields(&old, &new),
+            vec![
+                "audio.prebuffer_seconds",
+                "behavior.auto_paste",
+                "whisper.api_key"
+            ]
+        );
+        assert!(changed_fields(&old, &Config::default()).is_empty());
+    }
+
+    #[test]
+    fn startup_only_settings_are_flagged_for_restart() {
+        assert!(needs_restart("audio.prebuffer_seconds"));
+        assert!(needs_restart("audio.input_device"));
+        assert!(!needs_restart("audio.vad_threshold"));
+        assert!(!needs_restart("audio.temp_dir"));
+        assert!(needs_restart("meeting.diarize"));
+        assert!(needs_restart("ui.waybar.idle_text"));
+        assert!(needs_restart("api.port"));
+        assert!(needs_restart("behavior.hotkey"));
+        assert!(!needs_restart("behavior.auto_paste"));
+        assert!(!needs_restart("ui.notification_color"));
+        assert!(!needs_restart("whisper.provider"));
+        assert!(!needs_restart("audiobook"));
+    }
+
+    #[test]
+    fn broken_provider_settings_are_rejected() {
+        let mut config = Config::default();
+        config.whisper.provider = Some("deepgram".to_string());
+        config.whisper.api_key = None;
+
+        let err = prepare_from(&Config::default(), config)
+            .err()
+            .expect("reload refused");
+        assert!(err.to_string().contains("API key required for Deepgram"));
+    }
+}
@@ -2,6 +2,56 @@
 //!
 //! Pure function (no state, no side effects) — easy to test.
 
+use audetic_core::config::MeetingConfig;
+
+/// Levels for [`AudioMixer::mix_meeting`] (`[meeting].mic_gain`,
+/// `system_gain`, `ducking`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MixOptions {
+    pub mic_gain: f32,
+    pub system_gain: f32,
+    /// Fraction system audio is lowered by while the mic carries speech;
+    /// 0.0 disables ducking.
+    pub ducking: f32,
+}
+
+impl Default for MixOptions {
+    fn default() -> Self {
+        Self {
+            mic_gain: 1.0,
+            system_gain: 1.0,
+            ducking: 0.0,
+        }
+    }
+}
+
+impl From<&MeetingConfig> for MixOptions {
+    /// Invalid gains fall back to 1.0 and ducking is clamped to 0.0-1.0;
+    /// `Config::validate` warns about both.
+    fn from(config: &MeetingConfig) -> Self {
+        let gain = |g: f32| if g >= 0.0 && g.is_finite() { g } else { 1.0 };
+        Self {
+            mic_gain: gain(config.mic_gain),
+            system_gain: gain(config.system_gain),
+            ducking: if config.ducking.is_nan() {
+                0.0
+            } else {
+                config.ducking.clamp(0.0, 1.0)
+            },
+        }
+    }
+}
+
+/// Window the mic is measured over when deciding whether to duck (20 ms).
+const DUCK_WINDOW_SECS: f32 = 0.02;
+/// Mic RMS above which the window counts as speech.
+const DUCK_THRESHOLD: f32 = 0.02;
+/// How long system audio stays ducked after the mic goes quiet, so it
+/// doesn't pump back up between words.
+const DUCK_HOLD_SECS: f32 = 0.3;
+/// Level above which [`soft_clip`] starts compressing.
+const SOFT_CLIP_KNEE: f32 = 0.8;
+
 /// Mix multiple sample vectors into a single mono output.
 ///
 /// Handles:
@@ -60,6 +110,38 @@ impl AudioMixer {
         mixed
     }
 
+    /// Mix a meeting's mic and system tracks (both at `sample_rate`) with
+    /// per-source gains, optionally ducking system audio while the mic
+    /// carries speech.
+    ///
+    /// Unlike [`AudioMixer::mix`], loud moments are soft-clipped rather than
+    /// the whole recording being scaled down, so one loud burst doesn't make
+    /// every other part quieter. Samples below the knee pass through
+    /// unchanged and the output never leaves [-1, 1].
+    pub fn mix_meeting(
+        mic: &[f32],
+        system: &[f32],
+        sample_rate: u32,
+        options: &MixOptions,
+    ) -> Vec<f32> {
+        let len = mic.len().max(system.len());
+        let system_levels = if options.ducking > 0.0 && !mic.is_empty() {
+            ducking_envelope(mic, options.mic_gain, sample_rate, options.ducking)
+        } else {
+            Vec::new()
+        };
+
+        (0..len)
+            .map(|i| {
+                let mic_sample = mic.get(i).copied().unwrap_or(0.0) * options.mic_gain;
+                let duck = system_levels.get(i).copied().unwrap_or(1.0);
+                let system_sample =
+                    system.get(i).copied().unwrap_or(0.0) * options.system_gain * duck;
+                soft_clip(mic_sample + system_sample)
+            })
+            .collect()
+    }
+
     /// Resample audio from one sample rate to another using linear interpolation.
     /// Suitable for speech audio where perfect quality isn't critical.
     pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
@@ -92,6 +174,65 @@ impl AudioMixer {
     }
 }
 
+/// Per-sample level for system audio: `1.0 - ducking` while the mic (after
+/// `mic_gain`) is above [`DUCK_THRESHOLD`] or was within the last
+/// [`DUCK_HOLD_SECS`], 1.0 otherwise. Changes ramp across one window so the
+/// ducking doesn't click.
+fn ducking_envelope(mic: &[f32], mic_gain: f32, sample_rate: u32, ducking: f32) -> Vec<f32> {
+    let window = ((sample_rate as f32 * DUCK_WINDOW_SECS) as usize).max(1);
+    let hold_windows = (DUCK_HOLD_SECS / DUCK_WINDOW_SECS).ceil() as usize;
+    let ducked = 1.0 - ducking;
+
+    let mut targets = Vec::with_capacity(mic.len().div_ceil(window));
+    let mut since_speech = usize::MAX;
+    for chunk in mic.chunks(window) {
+        let rms =
+            (chunk.iter().map(|s| (s * mic_gain).powi(2)).sum::<f32>() / chunk.len() as f32).sqrt();
+        since_speech = if rms > DUCK_THRESHOLD {
+            0
+        } else {
+            since_speech.saturating_add(1)
+        };
+        targets.push(if since_speech <= hold_windows {
+            ducked
+        } else {
+            1.0
+        });
+    }
+
+    let mut levels = Vec::with_capacity(mic.len());
+    let mut previous = 1.0f32;
+    for (w, &target) in targets.iter().enumerate() {
+        let start = w * window;
+        let end = (start + window).min(mic.len());
+        for i in start..end {
+            let t = (i - start + 1) as f32 / window as f32;
+            levels.push(previous + (target - previous) * t);
+        }
+        previous = target;
+    }
+    levels
+}
+
+/// Pass samples within [`SOFT_CLIP_KNEE`] through unchanged and bend louder
+/// ones smoothly towards ±1.0, never past it.
+fn soft_clip(sample: f32) -> f32 {
+    let magnitude = sample.abs();
+    if magnitude <= SOFT_CLIP_KNEE {
+        return sample;
+    }
+    if !magnitude.is_finite() {
+        return if sample.is_nan() {
+            0.0
+        } else {
+            sample.signum()
+        };
+    }
+    let headroom = 1.0 - SOFT_CLIP_KNEE;
+    let bent = SOFT_CLIP_KNEE + headroom * ((magnitude - SOFT_CLIP_KNEE) / headroom).tanh();
+    bent.min(1.0).copysign(sample)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +304,115 @@ mod tests {
         assert_eq!(result, voice);
     }
 
+    /// A 200 Hz sine at `amplitude`, `secs` long at 16 kHz.
+    fn tone(amplitude: f32, secs: f32) -> Vec<f32> {
+        (0..(16_000.0 * secs) as usize)
+            .map(|i| amplitude * (i as f32 * 200.0 * std::f32::consts::TAU / 16_000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_mix_meeting_stays_within_limits() {
+        let loud_mic = tone(0.9, 0.5);
+        let loud_system = tone(1.0, 0.5);
+        let options = MixOptions {
+            mic_gain: 2.0,
+            system_gain: 3.0,
+            ducking: 0.0,
+        };
+
+        let mixed = AudioMixer::mix_meeting(&loud_mic, &loud_system, 16_000, &options);
+
+        assert_eq!(mixed.len(), loud_mic.len());
+        assert!(mixed.iter().all(|s| (-1.0..=1.0).contains(s)));
+        assert!(mixed.iter().any(|s| s.abs() > 0.95));
+    }
+
+    #[test]
+    fn test_mix_meeting_gains_scale_quiet_sources() {
+        let mic = vec![0.1, -0.1, 0.05];
+        let system = vec![0.2, 0.2, 0.0, -0.2];
+        let options = MixOptions {
+            mic_gain: 2.0,
+            system_gain: 0.5,
+            ducking: 0.0,
+        };
+
+        let mixed = AudioMixer::mix_meeting(&mic, &system, 16_000, &options);
+
+        let expected = [0.3, -0.1, 0.1, -0.1];
+        assert_eq!(mixed.len(), expected.len());
+        for (got, want) in mixed.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-6, "{got} != {want}");
+        }
+    }
+
+    #[test]
+    fn test_mix_meeting_default_options_keep_lone_source_level() {
+        let voice = vec![0.002, -0.4, 0.7];
+        let mixed = AudioMixer::mix_meeting(&voice, &[], 16_000, &MixOptions::default());
+        assert_eq!(mixed, voice);
+    }
+
+    #[test]
+    fn test_mix_meeting_ducks_system_while_mic_speaks() {
+        // First half: mic talking over system audio; second half: system alone.
+        let mut mic = tone(0.3, 0.5);
+        mic.extend(vec![0.0; 16_000]);
+        let system = vec![0.4; mic.len()];
+        let options = MixOptions {
+            ducking: 0.75,
+            ..MixOptions::default()
+        };
+
+        let no_mic = AudioMixer::mix_meeting(&[], &system, 16_000, &options);
+        assert!(no_mic.iter().all(|&s| s == 0.4));
+        let silent_mic = AudioMixer::mix_meeting(&vec![0.0; mic.len()], &system, 16_000, &options);
+        assert!(silent_mic.iter().all(|&s| s == 0.4));
+
+        let with_mic = AudioMixer::mix_meeting(&mic, &system, 16_000, &options);
+
+        // Mid-speech, system is at a quarter: the mix is mic + 0.1.
+        let i = 4_000;
+        assert!((with_mic[i] - (mic[i] + 0.1)).abs() < 1e-6);
+        // Well after the mic stops (past the hold and ramp), it's back.
+        assert!((with_mic[with_mic.len() - 1] - 0.4).abs() < 1e-6);
+        assert!(with_mic.iter().all(|s| (-1.0..=1.0).contains(s)));
+    }
+
+    #[test]
+    fn test_mix_options_sanitize_config() {
+        let config = MeetingConfig {
+            mic_gain: -1.0,
+            system_gain: f32::NAN,
+            ducking: 3.0,
+            ..MeetingConfig::default()
+        };
+        assert_eq!(
+            MixOptions::from(&config),
+            MixOptions {
+                mic_gain: 1.0,
+                system_gain: 1.0,
+                ducking: 1.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_soft_clip_is_bounded_and_monotonic() {
+        assert_eq!(soft_clip(0.5), 0.5);
+        assert_eq!(soft_clip(-0.8), -0.8);
+        let mut previous = soft_clip(0.8);
+        for step in 1..200 {
+            let value = soft_clip(0.8 + step as f32 * 0.05);
+            assert!(value <= 1.0 && value >= previous);
+            previous = value;
+        }
+        assert_eq!(soft_clip(f32::INFINITY), 1.0);
+        assert_eq!(soft_clip(f32::NEG_INFINITY), -1.0);
+        assert_eq!(soft_clip(f32::NAN), 0.0);
+    }
+
     #[test]
     fn test_resample_same_rate() {
         let samples = vec![1.0, 2.0, 3.0];
@@ -2,13 +2,14 @@
 
 use anyhow::{Context, Result};
 use cpal::traits::{DeviceTrait, StreamTrait};
-use hound::{WavSpec, WavWriter};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info};
 
-use super::input_device::{open_default_input, OpenInput};
-use super::resample::{push_mono_f32, resample_mono_f32};
+use super::input_device::{open_input, OpenInput};
+use super::prebuffer::{Capture, PrebufferRing};
+use super::resample::resample_mono_f32;
+use super::wav;
 
 /// Target sample rate the VTT pipeline (Whisper) expects. The device may
 /// capture at a higher native rate; the WAV written on stop is at this rate.
@@ -19,40 +20,86 @@ const TARGET_SAMPLE_RATE: u32 = 16000; // Whisper optimal
 pub enum RecordingState {
     Idle,
     Recording,
+    /// Mid-recording, capture suspended; the samples so far are kept.
+    Paused,
     Stopping,
 }
 
 /// Manages the lifecycle of audio streams and recordings
 pub struct AudioStreamManager {
-    /// Default input device + native config, opened lazily on first
+    /// Input device + native config, opened lazily on first
     /// `start_recording`. Acquiring it touches a CoreAudio audio unit which
     /// gates on the macOS mic TCC permission — doing it eagerly at boot wedges
     /// the whole daemon in `tccd` until the grant resolves (see
     /// [`crate::audio::input_device`]). `Mutex` because `start_recording`
     /// takes `&self`.
     input: Mutex<Option<OpenInput>>,
-    /// Mono samples at the *native* rate, accumulated by the cpal callback.
-    samples: Arc<Mutex<Vec<f32>>>,
+    /// Name (or part of one) of the input device to record from
+    /// (`[audio].input_device`); `None` uses the system default.
+    device_selector: Option<String>,
+    /// Mono samples at the *native* rate, routed by the cpal callback into
+    /// the recording or, between recordings, the prebuffer ring.
+    capture: Arc<Mutex<Capture>>,
     active_stream: Arc<Mutex<Option<cpal::Stream>>>,
     state: Arc<Mutex<RecordingState>>,
+    /// Seconds of pre-roll to keep while idle; 0 disables prebuffering and
+    /// the stream only runs while recording.
+    prebuffer_seconds: u32,
+    /// Sample size of the WAV written on stop (`[audio].bits_per_sample`).
+    bits_per_sample: u16,
 }
 
 impl AudioStreamManager {
-    /// Create a new audio stream manager.
+    /// Create a new audio stream manager recording from the input device
+    /// `device_selector` names, or the system default when it's `None` or
+    /// matches nothing.
     ///
     /// Does **not** open the audio device — that's deferred to the first
     /// `start_recording` so the daemon boots even when the mic TCC grant
     /// hasn't been resolved yet. Returns `Result` only to keep the call site
     /// stable; construction itself is infallible.
-    pub fn new() -> Result<Self> {
+    pub fn new(device_selector: Option<String>) -> Result<Self> {
         Ok(Self {
             input: Mutex::new(None),
-            samples: Arc::new(Mutex::new(Vec::new())),
+            device_selector,
+            capture: Arc::new(Mutex::new(Capture::default())),
             active_stream: Arc::new(Mutex::new(None)),
             state: Arc::new(Mutex::new(RecordingState::Idle)),
+            prebuffer_seconds: 0,
+            bits_per_sample: 32,
         })
     }
 
+    /// Keep the last `seconds` of audio between recordings and prepend it to
+    /// the next one (`[audio].prebuffer_seconds`). The input stream then stays
+    /// open while idle; call [`Self::start_prebuffer`] to begin filling before
+    /// the first recording, otherwise it starts after the first one stops.
+    pub fn with_prebuffer_seconds(mut self, seconds: u32) -> Self {
+        self.prebuffer_seconds = seconds;
+        self
+    }
+
+    /// Store recordings as 16-bit PCM instead of 32-bit float when
+    /// `bits` is 16 (`[audio].bits_per_sample`).
+    pub fn with_bits_per_sample(mut self, bits: u16) -> Self {
+        self.bits_per_sample = bits;
+        self
+    }
+
+    /// Open the input and start filling the prebuffer ring. No-op when
+    /// prebuffering is off or the stream is already running.
+    pub fn start_prebuffer(&self) -> Result<()> {
+        if self.prebuffer_seconds == 0 || self.active_stream.lock().unwrap().is_some() {
+            return Ok(());
+        }
+        self.open_stream(false)?;
+        info!(
+            "Prebuffering the last {}s of dictation audio",
+            self.prebuffer_seconds
+        );
+        Ok(())
+    }
+
     /// Start recording audio, properly managing stream lifecycle
     pub async fn start_recording(&self) -> Result<()> {
         let mut state = self.state.lock().unwrap();
@@ -61,22 +108,71 @@ impl AudioStreamManager {
             RecordingState::Recording => {
                 return Err(anyhow::anyhow!("Recording already in progress"));
             }
+            RecordingState::Paused => {
+                return Err(anyhow::anyhow!("Recording paused; resume or stop it"));
+            }
             RecordingState::Stopping => {
                 return Err(anyhow::anyhow!("Previous recording still stopping"));
             }
             RecordingState::Idle => {}
         }
 
-        // Stop any existing stream before starting new one
-        self.cleanup_stream();
+        // A running stream means the prebuffer is filling: keep it and seed
+        // the recording with its contents. Otherwise open a fresh stream.
+        if self.active_stream.lock().unwrap().is_none() {
+            self.open_stream(false)?;
+        }
+        self.capture.lock().unwrap().begin_recording();
+        *state = RecordingState::Recording;
+        info!("Started audio recording");
+
+        Ok(())
+    }
+
+    /// Suspend the current recording without ending it. Audio captured so
+    /// far is kept; nothing is recorded until [`Self::resume_recording`].
+    /// Without a prebuffer the input stream is closed meanwhile.
+    pub async fn pause_recording(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if *state != RecordingState::Recording {
+            return Err(anyhow::anyhow!("No recording in progress to pause"));
+        }
+
+        self.capture.lock().unwrap().pause_recording();
+        if self.prebuffer_seconds == 0 {
+            self.cleanup_stream();
+        }
+        *state = RecordingState::Paused;
+        info!("Paused audio recording");
 
-        // Clear samples buffer for new recording
-        {
-            let mut samples = self.samples.lock().unwrap();
-            samples.clear();
-            samples.shrink_to_fit(); // Free memory from previous recordings
+        Ok(())
+    }
+
+    /// Continue a paused recording; new audio is appended to what was
+    /// captured before the pause.
+    pub async fn resume_recording(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if *state != RecordingState::Paused {
+            return Err(anyhow::anyhow!("No paused recording to resume"));
         }
 
+        if self.active_stream.lock().unwrap().is_none() {
+            self.open_stream(true)?;
+        }
+        self.capture.lock().unwrap().resume_recording();
+        *state = RecordingState::Recording;
+        info!("Resumed audio recording");
+
+        Ok(())
+    }
+
+    /// Open the selected input (first use only) and start a capture stream.
+    /// The stream writes into a fresh [`Capture`] unless `keep_capture` is
+    /// set, which resuming uses to hold on to the paused recording.
+    fn open_stream(&self, keep_capture: bool) -> Result<()> {
+        // Stop any existing stream before starting new one
+        self.cleanup_stream();
+
         debug!("Creating new audio stream");
 
         // Open the device on first use. On macOS this is the call that gates on
@@ -85,30 +181,37 @@ impl AudioStreamManager {
         // permission prompt at the right moment.
         let mut input = self.input.lock().unwrap();
         if input.is_none() {
-            *input = Some(open_default_input("Dictation")?);
+            *input = Some(open_input("Dictation", self.device_selector.as_deref())?);
         }
         let input = input.as_ref().unwrap();
 
-        let samples_clone = self.samples.clone();
+        // Fresh buffers for the new stream; frees memory from previous
+        // recordings. The ring is sized at the device's native rate.
+        if !keep_capture {
+            let prebuffer = (self.prebuffer_seconds > 0)
+                .then(|| PrebufferRing::new(self.prebuffer_seconds, input.native_sample_rate));
+            *self.capture.lock().unwrap() = Capture::with_prebuffer(prebuffer);
+        }
+
+        let capture = self.capture.clone();
         let channels = input.channels;
         let err_fn = |err| error!("Audio stream error: {}", err);
 
         let stream = input.device.build_input_stream(
             &input.config,
             move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                push_mono_f32(data, channels, &samples_clone);
+                if let Ok(mut capture) = capture.lock() {
+                    capture.push(data, channels);
+                }
             },
             err_fn,
             None,
         )?;
 
         stream.play()?;
-        info!("Started audio recording");
 
         // Store stream for proper cleanup
         *self.active_stream.lock().unwrap() = Some(stream);
-        *state = RecordingState::Recording;
-
         Ok(())
     }
 
@@ -123,20 +226,20 @@ impl AudioStreamManager {
             RecordingState::Stopping => {
                 return Err(anyhow::anyhow!("Recording already stopping"));
             }
-            RecordingState::Recording => {}
+            RecordingState::Recording | RecordingState::Paused => {}
         }
 
         *state = RecordingState::Stopping;
         drop(state); // Release lock before cleanup
 
-        // Stop and cleanup stream
-        self.cleanup_stream();
+        // With a prebuffer the stream keeps running and refills the ring;
+        // otherwise stop it now.
+        if self.prebuffer_seconds == 0 {
+            self.cleanup_stream();
+        }
 
         // Extract native-rate samples
-        let native = {
-            let samples_guard = self.samples.lock().unwrap();
-            samples_guard.clone()
-        };
+        let native = self.capture.lock().unwrap().finish_recording();
 
         if native.is_empty() {
             *self.state.lock().unwrap() = RecordingState::Idle;
@@ -167,26 +270,8 @@ impl AudioStreamManager {
             TARGET_SAMPLE_RATE
         );
 
-        // Write WAV file
-        let spec = WavSpec {
-            channels: 1,
-            sample_rate: TARGET_SAMPLE_RATE,
-            bits_per_sample: 32,
-            sample_format: hound::SampleFormat::Float,
-        };
-
-        let mut writer = WavWriter::create(&output_path, spec)?;
-        for sample in resampled {
-            writer.write_sample(sample)?;
-        }
-        writer.finalize()?;
-
-        // Clear samples and reset state
-        {
-            let mut samples = self.samples.lock().unwrap();
-            samples.clear();
-            samples.shrink_to_fit();
-        }
+        let spec = wav::mono_spec(TARGET_SAMPLE_RATE, self.bits_per_sample);
+        wav::write_samples(&output_path, spec, &resampled)?;
 
         *self.state.lock().unwrap() = RecordingState::Idle;
 
@@ -223,7 +308,7 @@ mod tests {
     /// opening the device eagerly, this would fail without hardware.
     #[tokio::test]
     async fn new_does_not_open_audio_device() {
-        let manager = AudioStreamManager::new();
+        let manager = AudioStreamManager::new(Some("no such device".to_string()));
         assert!(
             manager.is_ok(),
             "AudioStreamManager::new() must be infallible and device-free"
@@ -1,5 +1,5 @@
-//! Shared default-input-device acquisition for the dictation and meeting
-//! capture paths.
+//! Shared input-device acquisition for the dictation and meeting capture
+//! paths.
 //!
 //! Pulled out of `AudioStreamManager` and `MicAudioSource` so the blocking
 //! CoreAudio calls live in one place. On macOS `default_input_config()` opens
@@ -11,7 +11,9 @@
 use anyhow::{anyhow, Context, Result};
 use cpal::traits::{DeviceTrait, HostTrait};
 use cpal::SampleFormat;
-use tracing::info;
+use serde::Serialize;
+use tracing::{info, warn};
+use utoipa::ToSchema;
 
 /// An opened default input device plus the native config we capture at.
 ///
@@ -28,6 +30,46 @@ pub struct OpenInput {
     pub channels: usize,
 }
 
+/// An input device as offered by the host, for `GET /audio/devices`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InputDeviceInfo {
+    pub name: String,
+    /// Whether this is the system default input.
+    pub is_default: bool,
+}
+
+/// List the host's input devices. Like opening one, this may touch CoreAudio,
+/// so only call it on request.
+pub fn list_input_devices() -> Result<Vec<InputDeviceInfo>> {
+    let host = cpal::default_host();
+    let default = host.default_input_device().map(|d| device_name(&d));
+    let devices = host
+        .input_devices()
+        .context("Failed to enumerate input devices")?;
+    Ok(devices
+        .map(|device| {
+            let name = device_name(&device);
+            let is_default = default.as_deref() == Some(name.as_str());
+            InputDeviceInfo { name, is_default }
+        })
+        .collect())
+}
+
+/// Pick the device `selector` refers to (`[audio].input_device`): an exact
+/// name match wins, otherwise the first name containing it, both ignoring
+/// case. Returns the index into `names`.
+pub fn resolve_device<S: AsRef<str>>(names: &[S], selector: &str) -> Option<usize> {
+    let selector = selector.trim().to_lowercase();
+    if selector.is_empty() {
+        return None;
+    }
+    let lowered: Vec<String> = names.iter().map(|n| n.as_ref().to_lowercase()).collect();
+    lowered
+        .iter()
+        .position(|name| *name == selector)
+        .or_else(|| lowered.iter().position(|name| name.contains(&selector)))
+}
+
 /// Open the default input device and read its native config.
 ///
 /// On macOS this touches an audio unit and blocks in `tccd` until the
@@ -35,21 +77,29 @@ pub struct OpenInput {
 /// boot. `label` is used only for the device log line so callers can tell the
 /// dictation and meeting-mic sources apart.
 pub fn open_default_input(label: &str) -> Result<OpenInput> {
+    open_input(label, None)
+}
+
+/// Open the input device matching `selector` (see [`resolve_device`]), or the
+/// default one when there's no selector. A selector that matches nothing logs
+/// a warning and falls back to the default rather than failing the recording.
+/// Same lazy-call rule as [`open_default_input`].
+pub fn open_input(label: &str, selector: Option<&str>) -> Result<OpenInput> {
     let host = cpal::default_host();
-    let device = host
-        .default_input_device()
-        .context("No input device available")?;
+    let device = match selector.and_then(|selector| find_input(&host, selector)) {
+        Some(device) => device,
+        None => host
+            .default_input_device()
+            .context("No input device available")?,
+    };
 
     let supported = device
         .default_input_config()
-        .context("Failed to read default input config for audio device")?;
+        .context("Failed to read input config for audio device")?;
 
     info!(
         "{label} using device: {} ({} ch, {} Hz, {:?})",
-        device
-            .description()
-            .map(|d| d.name().to_string())
-            .unwrap_or_else(|_| "unknown".to_string()),
+        device_name(&device),
         supported.channels(),
         supported.sample_rate(),
         supported.sample_format()
@@ -60,7 +110,7 @@ pub fn open_default_input(label: &str) -> Result<OpenInput> {
     // hosts deliver f32 by default.
     if supported.sample_format() != SampleFormat::F32 {
         return Err(anyhow!(
-            "Default input device uses {:?} samples — only f32 is supported",
+            "Input device uses {:?} samples — only f32 is supported",
             supported.sample_format()
         ));
     }
@@ -76,3 +126,65 @@ pub fn open_default_input(label: &str) -> Result<OpenInput> {
         channels,
     })
 }
+
+/// The input device `selector` names, or `None` (with a warning) when no
+/// device matches or the host can't enumerate them.
+fn find_input(host: &cpal::Host, selector: &str) -> Option<cpal::Device> {
+    let devices: Vec<cpal::Device> = match host.input_devices() {
+        Ok(devices) => devices.collect(),
+        Err(e) => {
+            warn!("Couldn't list input devices, using the default: {e}");
+            return None;
+        }
+    };
+    let names: Vec<String> = devices.iter().map(device_name).collect();
+    match resolve_device(&names, selector) {
+        Some(index) => devices.into_iter().nth(index),
+        None => {
+            warn!(
+                "No input device matches [audio].input_device = {selector:?}, using the default. Available: {}",
+                names.join(", ")
+            );
+            None
+        }
+    }
+}
+
+fn device_name(device: &cpal::Device) -> String {
+    device
+        .description()
+        .map(|d| d.name().to_string())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICES: [&str; 4] = [
+        "Built-in Microphone",
+        "USB Audio Device",
+        "Blue Yeti USB Microphone",
+        "USB",
+    ];
+
+    #[test]
+    fn resolve_device_matches_substrings_ignoring_case() {
+        assert_eq!(resolve_device(&DEVICES, "yeti"), Some(2));
+        assert_eq!(resolve_device(&DEVICES, "BUILT-IN"), Some(0));
+        assert_eq!(resolve_device(&DEVICES, " microphone "), Some(0));
+    }
+
+    #[test]
+    fn resolve_device_prefers_an_exact_name() {
+        assert_eq!(resolve_device(&DEVICES, "usb"), Some(3));
+        assert_eq!(resolve_device(&DEVICES, "usb audio"), Some(1));
+    }
+
+    #[test]
+    fn resolve_device_without_a_match_is_none() {
+        assert_eq!(resolve_device(&DEVICES, "webcam"), None);
+        assert_eq!(resolve_device(&DEVICES, "  "), None);
+        assert_eq!(resolve_device::<&str>(&[], "usb"), None);
+    }
+}
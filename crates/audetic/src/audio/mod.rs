@@ -3,11 +3,16 @@ pub mod audio_source;
 pub mod audio_stream_manager;
 pub mod input_device;
 pub mod mic_source;
+pub mod prebuffer;
+pub mod recorder;
 pub mod recording_machine;
 pub mod resample;
 pub mod system_source;
+pub mod vad;
+pub mod wav;
 
 pub use audio_stream_manager::AudioStreamManager;
+pub use recorder::Recorder;
 pub use recording_machine::{
     BehaviorOptions, CompletedJob, JobOptions, RecordingMachine, RecordingPhase, RecordingStatus,
     RecordingStatusHandle, ToggleResult,
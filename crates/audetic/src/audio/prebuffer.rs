@@ -0,0 +1,200 @@
+//! Rolling pre-roll for dictation (`[audio].prebuffer_seconds`).
+//!
+//! With a prebuffer configured, `AudioStreamManager` keeps its input stream
+//! open between recordings and feeds it into a [`PrebufferRing`] that only
+//! ever holds the last N seconds. Starting a recording moves that audio to
+//! the front of the new recording, so words spoken just before the hotkey
+//! aren't lost. Memory is bounded by the ring's fixed capacity.
+
+use std::collections::VecDeque;
+
+use super::resample::extend_mono_f32;
+
+/// Fixed-capacity buffer of the most recent mono samples.
+pub struct PrebufferRing {
+    capacity: usize,
+    samples: VecDeque<f32>,
+}
+
+impl PrebufferRing {
+    /// A ring holding `seconds` of audio at `sample_rate`.
+    pub fn new(seconds: u32, sample_rate: u32) -> Self {
+        let capacity = seconds as usize * sample_rate as usize;
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Remove and return everything buffered, oldest first.
+    pub fn take(&mut self) -> Vec<f32> {
+        self.samples.drain(..).collect()
+    }
+}
+
+impl Extend<f32> for PrebufferRing {
+    fn extend<I: IntoIterator<Item = f32>>(&mut self, iter: I) {
+        if self.capacity == 0 {
+            return;
+        }
+        for sample in iter {
+            if self.samples.len() == self.capacity {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(sample);
+        }
+    }
+}
+
+/// Where the capture callback writes: the active recording, or the
+/// prebuffer ring between recordings. A paused recording keeps its samples
+/// and drops whatever arrives until it resumes.
+#[derive(Default)]
+pub struct Capture {
+    recording: bool,
+    paused: bool,
+    samples: Vec<f32>,
+    prebuffer: Option<PrebufferRing>,
+}
+
+impl Capture {
+    pub fn with_prebuffer(prebuffer: Option<PrebufferRing>) -> Self {
+        Self {
+            prebuffer,
+            ..Self::default()
+        }
+    }
+
+    /// Mix interleaved `data` to mono and route it. Audio arriving while
+    /// paused, or while not recording and without a ring, is dropped.
+    pub fn push(&mut self, data: &[f32], channels: usize) {
+        if self.recording {
+            extend_mono_f32(data, channels, &mut self.samples);
+        } else if self.paused {
+            // Not pre-rolled either, so resuming doesn't replay it.
+        } else if let Some(ring) = &mut self.prebuffer {
+            extend_mono_f32(data, channels, ring);
+        }
+    }
+
+    /// Start a recording, seeded with whatever the prebuffer holds.
+    pub fn begin_recording(&mut self) {
+        self.samples = self
+            .prebuffer
+            .as_mut()
+            .map(PrebufferRing::take)
+            .unwrap_or_default();
+        self.recording = true;
+    }
+
+    /// Stop adding to the recording but keep what it has so far.
+    pub fn pause_recording(&mut self) {
+        self.recording = false;
+        self.paused = true;
+    }
+
+    /// Carry on a paused recording; new audio is appended to what was
+    /// captured before the pause.
+    pub fn resume_recording(&mut self) {
+        self.paused = false;
+        self.recording = true;
+    }
+
+    /// End the recording (paused or not) and hand back its samples; the
+    /// ring (if any) resumes filling from here.
+    pub fn finish_recording(&mut self) -> Vec<f32> {
+        self.recording = false;
+        self.paused = false;
+        std::mem::take(&mut self.samples)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_keeps_only_the_last_n_seconds() {
+        // 2 seconds at a 4 Hz "sample rate" = 8 samples.
+        let mut ring = PrebufferRing::new(2, 4);
+        ring.extend((0..20).map(|i| i as f32));
+        assert_eq!(ring.len(), 8);
+        assert_eq!(ring.take(), (12..20).map(|i| i as f32).collect::<Vec<_>>());
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn zero_second_ring_stores_nothing() {
+        let mut ring = PrebufferRing::new(0, 16000);
+        ring.extend([0.1, 0.2]);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn recording_starts_with_the_prebuffer() {
+        let mut capture = Capture::with_prebuffer(Some(PrebufferRing::new(1, 3)));
+        capture.push(&[1.0, 2.0, 3.0, 4.0, 5.0], 1);
+
+        capture.begin_recording();
+        capture.push(&[6.0, 7.0], 1);
+        assert_eq!(capture.finish_recording(), vec![3.0, 4.0, 5.0, 6.0, 7.0]);
+
+        // The ring was drained into that recording; the next one only gets
+        // what arrived after it stopped.
+        capture.push(&[8.0], 1);
+        capture.begin_recording();
+        assert_eq!(capture.finish_recording(), vec![8.0]);
+    }
+
+    #[test]
+    fn without_prebuffer_idle_audio_is_dropped() {
+        let mut capture = Capture::default();
+        capture.push(&[1.0, 2.0], 1);
+        capture.begin_recording();
+        capture.push(&[0.5, 1.5, 2.5, 3.5], 2);
+        assert_eq!(capture.finish_recording(), vec![1.0, 3.0]);
+        assert!(!capture.is_recording());
+    }
+
+    #[test]
+    fn paused_audio_is_dropped_and_the_rest_concatenated() {
+        let mut capture = Capture::with_prebuffer(Some(PrebufferRing::new(1, 4)));
+        capture.begin_recording();
+        capture.push(&[1.0, 2.0], 1);
+
+        capture.pause_recording();
+        assert!(!capture.is_recording());
+        // Said while paused: not recorded, and not pre-rolled into the resume.
+        capture.push(&[9.0, 9.0, 9.0], 1);
+
+        capture.resume_recording();
+        capture.push(&[3.0, 4.0], 1);
+        assert_eq!(capture.finish_recording(), vec![1.0, 2.0, 3.0, 4.0]);
+
+        // Once finished, idle audio fills the ring again.
+        capture.push(&[5.0], 1);
+        capture.begin_recording();
+        assert_eq!(capture.finish_recording(), vec![5.0]);
+    }
+
+    #[test]
+    fn a_paused_recording_can_be_finished() {
+        let mut capture = Capture::default();
+        capture.begin_recording();
+        capture.push(&[1.0], 1);
+        capture.pause_recording();
+        assert_eq!(capture.finish_recording(), vec![1.0]);
+    }
+}
@@ -0,0 +1,47 @@
+//! Dictation capture abstraction.
+//!
+//! [`RecordingMachine`](super::RecordingMachine) only needs to start a
+//! recording, pause and resume it, and later write it out as a WAV.
+//! [`AudioStreamManager`] does that against the real input device; integration
+//! tests inject a recorder that writes canned audio instead.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+use super::AudioStreamManager;
+
+/// Captures one dictation at a time. Not `Send`: the production recorder owns
+/// a platform audio stream that must stay on the thread that opened it.
+#[async_trait(?Send)]
+pub trait Recorder {
+    async fn start_recording(&self) -> Result<()>;
+
+    /// Suspend the current recording, keeping what it has captured so far.
+    async fn pause_recording(&self) -> Result<()>;
+
+    /// Continue a paused recording, appending to the audio already captured.
+    async fn resume_recording(&self) -> Result<()>;
+
+    /// Stop the current recording and save it to `output_path` as WAV.
+    async fn stop_recording(&self, output_path: PathBuf) -> Result<PathBuf>;
+}
+
+#[async_trait(?Send)]
+impl Recorder for AudioStreamManager {
+    async fn start_recording(&self) -> Result<()> {
+        AudioStreamManager::start_recording(self).await
+    }
+
+    async fn pause_recording(&self) -> Result<()> {
+        AudioStreamManager::pause_recording(self).await
+    }
+
+    async fn resume_recording(&self) -> Result<()> {
+        AudioStreamManager::resume_recording(self).await
+    }
+
+    async fn stop_recording(&self, output_path: PathBuf) -> Result<PathBuf> {
+        AudioStreamManager::stop_recording(self, output_path).await
+    }
+}
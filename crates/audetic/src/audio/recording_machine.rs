@@ -1,26 +1,32 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
-use tokio::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{watch, Mutex};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use crate::audio::AudioStreamManager;
+use crate::audio::vad::{trim_silence, TrimOutcome, VadSettings};
+use crate::audio::{wav, Recorder};
 use crate::db::{self, VoiceToTextData, Workflow, WorkflowData, WorkflowType};
 use crate::post_processing::{
     DictationCompletedPayload, Event as PostProcessingEvent, PostProcessingService,
 };
-use crate::text_io::TextIoService;
-use crate::transcription::TranscriptionService;
+use crate::text_io::{stamp_text, TextOutput};
+use crate::transcription::{Transcript, TranscriptionService, NO_PROVIDER_CONFIGURED};
 use crate::ui::Indicator;
+use audetic_core::config::{InjectTimestampConfig, KeepAudio};
+use audetic_core::jobs_client::Segment;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RecordingPhase {
     Idle,
     Recording,
+    /// A recording on hold: its audio and job are kept until it resumes.
+    Paused,
     Processing,
     Error,
 }
@@ -30,6 +36,7 @@ impl RecordingPhase {
         match self {
             RecordingPhase::Idle => "idle",
             RecordingPhase::Recording => "recording",
+            RecordingPhase::Paused => "paused",
             RecordingPhase::Processing => "processing",
             RecordingPhase::Error => "error",
         }
@@ -59,6 +66,9 @@ pub struct RecordingStatus {
     /// Last successfully completed job
     pub last_completed_job: Option<CompletedJob>,
     pub last_error: Option<String>,
+    /// The transcript so far while processing, from providers that stream
+    /// it (`[whisper].stream_partials`). Cleared when the phase changes.
+    pub partial_text: Option<String>,
 }
 
 impl Default for RecordingStatus {
@@ -69,13 +79,28 @@ impl Default for RecordingStatus {
             current_job_options: None,
             last_completed_job: None,
             last_error: None,
+            partial_text: None,
         }
     }
 }
 
-#[derive(Clone, Default)]
+/// Shared dictation status. Every change is also published on a
+/// [`watch`] channel so `GET /status/stream` can push it instead of being
+/// polled.
+#[derive(Clone)]
 pub struct RecordingStatusHandle {
     inner: Arc<Mutex<RecordingStatus>>,
+    changes: Arc<watch::Sender<RecordingStatus>>,
+}
+
+impl Default for RecordingStatusHandle {
+    fn default() -> Self {
+        let (changes, _) = watch::channel(RecordingStatus::default());
+        Self {
+            inner: Arc::default(),
+            changes: Arc::new(changes),
+        }
+    }
 }
 
 impl RecordingStatusHandle {
@@ -83,10 +108,23 @@ impl RecordingStatusHandle {
         self.inner.lock().await.clone()
     }
 
+    /// A receiver that sees the status after every change. Each subscriber
+    /// gets its own; dropping it is all a disconnect needs.
+    pub fn subscribe(&self) -> watch::Receiver<RecordingStatus> {
+        self.changes.subscribe()
+    }
+
+    /// Called with the lock still held, so subscribers see changes in order.
+    fn publish(&self, status: &RecordingStatus) {
+        self.changes.send_replace(status.clone());
+    }
+
     pub async fn set_phase(&self, phase: RecordingPhase, last_error: Option<String>) {
         let mut status = self.inner.lock().await;
         status.phase = phase;
         status.last_error = last_error;
+        status.partial_text = None;
+        self.publish(&status);
     }
 
     pub async fn start_job(&self, job_id: String, options: JobOptions) {
@@ -95,6 +133,21 @@ impl RecordingStatusHandle {
         status.current_job_id = Some(job_id);
         status.current_job_options = Some(options);
         status.last_error = None;
+        self.publish(&status);
+    }
+
+    /// Put the current job on hold; its id and options are kept.
+    pub async fn pause_job(&self) {
+        let mut status = self.inner.lock().await;
+        status.phase = RecordingPhase::Paused;
+        self.publish(&status);
+    }
+
+    /// Carry on recording the current job.
+    pub async fn resume_job(&self) {
+        let mut status = self.inner.lock().await;
+        status.phase = RecordingPhase::Recording;
+        self.publish(&status);
     }
 
     pub async fn complete_job(&self, completed_job: CompletedJob) {
@@ -104,6 +157,8 @@ impl RecordingStatusHandle {
         status.current_job_options = None;
         status.last_completed_job = Some(completed_job);
         status.last_error = None;
+        status.partial_text = None;
+        self.publish(&status);
     }
 
     pub async fn fail_job(&self, error: String) {
@@ -112,12 +167,26 @@ impl RecordingStatusHandle {
         status.current_job_id = None;
         status.current_job_options = None;
         status.last_error = Some(error);
+        status.partial_text = None;
+        self.publish(&status);
     }
 
     pub async fn set_processing(&self) {
         let mut status = self.inner.lock().await;
         status.phase = RecordingPhase::Processing;
+        status.partial_text = None;
         // Keep the current_job_id during processing
+        self.publish(&status);
+    }
+
+    /// Record the transcript so far. Ignored outside processing, so a late
+    /// update can't outlive the job it belongs to.
+    pub async fn set_partial_text(&self, text: String) {
+        let mut status = self.inner.lock().await;
+        if status.phase == RecordingPhase::Processing {
+            status.partial_text = Some(text);
+            self.publish(&status);
+        }
     }
 
     pub async fn get_current_job_id(&self) -> Option<String> {
@@ -157,40 +226,68 @@ impl Default for JobOptions {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct BehaviorOptions {
     pub auto_paste: bool,
-    pub delete_audio_files: bool,
+    /// Which recordings to keep after transcription (`[behavior].keep_audio_on`).
+    pub keep_audio: KeepAudio,
+    /// Store transcript segments as searchable rows (`[history].index_segments`).
+    pub index_segments: bool,
+    /// Transcriptions kept before the oldest are pruned; 0 keeps all
+    /// (`[history].max_entries`).
+    pub max_history_entries: usize,
+    /// Stamp copied/injected text with the time (`[behavior.inject_timestamp]`).
+    pub inject_timestamp: Option<InjectTimestampConfig>,
+    /// Trim silence before transcribing (`[audio].vad_threshold`).
+    pub vad: Option<VadSettings>,
+    /// Where recordings are written before transcription (`[audio].temp_dir`).
+    pub temp_dir: PathBuf,
 }
 
 /// Context for running a transcription processing task.
 struct ProcessingContext {
     transcription: Arc<TranscriptionService>,
     indicator: Indicator,
-    text_io: TextIoService,
+    text_io: Arc<dyn TextOutput>,
     job_options: JobOptions,
     temp_path: PathBuf,
     job_id: Option<String>,
-    delete_audio_files: bool,
+    keep_audio: KeepAudio,
+    index_segments: bool,
+    max_history_entries: usize,
+    inject_timestamp: Option<InjectTimestampConfig>,
+    vad: Option<VadSettings>,
     post_processing: Arc<PostProcessingService>,
 }
 
+/// The spawned transcription of the last stopped recording, kept so
+/// shutdown can wait for it (or settle its recording if it runs over).
+struct ProcessingTask {
+    handle: JoinHandle<()>,
+    temp_path: PathBuf,
+    job_id: Option<String>,
+    keep_audio: KeepAudio,
+}
+
 pub struct RecordingMachine {
-    audio: Arc<Mutex<AudioStreamManager>>,
-    transcription: Arc<TranscriptionService>,
+    audio: Arc<Mutex<dyn Recorder>>,
+    /// `None` when the service started without a provider; recording is
+    /// refused until one is configured.
+    transcription: Option<Arc<TranscriptionService>>,
     indicator: Indicator,
-    text_io: TextIoService,
+    text_io: Arc<dyn TextOutput>,
     behavior: BehaviorOptions,
     status: RecordingStatusHandle,
     post_processing: Arc<PostProcessingService>,
+    processing: std::sync::Mutex<Option<ProcessingTask>>,
 }
 
 impl RecordingMachine {
     pub fn new(
-        audio: Arc<Mutex<AudioStreamManager>>,
-        transcription: Arc<TranscriptionService>,
+        audio: Arc<Mutex<dyn Recorder>>,
+        transcription: Option<Arc<TranscriptionService>>,
         indicator: Indicator,
-        text_io: TextIoService,
+        text_io: Arc<dyn TextOutput>,
         behavior: BehaviorOptions,
         status: RecordingStatusHandle,
         post_processing: Arc<PostProcessingService>,
@@ -203,10 +300,27 @@ impl RecordingMachine {
             behavior,
             status,
             post_processing,
+            processing: std::sync::Mutex::new(None),
         }
     }
 
+    /// Swap in services rebuilt from a reloaded config. Takes effect from the
+    /// next toggle; a job already processing keeps the ones it started with.
+    pub fn reconfigure(
+        &mut self,
+        transcription: Option<Arc<TranscriptionService>>,
+        indicator: Indicator,
+        text_io: Arc<dyn TextOutput>,
+        behavior: BehaviorOptions,
+    ) {
+        self.transcription = transcription;
+        self.indicator = indicator;
+        self.text_io = text_io;
+        self.behavior = behavior;
+    }
+
     /// Toggle recording state and return the result with job information.
+    /// A paused recording is resumed rather than stopped.
     ///
     /// Returns a `ToggleResult` containing:
     /// - `phase`: The new recording phase
@@ -219,6 +333,7 @@ impl RecordingMachine {
         enum Transition {
             StartRecording,
             StopRecording,
+            ResumeRecording,
             Busy(RecordingPhase),
         }
 
@@ -226,6 +341,7 @@ impl RecordingMachine {
         let transition = match current.phase {
             RecordingPhase::Idle | RecordingPhase::Error => Transition::StartRecording,
             RecordingPhase::Recording => Transition::StopRecording,
+            RecordingPhase::Paused => Transition::ResumeRecording,
             RecordingPhase::Processing => Transition::Busy(RecordingPhase::Processing),
         };
 
@@ -261,34 +377,8 @@ impl RecordingMachine {
                     job_id: Some(job_id),
                 })
             }
-            Transition::StopRecording => {
-                let job_id = current.current_job_id.clone();
-                // Job options should always be set when recording started, fall back to defaults if not
-                let job_options = current.current_job_options.unwrap_or(JobOptions {
-                    copy_to_clipboard: true,
-                    auto_paste: self.behavior.auto_paste,
-                });
-                info!(
-                    "RecordingMachine: stopping recording and processing job_id={:?}, options={:?}",
-                    job_id, job_options
-                );
-                self.status.set_processing().await;
-
-                if let Err(e) = self.begin_processing(job_id.clone(), job_options).await {
-                    error!("Failed to start processing task: {}", e);
-                    self.status.fail_job(e.to_string()).await;
-                    let _ = self
-                        .indicator
-                        .show_error(&format!("Processing failed: {e}"))
-                        .await;
-                    return Err(e);
-                }
-
-                Ok(ToggleResult {
-                    phase: RecordingPhase::Processing,
-                    job_id,
-                })
-            }
+            Transition::StopRecording => self.stop_and_process(current).await,
+            Transition::ResumeRecording => self.resume().await,
             //NOTE: this could be annoying
             Transition::Busy(phase) => {
                 warn!(
@@ -303,7 +393,143 @@ impl RecordingMachine {
         }
     }
 
+    /// Stop the open recording (`current` is the status it was taken from)
+    /// and hand it to a background transcription task.
+    async fn stop_and_process(&self, current: RecordingStatus) -> Result<ToggleResult> {
+        let job_id = current.current_job_id.clone();
+        // Job options should always be set when recording started, fall back to defaults if not
+        let job_options = current.current_job_options.unwrap_or(JobOptions {
+            copy_to_clipboard: true,
+            auto_paste: self.behavior.auto_paste,
+        });
+        info!(
+            "RecordingMachine: stopping recording and processing job_id={:?}, options={:?}",
+            job_id, job_options
+        );
+        self.status.set_processing().await;
+
+        if let Err(e) = self.begin_processing(job_id.clone(), job_options).await {
+            error!("Failed to start processing task: {}", e);
+            self.status.fail_job(e.to_string()).await;
+            let _ = self
+                .indicator
+                .show_error(&format!("Processing failed: {e}"))
+                .await;
+            return Err(e);
+        }
+
+        Ok(ToggleResult {
+            phase: RecordingPhase::Processing,
+            job_id,
+        })
+    }
+
+    /// Settle dictation before the process exits. A recording that is still
+    /// open (recording or paused) is stopped and transcribed as if toggled
+    /// off; the transcription then gets up to `grace` to finish and reach
+    /// history. One that runs over is abandoned, logged as a failed job, and
+    /// its recording kept or deleted per `keep_audio` like any failure.
+    pub async fn shutdown(&self, grace: Duration) {
+        let current = self.status.get().await;
+        if matches!(
+            current.phase,
+            RecordingPhase::Recording | RecordingPhase::Paused
+        ) {
+            info!(
+                "RecordingMachine: shutting down mid-recording, processing job_id={:?}",
+                current.current_job_id
+            );
+            if let Err(e) = self.stop_and_process(current).await {
+                warn!("Failed to process the open recording at shutdown: {}", e);
+            }
+        }
+
+        let task = self
+            .processing
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        let Some(task) = task else { return };
+        if task.handle.is_finished() {
+            return;
+        }
+
+        info!(
+            "Waiting up to {}s for job_id={:?} to finish transcribing",
+            grace.as_secs(),
+            task.job_id
+        );
+        let abort = task.handle.abort_handle();
+        if tokio::time::timeout(grace, task.handle).await.is_err() {
+            abort.abort();
+            warn!(
+                "Transcription of job_id={:?} didn't finish within {}s; abandoning it",
+                task.job_id,
+                grace.as_secs()
+            );
+            record_failure(task.job_id, "Interrupted by shutdown".to_string()).await;
+            settle_audio_file(&task.temp_path, task.keep_audio, false).await;
+        }
+    }
+
+    /// Pause the current recording. The audio captured so far and the job
+    /// id are kept, and nothing is recorded until [`Self::resume`].
+    pub async fn pause(&self) -> Result<ToggleResult> {
+        let current = self.status.get().await;
+        if current.phase != RecordingPhase::Recording {
+            bail!("Not recording (currently {})", current.phase.as_str());
+        }
+
+        {
+            let recorder = self.audio.lock().await;
+            recorder.pause_recording().await?;
+        }
+        self.status.pause_job().await;
+        if let Err(e) = self.indicator.show_paused().await {
+            warn!("Failed to show paused indicator: {}", e);
+        }
+
+        info!(
+            "RecordingMachine: paused recording job_id={:?}",
+            current.current_job_id
+        );
+        Ok(ToggleResult {
+            phase: RecordingPhase::Paused,
+            job_id: current.current_job_id,
+        })
+    }
+
+    /// Resume a paused recording; new audio is appended to what was
+    /// captured before the pause.
+    pub async fn resume(&self) -> Result<ToggleResult> {
+        let current = self.status.get().await;
+        if current.phase != RecordingPhase::Paused {
+            bail!("No paused recording (currently {})", current.phase.as_str());
+        }
+
+        {
+            let recorder = self.audio.lock().await;
+            recorder.resume_recording().await?;
+        }
+        self.status.resume_job().await;
+        if let Err(e) = self.indicator.show_recording().await {
+            warn!("Failed to show recording indicator: {}", e);
+        }
+
+        info!(
+            "RecordingMachine: resumed recording job_id={:?}",
+            current.current_job_id
+        );
+        Ok(ToggleResult {
+            phase: RecordingPhase::Recording,
+            job_id: current.current_job_id,
+        })
+    }
+
     async fn start_recording(&self) -> Result<()> {
+        if self.transcription.is_none() {
+            bail!(NO_PROVIDER_CONFIGURED);
+        }
         if let Err(e) = self.indicator.show_recording().await {
             warn!("Failed to show recording indicator: {}", e);
         }
@@ -317,7 +543,8 @@ impl RecordingMachine {
         job_id: Option<String>,
         job_options: JobOptions,
     ) -> Result<()> {
-        let temp_path = Self::temp_audio_path();
+        let transcription = self.transcription.clone().context(NO_PROVIDER_CONFIGURED)?;
+        let temp_path = self.temp_audio_path()?;
 
         {
             let recorder = self.audio.lock().await;
@@ -329,22 +556,42 @@ impl RecordingMachine {
             warn!("Failed to show processing indicator: {}", e);
         }
         let indicator_for_error = self.indicator.clone();
+        let indicator_for_partials = self.indicator.clone();
+        let partials = transcription.partial_transcripts();
 
         let status = self.status.clone();
+        let job_id_for_failure = job_id.clone();
 
+        let pending = (temp_path.clone(), job_id.clone());
         let ctx = ProcessingContext {
-            transcription: Arc::clone(&self.transcription),
+            transcription,
             indicator: indicator_for_task,
-            text_io: self.text_io.clone(),
+            text_io: Arc::clone(&self.text_io),
             job_options,
             temp_path,
             job_id,
-            delete_audio_files: self.behavior.delete_audio_files,
+            keep_audio: self.behavior.keep_audio,
+            index_segments: self.behavior.index_segments,
+            max_history_entries: self.behavior.max_history_entries,
+            inject_timestamp: self.behavior.inject_timestamp.clone(),
+            vad: self.behavior.vad,
             post_processing: Arc::clone(&self.post_processing),
         };
 
-        tokio::spawn(async move {
-            let result = RecordingMachine::run_processing_task(ctx).await;
+        let handle = tokio::spawn(async move {
+            let processing = RecordingMachine::run_processing_task(ctx);
+            let result = match partials {
+                Some(partials) => {
+                    tokio::pin!(processing);
+                    tokio::select! {
+                        result = &mut processing => result,
+                        () = forward_partials(partials, &status, &indicator_for_partials) => {
+                            processing.await
+                        }
+                    }
+                }
+                None => processing.await,
+            };
 
             match result {
                 Ok(completed_job) => {
@@ -357,6 +604,7 @@ impl RecordingMachine {
                 }
                 Err(e) => {
                     error!("Recording pipeline failed: {}", e);
+                    record_failure(job_id_for_failure, e.to_string()).await;
                     status.fail_job(e.to_string()).await;
                     let _ = indicator_for_error
                         .show_error(&format!("Transcription failed: {e}"))
@@ -365,14 +613,45 @@ impl RecordingMachine {
             }
         });
 
+        let (temp_path, job_id) = pending;
+        *self.processing.lock().unwrap_or_else(|e| e.into_inner()) = Some(ProcessingTask {
+            handle,
+            temp_path,
+            job_id,
+            keep_audio: self.behavior.keep_audio,
+        });
         Ok(())
     }
 
-    /// Run the transcription processing task.
+    /// Run the transcription processing task, then keep or delete the
+    /// recording according to `keep_audio` and the outcome.
     /// Returns `Ok(Some(CompletedJob))` on success, `Ok(None)` if no speech detected.
     async fn run_processing_task(ctx: ProcessingContext) -> Result<Option<CompletedJob>> {
-        let completed_job = match ctx.transcription.transcribe(&ctx.temp_path).await {
-            Ok(text) => {
+        let temp_path = ctx.temp_path.clone();
+        let keep_audio = ctx.keep_audio;
+
+        let result = RecordingMachine::process_recording(ctx).await;
+        settle_audio_file(&temp_path, keep_audio, result.is_ok()).await;
+        result
+    }
+
+    /// Transcribe the recording and deliver the text (clipboard, injection,
+    /// history, post-processing).
+    async fn process_recording(ctx: ProcessingContext) -> Result<Option<CompletedJob>> {
+        if let Some(vad) = ctx.vad {
+            if !has_speech(&ctx.temp_path, vad).await {
+                warn!("No speech detected in recording; skipping transcription");
+                let _ = ctx.indicator.show_error("No speech detected").await;
+                return Ok(None);
+            }
+        }
+
+        let completed_job = match ctx.transcription.transcribe_with_raw(&ctx.temp_path).await {
+            Ok(Transcript {
+                raw,
+                text,
+                segments,
+            }) => {
                 if text.trim().is_empty() {
                     warn!("No speech detected in recording");
                     let _ = ctx.indicator.show_error("No speech detected").await;
@@ -380,15 +659,22 @@ impl RecordingMachine {
                 } else {
                     info!("Transcription complete: {} chars", text.len());
 
+                    // The stamped text goes to the clipboard too, so the paste
+                    // fallback below matches what injection would have typed.
+                    let output_text = match &ctx.inject_timestamp {
+                        Some(stamp) => stamp_text(&text, stamp, &chrono::Local::now()),
+                        None => text.clone(),
+                    };
+
                     // Use job_options to control clipboard/paste behavior
                     if ctx.job_options.copy_to_clipboard {
-                        if let Err(e) = ctx.text_io.copy_to_clipboard(&text).await {
+                        if let Err(e) = ctx.text_io.copy_to_clipboard(&output_text).await {
                             error!("Failed to copy to clipboard: {}", e);
                         }
                     }
 
                     if ctx.job_options.auto_paste {
-                        if let Err(e) = ctx.text_io.inject_text(&text).await {
+                        if let Err(e) = ctx.text_io.inject_text(&output_text).await {
                             error!("Failed to inject text: {}", e);
                             // Only try paste fallback if we copied to clipboard
                             if ctx.job_options.copy_to_clipboard {
@@ -401,13 +687,34 @@ impl RecordingMachine {
                         warn!("Failed to show completion indicator: {}", e);
                     }
 
-                    // Save transcription to database and get the history ID
+                    let text = if ctx
+                        .inject_timestamp
+                        .as_ref()
+                        .is_some_and(|stamp| stamp.include_in_history)
+                    {
+                        output_text
+                    } else {
+                        text
+                    };
+
+                    // Save transcription to database and get the history ID.
+                    // Segments are stored with the entry even though only the
+                    // plain text was delivered above.
                     let text_for_db = text.clone();
                     let temp_path_for_db = ctx.temp_path.clone();
                     let job_id_for_db = ctx.job_id.clone();
+                    let index_segments = ctx.index_segments;
+                    let max_history_entries = ctx.max_history_entries;
 
                     let db_result = tokio::task::spawn_blocking(move || {
-                        save_to_database(&text_for_db, &temp_path_for_db)
+                        save_to_database(
+                            &text_for_db,
+                            &raw,
+                            &segments,
+                            index_segments,
+                            max_history_entries,
+                            &temp_path_for_db,
+                        )
                     })
                     .await;
 
@@ -457,36 +764,97 @@ impl RecordingMachine {
             }
         };
 
-        if ctx.delete_audio_files {
-            if let Err(e) = tokio::fs::remove_file(&ctx.temp_path).await {
-                warn!(
-                    "Failed to delete temp audio file {:?}: {}",
-                    ctx.temp_path, e
-                );
-            } else {
-                debug!("Deleted temp audio file {:?}", ctx.temp_path);
-            }
-        }
-
         Ok(completed_job)
     }
 
-    fn temp_audio_path() -> PathBuf {
+    fn temp_audio_path(&self) -> Result<PathBuf> {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis();
-        PathBuf::from(format!("/tmp/audetic_{timestamp}.wav"))
+        wav::temp_wav_path(&self.behavior.temp_dir, &format!("audetic_{timestamp}"))
+    }
+}
+
+/// Show the provider's transcript so far in the status and indicator as it
+/// grows. Returns only if the provider goes away.
+async fn forward_partials(
+    mut partials: watch::Receiver<String>,
+    status: &RecordingStatusHandle,
+    indicator: &Indicator,
+) {
+    while partials.changed().await.is_ok() {
+        let text = partials.borrow_and_update().clone();
+        if text.is_empty() {
+            continue;
+        }
+        status.set_partial_text(text.clone()).await;
+        if let Err(e) = indicator.show_partial(&text).await {
+            debug!("Failed to show partial transcript: {}", e);
+        }
+    }
+}
+
+/// Delete a finished recording unless `keep_audio` keeps it for this
+/// outcome.
+async fn settle_audio_file(path: &Path, keep_audio: KeepAudio, succeeded: bool) {
+    if keep_audio.keeps(succeeded) {
+        debug!("Keeping audio file {:?} ({:?})", path, keep_audio);
+        return;
+    }
+    if let Err(e) = tokio::fs::remove_file(path).await {
+        warn!("Failed to delete temp audio file {:?}: {}", path, e);
+    } else {
+        debug!("Deleted temp audio file {:?}", path);
+    }
+}
+
+/// Trim silence off the recording in place. Returns false when it holds no
+/// speech; a file that can't be analyzed is sent untrimmed.
+async fn has_speech(path: &Path, vad: VadSettings) -> bool {
+    let path_for_task = path.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || trim_silence(&path_for_task, &vad)).await;
+
+    match result {
+        Ok(Ok(TrimOutcome::Silent)) => false,
+        Ok(Ok(TrimOutcome::Trimmed { kept, original })) => {
+            debug!(
+                "Trimmed silence from {:?}: kept {kept} of {original} samples",
+                path
+            );
+            true
+        }
+        Ok(Ok(TrimOutcome::Unchanged)) => true,
+        Ok(Err(e)) => {
+            warn!("Silence trimming failed, sending audio as recorded: {}", e);
+            true
+        }
+        Err(e) => {
+            warn!("Silence trimming task panicked: {}", e);
+            true
+        }
     }
 }
 
-/// Save transcription to database and return the history ID.
-fn save_to_database(text: &str, audio_path: &Path) -> Result<i64> {
+/// Save transcription to database and return the history ID. `segments` is
+/// empty unless the provider returned timestamps; they're kept on the entry,
+/// and also indexed as searchable rows when `index_segments` is on. The
+/// oldest entries beyond `max_entries` are pruned; 0 keeps them all.
+fn save_to_database(
+    text: &str,
+    raw_text: &str,
+    segments: &[Segment],
+    index_segments: bool,
+    max_entries: usize,
+    audio_path: &Path,
+) -> Result<i64> {
     let conn = db::init_db()?;
 
     let workflow_data = WorkflowData::VoiceToText(VoiceToTextData {
         text: text.to_string(),
         audio_path: audio_path.to_string_lossy().to_string(),
+        raw_text: Some(raw_text.to_string()),
+        segments: (!segments.is_empty()).then(|| segments.to_vec()),
     });
 
     let workflow = Workflow::new(WorkflowType::VoiceToText, workflow_data);
@@ -494,15 +862,38 @@ fn save_to_database(text: &str, audio_path: &Path) -> Result<i64> {
     let id = db::insert_workflow(&conn, &workflow)?;
     debug!("Saved transcription to database with ID: {}", id);
 
-    // Prune old workflows if count exceeds 10,000
-    let pruned = db::prune_old_workflows(&conn, 10_000)?;
-    if pruned > 0 {
-        info!("Pruned {} old transcriptions from database", pruned);
+    if index_segments && !segments.is_empty() {
+        let indexed = db::insert_segments(&conn, id, segments)?;
+        debug!("Indexed {} segments for transcription {}", indexed, id);
+    }
+
+    if max_entries > 0 {
+        let max_entries = i64::try_from(max_entries).unwrap_or(i64::MAX);
+        let pruned = db::prune_old_workflows(&conn, max_entries)?;
+        if pruned > 0 {
+            info!("Pruned {} old transcriptions from database", pruned);
+        }
     }
 
     Ok(id)
 }
 
+/// Persist a failed job so it shows up in `GET /jobs/recent`. Best effort:
+/// a database problem is logged, never surfaced over the original failure.
+async fn record_failure(job_id: Option<String>, error: String) {
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = db::init_db()?;
+        db::insert_failed_job(&conn, job_id.as_deref(), &error)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => warn!("Failed to record failed job: {}", e),
+        Err(e) => warn!("Failed-job recording task panicked: {}", e),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -511,6 +902,7 @@ mod tests {
     fn test_recording_phase_as_str() {
         assert_eq!(RecordingPhase::Idle.as_str(), "idle");
         assert_eq!(RecordingPhase::Recording.as_str(), "recording");
+        assert_eq!(RecordingPhase::Paused.as_str(), "paused");
         assert_eq!(RecordingPhase::Processing.as_str(), "processing");
         assert_eq!(RecordingPhase::Error.as_str(), "error");
     }
@@ -591,6 +983,30 @@ mod tests {
         assert!(status.current_job_options.is_some());
     }
 
+    #[tokio::test]
+    async fn test_status_handle_notifies_every_subscriber() {
+        let handle = RecordingStatusHandle::default();
+        let mut first = handle.subscribe();
+        let mut second = handle.subscribe();
+
+        handle
+            .start_job("job-1".to_string(), JobOptions::default())
+            .await;
+
+        for rx in [&mut first, &mut second] {
+            rx.changed().await.unwrap();
+            let status = rx.borrow_and_update().clone();
+            assert_eq!(status.phase, RecordingPhase::Recording);
+            assert_eq!(status.current_job_id.as_deref(), Some("job-1"));
+        }
+
+        // A dropped subscriber doesn't stop the others from hearing changes.
+        drop(first);
+        handle.fail_job("mic unplugged".to_string()).await;
+        second.changed().await.unwrap();
+        assert_eq!(second.borrow().phase, RecordingPhase::Error);
+    }
+
     #[tokio::test]
     async fn test_status_handle_complete_job() {
         let handle = RecordingStatusHandle::default();
@@ -759,4 +1175,150 @@ mod tests {
         assert_eq!(parsed.job_id, "test-uuid");
         assert_eq!(parsed.history_id, 42);
     }
+
+    /// Logs the calls it gets instead of touching an audio device.
+    struct FakeRecorder {
+        calls: Calls,
+    }
+
+    type Calls = Arc<std::sync::Mutex<Vec<&'static str>>>;
+
+    #[async_trait::async_trait(?Send)]
+    impl Recorder for FakeRecorder {
+        async fn start_recording(&self) -> Result<()> {
+            self.calls.lock().unwrap().push("start");
+            Ok(())
+        }
+
+        async fn pause_recording(&self) -> Result<()> {
+            self.calls.lock().unwrap().push("pause");
+            Ok(())
+        }
+
+        async fn resume_recording(&self) -> Result<()> {
+            self.calls.lock().unwrap().push("resume");
+            Ok(())
+        }
+
+        async fn stop_recording(&self, output_path: PathBuf) -> Result<PathBuf> {
+            self.calls.lock().unwrap().push("stop");
+            Ok(output_path)
+        }
+    }
+
+    struct NoTextOutput;
+
+    #[async_trait::async_trait]
+    impl TextOutput for NoTextOutput {
+        async fn copy_to_clipboard(&self, _text: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn inject_text(&self, _text: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn paste_from_clipboard(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A machine mid-recording of `job-1`, plus its recorder's call log.
+    async fn recording_machine() -> (RecordingMachine, Calls) {
+        let calls = Calls::default();
+        let recorder = FakeRecorder {
+            calls: calls.clone(),
+        };
+        let status = RecordingStatusHandle::default();
+        status
+            .start_job("job-1".to_string(), JobOptions::default())
+            .await;
+        let machine = RecordingMachine::new(
+            Arc::new(Mutex::new(recorder)),
+            None,
+            Indicator::new().with_audio_feedback(false),
+            Arc::new(NoTextOutput),
+            BehaviorOptions {
+                auto_paste: false,
+                keep_audio: KeepAudio::Never,
+                index_segments: false,
+                max_history_entries: 0,
+                inject_timestamp: None,
+                vad: None,
+                temp_dir: std::env::temp_dir(),
+            },
+            status,
+            Arc::new(PostProcessingService::new()),
+        );
+        (machine, calls)
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_keep_the_job() {
+        let (machine, calls) = recording_machine().await;
+
+        let paused = machine.pause().await.unwrap();
+        assert_eq!(paused.phase, RecordingPhase::Paused);
+        assert_eq!(paused.job_id.as_deref(), Some("job-1"));
+        let status = machine.status.get().await;
+        assert_eq!(status.phase, RecordingPhase::Paused);
+        assert_eq!(status.current_job_id.as_deref(), Some("job-1"));
+        assert!(status.current_job_options.is_some());
+
+        let resumed = machine.resume().await.unwrap();
+        assert_eq!(resumed.phase, RecordingPhase::Recording);
+        assert_eq!(resumed.job_id.as_deref(), Some("job-1"));
+        assert_eq!(*calls.lock().unwrap(), ["pause", "resume"]);
+    }
+
+    #[tokio::test]
+    async fn test_toggle_while_paused_resumes() {
+        let (machine, calls) = recording_machine().await;
+        machine.pause().await.unwrap();
+
+        let result = machine.toggle(None).await.unwrap();
+        assert_eq!(result.phase, RecordingPhase::Recording);
+        assert_eq!(result.job_id.as_deref(), Some("job-1"));
+        assert_eq!(machine.status.get().await.phase, RecordingPhase::Recording);
+        // Resumed, not stopped and restarted.
+        assert_eq!(*calls.lock().unwrap(), ["pause", "resume"]);
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_refuse_the_wrong_phase() {
+        let (machine, calls) = recording_machine().await;
+
+        assert!(machine.resume().await.is_err());
+        machine.pause().await.unwrap();
+        assert!(machine.pause().await.is_err());
+
+        machine.status.set_phase(RecordingPhase::Idle, None).await;
+        assert!(machine.pause().await.is_err());
+        assert!(machine.resume().await.is_err());
+        assert_eq!(*calls.lock().unwrap(), ["pause"]);
+    }
+
+    #[tokio::test]
+    async fn test_settle_audio_file_per_mode_and_outcome() {
+        let dir = tempfile::tempdir().unwrap();
+        for (mode, keep_on_success, keep_on_failure) in [
+            (KeepAudio::Always, true, true),
+            (KeepAudio::Never, false, false),
+            (KeepAudio::OnFailure, false, true),
+            (KeepAudio::OnSuccess, true, false),
+        ] {
+            for (succeeded, expect_kept) in [(true, keep_on_success), (false, keep_on_failure)] {
+                let path = dir.path().join(format!("{mode:?}-{succeeded}.wav"));
+                std::fs::write(&path, b"RIFF").unwrap();
+
+                settle_audio_file(&path, mode, succeeded).await;
+                assert_eq!(
+                    path.exists(),
+                    expect_kept,
+                    "{mode:?} after {}",
+                    if succeeded { "success" } else { "failure" }
+                );
+            }
+        }
+    }
 }
@@ -29,20 +29,25 @@ const RESAMPLE_CHUNK_FRAMES: usize = 1024;
 /// averaged across channels. Designed to be cheap enough for the audio
 /// callback — single mutex acquire, no allocation past `reserve()`.
 pub fn push_mono_f32(data: &[f32], channels: usize, dst: &Arc<Mutex<Vec<f32>>>) {
+    let Ok(mut buf) = dst.lock() else { return };
+    extend_mono_f32(data, channels, &mut *buf);
+}
+
+/// [`push_mono_f32`] for a destination the caller has already locked, e.g.
+/// a buffer that routes samples between a recording and a prebuffer ring.
+pub fn extend_mono_f32(data: &[f32], channels: usize, dst: &mut impl Extend<f32>) {
     if channels == 0 {
         return;
     }
-    let Ok(mut buf) = dst.lock() else { return };
     if channels == 1 {
-        buf.extend_from_slice(data);
+        dst.extend(data.iter().copied());
         return;
     }
     let inv = 1.0 / channels as f32;
-    buf.reserve(data.len() / channels);
-    for frame in data.chunks_exact(channels) {
-        let sum: f32 = frame.iter().sum();
-        buf.push(sum * inv);
-    }
+    dst.extend(
+        data.chunks_exact(channels)
+            .map(|frame| frame.iter().sum::<f32>() * inv),
+    );
 }
 
 /// Resample a mono buffer from `from_rate` Hz to `to_rate` Hz using rubato's
@@ -0,0 +1,204 @@
+//! Silence trimming for dictation (`[audio].vad_threshold`).
+//!
+//! A plain RMS energy gate, not a speech model: the recording is cut into
+//! short frames, frames at or above the threshold count as speech, and
+//! everything before the first and after the last speech frame is dropped
+//! (less a little padding so soft word onsets survive). Silence *between*
+//! speech is never touched. A clip with too little speech overall is
+//! reported as silent so it isn't uploaded at all.
+
+use anyhow::{Context, Result};
+use hound::WavReader;
+use std::ops::Range;
+use std::path::Path;
+
+use super::wav::{read_samples, write_samples};
+
+/// Length of one analysis frame.
+const FRAME_MS: u32 = 20;
+/// Audio kept on either side of the detected speech.
+const PADDING_MS: u32 = 200;
+
+/// When a frame counts as speech, and how much speech a clip needs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VadSettings {
+    /// RMS level in `[0.0, 1.0]` at or above which a frame is speech.
+    pub threshold: f32,
+    /// Clips with less speech than this in total are treated as silent.
+    pub min_speech_ms: u32,
+}
+
+/// What [`trim_silence`] did to a recording.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TrimOutcome {
+    /// Nothing above the threshold for long enough; don't transcribe it.
+    Silent,
+    /// Leading/trailing silence was cut, keeping `kept` of `original` samples.
+    Trimmed { kept: usize, original: usize },
+    /// Speech runs (close to) end to end; the file is unchanged.
+    Unchanged,
+}
+
+/// The sample range to keep: from the first to the last speech frame, padded,
+/// or `None` if the clip holds less than `min_speech_ms` of speech.
+pub fn speech_bounds(
+    samples: &[f32],
+    sample_rate: u32,
+    settings: &VadSettings,
+) -> Option<Range<usize>> {
+    let frame_len = (sample_rate * FRAME_MS / 1000).max(1) as usize;
+    let mut first = None;
+    let mut last = 0;
+    let mut speech_frames = 0u32;
+
+    for (index, frame) in samples.chunks(frame_len).enumerate() {
+        if rms(frame) >= settings.threshold {
+            first.get_or_insert(index);
+            last = index;
+            speech_frames += 1;
+        }
+    }
+
+    let first = first?;
+    if speech_frames * FRAME_MS < settings.min_speech_ms {
+        return None;
+    }
+
+    let padding = (sample_rate * PADDING_MS / 1000) as usize;
+    let start = (first * frame_len).saturating_sub(padding);
+    let end = ((last + 1) * frame_len + padding).min(samples.len());
+    Some(start..end)
+}
+
+/// Trim leading and trailing silence from the mono WAV at `path` in place.
+pub fn trim_silence(path: &Path, settings: &VadSettings) -> Result<TrimOutcome> {
+    let mut reader =
+        WavReader::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let spec = reader.spec();
+    if spec.channels != 1 {
+        return Ok(TrimOutcome::Unchanged);
+    }
+    let samples = read_samples(&mut reader)?;
+    drop(reader);
+
+    let Some(range) = speech_bounds(&samples, spec.sample_rate, settings) else {
+        return Ok(TrimOutcome::Silent);
+    };
+    if range.len() == samples.len() {
+        return Ok(TrimOutcome::Unchanged);
+    }
+
+    write_samples(path, spec, &samples[range.clone()])?;
+    Ok(TrimOutcome::Trimmed {
+        kept: range.len(),
+        original: samples.len(),
+    })
+}
+
+fn rms(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = frame.iter().map(|s| s * s).sum();
+    (sum / frame.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::wav::mono_spec;
+
+    const RATE: u32 = 16_000;
+
+    fn settings() -> VadSettings {
+        VadSettings {
+            threshold: 0.02,
+            min_speech_ms: 200,
+        }
+    }
+
+    fn silence(ms: u32) -> Vec<f32> {
+        vec![0.001; (RATE * ms / 1000) as usize]
+    }
+
+    /// A 220 Hz tone at 0.3 amplitude (RMS ≈ 0.21), standing in for speech.
+    fn tone(ms: u32) -> Vec<f32> {
+        (0..RATE * ms / 1000)
+            .map(|i| 0.3 * (i as f32 * 220.0 * std::f32::consts::TAU / RATE as f32).sin())
+            .collect()
+    }
+
+    fn samples_ms(ms: u32) -> usize {
+        (RATE * ms / 1000) as usize
+    }
+
+    #[test]
+    fn silence_only_is_silent() {
+        assert_eq!(speech_bounds(&silence(3000), RATE, &settings()), None);
+        assert_eq!(speech_bounds(&[], RATE, &settings()), None);
+    }
+
+    #[test]
+    fn blips_shorter_than_min_speech_are_silent() {
+        let clip = [silence(1000), tone(60), silence(1000)].concat();
+        assert_eq!(speech_bounds(&clip, RATE, &settings()), None);
+    }
+
+    #[test]
+    fn trims_leading_and_trailing_silence_with_padding() {
+        let clip = [silence(1000), tone(500), silence(2000)].concat();
+        let range = speech_bounds(&clip, RATE, &settings()).unwrap();
+        assert_eq!(range.start, samples_ms(1000 - PADDING_MS));
+        assert_eq!(range.end, samples_ms(1500 + PADDING_MS));
+    }
+
+    #[test]
+    fn pause_between_speech_is_preserved() {
+        let clip = [
+            silence(800),
+            tone(400),
+            silence(1500),
+            tone(400),
+            silence(800),
+        ]
+        .concat();
+        let range = speech_bounds(&clip, RATE, &settings()).unwrap();
+        assert_eq!(range.start, samples_ms(800 - PADDING_MS));
+        assert_eq!(range.end, samples_ms(3100 + PADDING_MS));
+        // The pause between the two bursts stays in.
+        assert!(range.contains(&samples_ms(1900)));
+    }
+
+    #[test]
+    fn speech_at_the_edges_keeps_everything() {
+        let clip = [tone(300), silence(500), tone(300)].concat();
+        assert_eq!(speech_bounds(&clip, RATE, &settings()), Some(0..clip.len()));
+    }
+
+    #[test]
+    fn trim_silence_rewrites_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clip.wav");
+        let clip = [silence(1000), tone(500), silence(1000)].concat();
+        write_samples(&path, mono_spec(RATE, 32), &clip).unwrap();
+
+        let outcome = trim_silence(&path, &settings()).unwrap();
+        let kept = samples_ms(500 + 2 * PADDING_MS);
+        assert_eq!(
+            outcome,
+            TrimOutcome::Trimmed {
+                kept,
+                original: clip.len()
+            }
+        );
+        let mut reader = WavReader::open(&path).unwrap();
+        assert_eq!(read_samples(&mut reader).unwrap().len(), kept);
+
+        let silent = dir.path().join("silent.wav");
+        write_samples(&silent, mono_spec(RATE, 16), &silence(2000)).unwrap();
+        assert_eq!(
+            trim_silence(&silent, &settings()).unwrap(),
+            TrimOutcome::Silent
+        );
+    }
+}
@@ -0,0 +1,160 @@
+//! WAV encoding shared by the dictation and meeting writers.
+//!
+//! Capture is always mono `f32`; `[audio].bits_per_sample` picks whether it's
+//! stored as 32-bit float or 16-bit PCM. At 16 kHz speech, 16-bit halves the
+//! file with no audible loss, which matters for recordings that are kept.
+
+use anyhow::{Context, Result};
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Mono spec for `bits_per_sample`: 16 is integer PCM, anything else 32-bit
+/// float.
+pub fn mono_spec(sample_rate: u32, bits_per_sample: u16) -> WavSpec {
+    if bits_per_sample == 16 {
+        WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: SampleFormat::Int,
+        }
+    } else {
+        WavSpec {
+            channels: 1,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        }
+    }
+}
+
+/// Convert a float sample in `[-1.0, 1.0]` to 16-bit PCM, clamping anything
+/// outside the range instead of letting it wrap.
+pub fn f32_to_i16(sample: f32) -> i16 {
+    let clamped = if sample.is_nan() {
+        0.0
+    } else {
+        sample.clamp(-1.0, 1.0)
+    };
+    (clamped * i16::MAX as f32).round() as i16
+}
+
+/// Write `samples` to `path` in the format `spec` describes.
+pub fn write_samples(path: &Path, spec: WavSpec, samples: &[f32]) -> Result<()> {
+    let mut writer = WavWriter::create(path, spec)
+        .with_context(|| format!("Failed to create WAV file: {path:?}"))?;
+    match spec.sample_format {
+        SampleFormat::Int => {
+            for &sample in samples {
+                writer.write_sample(f32_to_i16(sample))?;
+            }
+        }
+        SampleFormat::Float => {
+            for &sample in samples {
+                writer.write_sample(sample)?;
+            }
+        }
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+/// Path for a temporary WAV named `{stem}.wav` in `temp_dir`
+/// (`[audio].temp_dir`), creating the directory if it doesn't exist yet.
+/// Every temp WAV the daemon records goes through here, so the setting
+/// applies to all of them.
+pub fn temp_wav_path(temp_dir: &Path, stem: &str) -> Result<PathBuf> {
+    temp_file_path(temp_dir, stem, "wav")
+}
+
+/// [`temp_wav_path`] for audio that keeps its own extension, such as an
+/// upload staged for transcription.
+pub fn temp_file_path(temp_dir: &Path, stem: &str, ext: &str) -> Result<PathBuf> {
+    std::fs::create_dir_all(temp_dir)
+        .with_context(|| format!("Failed to create temp directory: {temp_dir:?}"))?;
+    Ok(temp_dir.join(format!("{stem}.{ext}")))
+}
+
+/// Read every sample as `f32` in `[-1.0, 1.0]`, whichever format the file
+/// was written in.
+pub fn read_samples<R: Read>(reader: &mut WavReader<R>) -> Result<Vec<f32>> {
+    let spec = reader.spec();
+    let samples = match spec.sample_format {
+        SampleFormat::Float => reader.samples::<f32>().collect::<Result<Vec<_>, _>>(),
+        SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / scale))
+                .collect::<Result<Vec<_>, _>>()
+        }
+    };
+    samples.context("Failed to read WAV samples")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn f32_to_i16_clamps_out_of_range_samples() {
+        assert_eq!(f32_to_i16(0.0), 0);
+        assert_eq!(f32_to_i16(1.0), i16::MAX);
+        assert_eq!(f32_to_i16(-1.0), -i16::MAX);
+        assert_eq!(f32_to_i16(1.7), i16::MAX);
+        assert_eq!(f32_to_i16(-3.0), -i16::MAX);
+        assert_eq!(f32_to_i16(f32::NAN), 0);
+        assert_eq!(f32_to_i16(0.5), 16384);
+    }
+
+    #[test]
+    fn spec_follows_bits_per_sample() {
+        let pcm = mono_spec(16_000, 16);
+        assert_eq!(pcm.bits_per_sample, 16);
+        assert_eq!(pcm.sample_format, SampleFormat::Int);
+
+        let float = mono_spec(16_000, 32);
+        assert_eq!(float.bits_per_sample, 32);
+        assert_eq!(float.sample_format, SampleFormat::Float);
+        assert_eq!(mono_spec(16_000, 24), float);
+    }
+
+    #[test]
+    fn temp_wav_path_uses_and_creates_the_configured_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let temp_dir = dir.path().join("nested").join("audetic-tmp");
+
+        let path = temp_wav_path(&temp_dir, "audetic_1700000000").unwrap();
+
+        assert_eq!(path, temp_dir.join("audetic_1700000000.wav"));
+        assert!(temp_dir.is_dir());
+        assert_eq!(
+            temp_file_path(&temp_dir, "upload", "mp3").unwrap(),
+            temp_dir.join("upload.mp3")
+        );
+    }
+
+    #[test]
+    fn sixteen_bit_round_trip_halves_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let samples: Vec<f32> = (0..1600).map(|i| (i as f32 / 1600.0) - 0.5).collect();
+
+        let pcm_path = dir.path().join("pcm.wav");
+        let float_path = dir.path().join("float.wav");
+        write_samples(&pcm_path, mono_spec(16_000, 16), &samples).unwrap();
+        write_samples(&float_path, mono_spec(16_000, 32), &samples).unwrap();
+
+        let mut reader = WavReader::open(&pcm_path).unwrap();
+        assert_eq!(reader.spec(), mono_spec(16_000, 16));
+        let decoded = read_samples(&mut reader).unwrap();
+        assert_eq!(decoded.len(), samples.len());
+        for (a, b) in decoded.iter().zip(&samples) {
+            assert!((a - b).abs() < 1e-4);
+        }
+
+        let pcm_len = std::fs::metadata(&pcm_path).unwrap().len();
+        let float_len = std::fs::metadata(&float_path).unwrap().len();
+        assert!(pcm_len < float_len / 2 + 64);
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use rusqlite::Connection;
 use std::time::Duration;
+use tracing::{debug, warn};
 
 pub fn init_db() -> Result<Connection> {
     let db_path = crate::global::db_file()?;
@@ -23,19 +24,96 @@ pub fn init_db() -> Result<Connection> {
     Ok(conn)
 }
 
+/// One schema change. [`MIGRATIONS`] are applied in order, each in its own
+/// transaction, and `PRAGMA user_version` records how many have run.
+struct Migration {
+    description: &'static str,
+    apply: fn(&Connection) -> Result<()>,
+}
+
+/// Every schema change, oldest first. Append new steps; never edit or reorder
+/// ones that have shipped, since a database's `user_version` is its position
+/// in this list.
+const MIGRATIONS: &[Migration] = &[Migration {
+    description: "baseline schema",
+    apply: baseline_schema,
+}];
+
+/// The `user_version` a fully migrated database has.
+pub const SCHEMA_VERSION: i64 = MIGRATIONS.len() as i64;
+
+/// Bring the schema up to [`SCHEMA_VERSION`], running only the migrations
+/// this database hasn't had. Safe on every startup. A database written by a
+/// newer Audetic (after a downgrade) is left as is.
 pub fn migrate(conn: &Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+    if current > SCHEMA_VERSION {
+        warn!(
+            "Database schema version {} is newer than this build supports ({}); \
+             leaving it unchanged",
+            current, SCHEMA_VERSION
+        );
+    }
+
+    for (version, migration) in (1..).zip(MIGRATIONS).skip(current.max(0) as usize) {
+        debug!(
+            "Applying database migration {}: {}",
+            version, migration.description
+        );
+        let tx = conn
+            .unchecked_transaction()
+            .context("Failed to start migration transaction")?;
+        (migration.apply)(&tx)
+            .with_context(|| format!("Migration {version} ({}) failed", migration.description))?;
+        tx.pragma_update(None, "user_version", version)
+            .context("Failed to record schema version")?;
+        tx.commit()
+            .with_context(|| format!("Failed to commit migration {version}"))?;
+    }
+
+    // Depends on how the system SQLite was built rather than on the schema
+    // version, so it's checked on every open.
+    create_workflows_fts(conn)
+}
+
+/// The schema version recorded in the database; 0 for a new database or
+/// one created before versioning.
+pub fn schema_version(conn: &Connection) -> Result<i64> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+        .context("Failed to read schema version")
+}
+
+/// Version 1: every table, column, and index from before migrations were
+/// versioned. Databases of any earlier vintage sit at version 0, so each
+/// statement tolerates the object already existing and this step brings
+/// them all to the same shape.
+fn baseline_schema(conn: &Connection) -> Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS workflows (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             workflow_type TEXT NOT NULL,
             text TEXT NOT NULL,
             audio_path TEXT NOT NULL,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            raw_text TEXT,
+            tags TEXT,
+            segments TEXT
         )",
         [],
     )
     .context("Failed to create workflows table")?;
 
+    // Pre-normalization provider output, used by `history renormalize`.
+    // Rows from before this column have NULL and are skipped there.
+    add_column_if_missing(conn, "workflows", "raw_text", "TEXT")?;
+
+    // User-assigned tags as a JSON array of strings; NULL when untagged.
+    add_column_if_missing(conn, "workflows", "tags", "TEXT")?;
+
+    // The transcript's timestamped segments as a JSON array, when the
+    // provider returned them. NULL for older rows and text-only providers.
+    add_column_if_missing(conn, "workflows", "segments", "TEXT")?;
+
     // Create index for faster text searches
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_workflows_created_at ON workflows(created_at DESC)",
@@ -43,6 +121,41 @@ pub fn migrate(conn: &Connection) -> Result<()> {
     )
     .context("Failed to create index on created_at")?;
 
+    // Timestamped segments of a workflow's transcript, one row each, so text
+    // can be searched down to the moment it was said. Only populated when
+    // `[history].index_segments` is on.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS workflow_segments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            workflow_id INTEGER NOT NULL REFERENCES workflows(id) ON DELETE CASCADE,
+            start REAL NOT NULL,
+            end REAL NOT NULL,
+            text TEXT NOT NULL
+        )",
+        [],
+    )
+    .context("Failed to create workflow_segments table")?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_workflow_segments_workflow_id \
+         ON workflow_segments(workflow_id)",
+        [],
+    )
+    .context("Failed to create index on workflow_segments")?;
+
+    // Dictation jobs that failed before reaching `workflows`, so recent
+    // outcomes (`GET /jobs/recent`) show failures next to successes.
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS failed_jobs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            job_id TEXT,
+            error TEXT NOT NULL,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )
+    .context("Failed to create failed_jobs table")?;
+
     // Meetings table
     conn.execute(
         "CREATE TABLE IF NOT EXISTS meetings (
@@ -53,6 +166,7 @@ pub fn migrate(conn: &Connection) -> Result<()> {
             transcript_path TEXT,
             transcript_text TEXT,
             transcript_segments TEXT,
+            transcript_utterances TEXT,
             duration_seconds INTEGER,
             started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
             completed_at TIMESTAMP,
@@ -74,6 +188,10 @@ pub fn migrate(conn: &Connection) -> Result<()> {
     // older rows just have NULL and the UI falls back to plain text.
     add_column_if_missing(conn, "meetings", "transcript_segments", "TEXT")?;
 
+    // Speaker-labelled turns (JSON array of {speaker,start,end,text}) for
+    // meetings transcribed with `[meeting].diarize`. NULL everywhere else.
+    add_column_if_missing(conn, "meetings", "transcript_utterances", "TEXT")?;
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_meetings_started_at ON meetings(started_at DESC)",
         [],
@@ -190,10 +308,65 @@ pub fn migrate(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Full-text index over `workflows.text`, kept in sync by triggers and
+/// searched by `search_workflows`. Porter stemming lets "meeting" find
+/// "meetings". A system SQLite built without FTS5 can't create it; search
+/// then keeps using `LIKE`.
+fn create_workflows_fts(conn: &Connection) -> Result<()> {
+    let existed = fts_enabled(conn)?;
+
+    if let Err(e) = conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS workflows_fts USING fts5(
+            text, content='workflows', content_rowid='id', tokenize='porter unicode61'
+        )",
+        [],
+    ) {
+        debug!("Full-text search unavailable, using LIKE: {}", e);
+        return Ok(());
+    }
+
+    conn.execute_batch(
+        "CREATE TRIGGER IF NOT EXISTS workflows_fts_insert AFTER INSERT ON workflows BEGIN
+            INSERT INTO workflows_fts(rowid, text) VALUES (new.id, new.text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS workflows_fts_delete AFTER DELETE ON workflows BEGIN
+            INSERT INTO workflows_fts(workflows_fts, rowid, text) VALUES ('delete', old.id, old.text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS workflows_fts_update AFTER UPDATE OF text ON workflows BEGIN
+            INSERT INTO workflows_fts(workflows_fts, rowid, text) VALUES ('delete', old.id, old.text);
+            INSERT INTO workflows_fts(rowid, text) VALUES (new.id, new.text);
+        END;",
+    )
+    .context("Failed to create workflows_fts triggers")?;
+
+    // Databases from before the index have rows the triggers never saw.
+    if !existed {
+        conn.execute(
+            "INSERT INTO workflows_fts(workflows_fts) VALUES ('rebuild')",
+            [],
+        )
+        .context("Failed to backfill workflows_fts")?;
+    }
+    Ok(())
+}
+
+/// Whether the `workflows_fts` index exists in this database.
+pub(super) fn fts_enabled(conn: &Connection) -> Result<bool> {
+    let count: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'workflows_fts'",
+            [],
+            |row| row.get(0),
+        )
+        .context("Failed to check for workflows_fts")?;
+    Ok(count > 0)
+}
+
 /// Add `column` to `table` only if it isn't already there. SQLite has no
-/// `ADD COLUMN IF NOT EXISTS`, and there's no versioned-migration system here,
-/// so we inspect `PRAGMA table_info` first and `ALTER` only when missing —
-/// keeping `migrate()` safe to run on every startup against any DB vintage.
+/// `ADD COLUMN IF NOT EXISTS`, so we inspect `PRAGMA table_info` first and
+/// `ALTER` only when missing. The baseline migration needs this because
+/// unversioned databases may or may not have each column; later migrations
+/// know exactly what they're upgrading from.
 fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
     let mut stmt = conn
         .prepare(&format!("PRAGMA table_info({table})"))
@@ -264,6 +264,7 @@ mod tests {
             "/tmp/meeting.txt",
             "we made a decision",
             None,
+            None,
             30,
         )?;
 
@@ -7,6 +7,7 @@ use anyhow::{Context, Result};
 use rusqlite::{params, Connection, OptionalExtension};
 
 use crate::meeting::status::MeetingPhase;
+use crate::transcription::Utterance;
 use audetic_core::jobs_client::Segment;
 
 /// Result of a soft-delete attempt, so the API can answer with the right
@@ -36,6 +37,9 @@ pub struct MeetingRecord {
     /// malformed). Stored as a JSON array in the `transcript_segments` column;
     /// the repository owns that encoding so callers work with typed segments.
     pub transcript_segments: Option<Vec<Segment>>,
+    /// Speaker-labelled turns, or `None` when the meeting was transcribed
+    /// without diarization. Same JSON-column treatment as the segments.
+    pub transcript_utterances: Option<Vec<Utterance>>,
     pub duration_seconds: Option<i64>,
     pub started_at: String,
     pub completed_at: Option<String>,
@@ -108,6 +112,7 @@ impl MeetingRepository {
         transcript_path: &str,
         transcript_text: &str,
         transcript_segments: Option<&[Segment]>,
+        transcript_utterances: Option<&[Utterance]>,
         duration_seconds: i64,
     ) -> Result<()> {
         // Encode segments as the JSON array stored in `transcript_segments`.
@@ -117,15 +122,19 @@ impl MeetingRepository {
         let segments_json = transcript_segments
             .filter(|s| !s.is_empty())
             .and_then(|s| serde_json::to_string(s).ok());
+        let utterances_json = transcript_utterances
+            .filter(|u| !u.is_empty())
+            .and_then(|u| serde_json::to_string(u).ok());
         conn.execute(
             "UPDATE meetings SET status = ?1, transcript_path = ?2, transcript_text = ?3, \
-             transcript_segments = ?4, duration_seconds = ?5, error = NULL, \
-             completed_at = CURRENT_TIMESTAMP WHERE id = ?6",
+             transcript_segments = ?4, transcript_utterances = ?5, duration_seconds = ?6, \
+             error = NULL, completed_at = CURRENT_TIMESTAMP WHERE id = ?7",
             params![
                 MeetingPhase::Completed.as_str(),
                 transcript_path,
                 transcript_text,
                 segments_json,
+                utterances_json,
                 duration_seconds,
                 id,
             ],
@@ -169,6 +178,27 @@ impl MeetingRepository {
         Ok(affected > 0)
     }
 
+    /// Atomically flip a resumable meeting (see
+    /// [`MeetingPhase::RESUMABLE_STATUSES`]) to `compressing` before the
+    /// pipeline is re-entered. Same rationale as [`Self::begin_retry`]: the
+    /// guard and the transition are one statement, so a completed meeting
+    /// can never be resumed and a concurrent DELETE can't hide the row
+    /// mid-resume. Returns false if the row wasn't resumable.
+    pub fn begin_resume(conn: &Connection, id: i64) -> Result<bool> {
+        // Compile-time constants only, same as `soft_delete`.
+        let resumable = MeetingPhase::RESUMABLE_STATUSES.join("', '");
+        let affected = conn
+            .execute(
+                &format!(
+                    "UPDATE meetings SET status = ?1 \
+                     WHERE id = ?2 AND deleted_at IS NULL AND status IN ('{resumable}')"
+                ),
+                params![MeetingPhase::Compressing.as_str(), id],
+            )
+            .context("Failed to mark meeting resume in-flight")?;
+        Ok(affected > 0)
+    }
+
     /// Mark meeting as cancelled with the recorded duration.
     pub fn cancel(conn: &Connection, id: i64, duration_seconds: i64) -> Result<()> {
         conn.execute(
@@ -246,34 +276,13 @@ impl MeetingRepository {
             .prepare(
                 "SELECT id, title, status, audio_path, transcript_path, transcript_text, \
                  duration_seconds, started_at, completed_at, error, created_at, deleted_at, \
-                 transcript_segments \
+                 transcript_segments, transcript_utterances \
                  FROM meetings WHERE id = ?1 AND deleted_at IS NULL",
             )
             .context("Failed to prepare meeting query")?;
 
         let mut rows = stmt
-            .query_map(params![id], |row| {
-                Ok(MeetingRecord {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    status: row.get(2)?,
-                    audio_path: row.get(3)?,
-                    transcript_path: row.get(4)?,
-                    transcript_text: row.get(5)?,
-                    duration_seconds: row.get(6)?,
-                    started_at: row.get(7)?,
-                    completed_at: row.get(8)?,
-                    error: row.get(9)?,
-                    created_at: row.get(10)?,
-                    deleted_at: row.get(11)?,
-                    // Tolerate malformed/legacy JSON by decoding to None, so a
-                    // bad value just drops back to the plain-text transcript.
-                    transcript_segments: row
-                        .get::<_, Option<String>>(12)?
-                        .as_deref()
-                        .and_then(|json| serde_json::from_str(json).ok()),
-                })
-            })
+            .query_map(params![id], meeting_from_row)
             .context("Failed to query meeting")?;
 
         match rows.next() {
@@ -289,35 +298,14 @@ impl MeetingRepository {
             .prepare(
                 "SELECT id, title, status, audio_path, transcript_path, transcript_text, \
                  duration_seconds, started_at, completed_at, error, created_at, deleted_at, \
-                 transcript_segments \
+                 transcript_segments, transcript_utterances \
                  FROM meetings WHERE deleted_at IS NULL \
                  ORDER BY started_at DESC, id DESC LIMIT ?1",
             )
             .context("Failed to prepare meetings list query")?;
 
         let rows = stmt
-            .query_map(params![limit as i64], |row| {
-                Ok(MeetingRecord {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    status: row.get(2)?,
-                    audio_path: row.get(3)?,
-                    transcript_path: row.get(4)?,
-                    transcript_text: row.get(5)?,
-                    duration_seconds: row.get(6)?,
-                    started_at: row.get(7)?,
-                    completed_at: row.get(8)?,
-                    error: row.get(9)?,
-                    created_at: row.get(10)?,
-                    deleted_at: row.get(11)?,
-                    // Tolerate malformed/legacy JSON by decoding to None, so a
-                    // bad value just drops back to the plain-text transcript.
-                    transcript_segments: row
-                        .get::<_, Option<String>>(12)?
-                        .as_deref()
-                        .and_then(|json| serde_json::from_str(json).ok()),
-                })
-            })
+            .query_map(params![limit as i64], meeting_from_row)
             .context("Failed to list meetings")?;
 
         let mut meetings = Vec::new();
@@ -327,6 +315,70 @@ impl MeetingRepository {
 
         Ok(meetings)
     }
+
+    /// Meetings a previous daemon left in a non-terminal state (recording,
+    /// review, compressing, transcribing) that started at least
+    /// `older_than_seconds` ago. Oldest first. Used by startup reconciliation
+    /// to find rows no live process is advancing.
+    pub fn list_stale_in_flight(
+        conn: &Connection,
+        older_than_seconds: u64,
+    ) -> Result<Vec<MeetingRecord>> {
+        // Compile-time constants only, same as `soft_delete`.
+        let terminal = MeetingPhase::TERMINAL_STATUSES.join("', '");
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT id, title, status, audio_path, transcript_path, transcript_text, \
+                 duration_seconds, started_at, completed_at, error, created_at, deleted_at, \
+                 transcript_segments, transcript_utterances \
+                 FROM meetings WHERE deleted_at IS NULL AND status NOT IN ('{terminal}') \
+                 AND started_at <= datetime('now', ?1) \
+                 ORDER BY started_at ASC, id ASC"
+            ))
+            .context("Failed to prepare stale meetings query")?;
+
+        let rows = stmt
+            .query_map(
+                params![format!("-{} seconds", older_than_seconds)],
+                meeting_from_row,
+            )
+            .context("Failed to list stale meetings")?;
+
+        let mut meetings = Vec::new();
+        for row in rows {
+            meetings.push(row?);
+        }
+
+        Ok(meetings)
+    }
+}
+
+/// Map a row selected with the column order shared by every meeting query.
+fn meeting_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<MeetingRecord> {
+    Ok(MeetingRecord {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        status: row.get(2)?,
+        audio_path: row.get(3)?,
+        transcript_path: row.get(4)?,
+        transcript_text: row.get(5)?,
+        duration_seconds: row.get(6)?,
+        started_at: row.get(7)?,
+        completed_at: row.get(8)?,
+        error: row.get(9)?,
+        created_at: row.get(10)?,
+        deleted_at: row.get(11)?,
+        // Tolerate malformed/legacy JSON by decoding to None, so a
+        // bad value just drops back to the plain-text transcript.
+        transcript_segments: row
+            .get::<_, Option<String>>(12)?
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok()),
+        transcript_utterances: row
+            .get::<_, Option<String>>(13)?
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok()),
+    })
 }
 
 #[cfg(test)]
@@ -388,6 +440,7 @@ mod tests {
             "/tmp/test.txt",
             "Hello world transcript",
             None,
+            None,
             3600,
         )
         .unwrap();
@@ -403,6 +456,7 @@ mod tests {
         assert!(meeting.completed_at.is_some());
         // No segments passed → column stays NULL.
         assert!(meeting.transcript_segments.is_none());
+        assert!(meeting.transcript_utterances.is_none());
     }
 
     #[test]
@@ -415,15 +469,25 @@ mod tests {
                 start: 0.0,
                 end: 2.5,
                 text: "Hello".into(),
+                words: None,
             },
             Segment {
                 start: 2.5,
                 end: 5.0,
                 text: "world".into(),
+                words: None,
             },
         ];
-        MeetingRepository::complete(&conn, id, "/tmp/t.txt", "Hello world", Some(&segments), 10)
-            .unwrap();
+        MeetingRepository::complete(
+            &conn,
+            id,
+            "/tmp/t.txt",
+            "Hello world",
+            Some(&segments),
+            None,
+            10,
+        )
+        .unwrap();
 
         // Round-trips through the column as typed segments, no caller-side JSON.
         let got = MeetingRepository::get(&conn, id)
@@ -436,6 +500,40 @@ mod tests {
         assert_eq!(got[1].text, "world");
     }
 
+    #[test]
+    fn test_complete_persists_and_decodes_utterances() {
+        let conn = setup_db();
+        let id = MeetingRepository::insert(&conn, Some("Meeting"), "/tmp/test.wav").unwrap();
+
+        let utterances = vec![
+            Utterance {
+                speaker: "A".into(),
+                start: 0.0,
+                end: 2.5,
+                text: "Shall we start?".into(),
+            },
+            Utterance {
+                speaker: "B".into(),
+                start: 2.5,
+                end: 4.0,
+                text: "Sure.".into(),
+            },
+        ];
+        MeetingRepository::complete(
+            &conn,
+            id,
+            "/tmp/t.txt",
+            "Shall we start? Sure.",
+            None,
+            Some(&utterances),
+            10,
+        )
+        .unwrap();
+
+        let meeting = MeetingRepository::get(&conn, id).unwrap().unwrap();
+        assert_eq!(meeting.transcript_utterances, Some(utterances));
+    }
+
     #[test]
     fn test_complete_empty_segments_stored_as_none() {
         let conn = setup_db();
@@ -443,7 +541,7 @@ mod tests {
 
         // An empty slice collapses to NULL so the UI falls back to plain text,
         // rather than storing a useless "[]".
-        MeetingRepository::complete(&conn, id, "/tmp/t.txt", "txt", Some(&[]), 10).unwrap();
+        MeetingRepository::complete(&conn, id, "/tmp/t.txt", "txt", Some(&[]), None, 10).unwrap();
 
         let meeting = MeetingRepository::get(&conn, id).unwrap().unwrap();
         assert!(meeting.transcript_segments.is_none());
@@ -502,7 +600,7 @@ mod tests {
     /// tests move it to `completed` first.
     fn insert_completed(conn: &Connection, title: &str, path: &str) -> i64 {
         let id = MeetingRepository::insert(conn, Some(title), path).unwrap();
-        MeetingRepository::complete(conn, id, "/tmp/t.txt", "transcript", None, 10).unwrap();
+        MeetingRepository::complete(conn, id, "/tmp/t.txt", "transcript", None, None, 10).unwrap();
         id
     }
 
@@ -596,6 +694,37 @@ mod tests {
         assert!(!MeetingRepository::begin_retry(&conn, id).unwrap());
     }
 
+    #[test]
+    fn test_begin_resume_accepts_failed_and_stranded_meetings() {
+        let conn = setup_db();
+        let failed = MeetingRepository::insert(&conn, Some("Failed"), "/tmp/a.wav").unwrap();
+        MeetingRepository::fail(&conn, failed, "boom", 10).unwrap();
+        let stranded = MeetingRepository::insert(&conn, Some("Crash"), "/tmp/b.wav").unwrap();
+        MeetingRepository::update_status(&conn, stranded, MeetingPhase::Transcribing).unwrap();
+
+        for id in [failed, stranded] {
+            assert!(MeetingRepository::begin_resume(&conn, id).unwrap());
+            assert_eq!(
+                MeetingRepository::get(&conn, id).unwrap().unwrap().status,
+                "compressing"
+            );
+        }
+    }
+
+    #[test]
+    fn test_begin_resume_rejects_completed_and_recording() {
+        let conn = setup_db();
+        let done = insert_completed(&conn, "Done", "/tmp/done.wav");
+        let live = MeetingRepository::insert(&conn, Some("Live"), "/tmp/live.wav").unwrap();
+
+        assert!(!MeetingRepository::begin_resume(&conn, done).unwrap());
+        assert!(!MeetingRepository::begin_resume(&conn, live).unwrap());
+        assert_eq!(
+            MeetingRepository::get(&conn, done).unwrap().unwrap().status,
+            "completed"
+        );
+    }
+
     #[test]
     fn test_begin_retry_blocks_delete_window() {
         // Reproduces the race the guard closes: once a retry is accepted, the
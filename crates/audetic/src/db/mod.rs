@@ -9,8 +9,14 @@ mod schemas;
 mod tests;
 
 // Re-export public API
-pub use init::{init_db, migrate};
+pub use init::{init_db, migrate, schema_version, SCHEMA_VERSION};
 pub use operations::{
-    count_workflows, get_recent_workflows, insert_workflow, prune_old_workflows, search_workflows,
+    count_workflows, delete_workflow, for_each_workflow, get_recent_jobs, get_recent_workflows,
+    get_workflow_by_id, get_workflows_with_raw_text, insert_failed_job, insert_segments,
+    insert_workflow, prune_before, prune_old_workflows, search_segments, search_workflows,
+    set_workflow_tags, update_workflow_text, update_workflow_transcript, workflow_stats,
+};
+pub use schemas::{
+    DayCount, HistoryStats, RecentJob, RecentJobStatus, SegmentMatch, VoiceToTextData, Workflow,
+    WorkflowData, WorkflowType,
 };
-pub use schemas::{VoiceToTextData, Workflow, WorkflowData, WorkflowType};
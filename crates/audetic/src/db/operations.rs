@@ -1,51 +1,103 @@
 use anyhow::{Context, Result};
-use rusqlite::Connection;
+use audetic_core::jobs_client::Segment;
+use rusqlite::{Connection, OptionalExtension};
 
-use super::schemas::{VoiceToTextData, Workflow, WorkflowData, WorkflowType};
+use super::init::fts_enabled;
+use super::schemas::{
+    DayCount, HistoryStats, RecentJob, RecentJobStatus, SegmentMatch, VoiceToTextData, Workflow,
+    WorkflowData, WorkflowType,
+};
 
 pub fn insert_workflow(conn: &Connection, workflow: &Workflow) -> Result<i64> {
     let (workflow_type_str, _json_data) = workflow.to_row()?;
 
-    // Extract text and audio_path from the workflow data
-    let (text, audio_path) = match &workflow.data {
-        WorkflowData::VoiceToText(data) => (&data.text, &data.audio_path),
+    // Extract the column values from the workflow data
+    let (text, audio_path, raw_text, segments) = match &workflow.data {
+        WorkflowData::VoiceToText(data) => (
+            &data.text,
+            &data.audio_path,
+            &data.raw_text,
+            segments_to_column(data.segments.as_deref())?,
+        ),
     };
 
     conn.execute(
-        "INSERT INTO workflows (workflow_type, text, audio_path) VALUES (?1, ?2, ?3)",
-        rusqlite::params![workflow_type_str, text, audio_path],
+        "INSERT INTO workflows (workflow_type, text, audio_path, raw_text, tags, segments) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        rusqlite::params![workflow_type_str, text, audio_path, raw_text, tags_to_column(&workflow.tags)?, segments],
     )
     .context("Failed to insert workflow")?;
 
     Ok(conn.last_insert_rowid())
 }
 
+/// `tags` column value: a JSON array, or NULL for no tags.
+fn tags_to_column(tags: &[String]) -> Result<Option<String>> {
+    if tags.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        serde_json::to_string(tags).context("Failed to serialize tags")?,
+    ))
+}
+
+/// `segments` column value: a JSON array, or NULL when there are none.
+fn segments_to_column(segments: Option<&[Segment]>) -> Result<Option<String>> {
+    match segments {
+        Some(segments) if !segments.is_empty() => Ok(Some(
+            serde_json::to_string(segments).context("Failed to serialize segments")?,
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Column order shared by every workflow `SELECT`.
+fn workflow_from_row(row: &rusqlite::Row) -> rusqlite::Result<Workflow> {
+    let id: i64 = row.get(0)?;
+    let workflow_type: String = row.get(1)?;
+    let text: String = row.get(2)?;
+    let audio_path: String = row.get(3)?;
+    let created_at: String = row.get(4)?;
+    let raw_text: Option<String> = row.get(5)?;
+    let tags: Option<String> = row.get(6)?;
+    let tags = match tags {
+        Some(json) => serde_json::from_str(&json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(6, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        None => Vec::new(),
+    };
+    // Malformed JSON decodes to None, like a row without segments.
+    let segments = row
+        .get::<_, Option<String>>(7)?
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok());
+
+    // Reconstruct the WorkflowData from the database fields
+    let data = WorkflowData::VoiceToText(VoiceToTextData {
+        text,
+        audio_path,
+        raw_text,
+        segments,
+    });
+
+    let workflow_type_enum =
+        WorkflowType::parse(&workflow_type).map_err(|_| rusqlite::Error::InvalidQuery)?;
+
+    Ok(Workflow {
+        id: Some(id),
+        workflow_type: workflow_type_enum,
+        data,
+        created_at: Some(created_at),
+        tags,
+    })
+}
+
 pub fn get_recent_workflows(conn: &Connection, limit: usize) -> Result<Vec<Workflow>> {
     let mut stmt = conn
-        .prepare("SELECT id, workflow_type, text, audio_path, created_at FROM workflows ORDER BY created_at DESC LIMIT ?1")
+        .prepare("SELECT id, workflow_type, text, audio_path, created_at, raw_text, tags, segments FROM workflows ORDER BY created_at DESC LIMIT ?1")
         .context("Failed to prepare query")?;
 
     let workflows = stmt
-        .query_map([limit], |row| {
-            let id: i64 = row.get(0)?;
-            let workflow_type: String = row.get(1)?;
-            let text: String = row.get(2)?;
-            let audio_path: String = row.get(3)?;
-            let created_at: String = row.get(4)?;
-
-            // Reconstruct the WorkflowData from the database fields
-            let data = WorkflowData::VoiceToText(VoiceToTextData { text, audio_path });
-
-            let workflow_type_enum =
-                WorkflowType::parse(&workflow_type).map_err(|_| rusqlite::Error::InvalidQuery)?;
-
-            Ok(Workflow {
-                id: Some(id),
-                workflow_type: workflow_type_enum,
-                data,
-                created_at: Some(created_at),
-            })
-        })
+        .query_map([limit], workflow_from_row)
         .context("Failed to query workflows")?
         .collect::<std::result::Result<Vec<_>, _>>()
         .context("Failed to map workflows")?;
@@ -53,6 +105,17 @@ pub fn get_recent_workflows(conn: &Connection, limit: usize) -> Result<Vec<Workf
     Ok(workflows)
 }
 
+/// The workflow with `id`, or `None` if there isn't one.
+pub fn get_workflow_by_id(conn: &Connection, id: i64) -> Result<Option<Workflow>> {
+    conn.query_row(
+        "SELECT id, workflow_type, text, audio_path, created_at, raw_text, tags, segments FROM workflows WHERE id = ?1",
+        [id],
+        workflow_from_row,
+    )
+    .optional()
+    .context("Failed to get workflow")
+}
+
 pub fn count_workflows(conn: &Connection) -> Result<i64> {
     let count: i64 = conn
         .query_row("SELECT COUNT(*) FROM workflows", [], |row| row.get(0))
@@ -61,6 +124,88 @@ pub fn count_workflows(conn: &Connection) -> Result<i64> {
     Ok(count)
 }
 
+/// Each workflow's id, day, word count, and character count, for the
+/// aggregates in [`workflow_stats`]. Newlines and tabs count as spaces; runs
+/// of spaces are squeezed a few times over so double spaces don't add words.
+const WORKFLOW_SIZES: &str = "WITH spaced AS (
+        SELECT id, created_at, length(text) AS characters,
+            trim(replace(replace(replace(text, char(13), ' '), char(10), ' '), char(9), ' ')) AS t
+        FROM workflows
+    ), squeezed AS (
+        SELECT id, created_at, characters,
+            replace(replace(replace(replace(t, '  ', ' '), '  ', ' '), '  ', ' '), '  ', ' ') AS t
+        FROM spaced
+    ), sizes AS (
+        SELECT id, created_at, characters,
+            CASE WHEN t = '' THEN 0 ELSE length(t) - length(replace(t, ' ', '')) + 1 END AS words
+        FROM squeezed
+    )";
+
+/// Totals, averages, and per-day counts over every workflow.
+pub fn workflow_stats(conn: &Connection) -> Result<HistoryStats> {
+    let (total, total_words, total_characters, average_words, average_characters, this_week) = conn
+        .query_row(
+            &format!(
+                "{WORKFLOW_SIZES}
+                SELECT COUNT(*), COALESCE(SUM(words), 0), COALESCE(SUM(characters), 0),
+                    COALESCE(AVG(words), 0.0), COALESCE(AVG(characters), 0.0),
+                    COUNT(CASE WHEN created_at >= datetime('now', '-7 days') THEN 1 END)
+                FROM sizes"
+            ),
+            [],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            },
+        )
+        .context("Failed to compute history totals")?;
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "{WORKFLOW_SIZES}
+            SELECT date(created_at) AS day, COUNT(*), SUM(words) FROM sizes
+            GROUP BY day ORDER BY day DESC"
+        ))
+        .context("Failed to prepare per-day counts")?;
+    let per_day = stmt
+        .query_map([], |row| {
+            Ok(DayCount {
+                date: row.get(0)?,
+                count: row.get(1)?,
+                words: row.get(2)?,
+            })
+        })
+        .context("Failed to count transcriptions per day")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to map per-day counts")?;
+
+    // Newest first, so the first of the busiest days is the latest.
+    let most_active_day = per_day
+        .iter()
+        .fold(None::<&DayCount>, |best, day| match best {
+            Some(best) if best.count >= day.count => Some(best),
+            _ => Some(day),
+        })
+        .cloned();
+
+    Ok(HistoryStats {
+        total,
+        total_words,
+        total_characters,
+        average_words,
+        average_characters,
+        this_week,
+        most_active_day,
+        per_day,
+    })
+}
+
 pub fn prune_old_workflows(conn: &Connection, max_count: i64) -> Result<usize> {
     let count = count_workflows(conn)?;
 
@@ -78,37 +223,90 @@ pub fn prune_old_workflows(conn: &Connection, max_count: i64) -> Result<usize> {
             [to_delete],
         )
         .context("Failed to prune old workflows")?;
+    delete_orphaned_segments(conn)?;
+
+    Ok(deleted)
+}
+
+/// Delete every workflow created before `date` (`YYYY-MM-DD`, or a full
+/// `YYYY-MM-DD HH:MM:SS` timestamp). Returns the number deleted.
+pub fn prune_before(conn: &Connection, date: &str) -> Result<usize> {
+    let deleted = conn
+        .execute("DELETE FROM workflows WHERE created_at < ?1", [date])
+        .context("Failed to prune workflows by date")?;
+    delete_orphaned_segments(conn)?;
 
     Ok(deleted)
 }
 
+/// SQLite leaves foreign keys unenforced unless asked, so the cascade on
+/// `workflow_segments` doesn't fire when workflows are pruned; drop the
+/// orphans by hand.
+fn delete_orphaned_segments(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "DELETE FROM workflow_segments WHERE workflow_id NOT IN (SELECT id FROM workflows)",
+        [],
+    )
+    .context("Failed to prune orphaned workflow segments")?;
+    Ok(())
+}
+
 pub fn search_workflows(
     conn: &Connection,
     query: Option<&str>,
     date_from: Option<&str>,
     date_to: Option<&str>,
+    tag: Option<&str>,
     limit: usize,
 ) -> Result<Vec<Workflow>> {
-    let mut sql = "SELECT id, workflow_type, text, audio_path, created_at FROM workflows WHERE 1=1"
-        .to_string();
+    // Term queries go through the full-text index when there is one,
+    // ranked by relevance; anything else is a substring match by date.
+    let fts_match = match query {
+        Some(q) if fts_enabled(conn)? => fts_match_expression(q),
+        _ => None,
+    };
+
+    let mut sql =
+        "SELECT w.id, w.workflow_type, w.text, w.audio_path, w.created_at, w.raw_text, w.tags, w.segments FROM workflows w"
+            .to_string();
     let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
-    if let Some(q) = query {
-        sql.push_str(" AND text LIKE ?");
-        params.push(Box::new(format!("%{}%", q)));
+    if let Some(expr) = &fts_match {
+        sql.push_str(
+            " JOIN workflows_fts ON workflows_fts.rowid = w.id WHERE workflows_fts MATCH ?",
+        );
+        params.push(Box::new(expr.clone()));
+    } else {
+        sql.push_str(" WHERE 1=1");
+        if let Some(q) = query {
+            sql.push_str(" AND w.text LIKE ?");
+            params.push(Box::new(format!("%{}%", q)));
+        }
     }
 
     if let Some(from) = date_from {
-        sql.push_str(" AND created_at >= ?");
+        sql.push_str(" AND w.created_at >= ?");
         params.push(Box::new(from.to_string()));
     }
 
     if let Some(to) = date_to {
-        sql.push_str(" AND created_at <= ?");
+        sql.push_str(" AND w.created_at <= ?");
         params.push(Box::new(to.to_string()));
     }
 
-    sql.push_str(" ORDER BY created_at DESC LIMIT ?");
+    if let Some(tag) = tag {
+        // Tags are stored as a JSON array, so the JSON-quoted tag only
+        // matches a whole element. `instr` keeps it exact and free of LIKE
+        // wildcards, and needs no JSON1 support from the system SQLite.
+        sql.push_str(" AND instr(w.tags, ?) > 0");
+        params.push(Box::new(serde_json::to_string(tag)?));
+    }
+
+    if fts_match.is_some() {
+        sql.push_str(" ORDER BY bm25(workflows_fts), w.created_at DESC LIMIT ?");
+    } else {
+        sql.push_str(" ORDER BY w.created_at DESC LIMIT ?");
+    }
     params.push(Box::new(limit));
 
     let mut stmt = conn
@@ -118,28 +316,253 @@ pub fn search_workflows(
     let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
 
     let workflows = stmt
-        .query_map(param_refs.as_slice(), |row| {
-            let id: i64 = row.get(0)?;
-            let workflow_type: String = row.get(1)?;
-            let text: String = row.get(2)?;
-            let audio_path: String = row.get(3)?;
-            let created_at: String = row.get(4)?;
-
-            let data = WorkflowData::VoiceToText(VoiceToTextData { text, audio_path });
-
-            let workflow_type_enum =
-                WorkflowType::parse(&workflow_type).map_err(|_| rusqlite::Error::InvalidQuery)?;
-
-            Ok(Workflow {
-                id: Some(id),
-                workflow_type: workflow_type_enum,
-                data,
-                created_at: Some(created_at),
-            })
-        })
+        .query_map(param_refs.as_slice(), workflow_from_row)
         .context("Failed to execute search query")?
         .collect::<std::result::Result<Vec<_>, _>>()
         .context("Failed to map search results")?;
 
     Ok(workflows)
 }
+
+/// Turn a user's search text into an FTS5 `MATCH` expression: `"quoted
+/// text"` stays an exact phrase, every other word must appear somewhere as
+/// the start of a word (`"transcr"*`), the way the `LIKE` fallback matches
+/// partial words. Each part is quoted so punctuation and FTS operators are
+/// taken literally. `None` when there's nothing to search for (e.g. only
+/// punctuation).
+fn fts_match_expression(query: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    for (i, part) in query.split('"').enumerate() {
+        // Odd-numbered parts sat between quotes.
+        let is_phrase = i % 2 == 1;
+        let pieces: Vec<&str> = if is_phrase {
+            vec![part]
+        } else {
+            part.split_whitespace().collect()
+        };
+        for piece in pieces {
+            if piece.chars().any(char::is_alphanumeric) {
+                let prefix = if is_phrase { "" } else { "*" };
+                terms.push(format!("\"{}\"{prefix}", piece.trim()));
+            }
+        }
+    }
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Call `f` with every workflow, oldest first, reading rows one at a time
+/// rather than collecting them, so exports scale to any history size.
+/// Returns the number of workflows visited.
+pub fn for_each_workflow(
+    conn: &Connection,
+    mut f: impl FnMut(Workflow) -> Result<()>,
+) -> Result<usize> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, workflow_type, text, audio_path, created_at, raw_text, tags, segments FROM workflows
+             ORDER BY created_at ASC, id ASC",
+        )
+        .context("Failed to prepare workflow scan")?;
+
+    let mut rows = stmt.query([]).context("Failed to scan workflows")?;
+    let mut visited = 0;
+    while let Some(row) = rows.next().context("Failed to read workflow row")? {
+        f(workflow_from_row(row).context("Failed to map workflow")?)?;
+        visited += 1;
+    }
+
+    Ok(visited)
+}
+
+/// Workflows that have stored raw text, oldest first, optionally only those
+/// created on or after `date_from`.
+pub fn get_workflows_with_raw_text(
+    conn: &Connection,
+    date_from: Option<&str>,
+) -> Result<Vec<Workflow>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, workflow_type, text, audio_path, created_at, raw_text, tags, segments FROM workflows
+             WHERE raw_text IS NOT NULL AND (?1 IS NULL OR created_at >= ?1)
+             ORDER BY created_at ASC",
+        )
+        .context("Failed to prepare raw text query")?;
+
+    let workflows = stmt
+        .query_map([date_from], workflow_from_row)
+        .context("Failed to query workflows with raw text")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to map workflows")?;
+
+    Ok(workflows)
+}
+
+/// Replace a workflow's tags. Returns false if no workflow has `id`.
+pub fn set_workflow_tags(conn: &Connection, id: i64, tags: &[String]) -> Result<bool> {
+    let updated = conn
+        .execute(
+            "UPDATE workflows SET tags = ?1 WHERE id = ?2",
+            rusqlite::params![tags_to_column(tags)?, id],
+        )
+        .context("Failed to update workflow tags")?;
+
+    Ok(updated > 0)
+}
+
+/// Delete a workflow and its segments. Returns false if no workflow has `id`.
+pub fn delete_workflow(conn: &Connection, id: i64) -> Result<bool> {
+    // Foreign keys aren't enforced, so remove the segments explicitly.
+    conn.execute("DELETE FROM workflow_segments WHERE workflow_id = ?1", [id])
+        .context("Failed to delete workflow segments")?;
+    let deleted = conn
+        .execute("DELETE FROM workflows WHERE id = ?1", [id])
+        .context("Failed to delete workflow")?;
+
+    Ok(deleted > 0)
+}
+
+pub fn update_workflow_text(conn: &Connection, id: i64, text: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE workflows SET text = ?1 WHERE id = ?2",
+        rusqlite::params![text, id],
+    )
+    .context("Failed to update workflow text")?;
+
+    Ok(())
+}
+
+/// Replace a workflow's transcript after re-transcribing its audio: the
+/// normalized text, the provider's raw output, and its segments. Indexed
+/// segment rows from the old transcript are dropped; re-index with
+/// `insert_segments`. Returns false if no workflow has `id`.
+pub fn update_workflow_transcript(
+    conn: &Connection,
+    id: i64,
+    text: &str,
+    raw_text: &str,
+    segments: &[Segment],
+) -> Result<bool> {
+    let updated = conn
+        .execute(
+            "UPDATE workflows SET text = ?1, raw_text = ?2, segments = ?3 WHERE id = ?4",
+            rusqlite::params![text, raw_text, segments_to_column(Some(segments))?, id],
+        )
+        .context("Failed to update workflow transcript")?;
+    if updated == 0 {
+        return Ok(false);
+    }
+
+    conn.execute("DELETE FROM workflow_segments WHERE workflow_id = ?1", [id])
+        .context("Failed to clear workflow segments")?;
+
+    Ok(true)
+}
+
+/// Store a workflow's timestamped segments for `search_segments`. Returns the
+/// number of rows written.
+pub fn insert_segments(conn: &Connection, workflow_id: i64, segments: &[Segment]) -> Result<usize> {
+    let mut stmt = conn
+        .prepare(
+            "INSERT INTO workflow_segments (workflow_id, start, end, text) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .context("Failed to prepare segment insert")?;
+
+    let mut inserted = 0;
+    for segment in segments.iter().filter(|s| !s.text.trim().is_empty()) {
+        stmt.execute(rusqlite::params![
+            workflow_id,
+            segment.start,
+            segment.end,
+            segment.text.trim()
+        ])
+        .context("Failed to insert workflow segment")?;
+        inserted += 1;
+    }
+
+    Ok(inserted)
+}
+
+/// Segments whose text contains `query` (case-insensitive for ASCII), newest
+/// workflow first and in playback order within a workflow.
+pub fn search_segments(conn: &Connection, query: &str) -> Result<Vec<SegmentMatch>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.workflow_id, s.start, s.end, s.text FROM workflow_segments s
+             JOIN workflows w ON w.id = s.workflow_id
+             WHERE s.text LIKE ?1
+             ORDER BY w.created_at DESC, w.id DESC, s.start ASC",
+        )
+        .context("Failed to prepare segment search")?;
+
+    let matches = stmt
+        .query_map([format!("%{}%", query)], |row| {
+            Ok(SegmentMatch {
+                workflow_id: row.get(0)?,
+                start: row.get(1)?,
+                end: row.get(2)?,
+                text: row.get(3)?,
+            })
+        })
+        .context("Failed to search workflow segments")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to map segment matches")?;
+
+    Ok(matches)
+}
+
+/// How many failed jobs are kept; older ones are pruned on insert.
+const MAX_FAILED_JOBS: i64 = 1_000;
+
+/// Record a dictation job that failed, returning the row id.
+pub fn insert_failed_job(conn: &Connection, job_id: Option<&str>, error: &str) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO failed_jobs (job_id, error) VALUES (?1, ?2)",
+        rusqlite::params![job_id, error],
+    )
+    .context("Failed to insert failed job")?;
+    let id = conn.last_insert_rowid();
+
+    conn.execute(
+        "DELETE FROM failed_jobs WHERE id NOT IN (
+            SELECT id FROM failed_jobs ORDER BY id DESC LIMIT ?1
+        )",
+        [MAX_FAILED_JOBS],
+    )
+    .context("Failed to prune old failed jobs")?;
+
+    Ok(id)
+}
+
+/// The newest `limit` job outcomes, completed and failed, newest first.
+pub fn get_recent_jobs(conn: &Connection, limit: usize) -> Result<Vec<RecentJob>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT 'completed', id, NULL, text, NULL, created_at FROM workflows
+             UNION ALL
+             SELECT 'failed', NULL, job_id, NULL, error, created_at FROM failed_jobs
+             ORDER BY 6 DESC LIMIT ?1",
+        )
+        .context("Failed to prepare recent jobs query")?;
+
+    let jobs = stmt
+        .query_map([limit], |row| {
+            let status: String = row.get(0)?;
+            Ok(RecentJob {
+                status: if status == "failed" {
+                    RecentJobStatus::Failed
+                } else {
+                    RecentJobStatus::Completed
+                },
+                history_id: row.get(1)?,
+                job_id: row.get(2)?,
+                text: row.get(3)?,
+                error: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })
+        .context("Failed to query recent jobs")?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to map recent jobs")?;
+
+    Ok(jobs)
+}
@@ -1,10 +1,80 @@
 use anyhow::Result;
+use audetic_core::jobs_client::Segment;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VoiceToTextData {
     pub text: String,
     pub audio_path: String,
+    /// Provider output before normalization, kept so history can be
+    /// re-normalized later. `None` for entries recorded before it was stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_text: Option<String>,
+    /// Timestamped segments, when the provider returned them. Delivery
+    /// (clipboard, injection) only ever uses `text`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<Segment>>,
+}
+
+/// A stored transcript segment matching a search, with the workflow it came
+/// from and where in the recording it was said.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SegmentMatch {
+    pub workflow_id: i64,
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// How a dictation job ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RecentJobStatus {
+    Completed,
+    Failed,
+}
+
+/// One recent dictation outcome: a history entry or a recorded failure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RecentJob {
+    pub status: RecentJobStatus,
+    /// History entry id; set for completed jobs.
+    pub history_id: Option<i64>,
+    /// Id the job ran under; set for failures.
+    pub job_id: Option<String>,
+    /// Transcribed text; set for completed jobs.
+    pub text: Option<String>,
+    /// What went wrong; set for failures.
+    pub error: Option<String>,
+    pub created_at: String,
+}
+
+/// Usage totals over the whole transcription history. Words are counted as
+/// whitespace-separated runs; days are UTC calendar days, as `created_at`
+/// is stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HistoryStats {
+    pub total: i64,
+    pub total_words: i64,
+    pub total_characters: i64,
+    /// 0 with an empty history.
+    pub average_words: f64,
+    pub average_characters: f64,
+    /// Transcriptions in the last 7 days.
+    pub this_week: i64,
+    /// The day with the most transcriptions, the latest on a tie.
+    pub most_active_day: Option<DayCount>,
+    /// Every day with at least one transcription, newest first.
+    pub per_day: Vec<DayCount>,
+}
+
+/// Transcriptions on one day (`YYYY-MM-DD`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DayCount {
+    pub date: String,
+    pub count: i64,
+    pub words: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -40,6 +110,8 @@ pub struct Workflow {
     pub workflow_type: WorkflowType,
     pub data: WorkflowData,
     pub created_at: Option<String>,
+    /// User-assigned labels ("work", "ideas") for filtering history.
+    pub tags: Vec<String>,
 }
 
 impl Workflow {
@@ -61,6 +133,7 @@ impl Workflow {
             workflow_type: WorkflowType::parse(&workflow_type)?,
             data: serde_json::from_str(&json)?,
             created_at: Some(created_at),
+            tags: Vec::new(),
         })
     }
 
@@ -70,6 +143,12 @@ impl Workflow {
             workflow_type,
             data,
             created_at: None,
+            tags: Vec::new(),
         }
     }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
 }
@@ -1,7 +1,10 @@
-use super::init::migrate;
+use super::init::{migrate, schema_version, SCHEMA_VERSION};
 use super::operations::*;
-use super::schemas::{VoiceToTextData, Workflow, WorkflowData, WorkflowType};
+use super::schemas::{
+    RecentJobStatus, SegmentMatch, VoiceToTextData, Workflow, WorkflowData, WorkflowType,
+};
 use anyhow::Result;
+use audetic_core::jobs_client::Segment;
 use rusqlite::Connection;
 
 fn setup_test_db() -> Result<Connection> {
@@ -16,6 +19,8 @@ fn create_test_workflow(text: &str) -> Workflow {
         WorkflowData::VoiceToText(VoiceToTextData {
             text: text.to_string(),
             audio_path: "/tmp/test.wav".to_string(),
+            raw_text: None,
+            segments: None,
         }),
     )
 }
@@ -36,6 +41,76 @@ fn test_migrate_creates_table() {
     assert_eq!(count, 1);
 }
 
+fn column_names(conn: &Connection, table: &str) -> Vec<String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({table})"))
+        .unwrap();
+    stmt.query_map([], |row| row.get::<_, String>(1))
+        .unwrap()
+        .collect::<rusqlite::Result<_>>()
+        .unwrap()
+}
+
+#[test]
+fn test_migrate_twice_is_idempotent() {
+    let conn = setup_test_db().unwrap();
+    assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    insert_workflow(&conn, &create_test_workflow("Kept across runs")).unwrap();
+
+    migrate(&conn).unwrap();
+    assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    assert_eq!(count_workflows(&conn).unwrap(), 1);
+}
+
+#[test]
+fn test_migrate_upgrades_unversioned_schema() {
+    // The shape of a database from before raw text, tags, segments, soft
+    // deletes, and versioning existed.
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE workflows (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            workflow_type TEXT NOT NULL,
+            text TEXT NOT NULL,
+            audio_path TEXT NOT NULL,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE TABLE meetings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT,
+            status TEXT NOT NULL DEFAULT 'recording',
+            audio_path TEXT NOT NULL,
+            transcript_path TEXT,
+            transcript_text TEXT,
+            duration_seconds INTEGER,
+            started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            completed_at TIMESTAMP,
+            error TEXT,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+        INSERT INTO workflows (workflow_type, text, audio_path)
+            VALUES ('VoiceToText', 'Dictated before the upgrade', '/tmp/old.wav');",
+    )
+    .unwrap();
+    assert_eq!(schema_version(&conn).unwrap(), 0);
+
+    migrate(&conn).unwrap();
+
+    assert_eq!(schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    let workflow_columns = column_names(&conn, "workflows");
+    for column in ["raw_text", "tags", "segments"] {
+        assert!(workflow_columns.iter().any(|c| c == column), "{column}");
+    }
+    let meeting_columns = column_names(&conn, "meetings");
+    for column in ["deleted_at", "transcript_segments", "transcript_utterances"] {
+        assert!(meeting_columns.iter().any(|c| c == column), "{column}");
+    }
+
+    let found = search_workflows(&conn, Some("upgrade"), None, None, None, 10).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, Some(1));
+}
+
 #[test]
 fn test_insert_workflow() {
     let conn = setup_test_db().unwrap();
@@ -128,11 +203,11 @@ fn test_search_workflows_by_text() {
     insert_workflow(&conn, &workflow3).unwrap();
 
     // Search for "Hello"
-    let results = search_workflows(&conn, Some("Hello"), None, None, 10).unwrap();
+    let results = search_workflows(&conn, Some("Hello"), None, None, None, 10).unwrap();
     assert_eq!(results.len(), 2);
 
     // Search for "Goodbye"
-    let results = search_workflows(&conn, Some("Goodbye"), None, None, 10).unwrap();
+    let results = search_workflows(&conn, Some("Goodbye"), None, None, None, 10).unwrap();
     assert_eq!(results.len(), 1);
 }
 
@@ -146,10 +221,67 @@ fn test_search_workflows_limit() {
     }
 
     // Search with limit
-    let results = search_workflows(&conn, None, None, None, 5).unwrap();
+    let results = search_workflows(&conn, None, None, None, None, 5).unwrap();
     assert_eq!(results.len(), 5);
 }
 
+#[test]
+fn test_workflow_tags_round_trip() {
+    let conn = setup_test_db().unwrap();
+    let tagged =
+        create_test_workflow("Tagged").with_tags(vec!["work".to_string(), "ideas".to_string()]);
+    let id = insert_workflow(&conn, &tagged).unwrap();
+    insert_workflow(&conn, &create_test_workflow("Untagged")).unwrap();
+
+    let results = get_recent_workflows(&conn, 10).unwrap();
+    let stored = results.iter().find(|w| w.id == Some(id)).unwrap();
+    assert_eq!(stored.tags, vec!["work", "ideas"]);
+    let untagged = results.iter().find(|w| w.id != Some(id)).unwrap();
+    assert!(untagged.tags.is_empty());
+
+    assert!(set_workflow_tags(&conn, id, &["later".to_string()]).unwrap());
+    let results = search_workflows(&conn, None, None, None, Some("later"), 10).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].tags, vec!["later"]);
+
+    assert!(!set_workflow_tags(&conn, 9999, &[]).unwrap());
+}
+
+#[test]
+fn test_search_workflows_by_tag() {
+    let conn = setup_test_db().unwrap();
+    insert_workflow(
+        &conn,
+        &create_test_workflow("Standup notes").with_tags(vec!["work".to_string()]),
+    )
+    .unwrap();
+    insert_workflow(
+        &conn,
+        &create_test_workflow("App idea").with_tags(vec!["ideas".to_string(), "work".to_string()]),
+    )
+    .unwrap();
+    insert_workflow(
+        &conn,
+        &create_test_workflow("Workout plan").with_tags(vec!["workout".to_string()]),
+    )
+    .unwrap();
+    insert_workflow(&conn, &create_test_workflow("Untagged work")).unwrap();
+
+    let results = search_workflows(&conn, None, None, None, Some("work"), 10).unwrap();
+    let mut texts: Vec<String> = results
+        .into_iter()
+        .map(|w| match w.data {
+            WorkflowData::VoiceToText(data) => data.text,
+        })
+        .collect();
+    texts.sort();
+    // Whole-tag matches only; "workout" and the untagged entry are excluded.
+    assert_eq!(texts, vec!["App idea", "Standup notes"]);
+
+    let results = search_workflows(&conn, Some("idea"), None, None, Some("work"), 10).unwrap();
+    assert_eq!(results.len(), 1);
+}
+
 #[test]
 fn test_workflow_serialization() {
     let workflow = create_test_workflow("Test text");
@@ -178,3 +310,437 @@ fn test_workflow_from_row() {
     assert_eq!(data.text, "Test");
     assert_eq!(data.audio_path, "/tmp/test.wav");
 }
+
+#[test]
+fn test_raw_text_round_trip_and_update() {
+    let conn = setup_test_db().unwrap();
+
+    let mut workflow = create_test_workflow("Hello world.");
+    let WorkflowData::VoiceToText(data) = &mut workflow.data;
+    data.raw_text = Some("  hello world  ".to_string());
+    let id = insert_workflow(&conn, &workflow).unwrap();
+    insert_workflow(&conn, &create_test_workflow("No raw text")).unwrap();
+
+    let with_raw = get_workflows_with_raw_text(&conn, None).unwrap();
+    assert_eq!(with_raw.len(), 1);
+    let WorkflowData::VoiceToText(data) = &with_raw[0].data;
+    assert_eq!(data.raw_text.as_deref(), Some("  hello world  "));
+
+    update_workflow_text(&conn, id, "Hello, world!").unwrap();
+    let recent = get_recent_workflows(&conn, 10).unwrap();
+    assert!(recent.iter().any(|w| {
+        let WorkflowData::VoiceToText(data) = &w.data;
+        w.id == Some(id) && data.text == "Hello, world!"
+    }));
+}
+
+fn segment(start: f64, end: f64, text: &str) -> Segment {
+    Segment {
+        start,
+        end,
+        text: text.to_string(),
+        words: None,
+    }
+}
+
+#[test]
+fn test_segments_round_trip_through_the_workflow_row() {
+    let conn = setup_test_db().unwrap();
+    let mut workflow = create_test_workflow("Hello there. General Kenobi.");
+    let WorkflowData::VoiceToText(data) = &mut workflow.data;
+    data.segments = Some(vec![
+        segment(0.0, 1.2, "Hello there."),
+        segment(1.2, 2.75, "General Kenobi."),
+    ]);
+    let id = insert_workflow(&conn, &workflow).unwrap();
+
+    let WorkflowData::VoiceToText(stored) = get_workflow_by_id(&conn, id).unwrap().unwrap().data;
+    let stored = stored.segments.expect("segments persisted");
+    let timings: Vec<(f64, f64, &str)> = stored
+        .iter()
+        .map(|s| (s.start, s.end, s.text.as_str()))
+        .collect();
+    assert_eq!(
+        timings,
+        [(0.0, 1.2, "Hello there."), (1.2, 2.75, "General Kenobi.")]
+    );
+}
+
+#[test]
+fn test_rows_without_segments_read_as_none() {
+    let conn = setup_test_db().unwrap();
+    // Provider without segments: an empty list is stored as NULL.
+    let mut workflow = create_test_workflow("no timings");
+    let WorkflowData::VoiceToText(data) = &mut workflow.data;
+    data.segments = Some(Vec::new());
+    let empty = insert_workflow(&conn, &workflow).unwrap();
+    // A row from before the column existed, or with a damaged value.
+    let old = insert_workflow(&conn, &create_test_workflow("old row")).unwrap();
+    let damaged = insert_workflow(&conn, &create_test_workflow("damaged")).unwrap();
+    conn.execute(
+        "UPDATE workflows SET segments = 'not json' WHERE id = ?1",
+        [damaged],
+    )
+    .unwrap();
+
+    for id in [empty, old, damaged] {
+        let WorkflowData::VoiceToText(data) = get_workflow_by_id(&conn, id).unwrap().unwrap().data;
+        assert!(data.segments.is_none(), "row {id}");
+    }
+}
+
+#[test]
+fn test_insert_and_search_segments() {
+    let conn = setup_test_db().unwrap();
+
+    let standup = insert_workflow(&conn, &create_test_workflow("standup notes")).unwrap();
+    let inserted = insert_segments(
+        &conn,
+        standup,
+        &[
+            segment(0.0, 2.5, " Morning everyone. "),
+            segment(2.5, 6.0, "The deploy is blocked on the migration."),
+            segment(6.0, 7.0, "   "),
+        ],
+    )
+    .unwrap();
+    // Blank segments are skipped.
+    assert_eq!(inserted, 2);
+
+    let other = insert_workflow(&conn, &create_test_workflow("other")).unwrap();
+    insert_segments(&conn, other, &[segment(1.0, 3.0, "Unrelated chatter")]).unwrap();
+
+    let matches = search_segments(&conn, "MIGRATION").unwrap();
+    assert_eq!(
+        matches,
+        vec![SegmentMatch {
+            workflow_id: standup,
+            start: 2.5,
+            end: 6.0,
+            text: "The deploy is blocked on the migration.".to_string(),
+        }]
+    );
+
+    assert!(search_segments(&conn, "nothing like this")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_search_segments_orders_within_workflow_by_time() {
+    let conn = setup_test_db().unwrap();
+    let id = insert_workflow(&conn, &create_test_workflow("demo")).unwrap();
+    insert_segments(
+        &conn,
+        id,
+        &[
+            segment(10.0, 12.0, "second mention of the demo"),
+            segment(1.0, 3.0, "first mention of the demo"),
+        ],
+    )
+    .unwrap();
+
+    let starts: Vec<f64> = search_segments(&conn, "demo")
+        .unwrap()
+        .iter()
+        .map(|m| m.start)
+        .collect();
+    assert_eq!(starts, vec![1.0, 10.0]);
+}
+
+#[test]
+fn test_update_workflow_transcript_replaces_text_raw_and_segments() {
+    let conn = setup_test_db().unwrap();
+    let id = insert_workflow(&conn, &create_test_workflow("teh old transcript")).unwrap();
+    insert_segments(&conn, id, &[segment(0.0, 1.0, "teh old transcript")]).unwrap();
+
+    let segments = [segment(0.0, 1.5, "The new transcript.")];
+    assert!(update_workflow_transcript(
+        &conn,
+        id,
+        "The new transcript.",
+        " the new transcript ",
+        &segments
+    )
+    .unwrap());
+
+    let workflow = get_workflow_by_id(&conn, id).unwrap().unwrap();
+    let WorkflowData::VoiceToText(data) = workflow.data;
+    assert_eq!(data.text, "The new transcript.");
+    assert_eq!(data.raw_text.as_deref(), Some(" the new transcript "));
+    let stored = data.segments.unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].text, "The new transcript.");
+    // The old indexed rows are gone until the caller re-indexes.
+    assert!(search_segments(&conn, "old").unwrap().is_empty());
+
+    assert!(!update_workflow_transcript(&conn, id + 1, "x", "x", &[]).unwrap());
+}
+
+#[test]
+fn test_prune_before_deletes_by_age() {
+    let conn = setup_test_db().unwrap();
+    let mut ids = Vec::new();
+    for created_at in [
+        "2024-05-30 12:00:00",
+        "2024-05-31 23:59:59",
+        "2024-06-01 00:00:00",
+    ] {
+        let id = insert_workflow(&conn, &create_test_workflow(created_at)).unwrap();
+        insert_segments(&conn, id, &[segment(0.0, 1.0, "dated words")]).unwrap();
+        conn.execute(
+            "UPDATE workflows SET created_at = ?1 WHERE id = ?2",
+            rusqlite::params![created_at, id],
+        )
+        .unwrap();
+        ids.push(id);
+    }
+
+    assert_eq!(prune_before(&conn, "2024-06-01").unwrap(), 2);
+    assert_eq!(count_workflows(&conn).unwrap(), 1);
+    assert!(get_workflow_by_id(&conn, ids[2]).unwrap().is_some());
+    let remaining = search_segments(&conn, "words").unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].workflow_id, ids[2]);
+
+    assert_eq!(prune_before(&conn, "2024-06-01").unwrap(), 0);
+}
+
+#[test]
+fn test_prune_drops_segments_of_pruned_workflows() {
+    let conn = setup_test_db().unwrap();
+    let old = insert_workflow(&conn, &create_test_workflow("old")).unwrap();
+    insert_segments(&conn, old, &[segment(0.0, 1.0, "old words")]).unwrap();
+    conn.execute(
+        "UPDATE workflows SET created_at = datetime('now', '-1 day') WHERE id = ?1",
+        [old],
+    )
+    .unwrap();
+    let new = insert_workflow(&conn, &create_test_workflow("new")).unwrap();
+    insert_segments(&conn, new, &[segment(0.0, 1.0, "new words")]).unwrap();
+
+    prune_old_workflows(&conn, 1).unwrap();
+
+    let remaining = search_segments(&conn, "words").unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].workflow_id, new);
+}
+
+#[test]
+fn test_workflow_stats_counts_words_days_and_averages() {
+    let conn = setup_test_db().unwrap();
+    let empty = workflow_stats(&conn).unwrap();
+    assert_eq!(empty.total, 0);
+    assert_eq!(empty.average_words, 0.0);
+    assert!(empty.most_active_day.is_none());
+
+    for (text, created_at) in [
+        ("one two three", "2024-06-01 09:00:00"),
+        ("four  five\nsix seven", "2024-06-01 17:30:00"),
+        ("eight", "2024-06-02 08:15:00"),
+    ] {
+        let id = insert_workflow(&conn, &create_test_workflow(text)).unwrap();
+        conn.execute(
+            "UPDATE workflows SET created_at = ?1 WHERE id = ?2",
+            rusqlite::params![created_at, id],
+        )
+        .unwrap();
+    }
+    insert_workflow(&conn, &create_test_workflow("  just now  ")).unwrap();
+
+    let stats = workflow_stats(&conn).unwrap();
+    assert_eq!(stats.total, 4);
+    assert_eq!(stats.total_words, 10);
+    assert_eq!(stats.average_words, 2.5);
+    assert_eq!(stats.total_characters, 13 + 20 + 5 + 12);
+    assert_eq!(stats.average_characters, 12.5);
+    assert_eq!(stats.this_week, 1);
+
+    let days: Vec<(&str, i64, i64)> = stats
+        .per_day
+        .iter()
+        .map(|d| (d.date.as_str(), d.count, d.words))
+        .collect();
+    assert_eq!(&days[1..], [("2024-06-02", 1, 1), ("2024-06-01", 2, 7)]);
+    assert_eq!(stats.most_active_day.unwrap().date, "2024-06-01");
+}
+
+#[test]
+fn test_recent_jobs_interleave_completed_and_failed() {
+    let conn = setup_test_db().unwrap();
+    let older = insert_workflow(&conn, &create_test_workflow("older")).unwrap();
+    conn.execute(
+        "UPDATE workflows SET created_at = datetime('now', '-2 hours') WHERE id = ?1",
+        [older],
+    )
+    .unwrap();
+    let failed = insert_failed_job(&conn, Some("job-2"), "provider timed out").unwrap();
+    conn.execute(
+        "UPDATE failed_jobs SET created_at = datetime('now', '-1 hour') WHERE id = ?1",
+        [failed],
+    )
+    .unwrap();
+    let newest = insert_workflow(&conn, &create_test_workflow("newest")).unwrap();
+
+    let jobs = get_recent_jobs(&conn, 10).unwrap();
+    let statuses: Vec<_> = jobs.iter().map(|j| j.status).collect();
+    assert_eq!(
+        statuses,
+        vec![
+            RecentJobStatus::Completed,
+            RecentJobStatus::Failed,
+            RecentJobStatus::Completed
+        ]
+    );
+    assert_eq!(jobs[0].history_id, Some(newest));
+    assert_eq!(jobs[0].text.as_deref(), Some("newest"));
+    assert_eq!(jobs[1].job_id.as_deref(), Some("job-2"));
+    assert_eq!(jobs[1].error.as_deref(), Some("provider timed out"));
+    assert_eq!(jobs[1].history_id, None);
+
+    assert_eq!(get_recent_jobs(&conn, 2).unwrap().len(), 2);
+}
+
+#[test]
+fn test_get_workflow_by_id() {
+    let conn = setup_test_db().unwrap();
+    insert_workflow(&conn, &create_test_workflow("first")).unwrap();
+    let middle = insert_workflow(&conn, &create_test_workflow("second")).unwrap();
+    insert_workflow(&conn, &create_test_workflow("third")).unwrap();
+
+    let workflow = get_workflow_by_id(&conn, middle).unwrap().expect("found");
+    assert_eq!(workflow.id, Some(middle));
+    let WorkflowData::VoiceToText(data) = workflow.data;
+    assert_eq!(data.text, "second");
+
+    assert!(get_workflow_by_id(&conn, 9999).unwrap().is_none());
+}
+
+fn search_texts(conn: &Connection, query: &str) -> Vec<String> {
+    search_workflows(conn, Some(query), None, None, None, 10)
+        .unwrap()
+        .into_iter()
+        .map(|w| {
+            let WorkflowData::VoiceToText(data) = w.data;
+            data.text
+        })
+        .collect()
+}
+
+#[test]
+fn test_search_workflows_multi_word_matches_all_terms_anywhere() {
+    let conn = setup_test_db().unwrap();
+    insert_workflow(&conn, &create_test_workflow("Hello big wide world")).unwrap();
+    insert_workflow(&conn, &create_test_workflow("Hello there")).unwrap();
+    insert_workflow(&conn, &create_test_workflow("Planning meetings for Monday")).unwrap();
+
+    assert_eq!(
+        search_texts(&conn, "world hello"),
+        vec!["Hello big wide world"]
+    );
+    // Stemming: "meeting" finds "meetings".
+    assert_eq!(
+        search_texts(&conn, "meeting"),
+        vec!["Planning meetings for Monday"]
+    );
+    // Punctuation-only queries fall back to a substring match.
+    assert!(search_texts(&conn, "!!").is_empty());
+}
+
+#[test]
+fn test_search_workflows_matches_partial_words() {
+    let conn = setup_test_db().unwrap();
+    insert_workflow(&conn, &create_test_workflow("Transcription of the standup")).unwrap();
+    insert_workflow(&conn, &create_test_workflow("Planning meetings for Monday")).unwrap();
+
+    assert_eq!(
+        search_texts(&conn, "transcr"),
+        vec!["Transcription of the standup"]
+    );
+    assert_eq!(
+        search_texts(&conn, "plan mon"),
+        vec!["Planning meetings for Monday"]
+    );
+    // Quoted phrases still need whole words.
+    assert!(search_texts(&conn, "\"transcr\"").is_empty());
+}
+
+#[test]
+fn test_search_workflows_phrase_match() {
+    let conn = setup_test_db().unwrap();
+    insert_workflow(&conn, &create_test_workflow("Hello big wide world")).unwrap();
+    insert_workflow(&conn, &create_test_workflow("wide open, big world")).unwrap();
+
+    assert_eq!(
+        search_texts(&conn, "\"wide world\""),
+        vec!["Hello big wide world"]
+    );
+    assert_eq!(
+        search_texts(&conn, "\"big world\""),
+        vec!["wide open, big world"]
+    );
+    assert_eq!(search_texts(&conn, "big world").len(), 2);
+}
+
+#[test]
+fn test_search_workflows_ranks_by_relevance() {
+    let conn = setup_test_db().unwrap();
+    insert_workflow(
+        &conn,
+        &create_test_workflow("Standup went long, lots of unrelated chatter today"),
+    )
+    .unwrap();
+    insert_workflow(&conn, &create_test_workflow("Standup standup notes")).unwrap();
+
+    assert_eq!(search_texts(&conn, "standup")[0], "Standup standup notes");
+}
+
+#[test]
+fn test_migrate_backfills_full_text_index() {
+    let conn = setup_test_db().unwrap();
+    conn.execute_batch(
+        "DROP TRIGGER workflows_fts_insert;
+         DROP TRIGGER workflows_fts_delete;
+         DROP TRIGGER workflows_fts_update;
+         DROP TABLE workflows_fts;",
+    )
+    .unwrap();
+    insert_workflow(&conn, &create_test_workflow("Recorded before the index")).unwrap();
+
+    migrate(&conn).unwrap();
+
+    assert_eq!(
+        search_texts(&conn, "recorded"),
+        vec!["Recorded before the index"]
+    );
+}
+
+#[test]
+fn test_full_text_index_follows_updates_and_prunes() {
+    let conn = setup_test_db().unwrap();
+    let id = insert_workflow(&conn, &create_test_workflow("teh typo")).unwrap();
+    update_workflow_text(&conn, id, "the fix").unwrap();
+    assert!(search_texts(&conn, "teh").is_empty());
+    assert_eq!(search_texts(&conn, "fix"), vec!["the fix"]);
+
+    prune_old_workflows(&conn, 0).unwrap();
+    assert!(search_texts(&conn, "fix").is_empty());
+}
+
+#[test]
+fn test_delete_workflow_removes_row_segments_and_index() {
+    let conn = setup_test_db().unwrap();
+    let keep = insert_workflow(&conn, &create_test_workflow("keep this note")).unwrap();
+    let gone = insert_workflow(&conn, &create_test_workflow("delete this note")).unwrap();
+    insert_segments(&conn, gone, &[segment(0.0, 1.0, "delete this note")]).unwrap();
+
+    assert!(delete_workflow(&conn, gone).unwrap());
+
+    assert!(get_workflow_by_id(&conn, gone).unwrap().is_none());
+    assert!(get_workflow_by_id(&conn, keep).unwrap().is_some());
+    assert!(search_segments(&conn, "delete").unwrap().is_empty());
+    assert_eq!(search_texts(&conn, "note"), vec!["keep this note"]);
+
+    // A second delete finds nothing.
+    assert!(!delete_workflow(&conn, gone).unwrap());
+}
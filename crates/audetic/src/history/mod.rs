@@ -4,8 +4,16 @@
 //! and managing transcription history. It is used by both the CLI and REST API.
 
 use crate::db::{self, Workflow, WorkflowData};
+use crate::normalizer::TranscriptionNormalizer;
+use crate::transcription::Transcript;
 use anyhow::{anyhow, Result};
+use audetic_core::jobs_client::Segment;
+use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::Path;
+use tracing::warn;
 use utoipa::ToSchema;
 
 /// Parameters for searching transcription history.
@@ -17,6 +25,8 @@ pub struct SearchParams {
     pub from: Option<String>,
     /// Filter by end date (YYYY-MM-DD format)
     pub to: Option<String>,
+    /// Only entries carrying this tag
+    pub tag: Option<String>,
     /// Maximum number of results
     pub limit: usize,
 }
@@ -45,9 +55,14 @@ impl SearchParams {
         self
     }
 
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
     /// Returns true if no filters are specified (only limit)
     pub fn has_filters(&self) -> bool {
-        self.query.is_some() || self.from.is_some() || self.to.is_some()
+        self.query.is_some() || self.from.is_some() || self.to.is_some() || self.tag.is_some()
     }
 }
 
@@ -58,22 +73,53 @@ pub struct HistoryEntry {
     pub text: String,
     pub audio_path: String,
     pub created_at: String,
+    /// User-assigned tags, empty when untagged
+    pub tags: Vec<String>,
+    /// Timestamped segments, when the provider returned them. Absent for
+    /// entries recorded without them or before they were stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<Segment>>,
+    /// Words in the full transcript, whether or not `text` was shortened.
+    #[serde(default)]
+    pub word_count: usize,
+    /// Whether `text` was cut to a preview (see [`HistoryEntry::into_preview`]).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
 }
 
 impl From<Workflow> for HistoryEntry {
     fn from(workflow: Workflow) -> Self {
-        let (text, audio_path) = match workflow.data {
-            WorkflowData::VoiceToText(data) => (data.text, data.audio_path),
+        let (text, audio_path, segments) = match workflow.data {
+            WorkflowData::VoiceToText(data) => (data.text, data.audio_path, data.segments),
         };
         Self {
             id: workflow.id.unwrap_or(0),
+            word_count: text.split_whitespace().count(),
             text,
             audio_path,
             created_at: workflow.created_at.unwrap_or_else(|| "Unknown".to_string()),
+            tags: workflow.tags,
+            segments,
+            truncated: false,
         }
     }
 }
 
+impl HistoryEntry {
+    /// Cut `text` to its first `max_chars` characters for a listing, marking
+    /// the entry `truncated` when anything was dropped. Segments go too, since
+    /// they repeat the full text. `word_count` still describes the whole
+    /// transcript.
+    pub fn into_preview(mut self, max_chars: usize) -> Self {
+        if let Some((cut, _)) = self.text.char_indices().nth(max_chars) {
+            self.text.truncate(cut);
+            self.truncated = true;
+        }
+        self.segments = None;
+        self
+    }
+}
+
 /// Search transcription history with optional filters.
 ///
 /// If no filters are specified, returns recent transcriptions.
@@ -86,6 +132,7 @@ pub fn search(params: &SearchParams) -> Result<Vec<HistoryEntry>> {
             params.query.as_deref(),
             params.from.as_deref(),
             params.to.as_deref(),
+            params.tag.as_deref().map(str::to_lowercase).as_deref(),
             params.limit,
         )?
     } else {
@@ -105,14 +152,7 @@ pub fn get_recent(limit: usize) -> Result<Vec<HistoryEntry>> {
 /// Get a single transcription by ID.
 pub fn get_by_id(id: i64) -> Result<Option<HistoryEntry>> {
     let conn = db::init_db()?;
-    // Use search with a high limit to find by ID
-    // TODO: Add a proper get_by_id to db module
-    let workflows = db::search_workflows(&conn, None, None, None, 10000)?;
-
-    Ok(workflows
-        .into_iter()
-        .find(|w| w.id == Some(id))
-        .map(HistoryEntry::from))
+    Ok(db::get_workflow_by_id(&conn, id)?.map(HistoryEntry::from))
 }
 
 /// Get the text content of a transcription by ID.
@@ -124,9 +164,545 @@ pub fn get_text_by_id(id: i64) -> Result<String> {
         .ok_or_else(|| anyhow!("Workflow with ID {} not found", id))
 }
 
+/// Replace the tags on a transcription; an empty list clears them. Returns
+/// the updated entry, or `None` if `id` doesn't exist.
+pub fn set_tags(id: i64, tags: &[String]) -> Result<Option<HistoryEntry>> {
+    let conn = db::init_db()?;
+    if !db::set_workflow_tags(&conn, id, &normalize_tags(tags))? {
+        return Ok(None);
+    }
+    get_by_id(id)
+}
+
+/// Delete a transcription. Its recording is removed too when it's still in the
+/// temp directory (`[audio].temp_dir`); files elsewhere (imports, kept
+/// recordings the user moved) are left alone. Returns false if `id` doesn't
+/// exist.
+pub fn delete(id: i64) -> Result<bool> {
+    let conn = db::init_db()?;
+    let Some(entry) = db::get_workflow_by_id(&conn, id)?.map(HistoryEntry::from) else {
+        return Ok(false);
+    };
+    if !db::delete_workflow(&conn, id)? {
+        return Ok(false);
+    }
+
+    let temp_dir = crate::config::Config::load()
+        .map(|config| config.audio.temp_dir())
+        .unwrap_or_else(|_| std::env::temp_dir());
+    let audio = Path::new(&entry.audio_path);
+    if is_temp_audio(audio, &temp_dir) && audio.exists() {
+        if let Err(e) = std::fs::remove_file(audio) {
+            warn!("Failed to delete audio {}: {e}", audio.display());
+        }
+    }
+    Ok(true)
+}
+
+/// Store a fresh transcript of an entry's audio in place of the old one,
+/// re-indexing its segments when `[history].index_segments` is on. Returns
+/// the updated entry, or `None` if `id` doesn't exist.
+pub fn replace_transcript(id: i64, transcript: &Transcript) -> Result<Option<HistoryEntry>> {
+    let index_segments = crate::config::Config::load()?.history.index_segments;
+    let conn = db::init_db()?;
+    if !db::update_workflow_transcript(
+        &conn,
+        id,
+        &transcript.text,
+        &transcript.raw,
+        &transcript.segments,
+    )? {
+        return Ok(None);
+    }
+    if index_segments && !transcript.segments.is_empty() {
+        db::insert_segments(&conn, id, &transcript.segments)?;
+    }
+    Ok(db::get_workflow_by_id(&conn, id)?.map(HistoryEntry::from))
+}
+
+/// Usage statistics over the whole history.
+pub fn stats() -> Result<db::HistoryStats> {
+    let conn = db::init_db()?;
+    db::workflow_stats(&conn)
+}
+
+/// Delete every transcription created before `date` (`YYYY-MM-DD`).
+/// Returns how many were deleted.
+pub fn clear_before(date: &str) -> Result<usize> {
+    let conn = db::init_db()?;
+    db::prune_before(&conn, date)
+}
+
+/// Whether `path` is a recording in `temp_dir`, where dictation audio is
+/// written. `/tmp` always counts, since recordings went there before the
+/// directory was configurable.
+fn is_temp_audio(path: &Path, temp_dir: &Path) -> bool {
+    !path.as_os_str().is_empty()
+        && (path.starts_with("/tmp")
+            || path.starts_with(temp_dir)
+            || path.starts_with(std::env::temp_dir()))
+}
+
+/// Trim and lowercase tags, dropping empty and duplicate ones, so `Work`
+/// and `work ` file under the same tag.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// Output format for a history export.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// A single JSON array
+    #[default]
+    Json,
+    /// One JSON object per line, for piping into `jq` and other line tools
+    Ndjson,
+    /// `id,created_at,text,audio_path` rows with a header, for spreadsheets
+    Csv,
+    /// A bulleted list headed by each entry's date and time
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Markdown => "text/markdown; charset=utf-8",
+        }
+    }
+}
+
+/// Render the history entries matching `params`, oldest first. Exports of
+/// the whole history should use [`export_to`], which streams instead.
+pub fn export(params: &SearchParams, format: ExportFormat) -> Result<String> {
+    render_export(search(params)?, format)
+}
+
+fn render_export(mut entries: Vec<HistoryEntry>, format: ExportFormat) -> Result<String> {
+    // Text searches come back ranked by relevance; exports read by date.
+    entries.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+
+    let mut out = Vec::new();
+    let mut writer = ExportWriter::begin(format, &mut out)?;
+    for entry in &entries {
+        writer.entry(entry)?;
+    }
+    writer.finish()?;
+    Ok(String::from_utf8(out)?)
+}
+
+/// Write every history entry, oldest first, to `out` as they're read from
+/// the database — nothing is collected in memory. Returns the entry count.
+pub fn export_to(conn: &Connection, format: ExportFormat, out: &mut dyn Write) -> Result<usize> {
+    let mut writer = ExportWriter::begin(format, out)?;
+    db::for_each_workflow(conn, |workflow| writer.entry(&HistoryEntry::from(workflow)))?;
+    writer.finish()
+}
+
+/// Writes an export one entry at a time, so [`export_to`] can stream rows
+/// straight from the database.
+struct ExportWriter<'a> {
+    format: ExportFormat,
+    out: &'a mut dyn Write,
+    count: usize,
+}
+
+impl<'a> ExportWriter<'a> {
+    fn begin(format: ExportFormat, out: &'a mut dyn Write) -> Result<Self> {
+        match format {
+            ExportFormat::Json => out.write_all(b"[")?,
+            ExportFormat::Ndjson => {}
+            ExportFormat::Csv => out.write_all(b"id,created_at,text,audio_path\n")?,
+            ExportFormat::Markdown => out.write_all(b"# Audetic history\n\n")?,
+        }
+        Ok(Self {
+            format,
+            out,
+            count: 0,
+        })
+    }
+
+    fn entry(&mut self, entry: &HistoryEntry) -> Result<()> {
+        let out = &mut *self.out;
+        match self.format {
+            ExportFormat::Json => {
+                out.write_all(if self.count == 0 { b"\n  " } else { b",\n  " })?;
+                serde_json::to_writer(&mut *out, entry)?;
+            }
+            ExportFormat::Ndjson => {
+                serde_json::to_writer(&mut *out, entry)?;
+                out.write_all(b"\n")?;
+            }
+            ExportFormat::Csv => writeln!(
+                out,
+                "{},{},{},{}",
+                entry.id,
+                csv_field(&entry.created_at),
+                csv_field(&entry.text),
+                csv_field(&entry.audio_path)
+            )?,
+            ExportFormat::Markdown => {
+                // Continuation lines are indented so a multi-line transcript
+                // stays inside its bullet.
+                let text = entry.text.trim().replace('\n', "\n  ");
+                writeln!(out, "- **{}** {}", entry.created_at, text)?;
+            }
+        }
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(self) -> Result<usize> {
+        if self.format == ExportFormat::Json {
+            self.out
+                .write_all(if self.count == 0 { b"]\n" } else { b"\n]\n" })?;
+        }
+        self.out.flush()?;
+        Ok(self.count)
+    }
+}
+
+/// Quote a CSV field when it holds a comma, quote, or line break, doubling
+/// any quotes inside (RFC 4180).
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Outcome of re-running the normalizer over stored raw text.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RenormalizeReport {
+    /// Entries with stored raw text that were considered
+    pub scanned: usize,
+    /// Entries whose normalized text differs (and was updated unless dry run)
+    pub changed: usize,
+    /// Whether the run was a dry run (nothing written)
+    pub dry_run: bool,
+}
+
+/// Re-normalize every history entry with stored raw text using the
+/// configured provider's normalizer.
+pub fn renormalize(from: Option<&str>, dry_run: bool) -> Result<RenormalizeReport> {
+    let normalizer = crate::transcription::configured_normalizer()?;
+    let conn = db::init_db()?;
+    renormalize_with(&conn, normalizer.as_ref(), from, dry_run)
+}
+
+/// Apply `normalizer` to the raw text of entries created on or after `from`,
+/// updating those whose normalized text changed. Entries without raw text
+/// (recorded before it was stored) are left alone.
+pub fn renormalize_with(
+    conn: &Connection,
+    normalizer: &dyn TranscriptionNormalizer,
+    from: Option<&str>,
+    dry_run: bool,
+) -> Result<RenormalizeReport> {
+    let mut report = RenormalizeReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    for workflow in db::get_workflows_with_raw_text(conn, from)? {
+        let WorkflowData::VoiceToText(data) = workflow.data;
+        let Some(raw) = data.raw_text else {
+            continue;
+        };
+        report.scanned += 1;
+
+        let normalized = normalizer.normalize(&raw);
+        if normalized == data.text {
+            continue;
+        }
+        report.changed += 1;
+
+        if !dry_run {
+            let id = workflow
+                .id
+                .ok_or_else(|| anyhow!("Stored workflow is missing an id"))?;
+            db::update_workflow_text(conn, id, &normalized)?;
+        }
+    }
+
+    Ok(report)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::{VoiceToTextData, WorkflowType};
+
+    /// Stands in for a normalizer after the user added a replacement rule.
+    struct ReplaceNormalizer;
+
+    impl TranscriptionNormalizer for ReplaceNormalizer {
+        fn normalize(&self, raw_output: &str) -> String {
+            raw_output.trim().replace("audetik", "Audetic")
+        }
+
+        fn name(&self) -> &'static str {
+            "ReplaceNormalizer"
+        }
+    }
+
+    fn setup_history(entries: &[(&str, Option<&str>)]) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        db::migrate(&conn).unwrap();
+        for (text, raw) in entries {
+            let workflow = Workflow::new(
+                WorkflowType::VoiceToText,
+                WorkflowData::VoiceToText(VoiceToTextData {
+                    text: text.to_string(),
+                    audio_path: "/tmp/test.wav".to_string(),
+                    raw_text: raw.map(str::to_string),
+                    segments: None,
+                }),
+            );
+            db::insert_workflow(&conn, &workflow).unwrap();
+        }
+        conn
+    }
+
+    fn texts(conn: &Connection) -> Vec<String> {
+        let mut texts: Vec<String> = db::get_recent_workflows(conn, 100)
+            .unwrap()
+            .into_iter()
+            .map(|w| HistoryEntry::from(w).text)
+            .collect();
+        texts.sort();
+        texts
+    }
+
+    #[test]
+    fn test_renormalize_applies_new_rule() {
+        let conn = setup_history(&[
+            ("I use audetik", Some(" I use audetik ")),
+            ("Nothing to change", Some("Nothing to change")),
+            ("legacy audetik", None),
+        ]);
+
+        let report = renormalize_with(&conn, &ReplaceNormalizer, None, false).unwrap();
+        assert_eq!(report.scanned, 2);
+        assert_eq!(report.changed, 1);
+        assert!(!report.dry_run);
+
+        assert_eq!(
+            texts(&conn),
+            vec!["I use Audetic", "Nothing to change", "legacy audetik"]
+        );
+    }
+
+    #[test]
+    fn test_renormalize_dry_run_writes_nothing() {
+        let conn = setup_history(&[("I use audetik", Some("I use audetik"))]);
+
+        let report = renormalize_with(&conn, &ReplaceNormalizer, None, true).unwrap();
+        assert_eq!(report.changed, 1);
+        assert!(report.dry_run);
+
+        assert_eq!(texts(&conn), vec!["I use audetik"]);
+    }
+
+    #[test]
+    fn test_renormalize_respects_from_date() {
+        let conn = setup_history(&[("I use audetik", Some("I use audetik"))]);
+
+        let report =
+            renormalize_with(&conn, &ReplaceNormalizer, Some("2999-01-01"), false).unwrap();
+        assert_eq!(report.scanned, 0);
+        assert_eq!(texts(&conn), vec!["I use audetik"]);
+    }
+
+    #[test]
+    fn test_export_ndjson_one_object_per_line() {
+        let conn = setup_history(&[
+            ("First entry", None),
+            ("Second, with \"quotes\"\nand a newline", None),
+            ("Third entry", Some("third entry")),
+        ]);
+
+        let mut out = Vec::new();
+        let count = export_to(&conn, ExportFormat::Ndjson, &mut out).unwrap();
+        assert_eq!(count, 3);
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        for line in &lines {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert!(value.is_object());
+            assert!(value["id"].is_i64());
+        }
+        let second: HistoryEntry = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second.text, "Second, with \"quotes\"\nand a newline");
+    }
+
+    #[test]
+    fn test_export_json_is_an_array() {
+        let conn = setup_history(&[("First entry", None), ("Second entry", None)]);
+        let mut out = Vec::new();
+        export_to(&conn, ExportFormat::Json, &mut out).unwrap();
+        let entries: Vec<HistoryEntry> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let empty = setup_history(&[]);
+        let mut out = Vec::new();
+        assert_eq!(export_to(&empty, ExportFormat::Json, &mut out).unwrap(), 0);
+        let entries: Vec<HistoryEntry> = serde_json::from_slice(&out).unwrap();
+        assert!(entries.is_empty());
+    }
+
+    const TRICKY_TEXT: &str = "Buy milk, eggs\nand \"fancy\" cheese";
+
+    fn history_entry(id: i64, created_at: &str, text: &str) -> HistoryEntry {
+        HistoryEntry {
+            id,
+            text: text.to_string(),
+            audio_path: "/tmp/test.wav".to_string(),
+            created_at: created_at.to_string(),
+            tags: Vec::new(),
+            segments: None,
+            word_count: text.split_whitespace().count(),
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_preview_cuts_on_char_boundaries() {
+        let entry = history_entry(1, "2024-01-01 00:00:00", "héllo wörld 👋 done");
+        assert_eq!(entry.word_count, 4);
+
+        // "héllo wörld 👋" is 13 chars but more bytes; a byte cut at 13 would
+        // land inside the emoji.
+        let preview = entry.clone().into_preview(13);
+        assert_eq!(preview.text, "héllo wörld 👋");
+        assert!(preview.truncated);
+        assert_eq!(preview.word_count, 4);
+
+        let preview = entry.clone().into_preview(2);
+        assert_eq!(preview.text, "hé");
+
+        let json = serde_json::to_value(&preview).unwrap();
+        assert_eq!(json["truncated"], true);
+        assert_eq!(json["word_count"], 4);
+    }
+
+    #[test]
+    fn test_preview_leaves_short_text_whole() {
+        let entry = history_entry(1, "2024-01-01 00:00:00", "short ✓");
+
+        for max_chars in [7, 100] {
+            let preview = entry.clone().into_preview(max_chars);
+            assert_eq!(preview.text, "short ✓");
+            assert!(!preview.truncated);
+        }
+
+        let json = serde_json::to_value(entry.into_preview(100)).unwrap();
+        assert!(json.get("truncated").is_none());
+    }
+
+    #[test]
+    fn test_entry_carries_segments_and_omits_them_when_absent() {
+        let segment = Segment {
+            start: 0.0,
+            end: 1.25,
+            text: "Hello there.".to_string(),
+            words: None,
+        };
+        let mut workflow = Workflow::new(
+            WorkflowType::VoiceToText,
+            WorkflowData::VoiceToText(VoiceToTextData {
+                text: "Hello there.".to_string(),
+                audio_path: "/tmp/test.wav".to_string(),
+                raw_text: None,
+                segments: Some(vec![segment]),
+            }),
+        );
+        workflow.id = Some(3);
+
+        let entry = HistoryEntry::from(workflow);
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["segments"][0]["end"], 1.25);
+        assert_eq!(json["segments"][0]["text"], "Hello there.");
+
+        let plain = history_entry(4, "2024-01-01 00:00:00", "no timings");
+        let json = serde_json::to_value(&plain).unwrap();
+        assert!(json.get("segments").is_none());
+        // Clients that saved entries before segments existed still parse.
+        let old: HistoryEntry = serde_json::from_value(serde_json::json!({
+            "id": 4,
+            "text": "no timings",
+            "audio_path": "/tmp/test.wav",
+            "created_at": "2024-01-01 00:00:00",
+            "tags": [],
+        }))
+        .unwrap();
+        assert!(old.segments.is_none());
+    }
+
+    #[test]
+    fn test_export_csv_quotes_commas_quotes_and_newlines() {
+        let conn = setup_history(&[("Plain entry", None), (TRICKY_TEXT, None)]);
+        let mut out = Vec::new();
+        assert_eq!(export_to(&conn, ExportFormat::Csv, &mut out).unwrap(), 2);
+
+        let output = String::from_utf8(out).unwrap();
+        let rows: Vec<&str> = output.splitn(2, '\n').collect();
+        assert_eq!(rows[0], "id,created_at,text,audio_path");
+        assert!(output.contains(",Plain entry,/tmp/test.wav\n"));
+        assert!(output.contains(",\"Buy milk, eggs\nand \"\"fancy\"\" cheese\",/tmp/test.wav\n"));
+    }
+
+    #[test]
+    fn test_export_markdown_keeps_multiline_text_in_its_bullet() {
+        let output = render_export(
+            vec![
+                history_entry(2, "2024-03-02 09:30:00", TRICKY_TEXT),
+                history_entry(1, "2024-03-01 18:00:00", "First thought"),
+            ],
+            ExportFormat::Markdown,
+        )
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "# Audetic history\n\n\
+             - **2024-03-01 18:00:00** First thought\n\
+             - **2024-03-02 09:30:00** Buy milk, eggs\n  and \"fancy\" cheese\n"
+        );
+    }
+
+    #[test]
+    fn test_render_export_json_round_trips_oldest_first() {
+        let output = render_export(
+            vec![
+                history_entry(2, "2024-03-02 09:30:00", TRICKY_TEXT),
+                history_entry(1, "2024-03-01 18:00:00", "First thought"),
+            ],
+            ExportFormat::Json,
+        )
+        .unwrap();
+
+        let entries: Vec<HistoryEntry> = serde_json::from_str(&output).unwrap();
+        let ids: Vec<i64> = entries.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(entries[1].text, TRICKY_TEXT);
+
+        let empty = render_export(Vec::new(), ExportFormat::Csv).unwrap();
+        assert_eq!(empty, "id,created_at,text,audio_path\n");
+    }
 
     #[test]
     fn test_search_params_has_filters() {
@@ -138,6 +714,43 @@ mod tests {
 
         let params = SearchParams::new().with_date_range(Some("2024-01-01".into()), None);
         assert!(params.has_filters());
+
+        let params = SearchParams::new().with_tag("work");
+        assert!(params.has_filters());
+    }
+
+    #[test]
+    fn test_only_temp_audio_is_deleted() {
+        let temp_dir = Path::new("/var/tmp/audetic");
+        assert!(is_temp_audio(
+            Path::new("/tmp/audetic_1700000000.wav"),
+            temp_dir
+        ));
+        assert!(is_temp_audio(
+            &std::env::temp_dir().join("clip.wav"),
+            temp_dir
+        ));
+        assert!(is_temp_audio(
+            Path::new("/var/tmp/audetic/audetic_1700000000.wav"),
+            temp_dir
+        ));
+        assert!(!is_temp_audio(
+            Path::new("/home/me/Recordings/talk.wav"),
+            temp_dir
+        ));
+        assert!(!is_temp_audio(Path::new("/tmpfoo/clip.wav"), temp_dir));
+        assert!(!is_temp_audio(Path::new(""), temp_dir));
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = vec![
+            " Work ".to_string(),
+            "ideas".to_string(),
+            "work".to_string(),
+            "".to_string(),
+        ];
+        assert_eq!(normalize_tags(&tags), vec!["work", "ideas"]);
     }
 
     #[test]
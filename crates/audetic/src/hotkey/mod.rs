@@ -0,0 +1,350 @@
+//! Built-in global hotkey (`[behavior] hotkey = "SUPER+R"`).
+//!
+//! Dictation is normally toggled by a window-manager keybind that curls the
+//! API, which GNOME and other desktops without a scriptable keybind config
+//! can't do. With the `hotkey` feature the daemon registers the shortcut
+//! itself: through the XDG GlobalShortcuts portal on Wayland (GNOME 48+,
+//! KDE, Hyprland), or an X grab on X11. Each press is delivered on the
+//! channel [`spawn_listener`] returns; the service loop turns it into a
+//! recording toggle.
+//!
+//! Where neither is available (macOS, Sway, no graphical session, or a build
+//! without the feature) the listener logs why and the keybind command to use
+//! instead.
+
+use anyhow::{bail, Result};
+use std::fmt;
+use std::str::FromStr;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+#[cfg(all(target_os = "linux", feature = "hotkey"))]
+mod portal;
+#[cfg(all(target_os = "linux", feature = "hotkey"))]
+mod x11;
+
+/// A parsed `[behavior] hotkey`: any number of modifiers plus one key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// The Windows/Command key; `SUPER`, `META`, `LOGO`, `WIN`, or `CMD`.
+    pub logo: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// `A`–`Z`, stored uppercase.
+    Letter(char),
+    /// `0`–`9`.
+    Digit(u8),
+    /// `F1`–`F24`.
+    Function(u8),
+    Space,
+    Enter,
+    Tab,
+    Escape,
+}
+
+impl FromStr for Hotkey {
+    type Err = anyhow::Error;
+
+    /// Parse `MOD+MOD+KEY`, case-insensitively, e.g. `SUPER+R`,
+    /// `ctrl+alt+space`, or `F9`.
+    fn from_str(spec: &str) -> Result<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut key = None;
+        for part in spec.split('+').map(str::trim) {
+            if part.is_empty() {
+                bail!("Invalid hotkey '{spec}': empty key between '+' signs");
+            }
+            let flag = match part.to_ascii_uppercase().as_str() {
+                "CTRL" | "CONTROL" => Some(&mut modifiers.ctrl),
+                "ALT" | "OPT" | "OPTION" => Some(&mut modifiers.alt),
+                "SHIFT" => Some(&mut modifiers.shift),
+                "SUPER" | "META" | "LOGO" | "WIN" | "CMD" => Some(&mut modifiers.logo),
+                _ => None,
+            };
+            match flag {
+                Some(flag) if *flag => bail!("Invalid hotkey '{spec}': '{part}' is repeated"),
+                Some(flag) => *flag = true,
+                None if key.is_some() => {
+                    bail!("Invalid hotkey '{spec}': only one non-modifier key is allowed")
+                }
+                None => key = Some(parse_key(spec, part)?),
+            }
+        }
+        match key {
+            Some(key) => Ok(Self { modifiers, key }),
+            None => bail!("Invalid hotkey '{spec}': add a key after the modifiers, e.g. SUPER+R"),
+        }
+    }
+}
+
+fn parse_key(spec: &str, part: &str) -> Result<Key> {
+    let upper = part.to_ascii_uppercase();
+    let mut chars = upper.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_uppercase() {
+            return Ok(Key::Letter(c));
+        }
+        if let Some(digit) = c.to_digit(10) {
+            return Ok(Key::Digit(digit as u8));
+        }
+    }
+    let key = match upper.as_str() {
+        "SPACE" => Key::Space,
+        "ENTER" | "RETURN" => Key::Enter,
+        "TAB" => Key::Tab,
+        "ESC" | "ESCAPE" => Key::Escape,
+        _ => match upper.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=24) => Key::Function(n),
+            _ => bail!(
+                "Invalid hotkey '{spec}': unknown key '{part}'. Use a letter, digit, F1–F24, \
+                 Space, Enter, Tab, or Escape"
+            ),
+        },
+    };
+    Ok(key)
+}
+
+impl Hotkey {
+    /// The trigger in the XDG shortcuts notation the GlobalShortcuts portal
+    /// takes as its preferred binding, e.g. `LOGO+r`.
+    pub fn portal_trigger(&self) -> String {
+        let mut parts: Vec<String> = self
+            .modifier_names(["CTRL", "ALT", "SHIFT", "LOGO"])
+            .map(str::to_string)
+            .collect();
+        parts.push(match self.key {
+            Key::Letter(c) => c.to_ascii_lowercase().to_string(),
+            Key::Digit(d) => d.to_string(),
+            Key::Function(n) => format!("F{n}"),
+            Key::Space => "space".to_string(),
+            Key::Enter => "Return".to_string(),
+            Key::Tab => "Tab".to_string(),
+            Key::Escape => "Escape".to_string(),
+        });
+        parts.join("+")
+    }
+
+    /// The key's W3C `KeyboardEvent.code` name (`KeyR`, `Digit1`, `F9`),
+    /// which is how the X11 backend names keys.
+    pub fn key_code_name(&self) -> String {
+        match self.key {
+            Key::Letter(c) => format!("Key{c}"),
+            Key::Digit(d) => format!("Digit{d}"),
+            Key::Function(n) => format!("F{n}"),
+            Key::Space => "Space".to_string(),
+            Key::Enter => "Enter".to_string(),
+            Key::Tab => "Tab".to_string(),
+            Key::Escape => "Escape".to_string(),
+        }
+    }
+
+    /// Names of the held modifiers, given what to call ctrl, alt, shift and
+    /// the logo key, in that order.
+    fn modifier_names<'a>(&self, names: [&'a str; 4]) -> impl Iterator<Item = &'a str> {
+        let held = [
+            self.modifiers.ctrl,
+            self.modifiers.alt,
+            self.modifiers.shift,
+            self.modifiers.logo,
+        ];
+        names
+            .into_iter()
+            .zip(held)
+            .filter_map(|(name, held)| held.then_some(name))
+    }
+}
+
+impl fmt::Display for Hotkey {
+    /// The canonical spelling, e.g. `SUPER+SHIFT+R` for `shift+super+r`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in self.modifier_names(["CTRL", "ALT", "SHIFT", "SUPER"]) {
+            write!(f, "{name}+")?;
+        }
+        match self.key {
+            Key::Letter(c) => write!(f, "{c}"),
+            Key::Digit(d) => write!(f, "{d}"),
+            Key::Function(n) => write!(f, "F{n}"),
+            Key::Space => f.write_str("SPACE"),
+            Key::Enter => f.write_str("ENTER"),
+            Key::Tab => f.write_str("TAB"),
+            Key::Escape => f.write_str("ESCAPE"),
+        }
+    }
+}
+
+/// How the hotkey can be registered in this session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// `org.freedesktop.portal.GlobalShortcuts`, the only option on Wayland.
+    Portal,
+    /// A passive key grab on the X server.
+    X11,
+}
+
+/// Pick a backend from the session's display variables. Wayland wins when
+/// both are set, since XWayland grabs only see keys pressed in X windows.
+pub fn detect_backend(wayland_display: bool, x_display: bool) -> Option<Backend> {
+    if !cfg!(target_os = "linux") {
+        None
+    } else if wayland_display {
+        Some(Backend::Portal)
+    } else if x_display {
+        Some(Backend::X11)
+    } else {
+        None
+    }
+}
+
+fn session_backend() -> Option<Backend> {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    let wayland = set("WAYLAND_DISPLAY")
+        || std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland");
+    detect_backend(wayland, set("DISPLAY"))
+}
+
+/// Register `spec` as a global shortcut and return a channel that receives
+/// one message per press. `None` (after logging why, and the keybind command
+/// to use instead) when the spec doesn't parse or no backend is available.
+/// A portal that refuses the shortcut later is logged the same way.
+pub fn spawn_listener(spec: &str) -> Option<mpsc::Receiver<()>> {
+    let hotkey = match spec.parse::<Hotkey>() {
+        Ok(hotkey) => hotkey,
+        Err(e) => {
+            warn!("[behavior].hotkey: {e:#}");
+            return None;
+        }
+    };
+    let Some(backend) = session_backend() else {
+        warn!(
+            "The built-in hotkey only works in a Linux desktop session. {}",
+            fallback_hint()
+        );
+        return None;
+    };
+    let (presses, rx) = mpsc::channel(4);
+    match start(hotkey, backend, presses) {
+        Ok(()) => {
+            info!("Listening for {hotkey} ({backend:?}) to toggle recording");
+            Some(rx)
+        }
+        Err(e) => {
+            warn!(
+                "Failed to register hotkey {hotkey}: {e:#}. {}",
+                fallback_hint()
+            );
+            None
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "hotkey"))]
+fn start(hotkey: Hotkey, backend: Backend, presses: mpsc::Sender<()>) -> Result<()> {
+    match backend {
+        Backend::Portal => portal::spawn(hotkey, presses),
+        Backend::X11 => x11::spawn(hotkey, presses),
+    }
+}
+
+#[cfg(not(all(target_os = "linux", feature = "hotkey")))]
+fn start(_hotkey: Hotkey, _backend: Backend, _presses: mpsc::Sender<()>) -> Result<()> {
+    bail!("this build of Audetic was compiled without the `hotkey` feature")
+}
+
+/// What to do instead when the built-in hotkey can't be used.
+pub(crate) fn fallback_hint() -> String {
+    let toggle =
+        crate::keybind::curl_post(&crate::api::url::api_url(crate::api::url::paths::TOGGLE));
+    format!("Bind a key to `{toggle}` in your desktop's keyboard shortcut settings instead")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(spec: &str) -> Hotkey {
+        spec.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_modifiers_and_key_case_insensitively() {
+        let hotkey = parse("SUPER+R");
+        assert!(hotkey.modifiers.logo);
+        assert!(!hotkey.modifiers.ctrl && !hotkey.modifiers.alt && !hotkey.modifiers.shift);
+        assert_eq!(hotkey.key, Key::Letter('R'));
+
+        assert_eq!(parse(" ctrl + Alt + space "), parse("CONTROL+OPTION+SPACE"));
+        assert_eq!(parse("meta+r"), parse("win+R"));
+        assert_eq!(parse("F9").key, Key::Function(9));
+        assert_eq!(parse("shift+1").key, Key::Digit(1));
+        assert_eq!(parse("ctrl+return").key, Key::Enter);
+    }
+
+    #[test]
+    fn rejects_malformed_hotkeys() {
+        for spec in [
+            "",
+            "SUPER",
+            "SUPER+",
+            "SUPER++R",
+            "SUPER+R+T",
+            "SUPER+SUPER+R",
+            "SUPER+F25",
+            "SUPER+F0",
+            "SUPER+PageUp",
+            "HYPER+R",
+        ] {
+            assert!(
+                spec.parse::<Hotkey>().is_err(),
+                "{spec:?} should be rejected"
+            );
+        }
+        let err = "SUPER+PageUp".parse::<Hotkey>().unwrap_err();
+        assert!(err.to_string().contains("unknown key 'PageUp'"));
+    }
+
+    #[test]
+    fn displays_in_canonical_order() {
+        assert_eq!(parse("shift+super+r").to_string(), "SHIFT+SUPER+R");
+        assert_eq!(parse("alt+ctrl+esc").to_string(), "CTRL+ALT+ESCAPE");
+        assert_eq!(parse("f12").to_string(), "F12");
+    }
+
+    #[test]
+    fn portal_trigger_uses_xdg_shortcut_names() {
+        assert_eq!(parse("SUPER+R").portal_trigger(), "LOGO+r");
+        assert_eq!(
+            parse("super+shift+space").portal_trigger(),
+            "SHIFT+LOGO+space"
+        );
+        assert_eq!(parse("ctrl+alt+enter").portal_trigger(), "CTRL+ALT+Return");
+        assert_eq!(parse("F9").portal_trigger(), "F9");
+    }
+
+    #[test]
+    fn key_code_names_follow_w3c_codes() {
+        assert_eq!(parse("SUPER+R").key_code_name(), "KeyR");
+        assert_eq!(parse("ctrl+7").key_code_name(), "Digit7");
+        assert_eq!(parse("F2").key_code_name(), "F2");
+        assert_eq!(parse("alt+space").key_code_name(), "Space");
+    }
+
+    #[test]
+    fn wayland_sessions_use_the_portal() {
+        let expected = |backend| cfg!(target_os = "linux").then_some(backend);
+        assert_eq!(detect_backend(true, true), expected(Backend::Portal));
+        assert_eq!(detect_backend(true, false), expected(Backend::Portal));
+        assert_eq!(detect_backend(false, true), expected(Backend::X11));
+        assert_eq!(detect_backend(false, false), None);
+    }
+}
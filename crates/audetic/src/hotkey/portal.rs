@@ -0,0 +1,78 @@
+//! Wayland backend: `org.freedesktop.portal.GlobalShortcuts`.
+//!
+//! Compositors don't let clients grab keys, so the shortcut is requested
+//! from the desktop portal instead. The hotkey is only a preferred trigger:
+//! the desktop shows it to the user on first use, who can accept or change
+//! it, and it sends an `Activated` signal on each press from then on.
+//! Portals without the interface (Sway's wlroots portal, older GNOME) fail
+//! here, and the failure is logged with the keybind fallback.
+
+use anyhow::{Context, Result};
+use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tracing::{debug, info, warn};
+
+use super::{fallback_hint, Hotkey};
+
+const SHORTCUT_ID: &str = "toggle-recording";
+
+/// Bind the shortcut in the background. Binding waits on the desktop (and
+/// possibly a confirmation dialog), so its outcome is logged rather than
+/// returned.
+pub fn spawn(hotkey: Hotkey, presses: mpsc::Sender<()>) -> Result<()> {
+    tokio::spawn(async move {
+        if let Err(e) = listen(hotkey, presses).await {
+            warn!(
+                "The desktop portal didn't register hotkey {hotkey}: {e:#}. {}",
+                fallback_hint()
+            );
+        }
+    });
+    Ok(())
+}
+
+async fn listen(hotkey: Hotkey, presses: mpsc::Sender<()>) -> Result<()> {
+    let portal = GlobalShortcuts::new()
+        .await
+        .context("No GlobalShortcuts portal on this desktop")?;
+    // The session must outlive the loop below; dropping it unbinds.
+    let session = portal
+        .create_session()
+        .await
+        .context("Failed to open a shortcuts session")?;
+
+    let trigger = hotkey.portal_trigger();
+    let shortcut = NewShortcut::new(SHORTCUT_ID, "Toggle Audetic dictation")
+        .preferred_trigger(trigger.as_str());
+    let bound = portal
+        .bind_shortcuts(&session, &[shortcut], None)
+        .await
+        .context("Failed to bind the shortcut")?
+        .response()
+        .context("The shortcut was not accepted")?;
+    match bound.shortcuts().iter().find(|s| s.id() == SHORTCUT_ID) {
+        Some(shortcut) => info!(
+            "Hotkey bound through the desktop portal: {}",
+            shortcut.trigger_description()
+        ),
+        None => warn!("The desktop portal bound no trigger for dictation yet"),
+    }
+
+    let activations = portal
+        .receive_activated()
+        .await
+        .context("Failed to listen for shortcut presses")?;
+    tokio::pin!(activations);
+    while let Some(activated) = activations.next().await {
+        if activated.shortcut_id() != SHORTCUT_ID {
+            continue;
+        }
+        debug!("Hotkey {} pressed", hotkey);
+        if presses.send(()).await.is_err() {
+            break;
+        }
+    }
+    drop(session);
+    Ok(())
+}
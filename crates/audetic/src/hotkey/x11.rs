@@ -0,0 +1,71 @@
+//! X11 backend: a passive grab on the root window via `global-hotkey`.
+
+use anyhow::{anyhow, Context, Result};
+use global_hotkey::hotkey::{Code, HotKey, Modifiers};
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use std::str::FromStr;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+use super::Hotkey;
+
+/// Grab `hotkey` on a dedicated thread, which blocks on key events for the
+/// life of the daemon. Returns once the grab is in place, or with the
+/// reason it couldn't be (usually another client already holds the combo).
+pub fn spawn(hotkey: Hotkey, presses: mpsc::Sender<()>) -> Result<()> {
+    let (registered_tx, registered_rx) = std::sync::mpsc::channel();
+    std::thread::Builder::new()
+        .name("audetic-hotkey".to_string())
+        .spawn(move || {
+            // The manager owns the grab, so it lives on this thread for as
+            // long as presses are being read.
+            let (_manager, id) = match register(&hotkey) {
+                Ok(registered) => {
+                    let _ = registered_tx.send(Ok(()));
+                    registered
+                }
+                Err(e) => {
+                    let _ = registered_tx.send(Err(e));
+                    return;
+                }
+            };
+            let events = GlobalHotKeyEvent::receiver();
+            while let Ok(event) = events.recv() {
+                if event.id() != id || event.state() != HotKeyState::Pressed {
+                    continue;
+                }
+                debug!("Hotkey {} pressed", hotkey);
+                if presses.blocking_send(()).is_err() {
+                    break;
+                }
+            }
+        })
+        .context("Failed to start the hotkey thread")?;
+
+    registered_rx
+        .recv()
+        .context("Hotkey thread exited before registering")?
+}
+
+fn register(hotkey: &Hotkey) -> Result<(GlobalHotKeyManager, u32)> {
+    let code = Code::from_str(&hotkey.key_code_name())
+        .map_err(|_| anyhow!("X11 has no key named {}", hotkey.key_code_name()))?;
+    let mut modifiers = Modifiers::empty();
+    for (held, modifier) in [
+        (hotkey.modifiers.ctrl, Modifiers::CONTROL),
+        (hotkey.modifiers.alt, Modifiers::ALT),
+        (hotkey.modifiers.shift, Modifiers::SHIFT),
+        (hotkey.modifiers.logo, Modifiers::SUPER),
+    ] {
+        if held {
+            modifiers |= modifier;
+        }
+    }
+
+    let manager = GlobalHotKeyManager::new().context("Failed to connect to the X server")?;
+    let grab = HotKey::new(Some(modifiers), code);
+    manager
+        .register(grab)
+        .with_context(|| format!("{hotkey} is already grabbed by another application"))?;
+    Ok((manager, grab.id()))
+}
@@ -4,6 +4,7 @@
 
 use super::{wait_for_daemon, InstallOptions};
 use crate::api::url;
+use crate::global;
 use anyhow::{anyhow, bail, Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -50,16 +51,16 @@ struct InstallPaths {
 
 impl InstallPaths {
     fn resolve() -> Result<Self> {
-        let data = dirs::data_local_dir()
-            .ok_or_else(|| anyhow!("Could not resolve XDG_DATA_HOME / ~/.local/share"))?;
-        let config = dirs::config_local_dir()
-            .ok_or_else(|| anyhow!("Could not resolve XDG_CONFIG_HOME / ~/.config"))?;
-
-        let data_dir = data.join("audetic");
+        let config_dir = global::config_dir()?;
+        let data_dir = global::data_dir()?;
         let installed_dir = data_dir.join("bin");
         let installed_binary = installed_dir.join("audeticd");
-        let systemd_unit = config.join("systemd").join("user").join(SERVICE_NAME);
-        let config_dir = config.join("audetic");
+        // Where systemd looks for user units; not ours to relocate.
+        let systemd_unit = dirs::config_dir()
+            .ok_or_else(|| anyhow!("Could not resolve XDG_CONFIG_HOME / ~/.config"))?
+            .join("systemd")
+            .join("user")
+            .join(SERVICE_NAME);
 
         Ok(Self {
             installed_dir,
@@ -120,17 +121,32 @@ fn write_unit(paths: &InstallPaths) -> Result<()> {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create {}", parent.display()))?;
     }
-    let exec_start = paths
-        .installed_binary
-        .to_str()
-        .ok_or_else(|| anyhow!("Install path contains non-UTF8 bytes; refusing to render unit"))?;
-    let unit = SERVICE_TEMPLATE.replace("__EXEC_START__", exec_start);
+    let utf8 = |path: &Path| {
+        path.to_str()
+            .ok_or_else(|| anyhow!("Install path contains non-UTF8 bytes; refusing to render unit"))
+    };
+    let exec_start = utf8(&paths.installed_binary)?;
+    let config_dir = unit_value(utf8(&paths.config_dir)?);
+    let data_dir = unit_value(utf8(&paths.data_dir)?);
+    let unit = SERVICE_TEMPLATE
+        .replace("__EXEC_START__", exec_start)
+        .replace("__CONFIG_DIR__", &config_dir)
+        .replace("__DATA_DIR__", &data_dir);
     fs::write(&paths.systemd_unit, unit)
         .with_context(|| format!("Failed to write {}", paths.systemd_unit.display()))?;
     println!("  · Wrote {}", paths.systemd_unit.display());
     Ok(())
 }
 
+/// Escape `value` for a unit file: `%` starts a specifier, and the value goes
+/// inside double quotes.
+fn unit_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%")
+}
+
 fn daemon_reload() -> Result<()> {
     println!("  · systemctl --user daemon-reload");
     let status = Command::new("systemctl")
@@ -9,6 +9,7 @@
 
 use super::{wait_for_daemon, InstallOptions};
 use crate::api::url;
+use crate::global;
 use anyhow::{anyhow, bail, Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -96,15 +97,10 @@ impl InstallPaths {
         let log_dir = home.join("Library").join("Logs").join("Audetic");
         let log_path = log_dir.join("audetic.log");
 
-        // `dirs::config_dir()` / `dirs::data_dir()` both resolve to
-        // `~/Library/Application Support` on macOS — same tree the rest of
-        // the daemon uses for state.
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow!("Could not resolve ~/Library/Application Support"))?
-            .join("audetic");
-        let data_dir = dirs::data_dir()
-            .ok_or_else(|| anyhow!("Could not resolve ~/Library/Application Support"))?
-            .join("audetic");
+        // `~/Library/Application Support/audetic` unless overridden, the same
+        // lookup the daemon and CLI use for state.
+        let config_dir = global::config_dir()?;
+        let data_dir = global::data_dir()?;
 
         Ok(Self {
             source_bundle,
@@ -207,11 +203,21 @@ fn write_plist(paths: &InstallPaths) -> Result<()> {
         .home
         .to_str()
         .ok_or_else(|| anyhow!("$HOME contains non-UTF8 bytes"))?;
+    let config_dir = paths
+        .config_dir
+        .to_str()
+        .ok_or_else(|| anyhow!("Config directory contains non-UTF8 bytes"))?;
+    let data_dir = paths
+        .data_dir
+        .to_str()
+        .ok_or_else(|| anyhow!("Data directory contains non-UTF8 bytes"))?;
 
     let plist = PLIST_TEMPLATE
         .replace("__EXEC_START__", exec)
         .replace("__LOG_PATH__", log)
-        .replace("__HOME__", home);
+        .replace("__HOME__", home)
+        .replace("__CONFIG_DIR__", config_dir)
+        .replace("__DATA_DIR__", data_dir);
 
     fs::write(&paths.plist_path, plist)
         .with_context(|| format!("Failed to write {}", paths.plist_path.display()))?;
@@ -3,7 +3,7 @@
 //! Linux installs a systemd user unit and `enable --now`s it. macOS
 //! installs a LaunchAgent at `~/Library/LaunchAgents/ai.audetic.daemon.plist`
 //! and `launchctl bootstrap`s it. Both flows finish with a readiness probe
-//! against 127.0.0.1:3737 and open the web UI in a browser.
+//! against 127.0.0.1 on the API port and open the web UI in a browser.
 
 use crate::api::url;
 use anyhow::{bail, Context, Result};
@@ -114,7 +114,7 @@ pub(crate) fn set_executable(path: &Path) -> Result<()> {
 /// the supervisor has been told to start the service.
 async fn wait_for_daemon(timeout: Duration) -> Result<()> {
     let probe_url = url::api_url(url::paths::VERSION);
-    let bind_addr = format!("{}:{}", url::HOST, url::DEFAULT_PORT);
+    let bind_addr = format!("{}:{}", url::HOST, url::port());
     println!("  · Waiting for daemon to bind {bind_addr}");
     let client = reqwest::Client::builder()
         .timeout(Duration::from_millis(1000))
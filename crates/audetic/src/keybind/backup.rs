@@ -24,6 +24,12 @@ impl BackupManager {
         Ok(Self { backup_dir })
     }
 
+    /// A backup manager writing to `backup_dir`, for tests.
+    #[cfg(test)]
+    pub(super) fn in_dir(backup_dir: PathBuf) -> Self {
+        Self { backup_dir }
+    }
+
     /// Create a backup of the given config file
     ///
     /// Returns the path to the backup file
@@ -8,7 +8,8 @@
 //! For most use cases, use the high-level functions:
 //! - [`get_status()`] - Check current keybind status
 //! - [`install()`] - Install a keybinding
-//! - [`uninstall()`] - Remove a keybinding
+//! - [`uninstall()`] - Remove Audetic keybindings from every config file
+//! - [`cleanup()`] - Collapse duplicate Audetic sections left by old installs
 //!
 //! # Low-level API
 //!
@@ -26,9 +27,9 @@ pub mod writer;
 pub use backup::BackupManager;
 pub use discovery::{discover_config, ConfigDiscovery};
 pub use parser::{parse_bindings, HyprBinding, Modifier, Modifiers};
-pub use writer::{remove_binding, write_binding};
+pub use writer::{collapse_duplicates, strip_audetic_bindings, write_binding, DuplicateScan};
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use discovery::get_all_config_files;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -46,6 +47,27 @@ pub fn audetic_toggle_endpoint() -> String {
     crate::api::url::api_url(crate::api::url::paths::TOGGLE)
 }
 
+/// `curl` command that POSTs to `url`, sending `[api] auth_token` when the
+/// API is token-protected.
+pub fn curl_post(url: &str) -> String {
+    match crate::api::url::auth_token() {
+        Some(token) => format!("curl -X POST -H 'Authorization: Bearer {token}' {url}"),
+        None => format!("curl -X POST {url}"),
+    }
+}
+
+/// Whether a bind command talks to the daemon, on the configured port or the
+/// default one (binds written before the port was changed).
+pub(crate) fn targets_daemon(command: &str) -> bool {
+    let configured = crate::api::url::port();
+    [configured, crate::api::url::DEFAULT_PORT]
+        .iter()
+        .any(|port| {
+            command.contains(&format!("127.0.0.1:{port}"))
+                || command.contains(&format!("localhost:{port}"))
+        })
+}
+
 /// Represents a proposed keybinding to install
 #[derive(Debug, Clone)]
 pub struct ProposedBinding {
@@ -61,7 +83,7 @@ impl Default for ProposedBinding {
             modifiers: Modifiers::from_strs(DEFAULT_MODIFIERS),
             key: DEFAULT_KEY.to_string(),
             description: "Audetic".to_string(),
-            command: format!("curl -X POST {}", audetic_toggle_endpoint()),
+            command: curl_post(&audetic_toggle_endpoint()),
         }
     }
 }
@@ -76,14 +98,45 @@ impl ProposedBinding {
         }
     }
 
-    /// Format the binding as a Hyprland bindd directive
+    /// Format the binding as a Hyprland bindd directive.
+    ///
+    /// `#` in the command is doubled, Hyprland's escape for a literal `#`,
+    /// so it isn't read as the start of a comment.
     pub fn to_hyprland_line(&self) -> String {
         format!(
             "bindd = {}, {}, {}, exec, {}",
-            self.modifiers, self.key, self.description, self.command
+            self.modifiers,
+            self.key,
+            self.description,
+            self.command.replace('#', "##")
         )
     }
 
+    /// Reject commands that can't be written as a single directive.
+    ///
+    /// A newline (or any other control character) would end the directive
+    /// and let the rest of the command through as new config lines, and
+    /// braces open or close Hyprland config categories. Commands that need
+    /// these belong in a script the binding runs instead.
+    pub fn validate(&self) -> Result<()> {
+        let command = self.command.trim();
+        if command.is_empty() {
+            return Err(anyhow!("Keybinding command is empty"));
+        }
+        if let Some(c) = command.chars().find(|c| c.is_control()) {
+            return Err(anyhow!(
+                "Keybinding command contains a control character ({:?}); Hyprland directives must fit on one line",
+                c
+            ));
+        }
+        if command.contains(['{', '}']) {
+            return Err(anyhow!(
+                "Keybinding command contains '{{' or '}}', which Hyprland reads as a config block; move it into a script"
+            ));
+        }
+        Ok(())
+    }
+
     /// Get a display string for the keybinding (e.g., "SUPER + R")
     pub fn display_key(&self) -> String {
         if self.modifiers.0.is_empty() {
@@ -129,8 +182,7 @@ pub fn find_audetic_bindings(bindings: &[HyprBinding]) -> Vec<&HyprBinding> {
     bindings
         .iter()
         .filter(|b| {
-            b.command.contains("127.0.0.1:3737")
-                || b.command.contains("localhost:3737")
+            targets_daemon(&b.command)
                 || b.description
                     .as_ref()
                     .map(|d| d.to_lowercase().contains("audetic"))
@@ -180,14 +232,40 @@ pub struct InstallResult {
 /// Result of an uninstall operation
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UninstallResult {
-    /// Whether a binding was actually removed
-    pub removed: bool,
-    /// Path to the backup file created (if any)
-    #[schema(value_type = Option<String>)]
-    pub backup_path: Option<PathBuf>,
-    /// Path to the config file modified
+    /// Number of Audetic bindings removed (or, in a dry run, to be removed)
+    pub removed_count: usize,
+    /// The bindings, with the file and line each was found at
+    pub removed: Vec<RemovedLine>,
+    /// Config files that held Audetic bindings
+    #[schema(value_type = Vec<String>)]
+    pub files: Vec<PathBuf>,
+    /// Backups created for each modified file; empty for a dry run
+    #[schema(value_type = Vec<String>)]
+    pub backup_paths: Vec<PathBuf>,
+    /// Whether files were left untouched
+    pub dry_run: bool,
+}
+
+/// A config line dropped by [`cleanup()`] or [`uninstall()`]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemovedLine {
+    /// File the line was removed from
     #[schema(value_type = String)]
-    pub config_path: PathBuf,
+    pub file: PathBuf,
+    /// 1-based line number before cleanup
+    pub line: usize,
+    /// The removed line
+    pub content: String,
+}
+
+/// Result of a cleanup operation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CleanupResult {
+    /// Duplicate markers and bindings that were removed
+    pub removed: Vec<RemovedLine>,
+    /// Backups created for each modified file
+    #[schema(value_type = Vec<String>)]
+    pub backup_paths: Vec<PathBuf>,
 }
 
 // ============================================================================
@@ -253,6 +331,7 @@ pub fn install(key: Option<&str>, dry_run: bool) -> Result<Option<InstallResult>
     } else {
         ProposedBinding::default()
     };
+    proposed.validate()?;
 
     // Check for conflicts
     let all_files = get_all_config_files(&discovery);
@@ -291,35 +370,139 @@ pub fn install(key: Option<&str>, dry_run: bool) -> Result<Option<InstallResult>
     }))
 }
 
-/// Uninstall the Audetic keybinding.
+/// Uninstall every Audetic keybinding.
+///
+/// Bindings can end up spread over several files (an old install in
+/// `hyprland.conf`, a newer one in a sourced `bindings.conf`), so every
+/// discovered config file is checked, not just the one [`install()`] writes.
+/// Each modified file is backed up before it is rewritten.
 ///
 /// # Arguments
-/// * `dry_run` - If true, only check without making changes.
+/// * `dry_run` - If true, only report what would be removed.
+pub fn uninstall(dry_run: bool) -> Result<UninstallResult> {
+    let discovery = discover_config()?;
+    if discovery.writable_config().is_none() {
+        return Err(anyhow!("No Hyprland configuration found"));
+    }
+
+    let files: Vec<PathBuf> = get_all_config_files(&discovery)
+        .into_iter()
+        .cloned()
+        .collect();
+    let backup_manager = if dry_run {
+        None
+    } else {
+        Some(BackupManager::new()?)
+    };
+
+    uninstall_from(&files, backup_manager.as_ref())
+}
+
+/// Strip Audetic bindings from `files`, writing (after a backup) only when
+/// given a backup manager.
+fn uninstall_from(
+    files: &[PathBuf],
+    backup_manager: Option<&BackupManager>,
+) -> Result<UninstallResult> {
+    let mut result = UninstallResult {
+        removed_count: 0,
+        removed: Vec::new(),
+        files: Vec::new(),
+        backup_paths: Vec::new(),
+        dry_run: backup_manager.is_none(),
+    };
+
+    for file in files {
+        let content = match std::fs::read_to_string(file) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let (new_content, removed) = strip_audetic_bindings(&content, file);
+        if new_content == content {
+            continue;
+        }
+
+        if let Some(backup_manager) = backup_manager {
+            result
+                .backup_paths
+                .push(backup_manager.create_backup(file)?);
+            std::fs::write(file, new_content)
+                .with_context(|| format!("Failed to write config file: {:?}", file))?;
+        }
+
+        if !removed.is_empty() {
+            result.files.push(file.clone());
+        }
+        result
+            .removed
+            .extend(removed.into_iter().map(|(line, content)| RemovedLine {
+                file: file.clone(),
+                line,
+                content,
+            }));
+    }
+
+    result.removed_count = result.removed.len();
+    Ok(result)
+}
+
+/// Remove duplicate Audetic sections and toggle bindings.
 ///
-/// # Returns
-/// * `Ok(Some(UninstallResult))` - Result of the uninstall operation
-/// * `Ok(None)` - Dry run mode, no changes made
-pub fn uninstall(dry_run: bool) -> Result<Option<UninstallResult>> {
+/// Repeated installs with the old append-only writer could leave several
+/// section markers and binds behind. This scans every discovered config file
+/// (the writable one first, so the section [`install()`] maintains is the one
+/// kept), keeps the first marker and toggle bind it sees, and removes the rest.
+/// Each modified file is backed up before it is rewritten.
+pub fn cleanup() -> Result<CleanupResult> {
     let discovery = discover_config()?;
-    let config_path = discovery
+    let writable = discovery
         .writable_config()
         .ok_or_else(|| anyhow!("No Hyprland configuration found"))?
         .clone();
 
-    if dry_run {
-        return Ok(None);
+    let mut files = vec![writable.clone()];
+    for file in get_all_config_files(&discovery) {
+        if *file != writable {
+            files.push(file.clone());
+        }
     }
 
+    let mut scan = DuplicateScan::default();
     let backup_manager = BackupManager::new()?;
-    let backup_path = backup_manager.create_backup(&config_path)?;
+    let mut result = CleanupResult {
+        removed: Vec::new(),
+        backup_paths: Vec::new(),
+    };
 
-    let removed = remove_binding(&config_path)?;
+    for file in files {
+        let content = match std::fs::read_to_string(&file) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
 
-    Ok(Some(UninstallResult {
-        removed,
-        backup_path: Some(backup_path),
-        config_path,
-    }))
+        let (new_content, removed) = collapse_duplicates(&content, &mut scan);
+        if removed.is_empty() {
+            continue;
+        }
+
+        result
+            .backup_paths
+            .push(backup_manager.create_backup(&file)?);
+
+        std::fs::write(&file, new_content)
+            .with_context(|| format!("Failed to write config file: {:?}", file))?;
+
+        result
+            .removed
+            .extend(removed.into_iter().map(|(line, content)| RemovedLine {
+                file: file.clone(),
+                line,
+                content,
+            }));
+    }
+
+    Ok(result)
 }
 
 /// Parse a key string like "SUPER SHIFT, R" or "SUPER+R" into a ProposedBinding.
@@ -346,3 +529,63 @@ pub fn parse_key_string(s: &str) -> Result<ProposedBinding> {
 
     Ok(ProposedBinding::new(&mod_strs, &key))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn uninstall_removes_bindings_from_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let toggle = format!(
+            "bindd = SUPER, R, Audetic, exec, curl -X POST {}",
+            audetic_toggle_endpoint()
+        );
+        let main = dir.path().join("hyprland.conf");
+        let bindings = dir.path().join("bindings.conf");
+        let main_content = format!(
+            "source = {}\nbind = SUPER, F9, exec, curl -X POST {}\nbind = SUPER, SPACE, exec, rofi\n",
+            bindings.display(),
+            audetic_toggle_endpoint()
+        );
+        let bindings_content =
+            format!("bind = SUPER, RETURN, exec, kitty\n\n{AUDETIC_SECTION_MARKER}\n{toggle}\n");
+        fs::write(&main, &main_content).unwrap();
+        fs::write(&bindings, &bindings_content).unwrap();
+        let files = vec![main.clone(), bindings.clone()];
+
+        let preview = uninstall_from(&files, None).unwrap();
+        assert!(preview.dry_run);
+        assert_eq!(preview.removed_count, 2);
+        assert_eq!(preview.files, files);
+        assert_eq!(preview.removed[0].line, 2);
+        assert_eq!(preview.removed[1].file, bindings);
+        assert_eq!(preview.removed[1].content, toggle);
+        assert!(preview.backup_paths.is_empty());
+        assert_eq!(fs::read_to_string(&main).unwrap(), main_content);
+        assert_eq!(fs::read_to_string(&bindings).unwrap(), bindings_content);
+
+        let backups = BackupManager::in_dir(dir.path().join("backups"));
+        fs::create_dir_all(dir.path().join("backups")).unwrap();
+        let result = uninstall_from(&files, Some(&backups)).unwrap();
+        assert!(!result.dry_run);
+        assert_eq!(result.removed_count, 2);
+        assert_eq!(result.backup_paths.len(), 2);
+        assert_eq!(
+            fs::read_to_string(&main).unwrap(),
+            format!(
+                "source = {}\nbind = SUPER, SPACE, exec, rofi\n",
+                bindings.display()
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(&bindings).unwrap(),
+            "bind = SUPER, RETURN, exec, kitty\n"
+        );
+
+        let again = uninstall_from(&files, Some(&backups)).unwrap();
+        assert_eq!(again.removed_count, 0);
+        assert!(again.backup_paths.is_empty());
+    }
+}
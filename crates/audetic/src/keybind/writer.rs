@@ -1,17 +1,24 @@
 //! Safe file modification for Hyprland keybindings.
 
 use anyhow::{Context, Result};
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
-use super::{ProposedBinding, AUDETIC_SECTION_MARKER};
+use super::parser::parse_bindings_from_content;
+use super::{find_audetic_bindings, targets_daemon, ProposedBinding, AUDETIC_SECTION_MARKER};
 
 /// Write a binding to the config file
 ///
 /// This function will:
 /// 1. Look for an existing Audetic section and update it
 /// 2. Or append a new section at the end of the file
+///
+/// The binding is validated first (see [`ProposedBinding::validate`]); a
+/// rejected command leaves the file untouched.
 pub fn write_binding(config_path: &Path, binding: &ProposedBinding) -> Result<()> {
+    binding.validate()?;
+
     let content = fs::read_to_string(config_path)
         .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
 
@@ -99,37 +106,113 @@ fn find_section_end(section: &str) -> usize {
     last_content_end
 }
 
-/// Remove Audetic binding from the config file
-pub fn remove_binding(config_path: &Path) -> Result<bool> {
-    let content = fs::read_to_string(config_path)
-        .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
+/// Drop every Audetic-managed binding from `content`, along with the section
+/// markers [`write_binding`] puts above them.
+///
+/// Bindings are found by parsing `content` (see [`find_audetic_bindings`]),
+/// so a bind pointing at the daemon goes whether or not it sits under a
+/// marker. Returns the rewritten content and the removed bindings as
+/// `(line_number, line)` pairs (1-based, numbered against the original
+/// content). Content with nothing to remove is returned unchanged.
+pub fn strip_audetic_bindings(content: &str, path: &Path) -> (String, Vec<(usize, String)>) {
+    let parsed = parse_bindings_from_content(content, path);
+    let audetic_lines: HashSet<usize> = find_audetic_bindings(&parsed)
+        .iter()
+        .map(|binding| binding.source.line)
+        .collect();
 
-    if let Some(start_idx) = content.find(AUDETIC_SECTION_MARKER) {
-        let after_marker = &content[start_idx..];
-        let section_end = find_section_end(after_marker);
-        let end_idx = start_idx + section_end;
+    let mut kept: Vec<&str> = Vec::new();
+    let mut removed = Vec::new();
+    let mut dropped_marker = false;
 
-        let mut new_content = String::new();
-        new_content.push_str(&content[..start_idx]);
-
-        // Skip any trailing newlines from the removed section
-        let remaining = content[end_idx..].trim_start_matches('\n');
-        if !remaining.is_empty() {
-            new_content.push_str(remaining);
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim() == AUDETIC_SECTION_MARKER {
+            // The section was written after a blank line; take that too.
+            if kept.last().is_some_and(|l| l.trim().is_empty()) {
+                kept.pop();
+            }
+            dropped_marker = true;
+            continue;
         }
+        if audetic_lines.contains(&(idx + 1)) {
+            removed.push((idx + 1, line.to_string()));
+            continue;
+        }
+        kept.push(line);
+    }
 
-        // Ensure file ends with newline
-        if !new_content.ends_with('\n') {
-            new_content.push('\n');
+    if removed.is_empty() && !dropped_marker {
+        return (content.to_string(), removed);
+    }
+
+    let mut new_content = kept.join("\n");
+    if !new_content.is_empty() {
+        new_content.push('\n');
+    }
+    (new_content, removed)
+}
+
+/// Tracks which Audetic lines have already been seen while collapsing
+/// duplicates, so the scan can span several config files.
+#[derive(Debug, Default)]
+pub struct DuplicateScan {
+    kept_marker: bool,
+    kept_bind: bool,
+}
+
+/// Drop every Audetic section marker and toggle bind after the first one seen.
+///
+/// Returns the rewritten content and the removed lines as `(line_number,
+/// line)` pairs (1-based, numbered against the original content). Content with
+/// nothing to remove is returned unchanged.
+pub fn collapse_duplicates(
+    content: &str,
+    scan: &mut DuplicateScan,
+) -> (String, Vec<(usize, String)>) {
+    let mut kept: Vec<&str> = Vec::new();
+    let mut removed = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim() == AUDETIC_SECTION_MARKER {
+            if scan.kept_marker {
+                // The append-only writer put a blank line before each section;
+                // drop it along with the marker so no gap is left behind.
+                if kept.last().is_some_and(|l| l.trim().is_empty()) {
+                    kept.pop();
+                }
+                removed.push((idx + 1, line.to_string()));
+                continue;
+            }
+            scan.kept_marker = true;
+        } else if is_audetic_toggle_bind(line) {
+            if scan.kept_bind {
+                removed.push((idx + 1, line.to_string()));
+                continue;
+            }
+            scan.kept_bind = true;
         }
+        kept.push(line);
+    }
 
-        fs::write(config_path, new_content)
-            .with_context(|| format!("Failed to write config file: {:?}", config_path))?;
+    if removed.is_empty() {
+        return (content.to_string(), removed);
+    }
 
-        Ok(true)
-    } else {
-        Ok(false)
+    let mut new_content = kept.join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
     }
+    (new_content, removed)
+}
+
+/// Whether a config line is a bind pointing at the dictation toggle endpoint.
+/// Meeting binds (`/meetings/toggle`) are left alone.
+fn is_audetic_toggle_bind(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("bind")
+        && targets_daemon(trimmed)
+        && trimmed.contains("/toggle")
+        && !trimmed.contains("/meetings/")
 }
 
 #[cfg(test)]
@@ -173,4 +256,98 @@ mod tests {
         assert!(!result.contains("old-command"));
         assert!(result.contains("# Other stuff"));
     }
+
+    fn binding_with_command(command: &str) -> ProposedBinding {
+        ProposedBinding {
+            command: command.to_string(),
+            ..ProposedBinding::default()
+        }
+    }
+
+    #[test]
+    fn test_write_rejects_command_with_newline() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let original = "bind = SUPER, SPACE, exec, rofi\n";
+        fs::write(file.path(), original).unwrap();
+
+        let binding = binding_with_command(
+            "curl -X POST http://127.0.0.1:3737/api/toggle\nexec-once = rm -rf ~",
+        );
+        let err = write_binding(file.path(), &binding).unwrap_err();
+
+        assert!(err.to_string().contains("one line"));
+        assert_eq!(fs::read_to_string(file.path()).unwrap(), original);
+    }
+
+    #[test]
+    fn test_write_accepts_default_curl() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(file.path(), "bind = SUPER, SPACE, exec, rofi\n").unwrap();
+
+        write_binding(file.path(), &ProposedBinding::default()).unwrap();
+
+        let written = fs::read_to_string(file.path()).unwrap();
+        assert!(written.contains(&format!("exec, curl -X POST {}", audetic_toggle_endpoint())));
+    }
+
+    #[test]
+    fn test_validate_rejects_braces_and_empty_commands() {
+        assert!(binding_with_command("curl http://x }").validate().is_err());
+        assert!(binding_with_command("   ").validate().is_err());
+    }
+
+    #[test]
+    fn test_hash_in_command_is_escaped() {
+        let line = binding_with_command("curl -H 'X-Token: a#b' http://x").to_hyprland_line();
+        assert!(line.ends_with("exec, curl -H 'X-Token: a##b' http://x"));
+    }
+
+    #[test]
+    fn test_collapse_two_sections_to_one() {
+        let line = format!(
+            "bindd = SUPER, R, Audetic, exec, curl -X POST {}",
+            audetic_toggle_endpoint()
+        );
+        let content = format!(
+            "# Existing config\nbind = SUPER, SPACE, exec, rofi\n\n{marker}\n{line}\n\n{marker}\n{line}\n",
+            marker = AUDETIC_SECTION_MARKER,
+        );
+
+        let mut scan = DuplicateScan::default();
+        let (result, removed) = collapse_duplicates(&content, &mut scan);
+
+        assert_eq!(result.matches(AUDETIC_SECTION_MARKER).count(), 1);
+        assert_eq!(result.matches(line.as_str()).count(), 1);
+        assert!(result.contains("bind = SUPER, SPACE, exec, rofi"));
+        assert_eq!(
+            removed,
+            vec![(7, AUDETIC_SECTION_MARKER.to_string()), (8, line.clone())]
+        );
+        assert_eq!(
+            result,
+            format!(
+                "# Existing config\nbind = SUPER, SPACE, exec, rofi\n\n{AUDETIC_SECTION_MARKER}\n{line}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_collapse_spans_files_and_keeps_meeting_bind() {
+        let toggle = format!(
+            "bindd = SUPER, R, Audetic, exec, curl -X POST {}",
+            audetic_toggle_endpoint()
+        );
+        let meeting = "bindd = SUPER SHIFT, R, Audetic Meeting, exec, curl -X POST http://127.0.0.1:3737/api/meetings/toggle";
+        let first = format!("{AUDETIC_SECTION_MARKER}\n{toggle}\n");
+        let second = format!("{meeting}\n{AUDETIC_SECTION_MARKER}\n{toggle}\n");
+
+        let mut scan = DuplicateScan::default();
+        let (first_out, first_removed) = collapse_duplicates(&first, &mut scan);
+        let (second_out, second_removed) = collapse_duplicates(&second, &mut scan);
+
+        assert_eq!(first_out, first);
+        assert!(first_removed.is_empty());
+        assert_eq!(second_out, format!("{meeting}\n"));
+        assert_eq!(second_removed.len(), 2);
+    }
 }
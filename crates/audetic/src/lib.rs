@@ -4,6 +4,7 @@ pub mod app;
 pub mod audio;
 pub mod db;
 pub mod history;
+pub mod hotkey;
 pub mod install;
 
 // Lightweight, daemon-independent modules live in `audetic-core` and are
@@ -0,0 +1,121 @@
+//! Writing the daemon's own logs so the journal knows their level.
+//!
+//! Under systemd, stderr goes to journald as a stream, and every line gets
+//! the stream's default priority (info) unless it starts with a `<N>` syslog
+//! prefix (`SyslogLevelPrefix=`, on by default). Without the prefix an error
+//! is stored as info, and `journalctl -p` (`audetic logs --level`) can't tell
+//! them apart.
+
+use std::fmt;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Whether stderr is connected to the journal. systemd sets
+/// `JOURNAL_STREAM` to the `device:inode` of the stream it hands the
+/// service; a child that inherited the variable but not the stream doesn't
+/// match.
+#[cfg(unix)]
+pub fn stderr_is_journal() -> bool {
+    use std::os::fd::AsFd;
+    use std::os::unix::fs::MetadataExt;
+
+    let Some(stream) = std::env::var_os("JOURNAL_STREAM") else {
+        return false;
+    };
+    let Some((device, inode)) = stream.to_str().and_then(|s| s.split_once(':')) else {
+        return false;
+    };
+    let Ok(stderr) = std::io::stderr().as_fd().try_clone_to_owned() else {
+        return false;
+    };
+    let Ok(metadata) = std::fs::File::from(stderr).metadata() else {
+        return false;
+    };
+    device.parse() == Ok(metadata.dev()) && inode.parse() == Ok(metadata.ino())
+}
+
+#[cfg(not(unix))]
+pub fn stderr_is_journal() -> bool {
+    false
+}
+
+/// The syslog priority journald should store an event at.
+pub fn syslog_priority(level: &Level) -> u8 {
+    if *level == Level::ERROR {
+        3
+    } else if *level == Level::WARN {
+        4
+    } else if *level == Level::INFO {
+        6
+    } else {
+        7
+    }
+}
+
+/// Event format that starts each event with its `<N>` syslog prefix, then
+/// formats it with `F`.
+pub struct SyslogPrefix<F>(pub F);
+
+impl<S, N, F> FormatEvent<S, N> for SyslogPrefix<F>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        write!(writer, "<{}>", syslog_priority(event.metadata().level()))?;
+        self.0.format_event(ctx, writer, event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn each_line_starts_with_its_syslog_priority() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .event_format(SyslogPrefix(
+                tracing_subscriber::fmt::format().without_time(),
+            ))
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::error!("disk full");
+            tracing::warn!("retrying");
+            tracing::info!("started");
+        });
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let prefixes: Vec<&str> = output.lines().map(|line| &line[..3]).collect();
+        assert_eq!(prefixes, ["<3>", "<4>", "<6>"], "{output}");
+        assert!(output.lines().next().unwrap().ends_with("disk full"));
+    }
+}
@@ -3,8 +3,10 @@
 //! This module provides the core business logic for fetching logs.
 //! It is used by both the CLI and REST API.
 
+pub mod journal;
+
 use crate::history::{self, HistoryEntry};
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
 #[cfg(target_os = "linux")]
 use std::process::Command;
@@ -19,22 +21,73 @@ pub struct LogsResult {
     pub transcriptions: Vec<HistoryEntry>,
 }
 
+/// Priority names `journalctl -p` accepts, most to least severe.
+pub const LOG_LEVELS: &[&str] = &[
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
 /// Options for log retrieval.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LogsOptions {
     /// Number of log entries to retrieve
     pub lines: usize,
+    /// Only entries at this priority or more severe (journalctl `-p`)
+    pub level: Option<String>,
+    /// Only entries at or after this time, in any form journalctl's
+    /// `--since` takes (`2024-05-01 09:00`, `yesterday`, `-1h`)
+    pub since: Option<String>,
+    /// Only entries at or before this time (journalctl `--until`)
+    pub until: Option<String>,
 }
 
 impl LogsOptions {
     pub fn new(lines: usize) -> Self {
-        Self { lines }
+        Self {
+            lines,
+            ..Self::default()
+        }
+    }
+
+    /// Reject a level journalctl wouldn't know and blank time bounds. The
+    /// time formats themselves are left to journalctl.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(level) = &self.level {
+            if journal_priority(level).is_none() {
+                bail!(
+                    "Invalid log level '{}'; expected one of: {} (or 0-7)",
+                    level,
+                    LOG_LEVELS.join(", ")
+                );
+            }
+        }
+        for (name, value) in [("since", &self.since), ("until", &self.until)] {
+            if value.as_deref().is_some_and(|v| v.trim().is_empty()) {
+                bail!("`{}` must not be empty", name);
+            }
+        }
+        Ok(())
     }
 }
 
+/// The journal priority name for `level`: one of [`LOG_LEVELS`], its number
+/// (0-7), or the `error`/`warn` spellings the daemon's own logs use.
+fn journal_priority(level: &str) -> Option<&'static str> {
+    let level = level.trim().to_ascii_lowercase();
+    let level = match level.as_str() {
+        "error" => "err",
+        "warn" => "warning",
+        other => other,
+    };
+    if let Ok(number) = level.parse::<usize>() {
+        return LOG_LEVELS.get(number).copied();
+    }
+    LOG_LEVELS.iter().copied().find(|name| *name == level)
+}
+
 /// Get combined application logs and transcription history.
 pub fn get_logs(options: &LogsOptions) -> Result<LogsResult> {
-    let app_logs = get_app_logs(options.lines)?;
+    options.validate()?;
+    let app_logs = get_app_logs(options)?;
     let transcriptions = history::get_recent(options.lines)?;
 
     Ok(LogsResult {
@@ -49,35 +102,60 @@ pub fn get_logs(options: &LogsOptions) -> Result<LogsResult> {
 /// macOS: tail `~/Library/Logs/Audetic/audetic.log` (written by launchd).
 /// Other: empty (no log integration yet).
 ///
+/// `level`, `since`, and `until` are journal filters; the macOS log file is
+/// tailed unfiltered.
+///
 /// Returns a vector of log lines. Returns empty vec if the source is
 /// unavailable rather than erroring — log retrieval is best-effort and
 /// shouldn't break the `audetic logs` command on a clean install.
-pub fn get_app_logs(lines: usize) -> Result<Vec<String>> {
+pub fn get_app_logs(options: &LogsOptions) -> Result<Vec<String>> {
     #[cfg(target_os = "linux")]
-    return get_app_logs_journalctl(lines);
+    return get_app_logs_journalctl(options);
 
     #[cfg(target_os = "macos")]
-    return get_app_logs_file(lines);
+    return get_app_logs_file(options.lines);
 
     #[cfg(not(any(target_os = "linux", target_os = "macos")))]
     {
-        let _ = lines;
+        let _ = options;
         Ok(Vec::new())
     }
 }
 
+/// Arguments for the `journalctl` call behind [`get_app_logs`].
+#[cfg(any(target_os = "linux", test))]
+fn journalctl_args(options: &LogsOptions) -> Vec<String> {
+    let mut args: Vec<String> = ["--user", "-u", "audetic.service", "-n"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    args.push(options.lines.to_string());
+    if let Some(priority) = options.level.as_deref().and_then(journal_priority) {
+        args.push("-p".to_string());
+        args.push(priority.to_string());
+    }
+    if let Some(since) = &options.since {
+        args.push(format!("--since={}", since.trim()));
+    }
+    if let Some(until) = &options.until {
+        args.push(format!("--until={}", until.trim()));
+    }
+    args.push("--output=short-iso".to_string());
+    args.push("--no-pager".to_string());
+    args
+}
+
 #[cfg(target_os = "linux")]
-fn get_app_logs_journalctl(lines: usize) -> Result<Vec<String>> {
-    let output = Command::new("journalctl")
-        .arg("--user")
-        .arg("-u")
-        .arg("audetic.service")
-        .arg("-n")
-        .arg(lines.to_string())
-        .arg("--output=short-iso")
-        .arg("--no-pager")
+fn get_app_logs_journalctl(options: &LogsOptions) -> Result<Vec<String>> {
+    let output = match Command::new("journalctl")
+        .args(journalctl_args(options))
         .output()
-        .context("Failed to execute journalctl. Is the service running?")?;
+    {
+        Ok(output) => output,
+        // No journalctl at all (non-systemd distro, minimal container).
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).context("Failed to execute journalctl. Is the service running?"),
+    };
 
     if output.status.success() {
         let logs = String::from_utf8_lossy(&output.stdout);
@@ -131,5 +209,79 @@ mod tests {
     fn test_logs_options_new() {
         let opts = LogsOptions::new(50);
         assert_eq!(opts.lines, 50);
+        assert!(opts.level.is_none());
+    }
+
+    #[test]
+    fn test_journalctl_args_without_filters() {
+        assert_eq!(
+            journalctl_args(&LogsOptions::new(30)),
+            [
+                "--user",
+                "-u",
+                "audetic.service",
+                "-n",
+                "30",
+                "--output=short-iso",
+                "--no-pager"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_journalctl_args_with_filters() {
+        let opts = LogsOptions {
+            lines: 100,
+            level: Some("WARN".to_string()),
+            since: Some(" 2024-05-01 09:00 ".to_string()),
+            until: Some("-1h".to_string()),
+        };
+        let args = journalctl_args(&opts);
+
+        assert_eq!(
+            args[5..],
+            [
+                "-p",
+                "warning",
+                "--since=2024-05-01 09:00",
+                "--until=-1h",
+                "--output=short-iso",
+                "--no-pager"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_journal_priority_accepts_names_numbers_and_aliases() {
+        assert_eq!(journal_priority("err"), Some("err"));
+        assert_eq!(journal_priority("error"), Some("err"));
+        assert_eq!(journal_priority("3"), Some("err"));
+        assert_eq!(journal_priority("Debug"), Some("debug"));
+        assert_eq!(journal_priority("8"), None);
+        assert_eq!(journal_priority("verbose"), None);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_level_and_blank_times() {
+        let bad_level = LogsOptions {
+            level: Some("loud".to_string()),
+            ..LogsOptions::new(10)
+        };
+        let err = bad_level.validate().unwrap_err().to_string();
+        assert!(err.contains("Invalid log level 'loud'"));
+        assert!(err.contains("warning"));
+
+        let blank_since = LogsOptions {
+            since: Some("  ".to_string()),
+            ..LogsOptions::new(10)
+        };
+        assert!(blank_since.validate().is_err());
+
+        let ok = LogsOptions {
+            level: Some("notice".to_string()),
+            since: Some("yesterday".to_string()),
+            ..LogsOptions::new(10)
+        };
+        assert!(ok.validate().is_ok());
     }
 }
@@ -4,17 +4,19 @@
 //! `audeticd` — the Audetic daemon.
 //!
 //! With no subcommand it runs the long-lived service (audio capture, the HTTP
-//! API on 127.0.0.1:3737, and the bundled web UI). The only subcommand is
-//! `install`, which bootstraps the platform service (systemd user unit on
-//! Linux, LaunchAgent on macOS) and places the standalone `audetic` CLI on
-//! PATH. `install` deliberately lives here rather than in the slim CLI because
-//! on macOS it must run from inside the `Audetic.app` bundle so TCC permission
-//! attribution lands on the bundle's cdhash.
+//! API on 127.0.0.1:3737 unless `[api] port` says otherwise, and the bundled
+//! web UI). The only subcommand is `install`, which bootstraps the platform
+//! service (systemd user unit on Linux, LaunchAgent on macOS) and places the
+//! standalone `audetic` CLI on PATH. `install` deliberately lives here rather
+//! than in the slim CLI because on macOS it must run from inside the
+//! `Audetic.app` bundle so TCC permission attribution lands on the bundle's
+//! cdhash.
 //!
 //! Day-to-day commands (meeting, history, transcribe, provider, …) live in the
 //! separate `audetic` binary, which talks to this daemon over its REST API.
 
 use anyhow::Result;
+use audetic::logs::journal;
 use audetic::{app, install};
 use clap::{Parser, Subcommand};
 use tracing_subscriber::EnvFilter;
@@ -41,7 +43,7 @@ enum Command {
     },
     /// Print the OpenAPI spec (JSON) to stdout and exit. Lets the web UI run
     /// `codegen` against a freshly built daemon without starting the service
-    /// or contending for port 3737.
+    /// or contending for the API port.
     Openapi,
 }
 
@@ -51,10 +53,20 @@ async fn main() -> Result<()> {
     let log_level = if cli.verbose { "debug" } else { "info" };
     let env_filter = EnvFilter::try_new(log_level).unwrap_or_else(|_| EnvFilter::new("info"));
 
-    tracing_subscriber::fmt()
+    let subscriber = tracing_subscriber::fmt()
         .with_env_filter(env_filter)
-        .with_writer(std::io::stderr)
-        .init();
+        .with_writer(std::io::stderr);
+    if journal::stderr_is_journal() {
+        // journald timestamps entries itself; the prefix carries the level.
+        subscriber
+            .with_ansi(false)
+            .event_format(journal::SyslogPrefix(
+                tracing_subscriber::fmt::format().without_time(),
+            ))
+            .init();
+    } else {
+        subscriber.init();
+    }
 
     match cli.command {
         Some(Command::Install { no_launch }) => {
@@ -65,6 +77,17 @@ async fn main() -> Result<()> {
             println!("{}", spec.to_pretty_json()?);
             Ok(())
         }
-        None => app::run_service().await,
+        None => {
+            if let Err(e) = app::run_service().await {
+                let code = app::exit_code(&e);
+                if code == app::EXIT_ALREADY_RUNNING {
+                    eprintln!("{e}");
+                } else {
+                    eprintln!("Error: {e:?}");
+                }
+                std::process::exit(code);
+            }
+            Ok(())
+        }
     }
 }
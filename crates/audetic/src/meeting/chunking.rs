@@ -0,0 +1,541 @@
+//! Chunked transcription for long meetings (`[meeting] chunk_minutes`).
+//!
+//! A multi-hour recording can exceed what a provider accepts in one upload.
+//! With chunking on, the meeting WAV is cut into pieces of `chunk_minutes`,
+//! each starting [`OVERLAP_SECONDS`] before the previous one ends so a word
+//! on a cut is heard whole at least once. Pieces are transcribed one at a
+//! time and stitched back together: timestamps are shifted by the piece's
+//! start, timed segments and speaker turns in an overlap are taken from
+//! whichever piece they fall on the near side of its midpoint, and words
+//! repeated across a join are dropped from the text.
+
+use anyhow::{Context, Result};
+use hound::{Sample, WavReader, WavSpec, WavWriter};
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::transcription::job_service::{TranscriptionJobResult, TranscriptionJobService};
+use crate::transcription::jobs_client::Segment;
+use crate::transcription::Utterance;
+use audetic_core::compression::{cleanup_temp_file, prepare_for_upload};
+
+/// How much each piece repeats of the one before it.
+pub const OVERLAP_SECONDS: f64 = 5.0;
+
+/// The most words a join is searched for repeats. A few seconds of speech
+/// is well under this.
+const MAX_REPEATED_WORDS: usize = 40;
+
+/// Words at the very edge of a piece that may be cut off mid-word, and so
+/// transcribed differently (or not at all) on either side of a join.
+const MAX_EDGE_WORDS: usize = 2;
+
+/// One piece of a recording, in seconds from its start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chunk {
+    pub start: f64,
+    pub end: f64,
+}
+
+/// The pieces to transcribe `wav` in, or `None` when it goes whole:
+/// chunking is off, the file isn't a WAV (imports of other formats), or
+/// it's no longer than one piece.
+pub fn plan_for(wav: &Path, chunk_minutes: u32) -> Option<Vec<Chunk>> {
+    if chunk_minutes == 0 {
+        return None;
+    }
+    let is_wav = wav
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    if !is_wav {
+        return None;
+    }
+    let duration = audetic_core::media::wav_duration_seconds(wav)?;
+    let chunks = plan_chunks(duration, f64::from(chunk_minutes) * 60.0, OVERLAP_SECONDS);
+    (chunks.len() > 1).then_some(chunks)
+}
+
+/// Cut `duration` seconds into pieces of `chunk_seconds`, each after the
+/// first starting `overlap` seconds early.
+pub fn plan_chunks(duration: f64, chunk_seconds: f64, overlap: f64) -> Vec<Chunk> {
+    if chunk_seconds <= 0.0 || duration <= chunk_seconds {
+        return vec![Chunk {
+            start: 0.0,
+            end: duration,
+        }];
+    }
+    let mut chunks = Vec::new();
+    let mut boundary = 0.0;
+    while boundary < duration {
+        chunks.push(Chunk {
+            start: (boundary - overlap).max(0.0),
+            end: (boundary + chunk_seconds).min(duration),
+        });
+        boundary += chunk_seconds;
+    }
+    chunks
+}
+
+/// Transcribe `wav` piece by piece and stitch the results. Each piece is
+/// written next to `wav`, compressed, uploaded, and deleted before the next
+/// one is cut, so at most one piece is on disk at a time.
+pub async fn transcribe_in_chunks(
+    transcription: &dyn TranscriptionJobService,
+    wav: &Path,
+    chunks: &[Chunk],
+    diarize: bool,
+) -> Result<TranscriptionJobResult> {
+    let mut parts = Vec::with_capacity(chunks.len());
+    for (index, chunk) in chunks.iter().enumerate() {
+        info!(
+            "Transcribing piece {}/{} of {:?} ({:.0}s-{:.0}s)",
+            index + 1,
+            chunks.len(),
+            wav,
+            chunk.start,
+            chunk.end
+        );
+        let piece = piece_path(wav, index);
+        let result = transcribe_piece(transcription, wav, *chunk, &piece, diarize).await;
+        if let Err(e) = std::fs::remove_file(&piece) {
+            warn!("Failed to delete meeting piece {:?}: {}", piece, e);
+        }
+        let result = result.with_context(|| format!("Piece {} of {}", index + 1, chunks.len()))?;
+        parts.push((*chunk, result));
+    }
+    Ok(stitch(parts))
+}
+
+async fn transcribe_piece(
+    transcription: &dyn TranscriptionJobService,
+    wav: &Path,
+    chunk: Chunk,
+    piece: &Path,
+    diarize: bool,
+) -> Result<TranscriptionJobResult> {
+    write_piece(wav, chunk, piece)?;
+    let (upload, temp) = prepare_for_upload(piece, false, 0)?;
+    let result = transcription.submit_and_poll(&upload, None, diarize).await;
+    if let Some(temp) = &temp {
+        cleanup_temp_file(temp);
+    }
+    result
+}
+
+fn piece_path(wav: &Path, index: usize) -> PathBuf {
+    let stem = wav
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "meeting".to_string());
+    wav.with_file_name(format!("{stem}.part{index}.wav"))
+}
+
+/// Copy `chunk` of `wav` into a new WAV at `destination`, same format.
+fn write_piece(wav: &Path, chunk: Chunk, destination: &Path) -> Result<()> {
+    let mut reader =
+        WavReader::open(wav).with_context(|| format!("Failed to open {:?} for chunking", wav))?;
+    let spec = reader.spec();
+    let first = (chunk.start * f64::from(spec.sample_rate)) as u32;
+    let frames = ((chunk.end - chunk.start) * f64::from(spec.sample_rate)) as u64;
+    reader.seek(first.min(reader.duration()))?;
+
+    match spec.sample_format {
+        hound::SampleFormat::Float => copy_frames::<f32, _>(reader, spec, frames, destination),
+        hound::SampleFormat::Int => copy_frames::<i32, _>(reader, spec, frames, destination),
+    }
+    .with_context(|| format!("Failed to write meeting piece {:?}", destination))
+}
+
+fn copy_frames<S: Sample, R: Read + Seek>(
+    mut reader: WavReader<R>,
+    spec: WavSpec,
+    frames: u64,
+    destination: &Path,
+) -> Result<()> {
+    let mut writer = WavWriter::create(destination, spec)?;
+    let samples = frames * u64::from(spec.channels);
+    for sample in reader.samples::<S>().take(samples as usize) {
+        writer.write_sample(sample?)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+/// Join per-piece results (in order, each with the piece it came from) into
+/// one transcript on the recording's timeline. Segments and utterances are
+/// kept only when every piece has them, since a partial timeline would
+/// misplace the gaps.
+pub fn stitch(parts: Vec<(Chunk, TranscriptionJobResult)>) -> TranscriptionJobResult {
+    // Piece `i` owns the timeline from the middle of its overlap with the
+    // previous piece to the middle of its overlap with the next.
+    let cuts: Vec<f64> = parts
+        .windows(2)
+        .map(|pair| (pair[0].0.end + pair[1].0.start) / 2.0)
+        .collect();
+    let owns = |index: usize, start: f64| {
+        (index == 0 || start >= cuts[index - 1]) && (index == cuts.len() || start < cuts[index])
+    };
+
+    let mut text = String::new();
+    let mut segments = Some(Vec::new());
+    let mut utterances = Some(Vec::new());
+    for (index, (chunk, result)) in parts.into_iter().enumerate() {
+        text = merge_text(&text, &result.text);
+
+        segments = match (segments, result.segments) {
+            (Some(mut all), Some(piece)) => {
+                all.extend(
+                    piece
+                        .into_iter()
+                        .map(|segment| offset_segment(segment, chunk.start))
+                        .filter(|segment| owns(index, segment.start)),
+                );
+                Some(all)
+            }
+            _ => None,
+        };
+        utterances = match (utterances, result.utterances) {
+            (Some(mut all), Some(piece)) => {
+                all.extend(
+                    piece
+                        .into_iter()
+                        .map(|utterance| Utterance {
+                            start: utterance.start + chunk.start,
+                            end: utterance.end + chunk.start,
+                            ..utterance
+                        })
+                        .filter(|utterance| owns(index, utterance.start)),
+                );
+                Some(all)
+            }
+            _ => None,
+        };
+    }
+
+    TranscriptionJobResult {
+        text,
+        segments,
+        utterances,
+    }
+}
+
+fn offset_segment(segment: Segment, offset: f64) -> Segment {
+    Segment {
+        start: segment.start + offset,
+        end: segment.end + offset,
+        words: segment.words.map(|words| {
+            words
+                .into_iter()
+                .map(|mut word| {
+                    word.start += offset;
+                    word.end += offset;
+                    word
+                })
+                .collect()
+        }),
+        ..segment
+    }
+}
+
+/// Append `next` to `previous`, dropping the run of words the two share
+/// across the join. The run may be preceded in `next` and followed in
+/// `previous` by a couple of words cut off at the piece edge; those go too.
+/// Only the words near the join are looked at; the rest of both texts,
+/// line breaks and all, is kept as the provider wrote it.
+fn merge_text(previous: &str, next: &str) -> String {
+    let previous = previous.trim_end();
+    let next = next.trim_start();
+    let window = MAX_REPEATED_WORDS + MAX_EDGE_WORDS;
+    let mut before: Vec<&str> = previous.split_whitespace().rev().take(window).collect();
+    before.reverse();
+    let after: Vec<&str> = next.split_whitespace().take(window).collect();
+
+    let (previous, next) = match repeated_run(&before, &after) {
+        // Words are slices of the texts, so their ends are cut points.
+        Some((keep, resume)) => (
+            &previous[..offset_in(previous, before[keep - 1]) + before[keep - 1].len()],
+            next[offset_in(next, after[resume - 1]) + after[resume - 1].len()..].trim_start(),
+        ),
+        None => (previous, next),
+    };
+    match (previous.is_empty(), next.is_empty()) {
+        (true, _) => next.to_string(),
+        (_, true) => previous.to_string(),
+        _ => format!("{previous} {next}"),
+    }
+}
+
+/// Byte offset of `word`, a slice of `text`, within it.
+fn offset_in(text: &str, word: &str) -> usize {
+    word.as_ptr() as usize - text.as_ptr() as usize
+}
+
+/// Where to cut `before` and resume `after` so the longest shared run of at
+/// least two words appears once: `(words of before to keep, index in after
+/// to resume from)`.
+fn repeated_run(before: &[&str], after: &[&str]) -> Option<(usize, usize)> {
+    let longest = MAX_REPEATED_WORDS.min(before.len()).min(after.len());
+    for len in (2..=longest).rev() {
+        for tail in 0..=MAX_EDGE_WORDS {
+            for head in 0..=MAX_EDGE_WORDS {
+                if tail + len > before.len() || head + len > after.len() {
+                    continue;
+                }
+                let end = before.len() - tail;
+                let shared = before[end - len..end]
+                    .iter()
+                    .zip(&after[head..head + len])
+                    .all(|(a, b)| same_word(a, b));
+                if shared {
+                    return Some((end, head + len));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Words match ignoring case and punctuation ("Okay," and "okay").
+fn same_word(a: &str, b: &str) -> bool {
+    let bare = |word: &str| -> String {
+        word.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    bare(a) == bare(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::jobs_client::Word;
+
+    fn segment(start: f64, end: f64, text: &str) -> Segment {
+        Segment {
+            start,
+            end,
+            text: text.to_string(),
+            words: None,
+        }
+    }
+
+    fn result(text: &str, segments: Option<Vec<Segment>>) -> TranscriptionJobResult {
+        TranscriptionJobResult {
+            text: text.to_string(),
+            segments,
+            utterances: None,
+        }
+    }
+
+    #[test]
+    fn plan_overlaps_each_piece_with_the_previous_one() {
+        assert_eq!(
+            plan_chunks(150.0, 60.0, 5.0),
+            [
+                Chunk {
+                    start: 0.0,
+                    end: 60.0
+                },
+                Chunk {
+                    start: 55.0,
+                    end: 120.0
+                },
+                Chunk {
+                    start: 115.0,
+                    end: 150.0
+                },
+            ]
+        );
+        assert_eq!(plan_chunks(60.0, 60.0, 5.0).len(), 1);
+        assert_eq!(plan_chunks(90.0, 0.0, 5.0).len(), 1);
+    }
+
+    #[test]
+    fn stitching_offsets_segments_and_splits_the_overlap_at_its_midpoint() {
+        let first = Chunk {
+            start: 0.0,
+            end: 60.0,
+        };
+        let second = Chunk {
+            start: 55.0,
+            end: 100.0,
+        };
+        let stitched = stitch(vec![
+            (
+                first,
+                result(
+                    "we should ship it on friday",
+                    Some(vec![
+                        segment(0.0, 50.0, "we should ship it"),
+                        segment(56.0, 59.5, "on friday"),
+                    ]),
+                ),
+            ),
+            (
+                second,
+                result(
+                    "on Friday, then rest",
+                    Some(vec![
+                        // 56s and 58s on the recording: the first is before
+                        // the 57.5s midpoint, so the earlier piece has it.
+                        segment(1.0, 4.5, "on friday"),
+                        segment(3.0, 10.0, "then rest"),
+                    ]),
+                ),
+            ),
+        ]);
+
+        assert_eq!(stitched.text, "we should ship it on friday then rest");
+        let segments = stitched.segments.unwrap();
+        let timeline: Vec<(f64, &str)> = segments
+            .iter()
+            .map(|s| (s.start, s.text.as_str()))
+            .collect();
+        assert_eq!(
+            timeline,
+            [
+                (0.0, "we should ship it"),
+                (56.0, "on friday"),
+                (58.0, "then rest"),
+            ]
+        );
+        assert_eq!(segments[2].end, 65.0);
+    }
+
+    #[test]
+    fn stitching_offsets_words_and_utterances() {
+        let mut piece = result(
+            "hello there",
+            Some(vec![Segment {
+                words: Some(vec![Word {
+                    start: 3.0,
+                    end: 3.5,
+                    text: "hello".to_string(),
+                }]),
+                ..segment(3.0, 4.0, "hello there")
+            }]),
+        );
+        piece.utterances = Some(vec![Utterance {
+            speaker: "A".to_string(),
+            start: 3.0,
+            end: 4.0,
+            text: "hello there".to_string(),
+        }]);
+        let mut opening = result(
+            "good morning",
+            Some(vec![segment(0.0, 1.0, "good morning")]),
+        );
+        opening.utterances = Some(Vec::new());
+
+        let stitched = stitch(vec![
+            (
+                Chunk {
+                    start: 0.0,
+                    end: 30.0,
+                },
+                opening,
+            ),
+            (
+                Chunk {
+                    start: 25.0,
+                    end: 40.0,
+                },
+                piece,
+            ),
+        ]);
+        let segments = stitched.segments.unwrap();
+        assert_eq!(segments[1].words.as_ref().unwrap()[0].start, 28.0);
+        let utterances = stitched.utterances.unwrap();
+        assert_eq!((utterances[0].start, utterances[0].end), (28.0, 29.0));
+    }
+
+    #[test]
+    fn segments_are_dropped_unless_every_piece_has_them() {
+        let stitched = stitch(vec![
+            (
+                Chunk {
+                    start: 0.0,
+                    end: 30.0,
+                },
+                result("one", Some(vec![segment(0.0, 1.0, "one")])),
+            ),
+            (
+                Chunk {
+                    start: 25.0,
+                    end: 40.0,
+                },
+                result("two", None),
+            ),
+        ]);
+        assert_eq!(stitched.text, "one two");
+        assert!(stitched.segments.is_none());
+        assert!(stitched.utterances.is_none());
+    }
+
+    #[test]
+    fn merging_drops_words_repeated_across_the_join() {
+        // Exact repeat.
+        assert_eq!(
+            merge_text("let's review the budget", "review the budget next"),
+            "let's review the budget next"
+        );
+        // Cut-off edge words on both sides ("bud", "get").
+        assert_eq!(
+            merge_text(
+                "let's review the quarterly bud",
+                "get the quarterly numbers now"
+            ),
+            "let's review the quarterly numbers now"
+        );
+        // A single shared word isn't enough to call it a repeat.
+        assert_eq!(merge_text("go to the", "the end"), "go to the the end");
+        assert_eq!(merge_text("", "first piece"), "first piece");
+        // Away from the join, the text is left alone.
+        assert_eq!(
+            merge_text(
+                "Agenda:\n\n1.  Budget review\nlet's review the budget ",
+                "review the budget next.\n\nAction  items:"
+            ),
+            "Agenda:\n\n1.  Budget review\nlet's review the budget next.\n\nAction  items:"
+        );
+    }
+
+    #[test]
+    fn pieces_are_cut_from_the_wav_in_the_same_format() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let wav = dir.join("meeting.wav");
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&wav, spec).unwrap();
+        for i in 0..1000i16 {
+            writer.write_sample(i).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let piece = piece_path(&wav, 1);
+        assert_eq!(piece, dir.join("meeting.part1.wav"));
+        write_piece(
+            &wav,
+            Chunk {
+                start: 2.5,
+                end: 6.0,
+            },
+            &piece,
+        )
+        .unwrap();
+
+        let mut reader = WavReader::open(&piece).unwrap();
+        assert_eq!(reader.spec(), spec);
+        let samples: Vec<i16> = reader.samples().map(|s| s.unwrap()).collect();
+        assert_eq!(samples.len(), 350);
+        assert_eq!((samples[0], samples[349]), (250, 599));
+    }
+}
@@ -9,18 +9,19 @@
 //! `Indicator` via `LiveProgressObserver`.
 
 use anyhow::{bail, Context, Result};
-use hound::{WavSpec, WavWriter};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
-use crate::audio::audio_mixer::AudioMixer;
+use crate::audio::audio_mixer::{AudioMixer, MixOptions};
 use crate::audio::audio_source::AudioSource;
+use crate::audio::wav;
 use crate::db::{self, meetings::MeetingRepository};
 use crate::post_processing::PostProcessingService;
 use crate::transcription::job_service::TranscriptionJobService;
 use crate::ui::Indicator;
 
+use super::chunking;
 use super::processing::{process_meeting, ProcessingArgs, ProcessingServices};
 use super::progress::LiveProgressObserver;
 use super::status::{MeetingPhase, MeetingStartOptions, MeetingStatusHandle};
@@ -81,6 +82,15 @@ pub struct MeetingMachine {
     indicator: Indicator,
     status: MeetingStatusHandle,
     meetings_dir: PathBuf,
+    /// Sample size of meeting WAVs (`[audio].bits_per_sample`).
+    bits_per_sample: u16,
+    /// Request speaker labels when transcribing (`[meeting].diarize`).
+    diarize: bool,
+    /// Transcribe in pieces of this many minutes (`[meeting].chunk_minutes`).
+    chunk_minutes: u32,
+    /// Mic/system levels and ducking for the mixdown (`[meeting].mic_gain`,
+    /// `system_gain`, `ducking`).
+    mix: MixOptions,
 }
 
 impl MeetingMachine {
@@ -101,9 +111,39 @@ impl MeetingMachine {
             indicator,
             status,
             meetings_dir,
+            bits_per_sample: 32,
+            diarize: false,
+            chunk_minutes: 0,
+            mix: MixOptions::default(),
         }
     }
 
+    /// Write meeting audio as 16-bit PCM instead of 32-bit float when `bits`
+    /// is 16 (`[audio].bits_per_sample`).
+    pub fn with_bits_per_sample(mut self, bits: u16) -> Self {
+        self.bits_per_sample = bits;
+        self
+    }
+
+    /// Ask the transcription service to label speakers (`[meeting].diarize`).
+    pub fn with_diarize(mut self, diarize: bool) -> Self {
+        self.diarize = diarize;
+        self
+    }
+
+    /// Transcribe long recordings in pieces (`[meeting].chunk_minutes`).
+    pub fn with_chunk_minutes(mut self, chunk_minutes: u32) -> Self {
+        self.chunk_minutes = chunk_minutes;
+        self
+    }
+
+    /// Balance the mic against system audio in the mixdown
+    /// (`[meeting].mic_gain`, `system_gain`, `ducking`).
+    pub fn with_mix(mut self, mix: MixOptions) -> Self {
+        self.mix = mix;
+        self
+    }
+
     /// Start a meeting recording.
     ///
     /// Returns an error if a meeting is already recording or if both audio
@@ -277,7 +317,8 @@ impl MeetingMachine {
         let target_rate: u32 = 16000; // Whisper optimal
         let mic_resampled = AudioMixer::resample(&mic_samples, mic_rate, target_rate);
         let system_resampled = AudioMixer::resample(&system_samples, system_rate, target_rate);
-        let mixed = AudioMixer::mix(&[mic_resampled, system_resampled]);
+        let mixed =
+            AudioMixer::mix_meeting(&mic_resampled, &system_resampled, target_rate, &self.mix);
 
         // Write WAV file
         self.write_wav(&audio_path, &mixed, target_rate)?;
@@ -382,6 +423,8 @@ impl MeetingMachine {
             services: ProcessingServices {
                 transcription: Arc::clone(&self.transcription),
                 post_processing: Arc::clone(&self.post_processing),
+                diarize: self.diarize,
+                chunk_minutes: self.chunk_minutes,
             },
             observer,
         };
@@ -465,18 +508,11 @@ impl MeetingMachine {
     }
 
     fn write_wav(&self, path: &Path, samples: &[f32], sample_rate: u32) -> Result<()> {
-        let spec = WavSpec {
-            channels: 1,
-            sample_rate,
-            bits_per_sample: 32,
-            sample_format: hound::SampleFormat::Float,
-        };
-
-        let mut writer = WavWriter::create(path, spec)?;
-        for &sample in samples {
-            writer.write_sample(sample)?;
-        }
-        writer.finalize()?;
+        wav::write_samples(
+            path,
+            wav::mono_spec(sample_rate, self.bits_per_sample),
+            samples,
+        )?;
 
         info!(
             "Meeting audio saved: {:?} ({} samples)",
@@ -498,10 +534,7 @@ impl MeetingMachine {
         let spec = reader.spec();
         let sample_rate = spec.sample_rate;
 
-        let samples: Vec<f32> = reader
-            .samples::<f32>()
-            .collect::<std::result::Result<Vec<f32>, _>>()
-            .context("Failed to read WAV samples")?;
+        let samples = wav::read_samples(&mut reader)?;
         let total = samples.len();
 
         let to_index = |secs: f64| -> usize {
@@ -520,12 +553,9 @@ impl MeetingMachine {
 
         let trimmed = &samples[start_idx..end_idx];
 
-        let mut writer = WavWriter::create(path, spec)
+        drop(reader);
+        wav::write_samples(path, spec, trimmed)
             .with_context(|| format!("Failed to rewrite trimmed WAV: {path:?}"))?;
-        for &sample in trimmed {
-            writer.write_sample(sample)?;
-        }
-        writer.finalize()?;
 
         info!(
             "Trimmed WAV {:?}: {} → {} samples ([{}, {}))",
@@ -561,7 +591,9 @@ pub enum ToggleOutcome {
 /// disk. Used by `POST /meetings/:id/retry` after a failed transcription
 /// (e.g. backend timeout) so the user doesn't have to re-record. Skips the
 /// compress step entirely — the durable mp3 from the original run is the
-/// upload payload.
+/// upload payload. When the original WAV is still next to it (a chunked
+/// run that failed keeps it), a long meeting is transcribed in pieces of
+/// `chunk_minutes` again instead.
 ///
 /// Updates the DB row to `transcribing` immediately, then `completed` or
 /// `error` once the polling resolves. Writes the transcript to a `.txt`
@@ -573,6 +605,8 @@ pub async fn retry_meeting_transcription(
     audio_path: PathBuf,
     duration_seconds: i64,
     transcription: Arc<dyn TranscriptionJobService>,
+    diarize: bool,
+    chunk_minutes: u32,
 ) {
     info!(
         "Retrying transcription for meeting {} from {:?}",
@@ -587,7 +621,30 @@ pub async fn retry_meeting_transcription(
         }
     }
 
-    let result = transcription.submit_and_poll(&audio_path, None).await;
+    let wav = audio_path.with_extension("wav");
+    let result = match chunking::plan_for(&wav, chunk_minutes) {
+        Some(chunks) => {
+            info!(
+                "Transcribing meeting {} in {} pieces",
+                meeting_id,
+                chunks.len()
+            );
+            let result =
+                chunking::transcribe_in_chunks(transcription.as_ref(), &wav, &chunks, diarize)
+                    .await;
+            if wav != audio_path && result.is_ok() {
+                if let Err(e) = std::fs::remove_file(&wav) {
+                    warn!("Failed to delete pre-compression source: {}", e);
+                }
+            }
+            result
+        }
+        None => {
+            transcription
+                .submit_and_poll(&audio_path, None, diarize)
+                .await
+        }
+    };
 
     match result {
         Ok(r) => {
@@ -603,6 +660,7 @@ pub async fn retry_meeting_transcription(
                     &transcript_path.to_string_lossy(),
                     &r.text,
                     r.segments.as_deref(),
+                    r.utterances.as_deref(),
                     duration_seconds,
                 ) {
                     error!("Failed to mark meeting {} completed: {}", meeting_id, e);
@@ -628,6 +686,7 @@ pub async fn retry_meeting_transcription(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use hound::{WavSpec, WavWriter};
 
     fn write_test_wav(path: &Path, samples: &[f32], sample_rate: u32) {
         let spec = WavSpec {
@@ -5,13 +5,19 @@
 //! [`crate::post_processing`] for any user-defined follow-up commands.
 //! Also handles importing existing media files as meetings — the import
 //! path stages the file under the meetings dir, creates the row, and
-//! drives the same post-recording pipeline a live recording uses.
+//! drives the same post-recording pipeline a live recording uses. Meetings
+//! whose pipeline failed or was cut short by a crash can be resumed from
+//! their stored audio, and startup reconciles the ones a crash left
+//! in-flight.
 
+pub mod chunking;
 pub mod import;
 pub mod media_inspector;
 pub mod meeting_machine;
 pub mod processing;
 pub mod progress;
+pub mod recovery;
+pub mod resume;
 pub mod status;
 
 pub use import::{import_meeting_file, ImportArgs, ImportResult};
@@ -22,4 +28,6 @@ pub use meeting_machine::{
 };
 pub use processing::{process_meeting, ProcessingArgs, ProcessingServices};
 pub use progress::{LiveProgressObserver, MeetingProgressObserver, NoopProgressObserver};
+pub use recovery::{reconcile_stale_meetings, recover_interrupted_meetings, ReconcileReport};
+pub use resume::{resume_meeting, ResumeOutcome};
 pub use status::{MeetingPhase, MeetingStartOptions, MeetingState, MeetingStatusHandle};
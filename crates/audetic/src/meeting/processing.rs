@@ -1,7 +1,8 @@
 //! Post-recording meeting pipeline.
 //!
 //! Drives a meeting from a freshly-staged audio file to a completed,
-//! transcribed record: compress → transcribe → write transcript →
+//! transcribed record: compress → transcribe (whole, or in pieces with
+//! `[meeting] chunk_minutes`; see `chunking`) → write transcript →
 //! dispatch the `meeting.completed` event → mark completed. Updates the DB
 //! row at every transition. Side effects that depend on the *caller*
 //! (live indicator, status handle) are delegated to a
@@ -22,15 +23,21 @@ use crate::post_processing::{
 use crate::transcription::job_service::TranscriptionJobService;
 use audetic_core::compression::{cleanup_temp_file, prepare_for_upload};
 
+use super::chunking;
 use super::progress::MeetingProgressObserver;
 use super::status::MeetingPhase;
 
 /// Dependencies the pipeline shares with every meeting-driving flow (live
-/// recording, import, retry). Cheap to clone — every field is an `Arc`.
+/// recording, import, retry). Cheap to clone — the services are `Arc`s.
 #[derive(Clone)]
 pub struct ProcessingServices {
     pub transcription: Arc<dyn TranscriptionJobService>,
     pub post_processing: Arc<PostProcessingService>,
+    /// Ask the transcription service for speaker labels (`[meeting].diarize`).
+    pub diarize: bool,
+    /// Transcribe long WAVs in pieces of this many minutes
+    /// (`[meeting].chunk_minutes`); 0 sends the whole file.
+    pub chunk_minutes: u32,
 }
 
 /// One pipeline invocation. The audio file at `audio_path` must already be
@@ -61,9 +68,13 @@ pub async fn process_meeting(args: ProcessingArgs) {
         observer,
     } = args;
 
+    // Planned before compression, which replaces the WAV the pieces are cut from.
+    let chunks = chunking::plan_for(&audio_path, services.chunk_minutes);
+
     info!("Compressing meeting {} audio: {:?}", meeting_id, audio_path);
 
-    let (temp_upload, temp_to_cleanup) = match prepare_for_upload(&audio_path, false) {
+    // Meetings always compress: the mp3 becomes the durable copy below.
+    let (temp_upload, temp_to_cleanup) = match prepare_for_upload(&audio_path, false, 0) {
         Ok(v) => v,
         Err(e) => {
             let error_msg = e.to_string();
@@ -80,12 +91,13 @@ pub async fn process_meeting(args: ProcessingArgs) {
     // Move the compressed mp3 next to the original via copy (cross-fs safe —
     // the temp dir is often tmpfs while the meetings dir is under
     // `~/.local/share`). The durable mp3 is what post-processing jobs and
-    // history reference; drop the original once the mp3 is in place.
+    // history reference; drop the original once the mp3 is in place (or,
+    // when transcribing in pieces, once they've all been cut from it).
     let durable_audio = if temp_to_cleanup.is_some() {
         let durable = audio_path.with_extension("mp3");
         match std::fs::copy(&temp_upload, &durable) {
             Ok(_) => {
-                if durable != audio_path {
+                if durable != audio_path && chunks.is_none() {
                     if let Err(e) = std::fs::remove_file(&audio_path) {
                         warn!("Failed to delete pre-compression source: {}", e);
                     }
@@ -121,10 +133,35 @@ pub async fn process_meeting(args: ProcessingArgs) {
         }
     }
 
-    let transcription_result = services
-        .transcription
-        .submit_and_poll(&temp_upload, None)
-        .await;
+    let transcription_result = match &chunks {
+        Some(chunks) => {
+            info!(
+                "Transcribing meeting {} in {} pieces",
+                meeting_id,
+                chunks.len()
+            );
+            let result = chunking::transcribe_in_chunks(
+                services.transcription.as_ref(),
+                &audio_path,
+                chunks,
+                services.diarize,
+            )
+            .await;
+            // Kept after a failure so a retry can cut the pieces again.
+            if durable_audio != audio_path && result.is_ok() {
+                if let Err(e) = std::fs::remove_file(&audio_path) {
+                    warn!("Failed to delete pre-compression source: {}", e);
+                }
+            }
+            result
+        }
+        None => {
+            services
+                .transcription
+                .submit_and_poll(&temp_upload, None, services.diarize)
+                .await
+        }
+    };
 
     if let Some(temp) = &temp_to_cleanup {
         cleanup_temp_file(temp);
@@ -144,6 +181,7 @@ pub async fn process_meeting(args: ProcessingArgs) {
                     &transcript_path.to_string_lossy(),
                     &result.text,
                     result.segments.as_deref(),
+                    result.utterances.as_deref(),
                     duration_seconds as i64,
                 );
             }
@@ -0,0 +1,194 @@
+//! Startup reconciliation for meetings a crashed daemon left in-flight.
+//!
+//! Every non-terminal meeting phase is owned by a live process — the meeting
+//! machine while recording or in review, a spawned `process_meeting` task
+//! while compressing or transcribing. If the daemon dies, nothing advances
+//! those rows again and they show as "in progress" forever. On startup we
+//! sweep them: meetings whose pipeline can pick up from the stored audio are
+//! resumed, everything else is marked `error` so the user can see what
+//! happened (and `meeting resume` it themselves if the audio is usable).
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use tracing::{info, warn};
+
+use crate::db::{self, meetings::MeetingRepository};
+
+use super::processing::ProcessingServices;
+use super::resume::{resolve_audio_path, resume_meeting, ResumeOutcome};
+use super::status::MeetingPhase;
+
+/// Error recorded on meetings the sweep couldn't resume.
+pub const INTERRUPTED_BY_RESTART: &str = "Interrupted by restart";
+
+/// What a reconciliation pass did to the stale rows it found.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// Meetings marked `error` with [`INTERRUPTED_BY_RESTART`].
+    pub failed: Vec<i64>,
+    /// Meetings left in their processing phase because their audio is still
+    /// on disk; the caller should resume them.
+    pub resumable: Vec<i64>,
+}
+
+/// Find meetings stuck in a non-terminal state for at least
+/// `older_than_seconds` and settle them. Recording and review rows are
+/// always failed — the WAV of an interrupted recording was never finalized,
+/// and the review UI state died with the old process. Compressing and
+/// transcribing rows whose audio still exists are reported as resumable and
+/// left untouched; the rest are failed.
+pub fn reconcile_stale_meetings(
+    conn: &Connection,
+    older_than_seconds: u64,
+) -> Result<ReconcileReport> {
+    let mut report = ReconcileReport::default();
+
+    for meeting in MeetingRepository::list_stale_in_flight(conn, older_than_seconds)? {
+        if MeetingPhase::is_resumable(&meeting.status)
+            && resolve_audio_path(&meeting.audio_path).is_some()
+        {
+            report.resumable.push(meeting.id);
+            continue;
+        }
+
+        warn!(
+            "Meeting {} was left '{}' by a previous run; marking it failed",
+            meeting.id, meeting.status
+        );
+        MeetingRepository::fail(
+            conn,
+            meeting.id,
+            INTERRUPTED_BY_RESTART,
+            meeting.duration_seconds.unwrap_or(0),
+        )?;
+        report.failed.push(meeting.id);
+    }
+
+    Ok(report)
+}
+
+/// Startup entry point: reconcile every in-flight meeting and resume the
+/// ones that can be. Must run before the meeting machine or API can create
+/// or advance meetings — at that point no row belongs to a live process, so
+/// no age threshold is needed.
+pub async fn recover_interrupted_meetings(services: ProcessingServices) -> Result<()> {
+    let report = {
+        let conn = db::init_db().context("Failed to open audetic database")?;
+        reconcile_stale_meetings(&conn, 0)?
+    };
+
+    if !report.failed.is_empty() || !report.resumable.is_empty() {
+        info!(
+            "Reconciled interrupted meetings: {} failed, {} resuming",
+            report.failed.len(),
+            report.resumable.len()
+        );
+    }
+
+    for meeting_id in report.resumable {
+        match resume_meeting(meeting_id, services.clone()).await {
+            Ok(ResumeOutcome::Started { .. }) => {}
+            // The audio vanished between the sweep and the resume; settle the
+            // row rather than leave it phantom-processing.
+            Ok(ResumeOutcome::AudioMissing { .. }) => {
+                let conn = db::init_db().context("Failed to open audetic database")?;
+                let duration = MeetingRepository::get(&conn, meeting_id)?
+                    .and_then(|m| m.duration_seconds)
+                    .unwrap_or(0);
+                MeetingRepository::fail(&conn, meeting_id, INTERRUPTED_BY_RESTART, duration)?;
+            }
+            Ok(other) => warn!("Meeting {} was not resumed: {:?}", meeting_id, other),
+            Err(e) => warn!("Failed to resume meeting {}: {}", meeting_id, e),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrate;
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        conn
+    }
+
+    /// Pretend the meeting started two hours ago.
+    fn backdate(conn: &Connection, id: i64) {
+        conn.execute(
+            "UPDATE meetings SET started_at = datetime('now', '-2 hours') WHERE id = ?1",
+            [id],
+        )
+        .unwrap();
+    }
+
+    fn status(conn: &Connection, id: i64) -> String {
+        MeetingRepository::get(conn, id).unwrap().unwrap().status
+    }
+
+    #[test]
+    fn stale_recording_is_marked_failed() {
+        let conn = setup_db();
+        let id = MeetingRepository::insert(&conn, Some("Crashed"), "/tmp/crashed.wav").unwrap();
+        backdate(&conn, id);
+
+        let report = reconcile_stale_meetings(&conn, 3600).unwrap();
+        assert_eq!(report.failed, vec![id]);
+        assert!(report.resumable.is_empty());
+
+        let meeting = MeetingRepository::get(&conn, id).unwrap().unwrap();
+        assert_eq!(meeting.status, "error");
+        assert_eq!(meeting.error.as_deref(), Some(INTERRUPTED_BY_RESTART));
+    }
+
+    #[test]
+    fn terminal_meetings_are_untouched() {
+        let conn = setup_db();
+        let done = MeetingRepository::insert(&conn, Some("Done"), "/tmp/done.wav").unwrap();
+        MeetingRepository::complete(&conn, done, "/tmp/done.txt", "text", None, None, 10).unwrap();
+        let failed = MeetingRepository::insert(&conn, Some("Failed"), "/tmp/f.wav").unwrap();
+        MeetingRepository::fail(&conn, failed, "backend timeout", 10).unwrap();
+        backdate(&conn, done);
+        backdate(&conn, failed);
+
+        let report = reconcile_stale_meetings(&conn, 3600).unwrap();
+        assert_eq!(report, ReconcileReport::default());
+
+        assert_eq!(status(&conn, done), "completed");
+        let failed = MeetingRepository::get(&conn, failed).unwrap().unwrap();
+        assert_eq!(failed.error.as_deref(), Some("backend timeout"));
+    }
+
+    #[test]
+    fn recent_in_flight_meeting_is_untouched() {
+        let conn = setup_db();
+        let id = MeetingRepository::insert(&conn, None, "/tmp/live.wav").unwrap();
+
+        let report = reconcile_stale_meetings(&conn, 3600).unwrap();
+        assert_eq!(report, ReconcileReport::default());
+        assert_eq!(status(&conn, id), "recording");
+    }
+
+    #[test]
+    fn stale_transcribing_with_audio_is_resumable() {
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("meeting.mp3");
+        std::fs::write(&audio, b"mp3").unwrap();
+
+        let conn = setup_db();
+        let with_audio = MeetingRepository::insert(&conn, None, &audio.to_string_lossy()).unwrap();
+        MeetingRepository::update_status(&conn, with_audio, MeetingPhase::Transcribing).unwrap();
+        let without_audio =
+            MeetingRepository::insert(&conn, None, "/nonexistent/meeting.wav").unwrap();
+        MeetingRepository::update_status(&conn, without_audio, MeetingPhase::Compressing).unwrap();
+
+        let report = reconcile_stale_meetings(&conn, 0).unwrap();
+        assert_eq!(report.resumable, vec![with_audio]);
+        assert_eq!(report.failed, vec![without_audio]);
+        assert_eq!(status(&conn, with_audio), "transcribing");
+        assert_eq!(status(&conn, without_audio), "error");
+    }
+}
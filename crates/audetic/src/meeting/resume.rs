@@ -0,0 +1,209 @@
+//! Resume a meeting whose post-recording pipeline never finished.
+//!
+//! Covers two cases: a run that ended in `error` (backend timeout, flaky
+//! network) and a run a daemon crash left stuck in `compressing` or
+//! `transcribing`. Either way the audio is still on disk, so we re-enter
+//! `process_meeting` from the compress step using the stored `audio_path`
+//! instead of asking the user to re-record.
+//!
+//! Like imports and retries, a resume never touches the singleton
+//! `MeetingStatusHandle` — the meeting row is the source of truth. Callers
+//! that can see the live machine (the HTTP handler) should refuse to resume
+//! the meeting it is currently processing.
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::info;
+
+use crate::db::{self, meetings::MeetingRepository};
+
+use super::processing::{process_meeting, ProcessingArgs, ProcessingServices};
+use super::progress::NoopProgressObserver;
+use super::status::MeetingPhase;
+
+/// Result of a resume attempt, so the API can answer with the right status
+/// code (202 / 404 / 409).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResumeOutcome {
+    /// The row was flipped to `compressing` and the pipeline was spawned
+    /// against `audio_path`.
+    Started { audio_path: PathBuf },
+    /// No live row with that id.
+    NotFound,
+    /// The meeting is in a state that can't be resumed — `completed`,
+    /// `cancelled`, or still recording / under review.
+    NotResumable { status: String },
+    /// Neither the stored audio path nor its `.mp3` sibling exists.
+    AudioMissing { audio_path: String },
+}
+
+/// Everything `process_meeting` needs that was read back from the row.
+struct ResumePlan {
+    audio_path: PathBuf,
+    title: Option<String>,
+    duration_seconds: u64,
+}
+
+/// Validate a meeting for resume and, if eligible, spawn the processing
+/// pipeline in the background. Returns as soon as the row has been flipped
+/// to `compressing`; poll the meeting row for the terminal state.
+pub async fn resume_meeting(
+    meeting_id: i64,
+    services: ProcessingServices,
+) -> Result<ResumeOutcome> {
+    let conn = db::init_db().context("Failed to open audetic database")?;
+    let plan = match prepare_resume(&conn, meeting_id)? {
+        Ok(plan) => plan,
+        Err(outcome) => return Ok(outcome),
+    };
+
+    info!("Resuming meeting {} from {:?}", meeting_id, plan.audio_path);
+
+    let audio_path = plan.audio_path.clone();
+    let pipeline_args = ProcessingArgs {
+        meeting_id,
+        audio_path: plan.audio_path,
+        title: plan.title,
+        duration_seconds: plan.duration_seconds,
+        services,
+        observer: Arc::new(NoopProgressObserver),
+    };
+    tokio::spawn(async move { process_meeting(pipeline_args).await });
+
+    Ok(ResumeOutcome::Started { audio_path })
+}
+
+/// Check eligibility, resolve the audio file, and atomically flip the row
+/// to `compressing`. `Err(outcome)` carries the reason a resume was refused.
+fn prepare_resume(
+    conn: &Connection,
+    meeting_id: i64,
+) -> Result<std::result::Result<ResumePlan, ResumeOutcome>> {
+    let Some(meeting) = MeetingRepository::get(conn, meeting_id)? else {
+        return Ok(Err(ResumeOutcome::NotFound));
+    };
+
+    if !MeetingPhase::is_resumable(&meeting.status) {
+        return Ok(Err(ResumeOutcome::NotResumable {
+            status: meeting.status,
+        }));
+    }
+
+    let Some(audio_path) = resolve_audio_path(&meeting.audio_path) else {
+        return Ok(Err(ResumeOutcome::AudioMissing {
+            audio_path: meeting.audio_path,
+        }));
+    };
+    if audio_path != Path::new(&meeting.audio_path) {
+        MeetingRepository::update_audio_path(conn, meeting_id, &audio_path.to_string_lossy())?;
+    }
+
+    // Re-check the state in the same statement that changes it, so a
+    // concurrent resume, retry, or completion between the read above and
+    // here can't be clobbered.
+    if !MeetingRepository::begin_resume(conn, meeting_id)? {
+        let status = MeetingRepository::get(conn, meeting_id)?
+            .map(|m| m.status)
+            .unwrap_or_default();
+        return Ok(Err(ResumeOutcome::NotResumable { status }));
+    }
+
+    Ok(Ok(ResumePlan {
+        audio_path,
+        title: meeting.title,
+        duration_seconds: meeting.duration_seconds.unwrap_or(0).max(0) as u64,
+    }))
+}
+
+/// The file a resume should read: the stored path if it still exists,
+/// otherwise its `.mp3` sibling. A crash between the compress step's copy and
+/// its DB update leaves the row pointing at a WAV that is already gone; the
+/// durable mp3 next to it is the file we want.
+pub(super) fn resolve_audio_path(stored: &str) -> Option<PathBuf> {
+    let stored = PathBuf::from(stored);
+    if stored.exists() {
+        return Some(stored);
+    }
+    let mp3_sibling = stored.with_extension("mp3");
+    mp3_sibling.exists().then_some(mp3_sibling)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrate;
+
+    fn setup_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn prepare_rejects_completed_meeting() {
+        let conn = setup_db();
+        let id = MeetingRepository::insert(&conn, Some("Done"), "/tmp/done.mp3").unwrap();
+        MeetingRepository::complete(&conn, id, "/tmp/done.txt", "text", None, None, 10).unwrap();
+
+        let outcome = prepare_resume(&conn, id).unwrap().err();
+        assert_eq!(
+            outcome,
+            Some(ResumeOutcome::NotResumable {
+                status: "completed".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn prepare_reports_unknown_meeting() {
+        let conn = setup_db();
+        let outcome = prepare_resume(&conn, 42).unwrap().err();
+        assert_eq!(outcome, Some(ResumeOutcome::NotFound));
+    }
+
+    #[test]
+    fn prepare_falls_back_to_mp3_sibling() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav = dir.path().join("meeting.wav");
+        let mp3 = dir.path().join("meeting.mp3");
+        std::fs::write(&mp3, b"mp3").unwrap();
+
+        let conn = setup_db();
+        let id = MeetingRepository::insert(&conn, None, &wav.to_string_lossy()).unwrap();
+        MeetingRepository::update_status(&conn, id, MeetingPhase::Compressing).unwrap();
+
+        let plan = match prepare_resume(&conn, id).unwrap() {
+            Ok(plan) => plan,
+            Err(outcome) => panic!("expected a resume plan, got {outcome:?}"),
+        };
+        assert_eq!(plan.audio_path, mp3);
+        assert_eq!(
+            MeetingRepository::get(&conn, id)
+                .unwrap()
+                .unwrap()
+                .audio_path,
+            mp3.to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn prepare_reports_missing_audio_without_touching_status() {
+        let conn = setup_db();
+        let id = MeetingRepository::insert(&conn, None, "/nonexistent/meeting.wav").unwrap();
+        MeetingRepository::fail(&conn, id, "boom", 5).unwrap();
+
+        let outcome = prepare_resume(&conn, id).unwrap().err();
+        assert_eq!(
+            outcome,
+            Some(ResumeOutcome::AudioMissing {
+                audio_path: "/nonexistent/meeting.wav".to_string()
+            })
+        );
+        assert_eq!(
+            MeetingRepository::get(&conn, id).unwrap().unwrap().status,
+            "error"
+        );
+    }
+}
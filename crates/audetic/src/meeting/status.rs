@@ -53,6 +53,18 @@ impl MeetingPhase {
     pub fn is_terminal(status: &str) -> bool {
         Self::TERMINAL_STATUSES.contains(&status)
     }
+
+    /// Stored `status` strings whose post-recording pipeline can be re-entered
+    /// by `meeting resume`: a failed run, or a processing phase a daemon crash
+    /// left behind. Shared by [`Self::is_resumable`] and the guarded SQL in
+    /// `MeetingRepository::begin_resume`. `completed` is deliberately absent —
+    /// re-running it would overwrite a finished transcript.
+    pub const RESUMABLE_STATUSES: [&'static str; 3] = ["error", "compressing", "transcribing"];
+
+    /// Whether a meeting with this stored `status` may be resumed.
+    pub fn is_resumable(status: &str) -> bool {
+        Self::RESUMABLE_STATUSES.contains(&status)
+    }
 }
 
 /// Options for starting a meeting.
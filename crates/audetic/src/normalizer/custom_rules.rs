@@ -0,0 +1,248 @@
+use anyhow::{Context, Result};
+use regex::{NoExpand, Regex};
+
+use super::TranscriptionNormalizer;
+use crate::config::NormalizerConfig;
+
+/// Applies the user's `[normalizer]` fixes on top of a provider's
+/// normalizer: the provider cleans up first, then the regex `rules` run in
+/// order, then whole-word `words` fixes, then optional sentence casing.
+pub struct CustomRulesNormalizer {
+    base: Box<dyn TranscriptionNormalizer>,
+    rules: Vec<(Regex, String)>,
+    words: Vec<(Regex, String)>,
+    sentence_case: bool,
+}
+
+impl CustomRulesNormalizer {
+    /// Compile `config` around `base`. An invalid rule pattern is an error
+    /// rather than being skipped, so a typo can't quietly disable a fix.
+    pub fn new(base: Box<dyn TranscriptionNormalizer>, config: &NormalizerConfig) -> Result<Self> {
+        let rules = config
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let regex = Regex::new(&rule.pattern).with_context(|| {
+                    format!(
+                        "[normalizer].rules[{i}]: invalid pattern `{}`",
+                        rule.pattern
+                    )
+                })?;
+                Ok((regex, rule.replace.clone()))
+            })
+            .collect::<Result<_>>()?;
+        let words = config
+            .words
+            .iter()
+            .filter(|(word, _)| !word.trim().is_empty())
+            .map(|(word, fix)| {
+                let regex = Regex::new(&format!(r"(?i)\b{}\b", regex::escape(word.trim())))
+                    .with_context(|| format!("[normalizer].words: can't match `{word}`"))?;
+                Ok((regex, fix.clone()))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            base,
+            rules,
+            words,
+            sentence_case: config.sentence_case,
+        })
+    }
+
+    /// Wrap `base` only when `config` has something to apply.
+    pub fn compose(
+        base: Box<dyn TranscriptionNormalizer>,
+        config: &NormalizerConfig,
+    ) -> Result<Box<dyn TranscriptionNormalizer>> {
+        if config.rules.is_empty() && config.words.is_empty() && !config.sentence_case {
+            return Ok(base);
+        }
+        Ok(Box::new(Self::new(base, config)?))
+    }
+}
+
+impl TranscriptionNormalizer for CustomRulesNormalizer {
+    fn normalize(&self, raw_output: &str) -> String {
+        let mut text = self.base.normalize(raw_output);
+        for (regex, replace) in &self.rules {
+            text = regex.replace_all(&text, replace.as_str()).into_owned();
+        }
+        for (regex, fix) in &self.words {
+            text = regex.replace_all(&text, NoExpand(fix)).into_owned();
+        }
+        if self.sentence_case {
+            text = sentence_case(&text);
+        }
+        text
+    }
+
+    fn name(&self) -> &'static str {
+        "CustomRulesNormalizer"
+    }
+}
+
+/// Uppercase the first letter of `text` and the first letter after each
+/// `.`, `!`, or `?` that is followed by whitespace. Everything else is kept
+/// as is, and a sentence starting with a mixed-case word ("iPhone",
+/// "eBay") is left alone.
+fn sentence_case(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut at_start = true;
+    let mut after_terminator = false;
+    for (i, c) in text.char_indices() {
+        if at_start && c.is_alphabetic() {
+            let word = text[i..]
+                .split(|ch: char| !ch.is_alphanumeric())
+                .next()
+                .unwrap_or_default();
+            if word.chars().skip(1).any(char::is_uppercase) {
+                out.push(c);
+            } else {
+                out.extend(c.to_uppercase());
+            }
+            at_start = false;
+            after_terminator = false;
+            continue;
+        }
+        if c.is_alphanumeric() {
+            at_start = false;
+        }
+        if matches!(c, '.' | '!' | '?') {
+            after_terminator = true;
+        } else if c.is_whitespace() {
+            if after_terminator {
+                at_start = true;
+            }
+        } else {
+            after_terminator = false;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ReplaceRule;
+
+    struct TrimNormalizer;
+
+    impl TranscriptionNormalizer for TrimNormalizer {
+        fn normalize(&self, raw_output: &str) -> String {
+            raw_output.trim().to_string()
+        }
+
+        fn name(&self) -> &'static str {
+            "TrimNormalizer"
+        }
+    }
+
+    fn rule(pattern: &str, replace: &str) -> ReplaceRule {
+        ReplaceRule {
+            pattern: pattern.to_string(),
+            replace: replace.to_string(),
+        }
+    }
+
+    fn normalizer(config: NormalizerConfig) -> CustomRulesNormalizer {
+        CustomRulesNormalizer::new(Box::new(TrimNormalizer), &config).unwrap()
+    }
+
+    #[test]
+    fn rules_apply_in_order_after_the_base_normalizer() {
+        let rules = vec![rule(r"\bcomma\b", ","), rule(r"\s+,", ",")];
+        let config = NormalizerConfig {
+            rules: rules.clone(),
+            ..Default::default()
+        };
+        // The second rule tidies up after the first.
+        assert_eq!(
+            normalizer(config).normalize("  yes comma please  "),
+            "yes, please"
+        );
+
+        let reversed = NormalizerConfig {
+            rules: rules.into_iter().rev().collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            normalizer(reversed).normalize("yes comma please"),
+            "yes , please"
+        );
+    }
+
+    #[test]
+    fn rules_expand_capture_groups() {
+        let config = NormalizerConfig {
+            rules: vec![rule(r"(\d+) percent", "$1%")],
+            ..Default::default()
+        };
+        assert_eq!(normalizer(config).normalize("up 40 percent"), "up 40%");
+    }
+
+    #[test]
+    fn word_fixes_match_whole_words_case_insensitively() {
+        let config = NormalizerConfig {
+            words: [("github", "GitHub"), ("audetik", "Audetic")]
+                .into_iter()
+                .map(|(word, fix)| (word.to_string(), fix.to_string()))
+                .collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            normalizer(config).normalize("push to Github and githubber, then open audetik"),
+            "push to GitHub and githubber, then open Audetic"
+        );
+    }
+
+    #[test]
+    fn sentence_case_capitalizes_sentence_starts_only() {
+        assert_eq!(
+            sentence_case("hello there. how are you? fine! on GitHub... ok"),
+            "Hello there. How are you? Fine! On GitHub... Ok"
+        );
+        assert_eq!(sentence_case("version 1.2 works"), "Version 1.2 works");
+        assert_eq!(sentence_case("  \"quoted\" start"), "  \"Quoted\" start");
+        assert_eq!(sentence_case(""), "");
+    }
+
+    #[test]
+    fn word_fixes_run_before_sentence_case() {
+        let config = NormalizerConfig {
+            words: [("iphone".to_string(), "iPhone".to_string())].into(),
+            sentence_case: true,
+            ..Default::default()
+        };
+        // Mixed-case words keep their casing at a sentence start.
+        assert_eq!(
+            normalizer(config).normalize("iphone sales. the iphone too"),
+            "iPhone sales. The iPhone too"
+        );
+    }
+
+    #[test]
+    fn invalid_patterns_are_errors() {
+        let config = NormalizerConfig {
+            rules: vec![rule("ok", "fine"), rule("(unclosed", "")],
+            ..Default::default()
+        };
+        let err = CustomRulesNormalizer::new(Box::new(TrimNormalizer), &config)
+            .err()
+            .unwrap();
+        assert!(
+            format!("{err:#}").contains("[normalizer].rules[1]"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn compose_leaves_the_base_alone_without_rules() {
+        let composed =
+            CustomRulesNormalizer::compose(Box::new(TrimNormalizer), &NormalizerConfig::default())
+                .unwrap();
+        assert_eq!(composed.name(), "TrimNormalizer");
+    }
+}
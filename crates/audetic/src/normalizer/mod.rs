@@ -1,3 +1,5 @@
+mod custom_rules;
 mod transcription_normalizer;
 
+pub use custom_rules::CustomRulesNormalizer;
 pub use transcription_normalizer::TranscriptionNormalizer;
@@ -0,0 +1,51 @@
+//! macOS text injection: simulate Cmd+V through System Events.
+//!
+//! The clipboard side already works through `arboard`; what macOS lacks is
+//! wtype/ydotool, so dictation is copied and then pasted with a synthetic
+//! keystroke. `osascript` needs no extra dependencies, but macOS only lets it
+//! send keystrokes once the app running it (Audetic, or the terminal it was
+//! started from) is allowed under Privacy & Security → Accessibility.
+
+#![cfg(target_os = "macos")]
+
+use anyhow::{anyhow, Context, Result};
+use std::process::Command;
+use tracing::debug;
+
+const PASTE_SCRIPT: &str =
+    r#"tell application "System Events" to keystroke "v" using command down"#;
+
+/// Press Cmd+V in the frontmost application.
+pub fn paste() -> Result<()> {
+    let output = Command::new("osascript")
+        .args(["-e", PASTE_SCRIPT])
+        .output()
+        .context("Failed to execute osascript")?;
+
+    if output.status.success() {
+        debug!("Successfully pasted with osascript");
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if is_permission_error(&stderr) {
+        return Err(anyhow!(
+            "macOS blocked the paste keystroke ({}). Allow Audetic under System Settings → \
+             Privacy & Security → Accessibility (and Automation → System Events if listed), \
+             then dictate again. The text is still on the clipboard.",
+            stderr.trim()
+        ));
+    }
+    Err(anyhow!("osascript paste failed: {}", stderr.trim()))
+}
+
+/// Whether osascript failed because the Accessibility (or Automation) grant
+/// is missing rather than for some other reason. System Events reports
+/// error 1002 ("not allowed to send keystrokes") and Apple Events -1743
+/// ("not authorized to send Apple events").
+fn is_permission_error(stderr: &str) -> bool {
+    stderr.contains("not allowed")
+        || stderr.contains("not authorized")
+        || stderr.contains("(1002)")
+        || stderr.contains("(-1743)")
+}
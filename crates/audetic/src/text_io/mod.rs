@@ -1,8 +1,11 @@
 use anyhow::{anyhow, Context, Result};
 use arboard::Clipboard;
+use async_trait::async_trait;
+use std::future::Future;
 use std::io::Write;
 use std::process::{Command, Stdio};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 use which::which;
@@ -14,6 +17,26 @@ use which::which;
 pub use audetic_core::clipboard::copy_to_clipboard_sync;
 use audetic_core::clipboard::CLIPBOARD_BACKENDS;
 
+/// Most characters handed to a single `xdotool type`. Longer dictations are
+/// typed in pieces so the argument stays far below the kernel's limit.
+const XDOTOOL_CHUNK_CHARS: usize = 1000;
+
+#[cfg(target_os = "macos")]
+mod macos;
+mod timestamp;
+pub use timestamp::stamp_text;
+
+/// Where finished dictation goes. [`TextIoService`] drives the real clipboard
+/// and keyboard; tests inject one that records what would have been typed.
+#[async_trait]
+pub trait TextOutput: Send + Sync {
+    async fn copy_to_clipboard(&self, text: &str) -> Result<()>;
+
+    async fn inject_text(&self, text: &str) -> Result<()>;
+
+    async fn paste_from_clipboard(&self) -> Result<()>;
+}
+
 #[derive(Clone)]
 pub struct TextIoService {
     inner: Arc<TextIoInner>,
@@ -23,10 +46,29 @@ struct TextIoInner {
     clipboard: Mutex<Option<Clipboard>>,
     preserve_previous: bool,
     injection_method: InjectionMethod,
+    /// `[behavior].paste_delay_ms`: pause before typing or pasting so the
+    /// target window can take focus back.
+    paste_delay: Duration,
+    /// `[behavior].clipboard_restore_delay_ms`: pause after pasting before
+    /// the previous clipboard contents go back.
+    restore_delay: Duration,
+    /// With `preserve_previous`, what the clipboard held before dictation was
+    /// copied onto it, until a paste puts it back.
+    saved: Mutex<Option<SavedClipboard>>,
 }
 
+/// What the clipboard held before dictation was copied onto it. `None` when
+/// it was empty or held something other than text, which can't be put back.
+#[derive(Debug, Clone, PartialEq)]
+struct SavedClipboard(Option<String>);
+
 impl TextIoService {
-    pub fn new(preferred_method: Option<&str>, preserve_previous: bool) -> Result<Self> {
+    pub fn new(
+        preferred_method: Option<&str>,
+        preserve_previous: bool,
+        paste_delay: Duration,
+        restore_delay: Duration,
+    ) -> Result<Self> {
         let clipboard = match Clipboard::new() {
             Ok(cb) => Some(cb),
             Err(err) => {
@@ -44,6 +86,9 @@ impl TextIoService {
                 clipboard: Mutex::new(clipboard),
                 preserve_previous,
                 injection_method,
+                paste_delay,
+                restore_delay,
+                saved: Mutex::new(None),
             }),
         })
     }
@@ -60,17 +105,20 @@ impl TextIoService {
         info!("Copying {} chars to clipboard", text.len());
         debug!("Text to copy: {}", text);
 
-        let preserve_previous = self.inner.preserve_previous;
-        let mut previous: Option<String> = None;
+        if self.inner.preserve_previous {
+            self.save_previous(text).await;
+        }
+        self.set_clipboard_text(text).await
+    }
+
+    /// Put `text` on the clipboard, through arboard when it works and the
+    /// system clipboard tools otherwise.
+    async fn set_clipboard_text(&self, text: &str) -> Result<()> {
         let mut used_native = false;
 
         {
             let mut clipboard_guard = self.inner.clipboard.lock().await;
             if let Some(clipboard) = clipboard_guard.as_mut() {
-                if preserve_previous {
-                    previous = clipboard.get_text().ok();
-                }
-
                 match clipboard.set_text(text) {
                     Ok(_) => {
                         used_native = true;
@@ -92,13 +140,38 @@ impl TextIoService {
             self.copy_with_system_backends(text).await?;
         }
 
-        if let Some(prev) = previous {
-            debug!("Previous clipboard content preserved: {} chars", prev.len());
-        }
-
         Ok(())
     }
 
+    /// Remember what the clipboard holds before `incoming` replaces it.
+    async fn save_previous(&self, incoming: &str) {
+        let current = {
+            let mut clipboard_guard = self.inner.clipboard.lock().await;
+            match clipboard_guard.as_mut() {
+                // arboard errors when the clipboard is empty or isn't text.
+                Some(clipboard) => clipboard.get_text().ok(),
+                None => read_with_system_backends(),
+            }
+        };
+        let mut saved = self.inner.saved.lock().await;
+        remember_previous(&mut saved, current, incoming);
+    }
+
+    /// After a paste, put back the clipboard contents [`Self::save_previous`]
+    /// captured, once `restore_delay` has passed.
+    async fn restore_previous(&self) {
+        let Some(saved) = self.inner.saved.lock().await.take() else {
+            return;
+        };
+        restore_after_delay(
+            saved,
+            self.inner.restore_delay,
+            tokio::time::sleep,
+            |text| async move { self.set_clipboard_text(&text).await },
+        )
+        .await;
+    }
+
     pub async fn inject_text(&self, text: &str) -> Result<()> {
         if text.is_empty() {
             return Ok(());
@@ -107,36 +180,85 @@ impl TextIoService {
         info!("Injecting text: {} chars", text.len());
         debug!("Text to inject: {}", text);
 
-        match self.inner.injection_method {
-            InjectionMethod::Wtype => {
-                self.try_with_clipboard_fallback(text, Self::inject_with_wtype)
-                    .await
-            }
-            InjectionMethod::Ydotool => {
-                self.try_with_clipboard_fallback(text, Self::inject_with_ydotool)
-                    .await
+        let inject = async {
+            match self.inner.injection_method {
+                InjectionMethod::Wtype => {
+                    self.try_with_clipboard_fallback(text, Self::inject_with_wtype)
+                        .await
+                }
+                InjectionMethod::Ydotool => {
+                    self.try_with_clipboard_fallback(text, Self::inject_with_ydotool)
+                        .await
+                }
+                InjectionMethod::Xdotool => {
+                    self.try_with_clipboard_fallback(text, Self::inject_with_xdotool)
+                        .await
+                }
+                InjectionMethod::Clipboard => self.simulate_paste().await,
+                #[cfg(target_os = "macos")]
+                InjectionMethod::Osascript => {
+                    self.copy_to_clipboard(text).await?;
+                    self.simulate_paste().await
+                }
             }
-            InjectionMethod::Clipboard => self.simulate_paste().await,
-        }
+        };
+        after_paste_delay(self.inner.paste_delay, tokio::time::sleep, inject).await?;
+        // On failure the caller falls back to `paste_from_clipboard`, which
+        // restores once that paste is done.
+        self.restore_previous().await;
+        Ok(())
     }
 
     pub async fn paste_from_clipboard(&self) -> Result<()> {
-        self.simulate_paste().await
+        let result = after_paste_delay(
+            self.inner.paste_delay,
+            tokio::time::sleep,
+            self.simulate_paste(),
+        )
+        .await;
+        self.restore_previous().await;
+        result
     }
 
+    /// Type `text` with `inject_fn`, pasting whatever it didn't get to.
+    /// `inject_fn` advances its second argument past each byte of `text`
+    /// that reached the window, so a failure partway through pastes only
+    /// the rest instead of repeating what's already there.
     async fn try_with_clipboard_fallback<F>(&self, text: &str, inject_fn: F) -> Result<()>
     where
-        F: Fn(&str) -> Result<()>,
+        F: Fn(&str, &mut usize) -> Result<()>,
     {
-        if let Err(err) = inject_fn(text) {
-            warn!(
-                "Direct text injection failed with {} – falling back to clipboard paste",
-                err
-            );
-            self.copy_to_clipboard(text).await?;
+        let mut typed = 0;
+        let Err(err) = inject_fn(text, &mut typed) else {
+            return Ok(());
+        };
+        warn!(
+            "Direct text injection failed with {} after {} of {} bytes – falling back to clipboard paste",
+            err,
+            typed,
+            text.len()
+        );
+        // Saved against the whole dictation, which is what the caller may
+        // already have copied over the user's clipboard.
+        if self.inner.preserve_previous {
+            self.save_previous(text).await;
+        }
+        let pasted = async {
+            self.set_clipboard_text(&text[typed..]).await?;
             self.simulate_paste().await
-        } else {
-            Ok(())
+        }
+        .await;
+        match pasted {
+            // The caller's own fallback would paste the whole dictation
+            // again, on top of the part that was typed.
+            Err(err) if typed > 0 => {
+                warn!(
+                    "Failed to paste the rest of the dictation ({}); the full text stays available from history",
+                    err
+                );
+                Ok(())
+            }
+            result => result,
         }
     }
 
@@ -176,7 +298,7 @@ impl TextIoService {
         ))
     }
 
-    fn inject_with_wtype(text: &str) -> Result<()> {
+    fn inject_with_wtype(text: &str, _typed: &mut usize) -> Result<()> {
         let output = Command::new("wtype")
             .arg(text)
             .output()
@@ -190,7 +312,7 @@ impl TextIoService {
         Ok(())
     }
 
-    fn inject_with_ydotool(text: &str) -> Result<()> {
+    fn inject_with_ydotool(text: &str, _typed: &mut usize) -> Result<()> {
         let output = Command::new("ydotool")
             .arg("type")
             .arg(text)
@@ -209,6 +331,32 @@ impl TextIoService {
         Ok(())
     }
 
+    /// Type `text` into the focused X11 window. `--clearmodifiers` keeps a
+    /// still-held hotkey modifier from turning letters into shortcuts.
+    fn inject_with_xdotool(text: &str, typed: &mut usize) -> Result<()> {
+        type_in_chunks(text, XDOTOOL_CHUNK_CHARS, typed, |chunk| {
+            let output = Command::new("xdotool")
+                .args(["type", "--clearmodifiers", "--", chunk])
+                .output()
+                .context("Failed to execute xdotool")?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(anyhow!("xdotool failed: {}", stderr));
+            }
+            Ok(())
+        })
+    }
+
+    /// On macOS a blocked paste is an error carrying the Accessibility hint,
+    /// rather than the silent fallback the Linux tools get.
+    #[cfg(target_os = "macos")]
+    async fn simulate_paste(&self) -> Result<()> {
+        info!("Simulating paste from clipboard");
+        macos::paste()
+    }
+
+    #[cfg(not(target_os = "macos"))]
     async fn simulate_paste(&self) -> Result<()> {
         info!("Simulating paste from clipboard");
 
@@ -271,25 +419,121 @@ impl TextIoService {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[async_trait]
+impl TextOutput for TextIoService {
+    async fn copy_to_clipboard(&self, text: &str) -> Result<()> {
+        TextIoService::copy_to_clipboard(self, text).await
+    }
+
+    async fn inject_text(&self, text: &str) -> Result<()> {
+        TextIoService::inject_text(self, text).await
+    }
+
+    async fn paste_from_clipboard(&self) -> Result<()> {
+        TextIoService::paste_from_clipboard(self).await
+    }
+}
+
+/// Run `keystrokes` (typing or a paste) once `delay` has passed, giving the
+/// window the text is meant for time to regain focus after the
+/// notification. `sleep` is injectable for tests.
+async fn after_paste_delay<S, T>(
+    delay: Duration,
+    sleep: impl FnOnce(Duration) -> S,
+    keystrokes: impl Future<Output = T>,
+) -> T
+where
+    S: Future<Output = ()>,
+{
+    if !delay.is_zero() {
+        debug!("Waiting {:?} for focus before pasting", delay);
+        sleep(delay).await;
+    }
+    keystrokes.await
+}
+
+/// Split `text` into pieces of at most `max_chars` characters, never inside
+/// a multibyte character.
+fn char_chunks(text: &str, max_chars: usize) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let cut = rest
+            .char_indices()
+            .nth(max_chars)
+            .map_or(rest.len(), |(i, _)| i);
+        let (chunk, tail) = rest.split_at(cut);
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+/// Type `text` with `type_chunk`, at most `max_chars` characters at a time,
+/// advancing `typed` past each chunk that went through. Stops at the first
+/// chunk that fails.
+fn type_in_chunks(
+    text: &str,
+    max_chars: usize,
+    typed: &mut usize,
+    mut type_chunk: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    for chunk in char_chunks(text, max_chars) {
+        type_chunk(chunk)?;
+        *typed += chunk.len();
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InjectionMethod {
     Wtype,
     Ydotool,
+    /// Types via `xdotool type`, for X11 sessions.
+    Xdotool,
     Clipboard,
+    /// Copy, then Cmd+V via `osascript`. The only method on macOS.
+    #[cfg(target_os = "macos")]
+    Osascript,
 }
 
 impl InjectionMethod {
+    /// wtype, ydotool, xdotool, and the Linux clipboard tools don't exist on
+    /// macOS, so `input_method` is ignored there.
+    #[cfg(target_os = "macos")]
     fn detect(preferred: Option<&str>) -> Self {
+        if let Some(choice) = preferred {
+            debug!("Ignoring input_method '{}' on macOS", choice);
+        }
+        info!("Using osascript paste for text injection (macOS)");
+        InjectionMethod::Osascript
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn detect(preferred: Option<&str>) -> Self {
+        Self::choose(preferred, &Session::current())
+    }
+
+    /// Pick a method for `session`: the configured one when its tool is
+    /// installed, then ydotool (any compositor), Wayland's clipboard, wtype,
+    /// and on X11 xdotool, before settling for clipboard-only.
+    #[cfg(not(target_os = "macos"))]
+    fn choose(preferred: Option<&str>, session: &Session) -> Self {
         if let Some(choice) = preferred {
             match choice {
-                "ydotool" if which("ydotool").is_ok() => {
+                "ydotool" if session.ydotool => {
                     info!("Using ydotool for text injection (per config)");
                     return InjectionMethod::Ydotool;
                 }
-                "wtype" if which("wtype").is_ok() => {
+                "wtype" if session.wtype => {
                     info!("Using wtype for text injection (per config)");
                     return InjectionMethod::Wtype;
                 }
+                "xdotool" if session.xdotool => {
+                    info!("Using xdotool for text injection (per config)");
+                    return InjectionMethod::Xdotool;
+                }
                 other => {
                     warn!(
                         "Unknown or unavailable input_method '{}', falling back to auto-detect",
@@ -299,26 +543,314 @@ impl InjectionMethod {
             }
         }
 
-        if which("ydotool").is_ok() {
+        if session.ydotool {
             info!("Using ydotool for text injection (auto-detected)");
             return InjectionMethod::Ydotool;
         }
 
-        if std::env::var("WAYLAND_DISPLAY").is_ok() && which("wl-copy").is_ok() {
+        if session.wayland && session.wl_copy {
             info!("Using clipboard-based injection (Wayland detected)");
             return InjectionMethod::Clipboard;
         }
 
-        if which("wtype").is_ok() {
+        if session.wtype {
             info!("Using wtype for text injection (auto-detected)");
             return InjectionMethod::Wtype;
         }
 
+        if session.x11 && !session.wayland && session.xdotool {
+            info!("Using xdotool for text injection (X11 detected)");
+            return InjectionMethod::Xdotool;
+        }
+
         info!("Falling back to clipboard-based injection");
         InjectionMethod::Clipboard
     }
 }
 
+/// What [`InjectionMethod::detect`] looks at: the display session and which
+/// tools are on `PATH`.
+#[cfg(not(target_os = "macos"))]
+#[derive(Debug, Default)]
+struct Session {
+    wayland: bool,
+    x11: bool,
+    ydotool: bool,
+    wtype: bool,
+    wl_copy: bool,
+    xdotool: bool,
+}
+
+#[cfg(not(target_os = "macos"))]
+impl Session {
+    fn current() -> Self {
+        Self {
+            wayland: std::env::var_os("WAYLAND_DISPLAY").is_some(),
+            x11: std::env::var_os("DISPLAY").is_some(),
+            ydotool: which("ydotool").is_ok(),
+            wtype: which("wtype").is_ok(),
+            wl_copy: which("wl-copy").is_ok(),
+            xdotool: which("xdotool").is_ok(),
+        }
+    }
+}
+
+/// Record `current`, the clipboard's contents before `incoming` is copied
+/// onto it. When the clipboard already holds `incoming` (the direct-typing
+/// fallback copies text the caller had just copied), the contents saved
+/// before that first copy are kept.
+fn remember_previous(saved: &mut Option<SavedClipboard>, current: Option<String>, incoming: &str) {
+    if saved.is_some() && current.as_deref() == Some(incoming) {
+        return;
+    }
+    let current = current.filter(|text| !text.is_empty());
+    match &current {
+        Some(text) => debug!("Saved previous clipboard content: {} chars", text.len()),
+        None => debug!("Previous clipboard is empty or not text; nothing to restore"),
+    }
+    *saved = Some(SavedClipboard(current));
+}
+
+/// Put `saved` back on the clipboard via `restore` once `delay` has passed,
+/// so the target app reads the pasted dictation before it changes. `sleep`
+/// is injectable for tests.
+async fn restore_after_delay<S, R>(
+    saved: SavedClipboard,
+    delay: Duration,
+    sleep: impl FnOnce(Duration) -> S,
+    restore: impl FnOnce(String) -> R,
+) where
+    S: Future<Output = ()>,
+    R: Future<Output = Result<()>>,
+{
+    let Some(text) = saved.0 else {
+        debug!("Leaving dictation on the clipboard; previous contents weren't text");
+        return;
+    };
+    if !delay.is_zero() {
+        sleep(delay).await;
+    }
+    match restore(text).await {
+        Ok(()) => debug!("Restored previous clipboard content"),
+        Err(err) => warn!("Failed to restore previous clipboard content: {}", err),
+    }
+}
+
+/// Read the clipboard's text with the first system tool that answers.
+/// Output that isn't UTF-8 (an image, say) counts as nothing to restore.
+fn read_with_system_backends() -> Option<String> {
+    CLIPBOARD_BACKENDS
+        .iter()
+        .filter(|backend| which(backend.paste_cmd).is_ok())
+        .find_map(|backend| {
+            let output = Command::new(backend.paste_cmd)
+                .args(backend.paste_args)
+                .stdin(Stdio::null())
+                .stderr(Stdio::null())
+                .output()
+                .ok()?;
+            output.status.success().then_some(output.stdout)
+        })
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+}
+
 // `ClipboardBackend`, `CLIPBOARD_BACKENDS`, and `copy_to_clipboard_sync` now
 // live in `audetic_core::clipboard` (imported/re-exported at the top of this
 // module).
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+
+    /// Runs [`after_paste_delay`] with a sleep and a paste that only record
+    /// themselves, returning what happened in order.
+    async fn events_for(delay: Duration) -> Vec<String> {
+        let events = StdMutex::new(Vec::new());
+        let sleep = |d: Duration| {
+            events
+                .lock()
+                .unwrap()
+                .push(format!("sleep {}ms", d.as_millis()));
+            async {}
+        };
+        let paste = async {
+            events.lock().unwrap().push("paste".to_string());
+        };
+        after_paste_delay(delay, sleep, paste).await;
+        events.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn paste_waits_for_the_configured_delay_first() {
+        assert_eq!(
+            events_for(Duration::from_millis(150)).await,
+            ["sleep 150ms", "paste"]
+        );
+    }
+
+    #[tokio::test]
+    async fn zero_delay_pastes_immediately() {
+        assert_eq!(events_for(Duration::ZERO).await, ["paste"]);
+    }
+
+    fn saved(text: &str) -> Option<SavedClipboard> {
+        Some(SavedClipboard(Some(text.to_string())))
+    }
+
+    #[test]
+    fn previous_clipboard_is_captured_once_per_dictation() {
+        let mut slot = None;
+        remember_previous(&mut slot, Some("link I copied".into()), "hello");
+        assert_eq!(slot, saved("link I copied"));
+
+        // The typing fallback copies the same dictation again.
+        remember_previous(&mut slot, Some("hello".into()), "hello");
+        assert_eq!(slot, saved("link I copied"));
+
+        // A copy that was never pasted doesn't leave stale contents behind.
+        remember_previous(&mut slot, Some("newer copy".into()), "next");
+        assert_eq!(slot, saved("newer copy"));
+    }
+
+    #[test]
+    fn empty_or_non_text_clipboard_has_nothing_to_restore() {
+        let mut slot = None;
+        remember_previous(&mut slot, Some(String::new()), "hello");
+        assert_eq!(slot, Some(SavedClipboard(None)));
+
+        remember_previous(&mut slot, None, "hello");
+        assert_eq!(slot, Some(SavedClipboard(None)));
+    }
+
+    async fn restore_events(saved: SavedClipboard, delay: Duration) -> Vec<String> {
+        let events = StdMutex::new(Vec::new());
+        let sleep = |d: Duration| {
+            events
+                .lock()
+                .unwrap()
+                .push(format!("sleep {}ms", d.as_millis()));
+            async {}
+        };
+        let restore = |text: String| {
+            events.lock().unwrap().push(format!("restore {text}"));
+            async { Ok(()) }
+        };
+        restore_after_delay(saved, delay, sleep, restore).await;
+        events.into_inner().unwrap()
+    }
+
+    #[tokio::test]
+    async fn previous_text_is_restored_after_the_delay() {
+        let saved = SavedClipboard(Some("link I copied".into()));
+        assert_eq!(
+            restore_events(saved, Duration::from_millis(300)).await,
+            ["sleep 300ms", "restore link I copied"]
+        );
+    }
+
+    #[tokio::test]
+    async fn nothing_is_restored_when_the_previous_clipboard_was_not_text() {
+        let events = restore_events(SavedClipboard(None), Duration::from_millis(300)).await;
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn long_text_is_chunked_on_char_boundaries() {
+        let chunks: Vec<_> = char_chunks("héllo wörld", 4).collect();
+        assert_eq!(chunks, ["héll", "o wö", "rld"]);
+        assert_eq!(char_chunks("abc", 3).collect::<Vec<_>>(), ["abc"]);
+        assert_eq!(char_chunks("", 3).count(), 0);
+    }
+
+    #[test]
+    fn a_failed_chunk_leaves_only_the_untyped_rest() {
+        let text = "héllo wörld";
+        let mut sent = Vec::new();
+        let mut typed = 0;
+        let result = type_in_chunks(text, 4, &mut typed, |chunk| {
+            if sent.len() == 1 {
+                anyhow::bail!("xdotool failed");
+            }
+            sent.push(chunk.to_string());
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(sent, ["héll"]);
+        assert_eq!(&text[typed..], "o wörld");
+
+        let mut typed = 0;
+        type_in_chunks(text, 4, &mut typed, |_| Ok(())).unwrap();
+        assert_eq!(typed, text.len());
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn detection_prefers_wayland_tools_then_xdotool_on_x11() {
+        let x11 = Session {
+            x11: true,
+            xdotool: true,
+            ..Session::default()
+        };
+        assert_eq!(
+            InjectionMethod::choose(None, &x11),
+            InjectionMethod::Xdotool
+        );
+
+        // Wayland tools still win when they're installed.
+        let with_wtype = Session { wtype: true, ..x11 };
+        assert_eq!(
+            InjectionMethod::choose(None, &with_wtype),
+            InjectionMethod::Wtype
+        );
+        let with_ydotool = Session {
+            ydotool: true,
+            ..with_wtype
+        };
+        assert_eq!(
+            InjectionMethod::choose(None, &with_ydotool),
+            InjectionMethod::Ydotool
+        );
+
+        // XWayland sets DISPLAY too, but xdotool can't reach Wayland windows.
+        let xwayland = Session {
+            wayland: true,
+            x11: true,
+            xdotool: true,
+            ..Session::default()
+        };
+        assert_eq!(
+            InjectionMethod::choose(None, &xwayland),
+            InjectionMethod::Clipboard
+        );
+        let without_display = Session {
+            xdotool: true,
+            ..Session::default()
+        };
+        assert_eq!(
+            InjectionMethod::choose(None, &without_display),
+            InjectionMethod::Clipboard
+        );
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn configured_method_wins_when_installed() {
+        let session = Session {
+            wayland: true,
+            ydotool: true,
+            xdotool: true,
+            ..Session::default()
+        };
+        assert_eq!(
+            InjectionMethod::choose(Some("xdotool"), &session),
+            InjectionMethod::Xdotool
+        );
+        // wtype isn't installed, so auto-detection takes over.
+        assert_eq!(
+            InjectionMethod::choose(Some("wtype"), &session),
+            InjectionMethod::Ydotool
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Time stamps for injected dictation (`[behavior.inject_timestamp]`).
+
+use audetic_core::config::{InjectTimestampConfig, TimestampPosition};
+use chrono::{DateTime, Local};
+use std::fmt::Write;
+use tracing::warn;
+
+/// `text` with the configured stamp for `now` placed before or after it,
+/// separated by a space. An invalid format leaves the text unstamped.
+pub fn stamp_text(text: &str, config: &InjectTimestampConfig, now: &DateTime<Local>) -> String {
+    let mut stamp = String::new();
+    if write!(stamp, "{}", now.format(&config.format)).is_err() {
+        warn!(
+            "Invalid [behavior.inject_timestamp] format '{}'; injecting without a timestamp",
+            config.format
+        );
+        return text.to_string();
+    }
+
+    match config.position {
+        TimestampPosition::Prefix => format!("{stamp} {text}"),
+        TimestampPosition::Suffix => format!("{text} {stamp}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at_nine_oh_five() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 7).unwrap()
+    }
+
+    fn config(format: &str, position: TimestampPosition) -> InjectTimestampConfig {
+        InjectTimestampConfig {
+            format: format.to_string(),
+            position,
+            include_in_history: false,
+        }
+    }
+
+    #[test]
+    fn prefix_places_stamp_before_text() {
+        let stamped = stamp_text(
+            "buy milk",
+            &config("[%H:%M]", TimestampPosition::Prefix),
+            &at_nine_oh_five(),
+        );
+        assert_eq!(stamped, "[09:05] buy milk");
+    }
+
+    #[test]
+    fn suffix_places_stamp_after_text() {
+        let stamped = stamp_text(
+            "buy milk",
+            &config("(%H:%M:%S)", TimestampPosition::Suffix),
+            &at_nine_oh_five(),
+        );
+        assert_eq!(stamped, "buy milk (09:05:07)");
+    }
+
+    #[test]
+    fn expands_date_fields() {
+        let stamped = stamp_text(
+            "entry",
+            &config("%Y-%m-%d %a", TimestampPosition::Prefix),
+            &at_nine_oh_five(),
+        );
+        assert_eq!(stamped, "2026-03-14 Sat entry");
+    }
+
+    #[test]
+    fn invalid_format_leaves_text_unstamped() {
+        let stamped = stamp_text(
+            "entry",
+            &config("%Q", TimestampPosition::Prefix),
+            &at_nine_oh_five(),
+        );
+        assert_eq!(stamped, "entry");
+    }
+}
@@ -0,0 +1,246 @@
+//! Client-side audio length and size limits for cloud providers.
+//!
+//! Providers reject over-long or oversized uploads with opaque server errors,
+//! after the whole file has been sent. We read the duration locally first
+//! (WAV header via `hound`, anything else via `ffprobe`) and the file size,
+//! and fail early with a message that names the limit and the way around it.
+
+use anyhow::Result;
+use std::fmt;
+use std::path::Path;
+use tracing::debug;
+
+use crate::meeting::{FfprobeMediaInspector, MediaInspector};
+
+/// Audio that's longer or larger than the provider accepts. The message
+/// names the limit and the way around it.
+#[derive(Debug)]
+pub struct OverLimit(pub(crate) String);
+
+impl fmt::Display for OverLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for OverLimit {}
+
+/// Built-in limit for `provider`, in seconds. `None` means no known limit;
+/// local engines window long audio themselves.
+pub fn default_max_audio_seconds(provider: &str) -> Option<u64> {
+    match provider {
+        // OpenAI's transcription models cap input at 1500 s (and 25 MB).
+        "openai-api" => Some(1_500),
+        // AssemblyAI accepts files up to 10 hours.
+        "assembly-ai" => Some(10 * 60 * 60),
+        // Groq's free tier allows 7200 audio seconds per hour, so a longer
+        // file can never go through in one request.
+        "groq" => Some(2 * 60 * 60),
+        _ => None,
+    }
+}
+
+/// Largest upload `provider` accepts, in bytes. `None` means no known limit.
+pub fn default_max_upload_bytes(provider: &str) -> Option<u64> {
+    match provider {
+        // Both reject files over 25 MB (Groq's paid tiers take more, but
+        // only by URL, which we don't use).
+        "openai-api" | "groq" => Some(25 * 1024 * 1024),
+        _ => None,
+    }
+}
+
+/// The upload size limit to enforce. `[whisper].max_audio_seconds = 0`
+/// turns it off along with the length check, e.g. for a self-hosted
+/// OpenAI-compatible server.
+pub fn effective_max_upload_bytes(provider: &str, configured_seconds: Option<u64>) -> Option<u64> {
+    match configured_seconds {
+        Some(0) => None,
+        _ => default_max_upload_bytes(provider),
+    }
+}
+
+/// The limit to enforce: `[whisper].max_audio_seconds` when set (0 turns the
+/// check off), otherwise the provider's built-in default.
+pub fn effective_max_audio_seconds(provider: &str, configured: Option<u64>) -> Option<u64> {
+    match configured {
+        Some(0) => None,
+        Some(seconds) => Some(seconds),
+        None => default_max_audio_seconds(provider),
+    }
+}
+
+/// Duration of the audio at `path` in seconds, or `None` if it can't be read.
+pub async fn audio_duration_seconds(path: &Path) -> Option<f64> {
+    let is_wav = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    if is_wav {
+        if let Ok(reader) = hound::WavReader::open(path) {
+            let spec = reader.spec();
+            if spec.sample_rate > 0 {
+                return Some(reader.duration() as f64 / spec.sample_rate as f64);
+            }
+        }
+    }
+    FfprobeMediaInspector
+        .probe_duration_seconds(path)
+        .await
+        .map(|secs| secs as f64)
+}
+
+/// Fail if `path` is longer than `max_seconds`. Audio whose duration can't
+/// be determined is let through; the provider remains the final judge.
+pub async fn enforce_max_duration(
+    path: &Path,
+    provider_name: &str,
+    max_seconds: Option<u64>,
+) -> Result<()> {
+    let Some(max_seconds) = max_seconds else {
+        return Ok(());
+    };
+    let Some(duration) = audio_duration_seconds(path).await else {
+        debug!(
+            "Could not read duration of {:?}; skipping length check",
+            path
+        );
+        return Ok(());
+    };
+    check_duration(duration, provider_name, max_seconds)
+}
+
+/// Fail if the file at `path` is larger than `max_bytes`. A file whose size
+/// can't be read is let through, like one with an unknown duration.
+pub fn enforce_max_size(path: &Path, provider_name: &str, max_bytes: Option<u64>) -> Result<()> {
+    let Some(max_bytes) = max_bytes else {
+        return Ok(());
+    };
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Ok(());
+    };
+    if metadata.len() <= max_bytes {
+        return Ok(());
+    }
+    Err(OverLimit(format!(
+        "Audio file is {:.1} MB, over the {} MB upload limit for {}. Split it into \
+         shorter clips, or use `audetic meeting import`, which uploads through the \
+         jobs API in chunks.",
+        metadata.len() as f64 / (1024.0 * 1024.0),
+        max_bytes / (1024 * 1024),
+        provider_name
+    ))
+    .into())
+}
+
+fn check_duration(duration: f64, provider_name: &str, max_seconds: u64) -> Result<()> {
+    if duration <= max_seconds as f64 {
+        return Ok(());
+    }
+    Err(OverLimit(format!(
+        "Audio is {} long, over the {} limit for {}. Split it into shorter clips, \
+         or use `audetic meeting import`, which uploads through the jobs API in chunks. \
+         Adjust the limit with [whisper].max_audio_seconds.",
+        format_duration(duration),
+        format_duration(max_seconds as f64),
+        provider_name
+    ))
+    .into())
+}
+
+fn format_duration(seconds: f64) -> String {
+    let total = seconds.round() as u64;
+    match (total / 3600, (total % 3600) / 60, total % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, s) => format!("{h}h {m:02}m {s:02}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{WavSpec, WavWriter};
+
+    /// Write a silent mono WAV of `seconds` at a tiny sample rate.
+    fn write_wav(dir: &Path, seconds: u32) -> std::path::PathBuf {
+        let path = dir.join(format!("clip-{seconds}s.wav"));
+        let spec = WavSpec {
+            channels: 1,
+            sample_rate: 100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = WavWriter::create(&path, spec).unwrap();
+        for _ in 0..seconds * 100 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn wav_under_limit_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let clip = write_wav(dir.path(), 30);
+        assert_eq!(audio_duration_seconds(&clip).await, Some(30.0));
+        enforce_max_duration(&clip, "OpenAI API", Some(60))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn wav_over_limit_fails_with_clear_message() {
+        let dir = tempfile::tempdir().unwrap();
+        let clip = write_wav(dir.path(), 90);
+        let err = enforce_max_duration(&clip, "OpenAI API", Some(60))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1m 30s"), "{err}");
+        assert!(err.contains("1m 00s limit for OpenAI API"), "{err}");
+        assert!(err.contains("meeting import"), "{err}");
+    }
+
+    #[test]
+    fn per_provider_defaults_and_overrides() {
+        assert_eq!(effective_max_audio_seconds("openai-api", None), Some(1_500));
+        assert_eq!(
+            effective_max_audio_seconds("assembly-ai", None),
+            Some(36_000)
+        );
+        assert_eq!(effective_max_audio_seconds("groq", None), Some(7_200));
+        assert_eq!(effective_max_audio_seconds("local", None), None);
+        assert_eq!(
+            effective_max_audio_seconds("openai-api", Some(600)),
+            Some(600)
+        );
+        assert_eq!(effective_max_audio_seconds("openai-api", Some(0)), None);
+    }
+
+    #[test]
+    fn upload_size_limits() {
+        assert_eq!(
+            effective_max_upload_bytes("groq", None),
+            Some(25 * 1024 * 1024)
+        );
+        assert_eq!(effective_max_upload_bytes("groq", Some(0)), None);
+        assert_eq!(effective_max_upload_bytes("deepgram", None), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let clip = dir.path().join("clip.wav");
+        std::fs::write(&clip, vec![0u8; 2048]).unwrap();
+        enforce_max_size(&clip, "Groq", Some(4096)).unwrap();
+        let err = enforce_max_size(&clip, "Groq", Some(1024))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("upload limit for Groq"), "{err}");
+    }
+
+    #[test]
+    fn format_duration_units() {
+        assert_eq!(format_duration(42.4), "42s");
+        assert_eq!(format_duration(1500.0), "25m 00s");
+        assert_eq!(format_duration(3725.0), "1h 02m 05s");
+    }
+}
@@ -6,26 +6,34 @@
 use anyhow::{bail, Result};
 use async_trait::async_trait;
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{info, warn};
 
-use super::jobs_client::{status, JobsClient, Segment};
-use super::TranscriptionService;
+use super::jobs_client::{status, JobLimiter, JobsClient, Segment};
+use super::{TranscriptionService, Utterance};
+use audetic_core::http::HttpOptions;
 
 /// Result of a completed transcription job.
 pub struct TranscriptionJobResult {
     pub text: String,
     pub segments: Option<Vec<Segment>>,
+    /// Speaker-labelled turns; `None` unless diarization was requested and
+    /// the provider labels speakers (AssemblyAI, Deepgram).
+    pub utterances: Option<Vec<Utterance>>,
 }
 
 /// Trait for submitting audio to a remote transcription service and getting results.
 #[async_trait]
 pub trait TranscriptionJobService: Send + Sync {
+    /// `diarize` asks for speaker labels; implementations that can't provide
+    /// them return the transcript without `utterances` rather than failing.
     async fn submit_and_poll(
         &self,
         file_path: &Path,
         language: Option<&str>,
+        diarize: bool,
     ) -> Result<TranscriptionJobResult>;
 }
 
@@ -43,12 +51,20 @@ impl RemoteTranscriptionJobService {
     /// # Arguments
     /// * `base_url` - Jobs API base URL
     /// * `timeout` - Maximum time to wait for transcription to complete
-    pub fn new(base_url: &str, timeout: Duration) -> Self {
-        Self {
-            client: JobsClient::new(base_url),
+    /// * `http` - Network settings (connect timeout, proxy)
+    pub fn new(base_url: &str, timeout: Duration, http: &HttpOptions) -> Result<Self> {
+        Ok(Self {
+            client: JobsClient::with_http(base_url, http)?,
             poll_interval: Duration::from_secs(2),
             timeout,
-        }
+        })
+    }
+
+    /// Count this service's jobs against `limiter`, shared with other
+    /// clients and resizable on config reload.
+    pub fn with_job_limiter(mut self, limiter: Arc<JobLimiter>) -> Self {
+        self.client = self.client.with_job_limiter(limiter);
+        self
     }
 }
 
@@ -58,8 +74,16 @@ impl TranscriptionJobService for RemoteTranscriptionJobService {
         &self,
         file_path: &Path,
         language: Option<&str>,
+        diarize: bool,
     ) -> Result<TranscriptionJobResult> {
+        // Held until this function returns, so a failed or timed-out job
+        // frees its slot the same as a completed one.
+        let _slot = self.client.acquire_job_slot().await;
+
         info!("Submitting file for transcription: {:?}", file_path);
+        if diarize {
+            info!("The jobs API doesn't label speakers; transcript will be unlabeled");
+        }
 
         // Use streaming upload for large files
         let job_id = self
@@ -95,6 +119,7 @@ impl TranscriptionJobService for RemoteTranscriptionJobService {
                     return Ok(TranscriptionJobResult {
                         text: result.text,
                         segments: result.segments,
+                        utterances: None,
                     });
                 }
                 status::FAILED => {
@@ -131,6 +156,9 @@ impl TranscriptionJobService for RemoteTranscriptionJobService {
 /// provider). Wraps the same [`TranscriptionService`] the dictation pipeline
 /// uses, so meetings reuse the one in-memory model load. There's no remote job
 /// to poll — it transcribes synchronously and returns the result.
+///
+/// Also runs meetings through AssemblyAI or Deepgram directly when they ask
+/// for speaker labels, since diarization comes from those providers' APIs.
 pub struct LocalTranscriptionJobService {
     service: TranscriptionService,
 }
@@ -147,8 +175,25 @@ impl TranscriptionJobService for LocalTranscriptionJobService {
         &self,
         file_path: &Path,
         _language: Option<&str>,
+        diarize: bool,
     ) -> Result<TranscriptionJobResult> {
         info!("Transcribing meeting locally: {:?}", file_path);
+        if diarize {
+            let path = file_path.to_path_buf();
+            if let Some(diarized) = self.service.transcribe_diarized(&path).await? {
+                info!(
+                    "Meeting transcription complete: {} chars, {} utterances",
+                    diarized.text.len(),
+                    diarized.utterances.len()
+                );
+                return Ok(TranscriptionJobResult {
+                    text: diarized.text,
+                    segments: None,
+                    utterances: Some(diarized.utterances),
+                });
+            }
+            info!("This provider can't label speakers; transcript will be unlabeled");
+        }
         let output = self
             .service
             .transcribe_detailed(&file_path.to_path_buf())
@@ -165,6 +210,7 @@ impl TranscriptionJobService for LocalTranscriptionJobService {
             } else {
                 Some(output.segments)
             },
+            utterances: None,
         })
     }
 }
@@ -178,6 +224,7 @@ mod tests {
         let result = TranscriptionJobResult {
             text: "Hello world".to_string(),
             segments: None,
+            utterances: None,
         };
         assert_eq!(result.text, "Hello world");
         assert!(result.segments.is_none());
@@ -191,7 +238,9 @@ mod tests {
                 start: 0.0,
                 end: 1.5,
                 text: "Hello world".to_string(),
+                words: None,
             }]),
+            utterances: None,
         };
         assert_eq!(result.segments.as_ref().unwrap().len(), 1);
         assert_eq!(result.segments.as_ref().unwrap()[0].start, 0.0);
@@ -202,7 +251,9 @@ mod tests {
         let service = RemoteTranscriptionJobService::new(
             "https://example.com/api/v1/jobs",
             Duration::from_secs(7200),
-        );
+            &HttpOptions::default(),
+        )
+        .unwrap();
         assert_eq!(service.timeout, Duration::from_secs(7200));
         assert_eq!(service.poll_interval, Duration::from_secs(2));
     }
@@ -0,0 +1,109 @@
+//! Choosing between `[whisper].candidate_languages`.
+//!
+//! When the provider reports confidence, the audio is transcribed once per
+//! candidate and the most confident transcript wins. Candidates that failed
+//! or came back unscored never beat a scored one; if nothing was scored the
+//! first candidate that succeeded is used, matching providers that can't
+//! score at all.
+
+use tracing::debug;
+
+use super::providers::ScoredTranscription;
+
+/// One candidate language's transcript.
+pub struct LanguageAttempt {
+    pub language: String,
+    pub result: ScoredTranscription,
+}
+
+/// The attempt to keep: highest confidence, earliest candidate on ties, or
+/// the first attempt when none was scored. `None` only for no attempts.
+pub fn select_best(attempts: Vec<LanguageAttempt>) -> Option<LanguageAttempt> {
+    let best_scored = attempts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, attempt)| attempt.result.confidence.map(|c| (i, c)))
+        .fold(
+            None,
+            |best: Option<(usize, f64)>, (i, confidence)| match best {
+                Some((_, best_confidence)) if best_confidence >= confidence => best,
+                _ => Some((i, confidence)),
+            },
+        );
+
+    let index = match best_scored {
+        Some((index, confidence)) => {
+            debug!(
+                "Selected language {} (confidence {:.3})",
+                attempts[index].language, confidence
+            );
+            index
+        }
+        None => 0,
+    };
+    attempts.into_iter().nth(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcription::TranscriptionOutput;
+
+    fn attempt(language: &str, confidence: Option<f64>) -> LanguageAttempt {
+        LanguageAttempt {
+            language: language.to_string(),
+            result: ScoredTranscription {
+                output: TranscriptionOutput {
+                    text: format!("transcript in {language}"),
+                    segments: Vec::new(),
+                },
+                confidence,
+            },
+        }
+    }
+
+    fn selected(attempts: Vec<LanguageAttempt>) -> String {
+        select_best(attempts).unwrap().language
+    }
+
+    #[test]
+    fn picks_the_most_confident_candidate() {
+        let attempts = vec![
+            attempt("en", Some(0.42)),
+            attempt("es", Some(0.91)),
+            attempt("pt", Some(0.77)),
+        ];
+        let best = select_best(attempts).unwrap();
+        assert_eq!(best.language, "es");
+        assert_eq!(best.result.output.text, "transcript in es");
+    }
+
+    #[test]
+    fn ties_go_to_the_earlier_candidate() {
+        assert_eq!(
+            selected(vec![attempt("en", Some(0.8)), attempt("es", Some(0.8))]),
+            "en"
+        );
+    }
+
+    #[test]
+    fn scored_candidates_beat_unscored_ones() {
+        assert_eq!(
+            selected(vec![attempt("en", None), attempt("es", Some(0.1))]),
+            "es"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_first_candidate_without_scores() {
+        assert_eq!(
+            selected(vec![attempt("de", None), attempt("en", None)]),
+            "de"
+        );
+    }
+
+    #[test]
+    fn no_attempts_selects_nothing() {
+        assert!(select_best(Vec::new()).is_none());
+    }
+}
@@ -1,12 +1,19 @@
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use tracing::info;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{info, warn};
 use utoipa::ToSchema;
 
-use crate::config::{Config, WhisperConfig};
-use crate::normalizer::TranscriptionNormalizer;
+use crate::config::{Config, NormalizerConfig, WhisperConfig};
+use crate::normalizer::{CustomRulesNormalizer, TranscriptionNormalizer};
+use audetic_core::http::{HttpOptions, DEFAULT_MAX_RETRIES, DEFAULT_REQUEST_TIMEOUT_SECS};
+use audetic_core::language;
 
+mod duration_limit;
+mod language_selection;
 mod transcription_service;
 mod windowing;
 
@@ -17,52 +24,136 @@ pub mod providers;
 // `audetic-core` and is re-exported here as `crate::transcription::jobs_client`.
 pub use audetic_core::jobs_client;
 
+// So is provider validation, which `audetic config check` runs too.
+pub use audetic_core::config::validate_provider_config;
+
+pub use duration_limit::OverLimit;
+
 pub use providers::{
-    AssemblyAIProvider, AudeticProvider, LocalEngineProvider, OpenAIProvider,
-    OpenAIWhisperCliProvider, TranscriptionOutput, TranscriptionProvider, WhisperCppProvider,
+    AssemblyAIProvider, AudeticProvider, CliLimits, DeepgramProvider, DiarizedTranscription,
+    LocalEngineProvider, OpenAICompatible, OpenAIProvider, OpenAIWhisperCliProvider, Reachability,
+    TranscriptionError, TranscriptionOutput, TranscriptionProvider, Utterance, WhisperCppProvider,
 };
 
 pub mod models;
 
-pub use transcription_service::TranscriptionService;
+pub use transcription_service::{Transcript, TranscriptionService};
+
+/// Shown when dictation is attempted on a service started without a provider.
+pub const NO_PROVIDER_CONFIGURED: &str = "No transcription provider configured. Run `audetic provider configure`, then `audetic config reload`.";
+
+/// `language` as handed to providers: an explicit code, or `auto` (also for
+/// a blank setting) so each provider leaves detection to the service.
+fn requested_language(language: &str) -> String {
+    language::explicit(language)
+        .unwrap_or(language::AUTO)
+        .to_string()
+}
 
 pub struct Transcriber {
     provider: Box<dyn TranscriptionProvider>,
     language: String,
+    /// `[whisper].candidate_languages`, tried in turn when the provider
+    /// reports confidence. `language` is the first of them when set.
+    candidate_languages: Vec<String>,
+    /// Longest audio sent to the provider, checked before upload.
+    max_audio_seconds: Option<u64>,
+    /// Largest file sent to the provider, checked before upload.
+    max_upload_bytes: Option<u64>,
+    /// `[normalizer]` fixes layered over the provider's normalizer.
+    custom_rules: NormalizerConfig,
 }
 
 impl Transcriber {
     pub fn with_provider(provider_name: &str, config: ProviderConfig) -> Result<Self> {
-        let language = config.language.clone().unwrap_or_else(|| "en".to_string());
+        let candidate_languages: Vec<String> = config
+            .candidate_languages
+            .iter()
+            .flatten()
+            .map(|language| language.trim().to_string())
+            .filter(|language| !language.is_empty())
+            .collect();
+        let language = candidate_languages
+            .first()
+            .cloned()
+            .or_else(|| config.language.clone())
+            .map(|language| requested_language(&language))
+            .unwrap_or_else(|| "en".to_string());
+        let max_audio_seconds =
+            duration_limit::effective_max_audio_seconds(provider_name, config.max_audio_seconds);
+        let max_upload_bytes =
+            duration_limit::effective_max_upload_bytes(provider_name, config.max_audio_seconds);
 
         let provider: Box<dyn TranscriptionProvider> = match provider_name {
-            "audetic-api" => Box::new(AudeticProvider::new(config.api_endpoint)?),
+            "audetic-api" => Box::new(AudeticProvider::new(
+                config.api_endpoint,
+                config.extra_headers.as_ref(),
+                &config.http,
+            )?),
             "assembly-ai" => {
                 let api_key = config
                     .api_key
                     .context("api_key is required for AssemblyAI provider")?;
 
-                Box::new(AssemblyAIProvider::new(api_key, config.api_endpoint)?)
+                Box::new(AssemblyAIProvider::new(
+                    api_key,
+                    config.api_endpoint,
+                    config.assemblyai_features,
+                    &config.http,
+                )?)
             }
-            "openai-api" => {
+            "deepgram" => {
                 let api_key = config
                     .api_key
-                    .context("api_key is required for OpenAI API provider")?;
+                    .context("api_key is required for Deepgram provider")?;
 
-                let model = config.model.unwrap_or_else(|| "whisper-1".to_string());
-                Box::new(OpenAIProvider::new(api_key, config.api_endpoint, model)?)
+                Box::new(DeepgramProvider::new(
+                    api_key,
+                    config.api_endpoint,
+                    &config.http,
+                )?)
+            }
+            "openai-api" | "groq" => {
+                let service = if provider_name == "groq" {
+                    OpenAICompatible::GROQ
+                } else {
+                    OpenAICompatible::OPENAI
+                };
+                let api_key = config
+                    .api_key
+                    .with_context(|| format!("api_key is required for {} provider", service.name))?;
+
+                Box::new(OpenAIProvider::new(
+                    service,
+                    api_key,
+                    config.api_endpoint,
+                    config.model,
+                    config.word_timestamps,
+                    config.extra_headers.as_ref(),
+                    &config.http,
+                )?)
             }
             "openai-cli" => {
+                let limits = config.cli_limits();
                 let model = config.model.unwrap_or_else(|| "base".to_string());
-                Box::new(OpenAIWhisperCliProvider::new(config.command_path, model)?)
+                Box::new(OpenAIWhisperCliProvider::new(
+                    config.command_path,
+                    model,
+                    limits,
+                )?)
             }
             "whisper-cpp" => {
+                let limits = config.cli_limits();
                 let model = config.model.unwrap_or_else(|| "base".to_string());
                 Box::new(WhisperCppProvider::new(
                     config.command_path,
                     model,
                     config.model_path,
-                )?)
+                    config.word_timestamps,
+                    limits,
+                )?
+                .with_stream_partials(config.stream_partials)
+                .with_temp_dir(config.temp_dir))
             }
             "local" => {
                 let model = config
@@ -71,14 +162,34 @@ impl Transcriber {
                 Box::new(LocalEngineProvider::new(&model)?)
             }
             _ => bail!(
-                "Unknown transcription provider '{}'. Supported providers: audetic-api, assembly-ai, openai-api, openai-cli, whisper-cpp, local",
+                "Unknown transcription provider '{}'. Supported providers: audetic-api, assembly-ai, deepgram, openai-api, groq, openai-cli, whisper-cpp, local",
                 provider_name
             ),
         };
 
         info!("Using {} for transcription", provider.name());
 
-        Ok(Self { provider, language })
+        Ok(Self {
+            provider,
+            language,
+            candidate_languages,
+            max_audio_seconds,
+            max_upload_bytes,
+            custom_rules: config.normalizer,
+        })
+    }
+
+    /// Wrap an already-built provider, e.g. a fake in tests. No candidate
+    /// languages and no duration limit.
+    pub fn from_provider(provider: Box<dyn TranscriptionProvider>, language: &str) -> Self {
+        Self {
+            provider,
+            language: requested_language(language),
+            candidate_languages: Vec::new(),
+            max_audio_seconds: None,
+            max_upload_bytes: None,
+            custom_rules: NormalizerConfig::default(),
+        }
     }
 
     pub async fn transcribe(&self, audio_path: &PathBuf) -> Result<String> {
@@ -87,9 +198,14 @@ impl Transcriber {
             audio_path,
             self.provider.name()
         );
-        self.provider
+        if self.tries_candidates() {
+            return Ok(self.transcribe_detailed(audio_path).await?.text);
+        }
+        self.check_duration(audio_path).await?;
+        Ok(self
+            .provider
             .transcribe(audio_path.as_path(), &self.language)
-            .await
+            .await?)
     }
 
     /// Transcribe and also return per-segment timestamps when available.
@@ -99,13 +215,99 @@ impl Transcriber {
             audio_path,
             self.provider.name()
         );
-        self.provider
+        self.check_duration(audio_path).await?;
+        if self.tries_candidates() {
+            return self.transcribe_candidates(audio_path).await;
+        }
+        Ok(self
+            .provider
             .transcribe_detailed(audio_path.as_path(), &self.language)
-            .await
+            .await?)
+    }
+
+    /// Transcribe with speaker labels when the provider can produce them;
+    /// `None` when it can't. Uses `language`, not the candidates.
+    pub async fn transcribe_diarized(
+        &self,
+        audio_path: &PathBuf,
+    ) -> Result<Option<DiarizedTranscription>> {
+        self.check_duration(audio_path).await?;
+        Ok(self
+            .provider
+            .transcribe_diarized(audio_path.as_path(), &self.language)
+            .await?)
+    }
+
+    /// Whether to transcribe once per candidate language. Without confidence
+    /// there's nothing to compare, so only the first candidate is used.
+    fn tries_candidates(&self) -> bool {
+        self.candidate_languages.len() > 1 && self.provider.reports_confidence()
     }
 
+    /// Transcribe in every candidate language and keep the most confident
+    /// result. Fails only if every candidate did.
+    async fn transcribe_candidates(&self, audio_path: &Path) -> Result<TranscriptionOutput> {
+        let mut attempts = Vec::new();
+        let mut last_error = None;
+        for language in &self.candidate_languages {
+            match self.provider.transcribe_scored(audio_path, language).await {
+                Ok(result) => attempts.push(language_selection::LanguageAttempt {
+                    language: language.clone(),
+                    result,
+                }),
+                Err(e) => {
+                    warn!(
+                        "Transcription in candidate language {} failed: {}",
+                        language, e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match language_selection::select_best(attempts) {
+            Some(best) => {
+                info!(
+                    "Using transcript in {} out of {} candidate languages",
+                    best.language,
+                    self.candidate_languages.len()
+                );
+                Ok(best.result.output)
+            }
+            None => Err(last_error.map_or_else(
+                || anyhow::anyhow!("No candidate languages"),
+                anyhow::Error::from,
+            )),
+        }
+    }
+
+    /// Refuse audio longer or larger than the provider accepts before
+    /// uploading it.
+    async fn check_duration(&self, audio_path: &Path) -> Result<()> {
+        duration_limit::enforce_max_size(audio_path, self.provider.name(), self.max_upload_bytes)?;
+        duration_limit::enforce_max_duration(
+            audio_path,
+            self.provider.name(),
+            self.max_audio_seconds,
+        )
+        .await
+    }
+
+    /// The provider's normalizer, followed by any `[normalizer]` rules.
     pub fn normalizer(&self) -> Result<Box<dyn TranscriptionNormalizer>> {
-        self.provider.normalizer()
+        CustomRulesNormalizer::compose(self.provider.normalizer()?, &self.custom_rules)
+    }
+
+    /// Probe the provider's endpoint with its credentials; see
+    /// [`TranscriptionProvider::check_reachability`].
+    pub async fn check_reachability(&self) -> Option<Reachability> {
+        self.provider.check_reachability().await
+    }
+
+    /// The provider's running transcript, when it streams one; see
+    /// [`TranscriptionProvider::partial_transcripts`].
+    pub fn partial_transcripts(&self) -> Option<watch::Receiver<String>> {
+        self.provider.partial_transcripts()
     }
 }
 
@@ -117,6 +319,26 @@ pub struct ProviderConfig {
     pub command_path: Option<String>,
     pub api_endpoint: Option<String>,
     pub api_key: Option<String>,
+    pub word_timestamps: bool,
+    /// Languages to choose between; see [`Transcriber`].
+    pub candidate_languages: Option<Vec<String>>,
+    /// Extra AssemblyAI request toggles (`punctuate`, `format_text`, ...).
+    pub assemblyai_features: Option<BTreeMap<String, bool>>,
+    /// Override for the provider's built-in audio length limit (0 = none).
+    pub max_audio_seconds: Option<u64>,
+    /// Wall-clock limit for CLI engine runs (whisper.cpp, OpenAI Whisper).
+    pub cli_timeout_seconds: Option<u64>,
+    /// Most stdout a CLI engine run may produce.
+    pub cli_max_output_bytes: Option<u64>,
+    /// Publish whisper.cpp's output as it's printed.
+    pub stream_partials: bool,
+    /// Headers the HTTP providers add on top of their defaults.
+    pub extra_headers: Option<BTreeMap<String, String>>,
+    /// `[audio].temp_dir`, for files a provider writes along the way.
+    pub temp_dir: PathBuf,
+    pub http: HttpOptions,
+    /// `[normalizer]` fixes applied after the provider's own cleanup.
+    pub normalizer: NormalizerConfig,
 }
 
 impl Default for ProviderConfig {
@@ -128,6 +350,17 @@ impl Default for ProviderConfig {
             command_path: None,
             api_endpoint: None,
             api_key: None,
+            word_timestamps: false,
+            candidate_languages: None,
+            assemblyai_features: None,
+            max_audio_seconds: None,
+            cli_timeout_seconds: None,
+            cli_max_output_bytes: None,
+            stream_partials: false,
+            extra_headers: None,
+            temp_dir: std::env::temp_dir(),
+            http: HttpOptions::default(),
+            normalizer: NormalizerConfig::default(),
         }
     }
 }
@@ -141,10 +374,56 @@ impl From<&WhisperConfig> for ProviderConfig {
             command_path: whisper.command_path.clone(),
             api_endpoint: whisper.api_endpoint.clone(),
             api_key: whisper.api_key.clone(),
+            word_timestamps: whisper.word_timestamps,
+            candidate_languages: whisper.candidate_languages.clone(),
+            assemblyai_features: whisper.assemblyai_features.clone(),
+            max_audio_seconds: whisper.max_audio_seconds,
+            cli_timeout_seconds: whisper.cli_timeout_seconds,
+            cli_max_output_bytes: whisper.cli_max_output_bytes,
+            stream_partials: whisper.stream_partials,
+            extra_headers: whisper.extra_headers.clone(),
+            temp_dir: std::env::temp_dir(),
+            http: provider_http(
+                whisper,
+                HttpOptions::with_connect_timeout_secs(whisper.connect_timeout_seconds),
+            ),
+            normalizer: NormalizerConfig::default(),
         }
     }
 }
 
+impl ProviderConfig {
+    /// Provider settings from `[whisper]` plus the `[network]` settings used
+    /// to build HTTP clients and the `[normalizer]` rules.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            http: provider_http(&config.whisper, HttpOptions::from_config(config)),
+            normalizer: config.normalizer.clone(),
+            temp_dir: config.audio.temp_dir(),
+            ..Self::from(&config.whisper)
+        }
+    }
+
+    fn cli_limits(&self) -> CliLimits {
+        CliLimits::new(self.cli_timeout_seconds, self.cli_max_output_bytes)
+    }
+}
+
+/// `base` plus the `[whisper]` settings only provider calls use. The request
+/// timeout stays out of [`HttpOptions::from_config`] so model downloads and
+/// meeting uploads aren't cut off; providers stretch it for their own audio
+/// uploads (see `providers::retry`).
+fn provider_http(whisper: &WhisperConfig, base: HttpOptions) -> HttpOptions {
+    let request_timeout = whisper
+        .request_timeout_seconds
+        .unwrap_or(DEFAULT_REQUEST_TIMEOUT_SECS);
+    HttpOptions {
+        request_timeout: (request_timeout > 0).then_some(Duration::from_secs(request_timeout)),
+        max_retries: whisper.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+        ..base
+    }
+}
+
 // ============================================================================
 // Provider status and validation
 // ============================================================================
@@ -176,6 +455,11 @@ pub struct ProviderTestResult {
     pub error: Option<String>,
     /// Time taken in seconds
     pub duration_secs: f64,
+    /// Whether an authenticated request to the provider's endpoint succeeded.
+    /// `None` when the network check wasn't requested or the provider runs
+    /// on-device; `error` says whether the key or the endpoint was at fault.
+    #[serde(default)]
+    pub reachable: Option<bool>,
 }
 
 /// Transcribe a file using whatever provider is configured, returning the
@@ -183,15 +467,110 @@ pub struct ProviderTestResult {
 /// CLI (which can't link the engine) can transcribe on-device through the
 /// daemon. The daemon must be running.
 pub async fn transcribe_with_configured_provider(audio_path: &Path) -> Result<String> {
+    Ok(transcribe_with_provider(audio_path, None).await?.text)
+}
+
+/// Transcribe a file with `provider`, or the configured one when `None`,
+/// keeping the raw output and segments. Backs `history retranscribe`.
+///
+/// Credentials are only stored for the configured provider, so an override
+/// runs without `api_key` and `extra_headers` (and without `model` and
+/// `api_endpoint`, which name things only the configured provider
+/// understands). Providers that need a key are refused with
+/// [`ProviderChoiceError::NeedsApiKey`] rather than sent someone else's.
+pub async fn transcribe_with_provider(
+    audio_path: &Path,
+    provider: Option<&str>,
+) -> Result<Transcript> {
+    let config = Config::load()?;
+    let configured = config.whisper.provider.as_deref();
+    let provider = provider
+        .or(configured)
+        .ok_or(ProviderChoiceError::NotConfigured)?;
+    let provider_config = if Some(provider) == configured {
+        ProviderConfig::from_config(&config)
+    } else {
+        override_provider_config(&config, provider)?
+    };
+    let transcriber = Transcriber::with_provider(provider, provider_config)?;
+    let service = TranscriptionService::new(transcriber)?;
+    service.transcribe_with_raw(&audio_path.to_path_buf()).await
+}
+
+/// Settings for `provider` when it isn't the configured one: `[whisper]`
+/// without the configured provider's credentials, model and endpoint.
+fn override_provider_config(
+    config: &Config,
+    provider: &str,
+) -> Result<ProviderConfig, ProviderChoiceError> {
+    let whisper = WhisperConfig {
+        model: None,
+        api_endpoint: None,
+        api_key: None,
+        extra_headers: None,
+        ..config.whisper.clone()
+    };
+    if let Some(reason) = validate_provider_config(provider, &whisper) {
+        let with_key = WhisperConfig {
+            api_key: Some(String::new()),
+            ..whisper
+        };
+        return Err(if validate_provider_config(provider, &with_key).is_none() {
+            ProviderChoiceError::NeedsApiKey(provider.to_string())
+        } else {
+            ProviderChoiceError::Unusable {
+                provider: provider.to_string(),
+                reason,
+            }
+        });
+    }
+    Ok(ProviderConfig {
+        model: None,
+        api_endpoint: None,
+        api_key: None,
+        extra_headers: None,
+        ..ProviderConfig::from_config(config)
+    })
+}
+
+/// Why a transcription couldn't pick a provider, before any audio was sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderChoiceError {
+    /// `[whisper].provider` is unset and no override was given.
+    NotConfigured,
+    /// The requested provider needs an API key, and only the configured
+    /// provider has one.
+    NeedsApiKey(String),
+    /// The requested provider is unknown or isn't set up, e.g. whisper.cpp
+    /// without a model path.
+    Unusable { provider: String, reason: String },
+}
+
+impl std::fmt::Display for ProviderChoiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotConfigured => f.write_str(NO_PROVIDER_CONFIGURED),
+            Self::NeedsApiKey(provider) => write!(
+                f,
+                "{provider} needs an API key, and keys are only stored for the configured provider. Make {provider} the configured one with `audetic provider configure` to use it"
+            ),
+            Self::Unusable { provider, reason } => write!(f, "Can't use {provider}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ProviderChoiceError {}
+
+/// Build the normalizer for the configured provider, without transcribing.
+/// Backs `history renormalize`, which re-applies it to stored raw text.
+pub fn configured_normalizer() -> Result<Box<dyn TranscriptionNormalizer>> {
     let config = Config::load()?;
     let provider = config
         .whisper
         .provider
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("No transcription provider configured"))?;
-    let transcriber = Transcriber::with_provider(provider, ProviderConfig::from(&config.whisper))?;
-    let service = TranscriptionService::new(transcriber)?;
-    service.transcribe(&audio_path.to_path_buf()).await
+    Transcriber::with_provider(provider, ProviderConfig::from_config(&config))?.normalizer()
 }
 
 /// Get the current provider status from config.
@@ -229,83 +608,32 @@ pub fn get_provider_status_from_config(whisper: &WhisperConfig) -> Result<Provid
     }
 }
 
-/// Validate provider configuration and return an error message if invalid.
-pub fn validate_provider_config(provider: &str, whisper: &WhisperConfig) -> Option<String> {
-    match provider {
-        "audetic-api" => None, // No additional config required
-        "assembly-ai" => {
-            if whisper.api_key.is_none() {
-                Some("API key required for AssemblyAI".to_string())
-            } else {
-                None
-            }
-        }
-        "openai-api" => {
-            if whisper.api_key.is_none() {
-                Some("API key required for OpenAI API".to_string())
-            } else {
-                None
-            }
-        }
-        "openai-cli" => {
-            if whisper.command_path.is_none() {
-                Some("Command path required for OpenAI CLI".to_string())
-            } else {
-                None
-            }
-        }
-        "whisper-cpp" => {
-            if whisper.command_path.is_none() {
-                Some("Command path required for whisper.cpp".to_string())
-            } else if whisper.model_path.is_none() {
-                Some("Model path required for whisper.cpp".to_string())
-            } else {
-                None
-            }
-        }
-        "local" => {
-            // A model is selected by id and downloaded into the data dir; the
-            // engine is linked in-process, so no command/model path is needed.
-            let model_id = whisper
-                .model
-                .as_deref()
-                .unwrap_or(audetic_core::local_models::DEFAULT_MODEL_ID);
-            match audetic_core::local_models::find(model_id) {
-                Some(model) => match audetic_core::global::data_dir() {
-                    Ok(data_dir) if audetic_core::local_models::is_installed(&data_dir, model) => {
-                        None
-                    }
-                    Ok(_) => Some(format!(
-                        "Local model '{model_id}' is not downloaded yet. Run `audetic models download {model_id}`."
-                    )),
-                    Err(e) => Some(format!("Could not resolve data directory: {e}")),
-                },
-                None => Some(format!("Unknown local model '{model_id}'.")),
-            }
-        }
-        _ => Some(format!("Unknown provider: {}", provider)),
-    }
-}
-
 /// Test the current provider with an optional audio file.
 ///
 /// If no file is provided, only validates that the provider can be initialized.
-pub async fn test_provider(audio_file: Option<&Path>) -> Result<ProviderTestResult> {
+/// `check_network` also makes a lightweight authenticated request to the
+/// provider's endpoint before anything is transcribed.
+pub async fn test_provider(
+    audio_file: Option<&Path>,
+    check_network: bool,
+) -> Result<ProviderTestResult> {
     let config = Config::load()?;
-    test_provider_with_config(&config.whisper, audio_file).await
+    test_provider_with_config(&config, audio_file, check_network).await
 }
 
 /// Test a provider with specific config.
 pub async fn test_provider_with_config(
-    whisper: &WhisperConfig,
+    config: &Config,
     audio_file: Option<&Path>,
+    check_network: bool,
 ) -> Result<ProviderTestResult> {
-    let provider_name = whisper
+    let provider_name = config
+        .whisper
         .provider
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("No transcription provider configured"))?;
 
-    let provider_config = ProviderConfig::from(whisper);
+    let provider_config = ProviderConfig::from_config(config);
 
     // Try to initialize
     let transcriber = match Transcriber::with_provider(provider_name, provider_config) {
@@ -316,10 +644,37 @@ pub async fn test_provider_with_config(
                 transcription: None,
                 error: Some(e.to_string()),
                 duration_secs: 0.0,
+                reachable: None,
             });
         }
     };
 
+    // Probe the endpoint first; there's no point uploading audio to one that
+    // rejects the key. On-device providers have nothing to probe.
+    let start = std::time::Instant::now();
+    let probe = if check_network {
+        transcriber.check_reachability().await
+    } else {
+        None
+    };
+    let probe_error = match &probe {
+        Some(Reachability::InvalidKey(status)) => Some(format!(
+            "Invalid API key: the provider rejected it with HTTP {status}"
+        )),
+        Some(Reachability::Unreachable(reason)) => Some(format!("Endpoint unreachable: {reason}")),
+        Some(Reachability::Ready) | None => None,
+    };
+    if let Some(error) = probe_error {
+        return Ok(ProviderTestResult {
+            success: false,
+            transcription: None,
+            error: Some(error),
+            duration_secs: start.elapsed().as_secs_f64(),
+            reachable: Some(false),
+        });
+    }
+    let reachable = probe.map(|_| true);
+
     // If audio file provided, actually transcribe
     if let Some(path) = audio_file {
         let start = std::time::Instant::now();
@@ -329,21 +684,24 @@ pub async fn test_provider_with_config(
                 transcription: Some(text),
                 error: None,
                 duration_secs: start.elapsed().as_secs_f64(),
+                reachable,
             }),
             Err(e) => Ok(ProviderTestResult {
                 success: false,
                 transcription: None,
                 error: Some(e.to_string()),
                 duration_secs: start.elapsed().as_secs_f64(),
+                reachable,
             }),
         }
     } else {
-        // Just validate initialization
+        // Just validate initialization (and the probe, if asked for)
         Ok(ProviderTestResult {
             success: true,
             transcription: None,
             error: None,
-            duration_secs: 0.0,
+            duration_secs: start.elapsed().as_secs_f64(),
+            reachable,
         })
     }
 }
@@ -378,3 +736,57 @@ pub fn get_provider_info_from_config(whisper: &WhisperConfig) -> ProviderInfo {
         model_path: whisper.model_path.clone(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_for(provider: &str) -> Config {
+        let mut config = Config::default();
+        config.whisper.provider = Some(provider.to_string());
+        config.whisper.api_key = Some("sk-configured".to_string());
+        config.whisper.extra_headers = Some(BTreeMap::from([(
+            "x-api-key".to_string(),
+            "secret".to_string(),
+        )]));
+        config.whisper.model = Some("whisper-1".to_string());
+        config.whisper.api_endpoint = Some("https://proxy.example/v1".to_string());
+        config
+    }
+
+    #[test]
+    fn overrides_never_get_the_configured_credentials() {
+        let mut config = config_for("openai-api");
+        config.whisper.command_path = Some("/usr/bin/whisper-cli".to_string());
+        config.whisper.model_path = Some("/models/ggml-base.bin".to_string());
+
+        let provider_config = override_provider_config(&config, "whisper-cpp").unwrap();
+        assert_eq!(provider_config.api_key, None);
+        assert_eq!(provider_config.extra_headers, None);
+        assert_eq!(provider_config.model, None);
+        assert_eq!(provider_config.api_endpoint, None);
+        assert_eq!(
+            provider_config.model_path.as_deref(),
+            Some("/models/ggml-base.bin")
+        );
+    }
+
+    #[test]
+    fn overrides_that_need_a_key_or_setup_are_refused() {
+        let config = config_for("openai-api");
+        for provider in ["deepgram", "assembly-ai", "groq"] {
+            assert_eq!(
+                override_provider_config(&config, provider).unwrap_err(),
+                ProviderChoiceError::NeedsApiKey(provider.to_string())
+            );
+        }
+        assert!(matches!(
+            override_provider_config(&config, "whisper-cpp").unwrap_err(),
+            ProviderChoiceError::Unusable { .. }
+        ));
+        assert!(matches!(
+            override_provider_config(&config, "nope").unwrap_err(),
+            ProviderChoiceError::Unusable { .. }
+        ));
+    }
+}
@@ -15,7 +15,9 @@ use tokio::io::AsyncWriteExt;
 use tracing::{info, warn};
 use utoipa::ToSchema;
 
+use audetic_core::config::Config;
 use audetic_core::global;
+use audetic_core::http::HttpOptions;
 use audetic_core::local_models::{self, ModelFile, ModelInfo};
 
 /// Public, serializable view of a catalog model plus its on-disk + download
@@ -173,6 +175,7 @@ async fn download_model_files(model: &'static ModelInfo, data_dir: &Path) -> Res
 
     let total = model.total_size_bytes();
     let mut completed_bytes: u64 = 0;
+    let client = HttpOptions::from_config(&Config::load()?).build_client()?;
 
     for file in model.files {
         let final_path = dir.join(file.name);
@@ -186,7 +189,7 @@ async fn download_model_files(model: &'static ModelInfo, data_dir: &Path) -> Res
             }
         }
 
-        download_one_file(model.id, file, &final_path, completed_bytes, total).await?;
+        download_one_file(&client, model.id, file, &final_path, completed_bytes, total).await?;
         completed_bytes += file.size_bytes;
         set_progress(model.id, completed_bytes, total);
     }
@@ -209,6 +212,7 @@ fn set_progress(id: &str, downloaded: u64, total: u64) {
 
 /// Download a single file with `.partial` staging and HTTP range resume.
 async fn download_one_file(
+    client: &reqwest::Client,
     model_id: &str,
     file: &ModelFile,
     final_path: &Path,
@@ -223,7 +227,6 @@ async fn download_one_file(
         .map(|m| m.len())
         .unwrap_or(0);
 
-    let client = reqwest::Client::new();
     let mut request = client.get(file.url);
     if existing > 0 {
         request = request.header(reqwest::header::RANGE, format!("bytes={existing}-"));
@@ -1,13 +1,21 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::path::Path;
 use std::pin::Pin;
 use std::time::Duration;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
-use super::TranscriptionProvider;
+use super::retry::{send_upload, send_with_retry, RetryPolicy};
+use super::{
+    probe, DiarizedTranscription, Reachability, TranscriptionError, TranscriptionProvider,
+    Utterance,
+};
 use crate::normalizer::TranscriptionNormalizer;
+use audetic_core::http::HttpOptions;
+use audetic_core::jobs_client::file_body;
+use audetic_core::language;
 
 /// Response from the upload endpoint
 #[derive(Debug, Deserialize)]
@@ -21,6 +29,32 @@ struct TranscriptRequest {
     audio_url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     language_code: Option<String>,
+    /// Feature toggles (`punctuate`, `format_text`, ...), see [`resolve_features`].
+    #[serde(flatten)]
+    features: BTreeMap<String, bool>,
+}
+
+/// Toggles AssemblyAI enables by default. Sent explicitly so the request
+/// body states exactly what was asked for.
+const DEFAULT_FEATURES: &[(&str, bool)] = &[("punctuate", true), ("format_text", true)];
+
+/// Request fields the provider sets itself; a feature can't override them.
+const RESERVED_FIELDS: &[&str] = &["audio_url", "language_code"];
+
+/// Merge `[whisper].assemblyai_features` over [`DEFAULT_FEATURES`].
+fn resolve_features(configured: Option<BTreeMap<String, bool>>) -> BTreeMap<String, bool> {
+    let mut features: BTreeMap<String, bool> = DEFAULT_FEATURES
+        .iter()
+        .map(|(name, enabled)| (name.to_string(), *enabled))
+        .collect();
+    for (name, enabled) in configured.unwrap_or_default() {
+        if RESERVED_FIELDS.contains(&name.as_str()) {
+            warn!("Ignoring AssemblyAI feature '{}': set by Audetic", name);
+            continue;
+        }
+        features.insert(name, enabled);
+    }
+    features
 }
 
 /// Response from transcript creation and polling
@@ -30,6 +64,29 @@ struct TranscriptResponse {
     status: TranscriptStatus,
     text: Option<String>,
     error: Option<String>,
+    /// Speaker turns, set once completed when `speaker_labels` was requested.
+    #[serde(default)]
+    utterances: Option<Vec<TranscriptUtterance>>,
+}
+
+/// One entry of `utterances`; times are in milliseconds.
+#[derive(Debug, Deserialize)]
+struct TranscriptUtterance {
+    speaker: String,
+    start: u64,
+    end: u64,
+    text: String,
+}
+
+impl TranscriptUtterance {
+    fn into_utterance(self) -> Utterance {
+        Utterance {
+            speaker: self.speaker,
+            start: self.start as f64 / 1000.0,
+            end: self.end as f64 / 1000.0,
+            text: self.text.trim().to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
@@ -50,11 +107,18 @@ pub struct AssemblyAIProvider {
     client: reqwest::Client,
     api_key: String,
     base_url: String,
+    features: BTreeMap<String, bool>,
+    retry: RetryPolicy,
 }
 
 impl AssemblyAIProvider {
-    pub fn new(api_key: String, endpoint: Option<String>) -> Result<Self> {
-        let client = reqwest::Client::new();
+    pub fn new(
+        api_key: String,
+        endpoint: Option<String>,
+        features: Option<BTreeMap<String, bool>>,
+        http: &HttpOptions,
+    ) -> Result<Self> {
+        let client = http.build_client()?;
         let base_url = endpoint.unwrap_or_else(|| "https://api.assemblyai.com/v2".to_string());
 
         info!(
@@ -66,6 +130,8 @@ impl AssemblyAIProvider {
             client,
             api_key,
             base_url,
+            features: resolve_features(features),
+            retry: RetryPolicy::new(http),
         })
     }
 
@@ -75,19 +141,23 @@ impl AssemblyAIProvider {
 
         debug!("Uploading audio file to AssemblyAI: {:?}", audio_path);
 
-        let audio_data = tokio::fs::read(audio_path)
-            .await
-            .context("Failed to read audio file")?;
-
-        let response = self
-            .client
-            .post(&upload_url)
-            .header("Authorization", &self.api_key)
-            .header("Content-Type", "application/octet-stream")
-            .body(audio_data)
-            .send()
-            .await
-            .context("Failed to upload audio to AssemblyAI")?;
+        // Streamed, and reopened for each attempt, so long recordings don't
+        // have to fit in memory.
+        let upload_size = std::fs::metadata(audio_path)
+            .with_context(|| format!("Failed to read {}", audio_path.display()))?
+            .len();
+        let context = "Failed to upload audio to AssemblyAI";
+        let response = send_upload(&self.retry, context, upload_size, || {
+            let (body, size) = file_body(audio_path)?;
+            Ok(self
+                .client
+                .post(&upload_url)
+                .header("Authorization", &self.api_key)
+                .header("Content-Type", "application/octet-stream")
+                .header("Content-Length", size)
+                .body(body))
+        })
+        .await?;
 
         let status = response.status();
         let response_text = response
@@ -100,11 +170,14 @@ impl AssemblyAIProvider {
                 "AssemblyAI upload failed with status {}: {}",
                 status, response_text
             );
-            return Err(anyhow::anyhow!(
-                "AssemblyAI upload failed with status {}: {}",
+            return Err(TranscriptionError::from_status(
                 status,
-                response_text
-            ));
+                format!(
+                    "AssemblyAI upload failed with status {}: {}",
+                    status, response_text
+                ),
+            )
+            .into());
         }
 
         let upload_response: UploadResponse =
@@ -117,32 +190,50 @@ impl AssemblyAIProvider {
         Ok(upload_response.upload_url)
     }
 
+    /// The transcript request; `speaker_labels` turns on AssemblyAI's
+    /// diarization, which adds `utterances` to the result.
+    fn transcript_request(
+        &self,
+        audio_url: String,
+        language: &str,
+        speaker_labels: bool,
+    ) -> TranscriptRequest {
+        let mut features = self.features.clone();
+        if speaker_labels {
+            features.insert("speaker_labels".to_string(), true);
+        }
+        TranscriptRequest {
+            audio_url,
+            language_code: language::explicit(language).map(str::to_string),
+            features,
+        }
+    }
+
     /// Submit transcription request
-    async fn submit_transcription(&self, audio_url: String, language: &str) -> Result<String> {
+    async fn submit_transcription(
+        &self,
+        audio_url: String,
+        language: &str,
+        speaker_labels: bool,
+    ) -> Result<String> {
         let transcript_url = format!("{}/transcript", self.base_url);
-
-        let language_code = if language.is_empty() || language == "auto" {
-            None
-        } else {
-            Some(language.to_string())
-        };
-
-        let request_body = TranscriptRequest {
-            audio_url,
-            language_code,
-        };
+        let request_body = self.transcript_request(audio_url, language, speaker_labels);
 
         debug!("Submitting transcription request to AssemblyAI");
 
-        let response = self
-            .client
-            .post(&transcript_url)
-            .header("Authorization", &self.api_key)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .context("Failed to submit transcription request")?;
+        let response = send_with_retry(
+            &self.retry,
+            "Failed to submit transcription request",
+            || {
+                Ok(self
+                    .client
+                    .post(&transcript_url)
+                    .header("Authorization", &self.api_key)
+                    .header("Content-Type", "application/json")
+                    .json(&request_body))
+            },
+        )
+        .await?;
 
         let status = response.status();
         let response_text = response
@@ -156,18 +247,14 @@ impl AssemblyAIProvider {
                 status, response_text
             );
 
-            if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&response_text) {
-                return Err(anyhow::anyhow!(
-                    "AssemblyAI API error: {}",
-                    error_response.error
-                ));
-            }
-
-            return Err(anyhow::anyhow!(
-                "AssemblyAI transcription request failed with status {}: {}",
-                status,
-                response_text
-            ));
+            let message = match serde_json::from_str::<ErrorResponse>(&response_text) {
+                Ok(error_response) => format!("AssemblyAI API error: {}", error_response.error),
+                Err(_) => format!(
+                    "AssemblyAI transcription request failed with status {}: {}",
+                    status, response_text
+                ),
+            };
+            return Err(TranscriptionError::from_status(status, message).into());
         }
 
         let transcript_response: TranscriptResponse = serde_json::from_str(&response_text)
@@ -180,8 +267,8 @@ impl AssemblyAIProvider {
         Ok(transcript_response.id)
     }
 
-    /// Poll for transcription completion
-    async fn poll_transcription(&self, transcript_id: &str) -> Result<String> {
+    /// Poll for transcription completion, returning the completed transcript
+    async fn poll_transcription(&self, transcript_id: &str) -> Result<TranscriptResponse> {
         let poll_url = format!("{}/transcript/{}", self.base_url, transcript_id);
         let poll_interval = Duration::from_secs(3);
         // lets make this 6 minutes
@@ -193,13 +280,14 @@ impl AssemblyAIProvider {
                 attempt, max_attempts, transcript_id
             );
 
-            let response = self
-                .client
-                .get(&poll_url)
-                .header("Authorization", &self.api_key)
-                .send()
-                .await
-                .context("Failed to poll transcription status")?;
+            let response =
+                send_with_retry(&self.retry, "Failed to poll transcription status", || {
+                    Ok(self
+                        .client
+                        .get(&poll_url)
+                        .header("Authorization", &self.api_key))
+                })
+                .await?;
 
             let status = response.status();
             let response_text = response
@@ -212,11 +300,14 @@ impl AssemblyAIProvider {
                     "AssemblyAI poll request failed with status {}: {}",
                     status, response_text
                 );
-                return Err(anyhow::anyhow!(
-                    "AssemblyAI poll request failed with status {}: {}",
+                return Err(TranscriptionError::from_status(
                     status,
-                    response_text
-                ));
+                    format!(
+                        "AssemblyAI poll request failed with status {}: {}",
+                        status, response_text
+                    ),
+                )
+                .into());
             }
 
             let transcript_response: TranscriptResponse =
@@ -224,13 +315,15 @@ impl AssemblyAIProvider {
 
             match transcript_response.status {
                 TranscriptStatus::Completed => {
-                    let text = transcript_response
-                        .text
-                        .unwrap_or_default()
-                        .trim()
-                        .to_string();
-                    info!("Transcription complete: {} chars", text.len());
-                    return Ok(text);
+                    info!(
+                        "Transcription complete: {} chars",
+                        transcript_response
+                            .text
+                            .as_deref()
+                            .unwrap_or_default()
+                            .len()
+                    );
+                    return Ok(transcript_response);
                 }
                 TranscriptStatus::Error => {
                     let error_msg = transcript_response
@@ -251,6 +344,39 @@ impl AssemblyAIProvider {
             max_attempts
         ))
     }
+
+    /// Upload, submit and poll until the transcript completes.
+    async fn run(
+        &self,
+        audio_path: &Path,
+        language: &str,
+        speaker_labels: bool,
+    ) -> Result<TranscriptResponse> {
+        // Step 1: Upload the audio file
+        let audio_url = self.upload_audio(audio_path).await?;
+
+        // Step 2: Submit transcription request
+        let transcript_id = self
+            .submit_transcription(audio_url, language, speaker_labels)
+            .await?;
+
+        // Step 3: Poll for completion
+        self.poll_transcription(&transcript_id).await
+    }
+}
+
+/// The text and speaker turns of a transcript requested with
+/// `speaker_labels`. A transcript without `utterances` has no turns.
+fn diarized(transcript: TranscriptResponse) -> DiarizedTranscription {
+    DiarizedTranscription {
+        text: transcript.text.unwrap_or_default().trim().to_string(),
+        utterances: transcript
+            .utterances
+            .unwrap_or_default()
+            .into_iter()
+            .map(TranscriptUtterance::into_utterance)
+            .collect(),
+    }
 }
 
 impl TranscriptionProvider for AssemblyAIProvider {
@@ -266,27 +392,55 @@ impl TranscriptionProvider for AssemblyAIProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
         Box::pin(async move {
             info!(
                 "Transcribing audio file via AssemblyAI API: {:?}",
                 audio_path
             );
 
-            // Step 1: Upload the audio file
-            let audio_url = self.upload_audio(audio_path).await?;
-
-            // Step 2: Submit transcription request
-            let transcript_id = self.submit_transcription(audio_url, language).await?;
-
-            // Step 3: Poll for completion
-            let text = self.poll_transcription(&transcript_id).await?;
-
+            let transcript = self.run(audio_path, language, false).await?;
+            let text = transcript.text.unwrap_or_default().trim().to_string();
             debug!("Raw transcription: {}", text);
             Ok(text)
         })
     }
 
+    fn transcribe_diarized<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<
+        Box<
+            dyn Future<Output = Result<Option<DiarizedTranscription>, TranscriptionError>>
+                + Send
+                + 'a,
+        >,
+    > {
+        Box::pin(async move {
+            info!(
+                "Transcribing audio file via AssemblyAI API with speaker labels: {:?}",
+                audio_path
+            );
+            let transcript = self.run(audio_path, language, true).await?;
+            Ok(Some(diarized(transcript)))
+        })
+    }
+
+    fn check_reachability<'a>(
+        &'a self,
+    ) -> Pin<Box<dyn Future<Output = Option<Reachability>> + Send + 'a>> {
+        Box::pin(async move {
+            // Listing a single transcript is free and needs a valid key.
+            let request = self
+                .client
+                .get(format!("{}/transcript", self.base_url))
+                .query(&[("limit", "1")])
+                .header("Authorization", &self.api_key);
+            Some(probe(request).await)
+        })
+    }
+
     fn normalizer(&self) -> Result<Box<dyn TranscriptionNormalizer>> {
         Ok(Box::new(AssemblyAINormalizer::new()))
     }
@@ -323,4 +477,179 @@ mod tests {
 
         assert_eq!(normalizer.normalize(input), expected);
     }
+
+    fn provider(features: Option<BTreeMap<String, bool>>) -> AssemblyAIProvider {
+        AssemblyAIProvider::new("key".to_string(), None, features, &HttpOptions::default()).unwrap()
+    }
+
+    fn request_body(provider: &AssemblyAIProvider, language: &str) -> serde_json::Value {
+        serde_json::to_value(provider.transcript_request(
+            "https://cdn/a.wav".to_string(),
+            language,
+            false,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn request_defaults_to_punctuation_and_formatting() {
+        let body = request_body(&provider(None), "en");
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "audio_url": "https://cdn/a.wav",
+                "language_code": "en",
+                "punctuate": true,
+                "format_text": true,
+            })
+        );
+    }
+
+    #[test]
+    fn configured_features_are_sent_and_override_defaults() {
+        let features = BTreeMap::from([
+            ("format_text".to_string(), false),
+            ("disfluencies".to_string(), true),
+        ]);
+        let body = request_body(&provider(Some(features)), "auto");
+        assert_eq!(body["punctuate"], true);
+        assert_eq!(body["format_text"], false);
+        assert_eq!(body["disfluencies"], true);
+        assert!(body.get("language_code").is_none());
+    }
+
+    #[tokio::test]
+    async fn upload_streams_the_file_and_is_retried() {
+        let (url, server) = super::super::retry::mock_server(vec![
+            "HTTP/1.1 503 Service Unavailable\r\nretry-after: 0\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 40\r\nconnection: close\r\n\r\n{\"upload_url\":\"https://cdn/meeting.wav\"}",
+        ])
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("meeting.wav");
+        std::fs::File::create(&audio)
+            .unwrap()
+            .set_len(4 * 1024 * 1024)
+            .unwrap();
+
+        let provider =
+            AssemblyAIProvider::new("key".to_string(), Some(url), None, &HttpOptions::default())
+                .unwrap();
+        assert_eq!(
+            provider.upload_audio(&audio).await.unwrap(),
+            "https://cdn/meeting.wav"
+        );
+        assert_eq!(server.await.unwrap(), vec!["POST /upload HTTP/1.1"; 2]);
+    }
+
+    #[test]
+    fn diarization_requests_speaker_labels() {
+        let provider = provider(None);
+        let body = serde_json::to_value(provider.transcript_request(
+            "https://cdn/a.wav".to_string(),
+            "en",
+            true,
+        ))
+        .unwrap();
+        assert_eq!(body["speaker_labels"], true);
+        assert!(request_body(&provider, "en")
+            .get("speaker_labels")
+            .is_none());
+    }
+
+    /// Trimmed from a completed `GET /v2/transcript/{id}` with
+    /// `speaker_labels: true`.
+    const DIARIZED_TRANSCRIPT: &str = r#"{
+        "id": "6rlr37h5wn-4a9b-4b2f-8c43-1f2bd6d8d1f4",
+        "language_model": "assemblyai_default",
+        "acoustic_model": "assemblyai_default",
+        "language_code": "en_us",
+        "status": "completed",
+        "audio_url": "https://cdn.assemblyai.com/upload/3b3e7c1c",
+        "text": "Hello, is everyone here? Yes, let's start.",
+        "words": [
+            {"text": "Hello,", "start": 80, "end": 480, "confidence": 0.99, "speaker": "A"},
+            {"text": "is", "start": 480, "end": 640, "confidence": 0.99, "speaker": "A"},
+            {"text": "everyone", "start": 640, "end": 1040, "confidence": 0.99, "speaker": "A"},
+            {"text": "here?", "start": 1040, "end": 1360, "confidence": 0.98, "speaker": "A"},
+            {"text": "Yes,", "start": 2160, "end": 2480, "confidence": 0.99, "speaker": "B"},
+            {"text": "let's", "start": 2480, "end": 2720, "confidence": 0.99, "speaker": "B"},
+            {"text": "start.", "start": 2720, "end": 3120, "confidence": 0.99, "speaker": "B"}
+        ],
+        "utterances": [
+            {
+                "confidence": 0.9875,
+                "end": 1360,
+                "speaker": "A",
+                "start": 80,
+                "text": "Hello, is everyone here?",
+                "words": [
+                    {"text": "Hello,", "start": 80, "end": 480, "confidence": 0.99, "speaker": "A"}
+                ]
+            },
+            {
+                "confidence": 0.99,
+                "end": 3120,
+                "speaker": "B",
+                "start": 2160,
+                "text": "Yes, let's start.",
+                "words": [
+                    {"text": "Yes,", "start": 2160, "end": 2480, "confidence": 0.99, "speaker": "B"}
+                ]
+            }
+        ],
+        "confidence": 0.9889,
+        "audio_duration": 4,
+        "punctuate": true,
+        "format_text": true,
+        "speaker_labels": true,
+        "speakers_expected": null,
+        "error": null
+    }"#;
+
+    #[test]
+    fn diarized_transcript_yields_speaker_turns_in_seconds() {
+        let transcript: TranscriptResponse = serde_json::from_str(DIARIZED_TRANSCRIPT).unwrap();
+        assert_eq!(transcript.status, TranscriptStatus::Completed);
+        let transcription = diarized(transcript);
+        assert_eq!(
+            transcription.text,
+            "Hello, is everyone here? Yes, let's start."
+        );
+        assert_eq!(
+            transcription.utterances,
+            vec![
+                Utterance {
+                    speaker: "A".to_string(),
+                    start: 0.08,
+                    end: 1.36,
+                    text: "Hello, is everyone here?".to_string(),
+                },
+                Utterance {
+                    speaker: "B".to_string(),
+                    start: 2.16,
+                    end: 3.12,
+                    text: "Yes, let's start.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn transcript_without_utterances_has_no_turns() {
+        let transcript: TranscriptResponse = serde_json::from_str(
+            r#"{"id": "abc", "status": "completed", "text": "Hi.", "utterances": null, "error": null}"#,
+        )
+        .unwrap();
+        let transcription = diarized(transcript);
+        assert_eq!(transcription.text, "Hi.");
+        assert!(transcription.utterances.is_empty());
+    }
+
+    #[test]
+    fn features_cannot_replace_request_fields() {
+        let features = BTreeMap::from([("audio_url".to_string(), false)]);
+        let body = request_body(&provider(Some(features)), "en");
+        assert_eq!(body["audio_url"], "https://cdn/a.wav");
+    }
 }
@@ -1,14 +1,19 @@
 use anyhow::{Context, Result};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::path::Path;
 use std::pin::Pin;
 use tokio::fs;
 use tracing::{debug, error, info};
 
-use super::TranscriptionProvider;
+use super::retry::{send_upload, RetryPolicy};
+use super::{Reachability, TranscriptionError, TranscriptionProvider};
 use crate::normalizer::TranscriptionNormalizer;
+use audetic_core::http::{header_map, HttpOptions};
+use audetic_core::language;
 
 async fn encode_file(path: &Path) -> anyhow::Result<String> {
     let bytes = fs::read(path).await?;
@@ -19,10 +24,22 @@ async fn encode_file(path: &Path) -> anyhow::Result<String> {
 #[derive(Debug, Serialize)]
 struct TranscriptionPayload {
     content: String, //base64 string
-    language: String,
+    /// Left out for `auto`, so the service detects the language.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
     timestamps: bool,
 }
 
+impl TranscriptionPayload {
+    fn new(content: String, language: &str) -> Self {
+        Self {
+            content,
+            language: language::explicit(language).map(str::to_string),
+            timestamps: false,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct TranscriptionResponse {
     result: TranscriptionResult,
@@ -48,17 +65,33 @@ struct ErrorDetail {
 pub struct AudeticProvider {
     client: reqwest::Client,
     endpoint: String,
+    /// `[whisper].extra_headers`, applied after the defaults.
+    extra_headers: HeaderMap,
+    retry: RetryPolicy,
 }
 
 impl AudeticProvider {
-    pub fn new(endpoint: Option<String>) -> Result<Self> {
-        let client = reqwest::Client::new();
+    pub fn new(
+        endpoint: Option<String>,
+        extra_headers: Option<&BTreeMap<String, String>>,
+        http: &HttpOptions,
+    ) -> Result<Self> {
+        let client = http.build_client()?;
+        let extra_headers = extra_headers
+            .map(header_map)
+            .transpose()?
+            .unwrap_or_default();
         let endpoint = endpoint
             .unwrap_or_else(|| "https://audio.audetic.link/api/v1/transcriptions".to_string());
 
         info!("Initialized Audetic provider with endpoint: {}", endpoint);
 
-        Ok(Self { client, endpoint })
+        Ok(Self {
+            client,
+            endpoint,
+            extra_headers,
+            retry: RetryPolicy::new(http),
+        })
     }
 }
 
@@ -75,27 +108,30 @@ impl TranscriptionProvider for AudeticProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
         Box::pin(async move {
             info!("Transcribing audio file via Audetic API: {:?}", audio_path);
 
             let content = encode_file(audio_path).await?;
 
-            let body = TranscriptionPayload {
-                content,
-                language: language.to_string(),
-                timestamps: false,
-            };
+            let body = TranscriptionPayload::new(content, language);
 
             debug!("Sending request to Audetic API with model");
 
-            let response = self
-                .client
-                .post(&self.endpoint)
-                .json(&body)
-                .send()
-                .await
-                .context("Failed to send request to Audetic API")?;
+            let size = body.content.len() as u64;
+            let response = send_upload(
+                &self.retry,
+                "Failed to send request to Audetic API",
+                size,
+                || {
+                    Ok(self
+                        .client
+                        .post(&self.endpoint)
+                        .json(&body)
+                        .headers(self.extra_headers.clone()))
+                },
+            )
+            .await?;
 
             let status = response.status();
             let response_text = response
@@ -109,20 +145,19 @@ impl TranscriptionProvider for AudeticProvider {
                     status, response_text
                 );
 
-                if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&response_text) {
-                    return Err(anyhow::anyhow!(
+                let message = match serde_json::from_str::<ErrorResponse>(&response_text) {
+                    Ok(error_response) => format!(
                         "Audetic API error: {} (type: {:?}, code: {:?})",
                         error_response.error.message,
                         error_response.error.r#type,
                         error_response.error.code
-                    ));
-                }
-
-                return Err(anyhow::anyhow!(
-                    "Audetic API request failed with status {}: {}",
-                    status,
-                    response_text
-                ));
+                    ),
+                    Err(_) => format!(
+                        "Audetic API request failed with status {}: {}",
+                        status, response_text
+                    ),
+                };
+                return Err(TranscriptionError::from_status(status, message));
             }
 
             let transcription: TranscriptionResponse = serde_json::from_str(&response_text)
@@ -136,6 +171,23 @@ impl TranscriptionProvider for AudeticProvider {
         })
     }
 
+    /// The Audetic API takes no key, so any HTTP answer (even a 404 or 405
+    /// to a GET on the upload endpoint) means it's up.
+    fn check_reachability<'a>(
+        &'a self,
+    ) -> Pin<Box<dyn Future<Output = Option<Reachability>> + Send + 'a>> {
+        Box::pin(async move {
+            let request = self
+                .client
+                .get(&self.endpoint)
+                .headers(self.extra_headers.clone());
+            Some(match request.send().await {
+                Ok(_) => Reachability::Ready,
+                Err(err) => Reachability::Unreachable(err.to_string()),
+            })
+        })
+    }
+
     fn normalizer(&self) -> Result<Box<dyn TranscriptionNormalizer>> {
         Ok(Box::new(AudeticWhisperNormalizer::new()))
     }
@@ -172,4 +224,13 @@ mod tests {
 
         assert_eq!(normalizer.normalize(input), expected);
     }
+
+    #[test]
+    fn test_payload_omits_auto_language() {
+        let body = serde_json::to_value(TranscriptionPayload::new("AAAA".into(), "auto")).unwrap();
+        assert!(body.get("language").is_none());
+
+        let body = serde_json::to_value(TranscriptionPayload::new("AAAA".into(), "fr")).unwrap();
+        assert_eq!(body["language"], "fr");
+    }
 }
@@ -0,0 +1,420 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use tracing::{debug, error, info};
+
+use super::retry::{send_upload, RetryPolicy};
+use super::{
+    probe, DiarizedTranscription, Reachability, TranscriptionError, TranscriptionProvider,
+    Utterance,
+};
+use crate::normalizer::TranscriptionNormalizer;
+use audetic_core::http::HttpOptions;
+use audetic_core::jobs_client::mime_type_for_extension;
+use audetic_core::language;
+
+/// Response from `/v1/listen` (pre-recorded audio). Only the transcript of
+/// the first channel's best alternative is used.
+#[derive(Debug, Deserialize)]
+struct ListenResponse {
+    results: ListenResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResults {
+    channels: Vec<Channel>,
+    /// Speaker turns, present when `utterances=true` was requested.
+    #[serde(default)]
+    utterances: Vec<ListenUtterance>,
+}
+
+/// One entry of `results.utterances`. `speaker` is only set with
+/// `diarize=true`; times are in seconds.
+#[derive(Debug, Deserialize)]
+struct ListenUtterance {
+    start: f64,
+    end: f64,
+    transcript: String,
+    speaker: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Channel {
+    alternatives: Vec<Alternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Alternative {
+    transcript: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    err_msg: String,
+}
+
+pub struct DeepgramProvider {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    retry: RetryPolicy,
+}
+
+impl DeepgramProvider {
+    pub fn new(api_key: String, endpoint: Option<String>, http: &HttpOptions) -> Result<Self> {
+        let client = http.build_client()?;
+        let base_url = endpoint
+            .unwrap_or_else(|| "https://api.deepgram.com".to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        info!("Initialized Deepgram provider with base URL: {}", base_url);
+
+        Ok(Self {
+            client,
+            api_key,
+            base_url,
+            retry: RetryPolicy::new(http),
+        })
+    }
+
+    /// POST the audio to `/v1/listen` and return the response body.
+    async fn listen(
+        &self,
+        audio_path: &Path,
+        language: &str,
+        diarize: bool,
+    ) -> Result<String, TranscriptionError> {
+        info!("Transcribing audio file via Deepgram API: {:?}", audio_path);
+
+        let audio_data = tokio::fs::read(audio_path)
+            .await
+            .context("Failed to read audio file")?;
+        let content_type = audio_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(|ext| mime_type_for_extension(&ext.to_lowercase()))
+            .unwrap_or("audio/wav");
+
+        let response = send_upload(
+            &self.retry,
+            "Failed to send request to Deepgram API",
+            audio_data.len() as u64,
+            || {
+                Ok(self
+                    .client
+                    .post(format!("{}/v1/listen", self.base_url))
+                    .query(&listen_params(language, diarize))
+                    .header("Authorization", format!("Token {}", self.api_key))
+                    .header("Content-Type", content_type)
+                    .body(audio_data.clone()))
+            },
+        )
+        .await?;
+
+        let status = response.status();
+        let response_text = response
+            .text()
+            .await
+            .context("Failed to read response body")?;
+
+        if !status.is_success() {
+            error!(
+                "Deepgram API request failed with status {}: {}",
+                status, response_text
+            );
+
+            let message = match serde_json::from_str::<ErrorResponse>(&response_text) {
+                Ok(error_response) => format!("Deepgram API error: {}", error_response.err_msg),
+                Err(_) => format!(
+                    "Deepgram API request failed with status {}: {}",
+                    status, response_text
+                ),
+            };
+            return Err(TranscriptionError::from_status(status, message));
+        }
+        Ok(response_text)
+    }
+}
+
+/// Query parameters for `/v1/listen`. An empty or `auto` language asks
+/// Deepgram to detect it. `diarize` labels each word's speaker and groups
+/// the words into per-speaker `utterances`.
+fn listen_params(language: &str, diarize: bool) -> Vec<(&'static str, String)> {
+    let mut params = vec![("smart_format", "true".to_string())];
+    match language::explicit(language) {
+        Some(language) => params.push(("language", language.to_string())),
+        None => params.push(("detect_language", "true".to_string())),
+    }
+    if diarize {
+        params.push(("diarize", "true".to_string()));
+        params.push(("utterances", "true".to_string()));
+    }
+    params
+}
+
+/// Pull the transcript out of a `/v1/listen` response body.
+fn parse_transcript(body: &str) -> Result<String> {
+    Ok(parse_diarized(body)?.text)
+}
+
+/// Pull the transcript and any speaker turns (from a diarized request) out
+/// of a `/v1/listen` response body.
+fn parse_diarized(body: &str) -> Result<DiarizedTranscription> {
+    let response: ListenResponse =
+        serde_json::from_str(body).context("Failed to parse Deepgram response")?;
+    let ListenResults {
+        channels,
+        utterances,
+    } = response.results;
+    let transcript = channels
+        .into_iter()
+        .next()
+        .and_then(|channel| channel.alternatives.into_iter().next())
+        .map(|alternative| alternative.transcript)
+        .context("Deepgram response contained no transcript")?;
+    let utterances = utterances
+        .into_iter()
+        .map(|utterance| Utterance {
+            speaker: utterance
+                .speaker
+                .map(|speaker| speaker.to_string())
+                .unwrap_or_default(),
+            start: utterance.start,
+            end: utterance.end,
+            text: utterance.transcript.trim().to_string(),
+        })
+        .collect();
+    Ok(DiarizedTranscription {
+        text: transcript.trim().to_string(),
+        utterances,
+    })
+}
+
+impl TranscriptionProvider for DeepgramProvider {
+    fn name(&self) -> &'static str {
+        "Deepgram API"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
+        Box::pin(async move {
+            let response_text = self.listen(audio_path, language, false).await?;
+            let text = parse_transcript(&response_text)?;
+            info!("Transcription complete: {} chars", text.len());
+            debug!("Raw transcription: {}", text);
+            Ok(text)
+        })
+    }
+
+    fn transcribe_diarized<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<
+        Box<
+            dyn Future<Output = Result<Option<DiarizedTranscription>, TranscriptionError>>
+                + Send
+                + 'a,
+        >,
+    > {
+        Box::pin(async move {
+            let response_text = self.listen(audio_path, language, true).await?;
+            let transcription = parse_diarized(&response_text)?;
+            info!(
+                "Diarized transcription complete: {} utterances",
+                transcription.utterances.len()
+            );
+            Ok(Some(transcription))
+        })
+    }
+
+    fn check_reachability<'a>(
+        &'a self,
+    ) -> Pin<Box<dyn Future<Output = Option<Reachability>> + Send + 'a>> {
+        Box::pin(async move {
+            // Listing the key's projects is free and needs a valid key.
+            let request = self
+                .client
+                .get(format!("{}/v1/projects", self.base_url))
+                .header("Authorization", format!("Token {}", self.api_key));
+            Some(probe(request).await)
+        })
+    }
+
+    fn normalizer(&self) -> Result<Box<dyn TranscriptionNormalizer>> {
+        Ok(Box::new(DeepgramNormalizer))
+    }
+}
+
+struct DeepgramNormalizer;
+
+impl TranscriptionNormalizer for DeepgramNormalizer {
+    fn normalize(&self, raw_output: &str) -> String {
+        raw_output.trim().to_string()
+    }
+
+    fn name(&self) -> &'static str {
+        "DeepgramNormalizer"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deepgram_normalizer() {
+        assert_eq!(
+            DeepgramNormalizer.normalize("  hello world  "),
+            "hello world"
+        );
+    }
+
+    #[test]
+    fn provider_needs_an_api_key() {
+        let http = HttpOptions::default();
+        assert!(DeepgramProvider::new("key".to_string(), None, &http)
+            .unwrap()
+            .is_available());
+        assert!(!DeepgramProvider::new(String::new(), None, &http)
+            .unwrap()
+            .is_available());
+    }
+
+    #[test]
+    fn auto_language_asks_for_detection() {
+        assert!(listen_params("auto", false).contains(&("detect_language", "true".to_string())));
+        assert!(listen_params("", false).contains(&("detect_language", "true".to_string())));
+
+        let params = listen_params("de", false);
+        assert!(params.contains(&("language", "de".to_string())));
+        assert!(!params.iter().any(|(name, _)| *name == "detect_language"));
+    }
+
+    #[test]
+    fn diarization_asks_for_speakers_and_utterances() {
+        let params = listen_params("en", true);
+        assert!(params.contains(&("diarize", "true".to_string())));
+        assert!(params.contains(&("utterances", "true".to_string())));
+        assert!(!listen_params("en", false)
+            .iter()
+            .any(|(name, _)| *name == "diarize" || *name == "utterances"));
+    }
+
+    /// Trimmed from a `/v1/listen?diarize=true&utterances=true` response.
+    const DIARIZED_RESPONSE: &str = r#"{
+        "metadata": {
+            "request_id": "1d4d4f2a-2bb9-4a5e-9c2c-3f0c5b2e8b11",
+            "created": "2024-05-02T15:34:12.371Z",
+            "duration": 6.4,
+            "channels": 1,
+            "models": ["1abfe86b-e047-4eed-858a-35e5625b41ee"]
+        },
+        "results": {
+            "channels": [{
+                "alternatives": [{
+                    "transcript": "Hello, is everyone here? Yes, let's start.",
+                    "confidence": 0.9921875,
+                    "words": [
+                        {"word": "hello", "start": 0.08, "end": 0.48, "confidence": 0.99, "speaker": 0, "speaker_confidence": 0.81, "punctuated_word": "Hello,"},
+                        {"word": "is", "start": 0.48, "end": 0.64, "confidence": 0.99, "speaker": 0, "speaker_confidence": 0.81, "punctuated_word": "is"},
+                        {"word": "everyone", "start": 0.64, "end": 1.04, "confidence": 0.99, "speaker": 0, "speaker_confidence": 0.81, "punctuated_word": "everyone"},
+                        {"word": "here", "start": 1.04, "end": 1.36, "confidence": 0.98, "speaker": 0, "speaker_confidence": 0.81, "punctuated_word": "here?"},
+                        {"word": "yes", "start": 2.16, "end": 2.48, "confidence": 0.99, "speaker": 1, "speaker_confidence": 0.66, "punctuated_word": "Yes,"},
+                        {"word": "let's", "start": 2.48, "end": 2.72, "confidence": 0.99, "speaker": 1, "speaker_confidence": 0.66, "punctuated_word": "let's"},
+                        {"word": "start", "start": 2.72, "end": 3.12, "confidence": 0.99, "speaker": 1, "speaker_confidence": 0.66, "punctuated_word": "start."}
+                    ]
+                }]
+            }],
+            "utterances": [
+                {
+                    "start": 0.08,
+                    "end": 1.36,
+                    "confidence": 0.99,
+                    "channel": 0,
+                    "transcript": "Hello, is everyone here?",
+                    "words": [],
+                    "speaker": 0,
+                    "id": "6b1a1c38-7f25-4a8d-9a53-0f6b2d1d7e3c"
+                },
+                {
+                    "start": 2.16,
+                    "end": 3.12,
+                    "confidence": 0.99,
+                    "channel": 0,
+                    "transcript": "Yes, let's start.",
+                    "words": [],
+                    "speaker": 1,
+                    "id": "0c6a4a0e-1a3c-43a5-8b4f-6f1f4f3e2d9a"
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn diarized_response_yields_speaker_turns() {
+        let transcription = parse_diarized(DIARIZED_RESPONSE).unwrap();
+        assert_eq!(
+            transcription.text,
+            "Hello, is everyone here? Yes, let's start."
+        );
+        assert_eq!(
+            transcription.utterances,
+            vec![
+                Utterance {
+                    speaker: "0".to_string(),
+                    start: 0.08,
+                    end: 1.36,
+                    text: "Hello, is everyone here?".to_string(),
+                },
+                Utterance {
+                    speaker: "1".to_string(),
+                    start: 2.16,
+                    end: 3.12,
+                    text: "Yes, let's start.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn response_without_utterances_has_no_turns() {
+        let body = r#"{"results": {"channels": [{"alternatives": [{"transcript": "Hi."}]}]}}"#;
+        let transcription = parse_diarized(body).unwrap();
+        assert_eq!(transcription.text, "Hi.");
+        assert!(transcription.utterances.is_empty());
+    }
+
+    #[test]
+    fn transcript_comes_from_first_channel_and_alternative() {
+        let body = r#"{
+            "metadata": {"request_id": "abc"},
+            "results": {"channels": [
+                {"alternatives": [
+                    {"transcript": " Hello there. ", "confidence": 0.98},
+                    {"transcript": "Hollow there.", "confidence": 0.4}
+                ]}
+            ]}
+        }"#;
+        assert_eq!(parse_transcript(body).unwrap(), "Hello there.");
+    }
+
+    #[test]
+    fn empty_results_are_an_error() {
+        let body = r#"{"results": {"channels": []}}"#;
+        assert!(parse_transcript(body)
+            .unwrap_err()
+            .to_string()
+            .contains("no transcript"));
+    }
+}
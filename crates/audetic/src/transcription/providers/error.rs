@@ -0,0 +1,203 @@
+//! Provider-independent transcription failures.
+//!
+//! Provider internals keep using `anyhow`; the trait boundary classifies what
+//! went wrong so callers (and the API, which turns these into status codes)
+//! can tell a bad key or a rate limit from an engine that simply failed.
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+/// Why a [`TranscriptionProvider`](super::TranscriptionProvider) call failed.
+/// Each variant carries the full error message, context included.
+#[derive(Debug, Error)]
+pub enum TranscriptionError {
+    /// The provider rejected the credentials (HTTP 401/403).
+    #[error("{0}")]
+    Auth(String),
+    /// The provider is throttling requests (HTTP 429) and retries ran out.
+    #[error("{0}")]
+    RateLimited(String),
+    /// The provider couldn't be reached: connection refused, DNS, timeout.
+    #[error("{0}")]
+    Network(String),
+    /// The audio couldn't be read or decoded by the provider or engine.
+    #[error("{0}")]
+    UnsupportedFormat(String),
+    /// Anything else the provider or engine reported.
+    #[error("{0}")]
+    Provider(String),
+}
+
+impl TranscriptionError {
+    /// Classify a failed HTTP response. A plain 400 only counts as a format
+    /// problem when the provider's message says so; services use it for every
+    /// kind of bad request.
+    pub fn from_status(status: StatusCode, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth(message),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(message),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE | StatusCode::UNPROCESSABLE_ENTITY => {
+                Self::UnsupportedFormat(message)
+            }
+            StatusCode::BAD_REQUEST if mentions_audio_format(&message) => {
+                Self::UnsupportedFormat(message)
+            }
+            _ => Self::Provider(message),
+        }
+    }
+
+    /// Stable name for the variant, returned to API clients as `code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => "provider_auth",
+            Self::RateLimited(_) => "provider_rate_limited",
+            Self::Network(_) => "provider_unreachable",
+            Self::UnsupportedFormat(_) => "unsupported_audio_format",
+            Self::Provider(_) => "provider_error",
+        }
+    }
+
+    /// The same kind of error with a different message.
+    fn with_message(&self, message: String) -> Self {
+        match self {
+            Self::Auth(_) => Self::Auth(message),
+            Self::RateLimited(_) => Self::RateLimited(message),
+            Self::Network(_) => Self::Network(message),
+            Self::UnsupportedFormat(_) => Self::UnsupportedFormat(message),
+            Self::Provider(_) => Self::Provider(message),
+        }
+    }
+}
+
+/// Providers return a classified error wrapped in `anyhow` (often under extra
+/// context); this finds it again. Failures nobody classified become `Network`
+/// when a connection or timeout error is in the chain, `Provider` otherwise.
+impl From<anyhow::Error> for TranscriptionError {
+    fn from(err: anyhow::Error) -> Self {
+        let message = format!("{err:#}");
+        if let Some(classified) = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<TranscriptionError>())
+        {
+            return classified.with_message(message);
+        }
+        let unreachable = err.chain().any(|cause| {
+            cause
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|e| e.is_connect() || e.is_timeout())
+        });
+        if unreachable {
+            Self::Network(message)
+        } else {
+            Self::Provider(message)
+        }
+    }
+}
+
+/// Wording the OpenAI-style APIs, Deepgram, and AssemblyAI use in 400s about
+/// audio they can't process.
+fn mentions_audio_format(message: &str) -> bool {
+    let message = message.to_lowercase();
+    ["format", "decode", "corrupt", "process audio"]
+        .iter()
+        .any(|needle| message.contains(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn http_statuses_map_to_variants() {
+        let cases = [
+            (StatusCode::UNAUTHORIZED, "Incorrect API key provided"),
+            (StatusCode::FORBIDDEN, "Insufficient permissions"),
+            (StatusCode::TOO_MANY_REQUESTS, "Rate limit reached"),
+            (StatusCode::UNSUPPORTED_MEDIA_TYPE, "Unsupported media type"),
+            (StatusCode::UNPROCESSABLE_ENTITY, "Unprocessable audio"),
+            (StatusCode::INTERNAL_SERVER_ERROR, "Internal error"),
+        ];
+        let kinds: Vec<_> = cases
+            .into_iter()
+            .map(|(status, message)| TranscriptionError::from_status(status, message))
+            .collect();
+
+        assert!(matches!(kinds[0], TranscriptionError::Auth(_)));
+        assert!(matches!(kinds[1], TranscriptionError::Auth(_)));
+        assert!(matches!(kinds[2], TranscriptionError::RateLimited(_)));
+        assert!(matches!(kinds[3], TranscriptionError::UnsupportedFormat(_)));
+        assert!(matches!(kinds[4], TranscriptionError::UnsupportedFormat(_)));
+        assert!(matches!(kinds[5], TranscriptionError::Provider(_)));
+    }
+
+    #[test]
+    fn bad_request_is_a_format_error_only_when_it_says_so() {
+        let openai = TranscriptionError::from_status(
+            StatusCode::BAD_REQUEST,
+            "OpenAI error: Invalid file format. Supported formats: ['flac', 'mp3', 'wav']",
+        );
+        let deepgram = TranscriptionError::from_status(
+            StatusCode::BAD_REQUEST,
+            "Deepgram error: failed to process audio: corrupt or unsupported data",
+        );
+        let language = TranscriptionError::from_status(
+            StatusCode::BAD_REQUEST,
+            "OpenAI error: Language 'xx' is not supported",
+        );
+
+        assert!(matches!(openai, TranscriptionError::UnsupportedFormat(_)));
+        assert!(matches!(deepgram, TranscriptionError::UnsupportedFormat(_)));
+        assert!(matches!(language, TranscriptionError::Provider(_)));
+    }
+
+    #[test]
+    fn classification_survives_anyhow_context() {
+        let err = anyhow::Error::new(TranscriptionError::from_status(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Groq error: rate limit exceeded",
+        ))
+        .context("Re-transcription failed");
+
+        match TranscriptionError::from(err) {
+            TranscriptionError::RateLimited(message) => assert_eq!(
+                message,
+                "Re-transcription failed: Groq error: rate limit exceeded"
+            ),
+            other => panic!("expected RateLimited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unclassified_failures_are_provider_errors() {
+        let err = Err::<(), _>(std::io::Error::other("disk on fire"))
+            .context("Failed to read audio file")
+            .unwrap_err();
+
+        match TranscriptionError::from(err) {
+            TranscriptionError::Provider(message) => {
+                assert_eq!(message, "Failed to read audio file: disk on fire")
+            }
+            other => panic!("expected Provider, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn refused_connections_are_network_errors() {
+        // Bind and drop a listener so the port is known to be closed.
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let err = reqwest::get(format!("http://{addr}/"))
+            .await
+            .context("Failed to send request to Deepgram API")
+            .unwrap_err();
+
+        assert!(matches!(
+            TranscriptionError::from(err),
+            TranscriptionError::Network(_)
+        ));
+    }
+}
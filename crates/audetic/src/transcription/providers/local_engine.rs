@@ -32,10 +32,11 @@ use transcribe_rs::{
     TranscriptionResult,
 };
 
-use super::{TranscriptionOutput, TranscriptionProvider};
+use super::{TranscriptionError, TranscriptionOutput, TranscriptionProvider};
 use crate::normalizer::TranscriptionNormalizer;
 use crate::transcription::windowing;
 use audetic_core::jobs_client::Segment;
+use audetic_core::language;
 use audetic_core::local_models::{self, Engine, ModelInfo};
 
 /// The exported Parakeet encoder precomputes its relative positional encoding
@@ -88,7 +89,8 @@ impl LocalEngineProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput, TranscriptionError>> + Send + 'a>>
+    {
         let model = self.model;
         let data_dir = self.data_dir.clone();
         let audio_path = audio_path.to_path_buf();
@@ -97,11 +99,12 @@ impl LocalEngineProvider {
         Box::pin(async move {
             // Loading and inference are both blocking + CPU-heavy; keep them off
             // the async runtime.
-            tokio::task::spawn_blocking(move || {
+            let output = tokio::task::spawn_blocking(move || {
                 transcribe_blocking(model, &data_dir, &audio_path, &language)
             })
             .await
-            .context("local transcription task panicked")?
+            .context("local transcription task panicked")??;
+            Ok(output)
         })
     }
 }
@@ -119,7 +122,7 @@ impl TranscriptionProvider for LocalEngineProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
         let fut = self.run(audio_path, language);
         Box::pin(async move { Ok(fut.await?.text) })
     }
@@ -128,7 +131,8 @@ impl TranscriptionProvider for LocalEngineProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput, TranscriptionError>> + Send + 'a>>
+    {
         self.run(audio_path, language)
     }
 
@@ -175,12 +179,8 @@ fn transcribe_blocking(
         }
         LoadedEngine::Whisper(whisper) => {
             // "auto"/empty → let Whisper detect; otherwise honor the setting.
-            let lang = match language.trim() {
-                "" | "auto" => None,
-                other => Some(other.to_string()),
-            };
             let params = WhisperInferenceParams {
-                language: lang,
+                language: language::explicit(language).map(str::to_string),
                 ..Default::default()
             };
             // whisper.cpp windows long audio internally, so no chunking here.
@@ -214,6 +214,7 @@ fn result_to_output(result: TranscriptionResult) -> TranscriptionOutput {
             start: s.start as f64,
             end: s.end as f64,
             text: s.text.trim().to_string(),
+            words: None,
         })
         .filter(|s| !s.text.is_empty())
         .collect();
@@ -327,7 +328,10 @@ fn transcode_via_ffmpeg(path: &Path) -> Result<Vec<f32>> {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         warn!("ffmpeg decode failed: {stderr}");
-        bail!("ffmpeg failed to decode audio: {stderr}");
+        return Err(TranscriptionError::UnsupportedFormat(format!(
+            "ffmpeg failed to decode audio: {stderr}"
+        ))
+        .into());
     }
 
     let samples: Vec<f32> = output
@@ -337,7 +341,9 @@ fn transcode_via_ffmpeg(path: &Path) -> Result<Vec<f32>> {
         .collect();
 
     if samples.is_empty() {
-        bail!("Decoded audio is empty");
+        return Err(
+            TranscriptionError::UnsupportedFormat("Decoded audio is empty".to_string()).into(),
+        );
     }
     Ok(samples)
 }
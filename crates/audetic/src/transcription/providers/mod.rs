@@ -1,10 +1,15 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::future::Future;
 use std::path::Path;
 use std::pin::Pin;
+use tokio::sync::watch;
+use utoipa::ToSchema;
 
 use crate::normalizer::TranscriptionNormalizer;
 use audetic_core::jobs_client::Segment;
+use tracing::warn;
 
 /// Transcription output with optional timing. `segments` is empty for providers
 /// that don't surface per-segment timestamps; consumers that only need text use
@@ -14,18 +19,62 @@ pub struct TranscriptionOutput {
     pub segments: Vec<Segment>,
 }
 
+/// One speaker's turn in a diarized transcript.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Utterance {
+    /// Provider-assigned label, e.g. `A` (AssemblyAI) or `0` (Deepgram).
+    pub speaker: String,
+    /// Seconds from the start of the audio.
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// A transcript with who said what, from a provider that labels speakers.
+pub struct DiarizedTranscription {
+    pub text: String,
+    pub utterances: Vec<Utterance>,
+}
+
+/// A transcript plus how confident the provider was in it, from 0.0 to 1.0.
+/// Used to choose between `[whisper].candidate_languages`.
+pub struct ScoredTranscription {
+    pub output: TranscriptionOutput,
+    /// `None` when the provider doesn't report confidence.
+    pub confidence: Option<f64>,
+}
+
+/// What a provider's reachability probe found.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reachability {
+    /// The endpoint answered and accepted the credentials.
+    Ready,
+    /// The endpoint answered but rejected the credentials (HTTP 401/403).
+    InvalidKey(u16),
+    /// No usable answer: the connection failed or timed out, or the endpoint
+    /// returned something unexpected (e.g. a 404 from a wrong base URL).
+    Unreachable(String),
+}
+
 pub mod assembly_api;
 pub mod audetic_api;
+pub mod deepgram_api;
+mod error;
 pub mod local_engine;
 pub mod openai_api;
 pub mod openai_cli;
+mod retry;
+pub mod subprocess;
 pub mod whisper_cpp;
 
 pub use assembly_api::AssemblyAIProvider;
 pub use audetic_api::AudeticProvider;
+pub use deepgram_api::DeepgramProvider;
+pub use error::TranscriptionError;
 pub use local_engine::LocalEngineProvider;
-pub use openai_api::OpenAIProvider;
+pub use openai_api::{OpenAICompatible, OpenAIProvider};
 pub use openai_cli::OpenAIWhisperCliProvider;
+pub use subprocess::CliLimits;
 pub use whisper_cpp::WhisperCppProvider;
 
 pub trait TranscriptionProvider: Send + Sync {
@@ -35,11 +84,13 @@ pub trait TranscriptionProvider: Send + Sync {
 
     fn normalizer(&self) -> Result<Box<dyn TranscriptionNormalizer>>;
 
+    /// Transcribe `audio_path` in `language`. Failures are classified (bad
+    /// key, rate limit, unreadable audio, ...) so callers can react to them.
     fn transcribe<'a>(
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>>;
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>>;
 
     /// Transcribe and also return per-segment timestamps when the engine
     /// produces them. The default delegates to [`transcribe`](Self::transcribe)
@@ -49,7 +100,8 @@ pub trait TranscriptionProvider: Send + Sync {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput, TranscriptionError>> + Send + 'a>>
+    {
         Box::pin(async move {
             let text = self.transcribe(audio_path, language).await?;
             Ok(TranscriptionOutput {
@@ -58,4 +110,145 @@ pub trait TranscriptionProvider: Send + Sync {
             })
         })
     }
+
+    /// Whether [`transcribe_scored`](Self::transcribe_scored) returns a
+    /// confidence. Candidate languages are only tried one by one when it does;
+    /// otherwise the first candidate is used without extra requests.
+    fn reports_confidence(&self) -> bool {
+        false
+    }
+
+    /// Make one lightweight authenticated request to confirm the endpoint is
+    /// up and accepts the configured credentials, without transcribing.
+    /// `None` for providers that don't talk to a network service.
+    fn check_reachability<'a>(
+        &'a self,
+    ) -> Pin<Box<dyn Future<Output = Option<Reachability>> + Send + 'a>> {
+        Box::pin(async { None })
+    }
+
+    /// Transcribe in `language` and report the provider's confidence. The
+    /// default delegates to [`transcribe_detailed`](Self::transcribe_detailed)
+    /// with no confidence.
+    fn transcribe_scored<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<ScoredTranscription, TranscriptionError>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let output = self.transcribe_detailed(audio_path, language).await?;
+            Ok(ScoredTranscription {
+                output,
+                confidence: None,
+            })
+        })
+    }
+
+    /// Transcribe with speaker labels, using the provider's own diarization
+    /// (AssemblyAI `speaker_labels`, Deepgram `diarize`). `None` from
+    /// providers that can't label speakers, without transcribing anything.
+    fn transcribe_diarized<'a>(
+        &'a self,
+        _audio_path: &'a Path,
+        _language: &'a str,
+    ) -> Pin<
+        Box<
+            dyn Future<Output = Result<Option<DiarizedTranscription>, TranscriptionError>>
+                + Send
+                + 'a,
+        >,
+    > {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// The transcript so far while a transcription runs, for providers that
+    /// stream it (whisper.cpp with `[whisper].stream_partials`). Each run
+    /// starts the text over from empty. `None` when the provider only has a
+    /// result at the end.
+    fn partial_transcripts(&self) -> Option<watch::Receiver<String>> {
+        None
+    }
+}
+
+/// Send a reachability probe and classify the answer. Sent once, without
+/// retries: the point is to report what happens right now.
+pub(crate) async fn probe(request: reqwest::RequestBuilder) -> Reachability {
+    match request.send().await {
+        Ok(response) => classify_probe(response.status()),
+        Err(err) => Reachability::Unreachable(err.to_string()),
+    }
+}
+
+fn classify_probe(status: reqwest::StatusCode) -> Reachability {
+    use reqwest::StatusCode;
+    if status.is_success() {
+        Reachability::Ready
+    } else if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+        Reachability::InvalidKey(status.as_u16())
+    } else {
+        Reachability::Unreachable(format!("unexpected HTTP {status}"))
+    }
+}
+
+/// Decode a CLI engine's output as UTF-8. Invalid sequences (a misconfigured
+/// locale, odd model output) become U+FFFD instead of failing, so one bad
+/// byte doesn't cost the whole transcript; `source` names the engine in the
+/// warning logged when that happens.
+pub(crate) fn decode_cli_output(bytes: &[u8], source: &str) -> String {
+    let (text, replaced) = lossy_utf8(bytes);
+    if replaced {
+        warn!(
+            "{} output was not valid UTF-8; invalid bytes were replaced with U+FFFD",
+            source
+        );
+    }
+    text
+}
+
+/// The decoded text and whether any bytes had to be replaced.
+fn lossy_utf8(bytes: &[u8]) -> (String, bool) {
+    match String::from_utf8_lossy(bytes) {
+        Cow::Borrowed(text) => (text.to_string(), false),
+        Cow::Owned(text) => (text, true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_utf8_is_decoded_unchanged() {
+        let (text, replaced) = lossy_utf8("café — ok".as_bytes());
+        assert_eq!(text, "café — ok");
+        assert!(!replaced);
+    }
+
+    #[test]
+    fn invalid_utf8_is_replaced_and_flagged() {
+        let bytes = b"hello \xff\xfe world \xc3";
+        let (text, replaced) = lossy_utf8(bytes);
+        assert_eq!(text, "hello \u{FFFD}\u{FFFD} world \u{FFFD}");
+        assert!(replaced);
+        assert_eq!(decode_cli_output(bytes, "whisper.cpp"), text);
+    }
+
+    #[tokio::test]
+    async fn probe_tells_a_bad_key_from_a_wrong_endpoint() {
+        let (url, server) = retry::mock_server(vec![
+            "HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 401 Unauthorized\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+        ])
+        .await;
+        let client = reqwest::Client::new();
+        assert_eq!(probe(client.get(&url)).await, Reachability::Ready);
+        assert_eq!(probe(client.get(&url)).await, Reachability::InvalidKey(401));
+        assert_eq!(
+            probe(client.get(&url)).await,
+            Reachability::Unreachable("unexpected HTTP 404 Not Found".to_string())
+        );
+        server.await.unwrap();
+    }
 }
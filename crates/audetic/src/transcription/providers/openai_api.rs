@@ -1,19 +1,48 @@
 use anyhow::{Context, Result};
+use reqwest::header::HeaderMap;
 use reqwest::multipart::{Form, Part};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::path::Path;
 use std::pin::Pin;
 use tracing::{debug, error, info};
 
-use super::TranscriptionProvider;
+use super::retry::{send_upload, RetryPolicy};
+use super::{
+    probe, Reachability, ScoredTranscription, TranscriptionError, TranscriptionOutput,
+    TranscriptionProvider,
+};
 use crate::normalizer::TranscriptionNormalizer;
+use audetic_core::http::{header_map, HttpOptions};
+use audetic_core::jobs_client::{Segment, Word};
+use audetic_core::language;
 
 #[derive(Debug, Deserialize)]
 struct TranscriptionResponse {
     text: String,
 }
 
+/// `verbose_json` response shape. Words are returned at the top level rather
+/// than nested under their segment.
+#[derive(Debug, Deserialize)]
+struct VerboseTranscriptionResponse {
+    text: String,
+    #[serde(default)]
+    segments: Vec<VerboseSegment>,
+    #[serde(default)]
+    words: Vec<Word>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VerboseSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    /// Mean token log-probability, the basis for the transcript's confidence.
+    avg_logprob: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ErrorResponse {
     error: ErrorDetail,
@@ -26,33 +55,160 @@ struct ErrorDetail {
     code: Option<String>,
 }
 
+/// A service speaking OpenAI's transcription API. They share the multipart
+/// request and the `json` / `verbose_json` responses, so one provider serves
+/// them all; only the name and the defaults for an unset `api_endpoint` and
+/// `model` differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenAICompatible {
+    /// Shown in logs and errors.
+    pub name: &'static str,
+    pub default_endpoint: &'static str,
+    pub default_model: &'static str,
+}
+
+impl OpenAICompatible {
+    /// `provider = "openai-api"`.
+    pub const OPENAI: Self = Self {
+        name: "OpenAI API",
+        default_endpoint: "https://api.openai.com/v1/audio/transcriptions",
+        default_model: "whisper-1",
+    };
+
+    /// `provider = "groq"`: Whisper on Groq's OpenAI-compatible endpoint.
+    pub const GROQ: Self = Self {
+        name: "Groq",
+        default_endpoint: "https://api.groq.com/openai/v1/audio/transcriptions",
+        default_model: "whisper-large-v3",
+    };
+}
+
 pub struct OpenAIProvider {
+    service: OpenAICompatible,
     client: reqwest::Client,
     api_key: String,
     endpoint: String,
     model: String,
+    word_timestamps: bool,
+    /// `[whisper].extra_headers`, applied after the defaults so they can
+    /// replace `Authorization` for gateways that want another scheme.
+    extra_headers: HeaderMap,
+    retry: RetryPolicy,
 }
 
 impl OpenAIProvider {
-    pub fn new(api_key: String, endpoint: Option<String>, model: String) -> Result<Self> {
-        let client = reqwest::Client::new();
-        let endpoint = endpoint
-            .unwrap_or_else(|| "https://api.openai.com/v1/audio/transcriptions".to_string());
-
-        info!("Initialized OpenAI provider with endpoint: {}", endpoint);
+    pub fn new(
+        service: OpenAICompatible,
+        api_key: String,
+        endpoint: Option<String>,
+        model: Option<String>,
+        word_timestamps: bool,
+        extra_headers: Option<&BTreeMap<String, String>>,
+        http: &HttpOptions,
+    ) -> Result<Self> {
+        let client = http.build_client()?;
+        let extra_headers = extra_headers
+            .map(header_map)
+            .transpose()?
+            .unwrap_or_default();
+        let endpoint = endpoint.unwrap_or_else(|| service.default_endpoint.to_string());
+        let model = model.unwrap_or_else(|| service.default_model.to_string());
+
+        info!(
+            "Initialized {} provider with endpoint: {}",
+            service.name, endpoint
+        );
 
         Ok(Self {
+            service,
             client,
             api_key,
             endpoint,
             model,
+            word_timestamps,
+            extra_headers,
+            retry: RetryPolicy::new(http),
         })
     }
+
+    /// Upload the audio and return the raw response body. `verbose` asks for
+    /// `verbose_json` with word and segment timestamps.
+    async fn request(&self, audio_path: &Path, language: &str, verbose: bool) -> Result<String> {
+        let audio_data = tokio::fs::read(audio_path)
+            .await
+            .context("Failed to read audio file")?;
+
+        let filename = audio_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("audio.wav");
+
+        debug!(
+            "Sending request to {} with model: {}, language: {}",
+            self.service.name, self.model, language
+        );
+
+        // Multipart bodies are consumed by sending, so each attempt gets a
+        // fresh form.
+        let context = format!("Failed to send request to {}", self.service.name);
+        let size = audio_data.len() as u64;
+        let response = send_upload(&self.retry, &context, size, || {
+            let audio_part = Part::bytes(audio_data.clone())
+                .file_name(filename.to_string())
+                .mime_str("audio/wav")
+                .context("Failed to set MIME type")?;
+
+            let form = form_fields(&self.model, language, verbose)
+                .into_iter()
+                .fold(
+                    Form::new().part("file", audio_part),
+                    |form, (name, value)| form.text(name, value),
+                );
+
+            Ok(self
+                .client
+                .post(&self.endpoint)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .multipart(form)
+                .headers(self.extra_headers.clone()))
+        })
+        .await?;
+
+        let status = response.status();
+        let response_text = response
+            .text()
+            .await
+            .context("Failed to read response body")?;
+
+        if !status.is_success() {
+            error!(
+                "{} request failed with status {}: {}",
+                self.service.name, status, response_text
+            );
+
+            let message = match serde_json::from_str::<ErrorResponse>(&response_text) {
+                Ok(error_response) => format!(
+                    "{} error: {} (type: {:?}, code: {:?})",
+                    self.service.name,
+                    error_response.error.message,
+                    error_response.error.r#type,
+                    error_response.error.code
+                ),
+                Err(_) => format!(
+                    "{} request failed with status {}: {}",
+                    self.service.name, status, response_text
+                ),
+            };
+            return Err(TranscriptionError::from_status(status, message).into());
+        }
+
+        Ok(response_text)
+    }
 }
 
 impl TranscriptionProvider for OpenAIProvider {
     fn name(&self) -> &'static str {
-        "OpenAI API"
+        self.service.name
     }
 
     fn is_available(&self) -> bool {
@@ -63,84 +219,107 @@ impl TranscriptionProvider for OpenAIProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
         Box::pin(async move {
-            info!("Transcribing audio file via OpenAI API: {:?}", audio_path);
+            info!(
+                "Transcribing audio file via {}: {:?}",
+                self.service.name, audio_path
+            );
 
-            let audio_data = tokio::fs::read(audio_path)
-                .await
-                .context("Failed to read audio file")?;
+            let response_text = self.request(audio_path, language, false).await?;
 
-            let filename = audio_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("audio.wav");
+            let transcription: TranscriptionResponse = serde_json::from_str(&response_text)
+                .context("Failed to parse transcription response")?;
 
-            let audio_part = Part::bytes(audio_data)
-                .file_name(filename.to_string())
-                .mime_str("audio/wav")
-                .context("Failed to set MIME type")?;
+            let text = transcription.text.trim().to_string();
+            info!("Transcription complete: {} chars", text.len());
+            debug!("Raw transcription: {}", text);
 
-            let mut form = Form::new()
-                .part("file", audio_part)
-                .text("model", self.model.clone());
+            Ok(text)
+        })
+    }
 
-            if !language.is_empty() && language != "auto" {
-                form = form.text("language", language.to_string());
-            }
+    fn transcribe_detailed<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput, TranscriptionError>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            info!(
+                "Transcribing audio file via {} (detailed): {:?}",
+                self.service.name, audio_path
+            );
 
-            form = form.text("response_format", "json");
+            let response_text = self
+                .request(audio_path, language, self.word_timestamps)
+                .await?;
+
+            if !self.word_timestamps {
+                let transcription: TranscriptionResponse = serde_json::from_str(&response_text)
+                    .context("Failed to parse transcription response")?;
+                return Ok(TranscriptionOutput {
+                    text: transcription.text.trim().to_string(),
+                    segments: Vec::new(),
+                });
+            }
 
-            debug!(
-                "Sending request to OpenAI API with model: {}, language: {}",
-                self.model, language
+            let output = parse_verbose_response(&response_text)?;
+            info!(
+                "Transcription complete: {} chars, {} segments",
+                output.text.len(),
+                output.segments.len()
             );
 
-            let response = self
-                .client
-                .post(&self.endpoint)
-                .header("Authorization", format!("Bearer {}", self.api_key))
-                .multipart(form)
-                .send()
-                .await
-                .context("Failed to send request to OpenAI API")?;
-
-            let status = response.status();
-            let response_text = response
-                .text()
-                .await
-                .context("Failed to read response body")?;
-
-            if !status.is_success() {
-                error!(
-                    "OpenAI API request failed with status {}: {}",
-                    status, response_text
-                );
+            Ok(output)
+        })
+    }
 
-                if let Ok(error_response) = serde_json::from_str::<ErrorResponse>(&response_text) {
-                    return Err(anyhow::anyhow!(
-                        "OpenAI API error: {} (type: {:?}, code: {:?})",
-                        error_response.error.message,
-                        error_response.error.r#type,
-                        error_response.error.code
-                    ));
-                }
+    fn reports_confidence(&self) -> bool {
+        true
+    }
 
-                return Err(anyhow::anyhow!(
-                    "OpenAI API request failed with status {}: {}",
-                    status,
-                    response_text
-                ));
-            }
+    fn transcribe_scored<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<ScoredTranscription, TranscriptionError>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            info!(
+                "Transcribing audio file via {} (scored, language {}): {:?}",
+                self.service.name, language, audio_path
+            );
 
-            let transcription: TranscriptionResponse = serde_json::from_str(&response_text)
-                .context("Failed to parse transcription response")?;
+            // Confidence comes from the per-segment log-probabilities, which
+            // only `verbose_json` includes.
+            let response_text = self.request(audio_path, language, true).await?;
+            let mut output = parse_verbose_response(&response_text)?;
+            let confidence = verbose_confidence(&response_text);
+            if !self.word_timestamps {
+                output.segments.clear();
+            }
 
-            let text = transcription.text.trim().to_string();
-            info!("Transcription complete: {} chars", text.len());
-            debug!("Raw transcription: {}", text);
+            debug!(
+                "Transcription in {} complete: {} chars, confidence {:?}",
+                language,
+                output.text.len(),
+                confidence
+            );
+            Ok(ScoredTranscription { output, confidence })
+        })
+    }
 
-            Ok(text)
+    fn check_reachability<'a>(
+        &'a self,
+    ) -> Pin<Box<dyn Future<Output = Option<Reachability>> + Send + 'a>> {
+        Box::pin(async move {
+            let request = self
+                .client
+                .get(models_url(&self.endpoint))
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .headers(self.extra_headers.clone());
+            Some(probe(request).await)
         })
     }
 
@@ -149,6 +328,98 @@ impl TranscriptionProvider for OpenAIProvider {
     }
 }
 
+/// Text fields sent alongside the audio. An empty or `auto` language is left
+/// out, which makes OpenAI detect it.
+fn form_fields(model: &str, language: &str, verbose: bool) -> Vec<(&'static str, String)> {
+    let mut fields = vec![("model", model.to_string())];
+    if let Some(language) = language::explicit(language) {
+        fields.push(("language", language.to_string()));
+    }
+    if verbose {
+        fields.push(("response_format", "verbose_json".to_string()));
+        fields.push(("timestamp_granularities[]", "word".to_string()));
+        fields.push(("timestamp_granularities[]", "segment".to_string()));
+    } else {
+        fields.push(("response_format", "json".to_string()));
+    }
+    fields
+}
+
+/// The `/models` listing next to a transcription endpoint: the cheapest call
+/// that checks the key. Endpoints not shaped like OpenAI's are probed as-is.
+fn models_url(endpoint: &str) -> String {
+    match endpoint
+        .trim_end_matches('/')
+        .strip_suffix("/audio/transcriptions")
+    {
+        Some(base) => format!("{base}/models"),
+        None => endpoint.to_string(),
+    }
+}
+
+/// Confidence in a `verbose_json` transcript: the duration-weighted mean of
+/// each segment's `avg_logprob`, mapped back to a probability. `None` when no
+/// segment carries one.
+fn verbose_confidence(body: &str) -> Option<f64> {
+    let response: VerboseTranscriptionResponse = serde_json::from_str(body).ok()?;
+    let (weighted, total) = response
+        .segments
+        .iter()
+        .filter_map(|seg| {
+            let weight = (seg.end - seg.start).max(f64::EPSILON);
+            seg.avg_logprob.map(|logprob| (logprob * weight, weight))
+        })
+        .fold((0.0, 0.0), |(sum, total), (value, weight)| {
+            (sum + value, total + weight)
+        });
+    (total > 0.0).then(|| (weighted / total).exp().clamp(0.0, 1.0))
+}
+
+/// Parse a `verbose_json` body, attaching each top-level word to the segment
+/// its start time falls in. Without segments, all words form a single one.
+fn parse_verbose_response(body: &str) -> Result<TranscriptionOutput> {
+    let response: VerboseTranscriptionResponse =
+        serde_json::from_str(body).context("Failed to parse verbose transcription response")?;
+
+    let text = response.text.trim().to_string();
+
+    if response.segments.is_empty() {
+        let segments = match (response.words.first(), response.words.last()) {
+            (Some(first), Some(last)) => vec![Segment {
+                start: first.start,
+                end: last.end,
+                text: text.clone(),
+                words: Some(response.words.clone()),
+            }],
+            _ => Vec::new(),
+        };
+        return Ok(TranscriptionOutput { text, segments });
+    }
+
+    let last_index = response.segments.len() - 1;
+    let segments = response
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(i, seg)| {
+            let words: Vec<Word> = response
+                .words
+                .iter()
+                .filter(|w| w.start >= seg.start && (w.start < seg.end || i == last_index))
+                .cloned()
+                .collect();
+            Segment {
+                start: seg.start,
+                end: seg.end,
+                text: seg.text.trim().to_string(),
+                words: (!words.is_empty()).then_some(words),
+            }
+        })
+        .collect();
+
+    Ok(TranscriptionOutput { text, segments })
+}
+
 struct OpenAIWhisperNormalizer;
 
 impl OpenAIWhisperNormalizer {
@@ -180,4 +451,199 @@ mod tests {
 
         assert_eq!(normalizer.normalize(input), expected);
     }
+
+    #[test]
+    fn test_form_fields_omit_auto_language() {
+        for language in ["auto", ""] {
+            let fields = form_fields("whisper-1", language, false);
+            assert!(!fields.iter().any(|(name, _)| *name == "language"));
+        }
+
+        let fields = form_fields("whisper-1", "de", true);
+        assert!(fields.contains(&("language", "de".to_string())));
+        assert!(fields.contains(&("response_format", "verbose_json".to_string())));
+    }
+
+    #[test]
+    fn test_parse_verbose_response_assigns_words_to_segments() {
+        let body = r#"{
+            "task": "transcribe",
+            "language": "english",
+            "duration": 3.0,
+            "text": " Hello world. Goodbye.",
+            "segments": [
+                {"id": 0, "start": 0.0, "end": 1.5, "text": " Hello world."},
+                {"id": 1, "start": 1.5, "end": 3.0, "text": " Goodbye."}
+            ],
+            "words": [
+                {"word": "Hello", "start": 0.0, "end": 0.6},
+                {"word": "world", "start": 0.7, "end": 1.4},
+                {"word": "Goodbye", "start": 1.6, "end": 3.0}
+            ]
+        }"#;
+
+        let output = parse_verbose_response(body).unwrap();
+        assert_eq!(output.text, "Hello world. Goodbye.");
+        assert_eq!(output.segments.len(), 2);
+
+        let first = output.segments[0].words.as_ref().unwrap();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].text, "Hello");
+        assert_eq!(first[1].end, 1.4);
+
+        let second = output.segments[1].words.as_ref().unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].text, "Goodbye");
+    }
+
+    #[test]
+    fn test_parse_verbose_response_words_only() {
+        let body = r#"{
+            "text": "Hi there",
+            "words": [
+                {"word": "Hi", "start": 0.2, "end": 0.4},
+                {"word": "there", "start": 0.5, "end": 0.9}
+            ]
+        }"#;
+
+        let output = parse_verbose_response(body).unwrap();
+        assert_eq!(output.segments.len(), 1);
+        assert_eq!(output.segments[0].start, 0.2);
+        assert_eq!(output.segments[0].end, 0.9);
+        assert_eq!(output.segments[0].words.as_ref().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_groq_responses_parse_like_openai() {
+        let body = r#"{"text": " Hello from Groq.", "x_groq": {"id": "req_01abc"}}"#;
+        let response: TranscriptionResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(response.text, " Hello from Groq.");
+
+        let body = r#"{
+            "task": "transcribe",
+            "language": "English",
+            "duration": 1.2,
+            "text": " Hello from Groq.",
+            "segments": [
+                {
+                    "id": 0, "seek": 0, "start": 0.0, "end": 1.2,
+                    "text": " Hello from Groq.", "tokens": [50365, 2425],
+                    "temperature": 0.0, "avg_logprob": -0.2,
+                    "compression_ratio": 0.8, "no_speech_prob": 0.01
+                }
+            ],
+            "words": [
+                {"word": "Hello", "start": 0.0, "end": 0.4},
+                {"word": "from", "start": 0.4, "end": 0.7},
+                {"word": "Groq.", "start": 0.7, "end": 1.2}
+            ],
+            "x_groq": {"id": "req_01abc"}
+        }"#;
+
+        let output = parse_verbose_response(body).unwrap();
+        assert_eq!(output.text, "Hello from Groq.");
+        assert_eq!(output.segments.len(), 1);
+        assert_eq!(output.segments[0].words.as_ref().unwrap().len(), 3);
+        assert!((verbose_confidence(body).unwrap() - (-0.2f64).exp()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_verbose_confidence_weights_segments_by_duration() {
+        let body = r#"{
+            "text": "Hola. Adios.",
+            "segments": [
+                {"start": 0.0, "end": 3.0, "text": "Hola.", "avg_logprob": -0.1},
+                {"start": 3.0, "end": 4.0, "text": "Adios.", "avg_logprob": -0.5}
+            ]
+        }"#;
+
+        let confidence = verbose_confidence(body).unwrap();
+        assert!((confidence - (-0.2f64).exp()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_verbose_confidence_absent_without_logprobs() {
+        let body = r#"{"text": "Hi", "segments": [{"start": 0.0, "end": 1.0, "text": "Hi"}]}"#;
+        assert_eq!(verbose_confidence(body), None);
+        assert_eq!(verbose_confidence(r#"{"text": "Hi"}"#), None);
+    }
+
+    #[test]
+    fn test_models_url_sits_beside_the_transcription_endpoint() {
+        assert_eq!(
+            models_url("https://api.openai.com/v1/audio/transcriptions"),
+            "https://api.openai.com/v1/models"
+        );
+        assert_eq!(
+            models_url("https://api.groq.com/openai/v1/audio/transcriptions/"),
+            "https://api.groq.com/openai/v1/models"
+        );
+        assert_eq!(
+            models_url("http://localhost:8080/inference"),
+            "http://localhost:8080/inference"
+        );
+    }
+
+    #[tokio::test]
+    async fn rate_limited_request_is_retried() {
+        let (url, server) = super::super::retry::mock_server(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 17\r\nconnection: close\r\n\r\n{\"text\":\" hello\"}",
+        ])
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("clip.wav");
+        std::fs::write(&audio, b"RIFF").unwrap();
+
+        let provider = OpenAIProvider::new(
+            OpenAICompatible::OPENAI,
+            "key".to_string(),
+            Some(format!("{url}/v1/audio/transcriptions")),
+            None,
+            false,
+            None,
+            &HttpOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(provider.transcribe(&audio, "en").await.unwrap(), "hello");
+        assert_eq!(
+            server.await.unwrap(),
+            vec!["POST /v1/audio/transcriptions HTTP/1.1"; 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn extra_headers_are_sent_and_override_defaults() {
+        let (url, server) = super::super::retry::mock_server_heads(vec![
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 17\r\nconnection: close\r\n\r\n{\"text\":\" hello\"}",
+        ])
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let audio = dir.path().join("clip.wav");
+        std::fs::write(&audio, b"RIFF").unwrap();
+
+        let headers = BTreeMap::from([
+            ("x-api-gateway-key".to_string(), "gw-123".to_string()),
+            ("Authorization".to_string(), "Token custom".to_string()),
+        ]);
+        let provider = OpenAIProvider::new(
+            OpenAICompatible::OPENAI,
+            "key".to_string(),
+            Some(format!("{url}/v1/audio/transcriptions")),
+            None,
+            false,
+            Some(&headers),
+            &HttpOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(provider.transcribe(&audio, "en").await.unwrap(), "hello");
+
+        let heads = server.await.unwrap();
+        let lines: Vec<String> = heads[0].lines().map(str::to_lowercase).collect();
+        assert!(lines.contains(&"x-api-gateway-key: gw-123".to_string()));
+        assert!(lines.contains(&"authorization: token custom".to_string()));
+        assert!(!lines
+            .iter()
+            .any(|line| line.starts_with("authorization: bearer")));
+    }
 }
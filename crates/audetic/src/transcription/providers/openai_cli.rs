@@ -6,16 +6,19 @@ use std::process::Command;
 use tracing::{error, info};
 use which::which;
 
-use super::TranscriptionProvider;
+use super::subprocess::{run_with_limits, CliLimits};
+use super::{decode_cli_output, TranscriptionError, TranscriptionProvider};
 use crate::normalizer::TranscriptionNormalizer;
+use audetic_core::language;
 
 pub struct OpenAIWhisperCliProvider {
     command_path: PathBuf,
     model: String,
+    limits: CliLimits,
 }
 
 impl OpenAIWhisperCliProvider {
-    pub fn new(command_path: Option<String>, model: String) -> Result<Self> {
+    pub fn new(command_path: Option<String>, model: String, limits: CliLimits) -> Result<Self> {
         let command_path = if let Some(path) = command_path {
             let custom_path = PathBuf::from(path);
             if custom_path.exists() {
@@ -52,6 +55,7 @@ impl OpenAIWhisperCliProvider {
         Ok(Self {
             command_path,
             model,
+            limits,
         })
     }
 }
@@ -73,32 +77,38 @@ impl TranscriptionProvider for OpenAIWhisperCliProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
         let audio_path = audio_path.to_path_buf();
         let language = language.to_string();
         let command_path = self.command_path.clone();
         let model = self.model.clone();
+        let limits = self.limits;
 
         Box::pin(async move {
             info!("Using OpenAI Whisper CLI to transcribe: {:?}", audio_path);
 
-            let output = Command::new(&command_path)
-                .arg(&audio_path)
-                .arg("--model")
-                .arg(&model)
-                .arg("--language")
-                .arg(&language)
-                .arg("--output_format")
+            let mut cmd = tokio::process::Command::new(&command_path);
+            cmd.arg(&audio_path).arg("--model").arg(&model);
+            // Whisper detects the language itself when `--language` is left
+            // out; it doesn't accept `auto`.
+            if let Some(language) = language::explicit(&language) {
+                cmd.arg("--language").arg(language);
+            }
+            cmd.arg("--output_format")
                 .arg("txt")
                 .arg("--output_dir")
-                .arg("/tmp")
-                .output()
+                .arg("/tmp");
+            let output = run_with_limits(cmd, &limits)
+                .await
                 .context("Failed to execute whisper command")?;
 
             if !output.status.success() {
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 error!("Whisper failed: {}", stderr);
-                return Err(anyhow::anyhow!("Whisper transcription failed: {}", stderr));
+                return Err(TranscriptionError::Provider(format!(
+                    "Whisper transcription failed: {}",
+                    stderr
+                )));
             }
 
             let audio_stem = audio_path
@@ -108,8 +118,9 @@ impl TranscriptionProvider for OpenAIWhisperCliProvider {
                 .context("Invalid audio filename")?;
 
             let output_path = PathBuf::from(format!("/tmp/{audio_stem}.txt"));
-            let transcription = std::fs::read_to_string(&output_path)
-                .context("Failed to read transcription output")?;
+            let transcription =
+                std::fs::read(&output_path).context("Failed to read transcription output")?;
+            let transcription = decode_cli_output(&transcription, "Whisper CLI");
 
             let _ = std::fs::remove_file(&output_path);
 
@@ -0,0 +1,400 @@
+//! Retries for HTTP provider calls.
+//!
+//! Rate limits (429), server errors (5xx), timeouts, and dropped connections
+//! are usually gone a moment later, so they're retried with exponential
+//! backoff and jitter instead of failing the recording. A `Retry-After`
+//! header, when the server sends one, replaces the computed delay. Any other
+//! response, including the 4xx errors a retry can't fix, is returned as-is
+//! for the provider to report.
+//!
+//! Uploads of the audio itself go through [`send_upload`] instead. Their
+//! timeout grows with the size of the body, so `request_timeout_seconds`
+//! bounds the wait for the provider rather than the transfer, and they're
+//! only retried when the request never reached the server: a provider that
+//! timed out or dropped the connection after receiving the audio may still
+//! be transcribing (and billing for) it.
+
+use anyhow::Result;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use tracing::warn;
+
+use audetic_core::http::HttpOptions;
+
+/// Longest `Retry-After` honored, so a misbehaving server can't park a
+/// recording indefinitely.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Slowest upload rate budgeted for on top of the request timeout, in bytes
+/// per second (256 kbit/s).
+const MIN_UPLOAD_BYTES_PER_SEC: u64 = 32 * 1024;
+
+#[derive(Debug, Clone)]
+pub(crate) struct RetryPolicy {
+    /// Supplies `max_retries` (attempts after the first; 0 disables
+    /// retrying) and the timeouts named in errors.
+    pub http: HttpOptions,
+    /// Delay before the first retry, doubled for each one after.
+    pub base_delay: Duration,
+    /// Cap on the computed (not `Retry-After`) delay.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(http: &HttpOptions) -> Self {
+        Self {
+            http: http.clone(),
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(20),
+        }
+    }
+
+    /// Delay before retry number `retry` (0-based): exponential, with the
+    /// upper half randomized so clients that failed together don't all come
+    /// back together.
+    fn backoff(&self, retry: u32) -> Duration {
+        let full = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let half = full / 2;
+        half + half.mul_f64(jitter())
+    }
+
+    /// Timeout for uploading `bytes`: the request timeout plus the time the
+    /// body takes at [`MIN_UPLOAD_BYTES_PER_SEC`]. `None` when requests are
+    /// unbounded.
+    fn upload_timeout(&self, bytes: u64) -> Option<Duration> {
+        self.http
+            .request_timeout
+            .map(|limit| limit + Duration::from_secs(bytes.div_ceil(MIN_UPLOAD_BYTES_PER_SEC)))
+    }
+}
+
+/// Send the request `build` creates, retrying transient failures. `build` is
+/// called once per attempt since request bodies can't be reused. `context`
+/// describes a failed send, both in retry logs and the final error.
+pub(crate) async fn send_with_retry(
+    policy: &RetryPolicy,
+    context: &str,
+    build: impl FnMut() -> Result<RequestBuilder>,
+) -> Result<Response> {
+    send(policy, context, None, build).await
+}
+
+/// [`send_with_retry`] for a request whose body is `bytes` of audio; see the
+/// module docs for how its timeout and retries differ.
+pub(crate) async fn send_upload(
+    policy: &RetryPolicy,
+    context: &str,
+    bytes: u64,
+    build: impl FnMut() -> Result<RequestBuilder>,
+) -> Result<Response> {
+    send(policy, context, Some(bytes), build).await
+}
+
+async fn send(
+    policy: &RetryPolicy,
+    context: &str,
+    upload: Option<u64>,
+    mut build: impl FnMut() -> Result<RequestBuilder>,
+) -> Result<Response> {
+    let max_retries = policy.http.max_retries;
+    let timeout = upload.and_then(|bytes| policy.upload_timeout(bytes));
+    // Names the timeout that actually applied in errors.
+    let http = HttpOptions {
+        request_timeout: timeout.or(policy.http.request_timeout),
+        ..policy.http.clone()
+    };
+    let mut retry = 0;
+    loop {
+        let mut request = build()?;
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        let result = request.send().await;
+        let delay = match &result {
+            Ok(response) if is_retryable_status(response.status()) => {
+                retry_after(response).unwrap_or_else(|| policy.backoff(retry))
+            }
+            Err(err) if upload.is_some() && !err.is_connect() => {
+                return result.map_err(|err| http.request_error(err, context));
+            }
+            Err(err) if is_retryable_error(err) => policy.backoff(retry),
+            _ => return result.map_err(|err| http.request_error(err, context)),
+        };
+        if retry >= max_retries {
+            return result.map_err(|err| http.request_error(err, context));
+        }
+
+        let reason = match &result {
+            Ok(response) => format!("HTTP {}", response.status()),
+            Err(err) => err.to_string(),
+        };
+        retry += 1;
+        warn!(
+            "{} ({}); retrying in {:?} ({}/{})",
+            context, reason, delay, retry, max_retries
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Timeouts, refused or reset connections. Errors building the request or
+/// decoding a response would fail the same way again.
+fn is_retryable_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_connect() || err.is_request()
+}
+
+/// `Retry-After` in seconds, capped at [`MAX_RETRY_AFTER`]. The HTTP-date
+/// form is ignored in favor of the computed backoff.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds: u64 = response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// A random fraction in `[0, 1)`, from the std hasher's per-instance keys.
+fn jitter() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// A one-connection-per-response HTTP server for provider tests: answers
+/// each request with the next of `responses` (raw HTTP) and returns the
+/// request lines it saw.
+#[cfg(test)]
+pub(crate) async fn mock_server(
+    responses: Vec<&'static str>,
+) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    let (url, heads) = mock_server_heads(responses).await;
+    let handle = tokio::spawn(async move {
+        heads
+            .await
+            .unwrap()
+            .iter()
+            .map(|head| head.lines().next().unwrap_or_default().to_string())
+            .collect()
+    });
+    (url, handle)
+}
+
+/// [`mock_server`], returning each request's whole head (request line and
+/// headers) for tests that check what was sent.
+#[cfg(test)]
+pub(crate) async fn mock_server_heads(
+    responses: Vec<&'static str>,
+) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut seen = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            // Read the whole request so closing the socket doesn't reset it.
+            let mut request = Vec::new();
+            let mut buf = [0u8; 8192];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some(head_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..head_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if request.len() >= head_end + 4 + content_length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let text = String::from_utf8_lossy(&request);
+            let head = text.split("\r\n\r\n").next().unwrap_or_default();
+            seen.push(head.to_string());
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.shutdown().await.ok();
+        }
+        seen
+    });
+    (url, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOO_MANY: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nretry-after: 0\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+    const UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+    const BAD_REQUEST: &str =
+        "HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+    const OK: &str = "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok";
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            http: HttpOptions {
+                max_retries,
+                ..HttpOptions::default()
+            },
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    async fn post(url: &str, policy: &RetryPolicy) -> Result<Response> {
+        let client = policy.http.build_client().unwrap();
+        send_with_retry(policy, "Failed to upload", || {
+            Ok(client.post(format!("{url}/upload")).body("audio"))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn rate_limit_then_success_is_retried() {
+        let (url, server) = mock_server(vec![TOO_MANY, OK]).await;
+        let response = post(&url, &policy(3)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.text().await.unwrap(), "ok");
+        assert_eq!(server.await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (url, server) = mock_server(vec![BAD_REQUEST]).await;
+        let response = post(&url, &policy(3)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries_with_the_last_response() {
+        let (url, server) = mock_server(vec![UNAVAILABLE, UNAVAILABLE, UNAVAILABLE]).await;
+        let response = post(&url, &policy(2)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn refused_connections_are_retried_then_reported() {
+        // Bind and drop to get a port nothing listens on.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let err = post(&format!("http://127.0.0.1:{port}"), &policy(1))
+            .await
+            .unwrap_err();
+        assert!(err.downcast_ref::<reqwest::Error>().unwrap().is_connect());
+    }
+
+    #[tokio::test]
+    async fn timeouts_are_retried_and_reported_as_such() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        // Accept connections but never answer.
+        let server = tokio::spawn(async move {
+            let mut held = Vec::new();
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                held.push(stream);
+            }
+        });
+
+        let mut policy = policy(1);
+        policy.http.request_timeout = Some(Duration::from_millis(50));
+        let err = post(&url, &policy).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to upload: request timed out after 50ms"
+        );
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn upload_timeouts_are_not_retried() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let seen = connections.clone();
+        // Accept connections but never answer.
+        let server = tokio::spawn(async move {
+            let mut held = Vec::new();
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                seen.fetch_add(1, Ordering::SeqCst);
+                held.push(stream);
+            }
+        });
+
+        let mut policy = policy(3);
+        policy.http.request_timeout = Some(Duration::from_millis(50));
+        let client = policy.http.build_client().unwrap();
+        let err = send_upload(&policy, "Failed to upload", 5, || {
+            Ok(client.post(format!("{url}/upload")).body("audio"))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Failed to upload: request timed out after 1.05s"
+        );
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+        server.abort();
+    }
+
+    #[test]
+    fn upload_timeout_grows_with_the_body() {
+        let mut policy = policy(0);
+        assert_eq!(policy.upload_timeout(10_000_000), None);
+
+        policy.http.request_timeout = Some(Duration::from_secs(120));
+        assert_eq!(
+            policy.upload_timeout(MIN_UPLOAD_BYTES_PER_SEC * 600),
+            Some(Duration::from_secs(720))
+        );
+        assert_eq!(policy.upload_timeout(1), Some(Duration::from_secs(121)));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap_with_jitter() {
+        let policy = RetryPolicy {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            ..policy(5)
+        };
+        for (retry, full) in [(0, 100), (1, 200), (2, 400), (3, 800), (4, 1000)] {
+            let delay = policy.backoff(retry);
+            assert!(
+                delay >= Duration::from_millis(full / 2) && delay <= Duration::from_millis(full),
+                "retry {retry}: {delay:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Bounded execution of CLI transcription engines (whisper.cpp, OpenAI
+//! Whisper).
+//!
+//! A wedged model run would otherwise hang the dictation pipeline forever,
+//! and a runaway one could fill memory through its captured stdout. Each run
+//! gets a wall-clock timeout and a stdout cap; hitting either kills the
+//! child's whole process group (engines may fork helpers) and returns a
+//! [`CliRunError`] saying which limit tripped.
+//!
+//! [`run_with_limits_observed`] additionally hands each stdout line to a
+//! callback as it arrives, for engines that print results progressively.
+
+use std::process::{Output, Stdio};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use tokio::process::{Child, Command};
+
+/// Default for `[whisper].cli_timeout_seconds`: generous enough for large
+/// models on long recordings with a slow CPU.
+pub const DEFAULT_CLI_TIMEOUT_SECS: u64 = 1800;
+/// Default for `[whisper].cli_max_output_bytes`.
+pub const DEFAULT_CLI_MAX_OUTPUT_BYTES: u64 = 64 * 1024 * 1024;
+/// stderr is only used for error messages; keep its head and drain the rest.
+const STDERR_KEEP_BYTES: usize = 64 * 1024;
+
+/// Limits applied to every CLI engine run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CliLimits {
+    pub timeout: Duration,
+    pub max_output_bytes: usize,
+}
+
+impl Default for CliLimits {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl CliLimits {
+    /// Limits from `[whisper]`, falling back to the defaults when unset.
+    pub fn new(timeout_seconds: Option<u64>, max_output_bytes: Option<u64>) -> Self {
+        let max_output_bytes = max_output_bytes.unwrap_or(DEFAULT_CLI_MAX_OUTPUT_BYTES);
+        Self {
+            timeout: Duration::from_secs(timeout_seconds.unwrap_or(DEFAULT_CLI_TIMEOUT_SECS)),
+            max_output_bytes: usize::try_from(max_output_bytes).unwrap_or(usize::MAX),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CliRunError {
+    #[error("timed out after {0}s (process killed)")]
+    TimedOut(u64),
+    #[error("produced more than {0} bytes of output (process killed)")]
+    OutputTooLarge(usize),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Run `cmd` to completion within `limits`, capturing stdout and stderr.
+///
+/// A non-zero exit is not an error here; callers check `status` as they
+/// would with [`Command::output`].
+pub async fn run_with_limits(cmd: Command, limits: &CliLimits) -> Result<Output, CliRunError> {
+    run_with_limits_observed(cmd, limits, |_| {}).await
+}
+
+/// [`run_with_limits`], calling `on_line` with each line of stdout (without
+/// its line ending) as soon as the child prints it. The returned `stdout` is
+/// still the complete output.
+pub async fn run_with_limits_observed(
+    mut cmd: Command,
+    limits: &CliLimits,
+    on_line: impl FnMut(&str),
+) -> Result<Output, CliRunError> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // Own process group, so a kill also reaches anything the engine forked.
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    let mut child = cmd.spawn()?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+
+    let run = async {
+        let (stdout, stderr) = tokio::try_join!(
+            read_capped(stdout, limits.max_output_bytes, on_line),
+            read_head(stderr, STDERR_KEEP_BYTES),
+        )?;
+        let status = child.wait().await?;
+        Ok::<_, CliRunError>(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    };
+
+    let result = match tokio::time::timeout(limits.timeout, run).await {
+        Ok(result) => result,
+        Err(_) => Err(CliRunError::TimedOut(limits.timeout.as_secs())),
+    };
+    if result.is_err() {
+        kill_process_group(&mut child).await;
+    }
+    result
+}
+
+/// Read all of `pipe` line by line, passing each to `on_line` and failing as
+/// soon as the total exceeds `max` bytes.
+async fn read_capped(
+    pipe: impl AsyncRead + Unpin,
+    max: usize,
+    mut on_line: impl FnMut(&str),
+) -> Result<Vec<u8>, CliRunError> {
+    let mut buf = Vec::new();
+    // One byte past the cap is enough to know it was exceeded.
+    let mut reader = BufReader::new(pipe.take(max as u64 + 1));
+    loop {
+        let start = buf.len();
+        if reader.read_until(b'\n', &mut buf).await? == 0 {
+            break;
+        }
+        if buf.len() > max {
+            return Err(CliRunError::OutputTooLarge(max));
+        }
+        let line = String::from_utf8_lossy(&buf[start..]);
+        on_line(line.trim_end_matches(['\r', '\n']));
+    }
+    Ok(buf)
+}
+
+/// Keep the first `keep` bytes of `pipe` and discard the rest, so the child
+/// never blocks on a full pipe.
+async fn read_head(mut pipe: impl AsyncRead + Unpin, keep: usize) -> Result<Vec<u8>, CliRunError> {
+    let mut buf = Vec::new();
+    (&mut pipe).take(keep as u64).read_to_end(&mut buf).await?;
+    tokio::io::copy(&mut pipe, &mut tokio::io::sink()).await?;
+    Ok(buf)
+}
+
+async fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // SAFETY: killpg only sends a signal; the group id is the child's
+        // pid because it was spawned with `process_group(0)`.
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill().await;
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn sh(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+
+    fn limits(timeout_ms: u64, max_output_bytes: usize) -> CliLimits {
+        CliLimits {
+            timeout: Duration::from_millis(timeout_ms),
+            max_output_bytes,
+        }
+    }
+
+    #[tokio::test]
+    async fn captures_output_within_limits() {
+        let output = run_with_limits(sh("echo transcript; echo oops >&2"), &limits(5000, 1024))
+            .await
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"transcript\n");
+        assert_eq!(output.stderr, b"oops\n");
+    }
+
+    #[tokio::test]
+    async fn observed_runs_see_each_line_and_keep_the_full_output() {
+        let mut lines = Vec::new();
+        let output = run_with_limits_observed(
+            sh("echo first; echo second; printf last"),
+            &limits(5000, 1024),
+            |line| lines.push(line.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(lines, ["first", "second", "last"]);
+        assert_eq!(output.stdout, b"first\nsecond\nlast");
+    }
+
+    #[tokio::test]
+    async fn sleeping_forever_hits_the_timeout() {
+        let started = Instant::now();
+        let err = run_with_limits(sh("sleep 1000"), &limits(200, 1024))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CliRunError::TimedOut(_)), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn huge_output_hits_the_cap() {
+        let err = run_with_limits(sh("yes transcript"), &limits(10_000, 64 * 1024))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CliRunError::OutputTooLarge(65536)), "{err}");
+        assert!(err.to_string().contains("65536 bytes"));
+    }
+
+    #[test]
+    fn limits_fall_back_to_defaults() {
+        let limits = CliLimits::new(None, Some(10));
+        assert_eq!(
+            limits.timeout,
+            Duration::from_secs(DEFAULT_CLI_TIMEOUT_SECS)
+        );
+        assert_eq!(limits.max_output_bytes, 10);
+    }
+}
@@ -3,17 +3,33 @@ use regex::Regex;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use std::process::{Command, Stdio};
+use tokio::process::Command;
+use tokio::sync::watch;
 use tracing::{debug, error, info, warn};
 use which::which;
 
-use super::TranscriptionProvider;
+use super::subprocess::{run_with_limits, run_with_limits_observed, CliLimits};
+use super::{decode_cli_output, TranscriptionError, TranscriptionOutput, TranscriptionProvider};
+use crate::audio::audio_mixer::AudioMixer;
+use crate::audio::resample::extend_mono_f32;
+use crate::audio::wav::{mono_spec, read_samples, temp_wav_path, write_samples};
 use crate::normalizer::TranscriptionNormalizer;
+use audetic_core::jobs_client::{Segment, Word};
+
+/// The only WAV format whisper.cpp reads correctly: 16 kHz mono 16-bit PCM.
+/// Anything else comes out as garbage rather than an error.
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
 
 pub struct WhisperCppProvider {
     command_path: PathBuf,
     model_path: Option<String>,
     model: String,
+    word_timestamps: bool,
+    limits: CliLimits,
+    /// Set with `[whisper].stream_partials`; see [`PartialSink`].
+    partials: Option<watch::Sender<String>>,
+    /// `[audio].temp_dir`, where converted copies of the input go.
+    temp_dir: PathBuf,
 }
 
 impl WhisperCppProvider {
@@ -21,6 +37,8 @@ impl WhisperCppProvider {
         command_path: Option<String>,
         model: String,
         model_path: Option<String>,
+        word_timestamps: bool,
+        limits: CliLimits,
     ) -> Result<Self> {
         let command_path = if let Some(path) = command_path {
             let custom_path = PathBuf::from(path);
@@ -46,8 +64,32 @@ impl WhisperCppProvider {
             command_path,
             model_path,
             model,
+            word_timestamps,
+            limits,
+            partials: None,
+            temp_dir: std::env::temp_dir(),
         })
     }
+
+    /// Publish the transcript as whisper.cpp prints it, through
+    /// [`TranscriptionProvider::partial_transcripts`].
+    pub fn with_stream_partials(mut self, enabled: bool) -> Self {
+        self.partials = enabled.then(|| watch::channel(String::new()).0);
+        self
+    }
+
+    /// Write converted input under `temp_dir` instead of the system one.
+    pub fn with_temp_dir(mut self, temp_dir: PathBuf) -> Self {
+        self.temp_dir = temp_dir;
+        self
+    }
+
+    fn model_arg(&self) -> String {
+        match &self.model_path {
+            Some(mp) => mp.clone(),
+            None => format!("models/ggml-{}.bin", self.model),
+        }
+    }
 }
 
 impl TranscriptionProvider for WhisperCppProvider {
@@ -63,39 +105,36 @@ impl TranscriptionProvider for WhisperCppProvider {
         &'a self,
         audio_path: &'a Path,
         language: &'a str,
-    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
         let audio_path = audio_path.to_path_buf();
         let language = language.to_string();
         let command_path = self.command_path.clone();
-        let model = self.model.clone();
+        let model_arg = self.model_arg();
         let model_path = self.model_path.clone();
+        let limits = self.limits;
 
         Box::pin(async move {
             info!("Using whisper.cpp to transcribe: {:?}", audio_path);
             warn!("whisper.cpp integration is experimental - consider using OpenAI whisper");
 
-            let model_arg = if let Some(mp) = &model_path {
+            if let Some(mp) = &model_path {
                 info!("Using custom model path: {}", mp);
-                mp.clone()
-            } else {
-                format!("models/ggml-{model}.bin")
-            };
+            }
 
+            let input = WhisperInput::prepare(&audio_path, &self.temp_dir).await?;
             let mut cmd = Command::new(&command_path);
             cmd.arg("-f")
-                .arg(&audio_path)
+                .arg(input.path())
                 .arg("-m")
                 .arg(&model_arg)
                 .arg("-l")
                 .arg(&language)
                 .arg("-nt")
-                .arg("-np")
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .stdin(Stdio::null());
+                .arg("-np");
 
-            let output = cmd
-                .output()
+            let mut partial = PartialSink::start(self.partials.as_ref());
+            let output = run_with_limits_observed(cmd, &limits, |line| partial.push_line(line))
+                .await
                 .context("Failed to execute whisper.cpp command")?;
 
             if !output.status.success() {
@@ -104,25 +143,27 @@ impl TranscriptionProvider for WhisperCppProvider {
 
                 warn!("Trying fallback whisper.cpp command");
                 let mut cmd = Command::new(&command_path);
-                cmd.arg("-f").arg(&audio_path);
+                cmd.arg("-f").arg(input.path());
 
                 if let Some(mp) = &model_path {
                     cmd.arg("-m").arg(mp);
                 }
 
-                let output = cmd
-                    .output()
+                let output = run_with_limits(cmd, &limits)
+                    .await
                     .context("Failed to execute fallback whisper.cpp command")?;
 
                 if !output.status.success() {
-                    return Err(anyhow::anyhow!("Whisper.cpp transcription failed"));
+                    return Err(TranscriptionError::Provider(
+                        "Whisper.cpp transcription failed".to_string(),
+                    ));
                 }
 
-                let transcription = String::from_utf8_lossy(&output.stdout);
+                let transcription = decode_cli_output(&output.stdout, "whisper.cpp");
                 return Ok(transcription.trim().to_string());
             }
 
-            let transcription = String::from_utf8_lossy(&output.stdout);
+            let transcription = decode_cli_output(&output.stdout, "whisper.cpp");
             let transcription = transcription.trim().to_string();
 
             info!("Transcription complete: {} chars", transcription.len());
@@ -131,9 +172,259 @@ impl TranscriptionProvider for WhisperCppProvider {
         })
     }
 
+    /// With `word_timestamps` enabled, runs whisper.cpp with one token per
+    /// timestamped line (`-ml 1 -sow`) and groups the words back into
+    /// sentence-level segments.
+    fn transcribe_detailed<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput, TranscriptionError>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            if !self.word_timestamps {
+                let text = self.transcribe(audio_path, language).await?;
+                return Ok(TranscriptionOutput {
+                    text,
+                    segments: Vec::new(),
+                });
+            }
+
+            info!(
+                "Using whisper.cpp to transcribe with word timestamps: {:?}",
+                audio_path
+            );
+
+            let input = WhisperInput::prepare(audio_path, &self.temp_dir).await?;
+            let mut cmd = Command::new(&self.command_path);
+            cmd.arg("-f")
+                .arg(input.path())
+                .arg("-m")
+                .arg(self.model_arg())
+                .arg("-l")
+                .arg(language)
+                .arg("-ml")
+                .arg("1")
+                .arg("-sow")
+                .arg("-np");
+            let mut partial = PartialSink::start(self.partials.as_ref());
+            let output =
+                run_with_limits_observed(cmd, &self.limits, |line| partial.push_line(line))
+                    .await
+                    .context("Failed to execute whisper.cpp command")?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                error!("Whisper.cpp failed: {}", stderr);
+                return Err(TranscriptionError::Provider(
+                    "Whisper.cpp transcription failed".to_string(),
+                ));
+            }
+
+            let words = parse_word_lines(&decode_cli_output(&output.stdout, "whisper.cpp"))?;
+            let segments = group_words(words);
+            let text = segments
+                .iter()
+                .map(|s| s.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            info!(
+                "Transcription complete: {} chars, {} segments",
+                text.len(),
+                segments.len()
+            );
+
+            Ok(TranscriptionOutput { text, segments })
+        })
+    }
+
     fn normalizer(&self) -> Result<Box<dyn TranscriptionNormalizer>> {
         Ok(Box::new(WhisperCppNormalizer::new()?))
     }
+
+    fn partial_transcripts(&self) -> Option<watch::Receiver<String>> {
+        self.partials.as_ref().map(watch::Sender::subscribe)
+    }
+}
+
+/// The file handed to whisper.cpp: the recording itself when it's already a
+/// 16 kHz mono 16-bit WAV, or a converted copy in the temp dir that is
+/// removed when this is dropped. Files `hound` can't read as WAV (mp3, ...)
+/// pass through for whisper.cpp to decode itself.
+struct WhisperInput {
+    path: PathBuf,
+    converted: bool,
+}
+
+impl WhisperInput {
+    async fn prepare(audio_path: &Path, temp_dir: &Path) -> Result<Self> {
+        let audio_path = audio_path.to_path_buf();
+        let temp_dir = temp_dir.to_path_buf();
+        tokio::task::spawn_blocking(move || Self::prepare_blocking(audio_path, &temp_dir))
+            .await
+            .context("WAV conversion task panicked")?
+    }
+
+    fn prepare_blocking(audio_path: PathBuf, temp_dir: &Path) -> Result<Self> {
+        let Ok(mut reader) = hound::WavReader::open(&audio_path) else {
+            return Ok(Self {
+                path: audio_path,
+                converted: false,
+            });
+        };
+        let spec = reader.spec();
+        if spec == mono_spec(WHISPER_SAMPLE_RATE, 16) {
+            return Ok(Self {
+                path: audio_path,
+                converted: false,
+            });
+        }
+
+        info!(
+            "Converting {:?} from {} Hz, {} channel(s), {}-bit to 16 kHz mono for whisper.cpp",
+            audio_path, spec.sample_rate, spec.channels, spec.bits_per_sample
+        );
+        let interleaved = read_samples(&mut reader)?;
+        let mut mono = Vec::with_capacity(interleaved.len() / usize::from(spec.channels.max(1)));
+        extend_mono_f32(&interleaved, usize::from(spec.channels), &mut mono);
+        let samples = AudioMixer::resample(&mono, spec.sample_rate, WHISPER_SAMPLE_RATE);
+
+        let path = temp_wav_path(
+            temp_dir,
+            &format!("audetic-whisper-{}", uuid::Uuid::new_v4()),
+        )?;
+        write_samples(&path, mono_spec(WHISPER_SAMPLE_RATE, 16), &samples)?;
+        Ok(Self {
+            path,
+            converted: true,
+        })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for WhisperInput {
+    fn drop(&mut self) {
+        if self.converted {
+            if let Err(e) = std::fs::remove_file(&self.path) {
+                warn!("Failed to remove converted WAV {:?}: {}", self.path, e);
+            }
+        }
+    }
+}
+
+/// Builds up the transcript from whisper.cpp's stdout while it runs and
+/// publishes it after every line. A no-op without a channel.
+///
+/// whisper-cli prints each segment once it's decoded and flushes, so this is
+/// only as incremental as the build's output; the returned transcript always
+/// comes from the complete output instead.
+struct PartialSink<'a> {
+    partials: Option<&'a watch::Sender<String>>,
+    text: String,
+}
+
+impl<'a> PartialSink<'a> {
+    /// Start a run, clearing the previous run's text.
+    fn start(partials: Option<&'a watch::Sender<String>>) -> Self {
+        if let Some(partials) = partials {
+            partials.send_replace(String::new());
+        }
+        Self {
+            partials,
+            text: String::new(),
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        let Some(partials) = self.partials else {
+            return;
+        };
+        let Some(piece) = parse_partial_line(line) else {
+            return;
+        };
+        if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        self.text.push_str(piece);
+        partials.send_replace(self.text.clone());
+    }
+}
+
+/// The transcript text on one line of whisper.cpp output: a segment with
+/// or without its `[start --> end]` prefix, or a word with `-ml 1`. Blank
+/// lines and non-speech markers such as `[BLANK_AUDIO]` have none.
+fn parse_partial_line(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let text = match line.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+        Some((stamp, rest)) if stamp.contains("-->") => rest.trim(),
+        _ => line,
+    };
+    let marker = (text.starts_with('[') && text.ends_with(']'))
+        || (text.starts_with('(') && text.ends_with(')'));
+    (!text.is_empty() && !marker).then_some(text)
+}
+
+/// Parse whisper.cpp `-ml 1` output, one `[hh:mm:ss.mmm --> hh:mm:ss.mmm] word`
+/// per line, into words. Lines without a timestamp or text are skipped.
+fn parse_word_lines(output: &str) -> Result<Vec<Word>> {
+    let line_regex = Regex::new(
+        r"^\[(\d{2}):(\d{2}):(\d{2})[:.](\d{3})\s*-->\s*(\d{2}):(\d{2}):(\d{2})[:.](\d{3})\]\s*(.*)$",
+    )?;
+
+    let seconds = |caps: &regex::Captures, first: usize| -> f64 {
+        let part = |i: usize| caps[first + i].parse::<u64>().unwrap_or(0);
+        let millis = ((part(0) * 60 + part(1)) * 60 + part(2)) * 1000 + part(3);
+        millis as f64 / 1000.0
+    };
+
+    Ok(output
+        .lines()
+        .filter_map(|line| line_regex.captures(line.trim()))
+        .filter_map(|caps| {
+            let text = caps[9].trim();
+            (!text.is_empty()).then(|| Word {
+                start: seconds(&caps, 1),
+                end: seconds(&caps, 5),
+                text: text.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Group words into segments, closing one after sentence-ending punctuation.
+fn group_words(words: Vec<Word>) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut current: Vec<Word> = Vec::new();
+
+    for word in words {
+        let ends_sentence = word.text.ends_with(['.', '?', '!']);
+        current.push(word);
+        if ends_sentence {
+            segments.push(segment_from_words(std::mem::take(&mut current)));
+        }
+    }
+    if !current.is_empty() {
+        segments.push(segment_from_words(current));
+    }
+
+    segments
+}
+
+fn segment_from_words(words: Vec<Word>) -> Segment {
+    Segment {
+        start: words.first().map(|w| w.start).unwrap_or_default(),
+        end: words.last().map(|w| w.end).unwrap_or_default(),
+        text: words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        words: Some(words),
+    }
 }
 
 struct WhisperCppNormalizer {
@@ -205,4 +496,122 @@ mod tests {
 
         assert_eq!(normalizer.normalize(input), expected);
     }
+
+    /// whisper-cli output captured with and without `-nt`.
+    const TIMESTAMPED_OUTPUT: &str = "\n[00:00:00.000 --> 00:00:04.200]   The quick brown fox jumps over the lazy dog.\n[00:00:04.200 --> 00:00:06.000]   [BLANK_AUDIO]\n[00:00:06.000 --> 00:00:09.480]   Then it ran off into the woods.\n\n";
+    const PLAIN_OUTPUT: &str =
+        " The quick brown fox jumps over the lazy dog.\r\n (wind blowing)\r\n Then it ran off into the woods.\r\n";
+
+    #[test]
+    fn partial_lines_keep_only_the_spoken_text() {
+        for output in [TIMESTAMPED_OUTPUT, PLAIN_OUTPUT] {
+            let texts: Vec<&str> = output.split('\n').filter_map(parse_partial_line).collect();
+            assert_eq!(
+                texts,
+                [
+                    "The quick brown fox jumps over the lazy dog.",
+                    "Then it ran off into the woods."
+                ]
+            );
+        }
+        assert_eq!(
+            parse_partial_line("[00:00:00.320 --> 00:00:00.900]  world."),
+            Some("world.")
+        );
+        assert_eq!(
+            parse_partial_line("[sic] as written"),
+            Some("[sic] as written")
+        );
+    }
+
+    #[test]
+    fn partial_sink_publishes_the_transcript_so_far() {
+        let (partials, mut rx) = watch::channel("left over from last run".to_string());
+        let mut sink = PartialSink::start(Some(&partials));
+        assert_eq!(*rx.borrow_and_update(), "");
+
+        let mut seen = Vec::new();
+        for line in TIMESTAMPED_OUTPUT.lines() {
+            sink.push_line(line);
+            if rx.has_changed().unwrap() {
+                seen.push(rx.borrow_and_update().clone());
+            }
+        }
+        assert_eq!(
+            seen,
+            [
+                "The quick brown fox jumps over the lazy dog.",
+                "The quick brown fox jumps over the lazy dog. Then it ran off into the woods."
+            ]
+        );
+
+        // Without a channel, lines are ignored.
+        PartialSink::start(None).push_line("anything");
+    }
+
+    fn write_wav(path: &Path, spec: hound::WavSpec, frames: usize) {
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for i in 0..frames * usize::from(spec.channels) {
+            writer.write_sample((i % 100) as i16 * 100).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[tokio::test]
+    async fn other_wav_formats_are_converted_to_a_temp_16k_mono_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("cd-quality.wav");
+        let stereo = hound::WavSpec {
+            channels: 2,
+            sample_rate: 44_100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        write_wav(&source, stereo, 44_100);
+
+        let temp_dir = dir.path().join("audetic-tmp");
+        let input = WhisperInput::prepare(&source, &temp_dir).await.unwrap();
+        let converted = input.path().to_path_buf();
+        assert_eq!(converted.parent(), Some(temp_dir.as_path()));
+        let reader = hound::WavReader::open(&converted).unwrap();
+        assert_eq!(reader.spec(), mono_spec(WHISPER_SAMPLE_RATE, 16));
+        assert_eq!(reader.duration(), 16_000);
+
+        drop(input);
+        assert!(!converted.exists());
+        assert!(source.exists());
+    }
+
+    #[tokio::test]
+    async fn whisper_ready_and_non_wav_files_pass_through() {
+        let dir = tempfile::tempdir().unwrap();
+        let ready = dir.path().join("ready.wav");
+        write_wav(&ready, mono_spec(WHISPER_SAMPLE_RATE, 16), 1600);
+        let mp3 = dir.path().join("clip.mp3");
+        std::fs::write(&mp3, b"ID3 not a wav").unwrap();
+
+        for path in [ready, mp3] {
+            let input = WhisperInput::prepare(&path, dir.path()).await.unwrap();
+            assert_eq!(input.path(), path);
+            drop(input);
+            assert!(path.exists());
+        }
+    }
+
+    #[test]
+    fn test_parse_word_lines_into_segments() {
+        let output = "\n[00:00:00.000 --> 00:00:00.320]  Hello\n[00:00:00.320 --> 00:00:00.900]  world.\n[00:00:01.100 --> 00:00:01.640]  Again\n";
+
+        let words = parse_word_lines(output).unwrap();
+        assert_eq!(words.len(), 3);
+        assert_eq!(words[0].text, "Hello");
+        assert_eq!(words[1].start, 0.32);
+        assert_eq!(words[2].end, 1.64);
+
+        let segments = group_words(words);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Hello world.");
+        assert_eq!(segments[0].end, 0.9);
+        assert_eq!(segments[1].words.as_ref().unwrap().len(), 1);
+    }
 }
@@ -1,9 +1,20 @@
 use anyhow::Result;
 use std::path::PathBuf;
+use tokio::sync::watch;
 use tracing::{debug, info};
 
-use super::{Transcriber, TranscriptionOutput};
+use super::{DiarizedTranscription, Transcriber, TranscriptionOutput, Utterance};
 use crate::normalizer::TranscriptionNormalizer;
+use audetic_core::jobs_client::Segment;
+
+/// Raw provider output alongside its normalized form.
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    pub raw: String,
+    pub text: String,
+    /// Provider timestamps; empty when the provider doesn't surface them.
+    pub segments: Vec<Segment>,
+}
 
 /// Service that orchestrates transcription and normalization
 pub struct TranscriptionService {
@@ -24,11 +35,22 @@ impl TranscriptionService {
 
     /// Transcribe audio file and return normalized text
     pub async fn transcribe(&self, audio_path: &PathBuf) -> Result<String> {
+        Ok(self.transcribe_with_raw(audio_path).await?.text)
+    }
+
+    /// Transcribe audio file and return both the provider's raw output and the
+    /// normalized text, so callers can persist the raw form for later
+    /// re-normalization, plus any segment timestamps the provider returned.
+    pub async fn transcribe_with_raw(&self, audio_path: &PathBuf) -> Result<Transcript> {
         info!("Starting transcription pipeline for: {:?}", audio_path);
 
-        // Step 1: Get raw transcription
+        // Step 1: Get raw transcription. Providers without timestamps fall
+        // back to plain text with no segments, so this costs nothing extra.
         debug!("Getting raw transcription");
-        let raw_transcription = self.transcriber.transcribe(audio_path).await?;
+        let TranscriptionOutput {
+            text: raw_transcription,
+            segments,
+        } = self.transcriber.transcribe_detailed(audio_path).await?;
 
         // Step 2: Normalize the transcription
         debug!("Normalizing transcription output");
@@ -40,7 +62,11 @@ impl TranscriptionService {
             normalized.len()
         );
 
-        Ok(normalized)
+        Ok(Transcript {
+            raw: raw_transcription,
+            text: normalized,
+            segments,
+        })
     }
 
     /// Transcribe and return normalized text plus per-segment timestamps (empty
@@ -57,6 +83,34 @@ impl TranscriptionService {
             segments: raw.segments,
         })
     }
+
+    /// Transcribe with speaker labels, normalizing the text and each
+    /// utterance. `None` when the provider can't label speakers.
+    pub async fn transcribe_diarized(
+        &self,
+        audio_path: &PathBuf,
+    ) -> Result<Option<DiarizedTranscription>> {
+        let Some(raw) = self.transcriber.transcribe_diarized(audio_path).await? else {
+            return Ok(None);
+        };
+        Ok(Some(DiarizedTranscription {
+            text: self.normalizer.normalize(&raw.text),
+            utterances: raw
+                .utterances
+                .into_iter()
+                .map(|utterance| Utterance {
+                    text: self.normalizer.normalize(&utterance.text),
+                    ..utterance
+                })
+                .collect(),
+        }))
+    }
+
+    /// The raw transcript so far while a transcription runs, when the
+    /// provider streams one. Not normalized: it's for progress display only.
+    pub fn partial_transcripts(&self) -> Option<watch::Receiver<String>> {
+        self.transcriber.partial_transcripts()
+    }
 }
 
 #[cfg(test)]
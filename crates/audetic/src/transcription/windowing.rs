@@ -20,7 +20,7 @@
 use anyhow::{Context, Result};
 
 use super::providers::TranscriptionOutput;
-use audetic_core::jobs_client::Segment;
+use audetic_core::jobs_client::{Segment, Word};
 
 /// All local audio is decoded to 16 kHz mono (`load_audio_16k_mono`); the
 /// windowing math relies on that invariant.
@@ -103,6 +103,16 @@ pub fn transcribe_windowed(
                 start: s.start + offset_secs,
                 end: s.end + offset_secs,
                 text: s.text,
+                words: s.words.map(|words| {
+                    words
+                        .into_iter()
+                        .map(|w| Word {
+                            start: w.start + offset_secs,
+                            end: w.end + offset_secs,
+                            text: w.text,
+                        })
+                        .collect()
+                }),
             }));
 
         start = end;
@@ -155,6 +165,7 @@ mod tests {
                     start: *s,
                     end: *e,
                     text: t.to_string(),
+                    words: None,
                 })
                 .collect(),
         }
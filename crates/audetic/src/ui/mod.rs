@@ -1,12 +1,23 @@
-use crate::config::UiConfig;
+mod notification;
+mod sounds;
+
+use crate::config::{IndicatorStyle, UiConfig};
 use anyhow::Result;
+use notification::DesktopNotifier;
+use sounds::{Sound, SoundEvent, SoundSet};
 use std::process::Command;
 use tracing::{debug, info, warn};
 
+/// Longest transcript preview shown when dictation completes, in characters.
+const PREVIEW_CHARS: usize = 50;
+
 #[derive(Clone)]
 pub struct Indicator {
+    style: IndicatorStyle,
     audio_feedback_enabled: bool,
+    sounds: SoundSet,
     notification_color: String,
+    desktop: DesktopNotifier,
 }
 
 impl Default for Indicator {
@@ -18,15 +29,21 @@ impl Default for Indicator {
 impl Indicator {
     pub fn new() -> Self {
         Self {
+            style: IndicatorStyle::default(),
             audio_feedback_enabled: true,
+            sounds: SoundSet::default(),
             notification_color: "rgb(ff1744)".to_string(),
+            desktop: DesktopNotifier::default(),
         }
     }
 
     pub fn from_config(config: &UiConfig) -> Self {
         Self {
+            style: config.indicator,
             audio_feedback_enabled: true,
+            sounds: SoundSet::from_config(&config.sounds),
             notification_color: config.notification_color.clone(),
+            desktop: DesktopNotifier::default(),
         }
     }
 
@@ -37,73 +54,91 @@ impl Indicator {
 
     pub async fn show_recording(&self) -> Result<()> {
         info!("Showing recording indicator");
-
-        if let Err(e) = self.hyprland_notify("󰻃 Recording...") {
-            debug!("Hyprland notification failed: {}", e);
-        }
+        self.notify(&Notice::Recording).await;
 
         // Play recording start sound
-        self.play_sound("start").await;
+        self.play_sound(SoundEvent::Start).await;
+
+        Ok(())
+    }
+
+    pub async fn show_paused(&self) -> Result<()> {
+        info!("Showing paused indicator");
+        self.notify(&Notice::Paused).await;
+
+        // Same tone as stopping: capture has halted for now.
+        self.play_sound(SoundEvent::Stop).await;
 
         Ok(())
     }
 
     pub async fn show_review(&self) -> Result<()> {
         info!("Showing review indicator");
-
-        if let Err(e) = self.hyprland_notify("󰏤 Recording saved — review before transcribing")
-        {
-            debug!("Hyprland notification failed: {}", e);
-        }
+        self.notify(&Notice::Review).await;
 
         // Reuse the recording-stop tone so the user hears the capture ended.
-        self.play_sound("stop").await;
+        self.play_sound(SoundEvent::Stop).await;
 
         Ok(())
     }
 
     pub async fn show_processing(&self) -> Result<()> {
         info!("Showing processing indicator");
-
-        if let Err(e) = self.hyprland_notify("󰦖 Processing...") {
-            debug!("Hyprland notification failed: {}", e);
-        }
+        self.notify(&Notice::Processing).await;
 
         // Play recording stop sound
-        self.play_sound("stop").await;
+        self.play_sound(SoundEvent::Stop).await;
 
         Ok(())
     }
 
-    pub async fn show_complete(&self, text: &str) -> Result<()> {
-        info!("Showing completion indicator");
+    /// Update the processing indicator with the transcript so far. Silent:
+    /// it can fire once per segment.
+    pub async fn show_partial(&self, text: &str) -> Result<()> {
+        debug!("Showing partial transcript");
+        let preview = tail_preview(text, PREVIEW_CHARS);
+        self.notify(&Notice::Partial(&preview)).await;
 
-        let preview = if text.len() > 50 {
-            format!("{}...", &text[..50])
-        } else {
-            text.to_string()
-        };
+        Ok(())
+    }
 
-        if let Err(e) = self.hyprland_notify(&format!("󰸞 {preview}")) {
-            debug!("Hyprland notification failed: {}", e);
-        }
+    pub async fn show_complete(&self, text: &str) -> Result<()> {
+        info!("Showing completion indicator");
+        let preview = preview(text, PREVIEW_CHARS);
+        self.notify(&Notice::Complete(&preview)).await;
 
         // Play completion sound
-        self.play_sound("complete").await;
+        self.play_sound(SoundEvent::Complete).await;
 
         Ok(())
     }
 
     pub async fn show_error(&self, error: &str) -> Result<()> {
         warn!("Showing error: {}", error);
+        self.notify(&Notice::Error(error)).await;
 
-        if let Err(e) = self.hyprland_notify(&format!("Error: {error}")) {
-            debug!("Hyprland notification failed: {}", e);
-        }
+        self.play_sound(SoundEvent::Error).await;
 
         Ok(())
     }
 
+    /// Show `notice` with the configured style. The indicator is best-effort:
+    /// a missing `hyprctl` or notification daemon is only logged.
+    async fn notify(&self, notice: &Notice<'_>) {
+        match self.style {
+            IndicatorStyle::Hyprland => {
+                if let Err(e) = self.hyprland_notify(&notice.hyprland_title()) {
+                    debug!("Hyprland notification failed: {}", e);
+                }
+            }
+            IndicatorStyle::Notification => {
+                if let Err(e) = self.desktop.show(notice).await {
+                    debug!("Desktop notification failed: {:#}", e);
+                }
+            }
+        }
+    }
+
     fn hyprland_notify(&self, title: &str) -> Result<()> {
         Command::new("hyprctl")
             .args(["notify", "-1", "3000", &self.notification_color, title])
@@ -112,29 +147,36 @@ impl Indicator {
         Ok(())
     }
 
-    async fn play_sound(&self, sound_type: &str) {
+    /// Play the `[ui.sounds]` choice for `event` in the background. A file
+    /// no player can handle falls back to the built-in beep.
+    async fn play_sound(&self, event: SoundEvent) {
         if !self.audio_feedback_enabled {
             return;
         }
 
-        debug!("Playing {} sound", sound_type);
+        let sound = self.sounds.get(event).clone();
+        if sound == Sound::Silent {
+            return;
+        }
+
+        debug!("Playing {} sound", event.key());
 
-        // Use a simple approach with system commands
-        let sound_type = sound_type.to_string();
         tokio::spawn(async move {
-            if let Err(e) = Self::play_simple_sound(&sound_type).await {
+            if let Sound::File(path) = &sound {
+                match sounds::play_file(path).await {
+                    Ok(()) => return,
+                    Err(e) => debug!("{}; falling back to the beep", e),
+                }
+            }
+            if let Err(e) = Self::play_simple_sound(event).await {
                 debug!("Failed to play sound: {}", e);
             }
         });
     }
 
-    async fn play_simple_sound(sound_type: &str) -> Result<()> {
-        let (freq, duration_ms) = match sound_type {
-            "start" => (800, 150),     // High pitch, short beep
-            "stop" => (400, 200),      // Low pitch, longer beep
-            "complete" => (1000, 100), // Very high pitch, very short beep
-            _ => (500, 150),
-        };
+    async fn play_simple_sound(event: SoundEvent) -> Result<()> {
+        let sound_type = event.key();
+        let (freq, duration_ms) = event.tone();
 
         // Try generating custom beep tones first (more distinctive)
         if let Ok(output) = Self::generate_beep_tone(freq, duration_ms).await {
@@ -222,3 +264,144 @@ for i in range(samples):
         Err(anyhow::anyhow!("No tone generation method available"))
     }
 }
+
+/// A state change the indicator reports, rendered per indicator style.
+enum Notice<'a> {
+    Recording,
+    Paused,
+    Review,
+    Processing,
+    /// Carries the latest, already-truncated part of the transcript so far.
+    Partial(&'a str),
+    /// Carries the already-truncated transcript preview.
+    Complete(&'a str),
+    Error(&'a str),
+}
+
+impl Notice<'_> {
+    /// One-line `hyprctl notify` text.
+    fn hyprland_title(&self) -> String {
+        match self {
+            Notice::Recording => "󰻃 Recording...".to_string(),
+            Notice::Paused => "󰏤 Paused".to_string(),
+            Notice::Review => "󰏤 Recording saved — review before transcribing".to_string(),
+            Notice::Processing => "󰦖 Processing...".to_string(),
+            Notice::Partial(preview) => format!("󰦖 {preview}"),
+            Notice::Complete(preview) => format!("󰸞 {preview}"),
+            Notice::Error(error) => format!("Error: {error}"),
+        }
+    }
+
+    /// Desktop notification title.
+    fn summary(&self) -> &'static str {
+        match self {
+            Notice::Recording => "Recording...",
+            Notice::Paused => "Recording paused",
+            Notice::Review => "Recording saved",
+            Notice::Processing => "Processing...",
+            Notice::Partial(_) => "Transcribing...",
+            Notice::Complete(_) => "Transcription complete",
+            Notice::Error(_) => "Audetic error",
+        }
+    }
+
+    /// Desktop notification body.
+    fn body(&self) -> &str {
+        match self {
+            Notice::Recording => "Toggle dictation again to stop",
+            Notice::Paused => "Toggle dictation to resume",
+            Notice::Review => "Review it before transcribing",
+            Notice::Processing => "Transcribing your recording",
+            Notice::Partial(preview) => preview,
+            Notice::Complete(preview) => preview,
+            Notice::Error(error) => error,
+        }
+    }
+
+    /// Freedesktop icon name.
+    fn icon(&self) -> &'static str {
+        match self {
+            Notice::Recording => "audio-input-microphone",
+            Notice::Paused => "media-playback-pause",
+            Notice::Review => "media-playback-pause",
+            Notice::Processing | Notice::Partial(_) => "emblem-synchronizing",
+            Notice::Complete(_) => "emblem-ok",
+            Notice::Error(_) => "dialog-error",
+        }
+    }
+}
+
+/// Truncate `text` to at most `max_chars` characters, marking the cut with
+/// "...". Counts characters rather than bytes so multibyte text is never
+/// split mid-character.
+fn preview(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => format!("{}...", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+/// The last `max_chars` characters of `text`, marking the cut with "...",
+/// so a growing transcript shows its newest words.
+fn tail_preview(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    match text.char_indices().nth(count - max_chars) {
+        Some((cut, _)) => format!("...{}", &text[cut..]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_keeps_short_text() {
+        assert_eq!(preview("hello world", 50), "hello world");
+        assert_eq!(preview("", 50), "");
+        assert_eq!(preview("abc", 3), "abc");
+    }
+
+    #[test]
+    fn preview_truncates_on_char_boundaries() {
+        assert_eq!(preview("abcdef", 3), "abc...");
+        // Each character is several bytes; byte slicing would panic here.
+        assert_eq!(preview("日本語のテキスト", 3), "日本語...");
+        assert_eq!(preview("héllo", 2), "hé...");
+    }
+
+    #[test]
+    fn tail_preview_keeps_the_newest_text() {
+        assert_eq!(tail_preview("hello world", 50), "hello world");
+        assert_eq!(tail_preview("abcdef", 3), "...def");
+        assert_eq!(tail_preview("日本語のテキスト", 3), "...キスト");
+    }
+
+    #[test]
+    fn notices_render_for_each_style() {
+        let complete = Notice::Complete("the transcript");
+        assert_eq!(complete.hyprland_title(), "󰸞 the transcript");
+        assert_eq!(complete.body(), "the transcript");
+
+        let error = Notice::Error("no microphone");
+        assert_eq!(error.hyprland_title(), "Error: no microphone");
+        assert_eq!(error.body(), "no microphone");
+        assert_eq!(error.icon(), "dialog-error");
+    }
+
+    #[test]
+    fn indicator_style_comes_from_config() {
+        let config = UiConfig {
+            indicator: IndicatorStyle::Notification,
+            ..UiConfig::default()
+        };
+        assert_eq!(
+            Indicator::from_config(&config).style,
+            IndicatorStyle::Notification
+        );
+        assert_eq!(Indicator::new().style, IndicatorStyle::Hyprland);
+    }
+}
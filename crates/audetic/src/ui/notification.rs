@@ -0,0 +1,63 @@
+//! `[ui] indicator = "notification"`: freedesktop desktop notifications.
+//!
+//! One notification follows each dictation through recording, processing,
+//! and completion: every update reuses the id the daemon handed back for
+//! the previous one, so it changes in place instead of stacking up.
+
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+
+use super::Notice;
+
+/// Posts notices as desktop notifications. Clones share the notification
+/// they update.
+#[derive(Clone, Default)]
+pub(super) struct DesktopNotifier {
+    /// Id of the last notification shown; the next one replaces it.
+    last_id: Arc<Mutex<Option<u32>>>,
+}
+
+impl DesktopNotifier {
+    #[cfg(target_os = "linux")]
+    pub async fn show(&self, notice: &Notice<'_>) -> Result<()> {
+        use notify_rust::{Notification, Timeout, Urgency};
+
+        let summary = notice.summary().to_string();
+        let body = notice.body().to_string();
+        let icon = notice.icon();
+        let (timeout, urgency) = match notice {
+            // Stays up until the next state replaces it.
+            Notice::Recording | Notice::Paused => (Timeout::Never, Urgency::Normal),
+            Notice::Error(_) => (Timeout::Milliseconds(8000), Urgency::Critical),
+            _ => (Timeout::Milliseconds(4000), Urgency::Normal),
+        };
+        let last_id = Arc::clone(&self.last_id);
+
+        // The D-Bus call blocks; the lock is held across it so updates land
+        // in the order they were made.
+        tokio::task::spawn_blocking(move || {
+            let mut last_id = last_id.lock().unwrap_or_else(|e| e.into_inner());
+            let mut notification = Notification::new();
+            notification
+                .appname("Audetic")
+                .summary(&summary)
+                .body(&body)
+                .icon(icon)
+                .timeout(timeout)
+                .urgency(urgency);
+            if let Some(id) = *last_id {
+                notification.id(id);
+            }
+            let handle = notification.show()?;
+            *last_id = Some(handle.id());
+            Ok(())
+        })
+        .await?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub async fn show(&self, _notice: &Notice<'_>) -> Result<()> {
+        let _ = &self.last_id;
+        anyhow::bail!("desktop notifications are only supported on Linux")
+    }
+}
@@ -0,0 +1,216 @@
+//! Sounds for indicator events, chosen under `[ui.sounds]`.
+//!
+//! Each entry is resolved once, when the indicator is built (startup and
+//! config reload), so a missing file is warned about then and replaced by the
+//! built-in beep instead of failing on every recording.
+
+use crate::config::SoundsConfig;
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tracing::{debug, warn};
+
+/// Where the freedesktop sound theme keeps the preset files.
+const FREEDESKTOP_SOUNDS: &str = "/usr/share/sounds/freedesktop/stereo";
+
+/// Presets other than `beep`, by file name in the freedesktop theme.
+const PRESETS: &[(&str, &str)] = &[
+    ("bell", "bell.oga"),
+    ("complete", "complete.oga"),
+    ("message", "message.oga"),
+    ("error", "dialog-error.oga"),
+];
+
+/// Players tried in order. `aplay` comes last since it only handles WAV.
+const PLAYERS: &[&str] = &["paplay", "pw-play", "afplay", "aplay"];
+
+/// An indicator event that can make a sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SoundEvent {
+    Start,
+    Stop,
+    Error,
+    Complete,
+}
+
+impl SoundEvent {
+    /// The `[ui.sounds]` key for this event.
+    pub(crate) fn key(self) -> &'static str {
+        match self {
+            SoundEvent::Start => "start",
+            SoundEvent::Stop => "stop",
+            SoundEvent::Error => "error",
+            SoundEvent::Complete => "complete",
+        }
+    }
+
+    /// The built-in beep as (frequency in Hz, duration in ms).
+    pub(crate) fn tone(self) -> (u32, u32) {
+        match self {
+            SoundEvent::Start => (800, 150),     // High pitch, short beep
+            SoundEvent::Stop => (400, 200),      // Low pitch, longer beep
+            SoundEvent::Error => (300, 300),     // Lowest pitch, longest beep
+            SoundEvent::Complete => (1000, 100), // Very high pitch, very short beep
+        }
+    }
+}
+
+/// What to play for one event.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Sound {
+    /// Set to `""`: nothing plays.
+    Silent,
+    /// The event's built-in tone.
+    Beep,
+    /// A sound file that existed when the config was read.
+    File(PathBuf),
+}
+
+/// The resolved sound for every event.
+#[derive(Debug, Clone)]
+pub(crate) struct SoundSet {
+    start: Sound,
+    stop: Sound,
+    error: Sound,
+    complete: Sound,
+}
+
+impl Default for SoundSet {
+    fn default() -> Self {
+        Self {
+            start: Sound::Beep,
+            stop: Sound::Beep,
+            error: Sound::Beep,
+            complete: Sound::Beep,
+        }
+    }
+}
+
+impl SoundSet {
+    pub fn from_config(config: &SoundsConfig) -> Self {
+        Self::resolve_in(config, Path::new(FREEDESKTOP_SOUNDS))
+    }
+
+    /// [`from_config`](Self::from_config) with presets looked up in
+    /// `theme_dir`.
+    fn resolve_in(config: &SoundsConfig, theme_dir: &Path) -> Self {
+        let entry = |event, value: &Option<String>| resolve(event, value.as_deref(), theme_dir);
+        Self {
+            start: entry(SoundEvent::Start, &config.start),
+            stop: entry(SoundEvent::Stop, &config.stop),
+            error: entry(SoundEvent::Error, &config.error),
+            complete: entry(SoundEvent::Complete, &config.complete),
+        }
+    }
+
+    pub fn get(&self, event: SoundEvent) -> &Sound {
+        match event {
+            SoundEvent::Start => &self.start,
+            SoundEvent::Stop => &self.stop,
+            SoundEvent::Error => &self.error,
+            SoundEvent::Complete => &self.complete,
+        }
+    }
+}
+
+/// Turn one `[ui.sounds]` entry into a [`Sound`]. Unset means the beep, `""`
+/// means silence; a preset or path whose file is missing falls back to the
+/// beep with a warning.
+fn resolve(event: SoundEvent, configured: Option<&str>, theme_dir: &Path) -> Sound {
+    let Some(value) = configured.map(str::trim) else {
+        return Sound::Beep;
+    };
+    if value.is_empty() {
+        return Sound::Silent;
+    }
+    if value == "beep" {
+        return Sound::Beep;
+    }
+
+    let path = match PRESETS.iter().find(|(name, _)| *name == value) {
+        Some((_, file)) => theme_dir.join(file),
+        None => PathBuf::from(value),
+    };
+    if path.is_file() {
+        Sound::File(path)
+    } else {
+        warn!(
+            "[ui.sounds].{}: {} not found; using the built-in beep instead",
+            event.key(),
+            path.display()
+        );
+        Sound::Beep
+    }
+}
+
+/// Play `path` with the first installed player that accepts it.
+pub(crate) async fn play_file(path: &Path) -> Result<()> {
+    for player in PLAYERS {
+        let status = tokio::process::Command::new(player)
+            .arg(path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+        match status {
+            Ok(status) if status.success() => {
+                debug!("Played {} with {}", path.display(), player);
+                return Ok(());
+            }
+            Ok(status) => debug!("{} couldn't play {}: {}", player, path.display(), status),
+            // Not installed; try the next one.
+            Err(_) => {}
+        }
+    }
+    bail!("No audio player could play {}", path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sounds_resolve_with_fallbacks() {
+        let theme = tempfile::tempdir().unwrap();
+        std::fs::write(theme.path().join("bell.oga"), b"").unwrap();
+        let custom = theme.path().join("ding.wav");
+        std::fs::write(&custom, b"").unwrap();
+
+        let config = SoundsConfig {
+            start: Some("bell".to_string()),
+            stop: Some(String::new()),
+            error: Some(custom.to_string_lossy().into_owned()),
+            complete: None,
+        };
+        let sounds = SoundSet::resolve_in(&config, theme.path());
+
+        assert_eq!(
+            sounds.get(SoundEvent::Start),
+            &Sound::File(theme.path().join("bell.oga"))
+        );
+        assert_eq!(sounds.get(SoundEvent::Stop), &Sound::Silent);
+        assert_eq!(sounds.get(SoundEvent::Error), &Sound::File(custom));
+        assert_eq!(sounds.get(SoundEvent::Complete), &Sound::Beep);
+
+        // A preset missing from the theme, or a path that isn't there, falls
+        // back to the beep rather than failing.
+        let missing = theme.path().join("nope.wav");
+        assert_eq!(
+            resolve(SoundEvent::Start, Some("message"), theme.path()),
+            Sound::Beep
+        );
+        assert_eq!(
+            resolve(
+                SoundEvent::Start,
+                Some(missing.to_str().unwrap()),
+                theme.path()
+            ),
+            Sound::Beep
+        );
+        assert_eq!(
+            resolve(SoundEvent::Start, Some(" beep "), theme.path()),
+            Sound::Beep
+        );
+    }
+}
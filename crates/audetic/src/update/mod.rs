@@ -1,6 +1,11 @@
+use crate::config::Config;
 use crate::global;
 use anyhow::{anyhow, Context, Result};
+use audetic_core::build_info;
+use audetic_core::http::HttpOptions;
+use audetic_core::rollback::{self, BIN_NAME};
 use fs2::FileExt;
+use minisign_verify::{PublicKey, Signature};
 use reqwest::Client;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -8,7 +13,7 @@ use sha2::{Digest, Sha256};
 use std::cmp::Ordering;
 use std::fs::File;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::fs;
 use tokio::io::AsyncReadExt;
@@ -17,9 +22,18 @@ use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
 const DEFAULT_BASE_URL: &str = "https://install.audetic.ai";
+/// Per-request limit for update checks and downloads. Longer than the
+/// provider default since it covers fetching the whole release archive.
+const UPDATE_REQUEST_TIMEOUT_SECS: u64 = 600;
 const DEFAULT_CHANNEL: &str = "stable";
-const BIN_NAME: &str = "audetic";
 const UPDATE_INTERVAL_HOURS: u64 = 1;
+/// While the same auto-update failure keeps repeating, log it only on every
+/// Nth consecutive occurrence (about once a day at the default interval).
+const FAILURE_LOG_EVERY: u32 = 24;
+/// Minisign public key release archives are signed with. The secret half
+/// lives with whoever cuts releases: `scripts/release/deploy.ts` signs every
+/// archive with it (`MINISIGN_SECRET_KEY`) and publishes `<archive>.sig`.
+const RELEASE_PUBLIC_KEY: &str = "RWR8RXu4bzJUcAK5MtrCrGi3m0dILkUeMlDPF+3jtZjiNtGPjovGTNuW";
 
 #[derive(Clone)]
 pub struct UpdateConfig {
@@ -33,6 +47,11 @@ pub struct UpdateConfig {
     pub target_id: Option<String>,
     pub current_version: String,
     pub restart_on_success: bool,
+    pub http: HttpOptions,
+    /// Refuse releases without a signature instead of warning.
+    pub require_signature: bool,
+    /// Skip signature verification entirely (`AUDETIC_SKIP_SIGNATURE`).
+    pub skip_signature: bool,
 }
 
 impl UpdateConfig {
@@ -53,7 +72,22 @@ impl UpdateConfig {
             .map(Duration::from_secs)
             .unwrap_or_else(|| Duration::from_secs(UPDATE_INTERVAL_HOURS * 3600));
         let restart_on_success = std::env::var("AUDETIC_DISABLE_AUTO_RESTART").is_err();
-        let target_id = default_target_id().map(|s| s.to_string());
+        let target_id = build_info::target_id().map(|s| s.to_string());
+        let skip_signature = std::env::var("AUDETIC_SKIP_SIGNATURE")
+            .map(|raw| raw == "1" || raw.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        // Proxy/timeout settings; an unreadable config shouldn't block updates.
+        let config = Config::load().ok();
+        let http = HttpOptions {
+            request_timeout: Some(Duration::from_secs(UPDATE_REQUEST_TIMEOUT_SECS)),
+            ..config
+                .as_ref()
+                .map(HttpOptions::from_config)
+                .unwrap_or_default()
+        };
+        let require_signature = config
+            .as_ref()
+            .is_some_and(|config| config.update.require_signature);
         Ok(Self {
             base_url,
             channel,
@@ -65,6 +99,9 @@ impl UpdateConfig {
             target_id,
             current_version: env!("CARGO_PKG_VERSION").to_string(),
             restart_on_success,
+            http,
+            require_signature,
+            skip_signature,
         })
     }
 }
@@ -79,14 +116,41 @@ struct UpdateEngineInner {
     config: UpdateConfig,
 }
 
+/// Collapses runs of identical auto-update failures, so a persistently
+/// unreachable server doesn't log a warning every interval forever.
+#[derive(Debug, Default)]
+struct FailureLog {
+    last: Option<String>,
+    repeats: u32,
+}
+
+impl FailureLog {
+    /// Record a failure. Returns how many times in a row it has now happened
+    /// when it should be logged: on the first occurrence, whenever the error
+    /// changes, and every [`FAILURE_LOG_EVERY`] repeats after that.
+    fn record(&mut self, message: String) -> Option<u32> {
+        if self.last.as_deref() == Some(message.as_str()) {
+            self.repeats += 1;
+        } else {
+            self.last = Some(message);
+            self.repeats = 1;
+        }
+        (self.repeats == 1 || self.repeats % FAILURE_LOG_EVERY == 0).then_some(self.repeats)
+    }
+
+    /// Forget the current run after a success, returning its length.
+    fn reset(&mut self) -> u32 {
+        self.last = None;
+        std::mem::take(&mut self.repeats)
+    }
+}
+
 impl UpdateEngine {
     pub fn new(config: UpdateConfig) -> Result<Self> {
         if config.target_id.is_none() {
             warn!("Auto-update disabled: unsupported target triple");
         }
-        let client = Client::builder()
-            .build()
-            .context("Failed to create HTTP client")?;
+        let client = config.http.build_client()?;
         Ok(Self {
             inner: Arc::new(UpdateEngineInner { client, config }),
         })
@@ -106,29 +170,52 @@ impl UpdateEngine {
         let engine = self.clone();
         let interval = engine.inner.config.check_interval;
         Some(tokio::spawn(async move {
-            // Load state to get the saved channel if no channel_override is provided
-            let state = engine.load_state().await.ok();
-            let channel = channel_override
-                .or_else(|| state.as_ref().map(|s| s.channel.clone()))
-                .unwrap_or_else(|| engine.inner.config.channel.clone());
-
             info!(
                 "Starting auto-update checks (channel={}, interval={}s)",
-                channel,
+                engine.background_channel(channel_override.as_deref()).await,
                 interval.as_secs()
             );
+            let mut failures = FailureLog::default();
             loop {
-                if let Err(err) = engine
+                // Re-read each time: a manual `audetic update --channel`
+                // moves the saved channel, and checking the old one would
+                // look like a switch back.
+                let channel = engine.background_channel(channel_override.as_deref()).await;
+                match engine
                     .check_and_update(&channel, UpdateMode::Install { force: false })
                     .await
                 {
-                    warn!("Auto-update check failed: {err:?}");
+                    Ok(_) => {
+                        let run = failures.reset();
+                        if run > 1 {
+                            info!("Auto-update check succeeded after {run} consecutive failures");
+                        }
+                    }
+                    Err(err) => match failures.record(format!("{err:#}")) {
+                        Some(1) => warn!("Auto-update check failed: {err:?}"),
+                        Some(count) => {
+                            warn!("Auto-update check failed {count} times in a row: {err:#}")
+                        }
+                        None => debug!("Auto-update check failed again: {err:#}"),
+                    },
                 }
                 tokio::time::sleep(interval).await;
             }
         }))
     }
 
+    /// The channel background checks follow: the override, else the saved one.
+    async fn background_channel(&self, channel_override: Option<&str>) -> String {
+        match channel_override {
+            Some(channel) => channel.to_string(),
+            None => self
+                .load_state()
+                .await
+                .map(|state| state.channel)
+                .unwrap_or_else(|_| self.inner.config.channel.clone()),
+        }
+    }
+
     pub async fn run_manual(&self, opts: UpdateOptions) -> Result<UpdateReport> {
         if opts.enable_auto_update {
             let state = self.set_auto_update(true).await?;
@@ -161,27 +248,28 @@ impl UpdateEngine {
 
         let _lock = self.acquire_lock().await?;
         let mut state = self.load_state().await?;
-        state.channel = channel.to_string();
+        let switching_channel = state.channel != channel;
+        // A check only looks; the channel moves once something installs.
+        if !mode.is_check_only() {
+            state.channel = channel.to_string();
+        }
         let auto_update_env_disabled = std::env::var("AUDETIC_DISABLE_AUTO_UPDATE")
             .map(|raw| raw == "1" || raw.eq_ignore_ascii_case("true"))
             .unwrap_or(false);
 
         let remote_version = self.fetch_remote_version(channel).await?;
         let current_version = self.inner.config.current_version.clone();
-        let comparison = compare_versions(&remote_version, &current_version);
-
-        let needs_update = match comparison {
-            Some(Ordering::Greater) => true,
-            Some(Ordering::Equal) => mode.force(),
-            Some(Ordering::Less) => mode.force(),
-            None => {
-                warn!(
-                    "Unable to compare versions (remote={}, local={})",
-                    remote_version, current_version
-                );
-                false
-            }
-        };
+        let needs_update = needs_update(
+            &remote_version,
+            &current_version,
+            switching_channel,
+            mode.force(),
+        );
+        let note = version_note(
+            &remote_version,
+            &current_version,
+            switching_channel.then_some(channel),
+        );
 
         let now = unix_timestamp();
         state.last_check_epoch = Some(now);
@@ -190,16 +278,15 @@ impl UpdateEngine {
 
         if mode.is_check_only() {
             self.save_state(&state).await?;
-            return Ok(UpdateReport::checked(
-                current_version,
-                remote_version,
-                needs_update,
-            ));
+            return Ok(
+                UpdateReport::checked(current_version, remote_version, needs_update)
+                    .with_note(note),
+            );
         }
 
         if !needs_update && !mode.force() {
             self.save_state(&state).await?;
-            return Ok(UpdateReport::up_to_date(current_version, remote_version));
+            return Ok(UpdateReport::up_to_date(current_version, remote_version).with_note(note));
         }
 
         if auto_update_env_disabled || (!state.auto_update && !mode.force()) {
@@ -278,6 +365,8 @@ impl UpdateEngine {
                 actual_sha
             ));
         }
+        self.check_signature(&archive_url, &target, &archive_path)
+            .await?;
 
         let staging_dir = download_dir.join("staging");
         if staging_dir.exists() {
@@ -345,16 +434,106 @@ impl UpdateEngine {
         body.split_whitespace().next().map(|s| s.to_string())
     }
 
+    /// Verify the archive's minisign signature, taken from the manifest's
+    /// `sig` (a URL or the signature itself) or else `<archive>.sig`. Only a
+    /// 404 for the latter counts as "unsigned"; any other failure to fetch it
+    /// fails the update, so a flaky or hostile mirror can't downgrade a
+    /// signed release to a checksum-only install.
+    async fn check_signature(
+        &self,
+        archive_url: &str,
+        target: &ReleaseTarget,
+        archive_path: &Path,
+    ) -> Result<()> {
+        if self.inner.config.skip_signature {
+            warn!("Skipping update signature verification (AUDETIC_SKIP_SIGNATURE is set)");
+            return Ok(());
+        }
+        let signature = match target.sig.as_deref() {
+            Some(sig) if sig.starts_with("https://") || sig.starts_with("http://") => Some(
+                self.fetch_text(sig)
+                    .await
+                    .with_context(|| format!("Failed to download release signature {sig}"))?,
+            ),
+            Some(sig) => Some(sig.to_string()),
+            None => self.fetch_signature(&format!("{archive_url}.sig")).await?,
+        };
+        let archive = fs::read(archive_path)
+            .await
+            .with_context(|| format!("Failed to read {}", archive_path.display()))?;
+        match verify_signature(RELEASE_PUBLIC_KEY, &archive, signature.as_deref())? {
+            SignatureCheck::Verified => {
+                info!("Update signature verified for {}", target.archive);
+            }
+            SignatureCheck::Missing if self.inner.config.require_signature => {
+                return Err(anyhow!(
+                    "Release {} is not signed and [update].require_signature is set",
+                    target.archive
+                ));
+            }
+            SignatureCheck::Missing => {
+                warn!(
+                    "Release {} has no signature; installing on checksum alone",
+                    target.archive
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch a detached signature; `None` when the server says it doesn't
+    /// exist (404).
+    async fn fetch_signature(&self, url: &str) -> Result<Option<String>> {
+        let failed = || format!("Failed to download release signature {url}");
+        let response = self
+            .inner
+            .client
+            .get(url)
+            .send()
+            .await
+            .with_context(failed)?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let text = response
+            .error_for_status()
+            .with_context(failed)?
+            .text()
+            .await
+            .with_context(failed)?;
+        Ok(Some(text))
+    }
+
+    async fn fetch_text(&self, url: &str) -> Result<String> {
+        Ok(self
+            .inner
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?)
+    }
+
     async fn fetch_to_file(&self, url: &str, destination: &Path) -> Result<()> {
+        let download_error = |err: reqwest::Error| {
+            self.inner
+                .config
+                .http
+                .request_error(err, &format!("Failed to download {url}"))
+        };
         let bytes = self
             .inner
             .client
             .get(url)
             .send()
-            .await?
+            .await
+            .map_err(download_error)?
             .error_for_status()?
             .bytes()
-            .await?;
+            .await
+            .map_err(download_error)?;
         fs::write(destination, &bytes)
             .await
             .with_context(|| format!("Failed to write download {}", destination.display()))?;
@@ -417,10 +596,7 @@ impl UpdateEngine {
             perms.set_mode(0o755);
             std::fs::set_permissions(&tmp_path, perms)?;
         }
-        let backup_path = parent.join(format!(
-            "{BIN_NAME}-{}.bak",
-            self.inner.config.current_version
-        ));
+        let backup_path = rollback::backup_path(parent, &self.inner.config.current_version);
         if target_path.exists() {
             if let Err(err) = std::fs::copy(target_path, &backup_path) {
                 warn!(
@@ -434,23 +610,44 @@ impl UpdateEngine {
         Ok(())
     }
 
+    /// Restore the binary saved by the most recent install, for when the new
+    /// version misbehaves. Takes effect after a restart.
+    pub async fn rollback(&self) -> Result<UpdateReport> {
+        let _lock = self.acquire_lock().await?;
+        let target_path = self.inner.config.binary_path.clone();
+        let (backup, version) = rollback::previous_binary(&target_path)?;
+        rollback::restore_binary(&backup, &target_path)?;
+        info!(
+            "Restored {} from {}. Restart required to take effect.",
+            target_path.display(),
+            backup.display()
+        );
+
+        let mut state = self.load_state().await?;
+        state.pending_restart = false;
+        state.last_downloaded_version = None;
+        state.current_version = Some(version.clone());
+        self.save_state(&state).await?;
+
+        Ok(UpdateReport::rolled_back(
+            self.inner.config.current_version.clone(),
+            version,
+        ))
+    }
+
+    /// Take the update lock for a read-modify-write of the state file (and
+    /// any install). The file lock keeps other processes out; flock doesn't
+    /// reliably exclude callers in this one (the background loop and API
+    /// requests each build their own engine), so those queue on an
+    /// in-process mutex first.
     async fn acquire_lock(&self) -> Result<UpdateLock> {
+        let guard = process_update_lock().lock().await;
         let path = self.inner.config.lock_file.clone();
-        tokio::task::spawn_blocking(move || {
-            if let Some(parent) = path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-            let file = std::fs::OpenOptions::new()
-                .create(true)
-                .read(true)
-                .write(true)
-                .truncate(false)
-                .open(&path)?;
-            file.lock_exclusive()
-                .with_context(|| "Failed to acquire update lock")?;
-            Ok(UpdateLock { file })
+        let file = tokio::task::spawn_blocking(move || rollback::lock_updates(&path)).await??;
+        Ok(UpdateLock {
+            file,
+            _guard: guard,
         })
-        .await?
     }
 
     async fn load_state(&self) -> Result<UpdateState> {
@@ -471,12 +668,20 @@ impl UpdateEngine {
         Ok(state)
     }
 
+    /// Write the state through a temp file and rename it into place, so a
+    /// reader outside the lock never sees half-written JSON. Callers hold
+    /// the update lock.
     async fn save_state(&self, state: &UpdateState) -> Result<()> {
-        if let Some(parent) = self.inner.config.state_file.parent() {
+        let path = &self.inner.config.state_file;
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).await?;
         }
         let content = serde_json::to_string_pretty(state)?;
-        fs::write(&self.inner.config.state_file, content).await?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, content).await?;
+        fs::rename(&tmp_path, path)
+            .await
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
         Ok(())
     }
 
@@ -518,8 +723,26 @@ impl UpdateMode {
     }
 }
 
+/// What an update check or install concluded, for scripts that shouldn't
+/// parse [`UpdateReport::message`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateOutcome {
+    UpToDate,
+    UpdateAvailable,
+    Installed,
+    /// A newer version exists but auto-update is turned off.
+    Disabled,
+    /// No release target for this platform.
+    Unsupported,
+    AutoUpdateChanged,
+    /// The previous binary was restored from its backup.
+    RolledBack,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct UpdateReport {
+    pub outcome: UpdateOutcome,
     pub current_version: String,
     pub remote_version: Option<String>,
     pub message: String,
@@ -530,6 +753,7 @@ pub struct UpdateReport {
 impl UpdateReport {
     fn unsupported(current: String) -> Self {
         Self {
+            outcome: UpdateOutcome::Unsupported,
             current_version: current,
             remote_version: None,
             message: "Auto-update not available on this platform".to_string(),
@@ -540,6 +764,7 @@ impl UpdateReport {
 
     fn disabled(current: String, remote: String) -> Self {
         Self {
+            outcome: UpdateOutcome::Disabled,
             current_version: current,
             remote_version: Some(remote),
             message: "Auto-update disabled. Enable it to install new versions.".to_string(),
@@ -550,6 +775,7 @@ impl UpdateReport {
 
     fn up_to_date(current: String, remote: String) -> Self {
         Self {
+            outcome: UpdateOutcome::UpToDate,
             current_version: current,
             remote_version: Some(remote.clone()),
             message: format!("Already on latest version ({remote})."),
@@ -559,12 +785,19 @@ impl UpdateReport {
     }
 
     fn checked(current: String, remote: String, needs_update: bool) -> Self {
-        let message = if needs_update {
-            format!("Update available: {current} → {remote}")
+        let (outcome, message) = if needs_update {
+            (
+                UpdateOutcome::UpdateAvailable,
+                format!("Update available: {current} → {remote}"),
+            )
         } else {
-            format!("Already on latest version ({remote})")
+            (
+                UpdateOutcome::UpToDate,
+                format!("Already on latest version ({remote})"),
+            )
         };
         Self {
+            outcome,
             current_version: current,
             remote_version: Some(remote),
             message,
@@ -575,6 +808,7 @@ impl UpdateReport {
 
     fn installed(current: String, remote: String) -> Self {
         Self {
+            outcome: UpdateOutcome::Installed,
             current_version: current,
             remote_version: Some(remote.clone()),
             message: format!("Update installed. Restart required to run {remote}."),
@@ -583,8 +817,29 @@ impl UpdateReport {
         }
     }
 
+    fn rolled_back(current: String, restored: String) -> Self {
+        Self {
+            outcome: UpdateOutcome::RolledBack,
+            current_version: current,
+            remote_version: Some(restored.clone()),
+            message: format!("Rolled back to {restored}. Restart required to run it."),
+            installed: false,
+            restart_required: true,
+        }
+    }
+
+    /// Append an explanation of how the versions were compared, if any.
+    fn with_note(mut self, note: Option<String>) -> Self {
+        if let Some(note) = note {
+            self.message = format!("{} {note}", self.message.trim_end_matches('.'));
+            self.message.push('.');
+        }
+        self
+    }
+
     fn auto_update_changed(requested: bool, actual: bool) -> Self {
         Self {
+            outcome: UpdateOutcome::AutoUpdateChanged,
             current_version: env!("CARGO_PKG_VERSION").to_string(),
             remote_version: None,
             message: if requested == actual {
@@ -671,8 +926,16 @@ impl UpdateState {
     }
 }
 
+/// Serializes updates within this process; see [`UpdateEngine::acquire_lock`].
+fn process_update_lock() -> &'static tokio::sync::Mutex<()> {
+    static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
+}
+
 struct UpdateLock {
     file: File,
+    /// Released after the file lock, since fields drop after `drop` runs.
+    _guard: tokio::sync::MutexGuard<'static, ()>,
 }
 
 impl Drop for UpdateLock {
@@ -683,18 +946,69 @@ impl Drop for UpdateLock {
     }
 }
 
-fn default_target_id() -> Option<&'static str> {
-    if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
-        Some("linux-x86_64-gnu")
-    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
-        Some("linux-aarch64-gnu")
-    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
-        Some("macos-aarch64")
-    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
-        Some("macos-x86_64")
-    } else {
-        None
+#[derive(Debug, PartialEq, Eq)]
+enum SignatureCheck {
+    Verified,
+    /// The release shipped without a signature.
+    Missing,
+}
+
+/// Check `archive` against a minisign `signature` made with `public_key`.
+/// A signature that is malformed or doesn't match is an error.
+fn verify_signature(
+    public_key: &str,
+    archive: &[u8],
+    signature: Option<&str>,
+) -> Result<SignatureCheck> {
+    let Some(signature) = signature else {
+        return Ok(SignatureCheck::Missing);
+    };
+    let public_key = PublicKey::from_base64(public_key)
+        .map_err(|err| anyhow!("Invalid release public key: {err}"))?;
+    let signature = Signature::decode(signature)
+        .map_err(|err| anyhow!("Malformed release signature: {err}"))?;
+    public_key
+        .verify(archive, &signature, false)
+        .map_err(|err| anyhow!("Release signature verification failed: {err}"))?;
+    Ok(SignatureCheck::Verified)
+}
+
+/// Whether `remote` should replace the running `current`.
+///
+/// Within a channel this is plain semver, where a pre-release sorts below
+/// its release (`1.2.0-beta.1` < `1.2.0`). That ordering says nothing about
+/// moving between channels, so a switch installs the other channel's
+/// version whichever way it compares; beta → stable may well go "down".
+fn needs_update(remote: &str, current: &str, switching_channel: bool, force: bool) -> bool {
+    if switching_channel && remote != current {
+        return true;
+    }
+    match compare_versions(remote, current) {
+        Some(Ordering::Greater) => true,
+        Some(Ordering::Equal | Ordering::Less) => force,
+        None => {
+            warn!(
+                "Unable to compare versions (remote={}, local={})",
+                remote, current
+            );
+            false
+        }
+    }
+}
+
+/// How `remote` and `current` were compared, when it's not obvious: a
+/// channel switch (`switched_to`), or an ordering involving pre-releases.
+fn version_note(remote: &str, current: &str, switched_to: Option<&str>) -> Option<String> {
+    if let Some(channel) = switched_to {
+        return (remote != current)
+            .then(|| format!("(switching to the {channel} channel, so version order is ignored)"));
     }
+    let is_pre = |v: &str| Version::parse(v).is_ok_and(|v| !v.pre.is_empty());
+    (is_pre(remote) || is_pre(current)).then(|| {
+        "(pre-releases sort below their release, e.g. 1.2.0-beta.1 < 1.2.0; \
+         use --channel to move between beta and stable)"
+            .to_string()
+    })
 }
 
 fn compare_versions(lhs: &str, rhs: &str) -> Option<Ordering> {
@@ -709,3 +1023,279 @@ fn unix_timestamp() -> u64 {
         .map(|d| d.as_secs())
         .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failure_log_suppresses_repeats_until_threshold() {
+        let mut log = FailureLog::default();
+        assert_eq!(log.record("server unreachable".into()), Some(1));
+        for _ in 2..FAILURE_LOG_EVERY {
+            assert_eq!(log.record("server unreachable".into()), None);
+        }
+        assert_eq!(
+            log.record("server unreachable".into()),
+            Some(FAILURE_LOG_EVERY)
+        );
+        assert_eq!(log.record("server unreachable".into()), None);
+    }
+
+    #[test]
+    fn failure_log_emits_on_new_error_and_resets_on_success() {
+        let mut log = FailureLog::default();
+        assert_eq!(log.record("timeout".into()), Some(1));
+        assert_eq!(log.record("timeout".into()), None);
+        assert_eq!(log.record("checksum mismatch".into()), Some(1));
+
+        assert_eq!(log.reset(), 1);
+        assert_eq!(log.record("checksum mismatch".into()), Some(1));
+    }
+
+    #[test]
+    fn report_constructors_set_outcome() {
+        let v = || "1.0.0".to_string();
+        let cases = [
+            (UpdateReport::unsupported(v()), UpdateOutcome::Unsupported),
+            (UpdateReport::disabled(v(), v()), UpdateOutcome::Disabled),
+            (UpdateReport::up_to_date(v(), v()), UpdateOutcome::UpToDate),
+            (
+                UpdateReport::checked(v(), "1.1.0".into(), true),
+                UpdateOutcome::UpdateAvailable,
+            ),
+            (
+                UpdateReport::checked(v(), v(), false),
+                UpdateOutcome::UpToDate,
+            ),
+            (UpdateReport::installed(v(), v()), UpdateOutcome::Installed),
+            (
+                UpdateReport::auto_update_changed(true, true),
+                UpdateOutcome::AutoUpdateChanged,
+            ),
+            (
+                UpdateReport::rolled_back(v(), v()),
+                UpdateOutcome::RolledBack,
+            ),
+        ];
+        for (report, outcome) in cases {
+            assert_eq!(report.outcome, outcome, "{}", report.message);
+        }
+    }
+
+    #[test]
+    fn in_channel_upgrades_follow_semver() {
+        assert!(needs_update("1.2.1", "1.2.0", false, false));
+        assert!(needs_update("1.3.0-beta.2", "1.3.0-beta.1", false, false));
+        assert!(!needs_update("1.2.0", "1.2.0", false, false));
+        assert!(!needs_update("1.1.0", "1.2.0", false, false));
+        assert!(needs_update("1.1.0", "1.2.0", false, true));
+        // Same channel: a release outranks its own pre-releases.
+        assert!(needs_update("1.2.0", "1.2.0-beta.3", false, false));
+        assert!(!needs_update("1.2.0-beta.1", "1.2.0", false, false));
+        assert!(!needs_update("garbage", "1.2.0", false, true));
+    }
+
+    #[test]
+    fn stable_to_beta_installs_regardless_of_order() {
+        assert!(needs_update("1.2.1-beta.1", "1.2.0", true, false));
+        // The beta of the release already installed sorts lower.
+        assert!(needs_update("1.2.0-beta.1", "1.2.0", true, false));
+    }
+
+    #[test]
+    fn beta_to_stable_installs_regardless_of_order() {
+        assert!(needs_update("1.1.9", "1.2.0-beta.3", true, false));
+        assert!(needs_update("1.2.0", "1.2.0-beta.3", true, false));
+        // Nothing to do when both channels point at the same build.
+        assert!(!needs_update("1.2.0", "1.2.0", true, false));
+    }
+
+    #[test]
+    fn notes_explain_channel_switches_and_pre_releases() {
+        let note = version_note("1.1.9", "1.2.0-beta.3", Some("stable")).unwrap();
+        assert!(note.contains("stable channel"), "{note}");
+        assert!(version_note("1.2.0", "1.2.0", Some("stable")).is_none());
+
+        let note = version_note("1.2.0-beta.1", "1.2.0", None).unwrap();
+        assert!(note.contains("1.2.0-beta.1 < 1.2.0"), "{note}");
+        assert!(version_note("1.2.1", "1.2.0", None).is_none());
+
+        let report = UpdateReport::up_to_date("1.2.0".into(), "1.2.0-beta.1".into())
+            .with_note(version_note("1.2.0-beta.1", "1.2.0", None));
+        assert!(report
+            .message
+            .starts_with("Already on latest version (1.2.0-beta.1) (pre-releases"));
+    }
+
+    #[test]
+    fn outcome_serializes_snake_case() {
+        let json =
+            serde_json::to_value(UpdateReport::checked("1.0.0".into(), "1.1.0".into(), true))
+                .unwrap();
+        assert_eq!(json["outcome"], "update_available");
+    }
+
+    /// A throwaway key pair's public half and its signature over
+    /// `SIGNED_ARCHIVE` (prehashed minisign format, as `minisign -S` writes).
+    const TEST_PUBLIC_KEY: &str = "RWQGE04wMKW31fEGUlV3MUsw9UZD6tUZgj66MLYwY2cbePSn2adFz/LA";
+    const SIGNED_ARCHIVE: &[u8] = b"audetic release archive";
+    const TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQGE04wMKW31dVie4JIeYSsfXn0tBHwk01FVxpHjAXxETO4Nku9tBDXmHjsjbN22H7mxUhjSooHzNkpgHjgDSsUxcwUxR1SUQE=
+trusted comment: timestamp:1760000000 file:audetic.tar.gz
+q+2FwOKCFJFrTZIeyVr/lT8xr0/ewgnudI3OL0k4XB9refXanmlG9TXw24fv+p3oaiZXcWkyO8r/gRMYJY/MBQ==
+";
+
+    #[test]
+    fn valid_signature_verifies() {
+        let check =
+            verify_signature(TEST_PUBLIC_KEY, SIGNED_ARCHIVE, Some(TEST_SIGNATURE)).unwrap();
+        assert_eq!(check, SignatureCheck::Verified);
+    }
+
+    #[test]
+    fn tampered_archive_fails_verification() {
+        let err = verify_signature(
+            TEST_PUBLIC_KEY,
+            b"audetic release archive!",
+            Some(TEST_SIGNATURE),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("verification failed"), "{err}");
+    }
+
+    #[test]
+    fn signature_from_another_key_is_rejected() {
+        assert!(
+            verify_signature(RELEASE_PUBLIC_KEY, SIGNED_ARCHIVE, Some(TEST_SIGNATURE)).is_err()
+        );
+        assert!(
+            verify_signature(TEST_PUBLIC_KEY, SIGNED_ARCHIVE, Some("not a signature")).is_err()
+        );
+    }
+
+    #[test]
+    fn missing_signature_is_reported_not_failed() {
+        let check = verify_signature(TEST_PUBLIC_KEY, SIGNED_ARCHIVE, None).unwrap();
+        assert_eq!(check, SignatureCheck::Missing);
+    }
+
+    /// Serves `version` to every request, for update checks.
+    async fn version_server(version: &'static str) -> String {
+        serve("200 OK", version).await
+    }
+
+    /// Answers every request with `status` and `body`.
+    async fn serve(status: &'static str, body: &'static str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len(),
+                    );
+                    stream.write_all(response.as_bytes()).await.ok();
+                    stream.shutdown().await.ok();
+                });
+            }
+        });
+        url
+    }
+
+    fn test_engine(base_url: String, dir: &Path) -> UpdateEngine {
+        UpdateEngine::new(UpdateConfig {
+            base_url,
+            channel: DEFAULT_CHANNEL.to_string(),
+            check_interval: Duration::from_secs(3600),
+            binary_path: dir.join(BIN_NAME),
+            updates_dir: dir.join("updates"),
+            state_file: dir.join("update_state.json"),
+            lock_file: dir.join("update.lock"),
+            target_id: Some("x86_64-unknown-linux-gnu".to_string()),
+            current_version: "0.1.0".to_string(),
+            restart_on_success: false,
+            http: HttpOptions::default(),
+            require_signature: false,
+            skip_signature: false,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_a_404_counts_as_an_unsigned_release() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let url = serve("404 Not Found", "").await;
+        let engine = test_engine(url.clone(), dir.path());
+        let sig_url = format!("{url}/archive.tar.gz.sig");
+        assert_eq!(engine.fetch_signature(&sig_url).await.unwrap(), None);
+
+        let url = serve("200 OK", TEST_SIGNATURE).await;
+        let engine = test_engine(url.clone(), dir.path());
+        let sig_url = format!("{url}/archive.tar.gz.sig");
+        assert_eq!(
+            engine.fetch_signature(&sig_url).await.unwrap().as_deref(),
+            Some(TEST_SIGNATURE)
+        );
+
+        let url = serve("503 Service Unavailable", "").await;
+        let engine = test_engine(url.clone(), dir.path());
+        let sig_url = format!("{url}/archive.tar.gz.sig");
+        assert!(engine.fetch_signature(&sig_url).await.is_err());
+
+        // Nothing listening: a network failure, not a missing signature.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let sig_url = format!(
+            "http://{}/archive.tar.gz.sig",
+            listener.local_addr().unwrap()
+        );
+        drop(listener);
+        assert!(engine.fetch_signature(&sig_url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn concurrent_checks_leave_consistent_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = version_server("0.2.0").await;
+        let check = || UpdateOptions {
+            channel: None,
+            check_only: true,
+            force: false,
+            enable_auto_update: false,
+            disable_auto_update: false,
+        };
+        // Separate engines, like the background loop and an API request.
+        let first = test_engine(url.clone(), dir.path());
+        let second = test_engine(url, dir.path());
+
+        let (a, b, c) = tokio::join!(
+            first.run_manual(check()),
+            second.run_manual(check()),
+            first.set_auto_update(false),
+        );
+        assert_eq!(a.unwrap().outcome, UpdateOutcome::UpdateAvailable);
+        assert_eq!(b.unwrap().outcome, UpdateOutcome::UpdateAvailable);
+        c.unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join("update_state.json")).unwrap();
+        let state: UpdateState = serde_json::from_str(&content).unwrap();
+        assert!(state.last_check_epoch.is_some());
+        assert_eq!(state.last_known_remote.as_deref(), Some("0.2.0"));
+        // Whatever the order, no check wrote back a stale copy over it.
+        assert!(!state.auto_update);
+        assert_eq!(content.matches("last_check_epoch").count(), 1);
+        assert!(!dir.path().join("update_state.json.tmp").exists());
+    }
+}
@@ -13,11 +13,16 @@
 //! - cancel: cleanup + persisted cancelled status
 //! - error propagation: stop when idle, start while recording
 //! - failed transcription: error text + duration persisted
+//! - resume: a failed meeting re-enters the pipeline; a completed one is refused
 
 use anyhow::Result;
 use async_trait::async_trait;
 use audetic::audio::audio_source::AudioSource;
-use audetic::meeting::{MeetingMachine, MeetingPhase, MeetingStartOptions, MeetingStatusHandle};
+use audetic::db::meetings::{MeetingRecord, MeetingRepository};
+use audetic::meeting::{
+    resume_meeting, MeetingMachine, MeetingPhase, MeetingStartOptions, MeetingStatusHandle,
+    ProcessingServices, ResumeOutcome,
+};
 use audetic::post_processing::PostProcessingService;
 use audetic::transcription::job_service::{TranscriptionJobResult, TranscriptionJobService};
 use audetic::ui::Indicator;
@@ -105,6 +110,7 @@ impl TranscriptionJobService for MockTranscription {
         &self,
         _file_path: &Path,
         _language: Option<&str>,
+        _diarize: bool,
     ) -> Result<TranscriptionJobResult> {
         self.call_count.fetch_add(1, Ordering::SeqCst);
         if self.should_fail {
@@ -113,6 +119,7 @@ impl TranscriptionJobService for MockTranscription {
         Ok(TranscriptionJobResult {
             text: self.text.clone(),
             segments: None,
+            utterances: None,
         })
     }
 }
@@ -136,7 +143,7 @@ fn build_test_machine(
 
     // Each test gets its own meetings dir under /tmp so concurrent test threads
     // can't clobber one another's audio files. Matches what `app::mod`
-    // computes from `dirs::data_dir()` in production.
+    // computes from `global::meetings_dir()` in production.
     let meetings_dir = tempfile::tempdir()
         .expect("create test meetings dir")
         .keep();
@@ -184,6 +191,32 @@ async fn wait_for_terminal(status: &MeetingStatusHandle, timeout: Duration) -> M
     }
 }
 
+/// Poll the meeting row until it reaches a terminal status or times out.
+/// Resumes run detached from the live status handle, so the row is the only
+/// place their outcome shows up.
+async fn wait_for_terminal_row(meeting_id: i64, timeout: Duration) -> MeetingRecord {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        let conn = audetic::db::init_db().expect("open db");
+        let meeting = MeetingRepository::get(&conn, meeting_id)
+            .expect("load meeting")
+            .expect("meeting exists");
+        if MeetingPhase::is_terminal(&meeting.status) || std::time::Instant::now() > deadline {
+            return meeting;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+fn test_services(transcription: Arc<dyn TranscriptionJobService>) -> ProcessingServices {
+    ProcessingServices {
+        transcription,
+        post_processing: Arc::new(PostProcessingService::new()),
+        diarize: false,
+        chunk_minutes: 0,
+    }
+}
+
 // ---- tests ----
 
 #[tokio::test]
@@ -399,3 +432,74 @@ async fn test_confirm_with_trim_shortens_duration() {
     // [0.25s, 1.25s) == exactly 1.0s of audio.
     assert_eq!(confirmed.duration_seconds, 1);
 }
+
+#[tokio::test]
+async fn test_resume_failed_meeting_completes() {
+    let (transcription, call_count) = MockTranscription::ok("resumed transcript");
+
+    // An mp3 skips the ffmpeg compress step, so this runs without ffmpeg.
+    let dir = tempfile::tempdir().expect("tempdir");
+    let audio = dir.path().join("meeting.mp3");
+    std::fs::write(&audio, b"not really an mp3").unwrap();
+
+    let id = {
+        let conn = audetic::db::init_db().expect("open db");
+        let id =
+            MeetingRepository::insert(&conn, Some("Resume me"), &audio.to_string_lossy()).unwrap();
+        MeetingRepository::fail(&conn, id, "backend timed out", 30).unwrap();
+        id
+    };
+
+    let outcome = resume_meeting(id, test_services(Arc::new(transcription)))
+        .await
+        .expect("resume");
+    assert_eq!(
+        outcome,
+        ResumeOutcome::Started {
+            audio_path: audio.clone()
+        }
+    );
+
+    let meeting = wait_for_terminal_row(id, Duration::from_secs(5)).await;
+    assert_eq!(meeting.status, "completed");
+    assert_eq!(
+        meeting.transcript_text.as_deref(),
+        Some("resumed transcript")
+    );
+    assert_eq!(meeting.duration_seconds, Some(30));
+    assert!(meeting.error.is_none(), "resume should clear the old error");
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_resume_completed_meeting_is_refused() {
+    let (transcription, call_count) = MockTranscription::ok("should not be called");
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let audio = dir.path().join("meeting.mp3");
+    std::fs::write(&audio, b"not really an mp3").unwrap();
+
+    let id = {
+        let conn = audetic::db::init_db().expect("open db");
+        let id = MeetingRepository::insert(&conn, Some("Done"), &audio.to_string_lossy()).unwrap();
+        MeetingRepository::complete(&conn, id, "/tmp/done.txt", "finished", None, None, 30)
+            .unwrap();
+        id
+    };
+
+    let outcome = resume_meeting(id, test_services(Arc::new(transcription)))
+        .await
+        .expect("resume");
+    assert_eq!(
+        outcome,
+        ResumeOutcome::NotResumable {
+            status: "completed".to_string()
+        }
+    );
+
+    let conn = audetic::db::init_db().expect("open db");
+    let meeting = MeetingRepository::get(&conn, id).unwrap().unwrap();
+    assert_eq!(meeting.status, "completed");
+    assert_eq!(meeting.transcript_text.as_deref(), Some("finished"));
+    assert_eq!(call_count.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,155 @@
+//! End-to-end test of the dictation service through its HTTP API.
+//!
+//! Starts the whole service on an ephemeral port with fakes for the
+//! recorder, transcription provider, and text output, then drives it the way
+//! a keybinding does. Config and data dirs point at a temp dir, so the
+//! workflow row lands in a throwaway database.
+
+mod support;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use audetic::app::{Service, ServiceDeps};
+use audetic::config::Config;
+use audetic::db::WorkflowData;
+use audetic::text_io::TextOutput;
+use audetic::transcription::jobs_client::Segment;
+use audetic::transcription::{Transcriber, TranscriptionService};
+use audetic::ui::Indicator;
+use serde_json::Value;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use support::{FakeProvider, FakeRecorder};
+use tokio::sync::Mutex;
+
+const TRANSCRIPT: &str = "hello from the fake provider";
+
+fn segment(start: f64, end: f64, text: &str) -> Segment {
+    Segment {
+        start,
+        end,
+        text: text.to_string(),
+        words: None,
+    }
+}
+
+// ---- fakes ----
+
+/// Records clipboard writes rather than touching the real clipboard.
+#[derive(Default)]
+struct FakeTextOutput {
+    copied: StdMutex<Vec<String>>,
+}
+
+#[async_trait]
+impl TextOutput for FakeTextOutput {
+    async fn copy_to_clipboard(&self, text: &str) -> Result<()> {
+        self.copied.lock().unwrap().push(text.to_string());
+        Ok(())
+    }
+
+    async fn inject_text(&self, _text: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn paste_from_clipboard(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// ---- helpers ----
+
+async fn api(client: &reqwest::Client, method: reqwest::Method, url: String) -> Value {
+    client
+        .request(method, url)
+        .send()
+        .await
+        .expect("request")
+        .json()
+        .await
+        .expect("json body")
+}
+
+/// Poll `/status` until the phase leaves `processing` or the timeout passes.
+async fn wait_for_settled(client: &reqwest::Client, base: &str) -> Value {
+    let deadline = std::time::Instant::now() + Duration::from_secs(10);
+    loop {
+        let status = api(client, reqwest::Method::GET, format!("{base}/status")).await;
+        if status["phase"] != "processing" || std::time::Instant::now() > deadline {
+            return status;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+// ---- test ----
+
+#[tokio::test]
+async fn toggle_twice_transcribes_and_returns_to_idle() {
+    let dirs = tempfile::tempdir().expect("tempdir");
+    std::env::set_var("AUDETIC_CONFIG_DIR", dirs.path().join("config"));
+    std::env::set_var("AUDETIC_DATA_DIR", dirs.path().join("data"));
+
+    let mut config = Config::default();
+    config.behavior.auto_paste = false;
+    config.behavior.audio_feedback = false;
+
+    let text_io = Arc::new(FakeTextOutput::default());
+    // Timestamped like a real provider, so the test can check that timing
+    // reaches history but never the clipboard.
+    let provider = FakeProvider::new(TRANSCRIPT).with_segments(vec![
+        segment(0.0, 1.2, "hello from the"),
+        segment(1.2, 2.5, "fake provider"),
+    ]);
+    let transcriber = Transcriber::from_provider(Box::new(provider), "en");
+    let deps = ServiceDeps {
+        config,
+        recorder: Arc::new(Mutex::new(FakeRecorder::default())),
+        transcription: Some(Arc::new(TranscriptionService::new(transcriber).unwrap())),
+        text_io: text_io.clone(),
+        indicator: Indicator::new().with_audio_feedback(false),
+        port: 0,
+        auto_update: false,
+    };
+
+    let service = Service::start(deps).await.expect("start service");
+    let base = format!("http://{}/api", service.local_addr());
+    let client = reqwest::Client::new();
+
+    let scenario = async {
+        let started = api(&client, reqwest::Method::POST, format!("{base}/toggle")).await;
+        assert_eq!(started["phase"], "recording");
+        let job_id = started["job_id"].as_str().expect("job id").to_string();
+
+        let stopped = api(&client, reqwest::Method::POST, format!("{base}/toggle")).await;
+        assert_eq!(stopped["success"], true);
+
+        let status = wait_for_settled(&client, &base).await;
+        assert_eq!(status["phase"], "idle", "status: {status}");
+        assert_eq!(status["last_completed_job"]["job_id"], job_id.as_str());
+        assert_eq!(status["last_completed_job"]["text"], TRANSCRIPT);
+        status["last_completed_job"]["history_id"]
+            .as_i64()
+            .expect("history id")
+    };
+
+    // The service loop isn't `Send` (it owns the recorder), so it runs on
+    // this task alongside the scenario rather than being spawned.
+    let history_id = tokio::select! {
+        result = service.run() => panic!("service exited early: {result:?}"),
+        history_id = scenario => history_id,
+    };
+
+    let conn = audetic::db::init_db().expect("open db");
+    let rows = audetic::db::search_workflows(&conn, None, None, None, None, 10).expect("query");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id, Some(history_id));
+    // Plain text is delivered; the timing is kept on the history entry.
+    assert_eq!(text_io.copied.lock().unwrap().as_slice(), [TRANSCRIPT]);
+    let WorkflowData::VoiceToText(data) = &rows[0].data;
+    assert_eq!(data.text, TRANSCRIPT);
+    let segments = data.segments.as_ref().expect("segments stored");
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[1].start, 1.2);
+    assert_eq!(segments[1].text, "fake provider");
+}
@@ -0,0 +1,87 @@
+//! Stopping the service mid-dictation (`systemctl stop`) must not lose the
+//! recording: it is stopped, transcribed, and saved to history before the
+//! service returns, and its temp WAV is cleaned up like any finished job.
+
+mod support;
+
+use audetic::app::{Service, ServiceDeps};
+use audetic::config::Config;
+use audetic::db::WorkflowData;
+use audetic::transcription::{Transcriber, TranscriptionService};
+use audetic::ui::Indicator;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use support::{FakeProvider, FakeRecorder, NoTextOutput};
+use tokio::sync::{oneshot, Mutex};
+
+const TRANSCRIPT: &str = "said just before shutdown";
+
+#[tokio::test]
+async fn shutdown_mid_recording_transcribes_and_saves_it() {
+    let dirs = tempfile::tempdir().expect("tempdir");
+    std::env::set_var("AUDETIC_CONFIG_DIR", dirs.path().join("config"));
+    std::env::set_var("AUDETIC_DATA_DIR", dirs.path().join("data"));
+
+    let mut config = Config::default();
+    config.behavior.auto_paste = false;
+    config.behavior.audio_feedback = false;
+
+    let recorder = FakeRecorder::default();
+    let written = Arc::clone(&recorder.written);
+    // Still transcribing when the shutdown starts waiting.
+    let provider = FakeProvider::new(TRANSCRIPT).with_delay(Duration::from_millis(200));
+    let transcriber = Transcriber::from_provider(Box::new(provider), "en");
+    let deps = ServiceDeps {
+        config,
+        recorder: Arc::new(Mutex::new(recorder)),
+        transcription: Some(Arc::new(TranscriptionService::new(transcriber).unwrap())),
+        text_io: Arc::new(NoTextOutput),
+        indicator: Indicator::new().with_audio_feedback(false),
+        port: 0,
+        auto_update: false,
+    };
+
+    let service = Service::start(deps).await.expect("start service");
+    let base = format!("http://{}/api", service.local_addr());
+    let (stop, stopped) = oneshot::channel::<()>();
+
+    let scenario = async {
+        let started: Value = reqwest::Client::new()
+            .post(format!("{base}/toggle"))
+            .send()
+            .await
+            .expect("toggle")
+            .json()
+            .await
+            .expect("json body");
+        assert_eq!(started["phase"], "recording");
+        stop.send(()).unwrap();
+    };
+
+    // The service loop isn't `Send` (it owns the recorder), so it runs on
+    // this task alongside the scenario rather than being spawned.
+    let run = service.run_until(async {
+        let _ = stopped.await;
+    });
+    let (result, ()) = tokio::time::timeout(Duration::from_secs(10), async {
+        tokio::join!(run, scenario)
+    })
+    .await
+    .expect("service stopped in time");
+    result.expect("clean shutdown");
+
+    let conn = audetic::db::init_db().expect("open db");
+    let rows = audetic::db::search_workflows(&conn, None, None, None, None, 10).expect("query");
+    assert_eq!(rows.len(), 1);
+    let WorkflowData::VoiceToText(data) = &rows[0].data;
+    assert_eq!(data.text, TRANSCRIPT);
+
+    let written = written.lock().unwrap();
+    assert_eq!(written.len(), 1);
+    assert!(
+        !written[0].exists(),
+        "temp WAV left behind: {:?}",
+        written[0]
+    );
+}
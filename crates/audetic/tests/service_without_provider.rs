@@ -0,0 +1,69 @@
+//! A fresh install has no transcription provider yet. The service must
+//! still come up so the provider can be configured through it, and refuse
+//! dictation with a clear error rather than failing to boot.
+
+mod support;
+
+use audetic::app::{Service, ServiceDeps};
+use audetic::config::Config;
+use audetic::ui::Indicator;
+use serde_json::Value;
+use std::sync::Arc;
+use support::{FakeRecorder, NoTextOutput};
+use tokio::sync::Mutex;
+
+#[tokio::test]
+async fn starts_without_a_provider_and_refuses_to_toggle() {
+    let dirs = tempfile::tempdir().expect("tempdir");
+    std::env::set_var("AUDETIC_CONFIG_DIR", dirs.path().join("config"));
+    std::env::set_var("AUDETIC_DATA_DIR", dirs.path().join("data"));
+
+    let mut config = Config::default();
+    config.whisper.provider = None;
+    config.behavior.audio_feedback = false;
+
+    let deps = ServiceDeps {
+        config,
+        recorder: Arc::new(Mutex::new(FakeRecorder::default())),
+        transcription: None,
+        text_io: Arc::new(NoTextOutput),
+        indicator: Indicator::new().with_audio_feedback(false),
+        port: 0,
+        auto_update: false,
+    };
+
+    let service = Service::start(deps).await.expect("service starts");
+    let base = format!("http://{}/api", service.local_addr());
+    let client = reqwest::Client::new();
+
+    let scenario = async {
+        let response = client
+            .post(format!("{base}/toggle"))
+            .send()
+            .await
+            .expect("toggle");
+        assert_eq!(response.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+        let body: Value = response.json().await.expect("json body");
+        assert!(
+            body["message"]
+                .as_str()
+                .is_some_and(|m| m.contains("No transcription provider configured")),
+            "body: {body}"
+        );
+
+        let status: Value = client
+            .get(format!("{base}/status"))
+            .send()
+            .await
+            .expect("status")
+            .json()
+            .await
+            .expect("json body");
+        assert_eq!(status["phase"], "idle");
+    };
+
+    tokio::select! {
+        result = service.run() => panic!("service exited early: {result:?}"),
+        () = scenario => {}
+    }
+}
@@ -0,0 +1,145 @@
+//! Fakes shared by the service tests: a recorder that never touches an
+//! audio device, a provider that answers with a fixed transcript, and text
+//! output that goes nowhere.
+
+// Each test binary compiles its own copy and uses only some of it.
+#![allow(dead_code)]
+
+use anyhow::Result;
+use async_trait::async_trait;
+use audetic::audio::Recorder;
+use audetic::normalizer::TranscriptionNormalizer;
+use audetic::text_io::TextOutput;
+use audetic::transcription::jobs_client::Segment;
+use audetic::transcription::{TranscriptionError, TranscriptionOutput, TranscriptionProvider};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+/// Writes a placeholder WAV on stop instead of touching an audio device,
+/// and remembers where.
+#[derive(Default)]
+pub struct FakeRecorder {
+    pub written: Arc<StdMutex<Vec<PathBuf>>>,
+}
+
+#[async_trait(?Send)]
+impl Recorder for FakeRecorder {
+    async fn start_recording(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn pause_recording(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn resume_recording(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop_recording(&self, output_path: PathBuf) -> Result<PathBuf> {
+        std::fs::write(&output_path, b"RIFF-fake-wav")?;
+        self.written.lock().unwrap().push(output_path.clone());
+        Ok(output_path)
+    }
+}
+
+/// Answers every recording with `transcript`, after `delay`.
+pub struct FakeProvider {
+    transcript: &'static str,
+    segments: Vec<Segment>,
+    delay: Duration,
+}
+
+impl FakeProvider {
+    pub fn new(transcript: &'static str) -> Self {
+        Self {
+            transcript,
+            segments: Vec::new(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Timestamp the transcript like a real provider would.
+    pub fn with_segments(mut self, segments: Vec<Segment>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Take this long to transcribe.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+impl TranscriptionProvider for FakeProvider {
+    fn name(&self) -> &'static str {
+        "Fake"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn normalizer(&self) -> Result<Box<dyn TranscriptionNormalizer>> {
+        Ok(Box::new(PassThrough))
+    }
+
+    fn transcribe<'a>(
+        &'a self,
+        _audio_path: &'a Path,
+        _language: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<String, TranscriptionError>> + Send + 'a>> {
+        Box::pin(async move {
+            tokio::time::sleep(self.delay).await;
+            Ok(self.transcript.to_string())
+        })
+    }
+
+    fn transcribe_detailed<'a>(
+        &'a self,
+        audio_path: &'a Path,
+        language: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<TranscriptionOutput, TranscriptionError>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            Ok(TranscriptionOutput {
+                text: self.transcribe(audio_path, language).await?,
+                segments: self.segments.clone(),
+            })
+        })
+    }
+}
+
+pub struct PassThrough;
+
+impl TranscriptionNormalizer for PassThrough {
+    fn normalize(&self, raw_output: &str) -> String {
+        raw_output.to_string()
+    }
+
+    fn name(&self) -> &'static str {
+        "PassThrough"
+    }
+}
+
+/// Drops every clipboard write and paste.
+pub struct NoTextOutput;
+
+#[async_trait]
+impl TextOutput for NoTextOutput {
+    async fn copy_to_clipboard(&self, _text: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn inject_text(&self, _text: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn paste_from_clipboard(&self) -> Result<()> {
+        Ok(())
+    }
+}
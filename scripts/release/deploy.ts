@@ -51,6 +51,12 @@ const config = {
 		env.MACOS_SIGN_IDENTITY ?? "Developer ID Application",
 	macosNotaryProfile: env.MACOS_NOTARY_PROFILE ?? "audetic-notary",
 	macosSkipNotarize: flag("MACOS_SKIP_NOTARIZE", false),
+	// Minisign secret key for release archives. Its public half is
+	// RELEASE_PUBLIC_KEY in crates/audetic/src/update/mod.rs; the updater
+	// refuses archives whose signature doesn't match it.
+	minisignKey:
+		env.MINISIGN_SECRET_KEY ||
+		path.join(os.homedir(), ".minisign", "audetic-release.key"),
 };
 
 if (!config.targets.length) {
@@ -87,7 +93,15 @@ console.log(`Dry run: ${config.dryRun ? "yes" : "no"}`);
 // Fail fast with a clear message if a chosen builder's tool is missing, instead
 // of a cryptic mid-build error on a fresh machine.
 const needCross = config.targets.some((t) => builderFor(t)[0] === "cross");
-await ensureCommands(["cargo", "tar", ...(needCross ? ["cross"] : [])]);
+await ensureCommands([
+	"cargo",
+	"tar",
+	...(needCross ? ["cross"] : []),
+	...(config.dryRun ? [] : ["minisign"]),
+]);
+if (!config.dryRun) {
+	await assertPath(config.minisignKey, "minisign secret key");
+}
 if (!config.allowDirty && !config.dryRun) {
 	await assertCleanGit();
 }
@@ -670,6 +684,7 @@ Installation instructions: https://install.audetic.ai/
 		`${archivePath}.sha256`,
 		`${sha}  ${path.basename(archivePath)}\n`,
 	);
+	await signArchive(archivePath, version, targetId);
 	const size = (await stat(archivePath)).size;
 	return { targetId, archivePath, sha, size };
 }
@@ -725,10 +740,23 @@ Installation: https://install.audetic.ai/cli/latest.sh
 		`${archivePath}.sha256`,
 		`${sha}  ${path.basename(archivePath)}\n`,
 	);
+	await signArchive(archivePath, version, targetId);
 	const size = (await stat(archivePath)).size;
 	return { targetId, archivePath, sha, size };
 }
 
+/// Write `<archive>.sig` next to the archive. The updater fetches it and
+/// verifies it against RELEASE_PUBLIC_KEY before installing. minisign
+/// prompts for the key's password.
+async function signArchive(
+	archivePath: string,
+	version: string,
+	targetId: string,
+) {
+	console.log(`==> [${targetId}] minisign ${path.basename(archivePath)}`);
+	await $`minisign -S -s ${config.minisignKey} -m ${archivePath} -x ${archivePath}.sig -t ${`audetic ${version} ${targetId}`}`;
+}
+
 async function assertPath(filePath: string, label: string) {
 	try {
 		await access(filePath);